@@ -0,0 +1,264 @@
+//! Bulk operation request/response types.
+//!
+//! Bulk create/update/delete endpoints share the same shape: a list of items in,
+//! a per-item status out. These types are recognized by the `OpenAPI` generator,
+//! so handlers using them are documented without extra annotations.
+//!
+//! # Key types
+//!
+//! - [`Bulk<T>`] — Request wrapper: `{ "items": [T, ...] }`
+//! - [`BulkResult`] — Response: per-item status plus success/failure counts
+//! - [`BulkItemResult`] — Outcome of a single item
+//!
+//! # Example
+//!
+//! ```ignore
+//! use vespera::bulk::{Bulk, BulkResult};
+//!
+//! #[vespera::route(post, path = "/bulk")]
+//! pub async fn bulk_create(Json(req): Json<Bulk<CreateUser>>) -> Json<BulkResult> {
+//!     let mut results = Vec::with_capacity(req.items.len());
+//!     for item in req.items {
+//!         results.push(create_user(item).await.map(|user| user.id));
+//!     }
+//!     Json(BulkResult::from_results(results))
+//! }
+//! ```
+
+use axum::http::StatusCode;
+use serde::{Deserialize, Serialize};
+
+use crate::Schema;
+
+/// Bulk request wrapper: `{ "items": [T, ...] }`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Bulk<T> {
+    /// Items to process, in order.
+    pub items: Vec<T>,
+}
+
+impl<T> Bulk<T> {
+    /// Create a bulk request from a list of items.
+    #[must_use]
+    pub fn new(items: Vec<T>) -> Self {
+        Self { items }
+    }
+
+    /// Number of items in the request.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Whether the request contains no items.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+impl<T> From<Vec<T>> for Bulk<T> {
+    fn from(items: Vec<T>) -> Self {
+        Self { items }
+    }
+}
+
+impl<T> IntoIterator for Bulk<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.into_iter()
+    }
+}
+
+vespera_core::__bulk_result_types!();
+
+impl BulkItemResult {
+    /// Successful item with the given status and optional record id.
+    #[must_use]
+    pub fn ok(index: usize, status: StatusCode, id: Option<serde_json::Value>) -> Self {
+        Self {
+            index,
+            status: status.as_u16(),
+            id,
+            error: None,
+        }
+    }
+
+    /// Failed item with the given status and error message.
+    #[must_use]
+    pub fn err(index: usize, status: StatusCode, error: impl Into<String>) -> Self {
+        Self {
+            index,
+            status: status.as_u16(),
+            id: None,
+            error: Some(error.into()),
+        }
+    }
+
+    /// Whether this item succeeded (2xx status).
+    #[must_use]
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+}
+
+impl BulkResult {
+    /// Build a result from per-item outcomes, computing the counts.
+    #[must_use]
+    pub fn new(items: Vec<BulkItemResult>) -> Self {
+        let succeeded = items.iter().filter(|item| item.is_success()).count();
+        let failed = items.len() - succeeded;
+        Self {
+            items,
+            succeeded,
+            failed,
+        }
+    }
+
+    /// Build a result from `Result`s in request order.
+    ///
+    /// `Ok(id)` becomes `200` with the id serialized; `Err(e)` becomes `400` with `e.to_string()`.
+    /// Use [`BulkResult::from_results_with`] when error messages must not reach clients.
+    pub fn from_results<I, T, E>(results: I) -> Self
+    where
+        I: IntoIterator<Item = Result<T, E>>,
+        T: Serialize,
+        E: std::fmt::Display,
+    {
+        let items = results
+            .into_iter()
+            .enumerate()
+            .map(|(index, result)| match result {
+                Ok(id) => BulkItemResult::ok(index, StatusCode::OK, serde_json::to_value(id).ok()),
+                Err(e) => BulkItemResult::err(index, StatusCode::BAD_REQUEST, e.to_string()),
+            })
+            .collect();
+        Self::new(items)
+    }
+
+    /// Build a result from `Result`s in request order, hiding error details from clients.
+    ///
+    /// `classify` maps each error to the item's status and a fixed message; the full error
+    /// is logged with `tracing` instead of being sent in the response.
+    pub fn from_results_with<I, T, E, F>(results: I, mut classify: F) -> Self
+    where
+        I: IntoIterator<Item = Result<T, E>>,
+        T: Serialize,
+        E: std::fmt::Display,
+        F: FnMut(&E) -> (StatusCode, &'static str),
+    {
+        let items = results
+            .into_iter()
+            .enumerate()
+            .map(|(index, result)| match result {
+                Ok(id) => BulkItemResult::ok(index, StatusCode::OK, serde_json::to_value(id).ok()),
+                Err(e) => {
+                    let (status, message) = classify(&e);
+                    if status.is_server_error() {
+                        tracing::error!(index, status = status.as_u16(), "bulk item failed: {e}");
+                    } else {
+                        tracing::warn!(index, status = status.as_u16(), "bulk item failed: {e}");
+                    }
+                    BulkItemResult::err(index, status, message)
+                }
+            })
+            .collect();
+        Self::new(items)
+    }
+
+    /// Status code summarizing the whole batch.
+    ///
+    /// `200` when every item succeeded, `422 Unprocessable Entity` when none did,
+    /// `207 Multi-Status` otherwise.
+    #[must_use]
+    pub const fn status(&self) -> StatusCode {
+        if self.failed == 0 {
+            StatusCode::OK
+        } else if self.succeeded == 0 {
+            StatusCode::UNPROCESSABLE_ENTITY
+        } else {
+            StatusCode::MULTI_STATUS
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bulk_deserialize() {
+        let bulk: Bulk<i32> = serde_json::from_str(r#"{"items":[1,2,3]}"#).unwrap();
+        assert_eq!(bulk.len(), 3);
+        assert!(!bulk.is_empty());
+        assert_eq!(bulk.into_iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_bulk_from_vec() {
+        let bulk: Bulk<&str> = vec!["a"].into();
+        assert_eq!(bulk, Bulk::new(vec!["a"]));
+    }
+
+    #[test]
+    fn test_bulk_result_from_results() {
+        let results: Vec<Result<i32, String>> = vec![Ok(1), Err("duplicate".to_string()), Ok(3)];
+        let result = BulkResult::from_results(results);
+        assert_eq!(result.succeeded, 2);
+        assert_eq!(result.failed, 1);
+        assert_eq!(result.items[0].id, Some(serde_json::json!(1)));
+        assert_eq!(result.items[1].status, 400);
+        assert_eq!(result.items[1].error.as_deref(), Some("duplicate"));
+        assert_eq!(result.status(), StatusCode::MULTI_STATUS);
+    }
+
+    #[test]
+    fn test_bulk_result_from_results_with() {
+        let results: Vec<Result<i32, String>> = vec![
+            Ok(1),
+            Err("UNIQUE constraint failed: user.email".to_string()),
+            Err("connection reset".to_string()),
+        ];
+        let result = BulkResult::from_results_with(results, |e| {
+            if e.starts_with("UNIQUE") {
+                (StatusCode::CONFLICT, "conflicts with an existing record")
+            } else {
+                (StatusCode::INTERNAL_SERVER_ERROR, "internal error")
+            }
+        });
+        assert_eq!(result.succeeded, 1);
+        assert_eq!(result.failed, 2);
+        assert_eq!(result.items[1].status, 409);
+        assert_eq!(
+            result.items[1].error.as_deref(),
+            Some("conflicts with an existing record")
+        );
+        assert_eq!(result.items[2].status, 500);
+        assert_eq!(result.items[2].error.as_deref(), Some("internal error"));
+    }
+
+    #[test]
+    fn test_bulk_result_all_failed() {
+        let results: Vec<Result<i32, String>> = vec![Err("invalid".to_string())];
+        let result = BulkResult::from_results(results);
+        assert_eq!(result.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(BulkResult::default().status(), StatusCode::OK);
+    }
+
+    #[test]
+    fn test_bulk_result_all_ok() {
+        let result = BulkResult::new(vec![BulkItemResult::ok(0, StatusCode::CREATED, None)]);
+        assert_eq!(result.status(), StatusCode::OK);
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "items": [{ "index": 0, "status": 201 }],
+                "succeeded": 1,
+                "failed": 0
+            })
+        );
+    }
+}
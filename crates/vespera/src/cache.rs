@@ -0,0 +1,66 @@
+//! Layer applied by `#[route(cache = (..., apply))]`.
+//!
+//! ```ignore
+//! #[vespera::route(get, cache = (max_age = 60, public, apply))]
+//! pub async fn list_products() -> Json<Vec<Product>> {
+//!     // ...
+//! }
+//! ```
+//!
+//! The policy is documented as the `Cache-Control` header of the route's success
+//! responses either way; `apply` also sets it at runtime. Responses that already carry a
+//! `Cache-Control` header keep it.
+
+use axum::http::{HeaderValue, header::CACHE_CONTROL};
+use tower_http::set_header::SetResponseHeaderLayer;
+
+/// Set `Cache-Control: {value}` on responses that do not set it themselves.
+///
+/// # Panics
+///
+/// Panics if `value` is not a valid header value.
+#[must_use]
+pub fn cache_control(value: &'static str) -> SetResponseHeaderLayer<HeaderValue> {
+    SetResponseHeaderLayer::if_not_present(CACHE_CONTROL, HeaderValue::from_static(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{Router, body::Body, http::Request, response::IntoResponse, routing::get};
+    use tower::ServiceExt;
+
+    use super::*;
+
+    async fn cache_header(router: Router) -> Option<HeaderValue> {
+        router
+            .oneshot(
+                Request::get("/")
+                    .body(Body::empty())
+                    .expect("valid request"),
+            )
+            .await
+            .expect("infallible")
+            .headers()
+            .get(CACHE_CONTROL)
+            .cloned()
+    }
+
+    #[tokio::test]
+    async fn test_cache_control() {
+        let router = Router::new().route(
+            "/",
+            get(|| async { "ok" }).layer(cache_control("public, max-age=60")),
+        );
+        assert_eq!(cache_header(router).await.unwrap(), "public, max-age=60");
+    }
+
+    #[tokio::test]
+    async fn test_cache_control_keeps_handler_header() {
+        let router = Router::new().route(
+            "/",
+            get(|| async { ([(CACHE_CONTROL, "no-store")], "ok").into_response() })
+                .layer(cache_control("public, max-age=60")),
+        );
+        assert_eq!(cache_header(router).await.unwrap(), "no-store");
+    }
+}
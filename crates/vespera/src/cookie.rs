@@ -0,0 +1,189 @@
+//! Typed cookie extraction.
+//!
+//! Axum's `CookieJar` exposes cookies by name at runtime, so the `OpenAPI` generator
+//! cannot tell which cookies a handler reads. [`Cookies<T>`] decodes the `Cookie`
+//! header into a struct instead, and each field is documented as a cookie parameter:
+//!
+//! ```text
+//! Cookie: session_id=abc123; theme=dark
+//! ```
+//!
+//! # Example
+//!
+//! ```ignore
+//! use vespera::Cookies;
+//!
+//! #[derive(Deserialize, Schema)]
+//! pub struct SessionCookies {
+//!     pub session_id: String,
+//!     pub theme: Option<String>,
+//! }
+//!
+//! #[vespera::route(get, path = "/me")]
+//! pub async fn me(Cookies(cookies): Cookies<SessionCookies>) -> Json<User> {
+//!     // ...
+//! }
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use axum::extract::FromRequestParts;
+use axum::http::request::Parts;
+use axum::http::{HeaderMap, StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use serde::de::DeserializeOwned;
+
+/// Rejection returned when the request cookies cannot be decoded into `T`.
+#[derive(Debug)]
+pub struct CookiesRejection {
+    source: serde_qs::Error,
+}
+
+impl fmt::Display for CookiesRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Failed to deserialize cookies: {}", self.source)
+    }
+}
+
+impl std::error::Error for CookiesRejection {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+impl IntoResponse for CookiesRejection {
+    fn into_response(self) -> Response {
+        (StatusCode::BAD_REQUEST, self.to_string()).into_response()
+    }
+}
+
+/// Axum extractor that decodes the `Cookie` header(s) into `T`.
+///
+/// Values are matched to fields by cookie name and parsed like query string values,
+/// so numbers and booleans work; a missing cookie leaves an `Option` field `None`.
+/// When a cookie is sent more than once, the first value wins.
+pub struct Cookies<T>(pub T);
+
+impl<T: DeserializeOwned> Cookies<T> {
+    /// Decode cookies from request headers.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CookiesRejection`] if the cookies do not match `T`.
+    pub fn from_headers(headers: &HeaderMap) -> Result<Self, CookiesRejection> {
+        let mut pairs = BTreeMap::new();
+        for value in headers.get_all(header::COOKIE) {
+            let Ok(value) = value.to_str() else {
+                continue;
+            };
+            for (name, value) in parse_cookie_header(value) {
+                pairs.entry(name).or_insert(value);
+            }
+        }
+        // Re-encode as a query string so serde_qs handles the value parsing
+        let query = serde_qs::to_string(&pairs).map_err(|source| CookiesRejection { source })?;
+        serde_qs::Config::new(0, false)
+            .deserialize_str(&query)
+            .map(Self)
+            .map_err(|source| CookiesRejection { source })
+    }
+}
+
+/// Split a `Cookie` header value into `(name, value)` pairs, unquoting quoted values.
+fn parse_cookie_header(value: &str) -> impl Iterator<Item = (&str, &str)> {
+    value.split(';').filter_map(|pair| {
+        let (name, value) = pair.split_once('=')?;
+        let name = name.trim();
+        if name.is_empty() {
+            return None;
+        }
+        let value = value.trim();
+        let value = value
+            .strip_prefix('"')
+            .and_then(|v| v.strip_suffix('"'))
+            .unwrap_or(value);
+        Some((name, value))
+    })
+}
+
+impl<T> std::ops::Deref for Cookies<T> {
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> std::ops::DerefMut for Cookies<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T, S> FromRequestParts<S> for Cookies<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = CookiesRejection;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Self::from_headers(&parts.headers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::HeaderValue;
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Session {
+        session_id: String,
+        visits: Option<u32>,
+    }
+
+    fn headers(values: &[&str]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for value in values {
+            headers.append(header::COOKIE, HeaderValue::from_str(value).unwrap());
+        }
+        headers
+    }
+
+    #[test]
+    fn test_from_headers() {
+        let Cookies(session) =
+            Cookies::<Session>::from_headers(&headers(&["session_id=abc; visits=3; other=x"]))
+                .unwrap();
+        assert_eq!(
+            session,
+            Session {
+                session_id: "abc".to_string(),
+                visits: Some(3),
+            }
+        );
+    }
+
+    #[test]
+    fn test_from_headers_multiple_headers_and_quotes() {
+        let session = Cookies::<Session>::from_headers(&headers(&[
+            "session_id=\"a&b=c\"",
+            "session_id=ignored; visits=1",
+        ]))
+        .unwrap();
+        assert_eq!(session.session_id, "a&b=c");
+        assert_eq!(session.visits, Some(1));
+    }
+
+    #[test]
+    fn test_from_headers_rejection() {
+        let err = Cookies::<Session>::from_headers(&headers(&["visits=1"]))
+            .err()
+            .unwrap();
+        assert!(err.to_string().starts_with("Failed to deserialize cookies"));
+        assert_eq!(err.into_response().status(), StatusCode::BAD_REQUEST);
+    }
+}
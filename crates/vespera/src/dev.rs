@@ -0,0 +1,52 @@
+//! Spec reload for development (`hot-reload` feature).
+//!
+//! With the feature enabled, debug builds of a `vespera!` app scan the route folder
+//! again whenever a docs page (`docs_url`, `redoc_url`) is requested, so edited doc
+//! comments and `#[route]` attributes show up on a page refresh instead of after a
+//! rebuild:
+//!
+//! ```toml
+//! [features]
+//! dev = ["vespera/hot-reload"]
+//! ```
+//!
+//! Only the docs and redoc pages are refreshed: the router keeps the handlers it was
+//! built with, and structs registered outside the route folder keep their built schema.
+//! The JSON files written by `openapi = "..."` are not rewritten either and keep the
+//! spec of the last build. Release builds never scan and always serve the spec built
+//! into the binary.
+
+use std::path::Path;
+
+/// Spec of the `vespera!` call with `input`, regenerated from the sources under
+/// `manifest_dir`. `None`, with a warning, when they cannot be scanned.
+#[doc(hidden)]
+pub fn reload_spec(manifest_dir: &str, input: &str, schemas: &str) -> Option<String> {
+    vespera_scanner::regenerate_spec(Path::new(manifest_dir), input, schemas)
+        .inspect_err(|e| tracing::warn!("vespera: serving the built spec, reload failed: {e}"))
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reload_spec() {
+        let dir = tempfile::tempdir().unwrap();
+        let routes = dir.path().join("src").join("routes");
+        std::fs::create_dir_all(&routes).unwrap();
+        std::fs::write(
+            routes.join("health.rs"),
+            "/// Liveness probe\n#[vespera::route(get)]\npub async fn health() -> &'static str { \"ok\" }\n",
+        )
+        .unwrap();
+        let manifest_dir = dir.path().to_str().unwrap();
+
+        let spec = reload_spec(manifest_dir, "title = \"Dev\"", "[]").unwrap();
+        assert!(spec.contains("Liveness probe"));
+
+        // Missing route folder: the caller falls back to the built spec
+        assert!(reload_spec(manifest_dir, "dir = \"missing\"", "[]").is_none());
+    }
+}
@@ -0,0 +1,229 @@
+//! Custom string formats registered with `vespera!(formats = { ... })`.
+//!
+//! Fields marked `#[schema(format = "ulid")]` document the format in the `OpenAPI`
+//! spec. Formats registered with a pattern also get that `pattern` in the spec, and
+//! `#[derive(Schema)]` implements [`FormattedFields`] for such structs, so the same
+//! patterns are enforced at runtime with [`check`]:
+//!
+//! ```ignore
+//! let app = vespera!(formats = { ulid = "^[0-9A-HJKMNP-TV-Z]{26}$", iban = "^[A-Z]{2}[0-9]{2}[A-Z0-9]{11,30}$" });
+//!
+//! #[derive(Deserialize, Serialize, Schema)]
+//! pub struct CreateTransfer {
+//!     #[schema(format = "iban")]
+//!     pub iban: String,
+//! }
+//!
+//! #[vespera::route(post)]
+//! pub async fn create_transfer(Json(body): Json<CreateTransfer>) -> Result<Json<Transfer>, FormatError> {
+//!     vespera::formats::check(&body)?;
+//!     // ...
+//! }
+//! ```
+//!
+//! Formats declared without a pattern are only documented, as are patterns that are
+//! not valid regexes.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::OnceLock;
+
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use regex::Regex;
+use serde::Serialize;
+
+static REGISTERED: OnceLock<&'static [(&'static str, &'static str)]> = OnceLock::new();
+static COMPILED: OnceLock<HashMap<&'static str, Regex>> = OnceLock::new();
+
+/// Register `(format, pattern)` pairs. Called by the router `vespera!` generates;
+/// only the first registration is kept.
+#[doc(hidden)]
+pub fn register(formats: &'static [(&'static str, &'static str)]) {
+    let _ = REGISTERED.set(formats);
+}
+
+/// Pattern registered for `format`.
+#[must_use]
+pub fn pattern(format: &str) -> Option<&'static str> {
+    REGISTERED
+        .get()?
+        .iter()
+        .find(|(name, _)| *name == format)
+        .map(|(_, pattern)| *pattern)
+}
+
+/// Whether `value` matches the pattern registered for `format`.
+///
+/// Formats without a registered pattern accept every value.
+#[must_use]
+pub fn is_valid(format: &str, value: &str) -> bool {
+    let compiled = COMPILED.get_or_init(|| {
+        REGISTERED
+            .get()
+            .into_iter()
+            .flat_map(|formats| formats.iter())
+            .filter_map(|(name, pattern)| Some((*name, Regex::new(pattern).ok()?)))
+            .collect()
+    });
+    compiled
+        .get(format)
+        .is_none_or(|regex| regex.is_match(value))
+}
+
+/// Fields of a type that declare a string format.
+///
+/// Implemented by `#[derive(Schema)]` for structs with `#[schema(format = "...")]` fields.
+pub trait FormattedFields {
+    /// `(json_field_name, format)` pairs.
+    const FORMATTED_FIELDS: &'static [(&'static str, &'static str)];
+}
+
+impl<T: FormattedFields> FormattedFields for Vec<T> {
+    const FORMATTED_FIELDS: &'static [(&'static str, &'static str)] = T::FORMATTED_FIELDS;
+}
+
+impl<T: FormattedFields> FormattedFields for Option<T> {
+    const FORMATTED_FIELDS: &'static [(&'static str, &'static str)] = T::FORMATTED_FIELDS;
+}
+
+/// A field whose value does not match its format.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormatError {
+    /// JSON name of the field
+    pub field: String,
+    /// Format the value failed to match
+    pub format: String,
+}
+
+impl fmt::Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Field `{}` is not a valid `{}`", self.field, self.format)
+    }
+}
+
+impl std::error::Error for FormatError {}
+
+impl IntoResponse for FormatError {
+    fn into_response(self) -> Response {
+        (StatusCode::UNPROCESSABLE_ENTITY, self.to_string()).into_response()
+    }
+}
+
+/// Check the formatted fields of `value` against the registered patterns.
+///
+/// Only top-level fields of `T` (or of each element, for `Vec<T>` / `Option<T>`) are
+/// checked; string lists are checked element by element.
+///
+/// # Errors
+///
+/// Returns the first field whose value does not match its format.
+pub fn check<T>(value: &T) -> Result<(), FormatError>
+where
+    T: Serialize + FormattedFields,
+{
+    let Ok(json) = serde_json::to_value(value) else {
+        return Ok(());
+    };
+    check_value(&json, T::FORMATTED_FIELDS)
+}
+
+/// Check the formatted fields of a JSON object, or of each object in an array.
+///
+/// # Errors
+///
+/// Returns the first field whose value does not match its format.
+pub fn check_value(
+    value: &serde_json::Value,
+    formatted_fields: &[(&str, &str)],
+) -> Result<(), FormatError> {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (field, format) in formatted_fields {
+                let values: Vec<&str> = match map.get(*field) {
+                    Some(serde_json::Value::String(s)) => vec![s.as_str()],
+                    Some(serde_json::Value::Array(items)) => {
+                        items.iter().filter_map(serde_json::Value::as_str).collect()
+                    }
+                    _ => Vec::new(),
+                };
+                if values.iter().any(|value| !is_valid(format, value)) {
+                    return Err(FormatError {
+                        field: (*field).to_string(),
+                        format: (*format).to_string(),
+                    });
+                }
+            }
+            Ok(())
+        }
+        serde_json::Value::Array(items) => items
+            .iter()
+            .try_for_each(|item| check_value(item, formatted_fields)),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Account {
+        id: String,
+        aliases: Vec<String>,
+        handle: String,
+        name: String,
+    }
+
+    impl FormattedFields for Account {
+        const FORMATTED_FIELDS: &'static [(&'static str, &'static str)] =
+            &[("id", "ulid"), ("aliases", "ulid"), ("handle", "slug")];
+    }
+
+    fn account(id: &str, aliases: &[&str]) -> Account {
+        Account {
+            id: id.to_string(),
+            aliases: aliases.iter().map(ToString::to_string).collect(),
+            handle: "Not A Slug".to_string(),
+            name: "n".to_string(),
+        }
+    }
+
+    const ULID: &str = "01ARZ3NDEKTSV4RRFFQ69G5FAV";
+
+    #[test]
+    fn test_register_and_check() {
+        register(&[("ulid", "^[0-9A-HJKMNP-TV-Z]{26}$"), ("broken", "(")]);
+        register(&[]);
+
+        assert_eq!(pattern("ulid"), Some("^[0-9A-HJKMNP-TV-Z]{26}$"));
+        assert_eq!(pattern("slug"), None);
+        assert!(is_valid("ulid", ULID));
+        assert!(!is_valid("ulid", "not-a-ulid"));
+        // Unregistered formats and invalid patterns are not enforced
+        assert!(is_valid("slug", "Not A Slug"));
+        assert!(is_valid("broken", "anything"));
+
+        assert_eq!(check(&account(ULID, &[ULID])), Ok(()));
+        assert_eq!(check(&vec![account(ULID, &[])]), Ok(()));
+        let err = check(&account("nope", &[])).unwrap_err();
+        assert_eq!(
+            err,
+            FormatError {
+                field: "id".to_string(),
+                format: "ulid".to_string(),
+            }
+        );
+        assert_eq!(err.to_string(), "Field `id` is not a valid `ulid`");
+        assert_eq!(
+            check(&Some(account(ULID, &[ULID, "nope"])))
+                .unwrap_err()
+                .field,
+            "aliases"
+        );
+        assert_eq!(
+            err.into_response().status(),
+            StatusCode::UNPROCESSABLE_ENTITY
+        );
+    }
+}
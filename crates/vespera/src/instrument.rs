@@ -0,0 +1,133 @@
+//! Per-route request logging for `vespera!(instrument = true)`.
+//!
+//! ```ignore
+//! let app = vespera!(instrument = true);
+//! ```
+//!
+//! Every generated route is wrapped in [`layer`], which opens a `tracing` span carrying
+//! the operation id and the route's path template, and emits one `INFO` event with the
+//! response status and latency when the response is ready:
+//!
+//! ```text
+//! INFO request{operation_id="users_get_user" path="/users/{id}" method=GET}: status=200 latency_ms=3
+//! ```
+//!
+//! With the `otel` feature the span fields follow the `OpenTelemetry` HTTP server
+//! semantic conventions instead (`http.route`, `http.request.method`,
+//! `http.response.status_code`, `otel.name`, `otel.kind`), plus `operation.id` and
+//! `vespera.tags`, so `tracing-opentelemetry` exports them as span attributes as-is.
+//!
+//! Nothing is logged unless a `tracing` subscriber is installed.
+
+use std::time::Duration;
+
+use axum::http::{Request, Response};
+use tower_http::classify::{ServerErrorsAsFailures, SharedClassifier};
+use tower_http::trace::{DefaultOnRequest, MakeSpan, OnResponse, TraceLayer};
+use tracing::Span;
+
+/// Layer returned by [`layer`].
+pub type InstrumentLayer = TraceLayer<
+    SharedClassifier<ServerErrorsAsFailures>,
+    RouteSpan,
+    DefaultOnRequest,
+    RouteResponse,
+>;
+
+/// Log requests to one route under `operation_id`, the path template `path` and its `tags`.
+#[must_use]
+pub fn layer(
+    operation_id: &'static str,
+    path: &'static str,
+    tags: &'static [&'static str],
+) -> InstrumentLayer {
+    TraceLayer::new_for_http()
+        .make_span_with(RouteSpan {
+            operation_id,
+            path,
+            tags,
+        })
+        .on_response(RouteResponse)
+}
+
+/// Opens the `request` span for a route.
+#[derive(Debug, Clone, Copy)]
+pub struct RouteSpan {
+    operation_id: &'static str,
+    path: &'static str,
+    tags: &'static [&'static str],
+}
+
+impl<B> MakeSpan<B> for RouteSpan {
+    #[cfg(not(feature = "otel"))]
+    fn make_span(&mut self, request: &Request<B>) -> Span {
+        tracing::info_span!(
+            "request",
+            operation_id = self.operation_id,
+            path = self.path,
+            method = %request.method(),
+            tags = %self.tags.join(","),
+        )
+    }
+
+    #[cfg(feature = "otel")]
+    fn make_span(&mut self, request: &Request<B>) -> Span {
+        let method = request.method();
+        tracing::info_span!(
+            "request",
+            otel.name = %format_args!("{method} {}", self.path),
+            otel.kind = "server",
+            http.route = self.path,
+            http.request.method = %method,
+            http.response.status_code = tracing::field::Empty,
+            operation.id = self.operation_id,
+            vespera.tags = %self.tags.join(","),
+        )
+    }
+}
+
+/// Logs the response status inside the route's span.
+#[derive(Debug, Clone, Copy)]
+pub struct RouteResponse;
+
+impl<B> OnResponse<B> for RouteResponse {
+    fn on_response(self, response: &Response<B>, latency: Duration, span: &Span) {
+        let status = response.status().as_u16();
+        if cfg!(feature = "otel") {
+            span.record("http.response.status_code", status);
+        }
+        tracing::info!(
+            status,
+            latency_ms = u64::try_from(latency.as_millis()).unwrap_or(u64::MAX),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{Router, body::Body, http::StatusCode, routing::get};
+    use tower::ServiceExt;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_layer_passes_response_through() {
+        let router = Router::new().route(
+            "/users/{id}",
+            get(|| async { StatusCode::ACCEPTED }).layer(layer(
+                "get_user",
+                "/users/{id}",
+                &["users"],
+            )),
+        );
+        let response = router
+            .oneshot(
+                Request::get("/users/1")
+                    .body(Body::empty())
+                    .expect("valid request"),
+            )
+            .await
+            .expect("infallible");
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+    }
+}
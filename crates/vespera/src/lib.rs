@@ -3,6 +3,9 @@
 //! This crate provides macros and utilities for generating `OpenAPI` documentation
 //! from your route definitions.
 
+// Lets `#[derive(Schema)]` expand inside this crate, as in `bulk`
+extern crate self as vespera;
+
 // Re-export vespera_core types so users don't need to depend on vespera_core directly
 pub mod schema {
     pub use vespera_core::schema::*;
@@ -16,11 +19,30 @@ pub mod openapi {
     pub use vespera_core::openapi::*;
 }
 
+pub mod stubs {
+    pub use vespera_core::stubs::*;
+}
+
+pub mod gateway {
+    pub use vespera_core::gateway::*;
+}
+
+pub mod kubernetes {
+    pub use vespera_core::kubernetes::*;
+}
+
+#[cfg(feature = "unstable-metadata")]
+pub mod metadata {
+    pub use vespera_core::metadata::*;
+}
+
 // Re-export OpenApi directly for convenience (used by merge feature)
 pub use vespera_core::openapi::OpenApi;
 
 // Re-export macros from vespera_macro
-pub use vespera_macro::{Multipart, Schema, cron, export_app, route, schema, schema_type, vespera};
+pub use vespera_macro::{
+    Multipart, Schema, cron, defaults, export_app, route, schema, schema_type, vespera,
+};
 
 // Re-export serde_json for merge feature (runtime spec merging)
 pub use serde_json;
@@ -32,6 +54,51 @@ pub use chrono;
 // Native multipart form data extraction (replaces axum_typed_multipart)
 pub mod multipart;
 
+// Bulk create/update/delete request and response types
+pub mod bulk;
+
+// Runtime stripping of `#[schema(scope = "...")]` fields
+pub mod scope;
+
+// Body size and timeout layers for `#[route(limits = (...))]`
+pub mod limits;
+
+// `Cache-Control` layer for `#[route(cache = (..., apply))]`
+pub mod cache;
+
+// Per-route `tracing` layer for `vespera!(instrument = true)`
+pub mod instrument;
+
+// Server URL variables filled in from the environment at runtime
+pub mod servers;
+
+// Custom string formats registered by `vespera!(formats = { ... })`
+pub mod formats;
+
+// Authorization policy matrix registered by `vespera!`
+pub mod policy;
+pub use policy::policies;
+
+// Nested query string extraction (`filter[name]=x`) via serde_qs
+pub mod qs;
+pub use qs::QsQuery;
+
+// Typed cookie extraction, documented as cookie parameters
+pub mod cookie;
+pub use cookie::Cookies;
+
+// Enum path/query parameters checked against their documented values
+pub mod params;
+pub use params::EnumParam;
+
+// Row cache threaded through nested `from_model` calls of `schema_type!`
+pub mod loader;
+pub use loader::LoaderContext;
+
+// Docs pages regenerating their spec from the source tree in debug builds
+#[cfg(feature = "hot-reload")]
+pub mod dev;
+
 // Re-export tempfile for schema_type! multipart mode (NamedTempFile)
 pub use tempfile;
 
@@ -118,10 +185,80 @@ where
     }
 }
 
+/// Response of the route handler named by `Route`, a marker type generated by
+/// `#[vespera::route]`; implemented for every `IntoResponse` type.
+#[doc(hidden)]
+#[diagnostic::on_unimplemented(
+    message = "route handler `{Route}` returns `{Self}`, which does not implement `IntoResponse`",
+    label = "not a valid handler response",
+    note = "derive or implement `IntoResponse`, or wrap the value (e.g. `Json<T>`)"
+)]
+pub trait RouteResponse<Route: ?Sized> {}
+
+impl<T: axum::response::IntoResponse, Route: ?Sized> RouteResponse<Route> for T {}
+
+/// Async function taking `Args`, exposing what its future resolves to.
+#[doc(hidden)]
+pub trait HandlerFn<Args> {
+    type Output;
+}
+
+macro_rules! impl_handler_fn {
+    ($($arg:ident),*) => {
+        impl<F, Fut, $($arg,)*> HandlerFn<($($arg,)*)> for F
+        where
+            F: FnOnce($($arg),*) -> Fut,
+            Fut: std::future::Future,
+        {
+            type Output = Fut::Output;
+        }
+    };
+}
+
+impl_handler_fn!();
+impl_handler_fn!(T1);
+impl_handler_fn!(T1, T2);
+impl_handler_fn!(T1, T2, T3);
+impl_handler_fn!(T1, T2, T3, T4);
+impl_handler_fn!(T1, T2, T3, T4, T5);
+impl_handler_fn!(T1, T2, T3, T4, T5, T6);
+impl_handler_fn!(T1, T2, T3, T4, T5, T6, T7);
+impl_handler_fn!(T1, T2, T3, T4, T5, T6, T7, T8);
+impl_handler_fn!(T1, T2, T3, T4, T5, T6, T7, T8, T9);
+impl_handler_fn!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10);
+impl_handler_fn!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11);
+impl_handler_fn!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12);
+impl_handler_fn!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13);
+impl_handler_fn!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14);
+impl_handler_fn!(
+    T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14, T15
+);
+impl_handler_fn!(
+    T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14, T15, T16
+);
+
+/// Compile-time check emitted by `#[vespera::route]` for each handler: what `handler`
+/// resolves to must implement `IntoResponse`. Covers `impl Trait` returns too.
+#[doc(hidden)]
+pub const fn assert_handler_response<Route: ?Sized, Args, F>(_handler: &F)
+where
+    F: HandlerFn<Args>,
+    F::Output: RouteResponse<Route>,
+{
+}
+
+/// Compile-time check emitted by `#[vespera::route]` for the return type `T` of a
+/// generic handler, which cannot be named without its type arguments.
+#[doc(hidden)]
+pub const fn assert_route_response<Route: ?Sized, T: RouteResponse<Route>>() {}
+
 // Re-export tower_layer and tower_service for the layer method
 pub use tower_layer;
 pub use tower_service;
 
+// Re-export tower_http for the layers behind `#[route(limits = (...))]`
+pub use tower_http;
+
 /// In-process dispatch — drive an axum Router without a TCP socket.
 #[cfg(feature = "inprocess")]
 pub use vespera_inprocess as inprocess;
@@ -0,0 +1,89 @@
+//! Layers applied by `#[route(limits = (...))]`.
+//!
+//! ```ignore
+//! #[vespera::route(post, limits = (body = "1MB", timeout = "30s"))]
+//! pub async fn upload(body: Bytes) -> StatusCode {
+//!     // ...
+//! }
+//! ```
+//!
+//! Oversized bodies are rejected with `413 Payload Too Large` and slow requests with
+//! `408 Request Timeout`. The generated router calls these functions; they are public so
+//! the same layers can be applied by hand. A body limit above axum's 2 MB extractor
+//! default also needs [`DefaultBodyLimit::max`](axum::extract::DefaultBodyLimit::max),
+//! which the generated router adds next to [`body_limit`].
+
+use std::time::Duration;
+
+use axum::http::StatusCode;
+use tower_http::limit::RequestBodyLimitLayer;
+use tower_http::timeout::TimeoutLayer;
+
+/// Reject request bodies larger than `bytes`.
+#[must_use]
+pub fn body_limit(bytes: usize) -> RequestBodyLimitLayer {
+    RequestBodyLimitLayer::new(bytes)
+}
+
+/// Answer `408 Request Timeout` when the handler takes longer than `duration`.
+#[must_use]
+pub fn timeout(duration: Duration) -> TimeoutLayer {
+    TimeoutLayer::with_status_code(StatusCode::REQUEST_TIMEOUT, duration)
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{Router, body::Body, extract::DefaultBodyLimit, http::Request, routing::post};
+    use tower::ServiceExt;
+
+    use super::*;
+
+    async fn status(router: Router, body: impl Into<Body>) -> StatusCode {
+        router
+            .oneshot(Request::post("/").body(body.into()).expect("valid request"))
+            .await
+            .expect("infallible")
+            .status()
+    }
+
+    #[tokio::test]
+    async fn test_body_limit() {
+        let router = Router::new().route(
+            "/",
+            post(|body: String| async move { body }).layer(body_limit(4)),
+        );
+        assert_eq!(status(router.clone(), "abcd").await, StatusCode::OK);
+        assert_eq!(status(router, "abcde").await, StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    #[tokio::test]
+    async fn test_body_limit_above_extractor_default() {
+        const LIMIT: usize = 10 * 1024 * 1024;
+        let router = Router::new().route(
+            "/",
+            post(|body: String| async move { body.len().to_string() })
+                // What `limits = (body = "10MB", timeout = "30s")` generates
+                .layer((
+                    body_limit(LIMIT),
+                    DefaultBodyLimit::max(LIMIT),
+                    timeout(Duration::from_secs(30)),
+                )),
+        );
+        let body = "a".repeat(3 * 1024 * 1024);
+        assert_eq!(status(router.clone(), body).await, StatusCode::OK);
+        let body = "a".repeat(LIMIT + 1);
+        assert_eq!(status(router, body).await, StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    #[tokio::test]
+    async fn test_timeout() {
+        let router = Router::new().route(
+            "/",
+            post(|| async {
+                tokio::time::sleep(Duration::from_secs(5)).await;
+            })
+            .layer(timeout(Duration::from_millis(10))),
+        );
+        assert_eq!(status(router, "").await, StatusCode::REQUEST_TIMEOUT);
+    }
+}
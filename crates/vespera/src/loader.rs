@@ -0,0 +1,93 @@
+//! Row cache shared by the nested `from_model` calls of one conversion.
+//!
+//! `schema_type!` generates `from_model_with_context` next to `from_model`. It loads
+//! `BelongsTo`/`HasOne` relations by foreign key through a [`LoaderContext`], so rows
+//! referenced many times (many memos of the same user) are fetched once:
+//!
+//! ```ignore
+//! let mut ctx = vespera::LoaderContext::new();
+//! let mut memos = Vec::new();
+//! for model in memo::Entity::find().all(db).await? {
+//!     memos.push(MemoSchema::from_model_with_context(model, db, &mut ctx).await?);
+//! }
+//! ```
+//!
+//! `from_model` uses a fresh context per call. Entries are keyed by entity type and
+//! primary key; rows that were not found are cached too.
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::fmt::Debug;
+
+/// Rows loaded by primary key, keyed by entity type and key value.
+#[derive(Default)]
+pub struct LoaderContext {
+    rows: HashMap<(TypeId, String), Box<dyn Any + Send>>,
+}
+
+impl LoaderContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The cached lookup of `E` by `key`: `Some(None)` when the row is known to be missing,
+    /// `None` when it was never loaded.
+    pub fn get<E: 'static, M: Clone + 'static>(&self, key: &impl Debug) -> Option<Option<M>> {
+        self.rows
+            .get(&Self::key::<E>(key))
+            .and_then(|row| row.downcast_ref::<Option<M>>())
+            .cloned()
+    }
+
+    /// Cache the lookup of `E` by `key`.
+    pub fn insert<E: 'static, M: Send + 'static>(&mut self, key: &impl Debug, row: Option<M>) {
+        self.rows.insert(Self::key::<E>(key), Box::new(row));
+    }
+
+    /// Number of cached lookups.
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    fn key<E: 'static>(key: &impl Debug) -> (TypeId, String) {
+        (TypeId::of::<E>(), format!("{key:?}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct UserEntity;
+    struct TeamEntity;
+
+    #[test]
+    fn test_get_after_insert() {
+        let mut ctx = LoaderContext::new();
+        assert!(ctx.get::<UserEntity, String>(&1).is_none());
+
+        ctx.insert::<UserEntity, _>(&1, Some("alice".to_string()));
+        ctx.insert::<UserEntity, String>(&2, None);
+
+        assert_eq!(
+            ctx.get::<UserEntity, String>(&1),
+            Some(Some("alice".to_string()))
+        );
+        assert_eq!(ctx.get::<UserEntity, String>(&2), Some(None));
+        assert_eq!(ctx.len(), 2);
+    }
+
+    #[test]
+    fn test_keys_are_per_entity() {
+        let mut ctx = LoaderContext::new();
+        ctx.insert::<UserEntity, _>(&1, Some("alice".to_string()));
+
+        assert!(ctx.get::<TeamEntity, String>(&1).is_none());
+        // A different row type under the same key is a miss, not a panic
+        assert!(ctx.get::<UserEntity, i32>(&1).is_none());
+    }
+}
@@ -0,0 +1,270 @@
+//! Enum path and query parameters checked against their documented values.
+//!
+//! A parameter typed as a `#[derive(Schema)]` string enum is documented with the
+//! `enum` of its values. `#[derive(Schema)]` also implements [`ParamEnum`] for such
+//! enums, and [`EnumParam<T>`] parses the parameter from exactly those values, so a
+//! request the docs call invalid is rejected with a `400 Bad Request` that lists them:
+//!
+//! ```text
+//! Invalid value `shipped`, expected one of: `pending`, `in_transit`, `done`
+//! ```
+//!
+//! # Example
+//!
+//! ```ignore
+//! use vespera::EnumParam;
+//!
+//! #[derive(Schema)]
+//! #[serde(rename_all = "snake_case")]
+//! pub enum OrderStatus {
+//!     Pending,
+//!     InTransit,
+//!     Done,
+//! }
+//!
+//! #[derive(Deserialize, Schema)]
+//! pub struct OrderFilter {
+//!     pub status: Option<EnumParam<OrderStatus>>,
+//! }
+//!
+//! #[vespera::route(get, path = "/{status}")]
+//! pub async fn by_status(Path(status): Path<EnumParam<OrderStatus>>) -> Json<Vec<Order>> {
+//!     // `*status` is the `OrderStatus`
+//! }
+//!
+//! #[vespera::route(get)]
+//! pub async fn list(Query(filter): Query<OrderFilter>) -> Json<Vec<Order>> {
+//!     // ...
+//! }
+//! ```
+//!
+//! `EnumParam<T>` is documented as `T`. Enums with a `#[serde(other)]` catch-all accept
+//! any value and get no [`ParamEnum`] impl.
+
+use std::fmt;
+use std::str::FromStr;
+
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// A string enum whose variants map to fixed parameter values.
+///
+/// Implemented by `#[derive(Schema)]` for enums of unit variants, using the same
+/// serde renames as the `enum` of the documented schema.
+pub trait ParamEnum: Sized {
+    /// Accepted values, in declaration order.
+    const VALUES: &'static [&'static str];
+
+    /// The variant written as `value`.
+    fn from_param(value: &str) -> Option<Self>;
+
+    /// The value the variant is written as.
+    fn as_param(&self) -> &'static str;
+}
+
+/// A parameter value that is not one of the accepted values.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidEnumValue {
+    /// Value received
+    pub value: String,
+    /// Values accepted instead
+    pub allowed: &'static [&'static str],
+}
+
+impl fmt::Display for InvalidEnumValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid value `{}`, expected one of: ", self.value)?;
+        for (i, allowed) in self.allowed.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "`{allowed}`")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for InvalidEnumValue {}
+
+impl IntoResponse for InvalidEnumValue {
+    fn into_response(self) -> Response {
+        (StatusCode::BAD_REQUEST, self.to_string()).into_response()
+    }
+}
+
+/// A [`ParamEnum`] parsed from, and written as, its documented values.
+///
+/// Use it inside `Path<...>` or as a field of a `Query<...>` struct; an invalid value
+/// makes the extractor reject the request with `400 Bad Request` and the
+/// [`InvalidEnumValue`] message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EnumParam<T>(pub T);
+
+impl<T: ParamEnum> FromStr for EnumParam<T> {
+    type Err = InvalidEnumValue;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        T::from_param(value)
+            .map(Self)
+            .ok_or_else(|| InvalidEnumValue {
+                value: value.to_string(),
+                allowed: T::VALUES,
+            })
+    }
+}
+
+impl<T: ParamEnum> fmt::Display for EnumParam<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0.as_param())
+    }
+}
+
+impl<'de, T: ParamEnum> Deserialize<'de> for EnumParam<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+impl<T: ParamEnum> Serialize for EnumParam<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.0.as_param())
+    }
+}
+
+impl<T> std::ops::Deref for EnumParam<T> {
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> std::ops::DerefMut for EnumParam<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{
+        Router,
+        body::Body,
+        extract::{Path, Query},
+        http::Request,
+        routing::get,
+    };
+    use tower::ServiceExt;
+
+    use super::*;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum OrderStatus {
+        Pending,
+        InTransit,
+    }
+
+    impl ParamEnum for OrderStatus {
+        const VALUES: &'static [&'static str] = &["pending", "in_transit"];
+
+        fn from_param(value: &str) -> Option<Self> {
+            match value {
+                "pending" => Some(Self::Pending),
+                "in_transit" => Some(Self::InTransit),
+                _ => None,
+            }
+        }
+
+        fn as_param(&self) -> &'static str {
+            match self {
+                Self::Pending => "pending",
+                Self::InTransit => "in_transit",
+            }
+        }
+    }
+
+    #[derive(Deserialize)]
+    struct OrderFilter {
+        status: Option<EnumParam<OrderStatus>>,
+    }
+
+    async fn get_response(router: Router, uri: &str) -> (StatusCode, String) {
+        let response = router
+            .oneshot(
+                Request::get(uri)
+                    .body(Body::empty())
+                    .expect("valid request"),
+            )
+            .await
+            .expect("infallible");
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("readable body");
+        (status, String::from_utf8_lossy(&body).into_owned())
+    }
+
+    #[test]
+    fn test_from_str_and_display() {
+        let status: EnumParam<OrderStatus> = "in_transit".parse().unwrap();
+        assert_eq!(*status, OrderStatus::InTransit);
+        assert_eq!(status.to_string(), "in_transit");
+
+        let err = "InTransit".parse::<EnumParam<OrderStatus>>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid value `InTransit`, expected one of: `pending`, `in_transit`"
+        );
+        assert_eq!(err.into_response().status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[test]
+    fn test_serde_round_trip() {
+        let status: EnumParam<OrderStatus> = serde_json::from_str("\"pending\"").unwrap();
+        assert_eq!(serde_json::to_string(&status).unwrap(), "\"pending\"");
+        assert!(serde_json::from_str::<EnumParam<OrderStatus>>("\"done\"").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_path_rejects_undocumented_value() {
+        let router = Router::new().route(
+            "/orders/{status}",
+            get(|Path(status): Path<EnumParam<OrderStatus>>| async move { status.to_string() }),
+        );
+        assert_eq!(
+            get_response(router.clone(), "/orders/pending").await,
+            (StatusCode::OK, "pending".to_string())
+        );
+        let (status, body) = get_response(router, "/orders/shipped").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(
+            body.contains("expected one of: `pending`, `in_transit`"),
+            "{body}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_query_rejects_undocumented_value() {
+        let router = Router::new().route(
+            "/orders",
+            get(|Query(filter): Query<OrderFilter>| async move {
+                filter
+                    .status
+                    .map_or_else(|| "any".to_string(), |status| status.to_string())
+            }),
+        );
+        assert_eq!(
+            get_response(router.clone(), "/orders").await,
+            (StatusCode::OK, "any".to_string())
+        );
+        assert_eq!(
+            get_response(router.clone(), "/orders?status=in_transit").await,
+            (StatusCode::OK, "in_transit".to_string())
+        );
+        let (status, body) = get_response(router, "/orders?status=shipped").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(body.contains("Invalid value `shipped`"), "{body}");
+    }
+}
@@ -0,0 +1,63 @@
+//! Authorization policy matrix of the routes collected by `vespera!`.
+//!
+//! The router built by `vespera!` registers one [`OperationPolicy`] per route, with
+//! document-level `security` already applied, so authorization middleware and audits
+//! read the same requirements the `OpenAPI` document declares:
+//!
+//! ```ignore
+//! let app = vespera!(security = ["bearer"]);
+//!
+//! let policy = vespera::policies().get("users_delete_user").unwrap();
+//! assert!(policy.scopes().contains("admin"));
+//!
+//! std::fs::write("policies.json", vespera::policies().to_json()?)?;
+//! ```
+//!
+//! Routes of apps merged with `merge = [...]` are not included. For a full document,
+//! including merged apps, use [`PolicyMatrix::from_openapi`].
+
+use std::sync::OnceLock;
+
+pub use vespera_core::policy::*;
+
+static REGISTERED: OnceLock<&'static str> = OnceLock::new();
+static POLICIES: OnceLock<PolicyMatrix> = OnceLock::new();
+static EMPTY: PolicyMatrix = PolicyMatrix {
+    operations: Vec::new(),
+};
+
+/// Register the policy matrix as JSON. Called by the router `vespera!` generates;
+/// only the first registration is kept.
+#[doc(hidden)]
+pub fn register(json: &'static str) {
+    let _ = REGISTERED.set(json);
+}
+
+/// Policy matrix of the `vespera!` router.
+///
+/// Empty until the router has been built.
+#[must_use]
+pub fn policies() -> &'static PolicyMatrix {
+    let Some(json) = REGISTERED.get() else {
+        return &EMPTY;
+    };
+    POLICIES.get_or_init(|| serde_json::from_str(json).unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_register_and_policies() {
+        register(
+            r#"{"operations":[{"operationId":"users_list","method":"GET","path":"/users","requirements":[{"oauth":["read"]}]}]}"#,
+        );
+        register(r#"{"operations":[]}"#);
+
+        let policy = policies().get("users_list").unwrap();
+        assert_eq!(policy.path, "/users");
+        assert!(policy.scopes().contains("read"));
+        assert!(!policy.is_public());
+    }
+}
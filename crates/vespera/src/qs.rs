@@ -0,0 +1,171 @@
+//! Nested query string extraction via `serde_qs`.
+//!
+//! Axum's `Query<T>` only handles flat key/value pairs. [`QsQuery<T>`] also decodes
+//! bracket notation, so query structs can contain nested structs and lists:
+//!
+//! ```text
+//! ?filter[name]=alice&filter[age]=30&ids[0]=1&ids[1]=2
+//! ```
+//!
+//! The `OpenAPI` generator expands `QsQuery<T>` like `Query<T>`, documenting struct and
+//! map fields with `style: deepObject, explode: true`. Arrays keep the default `form` style.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use vespera::QsQuery;
+//!
+//! #[derive(Deserialize, Schema)]
+//! pub struct Filter {
+//!     pub name: Option<String>,
+//!     pub age: Option<u32>,
+//! }
+//!
+//! #[derive(Deserialize, Schema)]
+//! pub struct SearchParams {
+//!     pub filter: Filter,
+//!     pub page: Option<u32>,
+//! }
+//!
+//! #[vespera::route(get, path = "/search")]
+//! pub async fn search(QsQuery(params): QsQuery<SearchParams>) -> Json<Vec<User>> {
+//!     // ...
+//! }
+//! ```
+
+use std::fmt;
+
+use axum::extract::FromRequestParts;
+use axum::http::StatusCode;
+use axum::http::request::Parts;
+use axum::response::{IntoResponse, Response};
+use serde::de::DeserializeOwned;
+
+/// Maximum nesting depth accepted in bracket notation.
+const MAX_DEPTH: usize = 5;
+
+/// Rejection returned when the query string cannot be decoded into `T`.
+#[derive(Debug)]
+pub struct QsQueryRejection {
+    source: serde_qs::Error,
+}
+
+impl fmt::Display for QsQueryRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Failed to deserialize query string: {}", self.source)
+    }
+}
+
+impl std::error::Error for QsQueryRejection {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+impl IntoResponse for QsQueryRejection {
+    fn into_response(self) -> Response {
+        (StatusCode::BAD_REQUEST, self.to_string()).into_response()
+    }
+}
+
+/// Axum extractor that decodes nested query strings with `serde_qs`.
+///
+/// Uses non-strict mode, so both `filter[name]` and percent-encoded
+/// `filter%5Bname%5D` are accepted.
+pub struct QsQuery<T>(pub T);
+
+impl<T: DeserializeOwned> QsQuery<T> {
+    /// Decode a raw query string (without the leading `?`).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`QsQueryRejection`] if the query string does not match `T`.
+    pub fn from_query(query: &str) -> Result<Self, QsQueryRejection> {
+        serde_qs::Config::new(MAX_DEPTH, false)
+            .deserialize_str(query)
+            .map(Self)
+            .map_err(|source| QsQueryRejection { source })
+    }
+}
+
+impl<T> std::ops::Deref for QsQuery<T> {
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> std::ops::DerefMut for QsQuery<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T, S> FromRequestParts<S> for QsQuery<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = QsQueryRejection;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Self::from_query(parts.uri.query().unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Filter {
+        name: String,
+        age: Option<u32>,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Search {
+        filter: Filter,
+        #[serde(default)]
+        ids: Vec<i32>,
+        page: Option<u32>,
+    }
+
+    #[test]
+    fn test_from_query_nested() {
+        let QsQuery(search) = QsQuery::<Search>::from_query(
+            "filter[name]=alice&filter[age]=30&ids[0]=1&ids[1]=2&page=3",
+        )
+        .unwrap();
+        assert_eq!(
+            search,
+            Search {
+                filter: Filter {
+                    name: "alice".to_string(),
+                    age: Some(30),
+                },
+                ids: vec![1, 2],
+                page: Some(3),
+            }
+        );
+    }
+
+    #[test]
+    fn test_from_query_percent_encoded_brackets() {
+        let query = QsQuery::<Search>::from_query("filter%5Bname%5D=bob").unwrap();
+        assert_eq!(query.filter.name, "bob");
+        assert!(query.ids.is_empty());
+    }
+
+    #[test]
+    fn test_from_query_rejection() {
+        let err = QsQuery::<Search>::from_query("page=1").err().unwrap();
+        assert!(
+            err.to_string()
+                .starts_with("Failed to deserialize query string")
+        );
+        assert_eq!(err.into_response().status(), StatusCode::BAD_REQUEST);
+    }
+}
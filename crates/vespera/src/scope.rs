@@ -0,0 +1,283 @@
+//! Runtime stripping of scoped fields.
+//!
+//! Fields marked `#[schema(scope = "admin")]` are left out of the public `OpenAPI`
+//! document. `#[derive(Schema)]` also implements [`ScopedFields`] for such structs,
+//! so the same fields can be removed from responses for callers lacking the scope.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use vespera::scope::Scoped;
+//!
+//! #[derive(Serialize, Schema)]
+//! pub struct Employee {
+//!     pub id: i32,
+//!     #[schema(scope = "admin")]
+//!     pub salary: i64,
+//! }
+//!
+//! #[vespera::route(get, path = "/{id}")]
+//! pub async fn get_employee(Path(id): Path<i32>, auth: Auth) -> Json<Scoped<Employee>> {
+//!     let employee = load(id).await;
+//!     Json(Scoped::new(employee, auth.scopes()))
+//! }
+//! ```
+//!
+//! Nested values are stripped too: fields of a struct field, or of each element of a
+//! `Vec` field, follow the [`ScopedFields`] of their own type. Fields typed by a generic
+//! parameter are left as they are.
+
+use serde::{Serialize, Serializer};
+
+/// Fields of a type that are restricted to a scope.
+///
+/// Implemented by `#[derive(Schema)]` for structs with `#[schema(scope = "...")]` fields
+/// or with fields of other types that may have some.
+pub trait ScopedFields {
+    /// `(json_field_name, scope)` pairs.
+    const SCOPED_FIELDS: &'static [(&'static str, &'static str)];
+
+    /// Strip the scoped fields of nested values, given the serialized `value` of `Self`.
+    fn strip_nested(_value: &mut serde_json::Value, _scopes: &[&str]) {}
+}
+
+impl<T: ScopedFields> ScopedFields for Vec<T> {
+    const SCOPED_FIELDS: &'static [(&'static str, &'static str)] = T::SCOPED_FIELDS;
+
+    fn strip_nested(value: &mut serde_json::Value, scopes: &[&str]) {
+        if let serde_json::Value::Array(items) = value {
+            for item in items {
+                T::strip_nested(item, scopes);
+            }
+        }
+    }
+}
+
+impl<T: ScopedFields> ScopedFields for Option<T> {
+    const SCOPED_FIELDS: &'static [(&'static str, &'static str)] = T::SCOPED_FIELDS;
+
+    fn strip_nested(value: &mut serde_json::Value, scopes: &[&str]) {
+        T::strip_nested(value, scopes);
+    }
+}
+
+impl<T: ScopedFields + ?Sized> ScopedFields for Box<T> {
+    const SCOPED_FIELDS: &'static [(&'static str, &'static str)] = T::SCOPED_FIELDS;
+
+    fn strip_nested(value: &mut serde_json::Value, scopes: &[&str]) {
+        T::strip_nested(value, scopes);
+    }
+}
+
+/// Remove every scoped field whose scope is not in `scopes`.
+///
+/// Objects are stripped directly; arrays are stripped element by element.
+pub fn strip_scoped_fields(
+    value: &mut serde_json::Value,
+    scoped_fields: &[(&str, &str)],
+    scopes: &[&str],
+) {
+    match value {
+        serde_json::Value::Object(map) => {
+            // `retain` keeps the remaining fields in order
+            map.retain(|key, _| {
+                scoped_fields
+                    .iter()
+                    .all(|(field, scope)| *field != key.as_str() || scopes.contains(scope))
+            });
+        }
+        serde_json::Value::Array(items) => {
+            for item in items {
+                strip_scoped_fields(item, scoped_fields, scopes);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Remove the scoped fields of `T` and of the values nested in it from `value`, the
+/// serialized form of a `T`.
+pub fn strip_scoped<T: ScopedFields + ?Sized>(value: &mut serde_json::Value, scopes: &[&str]) {
+    strip_scoped_fields(value, T::SCOPED_FIELDS, scopes);
+    T::strip_nested(value, scopes);
+}
+
+/// Serialize `value` to JSON, keeping only scoped fields allowed by `scopes`.
+///
+/// # Errors
+///
+/// Returns an error if `value` fails to serialize.
+pub fn to_scoped_value<T>(value: &T, scopes: &[&str]) -> serde_json::Result<serde_json::Value>
+where
+    T: Serialize + ScopedFields,
+{
+    let mut json = serde_json::to_value(value)?;
+    strip_scoped::<T>(&mut json, scopes);
+    Ok(json)
+}
+
+/// Serialization wrapper that strips scoped fields the caller may not see.
+pub struct Scoped<'a, T> {
+    value: T,
+    scopes: &'a [&'a str],
+}
+
+impl<'a, T> Scoped<'a, T> {
+    /// Wrap `value`, allowing fields whose scope is in `scopes`.
+    #[must_use]
+    pub const fn new(value: T, scopes: &'a [&'a str]) -> Self {
+        Self { value, scopes }
+    }
+
+    /// Unwrap the inner value.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T> Serialize for Scoped<'_, T>
+where
+    T: Serialize + ScopedFields,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        to_scoped_value(&self.value, self.scopes)
+            .map_err(serde::ser::Error::custom)?
+            .serialize(serializer)
+    }
+}
+
+/// Support code for `#[derive(Schema)]`; not public API.
+#[doc(hidden)]
+pub mod __private {
+    use std::marker::PhantomData;
+
+    use super::{ScopedFields, strip_scoped};
+
+    /// Names the type of a field, so the derive can strip it whether or not the type
+    /// implements [`ScopedFields`]: method resolution picks [`StripNested`] when it
+    /// does and falls back to [`StripNone`] through the extra reference otherwise.
+    pub struct Nested<T: ?Sized>(PhantomData<T>);
+
+    impl<T: ?Sized> Nested<T> {
+        pub const NEW: Self = Self(PhantomData);
+    }
+
+    pub trait StripNested {
+        fn strip(&self, value: &mut serde_json::Value, scopes: &[&str]);
+    }
+
+    impl<T: ScopedFields + ?Sized> StripNested for Nested<T> {
+        fn strip(&self, value: &mut serde_json::Value, scopes: &[&str]) {
+            strip_scoped::<T>(value, scopes);
+        }
+    }
+
+    pub trait StripNone {
+        fn strip(&self, _value: &mut serde_json::Value, _scopes: &[&str]) {}
+    }
+
+    impl<T: ?Sized> StripNone for &Nested<T> {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Employee {
+        id: i32,
+        salary: i64,
+        notes: String,
+    }
+
+    impl ScopedFields for Employee {
+        const SCOPED_FIELDS: &'static [(&'static str, &'static str)] =
+            &[("salary", "admin"), ("notes", "hr")];
+    }
+
+    fn employee() -> Employee {
+        Employee {
+            id: 1,
+            salary: 100,
+            notes: "n".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_to_scoped_value_public() {
+        let json = to_scoped_value(&employee(), &[]).unwrap();
+        assert_eq!(json, serde_json::json!({ "id": 1 }));
+    }
+
+    #[test]
+    fn test_to_scoped_value_admin() {
+        let json = to_scoped_value(&employee(), &["admin"]).unwrap();
+        assert_eq!(json, serde_json::json!({ "id": 1, "salary": 100 }));
+    }
+
+    #[test]
+    fn test_scoped_vec() {
+        let scoped = Scoped::new(vec![employee(), employee()], &["hr"]);
+        let json = serde_json::to_value(&scoped).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!([{ "id": 1, "notes": "n" }, { "id": 1, "notes": "n" }])
+        );
+        assert_eq!(scoped.into_inner().len(), 2);
+    }
+
+    #[derive(Serialize)]
+    struct Team {
+        lead: Employee,
+        members: Vec<Employee>,
+        name: String,
+    }
+
+    impl ScopedFields for Team {
+        const SCOPED_FIELDS: &'static [(&'static str, &'static str)] = &[];
+
+        // What `#[derive(Schema)]` generates for the fields of `Team`; the borrow is
+        // what lets `String` fall back to `StripNone`
+        #[allow(clippy::needless_borrow)]
+        fn strip_nested(value: &mut serde_json::Value, scopes: &[&str]) {
+            use __private::{Nested, StripNested as _, StripNone as _};
+
+            if let Some(field) = value.get_mut("lead") {
+                (&Nested::<Employee>::NEW).strip(field, scopes);
+            }
+            if let Some(field) = value.get_mut("members") {
+                (&Nested::<Vec<Employee>>::NEW).strip(field, scopes);
+            }
+            if let Some(field) = value.get_mut("name") {
+                (&Nested::<String>::NEW).strip(field, scopes);
+            }
+        }
+    }
+
+    #[test]
+    fn test_scoped_nested() {
+        let team = Team {
+            lead: employee(),
+            members: vec![employee()],
+            name: "core".to_string(),
+        };
+        let json = to_scoped_value(&team, &["admin"]).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "lead": { "id": 1, "salary": 100 },
+                "members": [{ "id": 1, "salary": 100 }],
+                "name": "core"
+            })
+        );
+    }
+
+    #[test]
+    fn test_scoped_option_none() {
+        let scoped: Scoped<'_, Option<Employee>> = Scoped::new(None, &[]);
+        assert_eq!(
+            serde_json::to_value(&scoped).unwrap(),
+            serde_json::Value::Null
+        );
+    }
+}
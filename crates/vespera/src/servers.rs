@@ -0,0 +1,107 @@
+//! Runtime server URL templating.
+//!
+//! Server URLs may contain variables (`https://{region}.api.example.com`). The docs
+//! handlers generated by `vespera!` fill them in from `VESPERA_SERVER_VAR_<NAME>`
+//! environment variables before serving the spec, so one binary presents the right
+//! server URLs in every environment:
+//!
+//! ```text
+//! VESPERA_SERVER_VAR_REGION=eu-west-1 ./my-api
+//! ```
+//!
+//! Variables without a value stay templated, keeping their `default` and `enum`.
+//! [`resolve_spec`] does the same for a spec served some other way, from any source:
+//!
+//! ```ignore
+//! let values = HashMap::from([("region".to_string(), "eu-west-1".to_string())]);
+//! let spec = vespera::servers::resolve_spec(OPENAPI_SPEC, |name| values.get(name).cloned());
+//! ```
+
+use vespera_core::openapi::OpenApi;
+
+/// Prefix of the environment variables read by [`from_env`].
+pub const ENV_PREFIX: &str = "VESPERA_SERVER_VAR_";
+
+/// Environment variable holding the value of server variable `name`
+/// (`region` → `VESPERA_SERVER_VAR_REGION`, `api-version` → `VESPERA_SERVER_VAR_API_VERSION`).
+#[must_use]
+pub fn env_var_name(name: &str) -> String {
+    let suffix: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("{ENV_PREFIX}{suffix}")
+}
+
+/// Value of server variable `name` from the environment.
+#[must_use]
+pub fn from_env(name: &str) -> Option<String> {
+    std::env::var(env_var_name(name)).ok()
+}
+
+/// Substitute the server variables of the JSON `spec` with the values returned by `lookup`.
+///
+/// A spec without server variables, or one that fails to parse, is returned unchanged.
+#[must_use]
+pub fn resolve_spec<F>(spec: &str, lookup: F) -> String
+where
+    F: FnMut(&str) -> Option<String>,
+{
+    if !spec.contains("\"variables\"") {
+        return spec.to_string();
+    }
+    let Ok(mut doc) = serde_json::from_str::<OpenApi>(spec) else {
+        return spec.to_string();
+    };
+    doc.resolve_server_variables(lookup);
+    serde_json::to_string(&doc).unwrap_or_else(|_| spec.to_string())
+}
+
+/// [`resolve_spec`] with values from `VESPERA_SERVER_VAR_<NAME>` environment variables.
+#[must_use]
+pub fn resolve_spec_from_env(spec: &str) -> String {
+    resolve_spec(spec, from_env)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEC: &str = r#"{"openapi":"3.1.0","info":{"title":"API","version":"1.0.0"},"servers":[{"url":"https://{region}.example.com","variables":{"region":{"default":"us-east-1"}}}],"paths":{}}"#;
+
+    #[test]
+    fn test_env_var_name() {
+        assert_eq!(env_var_name("region"), "VESPERA_SERVER_VAR_REGION");
+        assert_eq!(
+            env_var_name("api-version"),
+            "VESPERA_SERVER_VAR_API_VERSION"
+        );
+        assert_eq!(env_var_name("envName"), "VESPERA_SERVER_VAR_ENVNAME");
+    }
+
+    #[test]
+    fn test_resolve_spec() {
+        let resolved = resolve_spec(SPEC, |name| (name == "region").then(|| "eu-west-1".into()));
+        let doc: serde_json::Value = serde_json::from_str(&resolved).unwrap();
+        assert_eq!(
+            doc["servers"][0],
+            serde_json::json!({ "url": "https://eu-west-1.example.com" })
+        );
+    }
+
+    #[test]
+    fn test_resolve_spec_unchanged() {
+        for spec in [
+            r#"{"openapi":"3.1.0","servers":[{"url":"https://example.com"}]}"#,
+            "not json, but \"variables\"",
+        ] {
+            assert_eq!(resolve_spec(spec, |_| None), spec);
+        }
+    }
+}
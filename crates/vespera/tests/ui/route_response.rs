@@ -0,0 +1,13 @@
+pub struct Report;
+
+#[vespera::route(get)]
+pub async fn monthly_report() -> Report {
+    Report
+}
+
+#[vespera::route(get)]
+pub async fn yearly_report() -> impl Send {
+    Report
+}
+
+fn main() {}
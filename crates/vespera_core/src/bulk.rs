@@ -0,0 +1,65 @@
+//! Definitions of the `vespera::bulk` response types.
+//!
+//! `BulkItemResult` and `BulkResult` are declared once, here: `vespera::bulk` expands
+//! them with `#[derive(Schema)]`, and the `OpenAPI` generator parses [`RESULT_TYPES`]
+//! to document handlers returning them. A derive in the `vespera` crate itself never
+//! reaches the `vespera!` expansion of the crate using it, so the generator needs the
+//! source to derive the same schema.
+
+macro_rules! result_types {
+    ($($item:item)*) => {
+        /// Source of `BulkItemResult` and `BulkResult`, as expanded by `vespera::bulk`.
+        pub const RESULT_TYPES: &str = stringify!($($item)*);
+
+        /// Declare `BulkItemResult` and `BulkResult` in the calling module.
+        ///
+        /// Expects `Serialize`, `Deserialize` and `Schema` derives in scope.
+        #[doc(hidden)]
+        #[macro_export]
+        macro_rules! __bulk_result_types {
+            () => { $($item)* };
+        }
+    };
+}
+
+result_types! {
+    /// Outcome of a single item in a bulk operation.
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Schema)]
+    #[serde(rename_all = "camelCase")]
+    pub struct BulkItemResult {
+        /// Position of the item in the request.
+        pub index: usize,
+        /// HTTP status code for this item.
+        pub status: u16,
+        /// Identifier of the created/updated/deleted record, if any.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub id: Option<serde_json::Value>,
+        /// Error message when the item failed.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub error: Option<String>,
+    }
+
+    /// Bulk response: per-item status plus success/failure counts.
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, Schema)]
+    #[serde(rename_all = "camelCase")]
+    pub struct BulkResult {
+        /// Per-item outcomes, in request order.
+        pub items: Vec<BulkItemResult>,
+        /// Number of items that succeeded.
+        pub succeeded: usize,
+        /// Number of items that failed.
+        pub failed: usize,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_result_types_parse() {
+        assert!(RESULT_TYPES.contains("pub struct BulkItemResult"));
+        assert!(RESULT_TYPES.contains("pub struct BulkResult"));
+        assert!(RESULT_TYPES.contains(r#"rename_all = "camelCase""#));
+    }
+}
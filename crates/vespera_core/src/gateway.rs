@@ -0,0 +1,522 @@
+//! AWS API Gateway export.
+//!
+//! API Gateway imports `OpenAPI` 3.0 documents whose operations carry an
+//! `x-amazon-apigateway-integration` extension describing the backend. [`to_api_gateway`]
+//! adds those extensions from an [`ApiGatewayConfig`] and downgrades the document to
+//! `OpenAPI` 3.0.1, so the result can be passed to `aws_api_gateway_rest_api.body` as-is:
+//!
+//! ```json
+//! {
+//!   "integration": {
+//!     "type": "http_proxy",
+//!     "uri": "https://backend.example.com{path}",
+//!     "connectionType": "VPC_LINK",
+//!     "connectionId": "${vpc_link_id}"
+//!   },
+//!   "operations": {
+//!     "users_create_user": { "type": "aws_proxy", "uri": "${create_user_lambda_arn}" }
+//!   }
+//! }
+//! ```
+//!
+//! `{path}` in a `uri` is replaced by the operation's path template. Strings are otherwise
+//! copied verbatim, so Terraform `templatefile()` placeholders survive the export.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{openapi::OpenApi, route::HttpMethod};
+
+/// Extension key API Gateway reads integrations from
+pub const INTEGRATION_EXTENSION: &str = "x-amazon-apigateway-integration";
+
+/// Which backend each operation is integrated with.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ApiGatewayConfig {
+    /// Integration of every operation without an entry in `operations`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub integration: Option<Integration>,
+    /// Integrations keyed by `operationId`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub operations: BTreeMap<String, Integration>,
+}
+
+/// API Gateway integration type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IntegrationType {
+    /// Lambda proxy integration
+    AwsProxy,
+    /// AWS service integration
+    Aws,
+    /// HTTP proxy integration
+    HttpProxy,
+    /// HTTP integration with mapping templates
+    Http,
+    /// Response generated by API Gateway
+    Mock,
+}
+
+/// An `x-amazon-apigateway-integration` object.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Integration {
+    /// Integration type
+    #[serde(rename = "type")]
+    pub integration_type: IntegrationType,
+    /// Backend URI or Lambda invocation ARN; `{path}` becomes the operation's path
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uri: Option<String>,
+    /// Method used to call the backend (defaults to `POST` for AWS integrations and
+    /// the operation's own method for HTTP integrations)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub http_method: Option<String>,
+    /// `INTERNET` or `VPC_LINK`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub connection_type: Option<String>,
+    /// VPC link ID for `VPC_LINK` connections
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub connection_id: Option<String>,
+    /// IAM role API Gateway assumes to call the backend
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub credentials: Option<String>,
+    /// Integration timeout
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_in_millis: Option<u32>,
+    /// Backend request parameters mapped from method request parameters; path
+    /// parameters are mapped automatically for HTTP integrations
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub request_parameters: BTreeMap<String, String>,
+    /// Any other integration field (e.g. `requestTemplates`, `responses`), copied as-is
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, Value>,
+}
+
+impl Integration {
+    /// The integration for the operation serving `method` on `path`.
+    #[must_use]
+    pub fn for_operation(&self, method: HttpMethod, path: &str) -> Self {
+        let mut integration = self.clone();
+        integration.uri = self.uri.as_ref().map(|uri| uri.replace("{path}", path));
+        let http = matches!(
+            self.integration_type,
+            IntegrationType::Http | IntegrationType::HttpProxy
+        );
+        if integration.http_method.is_none() {
+            integration.http_method = match self.integration_type {
+                IntegrationType::AwsProxy | IntegrationType::Aws => Some("POST".to_string()),
+                IntegrationType::Http | IntegrationType::HttpProxy => Some(method.to_string()),
+                IntegrationType::Mock => None,
+            };
+        }
+        if http {
+            for name in path_parameter_names(path) {
+                integration
+                    .request_parameters
+                    .entry(format!("integration.request.path.{name}"))
+                    .or_insert_with(|| format!("method.request.path.{name}"));
+            }
+        }
+        integration
+    }
+}
+
+/// Why a document could not be exported.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApiGatewayError {
+    /// Operations with neither a default nor an own integration
+    Unmapped(Vec<String>),
+    /// `operations` keys that match no operation in the document
+    UnknownOperations(Vec<String>),
+}
+
+impl fmt::Display for ApiGatewayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unmapped(ids) => write!(
+                f,
+                "no integration for operation(s) {}; add them to `operations` or set a default `integration`",
+                ids.join(", ")
+            ),
+            Self::UnknownOperations(ids) => write!(
+                f,
+                "`operations` lists unknown operation(s) {}",
+                ids.join(", ")
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ApiGatewayError {}
+
+/// API Gateway import document for `doc`: integrations from `config`, `OpenAPI` 3.0.1.
+///
+/// Operations without an `operationId` are keyed as `"{METHOD} {path}"`.
+///
+/// # Errors
+///
+/// Returns an error if an operation has no integration, or `config.operations` names an
+/// operation the document does not have.
+pub fn to_api_gateway(doc: &OpenApi, config: &ApiGatewayConfig) -> Result<Value, ApiGatewayError> {
+    let mut doc = doc.clone();
+    let mut unmapped = Vec::new();
+    let mut used = Vec::new();
+    for (path, path_item) in &mut doc.paths {
+        for method in path_item.methods() {
+            let Some(operation) = path_item.operation_mut(method) else {
+                continue;
+            };
+            let id = operation
+                .operation_id
+                .clone()
+                .unwrap_or_else(|| format!("{method} {path}"));
+            let integration = if let Some(integration) = config.operations.get(&id) {
+                used.push(id);
+                integration
+            } else if let Some(integration) = &config.integration {
+                integration
+            } else {
+                unmapped.push(id);
+                continue;
+            };
+            operation.extensions.insert(
+                INTEGRATION_EXTENSION.to_string(),
+                serde_json::to_value(integration.for_operation(method, path)).unwrap_or_default(),
+            );
+        }
+    }
+    if !unmapped.is_empty() {
+        return Err(ApiGatewayError::Unmapped(unmapped));
+    }
+    let unknown: Vec<String> = config
+        .operations
+        .keys()
+        .filter(|id| !used.contains(id))
+        .cloned()
+        .collect();
+    if !unknown.is_empty() {
+        return Err(ApiGatewayError::UnknownOperations(unknown));
+    }
+
+    let mut value = serde_json::to_value(&doc).unwrap_or_default();
+    downgrade_to_3_0(&mut value);
+    Ok(value)
+}
+
+/// Rewrite an `OpenAPI` 3.1 document as 3.0.1.
+///
+/// Type arrays and `null` alternatives become `nullable`, `const` becomes a one-value
+/// `enum`, schema `examples` become `example`, `contentEncoding: base64` and
+/// `contentMediaType` become `format: byte` / `format: binary`, and keywords 3.0 does
+/// not know (`$defs`, `prefixItems`, `$dynamicRef`, ...) are dropped.
+pub fn downgrade_to_3_0(doc: &mut Value) {
+    let Some(root) = doc.as_object_mut() else {
+        return;
+    };
+    root.insert("openapi".to_string(), Value::from("3.0.1"));
+    root.remove("jsonSchemaDialect");
+    root.remove("webhooks");
+    if let Some(info) = root.get_mut("info").and_then(Value::as_object_mut) {
+        info.remove("summary");
+        if let Some(license) = info.get_mut("license").and_then(Value::as_object_mut) {
+            license.remove("identifier");
+        }
+    }
+    if let Some(schemas) = root
+        .get_mut("components")
+        .and_then(|components| components.get_mut("schemas"))
+        .and_then(Value::as_object_mut)
+    {
+        schemas.values_mut().for_each(downgrade_schema);
+    }
+    if let Some(paths) = root.get_mut("paths") {
+        downgrade_nested_schemas(paths);
+    }
+    if let Some(components) = root.get_mut("components").and_then(Value::as_object_mut) {
+        for (kind, value) in components.iter_mut() {
+            if kind != "schemas" {
+                downgrade_nested_schemas(value);
+            }
+        }
+    }
+}
+
+/// Downgrade every `schema` found below parameters, headers and media types.
+fn downgrade_nested_schemas(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map.iter_mut() {
+                if key == "schema" {
+                    downgrade_schema(child);
+                } else {
+                    downgrade_nested_schemas(child);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(downgrade_nested_schemas),
+        _ => {}
+    }
+}
+
+/// Keywords that only exist in JSON Schema 2020-12
+const JSON_SCHEMA_ONLY: &[&str] = &[
+    "$schema",
+    "$id",
+    "$anchor",
+    "$comment",
+    "$defs",
+    "$dynamicAnchor",
+    "$dynamicRef",
+    "prefixItems",
+    "unevaluatedItems",
+    "unevaluatedProperties",
+    "dependentRequired",
+    "dependentSchemas",
+    "contains",
+    "minContains",
+    "maxContains",
+    "propertyNames",
+    "if",
+    "then",
+    "else",
+];
+
+fn downgrade_schema(schema: &mut Value) {
+    let Some(map) = schema.as_object_mut() else {
+        return;
+    };
+    for keyword in JSON_SCHEMA_ONLY {
+        map.remove(*keyword);
+    }
+
+    if let Some(Value::Array(types)) = map.get("type").cloned() {
+        let (nulls, types): (Vec<_>, Vec<_>) =
+            types.into_iter().partition(|t| t.as_str() == Some("null"));
+        if !nulls.is_empty() {
+            map.insert("nullable".to_string(), Value::Bool(true));
+        }
+        match <[Value; 1]>::try_from(types) {
+            Ok([single]) => {
+                map.insert("type".to_string(), single);
+            }
+            Err(types) => {
+                map.remove("type");
+                if !types.is_empty() {
+                    let alternatives = types
+                        .into_iter()
+                        .map(|t| serde_json::json!({ "type": t }))
+                        .collect();
+                    map.insert("anyOf".to_string(), Value::Array(alternatives));
+                }
+            }
+        }
+    }
+    for combinator in ["oneOf", "anyOf"] {
+        let Some(Value::Array(alternatives)) = map.get_mut(combinator) else {
+            continue;
+        };
+        let before = alternatives.len();
+        alternatives.retain(|alternative| alternative.get("type") != Some(&Value::from("null")));
+        if alternatives.len() != before {
+            map.insert("nullable".to_string(), Value::Bool(true));
+        }
+    }
+
+    if let Some(value) = map.remove("const") {
+        map.insert("enum".to_string(), Value::Array(vec![value]));
+    }
+    if let Some(Value::Array(examples)) = map.remove("examples")
+        && let Some(first) = examples.into_iter().next()
+    {
+        map.entry("example").or_insert(first);
+    }
+    let encoding = map.remove("contentEncoding");
+    if map.remove("contentMediaType").is_some() || encoding.is_some() {
+        let format = if encoding.as_ref().and_then(Value::as_str) == Some("base64") {
+            "byte"
+        } else {
+            "binary"
+        };
+        map.entry("format").or_insert_with(|| Value::from(format));
+    }
+    for (bound, exclusive) in [
+        ("minimum", "exclusiveMinimum"),
+        ("maximum", "exclusiveMaximum"),
+    ] {
+        if let Some(limit) = map.get(exclusive).filter(|v| v.is_number()).cloned() {
+            map.insert(bound.to_string(), limit);
+            map.insert(exclusive.to_string(), Value::Bool(true));
+        }
+    }
+
+    if let Some(Value::Object(properties)) = map.get_mut("properties") {
+        properties.values_mut().for_each(downgrade_schema);
+    }
+    for key in ["items", "additionalProperties", "not"] {
+        if let Some(child) = map.get_mut(key) {
+            downgrade_schema(child);
+        }
+    }
+    for key in ["allOf", "anyOf", "oneOf"] {
+        if let Some(Value::Array(children)) = map.get_mut(key) {
+            children.iter_mut().for_each(downgrade_schema);
+        }
+    }
+}
+
+/// Names of the `{param}` segments of a path template.
+fn path_parameter_names(path: &str) -> impl Iterator<Item = &str> {
+    path.split('/').filter_map(|segment| {
+        segment
+            .strip_prefix('{')
+            .and_then(|s| s.strip_suffix('}'))
+            .map(|name| name.trim_end_matches('*'))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn doc() -> OpenApi {
+        serde_json::from_value(json!({
+            "openapi": "3.1.0",
+            "info": { "title": "API", "version": "1.0.0" },
+            "paths": {
+                "/users/{id}": {
+                    "get": {
+                        "operationId": "get_user",
+                        "responses": { "200": { "description": "OK" } }
+                    },
+                    "delete": {
+                        "operationId": "delete_user",
+                        "responses": { "204": { "description": "No Content" } }
+                    }
+                }
+            }
+        }))
+        .unwrap()
+    }
+
+    fn integration(value: Value) -> Integration {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_to_api_gateway() {
+        let config = ApiGatewayConfig {
+            integration: Some(integration(json!({
+                "type": "http_proxy",
+                "uri": "https://backend.internal{path}",
+                "connectionType": "VPC_LINK",
+                "connectionId": "${vpc_link_id}"
+            }))),
+            operations: BTreeMap::from([(
+                "delete_user".to_string(),
+                integration(json!({ "type": "aws_proxy", "uri": "${delete_user_arn}" })),
+            )]),
+        };
+
+        let spec = to_api_gateway(&doc(), &config).unwrap();
+
+        assert_eq!(spec["openapi"], "3.0.1");
+        let path = &spec["paths"]["/users/{id}"];
+        assert_eq!(
+            path["get"][INTEGRATION_EXTENSION],
+            json!({
+                "type": "http_proxy",
+                "uri": "https://backend.internal/users/{id}",
+                "httpMethod": "GET",
+                "connectionType": "VPC_LINK",
+                "connectionId": "${vpc_link_id}",
+                "requestParameters": { "integration.request.path.id": "method.request.path.id" }
+            })
+        );
+        assert_eq!(
+            path["delete"][INTEGRATION_EXTENSION],
+            json!({ "type": "aws_proxy", "uri": "${delete_user_arn}", "httpMethod": "POST" })
+        );
+    }
+
+    #[test]
+    fn test_to_api_gateway_errors() {
+        let err = to_api_gateway(&doc(), &ApiGatewayConfig::default()).unwrap_err();
+        assert_eq!(
+            err,
+            ApiGatewayError::Unmapped(vec!["get_user".to_string(), "delete_user".to_string()])
+        );
+
+        let config = ApiGatewayConfig {
+            integration: Some(integration(json!({ "type": "mock" }))),
+            operations: BTreeMap::from([(
+                "get_users".to_string(),
+                integration(json!({ "type": "mock" })),
+            )]),
+        };
+        let err = to_api_gateway(&doc(), &config).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "`operations` lists unknown operation(s) get_users"
+        );
+    }
+
+    #[test]
+    fn test_downgrade_to_3_0() {
+        let mut value = json!({
+            "openapi": "3.1.0",
+            "info": {
+                "title": "API",
+                "version": "1.0.0",
+                "license": { "name": "MIT", "identifier": "MIT" }
+            },
+            "jsonSchemaDialect": "https://spec.openapis.org/oas/3.1/dialect/base",
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "User": {
+                        "$schema": "https://json-schema.org/draft/2020-12/schema",
+                        "type": "object",
+                        "properties": {
+                            "nickname": { "type": ["string", "null"], "examples": ["neo"] },
+                            "avatar": { "type": "string", "contentMediaType": "image/png" },
+                            "team": {
+                                "oneOf": [{ "$ref": "#/components/schemas/Team" }, { "type": "null" }]
+                            }
+                        },
+                        "$defs": {}
+                    }
+                }
+            }
+        });
+        downgrade_to_3_0(&mut value);
+
+        assert_eq!(value["openapi"], "3.0.1");
+        assert_eq!(value["info"]["license"], json!({ "name": "MIT" }));
+        assert!(value.get("jsonSchemaDialect").is_none());
+        assert!(
+            value["components"]["schemas"]["User"]
+                .get("$schema")
+                .is_none()
+        );
+
+        let user = &value["components"]["schemas"]["User"];
+        assert!(user.get("$defs").is_none());
+        assert_eq!(
+            user["properties"]["nickname"],
+            json!({ "type": "string", "nullable": true, "example": "neo" })
+        );
+        assert_eq!(
+            user["properties"]["avatar"],
+            json!({ "type": "string", "format": "binary" })
+        );
+        assert_eq!(
+            user["properties"]["team"],
+            json!({ "oneOf": [{ "$ref": "#/components/schemas/Team" }], "nullable": true })
+        );
+    }
+}
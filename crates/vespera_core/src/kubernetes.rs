@@ -0,0 +1,492 @@
+//! Kubernetes routing manifests generated from the route table.
+//!
+//! [`http_route`] renders a Gateway API `HTTPRoute` and [`ingress`] a
+//! `networking.k8s.io/v1` `Ingress` for every path and method of a document, so the
+//! manifests that expose a service cannot drift from the routes it actually serves:
+//!
+//! ```yaml
+//! apiVersion: gateway.networking.k8s.io/v1
+//! kind: HTTPRoute
+//! metadata:
+//!   name: users-api
+//! spec:
+//!   parentRefs:
+//!     - name: public
+//!   rules:
+//!     - matches:
+//!         - path:
+//!             type: Exact
+//!             value: /users
+//!           method: GET
+//!       backendRefs:
+//!         - name: users
+//!           port: 8080
+//! ```
+//!
+//! Route timeouts (`#[route(limits = (timeout = "30s"))]`, recorded under
+//! `x-vespera-limits`) become `HTTPRoute` rule timeouts.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write as _;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{openapi::OpenApi, route::HttpMethod};
+
+/// Extension the route limits are recorded under
+const LIMITS_EXTENSION: &str = "x-vespera-limits";
+
+/// Maximum number of matches in one `HTTPRoute` rule
+const MAX_RULE_MATCHES: usize = 64;
+
+/// Where the manifests send traffic, and what they are attached to.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KubernetesConfig {
+    /// `metadata.name` of the generated resources
+    pub name: String,
+    /// `metadata.namespace` of the generated resources
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+    /// Backend `Service` name
+    pub service: String,
+    /// Backend `Service` port
+    pub port: u16,
+    /// `Gateway` the `HTTPRoute` attaches to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gateway: Option<String>,
+    /// Hostnames matched by both resources
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hostnames: Vec<String>,
+    /// `spec.ingressClassName` of the `Ingress`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ingress_class: Option<String>,
+}
+
+/// One served method on one path template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouteEntry {
+    /// HTTP method
+    pub method: HttpMethod,
+    /// Path template (`/users/{id}`)
+    pub path: String,
+    /// Handler timeout from the route's limits
+    pub timeout_ms: Option<u64>,
+}
+
+/// Every operation of `doc`, ordered by path, then method.
+#[must_use]
+pub fn route_table(doc: &OpenApi) -> Vec<RouteEntry> {
+    let mut routes = Vec::new();
+    for (path, path_item) in &doc.paths {
+        for method in path_item.methods() {
+            let Some(operation) = path_item.operation(method) else {
+                continue;
+            };
+            routes.push(RouteEntry {
+                method,
+                path: path.clone(),
+                timeout_ms: operation
+                    .extensions
+                    .get(LIMITS_EXTENSION)
+                    .and_then(|limits| limits.get("timeoutMs"))
+                    .and_then(serde_json::Value::as_u64),
+            });
+        }
+    }
+    routes
+}
+
+/// Gateway API `HTTPRoute` YAML for `doc`.
+///
+/// Static paths match `Exact`; templated ones match a `RegularExpression` with one
+/// `[^/]+` per parameter. Routes sharing a timeout share a rule.
+#[must_use]
+pub fn http_route(doc: &OpenApi, config: &KubernetesConfig) -> String {
+    let mut by_timeout: BTreeMap<Option<u64>, Vec<Yaml>> = BTreeMap::new();
+    for route in route_table(doc) {
+        let (match_type, value) = match path_regex(&route.path) {
+            Some(regex) => ("RegularExpression", regex),
+            None => ("Exact", route.path.clone()),
+        };
+        by_timeout
+            .entry(route.timeout_ms)
+            .or_default()
+            .push(Yaml::map([
+                (
+                    "path",
+                    Yaml::map([("type", Yaml::str(match_type)), ("value", Yaml::Str(value))]),
+                ),
+                ("method", Yaml::Str(route.method.to_string())),
+            ]));
+    }
+
+    let backend = Yaml::List(vec![Yaml::map([
+        ("name", Yaml::str(&config.service)),
+        ("port", Yaml::Int(u64::from(config.port))),
+    ])]);
+    let mut rules = Vec::new();
+    for (timeout_ms, matches) in by_timeout {
+        for chunk in matches.chunks(MAX_RULE_MATCHES) {
+            let mut rule = vec![
+                ("matches", Yaml::List(chunk.to_vec())),
+                ("backendRefs", backend.clone()),
+            ];
+            if let Some(timeout_ms) = timeout_ms {
+                rule.push((
+                    "timeouts",
+                    Yaml::map([("request", Yaml::Str(duration(timeout_ms)))]),
+                ));
+            }
+            rules.push(Yaml::Map(rule));
+        }
+    }
+
+    let mut spec = Vec::new();
+    if let Some(gateway) = &config.gateway {
+        spec.push((
+            "parentRefs",
+            Yaml::List(vec![Yaml::map([("name", Yaml::str(gateway))])]),
+        ));
+    }
+    if !config.hostnames.is_empty() {
+        spec.push((
+            "hostnames",
+            Yaml::List(config.hostnames.iter().map(Yaml::str).collect()),
+        ));
+    }
+    spec.push(("rules", Yaml::List(rules)));
+
+    Yaml::map([
+        ("apiVersion", Yaml::str("gateway.networking.k8s.io/v1")),
+        ("kind", Yaml::str("HTTPRoute")),
+        ("metadata", metadata(config)),
+        ("spec", Yaml::Map(spec)),
+    ])
+    .to_document()
+}
+
+/// `networking.k8s.io/v1` `Ingress` YAML for `doc`.
+///
+/// Ingress cannot match methods or path parameters: static paths match `Exact`, and
+/// templated ones match the `Prefix` before their first parameter.
+#[must_use]
+pub fn ingress(doc: &OpenApi, config: &KubernetesConfig) -> String {
+    let paths: BTreeSet<(String, &str)> = route_table(doc)
+        .into_iter()
+        .map(|route| match route.path.find('{') {
+            Some(index) => {
+                let prefix = route.path[..index].trim_end_matches('/');
+                let prefix = if prefix.is_empty() { "/" } else { prefix };
+                (prefix.to_string(), "Prefix")
+            }
+            None => (route.path, "Exact"),
+        })
+        .collect();
+    let backend = Yaml::map([(
+        "service",
+        Yaml::map([
+            ("name", Yaml::str(&config.service)),
+            (
+                "port",
+                Yaml::map([("number", Yaml::Int(u64::from(config.port)))]),
+            ),
+        ]),
+    )]);
+    let http = Yaml::map([(
+        "paths",
+        Yaml::List(
+            paths
+                .into_iter()
+                .map(|(path, path_type)| {
+                    Yaml::map([
+                        ("path", Yaml::Str(path)),
+                        ("pathType", Yaml::str(path_type)),
+                        ("backend", backend.clone()),
+                    ])
+                })
+                .collect(),
+        ),
+    )]);
+    let rules = if config.hostnames.is_empty() {
+        vec![Yaml::map([("http", http)])]
+    } else {
+        config
+            .hostnames
+            .iter()
+            .map(|host| Yaml::map([("host", Yaml::str(host)), ("http", http.clone())]))
+            .collect()
+    };
+
+    let mut spec = Vec::new();
+    if let Some(class) = &config.ingress_class {
+        spec.push(("ingressClassName", Yaml::str(class)));
+    }
+    spec.push(("rules", Yaml::List(rules)));
+
+    Yaml::map([
+        ("apiVersion", Yaml::str("networking.k8s.io/v1")),
+        ("kind", Yaml::str("Ingress")),
+        ("metadata", metadata(config)),
+        ("spec", Yaml::Map(spec)),
+    ])
+    .to_document()
+}
+
+fn metadata(config: &KubernetesConfig) -> Yaml {
+    let mut metadata = vec![("name", Yaml::str(&config.name))];
+    if let Some(namespace) = &config.namespace {
+        metadata.push(("namespace", Yaml::str(namespace)));
+    }
+    Yaml::Map(metadata)
+}
+
+/// `RegularExpression` matching a templated path, or `None` for a static one.
+fn path_regex(path: &str) -> Option<String> {
+    if !path.contains('{') {
+        return None;
+    }
+    let segments: Vec<String> = path
+        .split('/')
+        .map(|segment| match segment.strip_prefix('{') {
+            Some(param) if param.starts_with('*') => ".*".to_string(),
+            Some(_) => "[^/]+".to_string(),
+            None => segment
+                .chars()
+                .map(|c| {
+                    if "\\.+*?()|[]{}^$".contains(c) {
+                        format!("\\{c}")
+                    } else {
+                        c.to_string()
+                    }
+                })
+                .collect(),
+        })
+        .collect();
+    Some(segments.join("/"))
+}
+
+/// Gateway API duration (`"30s"`, `"1500ms"`).
+fn duration(ms: u64) -> String {
+    if ms.is_multiple_of(1_000) {
+        format!("{}s", ms / 1_000)
+    } else {
+        format!("{ms}ms")
+    }
+}
+
+/// Minimal ordered YAML tree; keys keep their insertion order.
+#[derive(Debug, Clone)]
+enum Yaml {
+    Str(String),
+    Int(u64),
+    Map(Vec<(&'static str, Self)>),
+    List(Vec<Self>),
+}
+
+impl Yaml {
+    fn str(value: impl AsRef<str>) -> Self {
+        Self::Str(value.as_ref().to_string())
+    }
+
+    fn map<const N: usize>(entries: [(&'static str, Self); N]) -> Self {
+        Self::Map(entries.into())
+    }
+
+    fn to_document(&self) -> String {
+        let mut out = String::new();
+        self.write(0, &mut out);
+        out
+    }
+
+    fn write(&self, indent: usize, out: &mut String) {
+        let pad = " ".repeat(indent);
+        match self {
+            Self::Map(entries) => {
+                for (key, value) in entries {
+                    if let Some(scalar) = value.scalar() {
+                        let _ = writeln!(out, "{pad}{key}: {scalar}");
+                    } else {
+                        let _ = writeln!(out, "{pad}{key}:");
+                        value.write(indent + 2, out);
+                    }
+                }
+            }
+            Self::List(items) => {
+                for item in items {
+                    if let Some(scalar) = item.scalar() {
+                        let _ = writeln!(out, "{pad}- {scalar}");
+                    } else {
+                        // Render one level deeper, then put the dash in the first line's indent
+                        let mut nested = String::new();
+                        item.write(indent + 2, &mut nested);
+                        let _ = write!(out, "{pad}- {}", &nested[indent + 2..]);
+                    }
+                }
+            }
+            Self::Str(_) | Self::Int(_) => {
+                if let Some(scalar) = self.scalar() {
+                    let _ = writeln!(out, "{pad}{scalar}");
+                }
+            }
+        }
+    }
+
+    fn scalar(&self) -> Option<String> {
+        match self {
+            Self::Str(value) => Some(quote(value)),
+            Self::Int(value) => Some(value.to_string()),
+            Self::Map(_) | Self::List(_) => None,
+        }
+    }
+}
+
+/// Plain scalar when unambiguous, otherwise a double-quoted one.
+fn quote(value: &str) -> String {
+    let plain = value
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphanumeric() || c == '/')
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./".contains(c))
+        && value.parse::<f64>().is_err()
+        && !matches!(
+            value.to_ascii_lowercase().as_str(),
+            "true" | "false" | "yes" | "no" | "on" | "off" | "null"
+        );
+    if plain {
+        value.to_string()
+    } else {
+        serde_json::to_string(value).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn doc() -> OpenApi {
+        serde_json::from_value(json!({
+            "openapi": "3.1.0",
+            "info": { "title": "API", "version": "1.0.0" },
+            "paths": {
+                "/users": {
+                    "get": { "responses": {} },
+                    "post": {
+                        "responses": {},
+                        "x-vespera-limits": { "body": 1024, "timeoutMs": 30000 }
+                    }
+                },
+                "/users/{id}": { "get": { "responses": {} } }
+            }
+        }))
+        .unwrap()
+    }
+
+    fn config() -> KubernetesConfig {
+        KubernetesConfig {
+            name: "users-api".to_string(),
+            namespace: Some("apps".to_string()),
+            service: "users".to_string(),
+            port: 8080,
+            gateway: Some("public".to_string()),
+            hostnames: vec!["api.example.com".to_string()],
+            ingress_class: Some("nginx".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_route_table() {
+        let routes = route_table(&doc());
+        assert_eq!(routes.len(), 3);
+        assert_eq!(routes[1].method, HttpMethod::Post);
+        assert_eq!(routes[1].timeout_ms, Some(30_000));
+        assert_eq!(routes[2].path, "/users/{id}");
+    }
+
+    #[test]
+    fn test_http_route() {
+        assert_eq!(
+            http_route(&doc(), &config()),
+            r#"apiVersion: gateway.networking.k8s.io/v1
+kind: HTTPRoute
+metadata:
+  name: users-api
+  namespace: apps
+spec:
+  parentRefs:
+    - name: public
+  hostnames:
+    - api.example.com
+  rules:
+    - matches:
+        - path:
+            type: Exact
+            value: /users
+          method: GET
+        - path:
+            type: RegularExpression
+            value: "/users/[^/]+"
+          method: GET
+      backendRefs:
+        - name: users
+          port: 8080
+    - matches:
+        - path:
+            type: Exact
+            value: /users
+          method: POST
+      backendRefs:
+        - name: users
+          port: 8080
+      timeouts:
+        request: 30s
+"#
+        );
+    }
+
+    #[test]
+    fn test_ingress() {
+        assert_eq!(
+            ingress(&doc(), &config()),
+            r"apiVersion: networking.k8s.io/v1
+kind: Ingress
+metadata:
+  name: users-api
+  namespace: apps
+spec:
+  ingressClassName: nginx
+  rules:
+    - host: api.example.com
+      http:
+        paths:
+          - path: /users
+            pathType: Exact
+            backend:
+              service:
+                name: users
+                port:
+                  number: 8080
+          - path: /users
+            pathType: Prefix
+            backend:
+              service:
+                name: users
+                port:
+                  number: 8080
+"
+        );
+    }
+
+    #[rstest::rstest]
+    #[case("/users", None)]
+    #[case("/users/{id}/posts", Some("/users/[^/]+/posts"))]
+    #[case("/files/{*rest}", Some("/files/.*"))]
+    #[case("/v1.0/{id}", Some("/v1\\.0/[^/]+"))]
+    fn test_path_regex(#[case] path: &str, #[case] expected: Option<&str>) {
+        assert_eq!(path_regex(path).as_deref(), expected);
+    }
+}
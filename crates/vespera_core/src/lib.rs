@@ -2,9 +2,17 @@
 //!
 //! Provides structures conforming to the `OpenAPI` 3.1 specification.
 
+pub mod bulk;
+pub mod gateway;
+pub mod kubernetes;
+#[cfg(feature = "unstable-metadata")]
+pub mod metadata;
 pub mod openapi;
+pub mod policy;
 pub mod route;
 pub mod schema;
+pub mod stubs;
+pub mod walk;
 
 pub use openapi::*;
 pub use route::*;
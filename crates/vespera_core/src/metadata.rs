@@ -0,0 +1,216 @@
+//! Read API over the routes and structs collected by `vespera!` (`unstable-metadata` feature).
+//!
+//! With the feature enabled on `vespera`, every `vespera!` expansion writes
+//! `target/vespera/metadata.json`, so companion proc-macros and build scripts can
+//! generate additional artifacts (permission tables, route lists, client stubs) without
+//! re-scanning the source tree:
+//!
+//! ```ignore
+//! let metadata = ApiMetadata::load(ApiMetadata::path_in("target"))?;
+//! for route in metadata.routes_with_tag("admin") {
+//!     println!("{} {} -> {}", route.method, route.path, route.operation_id);
+//! }
+//! ```
+//!
+//! The format follows the macro's internals and may change in any release; the `version`
+//! field is bumped when it does.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::route::{HttpMethod, SecurityRequirement};
+
+/// Current `version` of the metadata file.
+pub const METADATA_VERSION: u32 = 1;
+
+/// Routes and structs of one `vespera!` app.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiMetadata {
+    /// Format version, see [`METADATA_VERSION`]
+    pub version: u32,
+    /// Routes in collection order
+    #[serde(default)]
+    pub routes: Vec<RouteInfo>,
+    /// Structs known to the macro, including models kept out of the spec
+    #[serde(default)]
+    pub structs: Vec<StructInfo>,
+}
+
+/// A route handler.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RouteInfo {
+    /// Operation ID
+    pub operation_id: String,
+    /// HTTP method
+    pub method: HttpMethod,
+    /// Path template
+    pub path: String,
+    /// Handler function name
+    pub function_name: String,
+    /// Module path of the handler (e.g. `routes::users`)
+    pub module_path: String,
+    /// Source file of the handler
+    pub file_path: String,
+    /// Tags of the operation
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Route-level security requirement alternatives, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub security: Option<Vec<SecurityRequirement>>,
+    /// Whether the operation is deprecated
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub deprecated: bool,
+}
+
+/// A struct with `#[derive(Schema)]` or generated by `schema_type!`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StructInfo {
+    /// Schema name
+    pub name: String,
+    /// Struct definition as Rust source, parseable with `syn`
+    pub definition: String,
+    /// Whether the struct is a schema of the `OpenAPI` document
+    pub in_openapi: bool,
+}
+
+impl ApiMetadata {
+    /// Location of the metadata file under a target directory.
+    pub fn path_in(target_dir: impl AsRef<Path>) -> PathBuf {
+        target_dir.as_ref().join("vespera").join("metadata.json")
+    }
+
+    /// Read a metadata file written by `vespera!`.
+    ///
+    /// # Errors
+    /// When the file cannot be read or is not valid metadata JSON.
+    pub fn load(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        serde_json::from_str(&content)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+    }
+
+    /// Route with `operation_id`.
+    #[must_use]
+    pub fn route(&self, operation_id: &str) -> Option<&RouteInfo> {
+        self.routes
+            .iter()
+            .find(|route| route.operation_id == operation_id)
+    }
+
+    /// Routes tagged with `tag`.
+    pub fn routes_with_tag<'a>(&'a self, tag: &'a str) -> impl Iterator<Item = &'a RouteInfo> {
+        self.routes
+            .iter()
+            .filter(move |route| route.tags.iter().any(|t| t == tag))
+    }
+
+    /// Struct named `name`.
+    #[must_use]
+    pub fn struct_info(&self, name: &str) -> Option<&StructInfo> {
+        self.structs.iter().find(|s| s.name == name)
+    }
+
+    /// Structs that are schemas of the `OpenAPI` document.
+    pub fn schemas(&self) -> impl Iterator<Item = &StructInfo> {
+        self.structs.iter().filter(|s| s.in_openapi)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn route(operation_id: &str, tags: &[&str]) -> RouteInfo {
+        RouteInfo {
+            operation_id: operation_id.to_string(),
+            method: HttpMethod::Get,
+            path: format!("/{operation_id}"),
+            function_name: operation_id.to_string(),
+            module_path: "routes".to_string(),
+            file_path: "src/routes/mod.rs".to_string(),
+            tags: tags.iter().map(ToString::to_string).collect(),
+            security: None,
+            deprecated: false,
+        }
+    }
+
+    fn sample() -> ApiMetadata {
+        ApiMetadata {
+            version: METADATA_VERSION,
+            routes: vec![
+                route("list_users", &["users", "admin"]),
+                route("health", &[]),
+            ],
+            structs: vec![
+                StructInfo {
+                    name: "User".to_string(),
+                    definition: "pub struct User { pub id: i32 }".to_string(),
+                    in_openapi: true,
+                },
+                StructInfo {
+                    name: "Model".to_string(),
+                    definition: "pub struct Model { pub id: i32 }".to_string(),
+                    in_openapi: false,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_queries() {
+        let metadata = sample();
+        assert_eq!(metadata.route("health").unwrap().path, "/health");
+        assert!(metadata.route("missing").is_none());
+        assert_eq!(
+            metadata
+                .routes_with_tag("admin")
+                .map(|r| r.operation_id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["list_users"]
+        );
+        assert!(!metadata.struct_info("Model").unwrap().in_openapi);
+        assert_eq!(
+            metadata
+                .schemas()
+                .map(|s| s.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["User"]
+        );
+    }
+
+    #[test]
+    fn test_load_roundtrip() {
+        let dir = std::env::temp_dir().join(format!("vespera-metadata-{}", std::process::id()));
+        let path = ApiMetadata::path_in(&dir);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, serde_json::to_string(&sample()).unwrap()).unwrap();
+
+        assert_eq!(ApiMetadata::load(&path).unwrap(), sample());
+
+        std::fs::write(&path, "not json").unwrap();
+        assert_eq!(
+            ApiMetadata::load(&path).unwrap_err().kind(),
+            std::io::ErrorKind::InvalidData
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_route_json_shape() {
+        let json = serde_json::to_value(route("health", &[])).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "operationId": "health",
+                "method": "GET",
+                "path": "/health",
+                "functionName": "health",
+                "modulePath": "routes",
+                "filePath": "src/routes/mod.rs",
+            })
+        );
+    }
+}
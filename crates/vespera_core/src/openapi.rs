@@ -1,9 +1,34 @@
 //! `OpenAPI` document structure definitions
 
-use crate::route::PathItem;
-use crate::schema::{Components, ExternalDocumentation};
+use crate::route::{
+    Operation, Parameter, ParameterLocation, ParameterRef, PathItem, SecurityRequirement,
+};
+use crate::schema::{
+    ComponentKind, Components, ExternalDocumentation, Reference, Schema, SchemaRef, SecurityScheme,
+};
+use crate::walk::{self, Node, Walk};
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+/// Vendor extension naming an operation's SDK group (`#[route(sdk_group = "Users")]`).
+pub const SDK_GROUP_EXTENSION: &str = "x-sdk-group";
+
+/// Path items keyed by path template, in document order
+pub type Paths = IndexMap<String, PathItem>;
+
+/// Order of the `paths` of a document
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum PathOrder {
+    /// Sorted by path template
+    #[default]
+    Alphabetical,
+    /// Grouped by the first tag of each path's first operation, untagged paths last,
+    /// then sorted by path template
+    Tag,
+    /// The order the paths were added in
+    Source,
+}
 
 /// `OpenAPI` document version
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
@@ -100,6 +125,36 @@ pub struct Server {
     pub variables: Option<HashMap<String, ServerVariable>>,
 }
 
+impl Server {
+    /// Substitute the `{name}` variables of `url` for which `lookup` returns a value.
+    ///
+    /// Substituted variables are removed from `variables`; the rest stay templated.
+    /// A value outside a variable's `enum` is ignored.
+    pub fn resolve_variables<F>(&mut self, mut lookup: F)
+    where
+        F: FnMut(&str) -> Option<String>,
+    {
+        let Some(variables) = &mut self.variables else {
+            return;
+        };
+        variables.retain(|name, variable| {
+            let Some(value) = lookup(name).filter(|value| {
+                variable
+                    .r#enum
+                    .as_ref()
+                    .is_none_or(|allowed| allowed.contains(value))
+            }) else {
+                return true;
+            };
+            self.url = self.url.replace(&format!("{{{name}}}"), &value);
+            false
+        });
+        if variables.is_empty() {
+            self.variables = None;
+        }
+    }
+}
+
 /// Tag definition
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -122,17 +177,20 @@ pub struct OpenApi {
     pub openapi: OpenApiVersion,
     /// API information
     pub info: Info,
+    /// Default `$schema` of the document's schemas (e.g. `https://json-schema.org/draft/2020-12/schema`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub json_schema_dialect: Option<String>,
     /// Server list
     #[serde(skip_serializing_if = "Option::is_none")]
     pub servers: Option<Vec<Server>>,
     /// Path definitions
-    pub paths: BTreeMap<String, PathItem>,
+    pub paths: Paths,
     /// Components (reusable components)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub components: Option<Components>,
     /// Security requirements
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub security: Option<Vec<HashMap<String, Vec<String>>>>,
+    pub security: Option<Vec<SecurityRequirement>>,
     /// Tag definitions
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tags: Option<Vec<Tag>>,
@@ -142,15 +200,534 @@ pub struct OpenApi {
 }
 
 impl OpenApi {
+    /// Document a HEAD operation for every GET that lacks one.
+    pub fn add_head_operations(&mut self) {
+        for path_item in self.paths.values_mut() {
+            path_item.add_head_from_get();
+        }
+    }
+
+    /// Document an OPTIONS operation (with `Allow` header) for every path that lacks one.
+    pub fn add_options_operations(&mut self) {
+        for path_item in self.paths.values_mut() {
+            path_item.add_options();
+        }
+    }
+
+    /// Register `parameters` under `components/parameters` and reference them from every operation.
+    ///
+    /// Operations that already declare a parameter with the same name and location keep
+    /// their own. Header names are compared case-insensitively.
+    pub fn add_global_parameters(&mut self, parameters: &[(String, Parameter)]) {
+        if parameters.is_empty() {
+            return;
+        }
+        for path_item in self.paths.values_mut() {
+            for operation in path_item.operations_mut() {
+                let declared = operation.parameters.get_or_insert_with(Vec::new);
+                for (name, parameter) in parameters {
+                    let reference = Reference::parameter(name);
+                    let exists = declared.iter().any(|existing| match existing {
+                        ParameterRef::Ref(r) => r.ref_path == reference.ref_path,
+                        ParameterRef::Inline(p) => {
+                            p.r#in == parameter.r#in
+                                && if p.r#in == ParameterLocation::Header {
+                                    p.name.eq_ignore_ascii_case(&parameter.name)
+                                } else {
+                                    p.name == parameter.name
+                                }
+                        }
+                    });
+                    if !exists {
+                        declared.push(ParameterRef::Ref(reference));
+                    }
+                }
+            }
+        }
+        let components = self.components.get_or_insert(Components {
+            schemas: None,
+            responses: None,
+            parameters: None,
+            examples: None,
+            request_bodies: None,
+            headers: None,
+            security_schemes: None,
+        });
+        let registered = components.parameters.get_or_insert_with(HashMap::new);
+        for (name, parameter) in parameters {
+            registered
+                .entry(name.clone())
+                .or_insert_with(|| parameter.clone());
+        }
+    }
+
+    /// Register `schemes` under `components/securitySchemes`.
+    ///
+    /// Schemes already present in the document (e.g. from a merged child app) are kept.
+    pub fn add_security_schemes(&mut self, schemes: &[(String, SecurityScheme)]) {
+        if schemes.is_empty() {
+            return;
+        }
+        let components = self.components.get_or_insert(Components {
+            schemas: None,
+            responses: None,
+            parameters: None,
+            examples: None,
+            request_bodies: None,
+            headers: None,
+            security_schemes: None,
+        });
+        let registered = components.security_schemes.get_or_insert_with(HashMap::new);
+        for (name, scheme) in schemes {
+            registered
+                .entry(name.clone())
+                .or_insert_with(|| scheme.clone());
+        }
+    }
+
+    /// Set `additionalProperties: false` on every component object schema.
+    ///
+    /// See [`Schema::close_objects`](crate::schema::Schema::close_objects) for which
+    /// nested schemas are affected.
+    pub fn close_objects(&mut self) {
+        let schemas = self
+            .components
+            .as_mut()
+            .and_then(|components| components.schemas.as_mut());
+        for schema in schemas.into_iter().flat_map(|schemas| schemas.values_mut()) {
+            schema.close_objects();
+        }
+    }
+
+    /// Copy of this document with only the operations for which `predicate` returns `true`.
+    ///
+    /// Empty paths are dropped, and components, security schemes and tag definitions
+    /// no longer referenced by the remaining operations are pruned.
+    #[must_use]
+    pub fn filter<F>(&self, mut predicate: F) -> Self
+    where
+        F: FnMut(&Operation) -> bool,
+    {
+        let mut doc = self.clone();
+        for path_item in doc.paths.values_mut() {
+            for method in path_item.methods() {
+                if path_item
+                    .operation(method)
+                    .is_some_and(|operation| !predicate(operation))
+                {
+                    path_item.remove_operation(method);
+                }
+            }
+        }
+        doc.paths
+            .retain(|_, path_item| !path_item.methods().is_empty());
+        doc.prune_unreferenced();
+        doc
+    }
+
+    /// Copy of this document with only the operations tagged with any of `tags`.
+    ///
+    /// See [`OpenApi::filter`] for what gets pruned.
+    #[must_use]
+    pub fn retain_tags<I, S>(&self, tags: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let tags: Vec<String> = tags.into_iter().map(|t| t.as_ref().to_string()).collect();
+        self.filter(|operation| {
+            operation
+                .tags
+                .as_ref()
+                .is_some_and(|op_tags| op_tags.iter().any(|tag| tags.contains(tag)))
+        })
+    }
+
+    /// Copy of this document organized for SDK generators: every operation with an
+    /// [`SDK_GROUP_EXTENSION`] is tagged with its group alone, so generators that split
+    /// clients by tag (openapi-generator, Kiota, ...) emit one API class per group.
+    ///
+    /// Tag definitions are rebuilt from the tags still in use, keeping existing descriptions.
+    #[must_use]
+    pub fn group_by_sdk_group(&self) -> Self {
+        let mut doc = self.clone();
+        let mut used = BTreeSet::new();
+        for path_item in doc.paths.values_mut() {
+            for operation in path_item.operations_mut() {
+                if let Some(group) = operation
+                    .extensions
+                    .get(SDK_GROUP_EXTENSION)
+                    .and_then(serde_json::Value::as_str)
+                {
+                    operation.tags = Some(vec![group.to_string()]);
+                }
+                used.extend(operation.tags.iter().flatten().cloned());
+            }
+        }
+        let existing = doc.tags.take().unwrap_or_default();
+        let tags: Vec<Tag> = used
+            .into_iter()
+            .map(|name| {
+                existing
+                    .iter()
+                    .find(|tag| tag.name == name)
+                    .cloned()
+                    .unwrap_or(Tag {
+                        name,
+                        description: None,
+                        external_docs: None,
+                    })
+            })
+            .collect();
+        doc.tags = (!tags.is_empty()).then_some(tags);
+        doc
+    }
+
+    /// Point `#/components/schemas/{old}` references at `#/components/schemas/{new}`.
+    ///
+    /// Only `$ref`s are rewritten; the component keys themselves are left untouched.
+    pub fn rewrite_schema_refs(&mut self, renames: &HashMap<String, String>) {
+        if renames.is_empty() {
+            return;
+        }
+        let renames: HashMap<String, String> = renames
+            .iter()
+            .map(|(old, new)| {
+                (
+                    Reference::schema(old).ref_path,
+                    Reference::schema(new).ref_path,
+                )
+            })
+            .collect();
+        walk::rename_refs(self, &renames);
+    }
+
+    /// Give each schema component in `split` a `{name}Request` and a `{name}Response`
+    /// variant, and point request bodies at the first and responses at the second.
+    ///
+    /// The request variant leaves out `readOnly` properties and the response variant
+    /// `writeOnly` ones; references between split components follow the variant they
+    /// appear in. The original component is kept only while something else (a
+    /// parameter, another component) still references it.
+    pub fn split_read_write_schemas(&mut self, split: &BTreeSet<String>) {
+        let Some(schemas) = self
+            .components
+            .as_mut()
+            .and_then(|components| components.schemas.as_mut())
+        else {
+            return;
+        };
+        let split: Vec<&String> = split
+            .iter()
+            .filter(|name| schemas.contains_key(*name))
+            .collect();
+        if split.is_empty() {
+            return;
+        }
+        let variant_refs = |suffix: &str| -> HashMap<String, String> {
+            split
+                .iter()
+                .map(|name| {
+                    (
+                        Reference::schema(name).ref_path,
+                        Reference::schema(&format!("{name}{suffix}")).ref_path,
+                    )
+                })
+                .collect()
+        };
+        let (request_refs, response_refs) = (variant_refs("Request"), variant_refs("Response"));
+
+        let mut originals = BTreeMap::new();
+        for name in &split {
+            let Some(original) = schemas.remove(*name) else {
+                continue;
+            };
+            for (suffix, read_only, refs) in [
+                ("Request", true, &request_refs),
+                ("Response", false, &response_refs),
+            ] {
+                let variant_name = format!("{name}{suffix}");
+                let mut variant = original.clone();
+                remove_marked_properties(&mut variant, read_only);
+                walk::rename_refs(&mut variant, refs);
+                if let Some(title) = variant.title.as_mut() {
+                    title.clone_from(&variant_name);
+                }
+                schemas.insert(variant_name, variant);
+            }
+            originals.insert((*name).clone(), original);
+        }
+
+        for operation in self.paths.values_mut().flat_map(PathItem::operations_mut) {
+            walk::rename_refs(&mut operation.request_body, &request_refs);
+            walk::rename_refs(&mut operation.responses, &response_refs);
+        }
+
+        // Restore the originals still referenced, and whatever those reference in turn
+        loop {
+            let refs = walk::refs(self);
+            let referenced: Vec<String> = originals
+                .keys()
+                .filter(|name| refs.contains(&Reference::schema(name).ref_path))
+                .cloned()
+                .collect();
+            if referenced.is_empty() {
+                break;
+            }
+            let Some(schemas) = self
+                .components
+                .as_mut()
+                .and_then(|components| components.schemas.as_mut())
+            else {
+                break;
+            };
+            for name in referenced {
+                if let Some(original) = originals.remove(&name) {
+                    schemas.insert(name, original);
+                }
+            }
+        }
+    }
+
+    /// Add the validation pattern of each custom string format to the schemas using it.
+    ///
+    /// `patterns` maps format names (`"ulid"`) to regexes. Schemas that already
+    /// declare a `pattern` keep it.
+    pub fn apply_format_patterns(&mut self, patterns: &BTreeMap<String, String>) {
+        if patterns.is_empty() {
+            return;
+        }
+        self.walk(&mut |node| {
+            if let Node::Schema(schema) = node
+                && schema.pattern.is_none()
+                && let Some(pattern) = schema
+                    .format
+                    .as_ref()
+                    .and_then(|format| patterns.get(format))
+            {
+                schema.pattern = Some(pattern.clone());
+            }
+        });
+    }
+
+    /// Sort the properties of every schema alphabetically.
+    ///
+    /// Properties otherwise keep the order their fields are declared in.
+    pub fn sort_properties(&mut self) {
+        self.walk(&mut |node| {
+            if let Node::Schema(schema) = node
+                && let Some(properties) = schema.properties.as_mut()
+            {
+                properties.sort_keys();
+            }
+        });
+    }
+
+    /// Reorder `paths` by `order`.
+    pub fn sort_paths(&mut self, order: PathOrder) {
+        match order {
+            PathOrder::Alphabetical => self.paths.sort_keys(),
+            PathOrder::Tag => {
+                self.paths.sort_by(|path_a, item_a, path_b, item_b| {
+                    // `false` sorts first, putting untagged paths last
+                    let key = |item: &PathItem| {
+                        let tag = first_tag(item);
+                        (tag.is_none(), tag.map(str::to_string))
+                    };
+                    key(item_a)
+                        .cmp(&key(item_b))
+                        .then_with(|| path_a.cmp(path_b))
+                });
+            }
+            PathOrder::Source => {}
+        }
+    }
+
+    /// Merge paths that differ only in their parameter names (`/users/{id}` and
+    /// `/users/{user_id}`) into the first of them.
+    ///
+    /// The later path's path parameters are renamed to the first one's. Paths that
+    /// define a method the first path already has are left as they are.
+    pub fn collapse_equivalent_paths(&mut self) {
+        let mut collapsed = Paths::with_capacity(self.paths.len());
+        let mut first_by_shape: HashMap<String, String> = HashMap::new();
+        for (path, mut item) in std::mem::take(&mut self.paths) {
+            let shape = template_shape(&path);
+            if let Some(first) = first_by_shape.get(&shape)
+                && let Some(target) = collapsed.get_mut(first)
+                && item
+                    .methods()
+                    .iter()
+                    .all(|method| target.operation(*method).is_none())
+            {
+                let renames: HashMap<String, String> = template_params(&path)
+                    .zip(template_params(first))
+                    .map(|(from, to)| (from.to_string(), to.to_string()))
+                    .collect();
+                rename_item_params(&mut item, |name| {
+                    renames
+                        .get(name)
+                        .cloned()
+                        .unwrap_or_else(|| name.to_string())
+                });
+                for method in item.methods() {
+                    if let Some(operation) = item.remove_operation(method) {
+                        target.set_operation(method, operation);
+                    }
+                }
+                continue;
+            }
+            first_by_shape.entry(shape).or_insert_with(|| path.clone());
+            collapsed.insert(path, item);
+        }
+        self.paths = collapsed;
+    }
+
+    /// Rename every path parameter, in path templates and parameter definitions alike.
+    ///
+    /// Path parameter names never appear on the wire, so renaming them only changes the
+    /// documentation. A path whose renamed template already exists keeps its name.
+    pub fn rename_path_params(&mut self, rename: impl Fn(&str) -> String) {
+        let original: HashSet<String> = self.paths.keys().cloned().collect();
+        let mut renamed = Paths::with_capacity(self.paths.len());
+        for (path, mut item) in std::mem::take(&mut self.paths) {
+            let new_path = rename_template_params(&path, &rename);
+            if new_path == path || original.contains(&new_path) || renamed.contains_key(&new_path) {
+                renamed.insert(path, item);
+                continue;
+            }
+            rename_item_params(&mut item, &rename);
+            renamed.insert(new_path, item);
+        }
+        self.paths = renamed;
+    }
+
+    /// Drop components, security schemes and tag definitions unused by any operation.
+    fn prune_unreferenced(&mut self) {
+        let operations: Vec<&Operation> = self
+            .paths
+            .values()
+            .flat_map(|path_item| {
+                path_item
+                    .methods()
+                    .into_iter()
+                    .filter_map(|method| path_item.operation(method))
+            })
+            .collect();
+        let used_tags: BTreeSet<String> = operations
+            .iter()
+            .filter_map(|operation| operation.tags.as_ref())
+            .flatten()
+            .cloned()
+            .collect();
+        let used_schemes: BTreeSet<String> = operations
+            .iter()
+            .filter_map(|operation| operation.security.as_ref())
+            .chain(self.security.as_ref())
+            .flatten()
+            .flat_map(|requirement| requirement.keys().cloned())
+            .collect();
+        self.tags = self
+            .tags
+            .take()
+            .map(|tags| {
+                tags.into_iter()
+                    .filter(|tag| used_tags.contains(&tag.name))
+                    .collect::<Vec<_>>()
+            })
+            .filter(|tags| !tags.is_empty());
+
+        let Some(components) = self.components.as_mut() else {
+            return;
+        };
+        // Follow `$ref`s from paths through components until no new ones appear
+        let mut pending = walk::refs(&mut self.paths);
+        let mut referenced = BTreeSet::new();
+        while let Some(reference) = pending.pop() {
+            if referenced.contains(&reference) {
+                continue;
+            }
+            pending.extend(component_refs(components, &reference));
+            referenced.insert(reference);
+        }
+
+        let keep = |kind: ComponentKind, name: &String| {
+            referenced.contains(&Reference::component(kind, name).ref_path)
+        };
+        if let Some(schemas) = components.schemas.as_mut() {
+            schemas.retain(|name, _| keep(ComponentKind::Schemas, name));
+        }
+        if let Some(responses) = components.responses.as_mut() {
+            responses.retain(|name, _| keep(ComponentKind::Responses, name));
+        }
+        if let Some(parameters) = components.parameters.as_mut() {
+            parameters.retain(|name, _| keep(ComponentKind::Parameters, name));
+        }
+        if let Some(examples) = components.examples.as_mut() {
+            examples.retain(|name, _| keep(ComponentKind::Examples, name));
+        }
+        if let Some(request_bodies) = components.request_bodies.as_mut() {
+            request_bodies.retain(|name, _| keep(ComponentKind::RequestBodies, name));
+        }
+        if let Some(headers) = components.headers.as_mut() {
+            headers.retain(|name, _| keep(ComponentKind::Headers, name));
+        }
+        if let Some(security_schemes) = components.security_schemes.as_mut() {
+            security_schemes.retain(|name, _| used_schemes.contains(name));
+        }
+    }
+
+    /// Substitute server URL variables (`https://{region}.example.com`) with the values
+    /// returned by `lookup`. See [`Server::resolve_variables`].
+    pub fn resolve_server_variables<F>(&mut self, mut lookup: F)
+    where
+        F: FnMut(&str) -> Option<String>,
+    {
+        for server in self.servers.iter_mut().flatten() {
+            server.resolve_variables(&mut lookup);
+        }
+    }
+
     /// Merge another `OpenAPI` document into this one.
-    /// Paths, schemas, and tags from `other` are added to `self`.
+    /// Paths, components, and tags from `other` are added to `self`.
     /// If there are conflicts, `self` takes precedence.
-    pub fn merge(&mut self, other: Self) {
+    ///
+    /// A component of `other` whose name is taken by a different definition in `self`
+    /// is renamed (`OrdersUser` for prefix `/orders`, then `User2`, `User3`, ...) and
+    /// `other`'s references to it are rewritten. Identical definitions are shared. A
+    /// renamed security scheme is also renamed in `other`'s security requirements.
+    ///
+    /// With a `prefix`, `other`'s paths are mounted under it, followed by the base path
+    /// of `other`'s first server (`https://orders.internal/v1` + `/items` becomes
+    /// `/orders/v1/items`). This lets an API gateway aggregate the specs of the services
+    /// behind it, whose paths are then served from `self`'s servers.
+    ///
+    /// When `other` declares a `jsonSchemaDialect` different from `self`'s, its component
+    /// schemas keep that dialect as their own `$schema`.
+    pub fn merge(&mut self, other: Self, prefix: Option<&str>) {
+        let mount = prefix.map(|prefix| {
+            let server_path = other
+                .servers
+                .as_deref()
+                .and_then(<[Server]>::first)
+                .map_or("", |server| server_base_path(&server.url));
+            join_path(prefix, server_path)
+        });
+        let other = self.rename_colliding_components(other, prefix);
+
         // Merge paths (self takes precedence on conflict)
         for (path, item) in other.paths {
+            let path = match &mount {
+                Some(mount) => join_path(mount, &path),
+                None => path,
+            };
             self.paths.entry(path).or_insert(item);
         }
 
+        let other_dialect = other
+            .json_schema_dialect
+            .filter(|dialect| self.json_schema_dialect.as_ref() != Some(dialect));
+
         // Merge components
         if let Some(other_components) = other.components {
             let self_components = self.components.get_or_insert(Components {
@@ -166,11 +743,56 @@ impl OpenApi {
             // Merge schemas
             if let Some(other_schemas) = other_components.schemas {
                 let self_schemas = self_components.schemas.get_or_insert_with(BTreeMap::new);
-                for (name, schema) in other_schemas {
+                for (name, mut schema) in other_schemas {
+                    if schema.dialect.is_none() {
+                        schema.dialect.clone_from(&other_dialect);
+                    }
                     self_schemas.entry(name).or_insert(schema);
                 }
             }
 
+            // Merge responses
+            if let Some(other_responses) = other_components.responses {
+                let self_responses = self_components.responses.get_or_insert_with(HashMap::new);
+                for (name, response) in other_responses {
+                    self_responses.entry(name).or_insert(response);
+                }
+            }
+
+            // Merge parameters
+            if let Some(other_parameters) = other_components.parameters {
+                let self_parameters = self_components.parameters.get_or_insert_with(HashMap::new);
+                for (name, parameter) in other_parameters {
+                    self_parameters.entry(name).or_insert(parameter);
+                }
+            }
+
+            // Merge examples
+            if let Some(other_examples) = other_components.examples {
+                let self_examples = self_components.examples.get_or_insert_with(BTreeMap::new);
+                for (name, example) in other_examples {
+                    self_examples.entry(name).or_insert(example);
+                }
+            }
+
+            // Merge request bodies
+            if let Some(other_request_bodies) = other_components.request_bodies {
+                let self_request_bodies = self_components
+                    .request_bodies
+                    .get_or_insert_with(HashMap::new);
+                for (name, request_body) in other_request_bodies {
+                    self_request_bodies.entry(name).or_insert(request_body);
+                }
+            }
+
+            // Merge headers
+            if let Some(other_headers) = other_components.headers {
+                let self_headers = self_components.headers.get_or_insert_with(HashMap::new);
+                for (name, header) in other_headers {
+                    self_headers.entry(name).or_insert(header);
+                }
+            }
+
             // Merge security schemes
             if let Some(other_security_schemes) = other_components.security_schemes {
                 let self_security_schemes = self_components
@@ -192,13 +814,306 @@ impl OpenApi {
             }
         }
     }
+
+    /// Rename `other`'s components that collide with different definitions in `self`.
+    fn rename_colliding_components(&self, other: Self, prefix: Option<&str>) -> Self {
+        let (Some(self_components), Some(other_components)) =
+            (self.components.as_ref(), other.components.as_ref())
+        else {
+            return other;
+        };
+        let ours = serde_json::to_value(self_components).unwrap_or_default();
+        let theirs = serde_json::to_value(other_components).unwrap_or_default();
+        let colliding = |key: &str| match (
+            ours.get(key).and_then(serde_json::Value::as_object),
+            theirs.get(key).and_then(serde_json::Value::as_object),
+        ) {
+            (Some(ours), Some(theirs)) => colliding_names(ours, theirs, prefix),
+            _ => Vec::new(),
+        };
+
+        // "#/components/{kind}/{old}" -> "#/components/{kind}/{new}"
+        let mut renames: HashMap<String, String> = HashMap::new();
+        for kind in ComponentKind::ALL {
+            for (name, new_name) in colliding(kind.as_str()) {
+                renames.insert(
+                    Reference::component(kind, &name).ref_path,
+                    Reference::component(kind, &new_name).ref_path,
+                );
+            }
+        }
+        // Security schemes are named by requirement keys rather than `$ref`s
+        let scheme_renames: HashMap<String, String> =
+            colliding("securitySchemes").into_iter().collect();
+        if renames.is_empty() && scheme_renames.is_empty() {
+            return other;
+        }
+
+        let mut other = other;
+        walk::rename_refs(&mut other, &renames);
+        if let Some(components) = other.components.as_mut() {
+            for (old, new) in &renames {
+                if let (Some((kind, old_name)), Some((_, new_name))) =
+                    (Reference::parse(old), Reference::parse(new))
+                {
+                    rename_component(components, kind, old_name, new_name);
+                }
+            }
+        }
+        rename_security_schemes(&mut other, &scheme_renames);
+        other
+    }
+}
+
+/// New names for the entries of `theirs` taken by different definitions in `ours`:
+/// `OrdersUser` for prefix `/orders`, then `User2`, `User3`, ...
+fn colliding_names(
+    ours: &serde_json::Map<String, serde_json::Value>,
+    theirs: &serde_json::Map<String, serde_json::Value>,
+    prefix: Option<&str>,
+) -> Vec<(String, String)> {
+    let prefix = pascal_case(prefix.unwrap_or_default());
+    let mut taken: BTreeSet<String> = ours.keys().chain(theirs.keys()).cloned().collect();
+    let mut renames = Vec::new();
+    for (name, definition) in theirs {
+        if ours.get(name).is_none_or(|existing| existing == definition) {
+            continue;
+        }
+        // `bearer` -> `OrdersBearer`
+        let prefixed = (!prefix.is_empty()).then(|| format!("{prefix}{}", upper_first(name)));
+        let new_name = match prefixed {
+            Some(prefixed) if !taken.contains(&prefixed) => prefixed,
+            _ => (2..=taken.len() + 2)
+                .map(|n| format!("{name}{n}"))
+                .find(|candidate| !taken.contains(candidate))
+                .unwrap_or_default(),
+        };
+        taken.insert(new_name.clone());
+        renames.push((name.clone(), new_name));
+    }
+    renames
+}
+
+/// Rename the security schemes of `doc` in `renames`, along with the keys of the
+/// operation and top-level requirements naming them.
+fn rename_security_schemes(doc: &mut OpenApi, renames: &HashMap<String, String>) {
+    if renames.is_empty() {
+        return;
+    }
+    if let Some(schemes) = doc
+        .components
+        .as_mut()
+        .and_then(|components| components.security_schemes.as_mut())
+    {
+        for (old, new) in renames {
+            if let Some(scheme) = schemes.remove(old) {
+                schemes.insert(new.clone(), scheme);
+            }
+        }
+    }
+    let requirements = doc
+        .paths
+        .values_mut()
+        .flat_map(PathItem::operations_mut)
+        .filter_map(|operation| operation.security.as_mut())
+        .chain(doc.security.as_mut())
+        .flatten();
+    for requirement in requirements {
+        *requirement = std::mem::take(requirement)
+            .into_iter()
+            .map(|(name, scopes)| (renames.get(&name).cloned().unwrap_or(name), scopes))
+            .collect();
+    }
+}
+
+/// Remove the properties of `schema` (and of its `allOf` members) marked `readOnly`
+/// (`writeOnly` when `read_only` is false), along with their `required` entries.
+fn remove_marked_properties(schema: &mut Schema, read_only: bool) {
+    let is_marked = |property: &SchemaRef| {
+        let SchemaRef::Inline(property) = property else {
+            return false;
+        };
+        let flag = if read_only {
+            property.read_only
+        } else {
+            property.write_only
+        };
+        flag == Some(true)
+    };
+    if let Some(properties) = schema.properties.as_mut() {
+        let removed: Vec<String> = properties
+            .iter()
+            .filter(|(_, property)| is_marked(property))
+            .map(|(name, _)| name.clone())
+            .collect();
+        properties.retain(|name, _| !removed.contains(name));
+        if let Some(required) = schema.required.as_mut() {
+            required.retain(|name| !removed.contains(name));
+            if required.is_empty() {
+                schema.required = None;
+            }
+        }
+    }
+    for member in schema.all_of.iter_mut().flatten() {
+        if let SchemaRef::Inline(member) = member {
+            remove_marked_properties(member, read_only);
+        }
+    }
+}
+
+/// Move the `kind` component `old` to `new`.
+fn rename_component(components: &mut Components, kind: ComponentKind, old: &str, new: &str) {
+    macro_rules! rename {
+        ($definitions:expr) => {
+            if let Some(definitions) = $definitions.as_mut()
+                && let Some(definition) = definitions.remove(old)
+            {
+                definitions.insert(new.to_string(), definition);
+            }
+        };
+    }
+    match kind {
+        ComponentKind::Schemas => rename!(components.schemas),
+        ComponentKind::Responses => rename!(components.responses),
+        ComponentKind::Parameters => rename!(components.parameters),
+        ComponentKind::Examples => rename!(components.examples),
+        ComponentKind::RequestBodies => rename!(components.request_bodies),
+        ComponentKind::Headers => rename!(components.headers),
+    }
+}
+
+/// `/orders-api` -> `OrdersApi`
+fn pascal_case(value: &str) -> String {
+    value
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(upper_first)
+        .collect()
+}
+
+/// `bearer` -> `Bearer`
+fn upper_first(value: &str) -> String {
+    let mut chars = value.chars();
+    chars.next().map_or_else(String::new, |first| {
+        first.to_ascii_uppercase().to_string() + chars.as_str()
+    })
+}
+
+/// Path component of a server URL without trailing `/` (`https://host/v1/` -> `/v1`).
+fn server_base_path(url: &str) -> &str {
+    let path = url
+        .split_once("://")
+        .map_or(url, |(_, rest)| rest.find('/').map_or("", |i| &rest[i..]));
+    path.trim_end_matches('/')
+}
+
+/// Join a mount prefix and a path: (`orders/`, `/items`) -> `/orders/items`.
+fn join_path(prefix: &str, path: &str) -> String {
+    let prefix = prefix.trim_matches('/');
+    let path = path.trim_start_matches('/');
+    match (prefix.is_empty(), path.is_empty()) {
+        (true, _) => format!("/{path}"),
+        (false, true) => format!("/{prefix}"),
+        (false, false) => format!("/{prefix}/{path}"),
+    }
+}
+
+/// `$ref`s inside the component a `#/components/{kind}/{name}` reference points at.
+fn component_refs(components: &mut Components, reference: &str) -> Vec<String> {
+    let Some((kind, name)) = Reference::parse(reference) else {
+        return Vec::new();
+    };
+    match kind {
+        ComponentKind::Schemas => {
+            walk::refs(&mut components.schemas.as_mut().and_then(|m| m.get_mut(name)))
+        }
+        ComponentKind::Responses => {
+            walk::refs(&mut components.responses.as_mut().and_then(|m| m.get_mut(name)))
+        }
+        ComponentKind::Parameters => {
+            walk::refs(&mut components.parameters.as_mut().and_then(|m| m.get_mut(name)))
+        }
+        ComponentKind::Examples => Vec::new(),
+        ComponentKind::RequestBodies => walk::refs(
+            &mut components
+                .request_bodies
+                .as_mut()
+                .and_then(|m| m.get_mut(name)),
+        ),
+        ComponentKind::Headers => {
+            walk::refs(&mut components.headers.as_mut().and_then(|m| m.get_mut(name)))
+        }
+    }
+}
+
+/// First tag of the first operation of `item`, in `OpenAPI` path item order.
+fn first_tag(item: &PathItem) -> Option<&str> {
+    item.methods()
+        .into_iter()
+        .find_map(|method| item.operation(method)?.tags.as_deref()?.first())
+        .map(String::as_str)
+}
+
+/// Names of the `{...}` parameters of a path template, without the `*` of wildcards.
+fn template_params(path: &str) -> impl Iterator<Item = &str> {
+    path.split('/').filter_map(|segment| {
+        let name = segment.strip_prefix('{')?.strip_suffix('}')?;
+        Some(name.trim_start_matches('*'))
+    })
+}
+
+/// `path` with every parameter name replaced by `{}`, equal for equivalent templates.
+fn template_shape(path: &str) -> String {
+    rename_template_params(path, |_| String::new())
+}
+
+/// `path` with every `{name}` (or `{*name}`) renamed by `rename`.
+fn rename_template_params(path: &str, rename: impl Fn(&str) -> String) -> String {
+    path.split('/')
+        .map(|segment| {
+            let Some(name) = segment
+                .strip_prefix('{')
+                .and_then(|rest| rest.strip_suffix('}'))
+            else {
+                return segment.to_string();
+            };
+            name.strip_prefix('*').map_or_else(
+                || format!("{{{}}}", rename(name)),
+                |name| format!("{{*{}}}", rename(name)),
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Rename the path parameters defined on `item` and its operations.
+fn rename_item_params(item: &mut PathItem, rename: impl Fn(&str) -> String) {
+    let rename_parameter = |parameter: &mut Parameter| {
+        if parameter.r#in == ParameterLocation::Path {
+            parameter.name = rename(&parameter.name);
+        }
+    };
+    item.parameters
+        .iter_mut()
+        .flatten()
+        .for_each(rename_parameter);
+    for operation in item.operations_mut() {
+        for parameter in operation.parameters.iter_mut().flatten() {
+            if let ParameterRef::Inline(parameter) = parameter {
+                rename_parameter(parameter);
+            }
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::route::{Operation, PathItem};
-    use crate::schema::{Components, Schema, SchemaType, SecurityScheme, SecuritySchemeType};
+    use crate::route::{HttpMethod, Operation, PathItem};
+    use crate::schema::{
+        Components, OAuthFlows, Properties, Schema, SchemaRef, SchemaType, SecuritySchemeType,
+    };
+    use rstest::rstest;
 
     fn create_base_openapi() -> OpenApi {
         OpenApi {
@@ -212,8 +1127,9 @@ mod tests {
                 license: None,
                 summary: None,
             },
+            json_schema_dialect: None,
             servers: None,
-            paths: BTreeMap::new(),
+            paths: Paths::new(),
             components: None,
             security: None,
             tags: None,
@@ -231,7 +1147,9 @@ mod tests {
                 parameters: None,
                 request_body: None,
                 responses: BTreeMap::new(),
+                deprecated: None,
                 security: None,
+                extensions: BTreeMap::new(),
             }),
             ..Default::default()
         }
@@ -251,7 +1169,7 @@ mod tests {
             .paths
             .insert("/users".to_string(), create_path_item("Other users")); // Conflict
 
-        base.merge(other);
+        base.merge(other, None);
 
         // Both paths should exist
         assert!(base.paths.contains_key("/users"));
@@ -298,7 +1216,7 @@ mod tests {
             security_schemes: None,
         });
 
-        base.merge(other);
+        base.merge(other, None);
 
         let schemas = base.components.as_ref().unwrap().schemas.as_ref().unwrap();
         assert!(schemas.contains_key("User"));
@@ -328,13 +1246,99 @@ mod tests {
             security_schemes: None,
         });
 
-        base.merge(other);
+        base.merge(other, None);
 
         assert!(base.components.is_some());
         let schemas = base.components.as_ref().unwrap().schemas.as_ref().unwrap();
         assert!(schemas.contains_key("Post"));
     }
 
+    #[rstest]
+    #[case(
+        None,
+        Some("https://example.com/dialect"),
+        Some("https://example.com/dialect")
+    )]
+    #[case(
+        Some("https://example.com/dialect"),
+        Some("https://example.com/dialect"),
+        None
+    )]
+    #[case(Some("https://example.com/dialect"), None, None)]
+    fn test_merge_keeps_child_dialect(
+        #[case] base_dialect: Option<&str>,
+        #[case] other_dialect: Option<&str>,
+        #[case] expected: Option<&str>,
+    ) {
+        let mut base = create_base_openapi();
+        base.json_schema_dialect = base_dialect.map(str::to_string);
+
+        let mut other = create_base_openapi();
+        other.json_schema_dialect = other_dialect.map(str::to_string);
+        let mut pinned = Schema::string();
+        pinned.dialect = Some("https://json-schema.org/draft/2019-09/schema".to_string());
+        other.components = Some(Components {
+            schemas: Some(BTreeMap::from([
+                ("Post".to_string(), Schema::object()),
+                ("Slug".to_string(), pinned),
+            ])),
+            responses: None,
+            parameters: None,
+            examples: None,
+            request_bodies: None,
+            headers: None,
+            security_schemes: None,
+        });
+
+        base.merge(other, None);
+
+        let schemas = base.components.as_ref().unwrap().schemas.as_ref().unwrap();
+        assert_eq!(schemas["Post"].dialect.as_deref(), expected);
+        assert_eq!(
+            schemas["Slug"].dialect.as_deref(),
+            Some("https://json-schema.org/draft/2019-09/schema")
+        );
+        assert_eq!(base.json_schema_dialect.as_deref(), base_dialect);
+    }
+
+    #[test]
+    fn test_json_schema_dialect_serialization() {
+        let mut doc = create_base_openapi();
+        doc.json_schema_dialect =
+            Some("https://spec.openapis.org/oas/3.1/dialect/base".to_string());
+        let mut schema = Schema::string();
+        schema.dialect = Some("https://json-schema.org/draft/2020-12/schema".to_string());
+        doc.components = Some(Components {
+            schemas: Some(BTreeMap::from([("Slug".to_string(), schema)])),
+            responses: None,
+            parameters: None,
+            examples: None,
+            request_bodies: None,
+            headers: None,
+            security_schemes: None,
+        });
+
+        let value = serde_json::to_value(&doc).unwrap();
+        assert_eq!(
+            value["jsonSchemaDialect"],
+            "https://spec.openapis.org/oas/3.1/dialect/base"
+        );
+        assert_eq!(
+            value["components"]["schemas"]["Slug"]["$schema"],
+            "https://json-schema.org/draft/2020-12/schema"
+        );
+        let round_trip: OpenApi = serde_json::from_value(value).unwrap();
+        assert_eq!(
+            round_trip.json_schema_dialect.as_deref(),
+            Some("https://spec.openapis.org/oas/3.1/dialect/base")
+        );
+        assert!(
+            round_trip.components.unwrap().schemas.unwrap()["Slug"]
+                .dialect
+                .is_some()
+        );
+    }
+
     #[test]
     fn test_merge_security_schemes() {
         let mut base = create_base_openapi();
@@ -348,6 +1352,8 @@ mod tests {
                 r#in: None,
                 scheme: Some("bearer".to_string()),
                 bearer_format: Some("JWT".to_string()),
+                open_id_connect_url: None,
+                flows: None,
             },
         );
         base.components = Some(Components {
@@ -371,6 +1377,8 @@ mod tests {
                 r#in: Some("header".to_string()),
                 scheme: None,
                 bearer_format: None,
+                open_id_connect_url: None,
+                flows: None,
             },
         );
         other.components = Some(Components {
@@ -383,7 +1391,7 @@ mod tests {
             security_schemes: Some(other_security_schemes),
         });
 
-        base.merge(other);
+        base.merge(other, None);
 
         let security_schemes = base
             .components
@@ -419,7 +1427,7 @@ mod tests {
             }, // Duplicate
         ]);
 
-        base.merge(other);
+        base.merge(other, None);
 
         let tags = base.tags.as_ref().unwrap();
         assert_eq!(tags.len(), 2); // No duplicates
@@ -442,7 +1450,7 @@ mod tests {
             external_docs: None,
         }]);
 
-        base.merge(other);
+        base.merge(other, None);
 
         assert!(base.tags.is_some());
         assert_eq!(base.tags.as_ref().unwrap().len(), 1);
@@ -461,11 +1469,843 @@ mod tests {
 
         let other = create_base_openapi(); // Empty paths, no components, no tags
 
-        base.merge(other);
+        base.merge(other, None);
 
         // Base should remain unchanged
         assert_eq!(base.paths.len(), 1);
         assert!(base.paths.contains_key("/users"));
         assert_eq!(base.tags.as_ref().unwrap().len(), 1);
     }
+
+    #[test]
+    fn test_add_global_parameters() {
+        let mut base = create_base_openapi();
+        base.paths
+            .insert("/users".to_string(), create_path_item("Get users"));
+        let mut posts = create_path_item("Get posts");
+        posts.get.as_mut().unwrap().parameters =
+            Some(vec![Parameter::header("accept-language").into()]);
+        base.paths.insert("/posts".to_string(), posts);
+
+        let globals = vec![("AcceptLanguage".to_string(), Parameter::accept_language())];
+        base.add_global_parameters(&globals);
+        // Applying twice does not duplicate references
+        base.add_global_parameters(&globals);
+
+        let users = base.paths["/users"].get.as_ref().unwrap();
+        let users_params = users.parameters.as_ref().unwrap();
+        assert_eq!(users_params.len(), 1);
+        assert!(matches!(
+            &users_params[0],
+            ParameterRef::Ref(r) if r.ref_path == "#/components/parameters/AcceptLanguage"
+        ));
+
+        // Own header wins over the global one
+        let posts = base.paths["/posts"].get.as_ref().unwrap();
+        assert_eq!(posts.parameters.as_ref().unwrap().len(), 1);
+
+        let components = base.components.as_ref().unwrap();
+        assert_eq!(
+            components.parameters.as_ref().unwrap()["AcceptLanguage"].name,
+            "Accept-Language"
+        );
+    }
+
+    #[test]
+    fn test_add_security_schemes() {
+        let mut base = create_base_openapi();
+        base.add_security_schemes(&[("bearer_auth".to_string(), SecurityScheme::bearer(None))]);
+        base.add_security_schemes(&[
+            ("bearer_auth".to_string(), SecurityScheme::http("basic")),
+            (
+                "oauth".to_string(),
+                SecurityScheme::oauth2(OAuthFlows::default()),
+            ),
+        ]);
+
+        let schemes = base.components.unwrap().security_schemes.unwrap();
+        assert_eq!(schemes.len(), 2);
+        assert_eq!(schemes["bearer_auth"].scheme.as_deref(), Some("bearer"));
+        assert_eq!(schemes["oauth"].r#type, SecuritySchemeType::OAuth2);
+    }
+
+    #[test]
+    fn test_merge_parameters() {
+        let mut base = create_base_openapi();
+        let mut other = create_base_openapi();
+        other
+            .add_global_parameters(&[("RequestId".to_string(), Parameter::header("X-Request-Id"))]);
+
+        base.merge(other, None);
+
+        let parameters = base.components.unwrap().parameters.unwrap();
+        assert_eq!(parameters["RequestId"].name, "X-Request-Id");
+    }
+
+    fn create_tagged_path_item(tag: &str, schema: &str, security: Option<&str>) -> PathItem {
+        let content = BTreeMap::from([(
+            "application/json".to_string(),
+            crate::route::MediaType {
+                schema: Some(SchemaRef::Ref(Reference::schema(schema))),
+                example: None,
+                examples: None,
+            },
+        )]);
+        PathItem {
+            get: Some(Operation {
+                summary: None,
+                description: None,
+                operation_id: None,
+                tags: Some(vec![tag.to_string()]),
+                parameters: None,
+                request_body: None,
+                responses: BTreeMap::from([(
+                    "200".to_string(),
+                    crate::route::Response {
+                        description: "OK".to_string(),
+                        headers: None,
+                        content: Some(content),
+                        links: None,
+                    },
+                )]),
+                deprecated: None,
+                security: security
+                    .map(|name| vec![SecurityRequirement::from([(name.to_string(), vec![])])]),
+                extensions: BTreeMap::new(),
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn create_filterable_openapi() -> OpenApi {
+        let mut doc = create_base_openapi();
+        doc.paths.insert(
+            "/users".to_string(),
+            create_tagged_path_item("users", "User", None),
+        );
+        doc.paths.insert(
+            "/admin/report".to_string(),
+            create_tagged_path_item("admin", "AdminReport", Some("bearerAuth")),
+        );
+        let mut user = Schema::object();
+        user.properties = Some(Properties::from([(
+            "address".to_string(),
+            SchemaRef::Ref(Reference::schema("Address")),
+        )]));
+        doc.components = Some(Components {
+            schemas: Some(BTreeMap::from([
+                ("User".to_string(), user),
+                ("Address".to_string(), Schema::object()),
+                ("AdminReport".to_string(), Schema::object()),
+            ])),
+            responses: None,
+            parameters: None,
+            examples: None,
+            request_bodies: None,
+            headers: None,
+            security_schemes: Some(HashMap::from([(
+                "bearerAuth".to_string(),
+                SecurityScheme {
+                    r#type: SecuritySchemeType::Http,
+                    description: None,
+                    name: None,
+                    r#in: None,
+                    scheme: Some("bearer".to_string()),
+                    bearer_format: None,
+                    open_id_connect_url: None,
+                    flows: None,
+                },
+            )])),
+        });
+        doc.tags = Some(
+            ["users", "admin"]
+                .into_iter()
+                .map(|name| Tag {
+                    name: name.to_string(),
+                    description: None,
+                    external_docs: None,
+                })
+                .collect(),
+        );
+        doc
+    }
+
+    #[test]
+    fn test_retain_tags_prunes_unreferenced() {
+        let doc = create_filterable_openapi();
+        let users = doc.retain_tags(["users"]);
+
+        assert_eq!(users.paths.keys().collect::<Vec<_>>(), vec!["/users"]);
+        let components = users.components.as_ref().unwrap();
+        let schemas = components.schemas.as_ref().unwrap();
+        // Address is kept because User references it
+        assert_eq!(schemas.keys().collect::<Vec<_>>(), vec!["Address", "User"]);
+        assert!(components.security_schemes.as_ref().unwrap().is_empty());
+        assert_eq!(users.tags.as_ref().unwrap().len(), 1);
+
+        // The source document is untouched
+        assert_eq!(doc.paths.len(), 2);
+    }
+
+    #[test]
+    fn test_filter_by_predicate() {
+        let doc = create_filterable_openapi();
+        let secured = doc.filter(|operation| operation.security.is_some());
+
+        assert_eq!(
+            secured.paths.keys().collect::<Vec<_>>(),
+            vec!["/admin/report"]
+        );
+        let components = secured.components.as_ref().unwrap();
+        assert_eq!(
+            components
+                .schemas
+                .as_ref()
+                .unwrap()
+                .keys()
+                .collect::<Vec<_>>(),
+            vec!["AdminReport"]
+        );
+        assert!(
+            components
+                .security_schemes
+                .as_ref()
+                .unwrap()
+                .contains_key("bearerAuth")
+        );
+
+        let none = doc.filter(|_| false);
+        assert!(none.paths.is_empty());
+        assert!(none.tags.is_none());
+    }
+
+    #[test]
+    fn test_rewrite_schema_refs() {
+        let mut doc = create_base_openapi();
+        doc.paths.insert(
+            "/users".to_string(),
+            create_tagged_path_item("users", "User", None),
+        );
+        doc.paths.insert(
+            "/orders".to_string(),
+            create_tagged_path_item("orders", "Order", None),
+        );
+
+        doc.rewrite_schema_refs(&HashMap::from([("User".to_string(), "user".to_string())]));
+
+        let ref_path = |path: &str| match doc.paths[path].get.as_ref().unwrap().responses["200"]
+            .content
+            .as_ref()
+            .unwrap()["application/json"]
+            .schema
+            .clone()
+        {
+            Some(SchemaRef::Ref(r)) => r.ref_path,
+            other => panic!("expected $ref, got {other:?}"),
+        };
+        assert_eq!(ref_path("/users"), "#/components/schemas/user");
+        assert_eq!(ref_path("/orders"), "#/components/schemas/Order");
+    }
+
+    #[test]
+    fn test_split_read_write_schemas() {
+        let mut doc: OpenApi = serde_json::from_value(serde_json::json!({
+            "openapi": "3.1.0",
+            "info": { "title": "API", "version": "1.0.0" },
+            "paths": {
+                "/users": {
+                    "post": {
+                        "requestBody": {
+                            "content": { "application/json": { "schema": { "$ref": "#/components/schemas/User" } } }
+                        },
+                        "responses": {
+                            "200": {
+                                "description": "OK",
+                                "content": { "application/json": { "schema": {
+                                    "type": "array", "items": { "$ref": "#/components/schemas/User" }
+                                } } }
+                            }
+                        }
+                    }
+                }
+            },
+            "components": { "schemas": {
+                "User": {
+                    "type": "object",
+                    "title": "User",
+                    "properties": {
+                        "id": { "type": "integer", "readOnly": true },
+                        "name": { "type": "string" },
+                        "password": { "type": "string", "writeOnly": true },
+                        "team": { "allOf": [{ "$ref": "#/components/schemas/Team" }], "readOnly": true },
+                        "address": { "$ref": "#/components/schemas/Address" }
+                    },
+                    "required": ["id", "name", "password"]
+                },
+                "Address": {
+                    "type": "object",
+                    "properties": { "id": { "type": "integer", "readOnly": true } }
+                },
+                "Team": { "type": "object" },
+                "Audit": { "type": "object", "properties": { "address": { "$ref": "#/components/schemas/Address" } } }
+            } }
+        }))
+        .unwrap();
+
+        doc.split_read_write_schemas(&BTreeSet::from(["User".to_string(), "Address".to_string()]));
+
+        let value = serde_json::to_value(&doc).unwrap();
+        let schemas = &value["components"]["schemas"];
+        assert_eq!(
+            schemas["UserRequest"],
+            serde_json::json!({
+                "type": "object",
+                "title": "UserRequest",
+                "properties": {
+                    "name": { "type": "string" },
+                    "password": { "type": "string", "writeOnly": true },
+                    "address": { "$ref": "#/components/schemas/AddressRequest" }
+                },
+                "required": ["name", "password"]
+            })
+        );
+        assert_eq!(
+            schemas["UserResponse"]["properties"]
+                .as_object()
+                .unwrap()
+                .keys()
+                .map(String::as_str)
+                .collect::<BTreeSet<_>>(),
+            BTreeSet::from(["address", "id", "name", "team"])
+        );
+        assert_eq!(
+            schemas["UserResponse"]["required"],
+            serde_json::json!(["id", "name"])
+        );
+        assert_eq!(
+            schemas["UserResponse"]["properties"]["address"]["$ref"],
+            "#/components/schemas/AddressResponse"
+        );
+        // `AddressRequest` is left without properties
+        assert_eq!(
+            schemas["AddressRequest"],
+            serde_json::json!({ "type": "object", "properties": {} })
+        );
+
+        let operation = &value["paths"]["/users"]["post"];
+        assert_eq!(
+            operation["requestBody"]["content"]["application/json"]["schema"]["$ref"],
+            "#/components/schemas/UserRequest"
+        );
+        assert_eq!(
+            operation["responses"]["200"]["content"]["application/json"]["schema"]["items"]["$ref"],
+            "#/components/schemas/UserResponse"
+        );
+        // `Audit` still references `Address`; nothing references `User` any more
+        assert!(schemas["Address"].is_object());
+        assert!(schemas.get("User").is_none());
+    }
+
+    #[test]
+    fn test_apply_format_patterns() {
+        let mut doc = create_base_openapi();
+        doc.components = Some(Components {
+            schemas: Some(BTreeMap::from([(
+                "Account".to_string(),
+                serde_json::from_value(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "id": { "type": "string", "format": "ulid" },
+                        "code": { "type": "string", "format": "ulid", "pattern": "^[A-Z]+$" },
+                        "tags": { "type": "array", "items": { "type": "string", "format": "ulid" } },
+                        "email": { "type": "string", "format": "email" },
+                        "format": { "type": "string" }
+                    }
+                }))
+                .unwrap(),
+            )])),
+            responses: None,
+            parameters: None,
+            examples: None,
+            request_bodies: None,
+            headers: None,
+            security_schemes: None,
+        });
+
+        doc.apply_format_patterns(&BTreeMap::from([(
+            "ulid".to_string(),
+            "^[0-9A-HJKMNP-TV-Z]{26}$".to_string(),
+        )]));
+
+        let properties =
+            &serde_json::to_value(&doc).unwrap()["components"]["schemas"]["Account"]["properties"];
+        assert_eq!(properties["id"]["pattern"], "^[0-9A-HJKMNP-TV-Z]{26}$");
+        assert_eq!(properties["code"]["pattern"], "^[A-Z]+$");
+        assert_eq!(
+            properties["tags"]["items"]["pattern"],
+            "^[0-9A-HJKMNP-TV-Z]{26}$"
+        );
+        assert!(properties["email"].get("pattern").is_none());
+        assert!(properties["format"].get("pattern").is_none());
+    }
+
+    #[test]
+    fn test_sort_properties() {
+        let mut address = Schema::object();
+        address.properties = Some(Properties::from([
+            (
+                "zip".to_string(),
+                SchemaRef::Inline(Box::new(Schema::string())),
+            ),
+            (
+                "city".to_string(),
+                SchemaRef::Inline(Box::new(Schema::string())),
+            ),
+        ]));
+        let mut user = Schema::object();
+        user.properties = Some(Properties::from([
+            (
+                "name".to_string(),
+                SchemaRef::Inline(Box::new(Schema::string())),
+            ),
+            ("address".to_string(), SchemaRef::Inline(Box::new(address))),
+            (
+                "age".to_string(),
+                SchemaRef::Inline(Box::new(Schema::integer())),
+            ),
+        ]));
+        let mut doc = create_base_openapi();
+        doc.components = Some(Components {
+            schemas: Some(BTreeMap::from([("User".to_string(), user)])),
+            responses: None,
+            parameters: None,
+            examples: None,
+            request_bodies: None,
+            headers: None,
+            security_schemes: None,
+        });
+        let property_names = |doc: &OpenApi, nested: Option<&str>| -> Vec<String> {
+            let mut schema = &doc.components.as_ref().unwrap().schemas.as_ref().unwrap()["User"];
+            if let Some(name) = nested {
+                let SchemaRef::Inline(inner) = &schema.properties.as_ref().unwrap()[name] else {
+                    panic!("`{name}` is not inline");
+                };
+                schema = inner;
+            }
+            schema
+                .properties
+                .as_ref()
+                .unwrap()
+                .keys()
+                .cloned()
+                .collect()
+        };
+
+        // Declaration order is kept until sorted
+        assert_eq!(property_names(&doc, None), ["name", "address", "age"]);
+        doc.sort_properties();
+        assert_eq!(property_names(&doc, None), ["address", "age", "name"]);
+        assert_eq!(property_names(&doc, Some("address")), ["city", "zip"]);
+    }
+
+    #[test]
+    fn test_merge_with_prefix_renames_colliding_components() {
+        let mut gateway = create_base_openapi();
+        gateway.paths.insert(
+            "/users".to_string(),
+            create_tagged_path_item("users", "User", None),
+        );
+        gateway.components = Some(Components {
+            schemas: Some(BTreeMap::from([
+                ("User".to_string(), Schema::object()),
+                ("Money".to_string(), Schema::string()),
+            ])),
+            responses: None,
+            parameters: None,
+            examples: None,
+            request_bodies: None,
+            headers: None,
+            security_schemes: None,
+        });
+
+        let mut orders = create_base_openapi();
+        orders.servers = Some(vec![Server {
+            url: "https://orders.internal/v1/".to_string(),
+            description: None,
+            variables: None,
+        }]);
+        orders.paths.insert(
+            "/items".to_string(),
+            create_tagged_path_item("orders", "User", None),
+        );
+        orders
+            .paths
+            .insert("/".to_string(), create_path_item("Orders root"));
+        orders.components = Some(Components {
+            schemas: Some(BTreeMap::from([
+                ("User".to_string(), Schema::string()), // Different definition
+                ("Money".to_string(), Schema::string()), // Identical definition
+            ])),
+            responses: None,
+            parameters: None,
+            examples: None,
+            request_bodies: None,
+            headers: None,
+            security_schemes: None,
+        });
+
+        gateway.merge(orders, Some("/orders"));
+
+        assert_eq!(
+            gateway.paths.keys().collect::<Vec<_>>(),
+            vec!["/users", "/orders/v1/items", "/orders/v1"]
+        );
+        let schemas = gateway
+            .components
+            .as_ref()
+            .unwrap()
+            .schemas
+            .as_ref()
+            .unwrap();
+        assert_eq!(
+            schemas.keys().collect::<Vec<_>>(),
+            vec!["Money", "OrdersUser", "User"]
+        );
+        assert_eq!(schemas["User"].schema_type, Some(SchemaType::Object));
+        assert_eq!(schemas["OrdersUser"].schema_type, Some(SchemaType::String));
+
+        let item_schema = gateway.paths["/orders/v1/items"]
+            .get
+            .as_ref()
+            .unwrap()
+            .responses["200"]
+            .content
+            .as_ref()
+            .unwrap()["application/json"]
+            .schema
+            .clone();
+        assert!(matches!(
+            item_schema,
+            Some(SchemaRef::Ref(r)) if r.ref_path == "#/components/schemas/OrdersUser"
+        ));
+        // Untouched gateway route keeps its own reference
+        let user_schema = gateway.paths["/users"].get.as_ref().unwrap().responses["200"]
+            .content
+            .as_ref()
+            .unwrap()["application/json"]
+            .schema
+            .clone();
+        assert!(matches!(
+            user_schema,
+            Some(SchemaRef::Ref(r)) if r.ref_path == "#/components/schemas/User"
+        ));
+    }
+
+    #[test]
+    fn test_merge_without_prefix_numbers_colliding_components() {
+        let components = |schema: Schema| Components {
+            schemas: Some(BTreeMap::from([("User".to_string(), schema)])),
+            responses: None,
+            parameters: None,
+            examples: None,
+            request_bodies: None,
+            headers: None,
+            security_schemes: None,
+        };
+        let mut base = create_base_openapi();
+        base.components = Some(components(Schema::object()));
+        let mut other = create_base_openapi();
+        other.components = Some(components(Schema::string()));
+
+        base.merge(other, None);
+
+        let schemas = base.components.unwrap().schemas.unwrap();
+        assert_eq!(schemas.keys().collect::<Vec<_>>(), vec!["User", "User2"]);
+    }
+
+    #[test]
+    fn test_merge_renames_colliding_security_schemes() {
+        let bearer = |bearer_format: &str| SecurityScheme {
+            r#type: SecuritySchemeType::Http,
+            description: None,
+            name: None,
+            r#in: None,
+            scheme: Some("bearer".to_string()),
+            bearer_format: Some(bearer_format.to_string()),
+            open_id_connect_url: None,
+            flows: None,
+        };
+        let components = |scheme: SecurityScheme| Components {
+            schemas: None,
+            responses: None,
+            parameters: None,
+            examples: None,
+            request_bodies: None,
+            headers: None,
+            security_schemes: Some(HashMap::from([("bearer".to_string(), scheme)])),
+        };
+        let requirement = |name: &str| vec![BTreeMap::from([(name.to_string(), Vec::new())])];
+        let mut gateway = create_base_openapi();
+        gateway.components = Some(components(bearer("JWT")));
+        gateway.security = Some(requirement("bearer"));
+        let mut orders = create_base_openapi();
+        let mut item = create_path_item("Orders");
+        item.get.as_mut().unwrap().security = Some(requirement("bearer"));
+        orders.paths.insert("/items".to_string(), item);
+        orders.components = Some(components(bearer("opaque")));
+        orders.security = Some(requirement("bearer"));
+
+        gateway.merge(orders, Some("/orders"));
+
+        let schemes = gateway.components.unwrap().security_schemes.unwrap();
+        assert_eq!(schemes["bearer"].bearer_format.as_deref(), Some("JWT"));
+        assert_eq!(
+            schemes["OrdersBearer"].bearer_format.as_deref(),
+            Some("opaque")
+        );
+        let operation = gateway.paths["/orders/items"].get.as_ref().unwrap();
+        assert_eq!(operation.security, Some(requirement("OrdersBearer")));
+        // The gateway's own top-level requirement is kept
+        assert_eq!(gateway.security, Some(requirement("bearer")));
+    }
+
+    #[rstest]
+    #[case("https://orders.internal/v1/", "/v1")]
+    #[case("http://localhost:3000", "")]
+    #[case("/api", "/api")]
+    fn test_server_base_path(#[case] url: &str, #[case] expected: &str) {
+        assert_eq!(server_base_path(url), expected);
+    }
+
+    #[rstest]
+    #[case("/orders", "/items", "/orders/items")]
+    #[case("orders/", "/", "/orders")]
+    #[case("", "/items", "/items")]
+    #[case("/", "/", "/")]
+    fn test_join_path(#[case] prefix: &str, #[case] path: &str, #[case] expected: &str) {
+        assert_eq!(join_path(prefix, path), expected);
+    }
+
+    fn templated_server() -> Server {
+        let variable = |default: &str, allowed: Option<&[&str]>| ServerVariable {
+            default: default.to_string(),
+            r#enum: allowed.map(|allowed| allowed.iter().map(ToString::to_string).collect()),
+            description: None,
+        };
+        Server {
+            url: "https://{env}.{region}.example.com/v1".to_string(),
+            description: None,
+            variables: Some(HashMap::from([
+                ("env".to_string(), variable("dev", Some(&["dev", "prod"]))),
+                ("region".to_string(), variable("us-east-1", None)),
+            ])),
+        }
+    }
+
+    #[rstest]
+    #[case(&[("env", "prod"), ("region", "eu-west-1")], "https://prod.eu-west-1.example.com/v1", &[])]
+    #[case(&[("region", "eu-west-1")], "https://{env}.eu-west-1.example.com/v1", &["env"])]
+    #[case(&[("env", "staging")], "https://{env}.{region}.example.com/v1", &["env", "region"])]
+    #[case(&[("other", "x")], "https://{env}.{region}.example.com/v1", &["env", "region"])]
+    fn test_resolve_server_variables(
+        #[case] values: &[(&str, &str)],
+        #[case] expected_url: &str,
+        #[case] remaining: &[&str],
+    ) {
+        let mut doc = create_base_openapi();
+        doc.servers = Some(vec![templated_server()]);
+        doc.resolve_server_variables(|name| {
+            values
+                .iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| (*value).to_string())
+        });
+
+        let server = &doc.servers.unwrap()[0];
+        assert_eq!(server.url, expected_url);
+        let mut names: Vec<_> = server
+            .variables
+            .iter()
+            .flat_map(HashMap::keys)
+            .map(String::as_str)
+            .collect();
+        names.sort_unstable();
+        assert_eq!(names, remaining);
+        assert_eq!(server.variables.is_none(), remaining.is_empty());
+    }
+
+    #[test]
+    fn test_group_by_sdk_group() {
+        let mut doc = create_base_openapi();
+        let mut grouped = create_tagged_path_item("users", "User", None);
+        grouped
+            .get
+            .as_mut()
+            .unwrap()
+            .extensions
+            .insert(SDK_GROUP_EXTENSION.to_string(), "Accounts".into());
+        doc.paths.insert("/users".to_string(), grouped);
+        doc.paths.insert(
+            "/orders".to_string(),
+            create_tagged_path_item("orders", "Order", None),
+        );
+        doc.tags = Some(vec![
+            Tag {
+                name: "orders".to_string(),
+                description: Some("Orders".to_string()),
+                external_docs: None,
+            },
+            Tag {
+                name: "users".to_string(),
+                description: None,
+                external_docs: None,
+            },
+        ]);
+
+        let sdk = doc.group_by_sdk_group();
+        let tags_of = |path: &str| sdk.paths[path].get.as_ref().unwrap().tags.clone();
+        assert_eq!(tags_of("/users"), Some(vec!["Accounts".to_string()]));
+        assert_eq!(tags_of("/orders"), Some(vec!["orders".to_string()]));
+        let tags: Vec<_> = sdk
+            .tags
+            .unwrap()
+            .into_iter()
+            .map(|tag| (tag.name, tag.description))
+            .collect();
+        assert_eq!(
+            tags,
+            [
+                ("Accounts".to_string(), None),
+                ("orders".to_string(), Some("Orders".to_string())),
+            ]
+        );
+        // The source document is untouched
+        assert_eq!(
+            doc.paths["/users"].get.as_ref().unwrap().tags,
+            Some(vec!["users".to_string()])
+        );
+    }
+
+    fn create_path_param_item(method: HttpMethod, param: &str) -> PathItem {
+        let mut operation = create_path_item("op").get.unwrap();
+        operation.parameters = Some(vec![ParameterRef::Inline(Box::new(Parameter {
+            name: param.to_string(),
+            r#in: ParameterLocation::Path,
+            description: None,
+            required: Some(true),
+            schema: None,
+            example: None,
+            style: None,
+            explode: None,
+        }))]);
+        let mut item = PathItem::default();
+        item.set_operation(method, operation);
+        item
+    }
+
+    fn path_param_names(item: &PathItem) -> Vec<&str> {
+        HttpMethod::ALL
+            .into_iter()
+            .filter_map(|method| item.operation(method))
+            .flat_map(|operation| operation.parameters.iter().flatten())
+            .filter_map(ParameterRef::as_inline)
+            .map(|parameter| parameter.name.as_str())
+            .collect()
+    }
+
+    #[rstest]
+    #[case(PathOrder::Alphabetical, &["/health", "/posts", "/users"])]
+    #[case(PathOrder::Tag, &["/posts", "/users", "/health"])]
+    #[case(PathOrder::Source, &["/users", "/health", "/posts"])]
+    fn test_sort_paths(#[case] order: PathOrder, #[case] expected: &[&str]) {
+        let mut doc = create_base_openapi();
+        doc.paths.insert(
+            "/users".to_string(),
+            create_tagged_path_item("users", "User", None),
+        );
+        doc.paths
+            .insert("/health".to_string(), create_path_item("Health"));
+        doc.paths.insert(
+            "/posts".to_string(),
+            create_tagged_path_item("posts", "Post", None),
+        );
+
+        doc.sort_paths(order);
+
+        assert_eq!(doc.paths.keys().collect::<Vec<_>>(), expected);
+    }
+
+    #[test]
+    fn test_collapse_equivalent_paths() {
+        let mut doc = create_base_openapi();
+        doc.paths.insert(
+            "/users/{id}".to_string(),
+            create_path_param_item(HttpMethod::Get, "id"),
+        );
+        doc.paths.insert(
+            "/users/{user_id}".to_string(),
+            create_path_param_item(HttpMethod::Delete, "user_id"),
+        );
+        // GET clashes with `/users/{id}`, so this one stays separate
+        doc.paths.insert(
+            "/users/{uid}".to_string(),
+            create_path_param_item(HttpMethod::Get, "uid"),
+        );
+        doc.paths.insert(
+            "/users/{id}/posts".to_string(),
+            create_path_param_item(HttpMethod::Get, "id"),
+        );
+
+        doc.collapse_equivalent_paths();
+
+        assert_eq!(
+            doc.paths.keys().collect::<Vec<_>>(),
+            vec!["/users/{id}", "/users/{uid}", "/users/{id}/posts"]
+        );
+        let users = &doc.paths["/users/{id}"];
+        assert_eq!(users.methods(), vec![HttpMethod::Get, HttpMethod::Delete]);
+        assert_eq!(path_param_names(users), vec!["id", "id"]);
+    }
+
+    #[test]
+    fn test_rename_path_params() {
+        let mut doc = create_base_openapi();
+        doc.paths.insert(
+            "/users/{user_id}/files/{*file_path}".to_string(),
+            create_path_param_item(HttpMethod::Get, "user_id"),
+        );
+        doc.paths.insert(
+            "/posts/{postId}".to_string(),
+            create_path_param_item(HttpMethod::Get, "postId"),
+        );
+        // Renaming `/tags/{tag_id}` would collide with this path
+        doc.paths.insert(
+            "/tags/{tagId}".to_string(),
+            create_path_param_item(HttpMethod::Get, "tagId"),
+        );
+        doc.paths.insert(
+            "/tags/{tag_id}".to_string(),
+            create_path_param_item(HttpMethod::Delete, "tag_id"),
+        );
+
+        doc.rename_path_params(|name| name.replace("_i", "I").replace("_p", "P"));
+
+        assert_eq!(
+            doc.paths.keys().collect::<Vec<_>>(),
+            vec![
+                "/users/{userId}/files/{*filePath}",
+                "/posts/{postId}",
+                "/tags/{tagId}",
+                "/tags/{tag_id}",
+            ]
+        );
+        assert_eq!(
+            path_param_names(&doc.paths["/users/{userId}/files/{*filePath}"]),
+            vec!["userId"]
+        );
+        assert_eq!(
+            path_param_names(&doc.paths["/tags/{tag_id}"]),
+            vec!["tag_id"]
+        );
+    }
 }
@@ -0,0 +1,239 @@
+//! Authorization policy matrix: which credentials each operation requires.
+//!
+//! A [`PolicyMatrix`] flattens the security requirements of an API into one entry per
+//! operation, with document-level security already applied, so authorization middleware
+//! and audits can read a single table instead of re-deriving inheritance rules:
+//!
+//! ```json
+//! {
+//!   "operations": [
+//!     {
+//!       "operationId": "users_list_users",
+//!       "method": "GET",
+//!       "path": "/users",
+//!       "requirements": [{ "oauth": ["read:users"] }]
+//!     }
+//!   ]
+//! }
+//! ```
+//!
+//! `requirements` keeps `OpenAPI` semantics: alternatives (OR) of scheme sets (AND), where
+//! each scheme lists the scopes or roles it needs. No requirements means the operation is
+//! public, and an empty alternative means credentials are optional.
+
+use std::collections::BTreeSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    openapi::OpenApi,
+    route::{HttpMethod, SecurityRequirement},
+};
+
+/// Security policy of every operation in an API.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PolicyMatrix {
+    /// One entry per operation, ordered by path, then method
+    pub operations: Vec<OperationPolicy>,
+}
+
+/// Security policy of a single operation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OperationPolicy {
+    /// Operation ID
+    pub operation_id: String,
+    /// HTTP method
+    pub method: HttpMethod,
+    /// Path template
+    pub path: String,
+    /// Tags of the operation
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Accepted alternatives (OR) of scheme -> scopes/roles sets (AND)
+    #[serde(default)]
+    pub requirements: Vec<SecurityRequirement>,
+}
+
+impl OperationPolicy {
+    /// Whether a request without credentials is accepted.
+    #[must_use]
+    pub fn is_public(&self) -> bool {
+        self.requirements.is_empty() || self.requirements.iter().any(SecurityRequirement::is_empty)
+    }
+
+    /// Every scope or role named by any alternative, across all schemes.
+    #[must_use]
+    pub fn scopes(&self) -> BTreeSet<&str> {
+        self.requirements
+            .iter()
+            .flat_map(|requirement| requirement.values().flatten())
+            .map(String::as_str)
+            .collect()
+    }
+}
+
+impl PolicyMatrix {
+    /// Build the matrix from a finished document.
+    ///
+    /// Operations without their own `security` inherit the document-level requirement.
+    /// Operations without an `operationId` are keyed as `"{METHOD} {path}"`.
+    #[must_use]
+    pub fn from_openapi(doc: &OpenApi) -> Self {
+        let default_requirements = doc.security.clone().unwrap_or_default();
+        let mut operations = Vec::new();
+        for (path, path_item) in &doc.paths {
+            for method in path_item.methods() {
+                let Some(operation) = path_item.operation(method) else {
+                    continue;
+                };
+                operations.push(OperationPolicy {
+                    operation_id: operation
+                        .operation_id
+                        .clone()
+                        .unwrap_or_else(|| format!("{method} {path}")),
+                    method,
+                    path: path.clone(),
+                    tags: operation.tags.clone().unwrap_or_default(),
+                    requirements: operation
+                        .security
+                        .clone()
+                        .unwrap_or_else(|| default_requirements.clone()),
+                });
+            }
+        }
+        Self { operations }
+    }
+
+    /// Policy of the operation with `operation_id`.
+    #[must_use]
+    pub fn get(&self, operation_id: &str) -> Option<&OperationPolicy> {
+        self.operations
+            .iter()
+            .find(|policy| policy.operation_id == operation_id)
+    }
+
+    /// Policy of the operation serving `method` on the path template `path`.
+    #[must_use]
+    pub fn find(&self, method: HttpMethod, path: &str) -> Option<&OperationPolicy> {
+        self.operations
+            .iter()
+            .find(|policy| policy.method == method && policy.path == path)
+    }
+
+    /// Pretty-printed JSON, for writing the matrix to a file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails.
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+    use crate::{
+        openapi::{Info, OpenApiVersion, Paths},
+        route::{Operation, PathItem},
+    };
+
+    fn operation(id: &str, security: Option<Vec<SecurityRequirement>>) -> Operation {
+        Operation {
+            operation_id: Some(id.to_string()),
+            tags: Some(vec!["users".to_string()]),
+            summary: None,
+            description: None,
+            parameters: None,
+            request_body: None,
+            responses: BTreeMap::new(),
+            deprecated: None,
+            security,
+            extensions: BTreeMap::new(),
+        }
+    }
+
+    fn requirement(scheme: &str, scopes: &[&str]) -> SecurityRequirement {
+        BTreeMap::from([(
+            scheme.to_string(),
+            scopes.iter().map(ToString::to_string).collect(),
+        )])
+    }
+
+    #[test]
+    fn test_from_openapi_applies_document_security() {
+        let mut doc = OpenApi {
+            openapi: OpenApiVersion::V3_1_0,
+            info: Info::default(),
+            json_schema_dialect: None,
+            servers: None,
+            paths: Paths::new(),
+            components: None,
+            security: Some(vec![requirement("bearer", &[])]),
+            tags: None,
+            external_docs: None,
+        };
+        let mut users = PathItem::default();
+        users.set_operation(HttpMethod::Get, operation("list_users", None));
+        users.set_operation(
+            HttpMethod::Post,
+            operation(
+                "create_user",
+                Some(vec![requirement("oauth", &["write:users", "admin"])]),
+            ),
+        );
+        doc.paths.insert("/users".to_string(), users);
+        let mut health = PathItem::default();
+        health.set_operation(HttpMethod::Get, operation("health", Some(vec![])));
+        doc.paths.insert("/health".to_string(), health);
+
+        let matrix = PolicyMatrix::from_openapi(&doc);
+
+        assert_eq!(matrix.operations.len(), 3);
+        // Operations follow the document's path order
+        assert_eq!(
+            matrix
+                .operations
+                .iter()
+                .map(|op| op.operation_id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["list_users", "create_user", "health"]
+        );
+        assert!(matrix.get("health").unwrap().is_public());
+
+        let list = matrix.find(HttpMethod::Get, "/users").unwrap();
+        assert_eq!(list.requirements, vec![requirement("bearer", &[])]);
+        assert!(!list.is_public());
+
+        let create = matrix.get("create_user").unwrap();
+        assert_eq!(
+            create.scopes().into_iter().collect::<Vec<_>>(),
+            vec!["admin", "write:users"]
+        );
+        assert_eq!(
+            serde_json::to_value(create).unwrap(),
+            serde_json::json!({
+                "operationId": "create_user",
+                "method": "POST",
+                "path": "/users",
+                "tags": ["users"],
+                "requirements": [{ "oauth": ["write:users", "admin"] }]
+            })
+        );
+    }
+
+    #[test]
+    fn test_optional_credentials_are_public() {
+        let policy = OperationPolicy {
+            operation_id: "feed".to_string(),
+            method: HttpMethod::Get,
+            path: "/feed".to_string(),
+            tags: vec![],
+            requirements: vec![requirement("bearer", &[]), BTreeMap::new()],
+        };
+        assert!(policy.is_public());
+    }
+}
@@ -3,7 +3,7 @@
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, HashMap};
 
-use crate::SchemaRef;
+use crate::{Reference, Schema, SchemaRef};
 
 /// HTTP method
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -19,6 +19,37 @@ pub enum HttpMethod {
     Trace,
 }
 
+impl HttpMethod {
+    /// All methods, in `OpenAPI` path item order
+    pub const ALL: [Self; 8] = [
+        Self::Get,
+        Self::Post,
+        Self::Put,
+        Self::Patch,
+        Self::Delete,
+        Self::Head,
+        Self::Options,
+        Self::Trace,
+    ];
+
+    /// `Allow` header value for a resource serving `methods`.
+    ///
+    /// `HEAD` is implied by `GET`, and `OPTIONS` is always allowed.
+    #[must_use]
+    pub fn allow_header_value(methods: &[Self]) -> String {
+        Self::ALL
+            .iter()
+            .filter(|method| {
+                methods.contains(method)
+                    || **method == Self::Options
+                    || (**method == Self::Head && methods.contains(&Self::Get))
+            })
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
 impl std::fmt::Display for HttpMethod {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -62,6 +93,19 @@ pub enum ParameterLocation {
     Cookie,
 }
 
+/// Parameter serialization style
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ParameterStyle {
+    Matrix,
+    Label,
+    Form,
+    Simple,
+    SpaceDelimited,
+    PipeDelimited,
+    DeepObject,
+}
+
 /// Parameter definition
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -82,6 +126,69 @@ pub struct Parameter {
     /// Example value
     #[serde(skip_serializing_if = "Option::is_none")]
     pub example: Option<serde_json::Value>,
+    /// Serialization style (e.g., `deepObject` for nested query objects)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub style: Option<ParameterStyle>,
+    /// Whether arrays/objects generate separate parameters per value
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explode: Option<bool>,
+}
+
+impl Parameter {
+    /// Optional string header parameter.
+    #[must_use]
+    pub fn header(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            r#in: ParameterLocation::Header,
+            description: None,
+            required: Some(false),
+            schema: Some(SchemaRef::Inline(Box::new(Schema::string()))),
+            example: None,
+            style: None,
+            explode: None,
+        }
+    }
+
+    /// Optional `Accept-Language` header for selecting the response language.
+    #[must_use]
+    pub fn accept_language() -> Self {
+        Self {
+            description: Some(
+                "Preferred response languages as a weighted list of language tags (RFC 9110)"
+                    .to_string(),
+            ),
+            example: Some(serde_json::Value::String("en-US,en;q=0.9".to_string())),
+            ..Self::header("Accept-Language")
+        }
+    }
+}
+
+/// Parameter reference or inline parameter
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ParameterRef {
+    /// Parameter reference (e.g., "#/components/parameters/AcceptLanguage")
+    Ref(Reference),
+    /// Inline parameter
+    Inline(Box<Parameter>),
+}
+
+impl ParameterRef {
+    /// The inline parameter, or `None` for a reference.
+    #[must_use]
+    pub fn as_inline(&self) -> Option<&Parameter> {
+        match self {
+            Self::Inline(parameter) => Some(parameter),
+            Self::Ref(_) => None,
+        }
+    }
+}
+
+impl From<Parameter> for ParameterRef {
+    fn from(parameter: Parameter) -> Self {
+        Self::Inline(Box::new(parameter))
+    }
 }
 
 /// Request body definition
@@ -110,7 +217,23 @@ pub struct MediaType {
     pub example: Option<serde_json::Value>,
     /// Examples
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub examples: Option<HashMap<String, Example>>,
+    pub examples: Option<BTreeMap<String, ExampleRef>>,
+}
+
+/// Example reference or inline example
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ExampleRef {
+    /// Example reference (e.g., "#/components/examples/User.minimal")
+    Ref(Reference),
+    /// Inline example
+    Inline(Box<Example>),
+}
+
+impl From<Example> for ExampleRef {
+    fn from(example: Example) -> Self {
+        Self::Inline(Box::new(example))
+    }
 }
 
 /// Example definition
@@ -136,24 +259,104 @@ pub struct Response {
     pub description: String,
     /// Header definitions
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub headers: Option<HashMap<String, Header>>,
+    pub headers: Option<HashMap<String, HeaderRef>>,
     /// Schema per Content-Type
     #[serde(skip_serializing_if = "Option::is_none")]
     pub content: Option<BTreeMap<String, MediaType>>,
+    /// Operations reachable from this response, by link name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub links: Option<BTreeMap<String, Link>>,
+}
+
+/// Link from a response to another operation
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Link {
+    /// `operationId` of the linked operation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub operation_id: Option<String>,
+    /// Reference to the linked operation, when it has no `operationId`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub operation_ref: Option<String>,
+    /// Linked operation's parameters, as values or runtime expressions
+    /// (e.g. `"$response.body#/id"`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parameters: Option<BTreeMap<String, serde_json::Value>>,
+    /// Linked operation's request body, as a value or runtime expression
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_body: Option<serde_json::Value>,
+    /// Link description
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
 }
 
 /// Header definition
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Header {
     /// Header description
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    /// Whether the header is always sent
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub required: Option<bool>,
+    /// Whether the header is deprecated
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<bool>,
     /// Schema reference or inline schema
     #[serde(skip_serializing_if = "Option::is_none")]
     pub schema: Option<SchemaRef>,
+    /// Example value
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub example: Option<serde_json::Value>,
 }
 
+impl Header {
+    /// Create a header with a string schema
+    #[must_use]
+    pub fn string(description: &str) -> Self {
+        Self {
+            description: Some(description.to_string()),
+            schema: Some(SchemaRef::Inline(Box::new(Schema::string()))),
+            ..Self::default()
+        }
+    }
+}
+
+/// Header reference or inline header
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum HeaderRef {
+    /// Header reference (e.g., "#/components/headers/X-Rate-Limit")
+    Ref(Reference),
+    /// Inline header
+    Inline(Box<Header>),
+}
+
+impl HeaderRef {
+    /// The inline header, or `None` for a reference.
+    #[must_use]
+    pub fn as_inline(&self) -> Option<&Header> {
+        match self {
+            Self::Inline(header) => Some(header),
+            Self::Ref(_) => None,
+        }
+    }
+}
+
+impl From<Header> for HeaderRef {
+    fn from(header: Header) -> Self {
+        Self::Inline(Box::new(header))
+    }
+}
+
+/// Security requirement object (scheme name -> required scopes).
+///
+/// Every scheme in one requirement must be satisfied together (AND); a list of
+/// requirements is satisfied by any one of its entries (OR). An empty requirement
+/// makes authentication optional, and an empty list disables inherited security.
+pub type SecurityRequirement = BTreeMap<String, Vec<String>>;
+
 /// `OpenAPI` Operation definition
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -172,15 +375,21 @@ pub struct Operation {
     pub description: Option<String>,
     /// List of parameters
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub parameters: Option<Vec<Parameter>>,
+    pub parameters: Option<Vec<ParameterRef>>,
     /// Request body
     #[serde(skip_serializing_if = "Option::is_none")]
     pub request_body: Option<RequestBody>,
     /// Response definitions (status code -> Response)
     pub responses: BTreeMap<String, Response>,
+    /// Deprecated flag
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<bool>,
     /// Security requirements
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub security: Option<Vec<HashMap<String, Vec<String>>>>,
+    pub security: Option<Vec<SecurityRequirement>>,
+    /// Specification extensions (`x-*` fields)
+    #[serde(flatten, default)]
+    pub extensions: BTreeMap<String, serde_json::Value>,
 }
 
 /// Path Item definition (all HTTP methods for a specific path)
@@ -236,6 +445,147 @@ impl PathItem {
             HttpMethod::Trace => self.trace = Some(operation),
         }
     }
+
+    /// Remove and return the operation for a specific HTTP method
+    pub const fn remove_operation(&mut self, method: HttpMethod) -> Option<Operation> {
+        match method {
+            HttpMethod::Get => self.get.take(),
+            HttpMethod::Post => self.post.take(),
+            HttpMethod::Put => self.put.take(),
+            HttpMethod::Patch => self.patch.take(),
+            HttpMethod::Delete => self.delete.take(),
+            HttpMethod::Head => self.head.take(),
+            HttpMethod::Options => self.options.take(),
+            HttpMethod::Trace => self.trace.take(),
+        }
+    }
+
+    /// Get the operation for a specific HTTP method
+    #[must_use]
+    pub const fn operation(&self, method: HttpMethod) -> Option<&Operation> {
+        match method {
+            HttpMethod::Get => self.get.as_ref(),
+            HttpMethod::Post => self.post.as_ref(),
+            HttpMethod::Put => self.put.as_ref(),
+            HttpMethod::Patch => self.patch.as_ref(),
+            HttpMethod::Delete => self.delete.as_ref(),
+            HttpMethod::Head => self.head.as_ref(),
+            HttpMethod::Options => self.options.as_ref(),
+            HttpMethod::Trace => self.trace.as_ref(),
+        }
+    }
+
+    /// Get the operation for a specific HTTP method, mutably
+    pub const fn operation_mut(&mut self, method: HttpMethod) -> Option<&mut Operation> {
+        match method {
+            HttpMethod::Get => self.get.as_mut(),
+            HttpMethod::Post => self.post.as_mut(),
+            HttpMethod::Put => self.put.as_mut(),
+            HttpMethod::Patch => self.patch.as_mut(),
+            HttpMethod::Delete => self.delete.as_mut(),
+            HttpMethod::Head => self.head.as_mut(),
+            HttpMethod::Options => self.options.as_mut(),
+            HttpMethod::Trace => self.trace.as_mut(),
+        }
+    }
+
+    /// Every defined operation, mutably
+    pub fn operations_mut(&mut self) -> impl Iterator<Item = &mut Operation> {
+        [
+            &mut self.get,
+            &mut self.put,
+            &mut self.post,
+            &mut self.delete,
+            &mut self.options,
+            &mut self.head,
+            &mut self.patch,
+            &mut self.trace,
+        ]
+        .into_iter()
+        .flatten()
+    }
+
+    /// Methods that have an operation, in `OpenAPI` path item order
+    #[must_use]
+    pub fn methods(&self) -> Vec<HttpMethod> {
+        HttpMethod::ALL
+            .into_iter()
+            .filter(|method| self.operation(*method).is_some())
+            .collect()
+    }
+
+    /// Add a HEAD operation mirroring GET, with response bodies removed.
+    ///
+    /// Does nothing if HEAD is already defined or there is no GET.
+    pub fn add_head_from_get(&mut self) {
+        if self.head.is_some() {
+            return;
+        }
+        let Some(get) = &self.get else {
+            return;
+        };
+        let mut head = get.clone();
+        head.operation_id = get.operation_id.as_ref().map(|id| format!("{id}_head"));
+        for response in head.responses.values_mut() {
+            response.content = None;
+        }
+        self.head = Some(head);
+    }
+
+    /// Add an OPTIONS operation answering `204` with an `Allow` header.
+    ///
+    /// Path parameters are copied from an existing operation so the path template
+    /// stays valid. Security is cleared because CORS preflight requests carry no
+    /// credentials. Does nothing if OPTIONS is already defined or the path is empty.
+    pub fn add_options(&mut self) {
+        let methods = self.methods();
+        if self.options.is_some() || methods.is_empty() {
+            return;
+        }
+        let allow = HttpMethod::allow_header_value(&methods);
+        let path_parameters: Vec<ParameterRef> = methods
+            .iter()
+            .find_map(|method| self.operation(*method)?.parameters.as_ref())
+            .map(|parameters| {
+                parameters
+                    .iter()
+                    .filter(|p| {
+                        p.as_inline()
+                            .is_some_and(|p| p.r#in == ParameterLocation::Path)
+                    })
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default();
+        let headers = HashMap::from([(
+            "Allow".to_string(),
+            Header::string(&format!("Allowed methods: {allow}")).into(),
+        )]);
+        self.options = Some(Operation {
+            operation_id: None,
+            tags: None,
+            summary: Some("Allowed methods".to_string()),
+            description: None,
+            parameters: if path_parameters.is_empty() {
+                None
+            } else {
+                Some(path_parameters)
+            },
+            request_body: None,
+            responses: BTreeMap::from([(
+                "204".to_string(),
+                Response {
+                    description: "Allowed methods for this path".to_string(),
+                    headers: Some(headers),
+                    content: None,
+                    links: None,
+                },
+            )]),
+            deprecated: None,
+            security: Some(Vec::new()),
+            extensions: BTreeMap::new(),
+        });
+    }
 }
 
 #[cfg(test)]
@@ -320,7 +670,9 @@ mod tests {
             parameters: None,
             request_body: None,
             responses: BTreeMap::new(),
+            deprecated: None,
             security: None,
+            extensions: BTreeMap::new(),
         };
 
         // Test setting GET operation
@@ -378,6 +730,52 @@ mod tests {
         assert!(path_item.trace.is_some());
     }
 
+    #[test]
+    fn test_path_item_all_methods_round_trip() {
+        let mut path_item = PathItem::default();
+        for method in HttpMethod::ALL {
+            path_item.set_operation(
+                method,
+                Operation {
+                    operation_id: Some(method.to_string().to_lowercase()),
+                    tags: None,
+                    summary: None,
+                    description: None,
+                    parameters: None,
+                    request_body: None,
+                    responses: BTreeMap::new(),
+                    deprecated: None,
+                    security: None,
+                    extensions: BTreeMap::new(),
+                },
+            );
+        }
+
+        let json = serde_json::to_value(&path_item).unwrap();
+        for method in HttpMethod::ALL {
+            let name = method.to_string().to_lowercase();
+            assert_eq!(json[&name]["operationId"], name.as_str());
+        }
+
+        let round_trip: PathItem = serde_json::from_value(json).unwrap();
+        assert_eq!(round_trip.methods(), HttpMethod::ALL.to_vec());
+        for method in HttpMethod::ALL {
+            assert_eq!(
+                round_trip.operation(method).unwrap().operation_id,
+                Some(method.to_string().to_lowercase())
+            );
+            assert_eq!(
+                serde_json::from_value::<HttpMethod>(serde_json::to_value(method).unwrap())
+                    .unwrap(),
+                method
+            );
+            assert_eq!(
+                HttpMethod::try_from(method.to_string().as_str()),
+                Ok(method)
+            );
+        }
+    }
+
     #[test]
     fn test_path_item_set_operation_overwrites() {
         let mut path_item = PathItem::default();
@@ -390,7 +788,9 @@ mod tests {
             parameters: None,
             request_body: None,
             responses: BTreeMap::new(),
+            deprecated: None,
             security: None,
+            extensions: BTreeMap::new(),
         };
 
         let operation2 = Operation {
@@ -401,7 +801,9 @@ mod tests {
             parameters: None,
             request_body: None,
             responses: BTreeMap::new(),
+            deprecated: None,
             security: None,
+            extensions: BTreeMap::new(),
         };
 
         // Set first operation
@@ -461,4 +863,275 @@ mod tests {
         assert_eq!(map.get(&HttpMethod::Post), Some(&"POST method"));
         assert_eq!(map.get(&HttpMethod::Put), None);
     }
+
+    #[test]
+    fn test_operation_security_alternatives_serialization() {
+        let bearer = SecurityRequirement::from([("bearer".to_string(), vec![])]);
+        let key_and_client = SecurityRequirement::from([
+            ("api_key".to_string(), vec![]),
+            ("client_id".to_string(), vec![]),
+        ]);
+        let operation = Operation {
+            operation_id: None,
+            tags: None,
+            summary: None,
+            description: None,
+            parameters: None,
+            request_body: None,
+            responses: BTreeMap::new(),
+            deprecated: None,
+            security: Some(vec![bearer, key_and_client, SecurityRequirement::new()]),
+            extensions: BTreeMap::new(),
+        };
+        let json = serde_json::to_value(&operation).unwrap();
+        assert_eq!(
+            json["security"],
+            serde_json::json!([
+                { "bearer": [] },
+                { "api_key": [], "client_id": [] },
+                {}
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parameter_ref_serialization() {
+        let parameters = vec![
+            ParameterRef::Ref(Reference::parameter("AcceptLanguage")),
+            Parameter::header("X-Request-Id").into(),
+        ];
+        let json = serde_json::to_value(&parameters).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!([
+                { "$ref": "#/components/parameters/AcceptLanguage" },
+                {
+                    "name": "X-Request-Id",
+                    "in": "header",
+                    "required": false,
+                    "schema": { "type": "string" }
+                }
+            ])
+        );
+
+        let parsed: Vec<ParameterRef> = serde_json::from_value(json).unwrap();
+        assert!(parsed[0].as_inline().is_none());
+        assert_eq!(parsed[1].as_inline().unwrap().name, "X-Request-Id");
+    }
+
+    #[test]
+    fn test_header_ref_serialization() {
+        let headers = HashMap::from([
+            (
+                "X-Rate-Limit".to_string(),
+                HeaderRef::Ref(Reference::header("X-Rate-Limit")),
+            ),
+            (
+                "X-Trace-Id".to_string(),
+                Header {
+                    required: Some(true),
+                    deprecated: Some(true),
+                    example: Some(serde_json::json!("abc123")),
+                    ..Header::string("Trace identifier")
+                }
+                .into(),
+            ),
+        ]);
+        let json = serde_json::to_value(&headers).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "X-Rate-Limit": { "$ref": "#/components/headers/X-Rate-Limit" },
+                "X-Trace-Id": {
+                    "description": "Trace identifier",
+                    "required": true,
+                    "deprecated": true,
+                    "schema": { "type": "string" },
+                    "example": "abc123"
+                }
+            })
+        );
+
+        let parsed: HashMap<String, HeaderRef> = serde_json::from_value(json).unwrap();
+        assert!(parsed["X-Rate-Limit"].as_inline().is_none());
+        assert_eq!(
+            parsed["X-Trace-Id"].as_inline().unwrap().required,
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn test_example_ref_serialization() {
+        let examples = vec![
+            ExampleRef::Ref(Reference::example("User.minimal")),
+            Example {
+                summary: Some("Alice".to_string()),
+                description: None,
+                value: Some(serde_json::json!({ "name": "alice" })),
+            }
+            .into(),
+        ];
+        let json = serde_json::to_value(&examples).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!([
+                { "$ref": "#/components/examples/User.minimal" },
+                { "summary": "Alice", "value": { "name": "alice" } }
+            ])
+        );
+
+        let parsed: Vec<ExampleRef> = serde_json::from_value(json).unwrap();
+        assert!(matches!(parsed[0], ExampleRef::Ref(_)));
+        assert!(matches!(parsed[1], ExampleRef::Inline(_)));
+    }
+
+    #[test]
+    fn test_parameter_accept_language() {
+        let parameter = Parameter::accept_language();
+        assert_eq!(parameter.name, "Accept-Language");
+        assert_eq!(parameter.r#in, ParameterLocation::Header);
+        assert_eq!(parameter.required, Some(false));
+        assert!(parameter.description.is_some());
+    }
+
+    #[rstest]
+    #[case(&[HttpMethod::Get], "GET, HEAD, OPTIONS")]
+    #[case(&[HttpMethod::Post, HttpMethod::Get], "GET, POST, HEAD, OPTIONS")]
+    #[case(&[HttpMethod::Delete], "DELETE, OPTIONS")]
+    fn test_allow_header_value(#[case] methods: &[HttpMethod], #[case] expected: &str) {
+        assert_eq!(HttpMethod::allow_header_value(methods), expected);
+    }
+
+    fn operation_with_body(operation_id: &str) -> Operation {
+        Operation {
+            operation_id: Some(operation_id.to_string()),
+            tags: None,
+            summary: None,
+            description: None,
+            parameters: Some(vec![
+                Parameter {
+                    name: "id".to_string(),
+                    r#in: ParameterLocation::Path,
+                    description: None,
+                    required: Some(true),
+                    schema: None,
+                    example: None,
+                    style: None,
+                    explode: None,
+                }
+                .into(),
+                ParameterRef::Ref(Reference::parameter("AcceptLanguage")),
+            ]),
+            request_body: None,
+            responses: BTreeMap::from([(
+                "200".to_string(),
+                Response {
+                    description: "OK".to_string(),
+                    headers: None,
+                    content: Some(BTreeMap::from([(
+                        "application/json".to_string(),
+                        MediaType {
+                            schema: None,
+                            example: None,
+                            examples: None,
+                        },
+                    )])),
+                    links: None,
+                },
+            )]),
+            deprecated: None,
+            security: None,
+            extensions: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_path_item_add_head_from_get() {
+        let mut path_item = PathItem {
+            get: Some(operation_with_body("get_user")),
+            ..Default::default()
+        };
+        path_item.add_head_from_get();
+
+        let head = path_item.head.as_ref().unwrap();
+        assert_eq!(head.operation_id.as_deref(), Some("get_user_head"));
+        assert!(head.responses["200"].content.is_none());
+        assert!(
+            path_item.get.as_ref().unwrap().responses["200"]
+                .content
+                .is_some()
+        );
+
+        let mut post_only = PathItem {
+            post: Some(operation_with_body("create")),
+            ..Default::default()
+        };
+        post_only.add_head_from_get();
+        assert!(post_only.head.is_none());
+    }
+
+    #[test]
+    fn test_path_item_add_options() {
+        let mut path_item = PathItem {
+            get: Some(operation_with_body("get_user")),
+            delete: Some(operation_with_body("delete_user")),
+            ..Default::default()
+        };
+        path_item.add_options();
+
+        let options = path_item.options.as_ref().unwrap();
+        let response = &options.responses["204"];
+        assert_eq!(
+            response.headers.as_ref().unwrap()["Allow"]
+                .as_inline()
+                .unwrap()
+                .description
+                .as_deref(),
+            Some("Allowed methods: GET, DELETE, HEAD, OPTIONS")
+        );
+        let parameters = options.parameters.as_ref().unwrap();
+        assert_eq!(parameters.len(), 1);
+        assert_eq!(parameters[0].as_inline().unwrap().name, "id");
+        assert_eq!(options.security, Some(vec![]));
+        assert_eq!(
+            path_item.methods(),
+            vec![HttpMethod::Get, HttpMethod::Delete, HttpMethod::Options]
+        );
+
+        let mut empty = PathItem::default();
+        empty.add_options();
+        assert!(empty.options.is_none());
+    }
+
+    #[test]
+    fn test_response_links_serialization() {
+        let response = Response {
+            description: "Created".to_string(),
+            headers: None,
+            content: None,
+            links: Some(BTreeMap::from([(
+                "GetUser".to_string(),
+                Link {
+                    operation_id: Some("users_get_user".to_string()),
+                    parameters: Some(BTreeMap::from([(
+                        "id".to_string(),
+                        "$response.body#/id".into(),
+                    )])),
+                    ..Link::default()
+                },
+            )])),
+        };
+        assert_eq!(
+            serde_json::to_value(&response).unwrap(),
+            serde_json::json!({
+                "description": "Created",
+                "links": {
+                    "GetUser": {
+                        "operationId": "users_get_user",
+                        "parameters": { "id": "$response.body#/id" }
+                    }
+                }
+            })
+        );
+    }
 }
@@ -1,8 +1,12 @@
 //! Schema-related structure definitions
 
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, HashMap};
 
+/// Property definitions of an object schema, in insertion (field declaration) order
+pub type Properties = IndexMap<String, SchemaRef>;
+
 /// Schema reference or inline schema
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
@@ -21,17 +25,130 @@ pub struct Reference {
     pub ref_path: String,
 }
 
+/// Kind of component a `#/components/{kind}/{name}` reference points at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ComponentKind {
+    Schemas,
+    Responses,
+    Parameters,
+    Examples,
+    RequestBodies,
+    Headers,
+}
+
+impl ComponentKind {
+    /// Every kind, in `Components` field order
+    pub const ALL: [Self; 6] = [
+        Self::Schemas,
+        Self::Responses,
+        Self::Parameters,
+        Self::Examples,
+        Self::RequestBodies,
+        Self::Headers,
+    ];
+
+    /// JSON key of the kind under `components`
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Schemas => "schemas",
+            Self::Responses => "responses",
+            Self::Parameters => "parameters",
+            Self::Examples => "examples",
+            Self::RequestBodies => "requestBodies",
+            Self::Headers => "headers",
+        }
+    }
+
+    /// Kind named by a JSON key under `components`
+    #[must_use]
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.as_str() == key)
+    }
+}
+
+impl std::fmt::Display for ComponentKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 impl Reference {
+    /// Prefix of every component reference
+    pub const COMPONENTS_PREFIX: &str = "#/components/";
+
     /// Create a new reference
     #[must_use]
     pub const fn new(ref_path: String) -> Self {
         Self { ref_path }
     }
 
+    /// Create a reference to the component `name` of `kind`
+    #[must_use]
+    pub fn component(kind: ComponentKind, name: &str) -> Self {
+        Self::new(format!("{}{kind}/{name}", Self::COMPONENTS_PREFIX))
+    }
+
     /// Create a component schema reference
     #[must_use]
     pub fn schema(name: &str) -> Self {
-        Self::new(format!("#/components/schemas/{name}"))
+        Self::component(ComponentKind::Schemas, name)
+    }
+
+    /// Create a component response reference
+    #[must_use]
+    pub fn response(name: &str) -> Self {
+        Self::component(ComponentKind::Responses, name)
+    }
+
+    /// Create a component parameter reference
+    #[must_use]
+    pub fn parameter(name: &str) -> Self {
+        Self::component(ComponentKind::Parameters, name)
+    }
+
+    /// Create a component example reference
+    #[must_use]
+    pub fn example(name: &str) -> Self {
+        Self::component(ComponentKind::Examples, name)
+    }
+
+    /// Create a component request body reference
+    #[must_use]
+    pub fn request_body(name: &str) -> Self {
+        Self::component(ComponentKind::RequestBodies, name)
+    }
+
+    /// Create a component header reference
+    #[must_use]
+    pub fn header(name: &str) -> Self {
+        Self::component(ComponentKind::Headers, name)
+    }
+
+    /// Split a `#/components/{kind}/{name}` reference path into its kind and name.
+    ///
+    /// Returns `None` for external references and unknown kinds.
+    #[must_use]
+    pub fn parse(ref_path: &str) -> Option<(ComponentKind, &str)> {
+        let (kind, name) = ref_path
+            .strip_prefix(Self::COMPONENTS_PREFIX)?
+            .split_once('/')?;
+        Some((ComponentKind::from_key(kind)?, name))
+    }
+
+    /// Kind and name of the component this reference points at, see [`Reference::parse`]
+    #[must_use]
+    pub fn target(&self) -> Option<(ComponentKind, &str)> {
+        Self::parse(&self.ref_path)
+    }
+
+    /// Name of the schema a `#/components/schemas/{name}` reference path points at
+    #[must_use]
+    pub fn schema_name(ref_path: &str) -> Option<&str> {
+        match Self::parse(ref_path)? {
+            (ComponentKind::Schemas, name) => Some(name),
+            _ => None,
+        }
     }
 }
 
@@ -136,6 +253,12 @@ pub struct Schema {
     /// Pattern (regex)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub pattern: Option<String>,
+    /// Encoding of string content (e.g., "base64" for binary data embedded in JSON)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_encoding: Option<String>,
+    /// Media type of string content (e.g., "image/png")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_media_type: Option<String>,
 
     // Array constraints
     /// Array item schema
@@ -157,13 +280,16 @@ pub struct Schema {
     // Object constraints
     /// Property definitions
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub properties: Option<BTreeMap<String, SchemaRef>>,
+    pub properties: Option<Properties>,
     /// List of required properties
     #[serde(skip_serializing_if = "Option::is_none")]
     pub required: Option<Vec<String>>,
     /// Whether additional properties are allowed (can be boolean or `SchemaRef`)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub additional_properties: Option<serde_json::Value>,
+    /// Schema every property name must match
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub property_names: Option<Box<SchemaRef>>,
     /// Minimum number of properties
     #[serde(skip_serializing_if = "Option::is_none")]
     pub min_properties: Option<usize>,
@@ -175,6 +301,10 @@ pub struct Schema {
     /// Enum values
     #[serde(skip_serializing_if = "Option::is_none")]
     pub r#enum: Option<Vec<serde_json::Value>>,
+    /// Constant value (`const`), the only value the instance may take
+    #[serde(rename = "const")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub r#const: Option<serde_json::Value>,
     /// All conditions must be satisfied (AND)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub all_of: Option<Vec<SchemaRef>>,
@@ -201,9 +331,20 @@ pub struct Schema {
     /// Write-only flag
     #[serde(skip_serializing_if = "Option::is_none")]
     pub write_only: Option<bool>,
+    /// Deprecated flag
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<bool>,
     /// External documentation reference
     #[serde(skip_serializing_if = "Option::is_none")]
     pub external_docs: Option<ExternalDocumentation>,
+    /// XML representation (element name, namespace, attribute/wrapping)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub xml: Option<Xml>,
+
+    /// Dialect ($schema) of this schema, when it differs from the document's `jsonSchemaDialect`
+    #[serde(rename = "$schema")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dialect: Option<String>,
 
     // JSON Schema 2020-12 dynamic references
     /// Definitions ($defs) - reusable schema definitions
@@ -241,6 +382,8 @@ impl Schema {
             min_length: None,
             max_length: None,
             pattern: None,
+            content_encoding: None,
+            content_media_type: None,
             items: None,
             prefix_items: None,
             min_items: None,
@@ -249,9 +392,11 @@ impl Schema {
             properties: None,
             required: None,
             additional_properties: None,
+            property_names: None,
             min_properties: None,
             max_properties: None,
             r#enum: None,
+            r#const: None,
             all_of: None,
             any_of: None,
             one_of: None,
@@ -260,7 +405,10 @@ impl Schema {
             nullable: None,
             read_only: None,
             write_only: None,
+            deprecated: None,
             external_docs: None,
+            xml: None,
+            dialect: None,
             defs: None,
             dynamic_anchor: None,
             dynamic_ref: None,
@@ -291,6 +439,24 @@ impl Schema {
         Self::new(SchemaType::Boolean)
     }
 
+    /// Create a `format: binary` string schema for raw bytes
+    #[must_use]
+    pub fn binary() -> Self {
+        Self {
+            format: Some("binary".to_string()),
+            ..Self::string()
+        }
+    }
+
+    /// Create a binary schema whose `contentMediaType` is `media_type`
+    #[must_use]
+    pub fn binary_with_media_type(media_type: impl Into<String>) -> Self {
+        Self {
+            content_media_type: Some(media_type.into()),
+            ..Self::binary()
+        }
+    }
+
     /// Create an array schema
     #[must_use]
     pub fn array(items: SchemaRef) -> Self {
@@ -304,11 +470,34 @@ impl Schema {
     #[must_use]
     pub fn object() -> Self {
         Self {
-            properties: Some(BTreeMap::new()),
+            properties: Some(Properties::new()),
             required: Some(Vec::new()),
             ..Self::new(SchemaType::Object)
         }
     }
+
+    /// Set `additionalProperties: false` on this object schema and the inline object
+    /// schemas nested in its properties, items and `oneOf`/`anyOf` branches.
+    ///
+    /// Schemas that already declare `additionalProperties` (maps) are left alone, and
+    /// `allOf` members stay open because each one only sees part of the properties.
+    pub fn close_objects(&mut self) {
+        if self.schema_type == Some(SchemaType::Object) && self.additional_properties.is_none() {
+            self.additional_properties = Some(serde_json::Value::Bool(false));
+        }
+        let nested = self
+            .properties
+            .iter_mut()
+            .flat_map(Properties::values_mut)
+            .chain(self.items.as_deref_mut())
+            .chain(self.one_of.iter_mut().flatten())
+            .chain(self.any_of.iter_mut().flatten());
+        for schema_ref in nested {
+            if let SchemaRef::Inline(schema) = schema_ref {
+                schema.close_objects();
+            }
+        }
+    }
 }
 
 /// External documentation reference
@@ -336,6 +525,27 @@ pub struct Discriminator {
     pub mapping: Option<BTreeMap<String, String>>,
 }
 
+/// XML object describing how a schema is represented in XML
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Xml {
+    /// Element or attribute name (defaults to the property or component name)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Namespace URI
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+    /// Namespace prefix
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prefix: Option<String>,
+    /// Serialize the property as an attribute instead of an element
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attribute: Option<bool>,
+    /// Wrap array items in an outer element (arrays only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wrapped: Option<bool>,
+}
+
 /// `OpenAPI` Components (reusable components)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -351,7 +561,7 @@ pub struct Components {
     pub parameters: Option<HashMap<String, crate::route::Parameter>>,
     /// Example definitions
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub examples: Option<HashMap<String, crate::route::Example>>,
+    pub examples: Option<BTreeMap<String, crate::route::Example>>,
     /// Request body definitions
     #[serde(skip_serializing_if = "Option::is_none")]
     pub request_bodies: Option<HashMap<String, crate::route::RequestBody>>,
@@ -369,7 +579,9 @@ pub struct Components {
 pub enum SecuritySchemeType {
     ApiKey,
     Http,
+    #[serde(rename = "mutualTLS")]
     MutualTls,
+    #[serde(rename = "oauth2")]
     OAuth2,
     OpenIdConnect,
 }
@@ -395,6 +607,111 @@ pub struct SecurityScheme {
     /// Bearer format (for HTTP Bearer)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub bearer_format: Option<String>,
+    /// Discovery document URL (for OpenID Connect)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub open_id_connect_url: Option<String>,
+    /// Supported flows (for OAuth2)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flows: Option<OAuthFlows>,
+}
+
+/// OAuth2 flows supported by a security scheme
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OAuthFlows {
+    /// Implicit flow
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub implicit: Option<OAuthFlow>,
+    /// Resource owner password flow
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password: Option<OAuthFlow>,
+    /// Client credentials flow
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_credentials: Option<OAuthFlow>,
+    /// Authorization code flow
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authorization_code: Option<OAuthFlow>,
+}
+
+/// Configuration of a single OAuth2 flow
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OAuthFlow {
+    /// Authorization URL (implicit and authorization code flows)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authorization_url: Option<String>,
+    /// Token URL (password, client credentials and authorization code flows)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token_url: Option<String>,
+    /// Refresh URL
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refresh_url: Option<String>,
+    /// Available scopes mapped to their descriptions (may be empty)
+    #[serde(default)]
+    pub scopes: BTreeMap<String, String>,
+}
+
+impl SecurityScheme {
+    /// Create a scheme of the given type with no other fields set
+    #[must_use]
+    pub const fn new(r#type: SecuritySchemeType) -> Self {
+        Self {
+            r#type,
+            description: None,
+            name: None,
+            r#in: None,
+            scheme: None,
+            bearer_format: None,
+            open_id_connect_url: None,
+            flows: None,
+        }
+    }
+
+    /// Create an HTTP scheme, e.g. `"basic"` or `"bearer"`
+    #[must_use]
+    pub fn http(scheme: impl Into<String>) -> Self {
+        Self {
+            scheme: Some(scheme.into()),
+            ..Self::new(SecuritySchemeType::Http)
+        }
+    }
+
+    /// Create an HTTP bearer scheme with an optional token format hint (e.g. `"JWT"`)
+    #[must_use]
+    pub fn bearer(bearer_format: Option<&str>) -> Self {
+        Self {
+            bearer_format: bearer_format.map(str::to_string),
+            ..Self::http("bearer")
+        }
+    }
+
+    /// Create an API key scheme read from `location` (`"header"`, `"query"` or `"cookie"`)
+    #[must_use]
+    pub fn api_key(name: impl Into<String>, location: impl Into<String>) -> Self {
+        Self {
+            name: Some(name.into()),
+            r#in: Some(location.into()),
+            ..Self::new(SecuritySchemeType::ApiKey)
+        }
+    }
+
+    /// Create an OAuth2 scheme supporting `flows`
+    #[must_use]
+    pub fn oauth2(flows: OAuthFlows) -> Self {
+        Self {
+            flows: Some(flows),
+            ..Self::new(SecuritySchemeType::OAuth2)
+        }
+    }
+
+    /// Create an OpenID Connect scheme from its discovery document URL
+    #[must_use]
+    pub fn open_id_connect(url: impl Into<String>) -> Self {
+        Self {
+            open_id_connect_url: Some(url.into()),
+            ..Self::new(SecuritySchemeType::OpenIdConnect)
+        }
+    }
 }
 
 #[cfg(test)]
@@ -411,6 +728,153 @@ mod tests {
         assert_eq!(schema.schema_type, Some(expected));
     }
 
+    #[rstest]
+    #[case(
+        Reference::schema("User"),
+        "#/components/schemas/User",
+        ComponentKind::Schemas
+    )]
+    #[case(
+        Reference::response("NotFound"),
+        "#/components/responses/NotFound",
+        ComponentKind::Responses
+    )]
+    #[case(
+        Reference::request_body("NewUser"),
+        "#/components/requestBodies/NewUser",
+        ComponentKind::RequestBodies
+    )]
+    #[case(
+        Reference::example("User.full"),
+        "#/components/examples/User.full",
+        ComponentKind::Examples
+    )]
+    fn reference_roundtrip(
+        #[case] reference: Reference,
+        #[case] ref_path: &str,
+        #[case] kind: ComponentKind,
+    ) {
+        assert_eq!(reference.ref_path, ref_path);
+        let (parsed_kind, name) = reference.target().unwrap();
+        assert_eq!(parsed_kind, kind);
+        assert_eq!(Reference::component(parsed_kind, name).ref_path, ref_path);
+    }
+
+    #[rstest]
+    #[case("#/components/schemas/User", Some("User"))]
+    #[case("#/components/responses/User", None)]
+    #[case("#/components/unknown/User", None)]
+    #[case("other.json#/components/schemas/User", None)]
+    #[case("#/components/schemas", None)]
+    fn reference_schema_name(#[case] ref_path: &str, #[case] expected: Option<&str>) {
+        assert_eq!(Reference::schema_name(ref_path), expected);
+    }
+
+    #[rstest]
+    #[case(Schema::binary(), serde_json::json!({ "type": "string", "format": "binary" }))]
+    #[case(
+        Schema::binary_with_media_type("image/png"),
+        serde_json::json!({ "type": "string", "format": "binary", "contentMediaType": "image/png" })
+    )]
+    fn binary_helpers_serialize(#[case] schema: Schema, #[case] expected: serde_json::Value) {
+        assert_eq!(serde_json::to_value(schema).unwrap(), expected);
+    }
+
+    #[rstest]
+    #[case(SecurityScheme::http("basic"), serde_json::json!({ "type": "http", "scheme": "basic" }))]
+    #[case(
+        SecurityScheme::bearer(Some("JWT")),
+        serde_json::json!({ "type": "http", "scheme": "bearer", "bearerFormat": "JWT" })
+    )]
+    #[case(
+        SecurityScheme::api_key("X-API-Key", "header"),
+        serde_json::json!({ "type": "apiKey", "name": "X-API-Key", "in": "header" })
+    )]
+    #[case(
+        SecurityScheme::open_id_connect("https://example.com/.well-known/openid-configuration"),
+        serde_json::json!({
+            "type": "openIdConnect",
+            "openIdConnectUrl": "https://example.com/.well-known/openid-configuration"
+        })
+    )]
+    #[case(SecurityScheme::new(SecuritySchemeType::MutualTls), serde_json::json!({ "type": "mutualTLS" }))]
+    #[case(
+        SecurityScheme::oauth2(OAuthFlows {
+            authorization_code: Some(OAuthFlow {
+                authorization_url: Some("https://auth.example.com/authorize".to_string()),
+                token_url: Some("https://auth.example.com/token".to_string()),
+                refresh_url: None,
+                scopes: BTreeMap::from([("read:users".to_string(), "Read users".to_string())]),
+            }),
+            client_credentials: Some(OAuthFlow {
+                token_url: Some("https://auth.example.com/token".to_string()),
+                ..OAuthFlow::default()
+            }),
+            ..OAuthFlows::default()
+        }),
+        serde_json::json!({
+            "type": "oauth2",
+            "flows": {
+                "clientCredentials": {
+                    "tokenUrl": "https://auth.example.com/token",
+                    "scopes": {}
+                },
+                "authorizationCode": {
+                    "authorizationUrl": "https://auth.example.com/authorize",
+                    "tokenUrl": "https://auth.example.com/token",
+                    "scopes": { "read:users": "Read users" }
+                }
+            }
+        })
+    )]
+    fn security_scheme_helpers_serialize(
+        #[case] scheme: SecurityScheme,
+        #[case] expected: serde_json::Value,
+    ) {
+        assert_eq!(serde_json::to_value(scheme).unwrap(), expected);
+    }
+
+    #[test]
+    fn close_objects_closes_nested_objects_but_not_maps_or_all_of() {
+        let mut address = Schema::object();
+        address.properties.as_mut().unwrap().insert(
+            "city".to_string(),
+            SchemaRef::Inline(Box::new(Schema::string())),
+        );
+        let mut map = Schema::new(SchemaType::Object);
+        map.additional_properties = Some(serde_json::json!({ "type": "string" }));
+        let mut schema = Schema::object();
+        let properties = schema.properties.as_mut().unwrap();
+        properties.insert(
+            "addresses".to_string(),
+            SchemaRef::Inline(Box::new(Schema::array(SchemaRef::Inline(Box::new(
+                address,
+            ))))),
+        );
+        properties.insert("labels".to_string(), SchemaRef::Inline(Box::new(map)));
+        let mut composed = Schema {
+            all_of: Some(vec![SchemaRef::Inline(Box::new(Schema::object()))]),
+            ..Default::default()
+        };
+
+        schema.close_objects();
+        composed.close_objects();
+
+        let value = serde_json::to_value(&schema).unwrap();
+        assert_eq!(value["additionalProperties"], false);
+        assert_eq!(
+            value["properties"]["addresses"]["items"]["additionalProperties"],
+            false
+        );
+        assert_eq!(
+            value["properties"]["labels"]["additionalProperties"],
+            serde_json::json!({ "type": "string" })
+        );
+        let value = serde_json::to_value(&composed).unwrap();
+        assert!(value.get("additionalProperties").is_none());
+        assert!(value["allOf"][0].get("additionalProperties").is_none());
+    }
+
     #[test]
     fn array_helper_sets_type_and_items() {
         let item_schema = Schema::boolean();
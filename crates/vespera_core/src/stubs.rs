@@ -0,0 +1,793 @@
+//! Handler stub generation from an existing `OpenAPI` document.
+//!
+//! The reverse of what `vespera!` does: [`generate_stubs`] turns a published contract
+//! into Rust source laid out for vespera's file-based routing, so a team can adopt
+//! vespera without retyping their API by hand.
+//!
+//! - `models.rs` - one `#[derive(Schema)]` struct, enum or alias per `components/schemas` entry
+//! - `routes/mod.rs` - module declarations, plus routes that cannot live in a module file
+//! - `routes/{segment}.rs` - `#[vespera::route]` handlers grouped by first path segment
+//!
+//! The output is a starting point: handler bodies are `todo!()`, query parameters become a
+//! per-handler struct, header/cookie parameters and non-JSON/form bodies are left as
+//! `TODO` comments, and schemas without a direct Rust equivalent become `serde_json::Value`.
+//! Vespera maps `_` in route file names to `-` in paths, so path segments containing `_`
+//! are not reproduced exactly.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Write as _,
+};
+
+use crate::{
+    openapi::OpenApi,
+    route::{Operation, Parameter, ParameterLocation, ParameterRef},
+    schema::{Reference, Schema, SchemaRef, SchemaType},
+};
+
+/// Generate vespera source files for `doc`, keyed by path relative to `src/`.
+#[must_use]
+pub fn generate_stubs(doc: &OpenApi) -> BTreeMap<String, String> {
+    let mut files = BTreeMap::new();
+
+    if let Some(schemas) = doc
+        .components
+        .as_ref()
+        .and_then(|components| components.schemas.as_ref())
+        .filter(|schemas| !schemas.is_empty())
+    {
+        files.insert("models.rs".to_string(), generate_models(schemas));
+    }
+
+    // Module name (None = routes/mod.rs) -> handlers
+    let mut modules: BTreeMap<Option<String>, RouteFile> = BTreeMap::new();
+    for (path, path_item) in &doc.paths {
+        let (module, route_path) = split_route_path(path);
+        let file = modules.entry(module).or_default();
+        for method in path_item.methods() {
+            if let Some(operation) = path_item.operation(method) {
+                file.add_handler(
+                    &method.to_string().to_lowercase(),
+                    path,
+                    route_path,
+                    operation,
+                );
+            }
+        }
+    }
+
+    let mut root = modules.remove(&None).unwrap_or_default();
+    for (module, file) in modules {
+        let Some(module) = module else {
+            continue;
+        };
+        root.submodules.push(module.clone());
+        files.insert(format!("routes/{module}.rs"), file.render());
+    }
+    files.insert("routes/mod.rs".to_string(), root.render());
+    files
+}
+
+/// Route file under construction.
+#[derive(Default)]
+struct RouteFile {
+    submodules: Vec<String>,
+    handlers: Vec<String>,
+    function_names: BTreeSet<String>,
+    models: BTreeSet<String>,
+    axum_imports: BTreeSet<&'static str>,
+    uses_deserialize: bool,
+}
+
+impl RouteFile {
+    #[allow(clippy::too_many_lines)]
+    fn add_handler(
+        &mut self,
+        method: &str,
+        full_path: &str,
+        route_path: Option<&str>,
+        operation: &Operation,
+    ) {
+        let base_name = operation
+            .operation_id
+            .as_deref()
+            .map_or_else(|| default_function_name(method, full_path), field_ident);
+        let mut function_name = base_name.clone();
+        let mut n = 2;
+        while !self.function_names.insert(function_name.clone()) {
+            function_name = format!("{base_name}_{n}");
+            n += 1;
+        }
+
+        let parameters: Vec<&Parameter> = operation
+            .parameters
+            .iter()
+            .flatten()
+            .filter_map(ParameterRef::as_inline)
+            .collect();
+        let mut item = String::new();
+        let mut args = Vec::new();
+        let mut todos = Vec::new();
+
+        // Path parameters, in template order
+        let path_names = path_parameter_names(full_path);
+        if !path_names.is_empty() {
+            let mut idents = Vec::new();
+            let mut types = Vec::new();
+            for name in &path_names {
+                idents.push(field_ident(name));
+                types.push(
+                    parameters
+                        .iter()
+                        .find(|p| p.r#in == ParameterLocation::Path && p.name == *name)
+                        .and_then(|p| p.schema.as_ref())
+                        .map_or_else(|| "String".to_string(), |s| self.rust_type(s)),
+                );
+            }
+            self.axum_imports.insert("extract::Path");
+            if idents.len() == 1 {
+                args.push(format!("Path({}): Path<{}>", idents[0], types[0]));
+            } else {
+                args.push(format!(
+                    "Path(({})): Path<({})>",
+                    idents.join(", "),
+                    types.join(", ")
+                ));
+            }
+        }
+
+        // Query parameters become a struct
+        let query: Vec<&&Parameter> = parameters
+            .iter()
+            .filter(|p| p.r#in == ParameterLocation::Query)
+            .collect();
+        if !query.is_empty() {
+            let struct_name = format!("{}Query", type_name(&function_name));
+            let _ = writeln!(
+                item,
+                "#[derive(Debug, Deserialize, vespera::Schema)]\npub struct {struct_name} {{"
+            );
+            for parameter in query {
+                let ty = parameter
+                    .schema
+                    .as_ref()
+                    .map_or_else(|| "String".to_string(), |s| self.rust_type(s));
+                let ty = if parameter.required == Some(true) {
+                    ty
+                } else {
+                    optional(ty)
+                };
+                item.push_str(&doc_comment(parameter.description.as_deref(), "    "));
+                item.push_str(&field_line(&parameter.name, &ty));
+            }
+            item.push_str("}\n\n");
+            self.uses_deserialize = true;
+            self.axum_imports.insert("extract::Query");
+            args.push(format!("Query(query): Query<{struct_name}>"));
+        }
+
+        for parameter in parameters.iter().filter(|p| {
+            matches!(
+                p.r#in,
+                ParameterLocation::Header | ParameterLocation::Cookie
+            )
+        }) {
+            let location = if parameter.r#in == ParameterLocation::Header {
+                "header"
+            } else {
+                "cookie"
+            };
+            todos.push(format!("read {location} `{}`", parameter.name));
+        }
+
+        // Request body
+        if let Some(request_body) = &operation.request_body {
+            if let Some(media) = request_body.content.get("application/json") {
+                let ty = media
+                    .schema
+                    .as_ref()
+                    .map_or_else(|| "serde_json::Value".to_string(), |s| self.rust_type(s));
+                self.axum_imports.insert("Json");
+                args.push(format!("Json(body): Json<{ty}>"));
+            } else if let Some(media) = request_body
+                .content
+                .get("application/x-www-form-urlencoded")
+            {
+                let ty = media
+                    .schema
+                    .as_ref()
+                    .map_or_else(|| "serde_json::Value".to_string(), |s| self.rust_type(s));
+                self.axum_imports.insert("Form");
+                args.push(format!("Form(body): Form<{ty}>"));
+            } else if let Some(content_type) = request_body.content.keys().next() {
+                todos.push(format!("extract `{content_type}` request body"));
+            }
+        }
+
+        // Response: first 2xx
+        let success = operation
+            .responses
+            .iter()
+            .find(|(status, _)| status.starts_with('2'));
+        let json_schema = success
+            .and_then(|(_, response)| response.content.as_ref())
+            .and_then(|content| content.get("application/json"))
+            .and_then(|media| media.schema.as_ref());
+        let return_type = if let Some(schema) = json_schema {
+            self.axum_imports.insert("Json");
+            format!("Json<{}>", self.rust_type(schema))
+        } else {
+            self.axum_imports.insert("http::StatusCode");
+            "StatusCode".to_string()
+        };
+
+        // Attribute
+        let mut attr_args = vec![method.to_string()];
+        if let Some(route_path) = route_path {
+            attr_args.push(format!("path = {route_path:?}"));
+        }
+        if let Some(tags) = operation.tags.as_ref().filter(|tags| !tags.is_empty()) {
+            let tags: Vec<String> = tags.iter().map(|tag| format!("{tag:?}")).collect();
+            attr_args.push(format!("tags = [{}]", tags.join(", ")));
+        }
+
+        let doc = match (&operation.summary, &operation.description) {
+            (Some(summary), Some(description)) => Some(format!("{summary}\n\n{description}")),
+            (summary, description) => summary.clone().or_else(|| description.clone()),
+        };
+        item.push_str(&doc_comment(doc.as_deref(), ""));
+        let _ = writeln!(item, "#[vespera::route({})]", attr_args.join(", "));
+        let _ = writeln!(
+            item,
+            "pub async fn {function_name}({}) -> {return_type} {{",
+            args.join(", ")
+        );
+        for todo in todos {
+            let _ = writeln!(item, "    // TODO: {todo}");
+        }
+        item.push_str("    todo!()\n}\n");
+        self.handlers.push(item);
+    }
+
+    fn rust_type(&mut self, schema: &SchemaRef) -> String {
+        rust_type(schema, &mut self.models)
+    }
+
+    fn render(&self) -> String {
+        let mut out = String::new();
+        for module in &self.submodules {
+            let _ = writeln!(out, "pub mod {module};");
+        }
+        if !self.submodules.is_empty() && !self.handlers.is_empty() {
+            out.push('\n');
+        }
+        if self.uses_deserialize {
+            out.push_str("use serde::Deserialize;\n");
+        }
+        if !self.axum_imports.is_empty() {
+            let imports: Vec<&str> = self.axum_imports.iter().copied().collect();
+            let _ = writeln!(out, "use vespera::axum::{};", use_tree(&imports));
+        }
+        if !self.models.is_empty() {
+            let models: Vec<&str> = self.models.iter().map(String::as_str).collect();
+            let _ = writeln!(out, "\nuse crate::models::{};", use_tree(&models));
+        }
+        for handler in &self.handlers {
+            out.push('\n');
+            out.push_str(handler);
+        }
+        out
+    }
+}
+
+/// `Name` or `{A, B}`
+fn use_tree(names: &[&str]) -> String {
+    match names {
+        [name] => (*name).to_string(),
+        _ => format!("{{{}}}", names.join(", ")),
+    }
+}
+
+/// Split `/users/{id}` into the `users` module and the `/{id}` route path.
+///
+/// Paths whose first segment is a parameter or not a valid module name stay in
+/// `routes/mod.rs` with their full path. `None` means the module's own path.
+fn split_route_path(path: &str) -> (Option<String>, Option<&str>) {
+    let trimmed = path.trim_start_matches('/');
+    let (first, rest) = trimmed.split_once('/').unwrap_or((trimmed, ""));
+    let module = first.replace('-', "_");
+    let is_module = !first.is_empty()
+        && !first.contains('_')
+        && module.starts_with(|c: char| c.is_ascii_lowercase())
+        && module
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+        && !is_keyword(&module);
+    if !is_module {
+        return (None, (path != "/").then_some(path));
+    }
+    let route_path = (!rest.is_empty()).then(|| &path[path.len() - rest.len() - 1..]);
+    (Some(module), route_path)
+}
+
+/// `{name}` segments of a path template, in order.
+fn path_parameter_names(path: &str) -> Vec<String> {
+    path.split('/')
+        .filter_map(|segment| segment.strip_prefix('{')?.strip_suffix('}'))
+        .map(ToString::to_string)
+        .collect()
+}
+
+/// `get` + `/users/{id}/posts` -> `get_users_by_id_posts`
+fn default_function_name(method: &str, path: &str) -> String {
+    let mut name = method.to_string();
+    for segment in path.split('/').filter(|segment| !segment.is_empty()) {
+        name.push('_');
+        match segment.strip_prefix('{').and_then(|s| s.strip_suffix('}')) {
+            Some(parameter) => {
+                name.push_str("by_");
+                name.push_str(&snake_case(parameter));
+            }
+            None => name.push_str(&snake_case(segment)),
+        }
+    }
+    name
+}
+
+fn generate_models(schemas: &BTreeMap<String, Schema>) -> String {
+    let mut out = String::from("use serde::{Deserialize, Serialize};\n");
+    for (name, schema) in schemas {
+        let mut models = BTreeSet::new();
+        out.push('\n');
+        out.push_str(&doc_comment(schema.description.as_deref(), ""));
+        out.push_str(&model_item(&type_name(name), schema, &mut models));
+    }
+    out
+}
+
+fn model_item(name: &str, schema: &Schema, models: &mut BTreeSet<String>) -> String {
+    let string_variants: Option<Vec<&str>> = schema
+        .r#enum
+        .as_ref()
+        .and_then(|values| values.iter().map(serde_json::Value::as_str).collect());
+    if let Some(variants) = string_variants.filter(|variants| !variants.is_empty()) {
+        let mut out = format!(
+            "#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, vespera::Schema)]\npub enum {name} {{\n"
+        );
+        let mut seen = BTreeSet::new();
+        for value in variants {
+            let base = type_name(value);
+            let mut variant = base.clone();
+            let mut n = 2;
+            while !seen.insert(variant.clone()) {
+                variant = format!("{base}{n}");
+                n += 1;
+            }
+            if variant != value {
+                let _ = writeln!(out, "    #[serde(rename = {value:?})]");
+            }
+            let _ = writeln!(out, "    {variant},");
+        }
+        out.push_str("}\n");
+        return out;
+    }
+
+    if let Some(properties) = &schema.properties {
+        let required = schema.required.as_deref().unwrap_or_default();
+        let mut out = format!(
+            "#[derive(Debug, Clone, Serialize, Deserialize, vespera::Schema)]\npub struct {name} {{\n"
+        );
+        for (property, property_schema) in properties {
+            let ty = rust_type(property_schema, models);
+            let ty = if required.contains(property) {
+                ty
+            } else {
+                optional(ty)
+            };
+            if let SchemaRef::Inline(inline) = property_schema {
+                out.push_str(&doc_comment(inline.description.as_deref(), "    "));
+            }
+            out.push_str(&field_line(property, &ty));
+        }
+        out.push_str("}\n");
+        return out;
+    }
+
+    format!("pub type {name} = {};\n", inline_type(schema, models))
+}
+
+/// Rust type for a schema; component references are recorded in `models`.
+fn rust_type(schema: &SchemaRef, models: &mut BTreeSet<String>) -> String {
+    match schema {
+        SchemaRef::Ref(reference) => ref_type(&reference.ref_path, models),
+        SchemaRef::Inline(schema) => inline_type(schema, models),
+    }
+}
+
+fn inline_type(schema: &Schema, models: &mut BTreeSet<String>) -> String {
+    let ty = if let Some(ref_path) = &schema.ref_path {
+        ref_type(ref_path, models)
+    } else {
+        match schema.schema_type {
+            Some(SchemaType::String) => "String".to_string(),
+            Some(SchemaType::Integer) => match schema.format.as_deref() {
+                Some("int32") => "i32".to_string(),
+                _ => "i64".to_string(),
+            },
+            Some(SchemaType::Number) => match schema.format.as_deref() {
+                Some("float") => "f32".to_string(),
+                _ => "f64".to_string(),
+            },
+            Some(SchemaType::Boolean) => "bool".to_string(),
+            Some(SchemaType::Array) => schema.items.as_ref().map_or_else(
+                || "Vec<serde_json::Value>".to_string(),
+                |items| format!("Vec<{}>", rust_type(items, models)),
+            ),
+            Some(SchemaType::Object) if schema.properties.is_none() => {
+                match &schema.additional_properties {
+                    Some(value @ serde_json::Value::Object(_)) => {
+                        serde_json::from_value::<SchemaRef>(value.clone()).map_or_else(
+                            |_| "serde_json::Value".to_string(),
+                            |values| {
+                                format!(
+                                    "std::collections::HashMap<String, {}>",
+                                    rust_type(&values, models)
+                                )
+                            },
+                        )
+                    }
+                    Some(serde_json::Value::Bool(true)) => {
+                        "std::collections::HashMap<String, serde_json::Value>".to_string()
+                    }
+                    _ => "serde_json::Value".to_string(),
+                }
+            }
+            _ => "serde_json::Value".to_string(),
+        }
+    };
+    if schema.nullable == Some(true) {
+        optional(ty)
+    } else {
+        ty
+    }
+}
+
+fn ref_type(ref_path: &str, models: &mut BTreeSet<String>) -> String {
+    Reference::schema_name(ref_path).map_or_else(
+        || "serde_json::Value".to_string(),
+        |name| {
+            let name = type_name(name);
+            models.insert(name.clone());
+            name
+        },
+    )
+}
+
+fn optional(ty: String) -> String {
+    if ty.starts_with("Option<") {
+        ty
+    } else {
+        format!("Option<{ty}>")
+    }
+}
+
+/// `    pub field: Type,` with a serde rename when the Rust name differs.
+fn field_line(name: &str, ty: &str) -> String {
+    let ident = field_ident(name);
+    let rename = if ident.trim_start_matches("r#") == name {
+        String::new()
+    } else {
+        format!("    #[serde(rename = {name:?})]\n")
+    };
+    format!("{rename}    pub {ident}: {ty},\n")
+}
+
+fn doc_comment(text: Option<&str>, indent: &str) -> String {
+    text.map(str::trim)
+        .filter(|text| !text.is_empty())
+        .map(|text| {
+            text.lines()
+                .map(|line| {
+                    if line.trim().is_empty() {
+                        format!("{indent}///\n")
+                    } else {
+                        format!("{indent}/// {}\n", line.trim_end())
+                    }
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Words of an identifier: `createdAt`, `created_at` and `Created-At` all give `created`, `at`.
+fn words(value: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut previous_lower = false;
+    for c in value.chars() {
+        if !c.is_ascii_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            previous_lower = false;
+            continue;
+        }
+        if c.is_ascii_uppercase() && previous_lower && !current.is_empty() {
+            words.push(std::mem::take(&mut current));
+        }
+        previous_lower = c.is_ascii_lowercase() || c.is_ascii_digit();
+        current.push(c.to_ascii_lowercase());
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+fn snake_case(value: &str) -> String {
+    let name = words(value).join("_");
+    if name.is_empty() {
+        "field".to_string()
+    } else if name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{name}")
+    } else {
+        name
+    }
+}
+
+fn type_name(value: &str) -> String {
+    let name: String = words(value)
+        .iter()
+        .map(|word| {
+            let mut chars = word.chars();
+            chars.next().map_or_else(String::new, |first| {
+                first.to_ascii_uppercase().to_string() + chars.as_str()
+            })
+        })
+        .collect();
+    if name.is_empty() {
+        "Empty".to_string()
+    } else if name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("V{name}")
+    } else {
+        name
+    }
+}
+
+fn field_ident(name: &str) -> String {
+    let ident = snake_case(name);
+    match ident.as_str() {
+        "self" | "super" | "crate" => format!("{ident}_"),
+        _ if is_keyword(&ident) => format!("r#{ident}"),
+        _ => ident,
+    }
+}
+
+fn is_keyword(ident: &str) -> bool {
+    matches!(
+        ident,
+        "as" | "async"
+            | "await"
+            | "break"
+            | "const"
+            | "continue"
+            | "crate"
+            | "dyn"
+            | "else"
+            | "enum"
+            | "extern"
+            | "false"
+            | "fn"
+            | "for"
+            | "gen"
+            | "if"
+            | "impl"
+            | "in"
+            | "let"
+            | "loop"
+            | "match"
+            | "mod"
+            | "move"
+            | "mut"
+            | "pub"
+            | "ref"
+            | "return"
+            | "self"
+            | "static"
+            | "struct"
+            | "super"
+            | "trait"
+            | "true"
+            | "try"
+            | "type"
+            | "unsafe"
+            | "use"
+            | "where"
+            | "while"
+            | "yield"
+            | "abstract"
+            | "become"
+            | "box"
+            | "do"
+            | "final"
+            | "macro"
+            | "override"
+            | "priv"
+            | "typeof"
+            | "unsized"
+            | "virtual"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    const PETSTORE: &str = r##"{
+        "openapi": "3.1.0",
+        "info": { "title": "Pets", "version": "1.0.0" },
+        "paths": {
+            "/pets": {
+                "get": {
+                    "operationId": "listPets",
+                    "summary": "List pets",
+                    "tags": ["pets"],
+                    "parameters": [
+                        { "name": "limit", "in": "query", "schema": { "type": "integer", "format": "int32" } },
+                        { "name": "X-Request-Id", "in": "header", "schema": { "type": "string" } }
+                    ],
+                    "responses": {
+                        "200": {
+                            "description": "OK",
+                            "content": { "application/json": { "schema": { "type": "array", "items": { "$ref": "#/components/schemas/Pet" } } } }
+                        }
+                    }
+                },
+                "post": {
+                    "tags": ["pets"],
+                    "requestBody": {
+                        "content": { "application/json": { "schema": { "$ref": "#/components/schemas/NewPet" } } }
+                    },
+                    "responses": { "204": { "description": "Created" } }
+                }
+            },
+            "/pets/{petId}": {
+                "get": {
+                    "parameters": [
+                        { "name": "petId", "in": "path", "required": true, "schema": { "type": "integer" } }
+                    ],
+                    "responses": {
+                        "200": {
+                            "description": "OK",
+                            "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Pet" } } }
+                        }
+                    }
+                }
+            },
+            "/health": {
+                "get": { "responses": { "200": { "description": "OK" } } }
+            }
+        },
+        "components": {
+            "schemas": {
+                "Pet": {
+                    "type": "object",
+                    "description": "A pet",
+                    "required": ["id", "name"],
+                    "properties": {
+                        "id": { "type": "integer", "format": "int64" },
+                        "name": { "type": "string" },
+                        "type": { "$ref": "#/components/schemas/PetKind" },
+                        "createdAt": { "type": "string", "format": "date-time" }
+                    }
+                },
+                "NewPet": {
+                    "type": "object",
+                    "required": ["name"],
+                    "properties": { "name": { "type": "string" } }
+                },
+                "PetKind": { "type": "string", "enum": ["dog", "cat", "guinea-pig"] },
+                "Labels": { "type": "object", "additionalProperties": { "type": "string" } }
+            }
+        }
+    }"##;
+
+    fn stubs() -> BTreeMap<String, String> {
+        let doc: OpenApi = serde_json::from_str(PETSTORE).unwrap();
+        generate_stubs(&doc)
+    }
+
+    #[test]
+    fn test_generate_stubs_files() {
+        let stubs = stubs();
+        assert_eq!(
+            stubs.keys().collect::<Vec<_>>(),
+            vec![
+                "models.rs",
+                "routes/health.rs",
+                "routes/mod.rs",
+                "routes/pets.rs"
+            ]
+        );
+        assert_eq!(stubs["routes/mod.rs"], "pub mod health;\npub mod pets;\n");
+        assert_eq!(
+            stubs["routes/health.rs"],
+            "use vespera::axum::http::StatusCode;\n\n#[vespera::route(get)]\npub async fn get_health() -> StatusCode {\n    todo!()\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_generate_stubs_models() {
+        let stubs = stubs();
+        let models = &stubs["models.rs"];
+        assert!(models.contains(
+            "/// A pet\n#[derive(Debug, Clone, Serialize, Deserialize, vespera::Schema)]\npub struct Pet {\n"
+        ));
+        assert!(models.contains(
+            "    #[serde(rename = \"createdAt\")]\n    pub created_at: Option<String>,\n"
+        ));
+        assert!(models.contains("    pub id: i64,\n"));
+        assert!(models.contains("    pub r#type: Option<PetKind>,\n"));
+        assert!(models.contains("    #[serde(rename = \"guinea-pig\")]\n    GuineaPig,\n"));
+        assert!(models.contains("pub type Labels = std::collections::HashMap<String, String>;\n"));
+    }
+
+    #[test]
+    fn test_generate_stubs_handlers() {
+        let stubs = stubs();
+        let pets = &stubs["routes/pets.rs"];
+        assert!(pets.starts_with(
+            "use serde::Deserialize;\nuse vespera::axum::{Json, extract::Path, extract::Query, http::StatusCode};\n\nuse crate::models::{NewPet, Pet};\n"
+        ));
+        assert!(pets.contains(
+            "#[derive(Debug, Deserialize, vespera::Schema)]\npub struct ListPetsQuery {\n    pub limit: Option<i32>,\n}\n"
+        ));
+        assert!(pets.contains(
+            "/// List pets\n#[vespera::route(get, tags = [\"pets\"])]\npub async fn list_pets(Query(query): Query<ListPetsQuery>) -> Json<Vec<Pet>> {\n    // TODO: read header `X-Request-Id`\n    todo!()\n}\n"
+        ));
+        assert!(pets.contains(
+            "#[vespera::route(post, tags = [\"pets\"])]\npub async fn post_pets(Json(body): Json<NewPet>) -> StatusCode {\n"
+        ));
+        assert!(pets.contains(
+            "#[vespera::route(get, path = \"/{petId}\")]\npub async fn get_pets_by_pet_id(Path(pet_id): Path<i64>) -> Json<Pet> {\n"
+        ));
+    }
+
+    #[rstest]
+    #[case("/users/{id}", Some("users"), Some("/{id}"))]
+    #[case("/users", Some("users"), None)]
+    #[case("/user-profiles/me", Some("user_profiles"), Some("/me"))]
+    #[case("/{tenant}/users", None, Some("/{tenant}/users"))]
+    #[case("/", None, None)]
+    #[case("/type", None, Some("/type"))]
+    #[case("/v1.0/users", None, Some("/v1.0/users"))]
+    fn test_split_route_path(
+        #[case] path: &str,
+        #[case] module: Option<&str>,
+        #[case] route_path: Option<&str>,
+    ) {
+        assert_eq!(
+            split_route_path(path),
+            (module.map(ToString::to_string), route_path)
+        );
+    }
+
+    #[rstest]
+    #[case("createdAt", "created_at")]
+    #[case("X-Request-Id", "x_request_id")]
+    #[case("HTTPStatus", "httpstatus")]
+    #[case("2fa", "_2fa")]
+    fn test_snake_case(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(snake_case(input), expected);
+    }
+
+    #[rstest]
+    #[case("type", "r#type")]
+    #[case("self", "self_")]
+    #[case("userId", "user_id")]
+    fn test_field_ident(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(field_ident(input), expected);
+    }
+}
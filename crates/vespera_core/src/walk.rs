@@ -0,0 +1,249 @@
+//! Typed traversal of the schemas and `$ref`s of a document.
+//!
+//! Document-wide rewrites (`$ref` renames, format patterns, property sorting) walk the
+//! typed tree instead of round-tripping through `serde_json::Value`, which would lose
+//! the insertion order of [`Properties`](crate::schema::Properties) and
+//! [`Paths`](crate::openapi::Paths) unless `serde_json/preserve_order` is enabled.
+
+use std::collections::{BTreeMap, HashMap};
+use std::hash::BuildHasher;
+
+use indexmap::IndexMap;
+
+use crate::openapi::OpenApi;
+use crate::route::{
+    ExampleRef, Header, HeaderRef, MediaType, Operation, Parameter, ParameterRef, PathItem,
+    RequestBody, Response,
+};
+use crate::schema::{Components, Schema, SchemaRef};
+
+/// A schema or `$ref` reached while walking a document.
+pub enum Node<'a> {
+    /// An inline schema, visited before the schemas nested in it
+    Schema(&'a mut Schema),
+    /// The target of a `$ref` (`#/components/schemas/User`)
+    Ref(&'a mut String),
+}
+
+/// Depth-first traversal of the schemas and `$ref`s nested in a document node.
+pub trait Walk {
+    fn walk(&mut self, visit: &mut dyn FnMut(Node<'_>));
+}
+
+/// Point every `$ref` under `node` found in `renames` at its new target.
+pub fn rename_refs<S: BuildHasher>(node: &mut impl Walk, renames: &HashMap<String, String, S>) {
+    node.walk(&mut |node| {
+        if let Node::Ref(reference) = node
+            && let Some(new) = renames.get(reference.as_str())
+        {
+            reference.clone_from(new);
+        }
+    });
+}
+
+/// Every `$ref` target under `node`.
+pub fn refs(node: &mut impl Walk) -> Vec<String> {
+    let mut refs = Vec::new();
+    node.walk(&mut |node| {
+        if let Node::Ref(reference) = node {
+            refs.push(reference.clone());
+        }
+    });
+    refs
+}
+
+/// `$ref`s inside schemas kept as plain JSON (`additionalProperties`).
+fn walk_json_refs(value: &mut serde_json::Value, visit: &mut dyn FnMut(Node<'_>)) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, value) in map {
+                match value {
+                    serde_json::Value::String(reference) if key == "$ref" => {
+                        visit(Node::Ref(reference));
+                    }
+                    _ => walk_json_refs(value, visit),
+                }
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for item in items {
+                walk_json_refs(item, visit);
+            }
+        }
+        _ => {}
+    }
+}
+
+impl<T: Walk + ?Sized> Walk for &mut T {
+    fn walk(&mut self, visit: &mut dyn FnMut(Node<'_>)) {
+        (**self).walk(visit);
+    }
+}
+
+impl<T: Walk + ?Sized> Walk for Box<T> {
+    fn walk(&mut self, visit: &mut dyn FnMut(Node<'_>)) {
+        (**self).walk(visit);
+    }
+}
+
+impl<T: Walk> Walk for Option<T> {
+    fn walk(&mut self, visit: &mut dyn FnMut(Node<'_>)) {
+        if let Some(node) = self {
+            node.walk(visit);
+        }
+    }
+}
+
+impl<T: Walk> Walk for Vec<T> {
+    fn walk(&mut self, visit: &mut dyn FnMut(Node<'_>)) {
+        for node in self {
+            node.walk(visit);
+        }
+    }
+}
+
+impl<K, T: Walk> Walk for BTreeMap<K, T> {
+    fn walk(&mut self, visit: &mut dyn FnMut(Node<'_>)) {
+        for node in self.values_mut() {
+            node.walk(visit);
+        }
+    }
+}
+
+impl<K, T: Walk, S> Walk for HashMap<K, T, S> {
+    fn walk(&mut self, visit: &mut dyn FnMut(Node<'_>)) {
+        for node in self.values_mut() {
+            node.walk(visit);
+        }
+    }
+}
+
+impl<K, T: Walk, S> Walk for IndexMap<K, T, S> {
+    fn walk(&mut self, visit: &mut dyn FnMut(Node<'_>)) {
+        for node in self.values_mut() {
+            node.walk(visit);
+        }
+    }
+}
+
+impl Walk for Schema {
+    fn walk(&mut self, visit: &mut dyn FnMut(Node<'_>)) {
+        visit(Node::Schema(self));
+        if let Some(reference) = self.ref_path.as_mut() {
+            visit(Node::Ref(reference));
+        }
+        self.items.walk(visit);
+        self.prefix_items.walk(visit);
+        self.properties.walk(visit);
+        if let Some(additional) = self.additional_properties.as_mut() {
+            walk_json_refs(additional, visit);
+        }
+        self.property_names.walk(visit);
+        self.all_of.walk(visit);
+        self.any_of.walk(visit);
+        self.one_of.walk(visit);
+        self.not.walk(visit);
+        self.defs.walk(visit);
+    }
+}
+
+impl Walk for SchemaRef {
+    fn walk(&mut self, visit: &mut dyn FnMut(Node<'_>)) {
+        match self {
+            Self::Ref(reference) => visit(Node::Ref(&mut reference.ref_path)),
+            Self::Inline(schema) => schema.walk(visit),
+        }
+    }
+}
+
+impl Walk for Parameter {
+    fn walk(&mut self, visit: &mut dyn FnMut(Node<'_>)) {
+        self.schema.walk(visit);
+    }
+}
+
+impl Walk for ParameterRef {
+    fn walk(&mut self, visit: &mut dyn FnMut(Node<'_>)) {
+        match self {
+            Self::Ref(reference) => visit(Node::Ref(&mut reference.ref_path)),
+            Self::Inline(parameter) => parameter.walk(visit),
+        }
+    }
+}
+
+impl Walk for ExampleRef {
+    fn walk(&mut self, visit: &mut dyn FnMut(Node<'_>)) {
+        if let Self::Ref(reference) = self {
+            visit(Node::Ref(&mut reference.ref_path));
+        }
+    }
+}
+
+impl Walk for MediaType {
+    fn walk(&mut self, visit: &mut dyn FnMut(Node<'_>)) {
+        self.schema.walk(visit);
+        self.examples.walk(visit);
+    }
+}
+
+impl Walk for RequestBody {
+    fn walk(&mut self, visit: &mut dyn FnMut(Node<'_>)) {
+        self.content.walk(visit);
+    }
+}
+
+impl Walk for Header {
+    fn walk(&mut self, visit: &mut dyn FnMut(Node<'_>)) {
+        self.schema.walk(visit);
+    }
+}
+
+impl Walk for HeaderRef {
+    fn walk(&mut self, visit: &mut dyn FnMut(Node<'_>)) {
+        match self {
+            Self::Ref(reference) => visit(Node::Ref(&mut reference.ref_path)),
+            Self::Inline(header) => header.walk(visit),
+        }
+    }
+}
+
+impl Walk for Response {
+    fn walk(&mut self, visit: &mut dyn FnMut(Node<'_>)) {
+        self.headers.walk(visit);
+        self.content.walk(visit);
+    }
+}
+
+impl Walk for Operation {
+    fn walk(&mut self, visit: &mut dyn FnMut(Node<'_>)) {
+        self.parameters.walk(visit);
+        self.request_body.walk(visit);
+        self.responses.walk(visit);
+    }
+}
+
+impl Walk for PathItem {
+    fn walk(&mut self, visit: &mut dyn FnMut(Node<'_>)) {
+        for operation in self.operations_mut() {
+            operation.walk(visit);
+        }
+        self.parameters.walk(visit);
+    }
+}
+
+impl Walk for Components {
+    fn walk(&mut self, visit: &mut dyn FnMut(Node<'_>)) {
+        self.schemas.walk(visit);
+        self.responses.walk(visit);
+        self.parameters.walk(visit);
+        self.request_bodies.walk(visit);
+        self.headers.walk(visit);
+    }
+}
+
+impl Walk for OpenApi {
+    fn walk(&mut self, visit: &mut dyn FnMut(Node<'_>)) {
+        self.paths.walk(visit);
+        self.components.walk(visit);
+    }
+}
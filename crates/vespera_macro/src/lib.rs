@@ -1,82 +1,39 @@
-//! Vespera macro implementation crate.
+//! Vespera proc-macros.
 //!
 //! This crate contains all the proc-macros for Vespera:
 //! - `#[vespera::route(...)]` - Mark a function as a route handler
+//! - `defaults!(...)` - Declare route defaults for a module
 //! - `#[derive(Schema)]` - Register a type for `OpenAPI` schema generation
 //! - `schema!(...)` - Get `OpenAPI` schema at compile time
 //! - `vespera!(...)` - Generate Axum router with `OpenAPI`
 //! - `export_app!(...)` - Export router for merging
 //!
-//! # Architecture
-//!
-//! ```text
-//! ┌─────────────────────────────────────────────────────────────────┐
-//! │ Compile-time (vespera! macro)                                    │
-//! ├─────────────────────────────────────────────────────────────────┤
-//! │ 1. Scan src/routes/ for .rs files              [collector]       │
-//! │ 2. Parse #[route] attributes                   [args, route]     │
-//! │ 3. Extract handler signatures                  [parser]          │
-//! │ 4. Convert Rust types → JSON Schema            [parser/schema]   │
-//! │ 5. Build OpenAPI document                      [openapi_gen]     │
-//! │ 6. Write openapi.json to disk                  [vespera_impl]    │
-//! │ 7. Generate Axum Router TokenStream            [router_codegen]  │
-//! │ 8. Inject Swagger/ReDoc HTML routes           [router_codegen]  │
-//! └─────────────────────────────────────────────────────────────────┘
-//!
-//! # Module Organization
-//!
-//! - `args` - Parse `#[route(...)]` attribute arguments
-//! - `collector` - Filesystem scanning and route discovery
-//! - `error` - Unified error handling
-//! - `http` - HTTP method constants and validation
-//! - `metadata` - Type definitions for collected metadata
-//! - `method` - HTTP method token stream generation
-//! - `openapi_generator` - OpenAPI spec assembly
-//! - `parser` - Type extraction and schema generation
-//! - `route` - Route information structures
-//! - `route_impl` - Route attribute macro implementation
-//! - `router_codegen` - Router and macro input parsing
-//! - `schema_impl` - Schema derive macro implementation
-//! - `schema_macro` - `schema_type!` macro implementation
-//! - `vespera_impl` - Main macro orchestration
+//! The implementation lives in `vespera_scanner`, which the development spec
+//! reload also runs at runtime; the macros here only convert token streams.
 
-mod args;
-mod collector;
-mod cron_impl;
-mod error;
-mod file_utils;
-mod http;
-mod metadata;
-mod method;
-mod openapi_generator;
-
-mod multipart_impl;
-mod parser;
-mod route;
-mod route_impl;
-mod router_codegen;
-mod schema_impl;
-mod schema_macro;
-mod vespera_impl;
-
-pub(crate) use cron_impl::CRON_STORAGE;
 use proc_macro::TokenStream;
-pub(crate) use route_impl::ROUTE_STORAGE;
-pub(crate) use schema_impl::SCHEMA_STORAGE;
-
-use crate::{
-    router_codegen::{AutoRouterInput, ExportAppInput, process_vespera_input},
-    vespera_impl::{process_export_app, process_vespera_macro},
-};
 
 /// route attribute macro
 #[cfg(not(tarpaulin_include))]
 #[proc_macro_attribute]
 pub fn route(attr: TokenStream, item: TokenStream) -> TokenStream {
-    match route_impl::process_route_attribute(attr.into(), item.into()) {
-        Ok(tokens) => tokens.into(),
-        Err(e) => e.to_compile_error().into(),
-    }
+    vespera_scanner::route(attr.into(), item.into()).into()
+}
+
+/// Declare route defaults for the current module.
+///
+/// Merged by `vespera!()` into every route in this module and its submodules.
+/// Expands to nothing; arguments are only validated here.
+///
+/// # Example
+/// ```ignore
+/// // src/routes/admin/mod.rs
+/// vespera::defaults!(tags = ["admin"], security = ["bearer"], error_status = [500]);
+/// ```
+#[cfg(not(tarpaulin_include))]
+#[proc_macro]
+pub fn defaults(input: TokenStream) -> TokenStream {
+    vespera_scanner::defaults(input.into()).into()
 }
 
 /// cron attribute macro
@@ -93,26 +50,18 @@ pub fn route(attr: TokenStream, item: TokenStream) -> TokenStream {
 #[cfg(not(tarpaulin_include))]
 #[proc_macro_attribute]
 pub fn cron(attr: TokenStream, item: TokenStream) -> TokenStream {
-    match cron_impl::process_cron_attribute(attr.into(), item.into()) {
-        Ok(tokens) => tokens.into(),
-        Err(e) => e.to_compile_error().into(),
-    }
+    vespera_scanner::cron(attr.into(), item.into()).into()
 }
 
 /// Derive macro for Schema
 ///
-/// Supports `#[schema(name = "CustomName")]` attribute to set custom `OpenAPI` schema name.
+/// Supports `#[schema(name = "CustomName")]` (alias `rename`) to set a custom `OpenAPI` schema name,
+/// and `#[schema(rename_all = "camelCase")]` to derive it from the type name.
+/// `#[schema(remote = "other_crate::Money")]` registers a mirror struct under the remote type's name.
 #[cfg(not(tarpaulin_include))]
 #[proc_macro_derive(Schema, attributes(schema, serde))]
 pub fn derive_schema(input: TokenStream) -> TokenStream {
-    let input = syn::parse_macro_input!(input as syn::DeriveInput);
-    let (metadata, expanded) = schema_impl::process_derive_schema(&input);
-    let name = metadata.name.clone();
-    SCHEMA_STORAGE
-        .lock()
-        .unwrap_or_else(std::sync::PoisonError::into_inner)
-        .insert(name, metadata);
-    TokenStream::from(expanded)
+    vespera_scanner::derive_schema(input.into()).into()
 }
 
 /// Derive macro for `Multipart` with serde attribute support.
@@ -136,8 +85,7 @@ pub fn derive_schema(input: TokenStream) -> TokenStream {
 #[cfg(not(tarpaulin_include))]
 #[proc_macro_derive(Multipart, attributes(serde, form_data, try_from_multipart))]
 pub fn derive_multipart(input: TokenStream) -> TokenStream {
-    let input = syn::parse_macro_input!(input as syn::DeriveInput);
-    TokenStream::from(multipart_impl::process_derive(&input))
+    vespera_scanner::derive_multipart(input.into()).into()
 }
 
 /// Generate an `OpenAPI` Schema from a type with optional field filtering.
@@ -188,17 +136,7 @@ pub fn derive_multipart(input: TokenStream) -> TokenStream {
 #[cfg(not(tarpaulin_include))]
 #[proc_macro]
 pub fn schema(input: TokenStream) -> TokenStream {
-    let input = syn::parse_macro_input!(input as schema_macro::SchemaInput);
-
-    // Get stored schemas
-    let storage = SCHEMA_STORAGE
-        .lock()
-        .unwrap_or_else(std::sync::PoisonError::into_inner);
-
-    match schema_macro::generate_schema_code(&input, &storage) {
-        Ok(tokens) => TokenStream::from(tokens),
-        Err(e) => e.to_compile_error().into(),
-    }
+    vespera_scanner::schema(input.into()).into()
 }
 
 /// Generate a new struct type derived from an existing type with field filtering.
@@ -228,6 +166,10 @@ pub fn schema(input: TokenStream) -> TokenStream {
 /// - `clone = bool`: Whether to derive Clone (default: true)
 /// - `partial`: Make all fields `Option<T>` (fields already `Option<T>` are unchanged)
 /// - `partial = [...]`: Make only listed fields `Option<T>`
+/// - `filter`: Generate a filter/sort query struct instead (eq/lt/gt/contains per column,
+///   `sort_by`/`sort_order`, and `apply_to(select)` for `SeaORM` models)
+/// - `bulk`: For `SeaORM` models, implement `IntoActiveModel` and add
+///   `bulk_create(db, items)` / `bulk_update(db, items)` returning `vespera::bulk::BulkResult`
 ///
 /// Note: `omit` and `pick` cannot be used together.
 ///
@@ -258,47 +200,13 @@ pub fn schema(input: TokenStream) -> TokenStream {
 #[cfg(not(tarpaulin_include))]
 #[proc_macro]
 pub fn schema_type(input: TokenStream) -> TokenStream {
-    let input = syn::parse_macro_input!(input as schema_macro::SchemaTypeInput);
-
-    // Get stored schemas and generate code
-    let (tokens, generated_metadata) = {
-        let storage = SCHEMA_STORAGE
-            .lock()
-            .unwrap_or_else(std::sync::PoisonError::into_inner);
-        match schema_macro::generate_schema_type_code(&input, &storage) {
-            Ok(result) => result,
-            Err(e) => return e.to_compile_error().into(),
-        }
-    };
-
-    // If custom name is provided, register the schema directly
-    // This ensures it appears in OpenAPI even when `ignore` is set
-    if let Some(metadata) = generated_metadata {
-        let name = metadata.name.clone();
-        SCHEMA_STORAGE
-            .lock()
-            .unwrap_or_else(std::sync::PoisonError::into_inner)
-            .insert(name, metadata);
-    }
-    TokenStream::from(tokens)
+    vespera_scanner::schema_type(input.into()).into()
 }
 
 #[cfg(not(tarpaulin_include))]
 #[proc_macro]
 pub fn vespera(input: TokenStream) -> TokenStream {
-    let input = syn::parse_macro_input!(input as AutoRouterInput);
-    let processed = process_vespera_input(input);
-    let schema_storage = SCHEMA_STORAGE
-        .lock()
-        .unwrap_or_else(std::sync::PoisonError::into_inner);
-    let route_storage = ROUTE_STORAGE
-        .lock()
-        .unwrap_or_else(std::sync::PoisonError::into_inner);
-
-    match process_vespera_macro(&processed, &schema_storage, &route_storage) {
-        Ok(tokens) => tokens.into(),
-        Err(e) => e.to_compile_error().into(),
-    }
+    vespera_scanner::vespera(input.into()).into()
 }
 
 /// Export a vespera app as a reusable component.
@@ -326,30 +234,5 @@ pub fn vespera(input: TokenStream) -> TokenStream {
 #[cfg(not(tarpaulin_include))]
 #[proc_macro]
 pub fn export_app(input: TokenStream) -> TokenStream {
-    let ExportAppInput { name, dir } = syn::parse_macro_input!(input as ExportAppInput);
-    let folder_name = dir
-        .map(|d| d.value())
-        .or_else(|| std::env::var("VESPERA_DIR").ok())
-        .unwrap_or_else(|| "routes".to_string());
-    let schema_storage = SCHEMA_STORAGE
-        .lock()
-        .unwrap_or_else(std::sync::PoisonError::into_inner);
-    let Ok(manifest_dir) = std::env::var("CARGO_MANIFEST_DIR") else {
-        return syn::Error::new(proc_macro2::Span::call_site(), "export_app! macro: CARGO_MANIFEST_DIR is not set. This macro must be used within a cargo build.").to_compile_error().into();
-    };
-
-    let route_storage = ROUTE_STORAGE
-        .lock()
-        .unwrap_or_else(std::sync::PoisonError::into_inner);
-
-    match process_export_app(
-        &name,
-        &folder_name,
-        &schema_storage,
-        &manifest_dir,
-        &route_storage,
-    ) {
-        Ok(tokens) => tokens.into(),
-        Err(e) => e.to_compile_error().into(),
-    }
+    vespera_scanner::export_app(input.into()).into()
 }
@@ -1,13 +0,0 @@
-mod is_keyword_type;
-mod operation;
-mod parameters;
-mod path;
-mod request_body;
-mod response;
-mod schema;
-pub use operation::build_operation_from_function;
-pub use schema::{
-    extract_default, extract_field_rename, extract_rename_all, extract_skip,
-    extract_skip_serializing_if, parse_enum_to_schema, parse_struct_to_schema,
-    parse_type_to_schema_ref, rename_field, strip_raw_prefix_owned,
-};
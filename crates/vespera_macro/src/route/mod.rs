@@ -1,3 +0,0 @@
-mod utils;
-
-pub use utils::*;
@@ -0,0 +1,945 @@
+use vespera_core::route::SecurityRequirement;
+
+use crate::{
+    http::is_http_method,
+    metadata::{RouteCache, RouteLimits, RouteLink},
+    parser::parse_json_value,
+    route::parse_route_links,
+};
+
+pub struct RouteArgs {
+    pub method: Option<syn::Ident>,
+    pub path: Option<syn::LitStr>,
+    pub error_status: Option<syn::ExprArray>,
+    pub tags: Option<syn::ExprArray>,
+    pub description: Option<syn::LitStr>,
+    /// `summary = "List users"`: `summary` in place of the doc comment's first line
+    pub summary: Option<syn::LitStr>,
+    pub security: Option<Vec<SecurityRequirement>>,
+    pub limits: Option<RouteLimits>,
+    /// `cache = (max_age = 60, public)`: `Cache-Control` of the success responses
+    pub cache: Option<RouteCache>,
+    /// `response = Json<User>`: documented in place of the handler's return type
+    pub response: Option<syn::Type>,
+    /// `request_body = Json<CreateUser>`: documented in place of the body extractor.
+    /// `multipart = UploadForm` is shorthand for `request_body = TypedMultipart<UploadForm>`.
+    pub request_body: Option<syn::Type>,
+    /// `responses(200 = Json<User>, 404 = Json<ApiError>)`: one documented response per status
+    pub responses: Option<Vec<(u16, syn::Type)>>,
+    /// `params(id = Uuid, "X-Request-Id" = String)`: schema of each named parameter
+    /// in place of the inferred one
+    pub params: Option<Vec<(String, syn::Type)>>,
+    /// `links(GetUser = (operation_id = "users_get_user", parameters(id = "$response.body#/id")))`:
+    /// links from the success responses to other operations
+    pub links: Option<Vec<RouteLink>>,
+    /// `request_example = json!({ "name": "alice" })`: example of the request body
+    pub request_example: Option<serde_json::Value>,
+    /// `response_example = json!({ "id": 1 })`: example of the success response body
+    pub response_example: Option<serde_json::Value>,
+    /// `status = 201`: status of the success response in place of `200`
+    pub status: Option<u16>,
+    /// `sdk_group = "Users"`: emitted as the `x-sdk-group` extension
+    pub sdk_group: Option<syn::LitStr>,
+    /// `operation_id = "listUsers"`: `operationId` in place of the derived one
+    pub operation_id: Option<syn::LitStr>,
+    /// `content_type = "image/png"`: media type of raw byte bodies in place of
+    /// `application/octet-stream`
+    pub content_type: Option<syn::LitStr>,
+    /// `sse_event = ChatEvent`: payload of each event of a `text/event-stream` response
+    pub sse_event: Option<syn::Type>,
+    /// `deprecated`: marks the operation `deprecated: true`
+    pub deprecated: bool,
+}
+
+impl syn::parse::Parse for RouteArgs {
+    #[allow(clippy::too_many_lines)]
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut method: Option<syn::Ident> = None;
+        let mut path: Option<syn::LitStr> = None;
+        let mut error_status: Option<syn::ExprArray> = None;
+        let mut tags: Option<syn::ExprArray> = None;
+        let mut description: Option<syn::LitStr> = None;
+        let mut summary: Option<syn::LitStr> = None;
+        let mut security: Option<Vec<SecurityRequirement>> = None;
+        let mut limits: Option<RouteLimits> = None;
+        let mut cache: Option<RouteCache> = None;
+        let mut response: Option<syn::Type> = None;
+        let mut request_body: Option<syn::Type> = None;
+        let mut multipart: Option<syn::Type> = None;
+        let mut responses: Option<Vec<(u16, syn::Type)>> = None;
+        let mut params: Option<Vec<(String, syn::Type)>> = None;
+        let mut links: Option<Vec<RouteLink>> = None;
+        let mut request_example: Option<serde_json::Value> = None;
+        let mut response_example: Option<serde_json::Value> = None;
+        let mut status: Option<u16> = None;
+        let mut sdk_group: Option<syn::LitStr> = None;
+        let mut operation_id: Option<syn::LitStr> = None;
+        let mut content_type: Option<syn::LitStr> = None;
+        let mut sse_event: Option<syn::Type> = None;
+        let mut deprecated = false;
+
+        // Parse comma-separated list of arguments
+        while !input.is_empty() {
+            let lookahead = input.lookahead1();
+
+            if lookahead.peek(syn::Ident) {
+                // Try to parse as method identifier (get, post, etc.)
+                let ident: syn::Ident = input.parse()?;
+                let ident_str = ident.to_string().to_lowercase();
+                if is_http_method(&ident_str) {
+                    method = Some(ident);
+                } else if ident_str == "path" {
+                    input.parse::<syn::Token![=]>()?;
+                    let lit: syn::LitStr = input.parse()?;
+                    path = Some(lit);
+                } else if ident_str == "error_status" {
+                    input.parse::<syn::Token![=]>()?;
+                    let array: syn::ExprArray = input.parse()?;
+                    error_status = Some(array);
+                } else if ident_str == "tags" {
+                    input.parse::<syn::Token![=]>()?;
+                    let array: syn::ExprArray = input.parse()?;
+                    tags = Some(array);
+                } else if ident_str == "description" {
+                    input.parse::<syn::Token![=]>()?;
+                    let lit: syn::LitStr = input.parse()?;
+                    description = Some(lit);
+                } else if ident_str == "summary" {
+                    input.parse::<syn::Token![=]>()?;
+                    let lit: syn::LitStr = input.parse()?;
+                    summary = Some(lit);
+                } else if ident_str == "security" {
+                    input.parse::<syn::Token![=]>()?;
+                    let array: syn::ExprArray = input.parse()?;
+                    security = Some(parse_security_requirements(&array)?);
+                } else if ident_str == "limits" {
+                    input.parse::<syn::Token![=]>()?;
+                    let content;
+                    syn::parenthesized!(content in input);
+                    limits = Some(content.parse()?);
+                } else if ident_str == "cache" {
+                    input.parse::<syn::Token![=]>()?;
+                    let content;
+                    syn::parenthesized!(content in input);
+                    cache = Some(content.parse()?);
+                } else if ident_str == "response" {
+                    input.parse::<syn::Token![=]>()?;
+                    response = Some(input.parse()?);
+                } else if ident_str == "request_body" {
+                    input.parse::<syn::Token![=]>()?;
+                    request_body = Some(input.parse()?);
+                } else if ident_str == "multipart" {
+                    input.parse::<syn::Token![=]>()?;
+                    multipart = Some(input.parse()?);
+                } else if ident_str == "responses" {
+                    let content;
+                    syn::parenthesized!(content in input);
+                    responses = Some(parse_responses(&content)?);
+                } else if ident_str == "params" {
+                    let content;
+                    syn::parenthesized!(content in input);
+                    params = Some(parse_params(&content)?);
+                } else if ident_str == "links" {
+                    let content;
+                    syn::parenthesized!(content in input);
+                    links = Some(parse_route_links(&content)?);
+                } else if ident_str == "request_example" {
+                    input.parse::<syn::Token![=]>()?;
+                    request_example = Some(parse_json_value(input)?);
+                } else if ident_str == "response_example" {
+                    input.parse::<syn::Token![=]>()?;
+                    response_example = Some(parse_json_value(input)?);
+                } else if ident_str == "status" {
+                    input.parse::<syn::Token![=]>()?;
+                    let lit: syn::LitInt = input.parse()?;
+                    let code: u16 = lit.base10_parse()?;
+                    if !(200..400).contains(&code) {
+                        return Err(syn::Error::new(
+                            lit.span(),
+                            "status: success status must be 2xx or 3xx",
+                        ));
+                    }
+                    status = Some(code);
+                } else if ident_str == "sdk_group" {
+                    input.parse::<syn::Token![=]>()?;
+                    let lit: syn::LitStr = input.parse()?;
+                    if lit.value().trim().is_empty() {
+                        return Err(syn::Error::new(lit.span(), "sdk_group: must not be empty"));
+                    }
+                    sdk_group = Some(lit);
+                } else if ident_str == "operation_id" {
+                    input.parse::<syn::Token![=]>()?;
+                    let lit: syn::LitStr = input.parse()?;
+                    if lit.value().trim().is_empty() {
+                        return Err(syn::Error::new(
+                            lit.span(),
+                            "operation_id: must not be empty",
+                        ));
+                    }
+                    operation_id = Some(lit);
+                } else if ident_str == "content_type" {
+                    input.parse::<syn::Token![=]>()?;
+                    let lit: syn::LitStr = input.parse()?;
+                    let value = lit.value();
+                    let valid = value.split_once('/').is_some_and(|(kind, subtype)| {
+                        !kind.trim().is_empty() && !subtype.trim().is_empty()
+                    });
+                    if !valid {
+                        return Err(syn::Error::new(
+                            lit.span(),
+                            "content_type: expected a media type like `image/png`",
+                        ));
+                    }
+                    content_type = Some(lit);
+                } else if ident_str == "sse_event" {
+                    input.parse::<syn::Token![=]>()?;
+                    sse_event = Some(input.parse()?);
+                } else if ident_str == "deprecated" {
+                    deprecated = true;
+                } else {
+                    return Err(lookahead.error());
+                }
+
+                // Check if there's a comma
+                if input.peek(syn::Token![,]) {
+                    input.parse::<syn::Token![,]>()?;
+                } else {
+                    break;
+                }
+            } else {
+                return Err(lookahead.error());
+            }
+        }
+
+        if let Some(schema) = multipart {
+            if request_body.is_some() {
+                return Err(syn::Error::new_spanned(
+                    schema,
+                    "multipart: cannot be combined with `request_body`",
+                ));
+            }
+            request_body = Some(syn::parse_quote!(TypedMultipart<#schema>));
+        }
+
+        Ok(Self {
+            method,
+            path,
+            error_status,
+            tags,
+            description,
+            summary,
+            security,
+            limits,
+            cache,
+            response,
+            request_body,
+            responses,
+            params,
+            links,
+            request_example,
+            response_example,
+            status,
+            sdk_group,
+            operation_id,
+            content_type,
+            sse_event,
+            deprecated,
+        })
+    }
+}
+
+/// Parse the inside of `params(id = Uuid, "X-Request-Id" = String)`.
+///
+/// Names are identifiers, or string literals for names that are not (e.g. headers).
+fn parse_params(input: syn::parse::ParseStream) -> syn::Result<Vec<(String, syn::Type)>> {
+    use syn::ext::IdentExt;
+
+    let mut params: Vec<(String, syn::Type)> = Vec::new();
+    while !input.is_empty() {
+        let (name, span) = if input.peek(syn::LitStr) {
+            let lit: syn::LitStr = input.parse()?;
+            (lit.value(), lit.span())
+        } else {
+            let ident = input.call(syn::Ident::parse_any)?;
+            (ident.unraw().to_string(), ident.span())
+        };
+        if name.is_empty() {
+            return Err(syn::Error::new(span, "params: name must not be empty"));
+        }
+        if params.iter().any(|(existing, _)| *existing == name) {
+            return Err(syn::Error::new(
+                span,
+                format!("params: duplicate parameter `{name}`"),
+            ));
+        }
+        input.parse::<syn::Token![=]>()?;
+        params.push((name, input.parse()?));
+
+        if input.peek(syn::Token![,]) {
+            input.parse::<syn::Token![,]>()?;
+        } else {
+            break;
+        }
+    }
+    if !input.is_empty() {
+        return Err(input.error("params: expected `,`"));
+    }
+    Ok(params)
+}
+
+/// Parse the inside of `responses(200 = Json<User>, 404 = Json<ApiError>)`.
+fn parse_responses(input: syn::parse::ParseStream) -> syn::Result<Vec<(u16, syn::Type)>> {
+    let mut responses: Vec<(u16, syn::Type)> = Vec::new();
+    while !input.is_empty() {
+        let lit: syn::LitInt = input.parse()?;
+        let status: u16 = lit.base10_parse()?;
+        if !(100..=599).contains(&status) {
+            return Err(syn::Error::new(
+                lit.span(),
+                "responses: status code must be between 100 and 599",
+            ));
+        }
+        if responses.iter().any(|(existing, _)| *existing == status) {
+            return Err(syn::Error::new(
+                lit.span(),
+                format!("responses: duplicate status code `{status}`"),
+            ));
+        }
+        input.parse::<syn::Token![=]>()?;
+        responses.push((status, input.parse()?));
+
+        if input.peek(syn::Token![,]) {
+            input.parse::<syn::Token![,]>()?;
+        } else {
+            break;
+        }
+    }
+    if !input.is_empty() {
+        return Err(input.error("responses: expected `,`"));
+    }
+    Ok(responses)
+}
+
+/// Arguments of `vespera::defaults!(...)`, merged into every route of a module.
+pub struct DefaultsArgs {
+    pub error_status: Option<syn::ExprArray>,
+    pub tags: Option<syn::ExprArray>,
+    pub security: Option<Vec<SecurityRequirement>>,
+}
+
+impl syn::parse::Parse for DefaultsArgs {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut error_status: Option<syn::ExprArray> = None;
+        let mut tags: Option<syn::ExprArray> = None;
+        let mut security: Option<Vec<SecurityRequirement>> = None;
+
+        while !input.is_empty() {
+            let ident: syn::Ident = input.parse()?;
+            input.parse::<syn::Token![=]>()?;
+            match ident.to_string().as_str() {
+                "error_status" => error_status = Some(input.parse()?),
+                "tags" => tags = Some(input.parse()?),
+                "security" => security = Some(parse_security_requirements(&input.parse()?)?),
+                other => {
+                    return Err(syn::Error::new(
+                        ident.span(),
+                        format!(
+                            "defaults: unknown key `{other}`. Expected `tags`, `security`, or `error_status`."
+                        ),
+                    ));
+                }
+            }
+
+            if input.peek(syn::Token![,]) {
+                input.parse::<syn::Token![,]>()?;
+            } else {
+                break;
+            }
+        }
+
+        if !input.is_empty() {
+            return Err(input.error("defaults: expected `,` between arguments."));
+        }
+
+        Ok(Self {
+            error_status,
+            tags,
+            security,
+        })
+    }
+}
+
+/// Parse `security = [["bearer"], ["api_key", "client_id"]]`.
+///
+/// The outer array lists alternatives (OR); each inner array lists schemes that
+/// must all be satisfied (AND). A scheme is either `"name"` or `("name", ["scope", ...])`.
+/// A bare scheme is shorthand for a one-scheme alternative, so `["bearer", "api_key"]`
+/// means bearer OR api key. `[]` as an alternative makes authentication optional.
+pub fn parse_security_requirements(
+    array: &syn::ExprArray,
+) -> syn::Result<Vec<SecurityRequirement>> {
+    array
+        .elems
+        .iter()
+        .map(|alternative| {
+            let syn::Expr::Array(schemes) = alternative else {
+                let (name, scopes) = parse_security_scheme(alternative)?;
+                return Ok(SecurityRequirement::from([(name.value(), scopes)]));
+            };
+            let mut requirement = SecurityRequirement::new();
+            for scheme in &schemes.elems {
+                let (name, scopes) = parse_security_scheme(scheme)?;
+                if requirement.insert(name.value(), scopes).is_some() {
+                    return Err(syn::Error::new_spanned(
+                        name,
+                        "route attribute: duplicate security scheme in one alternative. List each scheme once.",
+                    ));
+                }
+            }
+            Ok(requirement)
+        })
+        .collect()
+}
+
+/// Parse `"name"` or `("name", ["scope", ...])`.
+fn parse_security_scheme(expr: &syn::Expr) -> syn::Result<(syn::LitStr, Vec<String>)> {
+    match expr {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Str(name),
+            ..
+        }) => Ok((name.clone(), Vec::new())),
+        syn::Expr::Tuple(tuple) if tuple.elems.len() == 2 => {
+            let (
+                syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Str(name),
+                    ..
+                }),
+                syn::Expr::Array(scopes),
+            ) = (&tuple.elems[0], &tuple.elems[1])
+            else {
+                return Err(syn::Error::new_spanned(
+                    tuple,
+                    "route attribute: scoped security scheme must be `(\"name\", [\"scope\", ...])`.",
+                ));
+            };
+            let scopes = scopes
+                .elems
+                .iter()
+                .map(|scope| match scope {
+                    syn::Expr::Lit(syn::ExprLit {
+                        lit: syn::Lit::Str(lit),
+                        ..
+                    }) => Ok(lit.value()),
+                    other => Err(syn::Error::new_spanned(
+                        other,
+                        "route attribute: security scopes must be string literals. Use `(\"oauth2\", [\"read:users\"])`.",
+                    )),
+                })
+                .collect::<syn::Result<Vec<_>>>()?;
+            Ok((name.clone(), scopes))
+        }
+        other => Err(syn::Error::new_spanned(
+            other,
+            "route attribute: security scheme must be a string or `(\"name\", [\"scope\", ...])`. Use `security = [[\"bearer\"]]`.",
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    // Method only
+    #[case("get", true, Some("get"), None, None)]
+    #[case("post", true, Some("post"), None, None)]
+    #[case("put", true, Some("put"), None, None)]
+    #[case("patch", true, Some("patch"), None, None)]
+    #[case("delete", true, Some("delete"), None, None)]
+    #[case("head", true, Some("head"), None, None)]
+    #[case("options", true, Some("options"), None, None)]
+    // Path only
+    #[case("path = \"/api\"", true, None, Some("/api"), None)]
+    #[case("path = \"/users\"", true, None, Some("/users"), None)]
+    #[case("path = \"/api/v1\"", true, None, Some("/api/v1"), None)]
+    // Method and path
+    #[case("get, path = \"/api\"", true, Some("get"), Some("/api"), None)]
+    #[case("post, path = \"/users\"", true, Some("post"), Some("/users"), None)]
+    #[case("path = \"/api\", get", true, Some("get"), Some("/api"), None)]
+    // Error status only
+    #[case("error_status = [400]", true, None, None, Some(vec![400]))]
+    #[case("error_status = [400, 404]", true, None, None, Some(vec![400, 404]))]
+    #[case("error_status = [400, 404, 500]", true, None, None, Some(vec![400, 404, 500]))]
+    // Method and error_status
+    #[case("get, error_status = [400]", true, Some("get"), None, Some(vec![400]))]
+    #[case("post, error_status = [400, 404]", true, Some("post"), None, Some(vec![400, 404]))]
+    // Path and error_status
+    #[case("path = \"/api\", error_status = [400]", true, None, Some("/api"), Some(vec![400]))]
+    // All three
+    #[case("get, path = \"/api\", error_status = [400]", true, Some("get"), Some("/api"), Some(vec![400]))]
+    #[case("post, path = \"/users\", error_status = [400, 404]", true, Some("post"), Some("/users"), Some(vec![400, 404]))]
+    #[case("path = \"/api\", get, error_status = [400]", true, Some("get"), Some("/api"), Some(vec![400]))]
+    // Empty input
+    #[case("", true, None, None, None)]
+    // Invalid cases
+    #[case("invalid", false, None, None, None)]
+    #[case("path", false, None, None, None)]
+    #[case("error_status", false, None, None, None)]
+    #[case("get, invalid", false, None, None, None)]
+    #[case("path =", false, None, None, None)]
+    #[case("error_status =", false, None, None, None)]
+    // Non-Ident tokens (should trigger line 40)
+    #[case("123", false, None, None, None)]
+    #[case("\"string\"", false, None, None, None)]
+    #[case("=", false, None, None, None)]
+    #[case("[", false, None, None, None)]
+    #[case("]", false, None, None, None)]
+    #[case(",", false, None, None, None)]
+    #[case("get, 123", false, None, None, None)]
+    #[case("get, =", false, None, None, None)]
+    fn test_route_args_parse(
+        #[case] input: &str,
+        #[case] should_parse: bool,
+        #[case] expected_method: Option<&str>,
+        #[case] expected_path: Option<&str>,
+        #[case] expected_error_status: Option<Vec<u16>>,
+    ) {
+        let result = syn::parse_str::<RouteArgs>(input);
+
+        match (should_parse, result) {
+            (true, Ok(route_args)) => {
+                // Check method
+                if let Some(exp_method) = expected_method {
+                    assert!(
+                        route_args.method.is_some(),
+                        "Expected method {exp_method} but got None for input: {input}"
+                    );
+                    assert_eq!(
+                        route_args.method.as_ref().unwrap().to_string(),
+                        exp_method,
+                        "Method mismatch for input: {input}"
+                    );
+                } else {
+                    assert!(
+                        route_args.method.is_none(),
+                        "Expected no method but got {:?} for input: {}",
+                        route_args.method,
+                        input
+                    );
+                }
+
+                // Check path
+                if let Some(exp_path) = expected_path {
+                    assert!(
+                        route_args.path.is_some(),
+                        "Expected path {exp_path} but got None for input: {input}"
+                    );
+                    assert_eq!(
+                        route_args.path.as_ref().unwrap().value(),
+                        exp_path,
+                        "Path mismatch for input: {input}"
+                    );
+                } else {
+                    assert!(
+                        route_args.path.is_none(),
+                        "Expected no path but got {:?} for input: {}",
+                        route_args.path,
+                        input
+                    );
+                }
+
+                // Check error_status
+                if let Some(exp_status) = expected_error_status {
+                    assert!(
+                        route_args.error_status.is_some(),
+                        "Expected error_status {exp_status:?} but got None for input: {input}"
+                    );
+                    let array = route_args.error_status.as_ref().unwrap();
+                    let mut status_codes = Vec::new();
+                    for elem in &array.elems {
+                        if let syn::Expr::Lit(syn::ExprLit {
+                            lit: syn::Lit::Int(lit_int),
+                            ..
+                        }) = elem
+                            && let Ok(code) = lit_int.base10_parse::<u16>()
+                        {
+                            status_codes.push(code);
+                        }
+                    }
+                    assert_eq!(
+                        status_codes, exp_status,
+                        "Error status mismatch for input: {input}"
+                    );
+                } else {
+                    assert!(
+                        route_args.error_status.is_none(),
+                        "Expected no error_status but got {:?} for input: {}",
+                        route_args.error_status,
+                        input
+                    );
+                }
+            }
+            (false, Err(_)) => {
+                // Expected error, test passes
+            }
+            (true, Err(e)) => {
+                panic!("Expected successful parse but got error: {e} for input: {input}");
+            }
+            (false, Ok(_)) => {
+                panic!("Expected parse error but got success for input: {input}");
+            }
+        }
+    }
+
+    #[rstest]
+    // Tags only
+    #[case("tags = [\"users\"]", true, vec!["users"])]
+    #[case("tags = [\"users\", \"admin\"]", true, vec!["users", "admin"])]
+    #[case("tags = [\"api\", \"v1\", \"users\"]", true, vec!["api", "v1", "users"])]
+    // Tags with method
+    #[case("get, tags = [\"users\"]", true, vec!["users"])]
+    #[case("post, tags = [\"users\", \"create\"]", true, vec!["users", "create"])]
+    // Tags with path
+    #[case("path = \"/api\", tags = [\"api\"]", true, vec!["api"])]
+    // Tags with method and path
+    #[case("get, path = \"/users\", tags = [\"users\"]", true, vec!["users"])]
+    // Empty tags array
+    #[case("tags = []", true, vec![])]
+    fn test_route_args_parse_tags(
+        #[case] input: &str,
+        #[case] should_parse: bool,
+        #[case] expected_tags: Vec<&str>,
+    ) {
+        let result = syn::parse_str::<RouteArgs>(input);
+
+        match (should_parse, result) {
+            (true, Ok(route_args)) => {
+                if expected_tags.is_empty() {
+                    // Empty array should result in Some with empty vec
+                    if let Some(tags_array) = &route_args.tags {
+                        assert!(tags_array.elems.is_empty());
+                    }
+                } else {
+                    assert!(
+                        route_args.tags.is_some(),
+                        "Expected tags but got None for input: {input}"
+                    );
+                    let tags_array = route_args.tags.as_ref().unwrap();
+                    let mut parsed_tags = Vec::new();
+                    for elem in &tags_array.elems {
+                        if let syn::Expr::Lit(syn::ExprLit {
+                            lit: syn::Lit::Str(lit_str),
+                            ..
+                        }) = elem
+                        {
+                            parsed_tags.push(lit_str.value());
+                        }
+                    }
+                    assert_eq!(
+                        parsed_tags, expected_tags,
+                        "Tags mismatch for input: {input}"
+                    );
+                }
+            }
+            (false, Err(_)) => {
+                // Expected error, test passes
+            }
+            (true, Err(e)) => {
+                panic!("Expected successful parse but got error: {e} for input: {input}");
+            }
+            (false, Ok(_)) => {
+                panic!("Expected parse error but got success for input: {input}");
+            }
+        }
+    }
+
+    #[rstest]
+    #[case("security = [[\"bearer\"], [\"api_key\"]]", serde_json::json!([{ "bearer": [] }, { "api_key": [] }]))]
+    #[case("security = [[\"api_key\", \"client_id\"]]", serde_json::json!([{ "api_key": [], "client_id": [] }]))]
+    #[case("security = [[(\"oauth2\", [\"read\", \"write\"])], []]", serde_json::json!([{ "oauth2": ["read", "write"] }, {}]))]
+    #[case("get, security = []", serde_json::json!([]))]
+    #[case("security = [\"bearer\", \"api_key\"]", serde_json::json!([{ "bearer": [] }, { "api_key": [] }]))]
+    fn test_route_args_parse_security(#[case] input: &str, #[case] expected: serde_json::Value) {
+        let route_args = syn::parse_str::<RouteArgs>(input).unwrap();
+        assert_eq!(
+            serde_json::to_value(route_args.security.unwrap()).unwrap(),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case("security = [123]", "security scheme must be a string")]
+    #[case("security = [[bearer]]", "security scheme must be a string")]
+    #[case(
+        "security = [[(\"oauth2\", \"read\")]]",
+        "scoped security scheme must be"
+    )]
+    #[case(
+        "security = [[(\"oauth2\", [read])]]",
+        "security scopes must be string literals"
+    )]
+    #[case("security = [[\"bearer\", \"bearer\"]]", "duplicate security scheme")]
+    fn test_route_args_parse_security_errors(#[case] input: &str, #[case] expected: &str) {
+        let err = syn::parse_str::<RouteArgs>(input).err().unwrap();
+        assert!(err.to_string().contains(expected), "{err}");
+    }
+
+    #[rstest]
+    #[case("post, response = Json<User>", Some("Json < User >"), None)]
+    #[case(
+        "request_body = Json<CreateUser>, response = (StatusCode, Json<User>)",
+        Some("(StatusCode , Json < User >)"),
+        Some("Json < CreateUser >")
+    )]
+    #[case(
+        "post, multipart = UploadForm",
+        None,
+        Some("TypedMultipart < UploadForm >")
+    )]
+    #[case("get", None, None)]
+    fn test_route_args_parse_overrides(
+        #[case] input: &str,
+        #[case] response: Option<&str>,
+        #[case] request_body: Option<&str>,
+    ) {
+        use quote::ToTokens;
+
+        let route_args = syn::parse_str::<RouteArgs>(input).unwrap();
+        let render = |ty: Option<syn::Type>| ty.map(|ty| ty.to_token_stream().to_string());
+        assert_eq!(render(route_args.response).as_deref(), response);
+        assert_eq!(render(route_args.request_body).as_deref(), request_body);
+    }
+
+    #[rstest]
+    #[case("get, sse_event = ChatEvent", Some("ChatEvent"))]
+    #[case(
+        "get, sse_event = Json<ChatEvent>, path = \"/events\"",
+        Some("Json < ChatEvent >")
+    )]
+    #[case("get", None)]
+    fn test_route_args_parse_sse_event(#[case] input: &str, #[case] expected: Option<&str>) {
+        use quote::ToTokens;
+
+        let route_args = syn::parse_str::<RouteArgs>(input).unwrap();
+        let sse_event = route_args
+            .sse_event
+            .map(|ty| ty.to_token_stream().to_string());
+        assert_eq!(sse_event.as_deref(), expected);
+    }
+
+    #[rstest]
+    #[case("post, multipart = UploadForm, request_body = Json<User>")]
+    #[case("post, request_body = Json<User>, multipart = UploadForm")]
+    fn test_route_args_parse_multipart_conflict(#[case] input: &str) {
+        let err = syn::parse_str::<RouteArgs>(input).err().unwrap();
+        assert!(
+            err.to_string()
+                .contains("cannot be combined with `request_body`"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_route_args_parse_responses() {
+        use quote::ToTokens;
+
+        let route_args = syn::parse_str::<RouteArgs>(
+            "get, responses(200 = Json<User>, 404 = Json<ErrorResponse>, 422 = ValidationError)",
+        )
+        .unwrap();
+        let responses: Vec<_> = route_args
+            .responses
+            .unwrap()
+            .into_iter()
+            .map(|(status, ty)| (status, ty.to_token_stream().to_string()))
+            .collect();
+        assert_eq!(
+            responses,
+            vec![
+                (200, "Json < User >".to_string()),
+                (404, "Json < ErrorResponse >".to_string()),
+                (422, "ValidationError".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_route_args_parse_params() {
+        use quote::ToTokens;
+
+        let route_args = syn::parse_str::<RouteArgs>(
+            "get, path = \"/{id}\", params(id = Uuid, r#type = Option<Kind>, \"X-Request-Id\" = String)",
+        )
+        .unwrap();
+        let params: Vec<_> = route_args
+            .params
+            .unwrap()
+            .into_iter()
+            .map(|(name, ty)| (name, ty.to_token_stream().to_string()))
+            .collect();
+        assert_eq!(
+            params,
+            vec![
+                ("id".to_string(), "Uuid".to_string()),
+                ("type".to_string(), "Option < Kind >".to_string()),
+                ("X-Request-Id".to_string(), "String".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_route_args_parse_links() {
+        let route_args = syn::parse_str::<RouteArgs>(
+            "post, status = 201, links(GetUser = (operation_id = \"users_get_user\", parameters(id = \"$response.body#/id\")))",
+        )
+        .unwrap();
+        let links = route_args.links.unwrap();
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].name, "GetUser");
+        assert_eq!(links[0].operation_id, "users_get_user");
+        assert_eq!(
+            links[0].parameters,
+            vec![("id".to_string(), "$response.body#/id".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_route_args_parse_examples() {
+        let route_args = syn::parse_str::<RouteArgs>(
+            "post, request_example = json!({ \"name\": \"alice\" }), response_example = json!({ \"id\": 1, \"tags\": [] })",
+        )
+        .unwrap();
+        assert_eq!(
+            route_args.request_example,
+            Some(serde_json::json!({ "name": "alice" }))
+        );
+        assert_eq!(
+            route_args.response_example,
+            Some(serde_json::json!({ "id": 1, "tags": [] }))
+        );
+    }
+
+    #[rstest]
+    #[case("params(id = Uuid, id = String)", "duplicate parameter `id`")]
+    #[case("params(\"\" = String)", "name must not be empty")]
+    #[case("params(id = Uuid page = u32)", "expected `,`")]
+    fn test_route_args_parse_params_errors(#[case] input: &str, #[case] expected: &str) {
+        let err = syn::parse_str::<RouteArgs>(input).err().unwrap();
+        assert!(err.to_string().contains(expected), "{err}");
+    }
+
+    #[rstest]
+    #[case("responses(200 = User, 200 = Other)", "duplicate status code `200`")]
+    #[case("responses(600 = User)", "between 100 and 599")]
+    #[case("responses(\"200\" = User)", "expected integer literal")]
+    #[case("responses(200 = User 404 = Other)", "expected `,`")]
+    fn test_route_args_parse_responses_errors(#[case] input: &str, #[case] expected: &str) {
+        let err = syn::parse_str::<RouteArgs>(input).err().unwrap();
+        assert!(err.to_string().contains(expected), "{err}");
+    }
+
+    #[rstest]
+    #[case("post, status = 201", Ok(Some(201)))]
+    #[case("post, status = 302", Ok(Some(302)))]
+    #[case("post", Ok(None))]
+    #[case("post, status = 404", Err("success status must be 2xx or 3xx"))]
+    #[case("post, status = \"201\"", Err("expected integer literal"))]
+    fn test_route_args_parse_status(
+        #[case] input: &str,
+        #[case] expected: Result<Option<u16>, &str>,
+    ) {
+        match (syn::parse_str::<RouteArgs>(input), expected) {
+            (Ok(route_args), Ok(status)) => assert_eq!(route_args.status, status),
+            (Err(err), Err(message)) => assert!(err.to_string().contains(message), "{err}"),
+            (_, expected) => panic!("unexpected result for {input}, expected {expected:?}"),
+        }
+    }
+
+    #[test]
+    fn test_route_args_parse_sdk_group() {
+        let route_args = syn::parse_str::<RouteArgs>("get, sdk_group = \"Users\"").unwrap();
+        assert_eq!(route_args.sdk_group.unwrap().value(), "Users");
+
+        let err = syn::parse_str::<RouteArgs>("get, sdk_group = \"\"")
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("must not be empty"), "{err}");
+    }
+
+    #[test]
+    fn test_route_args_parse_operation_id() {
+        let route_args = syn::parse_str::<RouteArgs>("get, operation_id = \"listUsers\"").unwrap();
+        assert_eq!(route_args.operation_id.unwrap().value(), "listUsers");
+
+        let err = syn::parse_str::<RouteArgs>("get, operation_id = \" \"")
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("must not be empty"), "{err}");
+    }
+
+    #[rstest]
+    #[case("get, content_type = \"image/png\"", Ok(Some("image/png")))]
+    #[case("get", Ok(None))]
+    #[case("get, content_type = \"png\"", Err("expected a media type"))]
+    #[case("get, content_type = \"image/\"", Err("expected a media type"))]
+    fn test_route_args_parse_content_type(
+        #[case] input: &str,
+        #[case] expected: Result<Option<&str>, &str>,
+    ) {
+        match (syn::parse_str::<RouteArgs>(input), expected) {
+            (Ok(route_args), Ok(content_type)) => assert_eq!(
+                route_args
+                    .content_type
+                    .as_ref()
+                    .map(syn::LitStr::value)
+                    .as_deref(),
+                content_type
+            ),
+            (Err(err), Err(message)) => assert!(err.to_string().contains(message), "{err}"),
+            (_, expected) => panic!("unexpected result for {input}, expected {expected:?}"),
+        }
+    }
+
+    #[rstest]
+    #[case("get, deprecated", true)]
+    #[case("get, deprecated, path = \"/old\"", true)]
+    #[case("get", false)]
+    fn test_route_args_parse_deprecated(#[case] input: &str, #[case] expected: bool) {
+        let route_args = syn::parse_str::<RouteArgs>(input).unwrap();
+        assert_eq!(route_args.deprecated, expected);
+    }
+
+    #[test]
+    fn test_route_args_parse_summary() {
+        let route_args =
+            syn::parse_str::<RouteArgs>("get, summary = \"List users\", description = \"All\"")
+                .unwrap();
+        assert_eq!(route_args.summary.unwrap().value(), "List users");
+        assert_eq!(route_args.description.unwrap().value(), "All");
+    }
+
+    #[test]
+    fn test_defaults_args_parse() {
+        let args = syn::parse_str::<DefaultsArgs>(
+            "tags = [\"users\"], security = [\"bearer\"], error_status = [500]",
+        )
+        .unwrap();
+        assert_eq!(args.tags.unwrap().elems.len(), 1);
+        assert_eq!(args.error_status.unwrap().elems.len(), 1);
+        assert_eq!(
+            serde_json::to_value(args.security.unwrap()).unwrap(),
+            serde_json::json!([{ "bearer": [] }])
+        );
+    }
+
+    #[rstest]
+    #[case("path = \"/x\"", "unknown key `path`")]
+    #[case("tags", "expected `=`")]
+    #[case("tags = [\"a\"] error_status = [500]", "expected `,`")]
+    fn test_defaults_args_parse_errors(#[case] input: &str, #[case] expected: &str) {
+        let err = syn::parse_str::<DefaultsArgs>(input).err().unwrap();
+        assert!(err.to_string().contains(expected), "{err}");
+    }
+}
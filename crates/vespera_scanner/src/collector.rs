@@ -6,10 +6,11 @@ use std::path::Path;
 use syn::Item;
 
 use crate::{
+    diagnostics::Diagnostic,
     error::{MacroResult, err_call_site},
     file_utils::{collect_files, file_to_segments},
-    metadata::{CollectedMetadata, RouteMetadata},
-    route::{extract_doc_comment, extract_route_info},
+    metadata::{CollectedMetadata, InvalidFile, RouteMetadata},
+    route::{extract_doc_comment, extract_module_defaults, extract_route_info, split_doc_comment},
     route_impl::StoredRouteInfo,
 };
 
@@ -22,6 +23,9 @@ use crate::{
 ///
 /// Returns the metadata AND the parsed file ASTs, so downstream consumers
 /// (e.g., `openapi_generator`) can reuse them without re-reading files from disk.
+///
+/// Files that cannot be read or parsed are skipped and listed in
+/// `metadata.invalid_files`, so every broken file is reported at once.
 #[allow(clippy::option_if_let_else, clippy::too_many_lines)]
 pub fn collect_metadata(
     folder_path: &Path,
@@ -30,7 +34,7 @@ pub fn collect_metadata(
 ) -> MacroResult<(CollectedMetadata, HashMap<String, syn::File>)> {
     let mut metadata = CollectedMetadata::new();
 
-    let files = collect_files(folder_path).map_err(|e| err_call_site(format!("vespera! macro: failed to scan route folder '{}': {}. Verify the folder exists and is readable.", folder_path.display(), e)))?;
+    let files = collect_files(folder_path).map_err(|e| Diagnostic::RouteFolderUnreadable.error_call_site(format_args!("vespera! macro: failed to scan route folder '{}': {}. Verify the folder exists and is readable.", folder_path.display(), e)))?;
 
     let mut file_asts = HashMap::with_capacity(files.len());
 
@@ -46,10 +50,6 @@ pub fn collect_metadata(
     };
 
     for file in files {
-        if file.extension().is_none_or(|e| e != "rs") {
-            continue;
-        }
-
         let file_path = file.display().to_string();
 
         // Get module path (cheap — no parsing needed)
@@ -71,6 +71,17 @@ pub fn collect_metadata(
             format!("{}::{}", folder_name, segments.join("::"))
         };
 
+        // Module defaults: cheap text check before parsing
+        if crate::schema_macro::file_cache::get_file_content(&file)
+            .is_some_and(|content| content.contains("defaults"))
+            && let Some(file_ast) = crate::schema_macro::file_cache::get_parsed_file(&file)
+            && let Some(defaults) = extract_module_defaults(&file_ast)?
+        {
+            metadata
+                .module_defaults
+                .insert(module_path.trim_end_matches("::").to_string(), defaults);
+        }
+
         // Pre-compute base path once per file (avoids repeated segments.join per route)
         let base_path = format!("/{}", segments.join("/"));
 
@@ -85,12 +96,18 @@ pub fn collect_metadata(
                 };
                 let route_path = route_path.replace('_', "-");
 
-                // Extract doc comment from fn_item_str if no explicit description
-                let description = stored.description.clone().or_else(|| {
-                    syn::parse_str::<syn::ItemFn>(&stored.fn_item_str)
-                        .ok()
-                        .and_then(|fn_item| extract_doc_comment(&fn_item.attrs))
-                });
+                // Split the doc comment in fn_item_str for anything not set explicitly
+                let (doc_summary, doc_description) =
+                    if stored.summary.is_some() && stored.description.is_some() {
+                        (None, None)
+                    } else {
+                        let doc = syn::parse_str::<syn::ItemFn>(&stored.fn_item_str)
+                            .ok()
+                            .and_then(|fn_item| extract_doc_comment(&fn_item.attrs));
+                        split_doc_comment(doc.as_deref())
+                    };
+                let description = stored.description.clone().or(doc_description);
+                let summary = stored.summary.clone().or(doc_summary);
 
                 metadata.routes.push(RouteMetadata {
                     method: stored.method.clone().unwrap_or_default(),
@@ -102,6 +119,23 @@ pub fn collect_metadata(
                     error_status: stored.error_status.clone(),
                     tags: stored.tags.clone(),
                     description,
+                    security: stored.security.clone(),
+                    limits: stored.limits,
+                    cache: stored.cache,
+                    response: stored.response.clone(),
+                    request_body: stored.request_body.clone(),
+                    responses: stored.responses.clone(),
+                    params: stored.params.clone(),
+                    links: stored.links.clone(),
+                    request_example: stored.request_example.clone(),
+                    response_example: stored.response_example.clone(),
+                    status: stored.status,
+                    sdk_group: stored.sdk_group.clone(),
+                    operation_id: stored.operation_id.clone(),
+                    content_type: stored.content_type.clone(),
+                    sse_event: stored.sse_event.clone(),
+                    summary,
+                    deprecated: stored.deprecated,
                 });
             }
 
@@ -111,7 +145,15 @@ pub fn collect_metadata(
         } else {
             // Slow path: full parsing (fallback for files not in ROUTE_STORAGE)
             // Uses get_parsed_file: single syn::parse_file entry point + content cache
-            let file_ast = crate::schema_macro::file_cache::get_parsed_file(&file).ok_or_else(|| err_call_site(format!("vespera! macro: cannot read or parse '{}'. Fix the Rust syntax errors in this file.", file.display())))?;
+            let file_ast = match crate::schema_macro::file_cache::try_parse_file(&file) {
+                Ok(file_ast) => file_ast,
+                Err(error) => {
+                    metadata
+                        .invalid_files
+                        .push(InvalidFile { file_path, error });
+                    continue;
+                }
+            };
 
             // Store file AST for downstream reuse
             file_asts.insert(file_path.clone(), file_ast);
@@ -130,11 +172,11 @@ pub fn collect_metadata(
                     };
                     let route_path = route_path.replace('_', "-");
 
-                    // Description priority: route attribute > doc comment
-                    let description = route_info
-                        .description
-                        .clone()
-                        .or_else(|| extract_doc_comment(&fn_item.attrs));
+                    // Route attribute > doc comment (first line summary, rest description)
+                    let (doc_summary, doc_description) =
+                        split_doc_comment(extract_doc_comment(&fn_item.attrs).as_deref());
+                    let description = route_info.description.clone().or(doc_description);
+                    let summary = route_info.summary.clone().or(doc_summary);
 
                     metadata.routes.push(RouteMetadata {
                         method: route_info.method,
@@ -146,6 +188,23 @@ pub fn collect_metadata(
                         error_status: route_info.error_status.clone(),
                         tags: route_info.tags.clone(),
                         description,
+                        security: route_info.security.clone(),
+                        limits: route_info.limits,
+                        cache: route_info.cache,
+                        response: route_info.response.clone(),
+                        request_body: route_info.request_body.clone(),
+                        responses: route_info.responses.clone(),
+                        params: route_info.params.clone(),
+                        links: route_info.links.clone(),
+                        request_example: route_info.request_example.clone(),
+                        response_example: route_info.response_example.clone(),
+                        status: route_info.status,
+                        sdk_group: route_info.sdk_group.clone(),
+                        operation_id: route_info.operation_id.clone(),
+                        content_type: route_info.content_type.clone(),
+                        sse_event: route_info.sse_event.clone(),
+                        summary,
+                        deprecated: route_info.deprecated,
                     });
                 }
             }
@@ -159,7 +218,7 @@ pub fn collect_metadata(
 /// Used for cache invalidation — much cheaper than full `collect_metadata()`.
 pub fn collect_file_fingerprints(folder_path: &Path) -> MacroResult<HashMap<String, u64>> {
     let files = collect_files(folder_path).map_err(|e| {
-        err_call_site(format!(
+        Diagnostic::RouteFolderUnreadable.error_call_site(format_args!(
             "vespera! macro: failed to scan route folder '{}': {}",
             folder_path.display(),
             e
@@ -168,9 +227,6 @@ pub fn collect_file_fingerprints(folder_path: &Path) -> MacroResult<HashMap<Stri
 
     let mut fingerprints = HashMap::with_capacity(files.len());
     for file in files {
-        if file.extension().is_none_or(|e| e != "rs") {
-            continue;
-        }
         let mtime = std::fs::metadata(&file)
             .and_then(|m| m.modified())
             .map_or(0, |t| {
@@ -503,6 +559,48 @@ pub struct Post {
         drop(temp_dir);
     }
 
+    #[test]
+    fn test_collect_metadata_module_defaults() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let folder_name = "routes";
+
+        create_temp_file(
+            &temp_dir,
+            "admin/mod.rs",
+            r#"
+vespera::defaults!(tags = ["admin"], security = ["bearer"]);
+"#,
+        );
+        create_temp_file(
+            &temp_dir,
+            "admin/users.rs",
+            r#"
+#[route(get)]
+pub fn list() -> String {
+    "users".to_string()
+}
+"#,
+        );
+
+        let (metadata, _file_asts) = collect_metadata(temp_dir.path(), folder_name, &[]).unwrap();
+
+        assert_eq!(metadata.routes.len(), 1);
+        assert_eq!(metadata.routes[0].module_path, "routes::admin::users");
+        let defaults = &metadata.module_defaults["routes::admin"];
+        assert_eq!(defaults.tags, vec!["admin".to_string()]);
+        assert!(defaults.security.as_ref().unwrap()[0].contains_key("bearer"));
+    }
+
+    #[test]
+    fn test_collect_metadata_module_defaults_invalid() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+
+        create_temp_file(&temp_dir, "mod.rs", "vespera::defaults!(method = \"get\");");
+
+        let result = collect_metadata(temp_dir.path(), "routes", &[]);
+        assert!(result.err().unwrap().to_string().contains("unknown key"));
+    }
+
     #[test]
     fn test_collect_metadata_with_mod_rs() {
         let temp_dir = TempDir::new().expect("Failed to create temp dir");
@@ -602,10 +700,20 @@ pub fn get_users() -> String {
 
         create_temp_file(&temp_dir, "invalid.rs", "invalid rust syntax {");
 
-        let metadata = collect_metadata(temp_dir.path(), folder_name, &[]).map(|(m, _)| m);
+        let (metadata, file_asts) = collect_metadata(temp_dir.path(), folder_name, &[]).unwrap();
 
-        // Only valid file should be processed
-        assert!(metadata.is_err());
+        // Only valid file should be processed, the invalid one is listed
+        assert_eq!(metadata.routes.len(), 1);
+        assert_eq!(file_asts.len(), 1);
+        assert_eq!(metadata.invalid_files.len(), 1);
+        assert!(metadata.invalid_files[0].file_path.ends_with("invalid.rs"));
+        assert!(
+            metadata.invalid_files[0]
+                .error
+                .starts_with("line 1, column "),
+            "{}",
+            metadata.invalid_files[0].error
+        );
 
         drop(temp_dir);
     }
@@ -670,12 +778,15 @@ pub fn head_handler() -> String { "head".to_string() }
 
 #[route(options)]
 pub fn options_handler() -> String { "options".to_string() }
+
+#[route(trace)]
+pub fn trace_handler() -> String { "trace".to_string() }
 "#,
         );
 
         let (metadata, _file_asts) = collect_metadata(temp_dir.path(), folder_name, &[]).unwrap();
 
-        assert_eq!(metadata.routes.len(), 7);
+        assert_eq!(metadata.routes.len(), 8);
 
         let methods: Vec<&str> = metadata.routes.iter().map(|r| r.method.as_str()).collect();
         assert!(methods.contains(&"get"));
@@ -685,6 +796,7 @@ pub fn options_handler() -> String { "options".to_string() }
         assert!(methods.contains(&"delete"));
         assert!(methods.contains(&"head"));
         assert!(methods.contains(&"options"));
+        assert!(methods.contains(&"trace"));
 
         drop(temp_dir);
     }
@@ -743,13 +855,10 @@ pub fn get_users() -> String {
             return;
         }
 
-        // Attempt to collect metadata - should fail with "failed to read route file" error
-        let result = collect_metadata(temp_dir.path(), folder_name, &[]);
-
-        // Verify error message
-        assert!(result.is_err());
-        let error_msg = result.unwrap_err().to_string();
-        assert!(error_msg.contains("failed to read route file"));
+        // Attempt to collect metadata - the file is listed as unreadable
+        let (metadata, _) = collect_metadata(temp_dir.path(), folder_name, &[]).unwrap();
+        assert_eq!(metadata.invalid_files.len(), 1);
+        assert_eq!(metadata.invalid_files[0].error, "cannot be read");
 
         // Restore permissions so tempdir cleanup doesn't fail
         let permissions = fs::Permissions::from_mode(0o644);
@@ -811,10 +920,9 @@ pub fn get() -> String { "ok".to_string() }
         create_temp_file(&temp_dir, "invalid.rs", "{{{");
 
         // This should fail during syntax parsing, not file reading
-        let result = collect_metadata(temp_dir.path(), folder_name, &[]);
-        assert!(result.is_err());
-        let error_msg = result.unwrap_err().to_string();
-        assert!(error_msg.contains("syntax error"));
+        let (metadata, _) = collect_metadata(temp_dir.path(), folder_name, &[]).unwrap();
+        assert_eq!(metadata.invalid_files.len(), 1);
+        assert_ne!(metadata.invalid_files[0].error, "cannot be read");
 
         drop(temp_dir);
     }
@@ -945,6 +1053,23 @@ pub async fn get_users() -> String {
             description: Some("Get all users".to_string()),
             fn_item_str: "pub async fn get_users() -> String { \"users\".to_string() }".to_string(),
             file_path: Some(file_path_str.clone()),
+            security: None,
+            limits: None,
+            cache: None,
+            response: None,
+            request_body: None,
+            responses: None,
+            params: None,
+            links: None,
+            request_example: None,
+            response_example: None,
+            status: None,
+            sdk_group: None,
+            operation_id: None,
+            content_type: None,
+            sse_event: None,
+            summary: None,
+            deprecated: false,
         }];
 
         let (metadata, file_asts) =
@@ -995,6 +1120,23 @@ pub async fn get_user() -> String {
             fn_item_str: "pub async fn get_user(id: i32) -> String { \"user\".to_string() }"
                 .to_string(),
             file_path: Some(file_path_str.clone()),
+            security: None,
+            limits: None,
+            cache: None,
+            response: None,
+            request_body: None,
+            responses: None,
+            params: None,
+            links: None,
+            request_example: None,
+            response_example: None,
+            status: None,
+            sdk_group: None,
+            operation_id: None,
+            content_type: None,
+            sse_event: None,
+            summary: None,
+            deprecated: false,
         }];
 
         let (metadata, _) = collect_metadata(temp_dir.path(), folder_name, &route_storage).unwrap();
@@ -1034,6 +1176,23 @@ pub async fn list_users() -> String {
             description: None,
             fn_item_str: "pub async fn list_users() -> String { \"list\".to_string() }".to_string(),
             file_path: Some(file_path_str),
+            security: None,
+            limits: None,
+            cache: None,
+            response: None,
+            request_body: None,
+            responses: None,
+            params: None,
+            links: None,
+            request_example: None,
+            response_example: None,
+            status: None,
+            sdk_group: None,
+            operation_id: None,
+            content_type: None,
+            sse_event: None,
+            summary: None,
+            deprecated: false,
         }];
 
         let (metadata, _) = collect_metadata(temp_dir.path(), folder_name, &route_storage).unwrap();
@@ -1068,6 +1227,23 @@ pub async fn list_users() -> String {
                 "/// List all items\npub async fn get_items() -> String { \"items\".to_string() }"
                     .to_string(),
             file_path: Some(file_path_str),
+            security: None,
+            limits: None,
+            cache: None,
+            response: None,
+            request_body: None,
+            responses: None,
+            params: None,
+            links: None,
+            request_example: None,
+            response_example: None,
+            status: None,
+            sdk_group: None,
+            operation_id: None,
+            content_type: None,
+            sse_event: None,
+            summary: None,
+            deprecated: false,
         }];
 
         let (metadata, _) = collect_metadata(temp_dir.path(), folder_name, &route_storage).unwrap();
@@ -1075,7 +1251,8 @@ pub async fn list_users() -> String {
         assert_eq!(metadata.routes.len(), 1);
         let route = &metadata.routes[0];
         // Description should be extracted from the doc comment in fn_item_str
-        assert_eq!(route.description, Some("List all items".to_string()));
+        assert_eq!(route.summary, Some("List all items".to_string()));
+        assert_eq!(route.description, None);
 
         drop(temp_dir);
     }
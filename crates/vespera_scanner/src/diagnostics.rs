@@ -0,0 +1,231 @@
+//! Diagnostic codes for macro errors and warnings.
+//!
+//! Every diagnostic message carries its code (`[VESPERA001] #[route] attribute: ...`),
+//! so errors are easy to search for and each class can be configured on its own.
+//!
+//! Warnings are configured through environment variables holding code lists
+//! (comma or whitespace separated):
+//!
+//! - `VESPERA_STRICT=1` turns every warning into an error, `VESPERA_STRICT=VESPERA014`
+//!   only the listed ones.
+//! - `VESPERA_ALLOW=VESPERA004,VESPERA014` silences the listed warnings, unless
+//!   `VESPERA_STRICT` names them explicitly.
+//!
+//! Errors are always errors.
+
+use std::fmt::Display;
+
+use proc_macro2::Span;
+
+use crate::error::MacroResult;
+
+/// A class of macro diagnostic with a stable code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Diagnostic {
+    /// `VESPERA001`: a `#[route]` handler is not `pub`
+    RouteNotPublic,
+    /// `VESPERA002`: a `#[route]` handler is not `async`
+    RouteNotAsync,
+    /// `VESPERA003`: `#[route]` is applied to something other than a function
+    RouteNotFunction,
+    /// `VESPERA004` (warning): a handler argument is left out of the spec
+    UndocumentedArgument,
+    /// `VESPERA005` (warning): a handler's response type cannot be inferred
+    UninferableResponse,
+    /// `VESPERA006`: the route folder cannot be scanned
+    RouteFolderUnreadable,
+    /// `VESPERA007`: a file in the route folder cannot be read or parsed
+    RouteFileUnparsable,
+    /// `VESPERA008`: two different structs produce the same schema name
+    DuplicateSchemaName,
+    /// `VESPERA009`: a `links(...)` entry names an unknown `operationId`
+    DanglingLink,
+    /// `VESPERA010` (warning): a route uses an unknown HTTP method and is skipped
+    UnknownHttpMethod,
+    /// `VESPERA011`: the spec cannot be serialized
+    SpecSerialization,
+    /// `VESPERA012`: a spec file cannot be written
+    SpecWrite,
+    /// `VESPERA013` (warning): an entity column is missing from the migrations
+    SchemaDrift,
+    /// `VESPERA014` (warning): an unknown type is documented as a plain object
+    UnknownTypeDefaulted,
+}
+
+/// How a warning is reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Level {
+    /// Not reported
+    Allow,
+    /// Reported as a warning
+    Warn,
+    /// Reported as an error
+    Deny,
+}
+
+impl Diagnostic {
+    /// Every diagnostic, in code order
+    #[cfg(test)]
+    pub const ALL: [Self; 14] = [
+        Self::RouteNotPublic,
+        Self::RouteNotAsync,
+        Self::RouteNotFunction,
+        Self::UndocumentedArgument,
+        Self::UninferableResponse,
+        Self::RouteFolderUnreadable,
+        Self::RouteFileUnparsable,
+        Self::DuplicateSchemaName,
+        Self::DanglingLink,
+        Self::UnknownHttpMethod,
+        Self::SpecSerialization,
+        Self::SpecWrite,
+        Self::SchemaDrift,
+        Self::UnknownTypeDefaulted,
+    ];
+
+    pub const fn code(self) -> &'static str {
+        match self {
+            Self::RouteNotPublic => "VESPERA001",
+            Self::RouteNotAsync => "VESPERA002",
+            Self::RouteNotFunction => "VESPERA003",
+            Self::UndocumentedArgument => "VESPERA004",
+            Self::UninferableResponse => "VESPERA005",
+            Self::RouteFolderUnreadable => "VESPERA006",
+            Self::RouteFileUnparsable => "VESPERA007",
+            Self::DuplicateSchemaName => "VESPERA008",
+            Self::DanglingLink => "VESPERA009",
+            Self::UnknownHttpMethod => "VESPERA010",
+            Self::SpecSerialization => "VESPERA011",
+            Self::SpecWrite => "VESPERA012",
+            Self::SchemaDrift => "VESPERA013",
+            Self::UnknownTypeDefaulted => "VESPERA014",
+        }
+    }
+
+    /// Whether the diagnostic is a warning that can be allowed or denied.
+    pub const fn is_warning(self) -> bool {
+        matches!(
+            self,
+            Self::UndocumentedArgument
+                | Self::UninferableResponse
+                | Self::UnknownHttpMethod
+                | Self::SchemaDrift
+                | Self::UnknownTypeDefaulted
+        )
+    }
+
+    /// `detail` prefixed with the code.
+    pub fn message(self, detail: impl Display) -> String {
+        format!("[{}] {detail}", self.code())
+    }
+
+    /// An error at `span`.
+    pub fn error(self, span: Span, detail: impl Display) -> syn::Error {
+        syn::Error::new(span, self.message(detail))
+    }
+
+    /// An error at the macro call site.
+    pub fn error_call_site(self, detail: impl Display) -> syn::Error {
+        self.error(Span::call_site(), detail)
+    }
+
+    /// Level configured for this diagnostic by `VESPERA_STRICT` and `VESPERA_ALLOW`.
+    pub fn level(self) -> Level {
+        self.level_with(
+            std::env::var("VESPERA_STRICT").ok().as_deref(),
+            std::env::var("VESPERA_ALLOW").ok().as_deref(),
+        )
+    }
+
+    fn level_with(self, strict: Option<&str>, allow: Option<&str>) -> Level {
+        if !self.is_warning() {
+            return Level::Deny;
+        }
+        let strict = strict.unwrap_or_default().trim();
+        let lists = |list: &str| list_contains(list, self.code());
+        if lists(strict) {
+            return Level::Deny;
+        }
+        if allow.is_some_and(lists) {
+            return Level::Allow;
+        }
+        if matches!(strict, "1" | "true" | "all") {
+            Level::Deny
+        } else {
+            Level::Warn
+        }
+    }
+
+    /// Report a warning without a span: printed to stderr, or an error when denied.
+    pub fn report(self, detail: impl Display) -> MacroResult<()> {
+        match self.level() {
+            Level::Allow => Ok(()),
+            Level::Warn => {
+                eprintln!("vespera: warning: {}", self.message(detail));
+                Ok(())
+            }
+            Level::Deny => Err(self.error_call_site(detail)),
+        }
+    }
+}
+
+/// Whether a comma or whitespace separated `list` names `code` (case-insensitive).
+fn list_contains(list: &str, code: &str) -> bool {
+    list.split(|c: char| c == ',' || c.is_whitespace())
+        .any(|item| item.eq_ignore_ascii_case(code))
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[test]
+    fn test_codes_are_sequential() {
+        for (i, diagnostic) in Diagnostic::ALL.iter().enumerate() {
+            assert_eq!(diagnostic.code(), format!("VESPERA{:03}", i + 1));
+        }
+    }
+
+    #[test]
+    fn test_message_starts_with_code() {
+        assert_eq!(
+            Diagnostic::RouteNotPublic.message("function must be public"),
+            "[VESPERA001] function must be public"
+        );
+        let error = Diagnostic::DanglingLink.error_call_site("unknown operation");
+        assert_eq!(error.to_string(), "[VESPERA009] unknown operation");
+    }
+
+    #[rstest]
+    #[case(None, None, Level::Warn)]
+    #[case(Some(""), None, Level::Warn)]
+    #[case(Some("0"), None, Level::Warn)]
+    #[case(Some("1"), None, Level::Deny)]
+    #[case(Some("true"), None, Level::Deny)]
+    #[case(Some("VESPERA014"), None, Level::Deny)]
+    #[case(Some("VESPERA004, vespera014"), None, Level::Deny)]
+    #[case(Some("VESPERA004"), None, Level::Warn)]
+    #[case(None, Some("VESPERA014"), Level::Allow)]
+    #[case(Some("1"), Some("VESPERA004 VESPERA014"), Level::Allow)]
+    #[case(Some("VESPERA014"), Some("VESPERA014"), Level::Deny)]
+    fn test_warning_level(
+        #[case] strict: Option<&str>,
+        #[case] allow: Option<&str>,
+        #[case] expected: Level,
+    ) {
+        assert_eq!(
+            Diagnostic::UnknownTypeDefaulted.level_with(strict, allow),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_errors_cannot_be_allowed() {
+        assert_eq!(
+            Diagnostic::RouteNotPublic.level_with(None, Some("VESPERA001")),
+            Level::Deny
+        );
+    }
+}
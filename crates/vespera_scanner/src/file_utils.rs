@@ -0,0 +1,564 @@
+//! Route folder scanning and module path derivation.
+//!
+//! Scans only collect `.rs` files. Hidden entries (`.git`, editor lock files) and `target`
+//! directories are always skipped, as are directories already being scanned higher up
+//! (symlink cycles). `vespera!(ignore = [...])` adds patterns through
+//! [`with_ignore_patterns`].
+
+use std::{
+    cell::RefCell,
+    io,
+    path::{Component, Path, PathBuf},
+};
+
+thread_local! {
+    static IGNORE_PATTERNS: RefCell<Vec<IgnorePattern>> = const { RefCell::new(Vec::new()) };
+}
+
+/// A gitignore-style pattern of paths to leave out of a scan.
+///
+/// `*` and `?` match within a name and `**` matches any number of directories. A pattern
+/// without a `/` matches a name at any depth (`generated/`, `*_old.rs`); one with a `/`
+/// is relative to the scanned folder (`admin/internal.rs`, `**/fixtures`). A trailing `/`
+/// only matches directories.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IgnorePattern {
+    segments: Vec<String>,
+    dir_only: bool,
+}
+
+impl IgnorePattern {
+    pub fn new(pattern: &str) -> Self {
+        let dir_only = pattern.ends_with('/');
+        let trimmed = pattern.trim_matches('/');
+        let mut segments: Vec<String> = trimmed
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .map(str::to_string)
+            .collect();
+        if !trimmed.contains('/') {
+            segments.insert(0, "**".to_string());
+        }
+        Self { segments, dir_only }
+    }
+
+    /// Whether the entry at `path` (segments relative to the scanned folder) is ignored.
+    fn matches(&self, path: &[&str], is_dir: bool) -> bool {
+        (is_dir || !self.dir_only) && match_segments(&self.segments, path)
+    }
+}
+
+fn match_segments(pattern: &[String], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((first, rest)) if first == "**" => {
+            (0..=path.len()).any(|skip| match_segments(rest, &path[skip..]))
+        }
+        Some((first, rest)) => path.split_first().is_some_and(|(name, path_rest)| {
+            match_name(first.as_bytes(), name.as_bytes()) && match_segments(rest, path_rest)
+        }),
+    }
+}
+
+fn match_name(pattern: &[u8], name: &[u8]) -> bool {
+    match pattern.split_first() {
+        None => name.is_empty(),
+        Some((b'*', rest)) => (0..=name.len()).any(|skip| match_name(rest, &name[skip..])),
+        Some((b'?', rest)) => !name.is_empty() && match_name(rest, &name[1..]),
+        Some((c, rest)) => name.first() == Some(c) && match_name(rest, &name[1..]),
+    }
+}
+
+/// Run `f` with `patterns` applied to every [`collect_files`] scan.
+pub fn with_ignore_patterns<R>(patterns: &[String], f: impl FnOnce() -> R) -> R {
+    let patterns = patterns.iter().map(|p| IgnorePattern::new(p)).collect();
+    let previous = IGNORE_PATTERNS.with(|active| active.replace(patterns));
+    let result = f();
+    IGNORE_PATTERNS.with(|active| *active.borrow_mut() = previous);
+    result
+}
+
+/// `.rs` files of a route folder, sorted, honoring [`with_ignore_patterns`].
+pub fn collect_files(folder_path: &Path) -> io::Result<Vec<PathBuf>> {
+    let patterns = IGNORE_PATTERNS.with(|active| active.borrow().clone());
+    collect_rs_files(folder_path, &patterns)
+}
+
+/// `.rs` files under `root`, sorted, leaving out entries matching `ignore`.
+pub fn collect_rs_files(root: &Path, ignore: &[IgnorePattern]) -> io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let mut ancestors = Vec::new();
+    scan_dir(root, &mut Vec::new(), ignore, &mut ancestors, &mut files)?;
+    // `read_dir` order is platform-dependent; routes are documented in file order
+    files.sort();
+    Ok(files)
+}
+
+fn scan_dir(
+    dir: &Path,
+    relative: &mut Vec<String>,
+    ignore: &[IgnorePattern],
+    ancestors: &mut Vec<PathBuf>,
+    files: &mut Vec<PathBuf>,
+) -> io::Result<()> {
+    // A symlink back to a directory being scanned would recurse forever
+    let canonical = dir.canonicalize()?;
+    if ancestors.contains(&canonical) {
+        return Ok(());
+    }
+    ancestors.push(canonical);
+    for entry in std::fs::read_dir(dir)? {
+        // `entry.path()` is already `dir/name`, through symlinks as named
+        let path = entry?.path();
+        let Some(name) = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+        else {
+            continue;
+        };
+        let is_dir = path.is_dir();
+        if name.starts_with('.') || (is_dir && name == "target") {
+            continue;
+        }
+        relative.push(name);
+        let segments: Vec<&str> = relative.iter().map(String::as_str).collect();
+        if !ignore
+            .iter()
+            .any(|pattern| pattern.matches(&segments, is_dir))
+        {
+            if is_dir {
+                scan_dir(&path, relative, ignore, ancestors, files)?;
+            } else if path.is_file() && path.extension().is_some_and(|ext| ext == "rs") {
+                files.push(path);
+            }
+        }
+        relative.pop();
+    }
+    ancestors.pop();
+    Ok(())
+}
+
+/// Module path segments of `file` relative to `base_path`
+/// (`routes/admin/users.rs` → `["admin", "users"]`, `routes/admin/mod.rs` → `["admin"]`).
+///
+/// A `file` outside `base_path` keeps all of its segments.
+pub fn file_to_segments(file: &Path, base_path: &Path) -> Vec<String> {
+    let mut segments = path_segments(file);
+    let base = path_segments(base_path);
+    if segments.starts_with(&base) {
+        segments.drain(..base.len());
+    }
+    if let Some(last) = segments.last_mut()
+        && let Some(stem) = last.strip_suffix(".rs")
+    {
+        *last = stem.to_string();
+    }
+    if segments.last().is_some_and(|last| last == "mod") {
+        segments.pop();
+    }
+    segments
+}
+
+/// Names of the normal components of `path`, without root, `.` or `..`.
+///
+/// `\` separates names on every platform, so Windows paths handled as strings split the
+/// same way, and a leading drive (`C:`) is dropped. Names that are not valid UTF-8 are
+/// converted lossily.
+fn path_segments(path: &Path) -> Vec<String> {
+    let mut segments: Vec<String> = path
+        .components()
+        .filter_map(|component| match component {
+            Component::Normal(name) => Some(name.to_string_lossy()),
+            _ => None,
+        })
+        .flat_map(|name| {
+            name.split('\\')
+                .filter(|segment| !segment.is_empty() && *segment != ".")
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    if segments.first().is_some_and(|first| {
+        first.len() == 2
+            && first.ends_with(':')
+            && first.starts_with(|c: char| c.is_ascii_alphabetic())
+    }) {
+        segments.remove(0);
+    }
+    segments
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use rstest::rstest;
+    use tempfile::TempDir;
+
+    use super::*;
+
+    #[rstest]
+    // Simple file paths
+    #[case("routes/users.rs", "routes", vec!["users"])]
+    #[case("routes/posts.rs", "routes", vec!["posts"])]
+    #[case("routes/users.rs", "routes/", vec!["users"])]
+    // Nested directories
+    #[case("routes/admin/users.rs", "routes", vec!["admin", "users"])]
+    #[case("routes/api/v1/users.rs", "routes", vec!["api", "v1", "users"])]
+    #[case("routes/admin/settings.rs", "routes", vec!["admin", "settings"])]
+    // Deep nesting
+    #[case("routes/api/v1/users/profile.rs", "routes", vec!["api", "v1", "users", "profile"])]
+    // mod.rs files
+    #[case("routes/mod.rs", "routes", vec![])]
+    #[case("routes/admin/mod.rs", "routes", vec!["admin"])]
+    #[case("routes/api/v1/mod.rs", "routes", vec!["api", "v1"])]
+    // mod in middle (should not be removed)
+    #[case("routes/mod_users.rs", "routes", vec!["mod_users"])]
+    // Windows-style paths (backslashes)
+    #[case("routes\\users.rs", "routes", vec!["users"])]
+    #[case("routes\\admin\\users.rs", "routes", vec!["admin", "users"])]
+    #[case("routes\\mod.rs", "routes", vec![])]
+    // Files without .rs extension (should still work)
+    #[case("routes/users", "routes", vec!["users"])]
+    #[case("routes/admin/users", "routes", vec!["admin", "users"])]
+    // Empty segments
+    #[case("routes//users.rs", "routes", vec!["users"])]
+    #[case("routes///admin//users.rs", "routes", vec!["admin", "users"])]
+    // Base path not matching
+    #[case("/absolute/path/users.rs", "routes", vec!["absolute", "path", "users"])]
+    #[case("different/path/users.rs", "routes", vec!["different", "path", "users"])]
+    // Root level files
+    #[case("users.rs", ".", vec!["users"])]
+    #[case("mod.rs", ".", vec![])]
+    fn test_file_to_segments(
+        #[case] file_path: &str,
+        #[case] base_path: &str,
+        #[case] expected: Vec<&str>,
+    ) {
+        // Normalize paths by replacing backslashes with forward slashes
+        // This ensures tests work cross-platform (Windows uses \, Unix uses /)
+        let normalized_file_path = file_path.replace('\\', "/");
+        let normalized_base_path = base_path.replace('\\', "/");
+        let file = PathBuf::from(normalized_file_path);
+        let base = PathBuf::from(normalized_base_path);
+        let result = file_to_segments(&file, &base);
+        let expected_vec: Vec<String> = expected
+            .iter()
+            .map(std::string::ToString::to_string)
+            .collect();
+        assert_eq!(
+            result, expected_vec,
+            "Failed for file: {file_path}, base: {base_path}"
+        );
+    }
+
+    #[rstest]
+    #[case(r"C:\app\src\routes\admin\users.rs", r"C:\app\src\routes", vec!["admin", "users"])]
+    #[case(r"C:\app\src\routes\admin\mod.rs", r"C:\app\src\routes", vec!["admin"])]
+    #[case(r"routes\api\v1\mod.rs", "routes", vec!["api", "v1"])]
+    #[case(r"routes/admin\users.rs", "routes", vec!["admin", "users"])]
+    #[case(r"D:\elsewhere\users.rs", r"C:\app\src\routes", vec!["elsewhere", "users"])]
+    #[case("./routes/./admin/users.rs", "routes", vec!["admin", "users"])]
+    // Only a trailing `.rs` extension is removed
+    #[case("routes/docs.rs/users.rs", "routes", vec!["docs.rs", "users"])]
+    #[case("routes/users.rs.bak", "routes", vec!["users.rs.bak"])]
+    fn test_file_to_segments_unnormalized(
+        #[case] file_path: &str,
+        #[case] base_path: &str,
+        #[case] expected: Vec<&str>,
+    ) {
+        assert_eq!(
+            file_to_segments(Path::new(file_path), Path::new(base_path)),
+            expected
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_file_to_segments_non_utf8() {
+        use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+
+        let file = Path::new("routes")
+            .join(OsStr::from_bytes(b"caf\xe9"))
+            .join("users.rs");
+        assert_eq!(
+            file_to_segments(&file, Path::new("routes")),
+            vec!["caf\u{FFFD}", "users"]
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_collect_files_through_symlinked_folder() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        create_test_structure(
+            &temp_dir,
+            &[
+                ("shared/users/mod.rs", true),
+                ("shared/users/profile.rs", true),
+                ("routes/health.rs", true),
+            ],
+        )
+        .expect("Failed to create test structure");
+        let routes = temp_dir.path().join("routes");
+        std::os::unix::fs::symlink(temp_dir.path().join("shared"), routes.join("v2"))
+            .expect("Failed to create symlink");
+
+        // Reached through a symlinked routes folder as well
+        let linked_routes = temp_dir.path().join("linked_routes");
+        std::os::unix::fs::symlink(&routes, &linked_routes).expect("Failed to create symlink");
+
+        for folder in [routes, linked_routes] {
+            let segments: Vec<Vec<String>> = collect_files(&folder)
+                .expect("collect_files failed")
+                .iter()
+                .map(|file| file_to_segments(file, &folder))
+                .collect();
+            assert_eq!(
+                segments,
+                vec![
+                    vec!["health"],
+                    vec!["v2", "users"],
+                    vec!["v2", "users", "profile"],
+                ]
+            );
+        }
+    }
+
+    fn create_test_structure(
+        temp_dir: &TempDir,
+        structure: &[(&str, bool)],
+    ) -> Result<(), std::io::Error> {
+        // (path, is_file)
+        for (path, is_file) in structure {
+            let full_path = temp_dir.path().join(path);
+            if *is_file {
+                if let Some(parent) = full_path.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::write(&full_path, "test content")?;
+            } else {
+                fs::create_dir_all(&full_path)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn normalize_paths(paths: &[PathBuf], base: &Path) -> Vec<String> {
+        let mut normalized: Vec<String> = paths
+            .iter()
+            .map(|p| {
+                p.strip_prefix(base)
+                    .unwrap_or(p)
+                    .to_string_lossy()
+                    .replace('\\', "/")
+            })
+            .collect();
+        normalized.sort();
+        normalized
+    }
+
+    #[rstest]
+    // Empty directory
+    #[case(vec![], vec![])]
+    // Single file
+    #[case(vec![("file1.rs", true)], vec!["file1.rs"])]
+    // Multiple files in root
+    #[case(
+        vec![("file1.rs", true), ("file2.rs", true), ("file3.rs", true)],
+        vec!["file1.rs", "file2.rs", "file3.rs"]
+    )]
+    // Single nested directory with file
+    #[case(
+        vec![("subdir", false), ("subdir/file.rs", true)],
+        vec!["subdir/file.rs"]
+    )]
+    // Multiple nested directories
+    #[case(
+        vec![
+            ("dir1", false),
+            ("dir1/file1.rs", true),
+            ("dir2", false),
+            ("dir2/file2.rs", true),
+        ],
+        vec!["dir1/file1.rs", "dir2/file2.rs"]
+    )]
+    // Deep nesting
+    #[case(
+        vec![
+            ("a", false),
+            ("a/b", false),
+            ("a/b/c", false),
+            ("a/b/c/file.rs", true),
+        ],
+        vec!["a/b/c/file.rs"]
+    )]
+    // Mixed structure
+    #[case(
+        vec![
+            ("root.rs", true),
+            ("dir1", false),
+            ("dir1/file1.rs", true),
+            ("dir1/file2.rs", true),
+            ("dir2", false),
+            ("dir2/subdir", false),
+            ("dir2/subdir/file.rs", true),
+        ],
+        vec!["dir1/file1.rs", "dir1/file2.rs", "dir2/subdir/file.rs", "root.rs"]
+    )]
+    // Only .rs files are collected
+    #[case(
+        vec![
+            ("file.rs", true),
+            ("file.txt", true),
+            ("file.md", true),
+        ],
+        vec!["file.rs"]
+    )]
+    // Hidden entries and target directories are skipped
+    #[case(
+        vec![
+            ("users.rs", true),
+            (".#users.rs", true),
+            (".git/hooks.rs", true),
+            ("target/debug/build.rs", true),
+            ("admin/target.rs", true),
+        ],
+        vec!["admin/target.rs", "users.rs"]
+    )]
+    // Empty subdirectories (should be ignored)
+    #[case(
+        vec![
+            ("empty_dir", false),
+            ("file.rs", true),
+        ],
+        vec!["file.rs"]
+    )]
+    fn test_collect_files(#[case] structure: Vec<(&str, bool)>, #[case] expected_files: Vec<&str>) {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        create_test_structure(&temp_dir, &structure).expect("Failed to create test structure");
+
+        let result = collect_files(temp_dir.path()).expect("collect_files failed");
+        let mut normalized_result = normalize_paths(&result, temp_dir.path());
+        normalized_result.sort();
+
+        let mut expected_normalized: Vec<String> = expected_files
+            .iter()
+            .map(std::string::ToString::to_string)
+            .collect();
+        expected_normalized.sort();
+
+        assert_eq!(
+            normalized_result, expected_normalized,
+            "Failed for structure: {structure:?}"
+        );
+
+        temp_dir.close().expect("Failed to close temp dir");
+    }
+
+    #[rstest]
+    #[case("generated/", "generated", true, true)]
+    #[case("generated/", "api/generated", true, true)]
+    #[case("generated/", "generated", false, false)]
+    #[case("generated", "api/generated", false, true)]
+    #[case("*_old.rs", "admin/users_old.rs", false, true)]
+    #[case("*_old.rs", "admin/users.rs", false, false)]
+    #[case("admin/internal.rs", "admin/internal.rs", false, true)]
+    #[case("admin/internal.rs", "v2/admin/internal.rs", false, false)]
+    #[case("**/fixtures", "a/b/fixtures", true, true)]
+    #[case("api/**/legacy", "api/legacy", true, true)]
+    #[case("api/**/legacy", "api/v1/v2/legacy", true, true)]
+    #[case("v?", "v1", true, true)]
+    #[case("v?", "v10", true, false)]
+    fn test_ignore_pattern_matches(
+        #[case] pattern: &str,
+        #[case] path: &str,
+        #[case] is_dir: bool,
+        #[case] expected: bool,
+    ) {
+        let segments: Vec<&str> = path.split('/').collect();
+        assert_eq!(
+            IgnorePattern::new(pattern).matches(&segments, is_dir),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_collect_files_with_ignore_patterns() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        create_test_structure(
+            &temp_dir,
+            &[
+                ("users.rs", true),
+                ("users_old.rs", true),
+                ("generated/client.rs", true),
+                ("admin/generated/client.rs", true),
+                ("admin/settings.rs", true),
+            ],
+        )
+        .expect("Failed to create test structure");
+
+        let patterns = ["generated/".to_string(), "*_old.rs".to_string()];
+        let result = with_ignore_patterns(&patterns, || collect_files(temp_dir.path()))
+            .expect("collect_files failed");
+        assert_eq!(
+            normalize_paths(&result, temp_dir.path()),
+            vec!["admin/settings.rs", "users.rs"]
+        );
+
+        // Patterns only apply inside `with_ignore_patterns`
+        let result = collect_files(temp_dir.path()).expect("collect_files failed");
+        assert_eq!(result.len(), 5);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_collect_files_symlink_cycle() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        create_test_structure(&temp_dir, &[("admin/users.rs", true)])
+            .expect("Failed to create test structure");
+        // admin/parent -> .., admin/self -> .
+        std::os::unix::fs::symlink(temp_dir.path(), temp_dir.path().join("admin/parent"))
+            .expect("Failed to create symlink");
+        std::os::unix::fs::symlink(
+            temp_dir.path().join("admin"),
+            temp_dir.path().join("admin/self"),
+        )
+        .expect("Failed to create symlink");
+
+        let result = collect_files(temp_dir.path()).expect("collect_files failed");
+        assert_eq!(
+            normalize_paths(&result, temp_dir.path()),
+            vec!["admin/users.rs"]
+        );
+    }
+
+    #[test]
+    fn test_collect_files_nonexistent_directory() {
+        let nonexistent = PathBuf::from("/nonexistent/path/that/does/not/exist");
+        let result = collect_files(&nonexistent);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_collect_files_recursive_deep() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+
+        // Create a very deep nested structure
+        let mut path = temp_dir.path().to_path_buf();
+        for i in 0..5 {
+            path = path.join(format!("level{i}"));
+            fs::create_dir_all(&path).expect("Failed to create nested dir");
+        }
+
+        // Create a file at the deepest level
+        let file_path = path.join("deep_file.rs");
+        fs::write(&file_path, "content").expect("Failed to write file");
+
+        let result = collect_files(temp_dir.path()).expect("collect_files failed");
+        assert_eq!(result.len(), 1);
+        assert!(result[0].ends_with("deep_file.rs"));
+
+        temp_dir.close().expect("Failed to close temp dir");
+    }
+}
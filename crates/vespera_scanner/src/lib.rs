@@ -0,0 +1,226 @@
+//! Vespera route scanning and `OpenAPI` generation.
+//!
+//! Everything behind the Vespera macros lives here, so it can run both inside
+//! `vespera_macro` at compile time and inside `vespera`'s development spec reload
+//! (`hot-reload` feature) at runtime:
+//! - `#[vespera::route(...)]` - Mark a function as a route handler
+//! - `defaults!(...)` - Declare route defaults for a module
+//! - `#[derive(Schema)]` - Register a type for `OpenAPI` schema generation
+//! - `schema!(...)` - Get `OpenAPI` schema at compile time
+//! - `vespera!(...)` - Generate Axum router with `OpenAPI`
+//! - `export_app!(...)` - Export router for merging
+//!
+//! The functions below take and return `proc_macro2` token streams; `vespera_macro`
+//! only converts from and to `proc_macro` ones.
+//!
+//! # Architecture
+//!
+//! ```text
+//! ┌─────────────────────────────────────────────────────────────────┐
+//! │ Compile-time (vespera! macro)                                    │
+//! ├─────────────────────────────────────────────────────────────────┤
+//! │ 1. Scan src/routes/ for .rs files              [collector]       │
+//! │ 2. Parse #[route] attributes                   [args, route]     │
+//! │ 3. Extract handler signatures                  [parser]          │
+//! │ 4. Convert Rust types → JSON Schema            [parser/schema]   │
+//! │ 5. Build OpenAPI document                      [openapi_gen]     │
+//! │ 6. Write openapi.json to disk                  [vespera_impl]    │
+//! │ 7. Generate Axum Router TokenStream            [router_codegen]  │
+//! │ 8. Inject Swagger/ReDoc HTML routes           [router_codegen]  │
+//! └─────────────────────────────────────────────────────────────────┘
+//! ```
+//!
+//! [`regenerate_spec`] runs steps 1-5 again at runtime for the development spec reload.
+//!
+//! # Module Organization
+//!
+//! - `args` - Parse `#[route(...)]` attribute arguments
+//! - `collector` - Filesystem scanning and route discovery
+//! - `error` - Unified error handling
+//! - `http` - HTTP method constants and validation
+//! - `metadata` - Type definitions for collected metadata
+//! - `method` - HTTP method token stream generation
+//! - `openapi_generator` - OpenAPI spec assembly
+//! - `parser` - Type extraction and schema generation
+//! - `route` - Route information structures
+//! - `route_impl` - Route attribute macro implementation
+//! - `router_codegen` - Router and macro input parsing
+//! - `schema_impl` - Schema derive macro implementation
+//! - `schema_macro` - `schema_type!` macro implementation
+//! - `vespera_impl` - Main macro orchestration
+
+mod args;
+mod collector;
+mod cron_impl;
+mod diagnostics;
+mod error;
+mod file_utils;
+mod http;
+mod metadata;
+mod method;
+mod openapi_generator;
+
+mod multipart_impl;
+mod parser;
+mod route;
+mod route_impl;
+mod router_codegen;
+mod schema_impl;
+mod schema_macro;
+mod vespera_impl;
+
+pub(crate) use cron_impl::CRON_STORAGE;
+use proc_macro2::TokenStream;
+pub(crate) use route_impl::ROUTE_STORAGE;
+pub(crate) use schema_impl::SCHEMA_STORAGE;
+pub use vespera_impl::regenerate_spec;
+
+use crate::{
+    router_codegen::{AutoRouterInput, ExportAppInput, process_vespera_input},
+    vespera_impl::{process_export_app, process_vespera_macro},
+};
+
+/// `#[route]` attribute macro
+#[cfg(not(tarpaulin_include))]
+pub fn route(attr: TokenStream, item: TokenStream) -> TokenStream {
+    route_impl::process_route_attribute(attr, item).unwrap_or_else(|e| e.to_compile_error())
+}
+
+/// `defaults!` macro: arguments are only validated here
+#[cfg(not(tarpaulin_include))]
+pub fn defaults(input: TokenStream) -> TokenStream {
+    match syn::parse2::<args::DefaultsArgs>(input) {
+        Ok(_) => TokenStream::new(),
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+/// `#[cron]` attribute macro
+#[cfg(not(tarpaulin_include))]
+pub fn cron(attr: TokenStream, item: TokenStream) -> TokenStream {
+    cron_impl::process_cron_attribute(attr, item).unwrap_or_else(|e| e.to_compile_error())
+}
+
+/// `#[derive(Schema)]`: registers the type for `vespera!`
+#[cfg(not(tarpaulin_include))]
+pub fn derive_schema(input: TokenStream) -> TokenStream {
+    let input = match syn::parse2::<syn::DeriveInput>(input) {
+        Ok(input) => input,
+        Err(e) => return e.to_compile_error(),
+    };
+    let (metadata, expanded) = schema_impl::process_derive_schema(&input);
+    let name = metadata.name.clone();
+    SCHEMA_STORAGE
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .insert(name, metadata);
+    expanded
+}
+
+/// `#[derive(Multipart)]`
+#[cfg(not(tarpaulin_include))]
+pub fn derive_multipart(input: TokenStream) -> TokenStream {
+    match syn::parse2::<syn::DeriveInput>(input) {
+        Ok(input) => multipart_impl::process_derive(&input),
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+/// `schema!` macro
+#[cfg(not(tarpaulin_include))]
+pub fn schema(input: TokenStream) -> TokenStream {
+    let input = match syn::parse2::<schema_macro::SchemaInput>(input) {
+        Ok(input) => input,
+        Err(e) => return e.to_compile_error(),
+    };
+
+    // Get stored schemas
+    let storage = SCHEMA_STORAGE
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+
+    schema_macro::generate_schema_code(&input, &storage).unwrap_or_else(|e| e.to_compile_error())
+}
+
+/// `schema_type!` macro
+#[cfg(not(tarpaulin_include))]
+pub fn schema_type(input: TokenStream) -> TokenStream {
+    let input = match syn::parse2::<schema_macro::SchemaTypeInput>(input) {
+        Ok(input) => input,
+        Err(e) => return e.to_compile_error(),
+    };
+
+    // Get stored schemas and generate code
+    let (tokens, generated_metadata) = {
+        let storage = SCHEMA_STORAGE
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        match schema_macro::generate_schema_type_code(&input, &storage) {
+            Ok(result) => result,
+            Err(e) => return e.to_compile_error(),
+        }
+    };
+
+    // If custom name is provided, register the schema directly
+    // This ensures it appears in OpenAPI even when `ignore` is set
+    if let Some(metadata) = generated_metadata {
+        let name = metadata.name.clone();
+        SCHEMA_STORAGE
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .insert(name, metadata);
+    }
+    tokens
+}
+
+/// `vespera!` macro
+#[cfg(not(tarpaulin_include))]
+pub fn vespera(input: TokenStream) -> TokenStream {
+    let source = input.to_string();
+    let input = match syn::parse2::<AutoRouterInput>(input) {
+        Ok(input) => input,
+        Err(e) => return e.to_compile_error(),
+    };
+    let processed = process_vespera_input(input);
+    let schema_storage = SCHEMA_STORAGE
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    let route_storage = ROUTE_STORAGE
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+
+    process_vespera_macro(&processed, &schema_storage, &route_storage, &source)
+        .unwrap_or_else(|e| e.to_compile_error())
+}
+
+/// `export_app!` macro
+#[cfg(not(tarpaulin_include))]
+pub fn export_app(input: TokenStream) -> TokenStream {
+    let ExportAppInput { name, dir } = match syn::parse2::<ExportAppInput>(input) {
+        Ok(input) => input,
+        Err(e) => return e.to_compile_error(),
+    };
+    let folder_name = dir
+        .map(|d| d.value())
+        .or_else(|| std::env::var("VESPERA_DIR").ok())
+        .unwrap_or_else(|| "routes".to_string());
+    let schema_storage = SCHEMA_STORAGE
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    let Ok(manifest_dir) = std::env::var("CARGO_MANIFEST_DIR") else {
+        return syn::Error::new(proc_macro2::Span::call_site(), "export_app! macro: CARGO_MANIFEST_DIR is not set. This macro must be used within a cargo build.").to_compile_error();
+    };
+
+    let route_storage = ROUTE_STORAGE
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+
+    process_export_app(
+        &name,
+        &folder_name,
+        &schema_storage,
+        &manifest_dir,
+        &route_storage,
+    )
+    .unwrap_or_else(|e| e.to_compile_error())
+}
@@ -0,0 +1,623 @@
+//! Metadata collection and storage for routes and schemas
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+use vespera_core::route::SecurityRequirement;
+#[cfg(feature = "unstable-metadata")]
+use vespera_core::{
+    metadata::{ApiMetadata, METADATA_VERSION, RouteInfo, StructInfo},
+    route::HttpMethod,
+};
+
+use crate::diagnostics::Diagnostic;
+
+/// Route metadata
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RouteMetadata {
+    /// HTTP method
+    pub method: String,
+    /// Route path
+    pub path: String,
+    /// Function name
+    pub function_name: String,
+    /// Module path
+    pub module_path: String,
+    /// File path
+    pub file_path: String,
+    /// Function signature (as string for serialization)
+    pub signature: String,
+    /// Additional error status codes from `error_status` attribute
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_status: Option<Vec<u16>>,
+    /// Tags for `OpenAPI` grouping
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+    /// Description for `OpenAPI` (from route attribute or the doc comment after its first line)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Security requirement alternatives (OR of AND-combined schemes)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub security: Option<Vec<SecurityRequirement>>,
+    /// Request body size and timeout limits from `limits = (...)`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limits: Option<RouteLimits>,
+    /// `Cache-Control` policy of the success responses, from `cache = (...)`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache: Option<RouteCache>,
+    /// Return type documented instead of the handler's, from `response = ...`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response: Option<String>,
+    /// Body extractor documented instead of the handler's, from `request_body = ...`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_body: Option<String>,
+    /// Per-status responses documented instead of the inferred ones, from `responses(...)`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub responses: Option<Vec<(u16, String)>>,
+    /// Parameter schemas documented instead of the inferred ones, from `params(...)`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub params: Option<Vec<(String, String)>>,
+    /// Links from the success responses to other operations, from `links(...)`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub links: Option<Vec<RouteLink>>,
+    /// Request body example, from `request_example = json!(...)`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_example: Option<serde_json::Value>,
+    /// Success response example, from `response_example = json!(...)`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_example: Option<serde_json::Value>,
+    /// Success status from `status = 201`, else inferred from `(StatusCode, T)` handlers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<u16>,
+    /// SDK group emitted as `x-sdk-group`, from `sdk_group = "..."`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sdk_group: Option<String>,
+    /// `operationId` from `operation_id = "..."`, see [`RouteMetadata::operation_id`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub operation_id: Option<String>,
+    /// Media type of raw byte bodies, from `content_type = "image/png"`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    /// Event payload type of a `text/event-stream` response, from `sse_event = ChatEvent`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sse_event: Option<String>,
+    /// Summary for `OpenAPI` (from route attribute or the doc comment's first line)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    /// Whether the operation is deprecated (`deprecated` route flag)
+    #[serde(default)]
+    pub deprecated: bool,
+}
+
+/// 64-bit FNV-1a hash of `bytes`.
+///
+/// Unlike `DefaultHasher`, the result is the same across builds and toolchains, so it
+/// can be persisted and compared by other tools.
+pub fn stable_hash(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Stable hash of a value's JSON serialization.
+fn content_hash_of(value: &impl Serialize) -> u64 {
+    stable_hash(serde_json::to_string(value).unwrap_or_default().as_bytes())
+}
+
+impl RouteMetadata {
+    /// Stable hash of everything the route contributes to the spec, see [`stable_hash`]
+    pub fn content_hash(&self) -> u64 {
+        content_hash_of(self)
+    }
+
+    /// The route's `operationId`: the explicit `operation_id = "..."`, or the module path
+    /// below the route folder and the function name joined with `_`
+    /// (`routes::admin::users::list` becomes `admin_users_list`).
+    pub fn operation_id(&self) -> String {
+        self.operation_id.clone().unwrap_or_else(|| {
+            self.module_path
+                .split("::")
+                .skip(1)
+                .filter(|segment| !segment.is_empty())
+                .chain(std::iter::once(self.function_name.as_str()))
+                .collect::<Vec<_>>()
+                .join("_")
+        })
+    }
+}
+
+/// Per-route limits from `limits = (body = "1MB", timeout = "30s")`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RouteLimits {
+    /// Maximum request body size in bytes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<u64>,
+    /// Request timeout in milliseconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_ms: Option<u64>,
+}
+
+/// Per-route cache policy from `cache = (max_age = 60, public)`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct RouteCache {
+    /// `max-age` in seconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_age: Option<u64>,
+    /// `s-maxage` in seconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub s_maxage: Option<u64>,
+    /// `public`
+    #[serde(default)]
+    pub public: bool,
+    /// `private`
+    #[serde(default)]
+    pub private: bool,
+    /// `no-cache`
+    #[serde(default)]
+    pub no_cache: bool,
+    /// `no-store`
+    #[serde(default)]
+    pub no_store: bool,
+    /// `must-revalidate`
+    #[serde(default)]
+    pub must_revalidate: bool,
+    /// `immutable`
+    #[serde(default)]
+    pub immutable: bool,
+    /// Whether the generated router also sets the header (`apply` flag)
+    #[serde(default)]
+    pub apply: bool,
+}
+
+/// A response link from `links(GetUser = (operation_id = "users_get_user", ...))`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RouteLink {
+    /// Link name, the key under the response's `links`
+    pub name: String,
+    /// `operationId` of the linked operation
+    pub operation_id: String,
+    /// Linked operation's parameters, as runtime expressions or literal values
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub parameters: Vec<(String, String)>,
+    /// Linked operation's request body, as a runtime expression or literal value
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_body: Option<String>,
+    /// Link description
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// Struct metadata
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StructMetadata {
+    /// Struct name
+    pub name: String,
+    /// Struct definition (as string for serialization)
+    pub definition: String,
+    /// Whether to include in `OpenAPI` spec (components/schemas)
+    /// - true: from #[derive(Schema)] - appears in openapi.json (DEFAULT)
+    /// - false: from cross-file lookup - only for `schema_type`! source, NOT in openapi.json
+    #[serde(default = "default_include_in_openapi")]
+    pub include_in_openapi: bool,
+    /// Pre-extracted default values for fields with `#[serde(default = "fn_name")]`.
+    /// Key: Rust field name, Value: extracted default value.
+    /// Populated by `#[derive(Schema)]` to avoid AST re-parsing in `vespera!()`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub field_defaults: BTreeMap<String, serde_json::Value>,
+}
+
+const fn default_include_in_openapi() -> bool {
+    true
+}
+
+impl Default for StructMetadata {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            definition: String::new(),
+            include_in_openapi: true,
+            field_defaults: BTreeMap::new(),
+        }
+    }
+}
+
+impl StructMetadata {
+    /// Create a new `StructMetadata` with `include_in_openapi` defaulting to true
+    pub const fn new(name: String, definition: String) -> Self {
+        Self {
+            name,
+            definition,
+            include_in_openapi: true,
+            field_defaults: BTreeMap::new(),
+        }
+    }
+
+    /// Stable hash of the name, definition and defaults, see [`stable_hash`]
+    pub fn content_hash(&self) -> u64 {
+        content_hash_of(self)
+    }
+
+    /// Create a new `StructMetadata` for model types (not included in `OpenAPI`)
+    pub const fn new_model(name: String, definition: String) -> Self {
+        Self {
+            name,
+            definition,
+            include_in_openapi: false,
+            field_defaults: BTreeMap::new(),
+        }
+    }
+}
+
+/// Cron job metadata
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CronMetadata {
+    /// Cron expression (e.g., "0 */5 * * * *")
+    pub expression: String,
+    /// Function name
+    pub function_name: String,
+    /// Module path (e.g., "cron::cleanup")
+    pub module_path: String,
+    /// File path
+    pub file_path: String,
+}
+
+/// Route defaults declared by a module via `vespera::defaults!(...)`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModuleDefaults {
+    /// Tags prepended to every route's tags
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Error status codes added to every route
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub error_status: Vec<u16>,
+    /// Security alternatives for routes without their own
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub security: Option<Vec<SecurityRequirement>>,
+}
+
+/// A route file that could not be read or parsed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InvalidFile {
+    /// File path
+    pub file_path: String,
+    /// Why it could not be parsed (e.g., "line 3, column 9: expected `;`")
+    pub error: String,
+}
+
+/// Collected metadata
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CollectedMetadata {
+    /// Routes
+    pub routes: Vec<RouteMetadata>,
+    /// Structs
+    pub structs: Vec<StructMetadata>,
+    /// Cron jobs
+    #[serde(default)]
+    pub crons: Vec<CronMetadata>,
+    /// Module defaults keyed by module path (e.g., "routes::admin")
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub module_defaults: BTreeMap<String, ModuleDefaults>,
+    /// Route files left out because they could not be read or parsed
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub invalid_files: Vec<InvalidFile>,
+}
+
+impl CollectedMetadata {
+    pub const fn new() -> Self {
+        Self {
+            routes: Vec::new(),
+            structs: Vec::new(),
+            crons: Vec::new(),
+            module_defaults: BTreeMap::new(),
+            invalid_files: Vec::new(),
+        }
+    }
+
+    /// The public view of the routes and structs, written for companion tools.
+    ///
+    /// Routes with an unknown HTTP method are left out.
+    #[cfg(feature = "unstable-metadata")]
+    pub fn to_api_metadata(&self) -> ApiMetadata {
+        ApiMetadata {
+            version: METADATA_VERSION,
+            routes: self
+                .routes
+                .iter()
+                .filter_map(|route| {
+                    Some(RouteInfo {
+                        operation_id: route.operation_id(),
+                        method: HttpMethod::try_from(route.method.as_str()).ok()?,
+                        path: route.path.clone(),
+                        function_name: route.function_name.clone(),
+                        module_path: route.module_path.clone(),
+                        file_path: route.file_path.clone(),
+                        tags: route.tags.clone().unwrap_or_default(),
+                        security: route.security.clone(),
+                        deprecated: route.deprecated,
+                    })
+                })
+                .collect(),
+            structs: self
+                .structs
+                .iter()
+                .map(|s| StructInfo {
+                    name: s.name.clone(),
+                    definition: s.definition.clone(),
+                    in_openapi: s.include_in_openapi,
+                })
+                .collect(),
+        }
+    }
+
+    /// Check for duplicate schema names among `include_in_openapi` structs.
+    /// Returns `Err` with a descriptive message if duplicates are found.
+    pub fn check_duplicate_schema_names(&self) -> Result<(), String> {
+        let mut seen: HashMap<&str, usize> = HashMap::new();
+        for (i, s) in self.structs.iter().enumerate() {
+            if !s.include_in_openapi {
+                continue;
+            }
+            if let Some(&prev_idx) = seen.get(s.name.as_str()) {
+                // Only report if definitions actually differ (identical re-registration is OK)
+                if self.structs[prev_idx].definition != s.definition {
+                    return Err(Diagnostic::DuplicateSchemaName.message(format_args!(
+                        "Duplicate OpenAPI schema name '{}'. Two different structs produce the same schema name, which would corrupt the OpenAPI spec. Rename one of them or use #[schema(name = \"...\")].",
+                        s.name
+                    )));
+                }
+            } else {
+                seen.insert(&s.name, i);
+            }
+        }
+        Ok(())
+    }
+
+    /// Check that every `links(...)` entry names the `operationId` of a collected route.
+    /// Returns `Err` with a descriptive message for the first dangling link.
+    pub fn check_link_targets(&self) -> Result<(), String> {
+        let operation_ids: HashSet<String> = self
+            .routes
+            .iter()
+            .map(RouteMetadata::operation_id)
+            .collect();
+        for route in &self.routes {
+            for link in route.links.iter().flatten() {
+                if !operation_ids.contains(&link.operation_id) {
+                    return Err(Diagnostic::DanglingLink.message(format_args!(
+                        "Link '{}' of '{}' points to operationId '{}', which no route has. Use the linked route's operationId: its `operation_id = \"...\"`, or its module path and function name joined with `_`.",
+                        link.name, route.function_name, link.operation_id
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn route(module_path: &str, operation_id: Option<&str>) -> RouteMetadata {
+        RouteMetadata {
+            method: "get".to_string(),
+            path: "/".to_string(),
+            function_name: "list".to_string(),
+            module_path: module_path.to_string(),
+            operation_id: operation_id.map(str::to_string),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_route_metadata_operation_id() {
+        assert_eq!(
+            route("routes::admin::users", None).operation_id(),
+            "admin_users_list"
+        );
+        assert_eq!(route("routes::", None).operation_id(), "list");
+        assert_eq!(
+            route("routes::users", Some("listUsers")).operation_id(),
+            "listUsers"
+        );
+    }
+
+    #[test]
+    fn test_struct_metadata_new() {
+        let meta = StructMetadata::new("User".to_string(), "struct User {}".to_string());
+        assert_eq!(meta.name, "User");
+        assert_eq!(meta.definition, "struct User {}");
+        assert!(meta.include_in_openapi); // Should default to true
+    }
+
+    #[test]
+    fn test_struct_metadata_new_model() {
+        let meta = StructMetadata::new_model("Model".to_string(), "struct Model {}".to_string());
+        assert_eq!(meta.name, "Model");
+        assert_eq!(meta.definition, "struct Model {}");
+        assert!(!meta.include_in_openapi); // Should be false for models
+    }
+
+    #[test]
+    fn test_struct_metadata_default() {
+        let meta = StructMetadata::default();
+        assert_eq!(meta.name, "");
+        assert_eq!(meta.definition, "");
+        assert!(meta.include_in_openapi); // Default to true
+    }
+
+    #[test]
+    fn test_struct_metadata_serde_with_include_in_openapi() {
+        let json = r#"{"name":"User","definition":"struct User {}","include_in_openapi":false}"#;
+        let meta: StructMetadata = serde_json::from_str(json).unwrap();
+        assert_eq!(meta.name, "User");
+        assert!(!meta.include_in_openapi);
+    }
+
+    #[test]
+    fn test_struct_metadata_serde_without_include_in_openapi() {
+        // This triggers the default_include_in_openapi() function (lines 45-46)
+        let json = r#"{"name":"User","definition":"struct User {}"}"#;
+        let meta: StructMetadata = serde_json::from_str(json).unwrap();
+        assert_eq!(meta.name, "User");
+        assert!(meta.include_in_openapi); // Should default to true via serde default
+    }
+
+    #[test]
+    fn test_struct_metadata_roundtrip() {
+        let original =
+            StructMetadata::new("Test".to_string(), "struct Test { x: i32 }".to_string());
+        let json = serde_json::to_string(&original).unwrap();
+        let restored: StructMetadata = serde_json::from_str(&json).unwrap();
+        assert_eq!(original.name, restored.name);
+        assert_eq!(original.definition, restored.definition);
+        assert_eq!(original.include_in_openapi, restored.include_in_openapi);
+        assert!(restored.field_defaults.is_empty());
+    }
+
+    #[test]
+    fn test_stable_hash_fnv1a_vectors() {
+        assert_eq!(stable_hash(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(stable_hash(b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(stable_hash(b"foobar"), 0x8594_4171_f739_67e8);
+    }
+
+    #[test]
+    fn test_content_hash_tracks_content() {
+        let user = StructMetadata::new("User".into(), "struct User { id: i32 }".into());
+        assert_eq!(user.content_hash(), user.clone().content_hash());
+
+        let changed = StructMetadata::new("User".into(), "struct User { id: i64 }".into());
+        assert_ne!(user.content_hash(), changed.content_hash());
+
+        let list = route("routes::users", None);
+        let mut deprecated = list.clone();
+        deprecated.deprecated = true;
+        assert_eq!(
+            list.content_hash(),
+            route("routes::users", None).content_hash()
+        );
+        assert_ne!(list.content_hash(), deprecated.content_hash());
+    }
+
+    #[test]
+    #[cfg(feature = "unstable-metadata")]
+    fn test_to_api_metadata() {
+        let mut meta = CollectedMetadata::new();
+        let mut tagged = route("routes::admin::users", None);
+        tagged.tags = Some(vec!["admin".to_string()]);
+        meta.routes.push(tagged);
+        let mut unknown_method = route("routes::users", None);
+        unknown_method.method = "connect".to_string();
+        meta.routes.push(unknown_method);
+        meta.structs
+            .push(StructMetadata::new("User".into(), "struct User {}".into()));
+        meta.structs.push(StructMetadata::new_model(
+            "Model".into(),
+            "struct Model {}".into(),
+        ));
+
+        let api = meta.to_api_metadata();
+        assert_eq!(api.version, METADATA_VERSION);
+        assert_eq!(api.routes.len(), 1);
+        let route = api.route("admin_users_list").unwrap();
+        assert_eq!(route.method, HttpMethod::Get);
+        assert_eq!(route.tags, vec!["admin"]);
+        assert_eq!(
+            api.schemas().map(|s| s.name.as_str()).collect::<Vec<_>>(),
+            vec!["User"]
+        );
+        assert!(api.struct_info("Model").is_some());
+    }
+
+    #[test]
+    fn test_collected_metadata_new() {
+        let meta = CollectedMetadata::new();
+        assert!(meta.routes.is_empty());
+        assert!(meta.structs.is_empty());
+    }
+
+    #[test]
+    fn test_check_duplicate_schema_names_no_duplicates() {
+        let mut meta = CollectedMetadata::new();
+        meta.structs
+            .push(StructMetadata::new("User".into(), "struct User {}".into()));
+        meta.structs
+            .push(StructMetadata::new("Post".into(), "struct Post {}".into()));
+        assert!(meta.check_duplicate_schema_names().is_ok());
+    }
+
+    #[test]
+    fn test_check_duplicate_schema_names_different_definitions() {
+        let mut meta = CollectedMetadata::new();
+        meta.structs.push(StructMetadata::new(
+            "User".into(),
+            "struct User { id: i32 }".into(),
+        ));
+        meta.structs.push(StructMetadata::new(
+            "User".into(),
+            "struct User { name: String }".into(),
+        ));
+        let err = meta.check_duplicate_schema_names().unwrap_err();
+        assert!(
+            err.contains("Duplicate OpenAPI schema name 'User'"),
+            "got: {err}"
+        );
+    }
+
+    #[test]
+    fn test_check_duplicate_schema_names_identical_definition_ok() {
+        let mut meta = CollectedMetadata::new();
+        let def = "struct User { id: i32 }".to_string();
+        meta.structs
+            .push(StructMetadata::new("User".into(), def.clone()));
+        meta.structs.push(StructMetadata::new("User".into(), def));
+        assert!(meta.check_duplicate_schema_names().is_ok());
+    }
+
+    #[test]
+    fn test_check_duplicate_schema_names_ignores_models() {
+        let mut meta = CollectedMetadata::new();
+        meta.structs.push(StructMetadata::new_model(
+            "Model".into(),
+            "struct Model { id: i32 }".into(),
+        ));
+        meta.structs.push(StructMetadata::new_model(
+            "Model".into(),
+            "struct Model { name: String }".into(),
+        ));
+        // Models (include_in_openapi=false) are not checked
+        assert!(meta.check_duplicate_schema_names().is_ok());
+    }
+
+    #[test]
+    fn test_check_duplicate_schema_names_empty() {
+        let meta = CollectedMetadata::new();
+        assert!(meta.check_duplicate_schema_names().is_ok());
+    }
+
+    #[test]
+    fn test_check_link_targets() {
+        let link = |operation_id: &str| RouteLink {
+            name: "GetUser".to_string(),
+            operation_id: operation_id.to_string(),
+            parameters: vec![("id".to_string(), "$response.body#/id".to_string())],
+            request_body: None,
+            description: None,
+        };
+        let mut meta = CollectedMetadata::new();
+        meta.routes
+            .push(route("routes::users", Some("users_get_user")));
+        let mut create = route("routes::users", None);
+        create.links = Some(vec![link("users_get_user")]);
+        meta.routes.push(create);
+        assert!(meta.check_link_targets().is_ok());
+
+        meta.routes[1].links = Some(vec![link("users_show")]);
+        let err = meta.check_link_targets().unwrap_err();
+        assert!(err.contains("'GetUser'"), "{err}");
+        assert!(err.contains("'users_show'"), "{err}");
+        assert!(err.starts_with("[VESPERA009]"), "{err}");
+    }
+}
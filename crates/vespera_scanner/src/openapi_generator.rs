@@ -4,16 +4,26 @@ use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::path::Path;
 
 use vespera_core::{
-    openapi::{Info, OpenApi, OpenApiVersion, Server, Tag},
-    route::{HttpMethod, PathItem},
-    schema::Components,
+    openapi::{Info, OpenApi, OpenApiVersion, Paths, SDK_GROUP_EXTENSION, Server, Tag},
+    route::{Example, ExampleRef, HttpMethod, MediaType, PathItem},
+    schema::{ComponentKind, Components, Reference, SchemaRef},
+    walk,
 };
 
 use crate::{
     metadata::CollectedMetadata,
     parser::{
-        build_operation_from_function, extract_default, extract_field_rename, extract_rename_all,
-        parse_enum_to_schema, parse_struct_to_schema, rename_field, strip_raw_prefix_owned,
+        build_operation_from_function, defaulted_fields, extract_default, extract_field_rename,
+        extract_rename_all, extract_schema_dialect, extract_schema_examples, extract_schema_split,
+        extract_schema_title, extract_schema_xml, find_crate_type_alias, find_type_alias,
+        infer_success_status, parse_enum_to_schema, parse_struct_to_schema, rename_field,
+        resolve_return_type_alias, returns_status_tuple, set_success_status,
+        strip_raw_prefix_owned, take_instantiations,
+    },
+    route::{
+        document_cache, document_limits, document_links, override_binary_media_type,
+        override_examples, override_parameter_schemas, override_request_body, override_responses,
+        override_return_type, override_sse_event,
     },
     route_impl::StoredRouteInfo,
     schema_macro::type_utils::get_type_default as utils_get_type_default,
@@ -21,6 +31,8 @@ use crate::{
 
 /// Generate `OpenAPI` document from collected metadata.
 ///
+/// Paths are in route order; callers sort them with [`OpenApi::sort_paths`].
+///
 /// When `file_cache` is provided (from collector), skips file I/O entirely.
 /// When `None`, falls back to reading files from disk (used in tests).
 pub fn generate_openapi_doc_with_metadata(
@@ -31,11 +43,20 @@ pub fn generate_openapi_doc_with_metadata(
     file_cache: Option<HashMap<String, syn::File>>,
     route_storage: &[StoredRouteInfo],
 ) -> OpenApi {
-    let (known_schema_names, struct_definitions) = build_schema_lookups(metadata);
+    // Instantiations left over from an earlier document must not leak into this one
+    take_instantiations();
+    let (mut known_schema_names, mut struct_definitions) = build_schema_lookups(metadata);
     let file_cache = file_cache.unwrap_or_else(|| build_file_cache(metadata));
     let struct_file_index = build_struct_file_index(&file_cache);
     let parsed_definitions = build_parsed_definitions(metadata);
-    let schemas = parse_component_schemas(
+    let component_aliases = build_component_aliases(metadata, &parsed_definitions);
+    for (type_name, component_name) in &component_aliases {
+        known_schema_names.insert(type_name.clone());
+        if let Some(definition) = struct_definitions.get(component_name).cloned() {
+            struct_definitions.insert(type_name.clone(), definition);
+        }
+    }
+    let mut schemas = parse_component_schemas(
         metadata,
         &known_schema_names,
         &struct_definitions,
@@ -50,14 +71,19 @@ pub fn generate_openapi_doc_with_metadata(
         &file_cache,
         route_storage,
     );
+    // Generic instantiations registered as components under `generic_names`
+    for (name, schema) in take_instantiations() {
+        schemas.entry(name).or_insert(schema);
+    }
 
-    OpenApi {
+    let mut openapi = OpenApi {
         openapi: OpenApiVersion::V3_1_0,
         info: Info {
             title: title.unwrap_or_else(|| "API".to_string()),
             version: version.unwrap_or_else(|| "1.0.0".to_string()),
             ..Default::default()
         },
+        json_schema_dialect: None,
         servers: servers.or_else(|| {
             Some(vec![Server {
                 url: "http://localhost:3000".to_string(),
@@ -95,6 +121,190 @@ pub fn generate_openapi_doc_with_metadata(
             )
         },
         external_docs: None,
+    };
+    openapi.rewrite_schema_refs(&component_aliases);
+    attach_schema_examples(&mut openapi, metadata, &parsed_definitions);
+    let split = split_components(metadata, &parsed_definitions);
+    openapi.split_read_write_schemas(&split);
+    relax_request_defaults(&mut openapi, &parsed_definitions, &split);
+    openapi
+}
+
+/// Drop the fields serde fills in when missing (see [`defaulted_fields`]) from
+/// `required` of the schema components no response reaches.
+///
+/// A request may leave such a field out, but serde always writes it, so schemas used in
+/// responses keep it required.
+fn relax_request_defaults(
+    openapi: &mut OpenApi,
+    parsed_definitions: &HashMap<String, syn::Item>,
+    split: &BTreeSet<String>,
+) {
+    let mut defaulted = HashMap::new();
+    for (name, item) in parsed_definitions {
+        let syn::Item::Struct(struct_item) = item else {
+            continue;
+        };
+        let fields = defaulted_fields(struct_item);
+        if fields.is_empty() {
+            continue;
+        }
+        if split.contains(name) {
+            defaulted.insert(format!("{name}Request"), fields.clone());
+        }
+        defaulted.insert(name.clone(), fields);
+    }
+    if defaulted.is_empty() {
+        return;
+    }
+
+    // Follow `$ref`s from responses through schema components
+    let mut pending: Vec<String> = openapi
+        .paths
+        .values_mut()
+        .flat_map(PathItem::operations_mut)
+        .flat_map(|operation| walk::refs(&mut operation.responses))
+        .collect();
+    let Some(schemas) = openapi
+        .components
+        .as_mut()
+        .and_then(|components| components.schemas.as_mut())
+    else {
+        return;
+    };
+    let mut responded = HashSet::new();
+    while let Some(reference) = pending.pop() {
+        if let Some((ComponentKind::Schemas, name)) = Reference::parse(&reference)
+            && responded.insert(name.to_string())
+        {
+            pending.extend(walk::refs(&mut schemas.get_mut(name)));
+        }
+    }
+
+    for (name, schema) in schemas.iter_mut() {
+        let Some(fields) = defaulted.get(name) else {
+            continue;
+        };
+        if responded.contains(name) {
+            continue;
+        }
+        // Structs with flattened fields keep their own in the first `allOf` member
+        let schema = match schema.all_of.as_mut().and_then(|all_of| all_of.first_mut()) {
+            Some(SchemaRef::Inline(own)) if own.properties.is_some() => own,
+            _ => schema,
+        };
+        if let Some(required) = schema.required.as_mut() {
+            required.retain(|field| !fields.contains(field));
+            if required.is_empty() {
+                schema.required = None;
+            }
+        }
+    }
+}
+
+/// Components of `#[schema(split)]` structs, which are published as `{Name}Request` and
+/// `{Name}Response` variants.
+fn split_components(
+    metadata: &CollectedMetadata,
+    parsed_definitions: &HashMap<String, syn::Item>,
+) -> BTreeSet<String> {
+    metadata
+        .structs
+        .iter()
+        .filter(|s| s.include_in_openapi)
+        .filter(|struct_meta| {
+            matches!(
+                parsed_definitions.get(&struct_meta.name),
+                Some(syn::Item::Struct(item)) if extract_schema_split(&item.attrs)
+            )
+        })
+        .map(|struct_meta| struct_meta.name.clone())
+        .collect()
+}
+
+/// Register `#[schema(examples(...))]` under `components.examples` and reference them as
+/// named `examples` from request and response media types whose schema is a `$ref` to
+/// that component.
+///
+/// Each example is stored once as `{Schema}.{name}`, however many operations use it;
+/// examples of components no operation uses are left out. Media types that already
+/// carry an `example` or `examples` are left alone.
+fn attach_schema_examples(
+    openapi: &mut OpenApi,
+    metadata: &CollectedMetadata,
+    parsed_definitions: &HashMap<String, syn::Item>,
+) {
+    let named: HashMap<String, (&str, _)> = metadata
+        .structs
+        .iter()
+        .filter(|s| s.include_in_openapi)
+        .filter_map(|struct_meta| {
+            let attrs = match parsed_definitions.get(&struct_meta.name)? {
+                syn::Item::Struct(item) => &item.attrs,
+                syn::Item::Enum(item) => &item.attrs,
+                _ => return None,
+            };
+            let examples = extract_schema_examples(attrs).ok()?;
+            (!examples.is_empty()).then(|| {
+                (
+                    Reference::schema(&struct_meta.name).ref_path,
+                    (struct_meta.name.as_str(), examples),
+                )
+            })
+        })
+        .collect();
+    if named.is_empty() {
+        return;
+    }
+
+    let mut used: BTreeMap<String, Example> = BTreeMap::new();
+    let mut attach = |media_type: &mut MediaType| {
+        if media_type.example.is_some() || media_type.examples.is_some() {
+            return;
+        }
+        let Some(SchemaRef::Ref(reference)) = &media_type.schema else {
+            return;
+        };
+        let Some((schema_name, examples)) = named.get(&reference.ref_path) else {
+            return;
+        };
+        let mut refs = BTreeMap::new();
+        for (name, value) in examples {
+            let component_name = format!("{schema_name}.{name}");
+            refs.insert(
+                name.clone(),
+                ExampleRef::Ref(Reference::example(&component_name)),
+            );
+            used.entry(component_name).or_insert_with(|| Example {
+                summary: None,
+                description: None,
+                value: Some(value.clone()),
+            });
+        }
+        media_type.examples = Some(refs);
+    };
+    for operation in openapi
+        .paths
+        .values_mut()
+        .flat_map(PathItem::operations_mut)
+    {
+        if let Some(request_body) = operation.request_body.as_mut() {
+            request_body.content.values_mut().for_each(&mut attach);
+        }
+        for response in operation.responses.values_mut() {
+            if let Some(content) = response.content.as_mut() {
+                content.values_mut().for_each(&mut attach);
+            }
+        }
+    }
+    if used.is_empty() {
+        return;
+    }
+    if let Some(components) = openapi.components.as_mut() {
+        components
+            .examples
+            .get_or_insert_with(BTreeMap::new)
+            .extend(used);
     }
 }
 
@@ -165,6 +375,38 @@ fn build_parsed_definitions(metadata: &CollectedMetadata) -> HashMap<String, syn
     parsed
 }
 
+/// Map Rust type names to the component names set by `#[schema(name/rename/rename_all)]`.
+///
+/// Type references resolve by type name, so renamed types are also registered under it
+/// and their `$ref`s are pointed at the component name afterwards. Type names that are
+/// themselves component names, or that several renamed types share, are left alone.
+fn build_component_aliases(
+    metadata: &CollectedMetadata,
+    parsed_definitions: &HashMap<String, syn::Item>,
+) -> HashMap<String, String> {
+    let component_names: HashSet<&str> = metadata.structs.iter().map(|s| s.name.as_str()).collect();
+    let mut aliases: HashMap<String, String> = HashMap::new();
+    let mut ambiguous = HashSet::new();
+    for (component_name, item) in parsed_definitions {
+        let type_name = match item {
+            syn::Item::Struct(item) => item.ident.to_string(),
+            syn::Item::Enum(item) => item.ident.to_string(),
+            _ => continue,
+        };
+        if type_name == *component_name || component_names.contains(type_name.as_str()) {
+            continue;
+        }
+        if aliases
+            .insert(type_name.clone(), component_name.clone())
+            .is_some()
+        {
+            ambiguous.insert(type_name);
+        }
+    }
+    aliases.retain(|type_name, _| !ambiguous.contains(type_name));
+    aliases
+}
+
 /// Parse struct and enum definitions into `OpenAPI` component schemas.
 ///
 /// Only includes structs where `include_in_openapi` is true
@@ -187,16 +429,32 @@ fn parse_component_schemas(
         let Some(parsed) = parsed_definitions.get(&struct_meta.name) else {
             continue;
         };
-        let mut schema = match parsed {
-            syn::Item::Struct(struct_item) => {
-                parse_struct_to_schema(struct_item, known_schema_names, struct_definitions)
-            }
-            syn::Item::Enum(enum_item) => {
-                parse_enum_to_schema(enum_item, known_schema_names, struct_definitions)
-            }
+        let (mut schema, ident, attrs) = match parsed {
+            syn::Item::Struct(struct_item) => (
+                parse_struct_to_schema(struct_item, known_schema_names, struct_definitions),
+                &struct_item.ident,
+                &struct_item.attrs,
+            ),
+            syn::Item::Enum(enum_item) => (
+                parse_enum_to_schema(enum_item, known_schema_names, struct_definitions),
+                &enum_item.ident,
+                &enum_item.attrs,
+            ),
             _ => continue,
         };
 
+        // Renderers show `title` more prominently than the name inside `$ref` paths
+        if schema.ref_path.is_none() {
+            schema.title = extract_schema_title(attrs).or_else(|| Some(ident.to_string()));
+            schema.xml = extract_schema_xml(attrs);
+            schema.dialect = extract_schema_dialect(attrs).ok().flatten();
+            if let Ok(examples) = extract_schema_examples(attrs)
+                && !examples.is_empty()
+            {
+                schema.examples = Some(examples.into_iter().map(|(_, value)| value).collect());
+            }
+        }
+
         // Process default values using cached file ASTs (O(1) lookup)
         if let syn::Item::Struct(struct_item) = parsed {
             let file_ast = struct_file_index
@@ -230,14 +488,15 @@ fn parse_component_schemas(
 /// Uses `route_storage` (from `#[route]` macro) as the primary source for function
 /// signatures. Falls back to pre-built `file_cache` when ROUTE_STORAGE doesn't
 /// have an entry (e.g., during tests or for routes added without the attribute).
+#[allow(clippy::too_many_lines)]
 fn build_path_items(
     metadata: &CollectedMetadata,
     known_schema_names: &HashSet<String>,
     struct_definitions: &HashMap<String, String>,
     file_cache: &HashMap<String, syn::File>,
     route_storage: &[StoredRouteInfo],
-) -> (BTreeMap<String, PathItem>, BTreeSet<String>) {
-    let mut paths = BTreeMap::new();
+) -> (Paths, BTreeSet<String>) {
+    let mut paths = Paths::new();
     let mut all_tags = BTreeSet::new();
 
     // Primary source: pre-parse function items from ROUTE_STORAGE (populated by #[route])
@@ -271,22 +530,34 @@ fn build_path_items(
 
     for route_meta in &metadata.routes {
         // Try ROUTE_STORAGE first (avoids file_cache dependency for known routes)
-        let fn_sig = if let Some(cached_fn) = route_fn_cache.get(route_meta.function_name.as_str())
+        let fn_item = if let Some(cached_fn) = route_fn_cache.get(route_meta.function_name.as_str())
         {
-            &cached_fn.sig
+            cached_fn
         } else if let Some(fns) = fn_index.get(route_meta.file_path.as_str())
             && let Some(fn_item) = fns.get(&route_meta.function_name)
         {
-            &fn_item.sig
+            *fn_item
         } else {
             continue;
         };
+        let fn_sig = &fn_item.sig;
+
+        // `response = ...` replaces the handler's return type for inference
+        let overridden_sig = override_return_type(fn_sig, route_meta.response.as_deref());
+        let fn_sig = overridden_sig.as_ref().unwrap_or(fn_sig);
+
+        // `-> ApiResult<T>`: expand crate-local aliases so `Result`/`Json` are recognised.
+        // Aliases in the route's own file win over ones found elsewhere in the crate.
+        let route_file = file_cache.get(route_meta.file_path.as_str());
+        let resolved_sig = resolve_return_type_alias(fn_sig, known_schema_names, |name| {
+            route_file
+                .and_then(|ast| find_type_alias(&ast.items, name))
+                .or_else(|| find_crate_type_alias(name))
+        });
+        let fn_sig = resolved_sig.as_ref().unwrap_or(fn_sig);
 
+        // Unknown methods are reported by `vespera!` as VESPERA010
         let Ok(method) = HttpMethod::try_from(route_meta.method.as_str()) else {
-            eprintln!(
-                "vespera: skipping route '{}' \u{2014} unknown HTTP method '{}'",
-                route_meta.path, route_meta.method
-            );
             continue;
         };
 
@@ -304,11 +575,76 @@ fn build_path_items(
             route_meta.error_status.as_deref(),
             route_meta.tags.as_deref(),
         );
+        // `status = 201`, else the `StatusCode::*` a `(StatusCode, T)` handler returns
+        let success_status = route_meta.status.or_else(|| {
+            infer_success_status(&fn_item.block).filter(|_| returns_status_tuple(&fn_sig.output))
+        });
+        if let Some(status) = success_status {
+            set_success_status(&mut operation.responses, status);
+        }
+        operation.operation_id = Some(route_meta.operation_id());
+        operation.summary.clone_from(&route_meta.summary);
+        if route_meta.deprecated {
+            operation.deprecated = Some(true);
+        }
         operation.description.clone_from(&route_meta.description);
+        operation.security.clone_from(&route_meta.security);
+        if let Some(sdk_group) = &route_meta.sdk_group {
+            operation
+                .extensions
+                .insert(SDK_GROUP_EXTENSION.to_string(), sdk_group.clone().into());
+        }
+        if let Some(request_body) = &route_meta.request_body {
+            override_request_body(
+                &mut operation,
+                request_body,
+                known_schema_names,
+                struct_definitions,
+            );
+        }
+        if let Some(responses) = &route_meta.responses {
+            override_responses(
+                &mut operation,
+                responses,
+                known_schema_names,
+                struct_definitions,
+            );
+        }
+        if let Some(content_type) = &route_meta.content_type {
+            override_binary_media_type(&mut operation, content_type);
+        }
+        if let Some(sse_event) = &route_meta.sse_event {
+            override_sse_event(
+                &mut operation,
+                sse_event,
+                known_schema_names,
+                struct_definitions,
+            );
+        }
+        if let Some(params) = &route_meta.params {
+            override_parameter_schemas(
+                &mut operation,
+                params,
+                known_schema_names,
+                struct_definitions,
+            );
+        }
+        override_examples(
+            &mut operation,
+            route_meta.request_example.as_ref(),
+            route_meta.response_example.as_ref(),
+        );
+        if let Some(links) = &route_meta.links {
+            document_links(&mut operation, links);
+        }
+        if let Some(limits) = &route_meta.limits {
+            document_limits(&mut operation, limits);
+        }
+        if let Some(cache) = &route_meta.cache {
+            document_cache(&mut operation, cache);
+        }
 
-        let path_item = paths
-            .entry(route_meta.path.clone())
-            .or_insert_with(PathItem::default);
+        let path_item = paths.entry(route_meta.path.clone()).or_default();
 
         path_item.set_operation(method, operation);
     }
@@ -321,7 +657,7 @@ fn build_path_items(
 /// Looks up `field_name` in the properties map. If found as an inline schema
 /// and the schema has no existing default, sets `value` as the default.
 fn set_property_default(
-    properties: &mut BTreeMap<String, vespera_core::schema::SchemaRef>,
+    properties: &mut vespera_core::schema::Properties,
     field_name: &str,
     value: serde_json::Value,
 ) {
@@ -334,6 +670,17 @@ fn set_property_default(
     }
 }
 
+/// Whether the inline property `field_name` is documented as `type: string`.
+fn is_string_property(properties: &vespera_core::schema::Properties, field_name: &str) -> bool {
+    use vespera_core::schema::{SchemaRef, SchemaType};
+
+    matches!(
+        properties.get(field_name),
+        Some(SchemaRef::Inline(prop_schema))
+            if prop_schema.schema_type == Some(SchemaType::String)
+    )
+}
+
 /// Process default functions for struct fields
 /// This function extracts default values from:
 /// 1. `#[schema(default = "value")]` attributes (generated by `schema_type!` from `sea_orm(default_value)`)
@@ -373,7 +720,12 @@ fn process_default_functions(
 
             // Priority 1: #[schema(default = "value")] from schema_type! macro
             if let Some(default_str) = extract_schema_default_attr(&field.attrs) {
-                let value = parse_default_string_to_json_value(&default_str);
+                // A string property (e.g. a `Decimal`) keeps `0.7` as `"0.7"`
+                let value = if is_string_property(properties, &field_name) {
+                    serde_json::Value::String(default_str)
+                } else {
+                    parse_default_string_to_json_value(&default_str)
+                };
                 set_property_default(properties, &field_name, value);
                 continue;
             }
@@ -606,9 +958,7 @@ pub fn get_users() -> String {
             module_path: "test::users".to_string(),
             file_path: route_file.to_string_lossy().to_string(),
             signature: "fn get_users() -> String".to_string(),
-            error_status: None,
-            tags: None,
-            description: None,
+            ..Default::default()
         });
 
         let doc = generate_openapi_doc_with_metadata(None, None, None, &metadata, None, &[]);
@@ -617,7 +967,49 @@ pub fn get_users() -> String {
         let path_item = doc.paths.get("/users").unwrap();
         assert!(path_item.get.is_some());
         let operation = path_item.get.as_ref().unwrap();
-        assert_eq!(operation.operation_id, Some("get_users".to_string()));
+        assert_eq!(operation.operation_id, Some("users_get_users".to_string()));
+    }
+
+    #[test]
+    fn test_generate_openapi_all_methods() {
+        use std::fmt::Write;
+
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let mut content = String::new();
+        for method in crate::http::HTTP_METHODS {
+            writeln!(
+                content,
+                "#[route({method})]\npub fn {method}_item() -> String {{ \"{method}\".to_string() }}\n"
+            )
+            .unwrap();
+        }
+        create_temp_file(&temp_dir, "items.rs", &content);
+        let (metadata, _) =
+            crate::collector::collect_metadata(temp_dir.path(), "routes", &[]).unwrap();
+
+        let doc = generate_openapi_doc_with_metadata(None, None, None, &metadata, None, &[]);
+        let path_item = doc.paths.get("/items").unwrap();
+        assert_eq!(path_item.methods(), HttpMethod::ALL.to_vec());
+        for method in HttpMethod::ALL {
+            let method_name = method.to_string().to_lowercase();
+            assert_eq!(
+                path_item.operation(method).unwrap().operation_id,
+                Some(format!("items_{method_name}_item"))
+            );
+        }
+
+        // Operations serialize in method order
+        let item = serde_json::to_string(&doc.paths["/items"]).unwrap();
+        let positions: Vec<usize> = [
+            "get", "post", "put", "patch", "delete", "head", "options", "trace",
+        ]
+        .iter()
+        .map(|method| item.find(&format!("\"{method}\":")).unwrap())
+        .collect();
+        assert!(positions.is_sorted(), "{item}");
+        let json = serde_json::to_value(&doc).unwrap();
+        let round_trip: OpenApi = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(serde_json::to_value(&round_trip).unwrap(), json);
     }
 
     #[test]
@@ -693,9 +1085,7 @@ pub fn get_status() -> Status {
             module_path: "test::status_route".to_string(),
             file_path: route_file.to_string_lossy().to_string(),
             signature: "fn get_status() -> Status".to_string(),
-            error_status: None,
-            tags: None,
-            description: None,
+            ..Default::default()
         });
 
         let doc = generate_openapi_doc_with_metadata(None, None, None, &metadata, None, &[]);
@@ -756,9 +1146,7 @@ pub fn get_user() -> User {
             module_path: "test::user_route".to_string(),
             file_path: route_file.to_string_lossy().to_string(),
             signature: "fn get_user() -> User".to_string(),
-            error_status: None,
-            tags: None,
-            description: None,
+            ..Default::default()
         });
 
         let doc = generate_openapi_doc_with_metadata(
@@ -781,6 +1169,627 @@ pub fn get_user() -> User {
         assert!(path_item.get.is_some());
     }
 
+    #[test]
+    fn test_generate_openapi_renamed_component_refs() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let route_content = r"
+pub fn get_user() -> Json<User> {
+    todo!()
+}
+";
+        let route_file = create_temp_file(&temp_dir, "user_route.rs", route_content);
+
+        let mut metadata = CollectedMetadata::new();
+        metadata.structs.push(StructMetadata {
+            name: "user".to_string(),
+            definition:
+                "#[schema(rename = \"user\")] struct User { id: i32, profile: UserProfile }"
+                    .to_string(),
+            ..Default::default()
+        });
+        metadata.structs.push(StructMetadata {
+            name: "userProfile".to_string(),
+            definition: "#[schema(rename_all = \"camelCase\")] struct UserProfile { bio: String }"
+                .to_string(),
+            ..Default::default()
+        });
+        metadata.routes.push(RouteMetadata {
+            method: "GET".to_string(),
+            path: "/user".to_string(),
+            function_name: "get_user".to_string(),
+            module_path: "test::user_route".to_string(),
+            file_path: route_file.to_string_lossy().to_string(),
+            signature: "fn get_user() -> Json<User>".to_string(),
+            ..Default::default()
+        });
+
+        let doc = generate_openapi_doc_with_metadata(None, None, None, &metadata, None, &[]);
+        let json = serde_json::to_value(&doc).unwrap();
+
+        let schemas = &json["components"]["schemas"];
+        assert_eq!(
+            schemas.as_object().unwrap().keys().collect::<Vec<_>>(),
+            vec!["user", "userProfile"]
+        );
+        assert_eq!(
+            schemas["user"]["properties"]["profile"]["$ref"],
+            "#/components/schemas/userProfile"
+        );
+        assert_eq!(
+            json["paths"]["/user"]["get"]["responses"]["200"]["content"]["application/json"]["schema"]
+                ["$ref"],
+            "#/components/schemas/user"
+        );
+    }
+
+    #[test]
+    fn test_generate_openapi_resolves_return_type_alias() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let route_content = r"
+pub type ApiResult<T> = Result<Json<T>, Json<ApiError>>;
+
+pub fn get_user() -> ApiResult<User> {
+    todo!()
+}
+";
+        let route_file = create_temp_file(&temp_dir, "user_route.rs", route_content);
+
+        let mut metadata = CollectedMetadata::new();
+        for (name, definition) in [
+            ("User", "struct User { id: i32 }"),
+            ("ApiError", "struct ApiError { message: String }"),
+        ] {
+            metadata.structs.push(StructMetadata::new(
+                name.to_string(),
+                definition.to_string(),
+            ));
+        }
+        metadata.routes.push(RouteMetadata {
+            method: "GET".to_string(),
+            path: "/user".to_string(),
+            function_name: "get_user".to_string(),
+            module_path: "test::user_route".to_string(),
+            file_path: route_file.to_string_lossy().to_string(),
+            signature: "fn get_user() -> ApiResult<User>".to_string(),
+            ..Default::default()
+        });
+
+        let doc = generate_openapi_doc_with_metadata(None, None, None, &metadata, None, &[]);
+        let json = serde_json::to_value(&doc).unwrap();
+
+        let responses = &json["paths"]["/user"]["get"]["responses"];
+        assert_eq!(
+            responses["200"]["content"]["application/json"]["schema"]["$ref"],
+            "#/components/schemas/User"
+        );
+        assert_eq!(
+            responses["400"]["content"]["application/json"]["schema"]["$ref"],
+            "#/components/schemas/ApiError"
+        );
+    }
+
+    #[test]
+    fn test_generate_openapi_response_and_request_body_overrides() {
+        let mut metadata = CollectedMetadata::new();
+        for (name, definition) in [
+            ("User", "struct User { id: i32 }"),
+            ("CreateUser", "struct CreateUser { name: String }"),
+        ] {
+            metadata.structs.push(StructMetadata::new(
+                name.to_string(),
+                definition.to_string(),
+            ));
+        }
+        metadata.routes.push(RouteMetadata {
+            method: "POST".to_string(),
+            path: "/users".to_string(),
+            function_name: "create_user".to_string(),
+            module_path: "test::users".to_string(),
+            response: Some("Json<User>".to_string()),
+            request_body: Some("CreateUser".to_string()),
+            operation_id: Some("createUser".to_string()),
+            ..Default::default()
+        });
+        let route_storage = vec![StoredRouteInfo {
+            fn_name: "create_user".to_string(),
+            method: Some("post".to_string()),
+            custom_path: None,
+            error_status: None,
+            tags: None,
+            description: None,
+            fn_item_str:
+                "pub async fn create_user(req: Request) -> impl IntoResponse { respond(req).await }"
+                    .to_string(),
+            file_path: None,
+            security: None,
+            limits: None,
+            cache: None,
+            response: Some("Json<User>".to_string()),
+            request_body: Some("CreateUser".to_string()),
+            responses: None,
+            params: None,
+            links: None,
+            request_example: None,
+            response_example: None,
+            status: None,
+            sdk_group: None,
+            operation_id: Some("createUser".to_string()),
+            content_type: None,
+            sse_event: None,
+            summary: None,
+            deprecated: false,
+        }];
+
+        let doc =
+            generate_openapi_doc_with_metadata(None, None, None, &metadata, None, &route_storage);
+        let operation = serde_json::to_value(&doc).unwrap()["paths"]["/users"]["post"].clone();
+
+        assert_eq!(
+            operation["responses"]["200"]["content"]["application/json"]["schema"]["$ref"],
+            "#/components/schemas/User"
+        );
+        assert_eq!(
+            operation["requestBody"]["content"]["application/json"]["schema"]["$ref"],
+            "#/components/schemas/CreateUser"
+        );
+        assert_eq!(operation["operationId"], "createUser");
+    }
+
+    #[rstest]
+    #[case(
+        "pub async fn create_user() -> (StatusCode, Json<User>) { (StatusCode::CREATED, Json(user())) }",
+        None,
+        "201"
+    )]
+    #[case(
+        "pub async fn create_user() -> Result<(StatusCode, Json<User>), String> { Ok((StatusCode::ACCEPTED, Json(user()))) }",
+        None,
+        "202"
+    )]
+    #[case(
+        "pub async fn create_user() -> impl IntoResponse { respond().await }",
+        Some(201),
+        "201"
+    )]
+    #[case(
+        "pub async fn create_user() -> Json<User> { let _ = (StatusCode::CREATED, 1); Json(user()) }",
+        None,
+        "200"
+    )]
+    fn test_generate_openapi_success_status(
+        #[case] fn_item_str: &str,
+        #[case] status: Option<u16>,
+        #[case] expected: &str,
+    ) {
+        let mut metadata = CollectedMetadata::new();
+        metadata.structs.push(StructMetadata::new(
+            "User".to_string(),
+            "struct User { id: i32 }".to_string(),
+        ));
+        metadata.routes.push(RouteMetadata {
+            method: "POST".to_string(),
+            path: "/users".to_string(),
+            function_name: "create_user".to_string(),
+            module_path: "test::users".to_string(),
+            response: status.map(|_| "Json<User>".to_string()),
+            status,
+            ..Default::default()
+        });
+        let route_storage = vec![StoredRouteInfo {
+            fn_name: "create_user".to_string(),
+            method: Some("post".to_string()),
+            custom_path: None,
+            error_status: None,
+            tags: None,
+            description: None,
+            summary: None,
+            deprecated: false,
+            fn_item_str: fn_item_str.to_string(),
+            file_path: None,
+            security: None,
+            limits: None,
+            cache: None,
+            response: None,
+            request_body: None,
+            responses: None,
+            params: None,
+            links: None,
+            request_example: None,
+            response_example: None,
+            status,
+            sdk_group: None,
+            operation_id: None,
+            content_type: None,
+            sse_event: None,
+        }];
+
+        let doc =
+            generate_openapi_doc_with_metadata(None, None, None, &metadata, None, &route_storage);
+        let responses =
+            serde_json::to_value(&doc).unwrap()["paths"]["/users"]["post"]["responses"].clone();
+        let statuses: Vec<_> = responses
+            .as_object()
+            .unwrap()
+            .keys()
+            .filter(|status| status.starts_with('2'))
+            .cloned()
+            .collect();
+        assert_eq!(statuses, [expected]);
+        assert_eq!(
+            responses[expected]["content"]["application/json"]["schema"]["$ref"],
+            "#/components/schemas/User"
+        );
+    }
+
+    #[test]
+    fn test_generate_openapi_sdk_group_extension() {
+        let mut metadata = CollectedMetadata::new();
+        metadata.routes.push(RouteMetadata {
+            method: "GET".to_string(),
+            path: "/users".to_string(),
+            function_name: "list_users".to_string(),
+            module_path: "test::users".to_string(),
+            tags: Some(vec!["users".to_string()]),
+            sdk_group: Some("Accounts".to_string()),
+            ..Default::default()
+        });
+        let route_storage = vec![StoredRouteInfo {
+            fn_name: "list_users".to_string(),
+            method: Some("get".to_string()),
+            custom_path: None,
+            error_status: None,
+            tags: None,
+            description: None,
+            summary: None,
+            deprecated: false,
+            fn_item_str: "pub async fn list_users() -> String { String::new() }".to_string(),
+            file_path: None,
+            security: None,
+            limits: None,
+            cache: None,
+            response: None,
+            request_body: None,
+            responses: None,
+            params: None,
+            links: None,
+            request_example: None,
+            response_example: None,
+            status: None,
+            sdk_group: Some("Accounts".to_string()),
+            operation_id: None,
+            content_type: None,
+            sse_event: None,
+        }];
+
+        let doc =
+            generate_openapi_doc_with_metadata(None, None, None, &metadata, None, &route_storage);
+        let operation = serde_json::to_value(&doc).unwrap()["paths"]["/users"]["get"].clone();
+        assert_eq!(operation[SDK_GROUP_EXTENSION], "Accounts");
+        assert_eq!(operation["tags"], serde_json::json!(["users"]));
+    }
+
+    #[test]
+    fn test_generate_openapi_split_schemas() {
+        let mut metadata = CollectedMetadata::new();
+        metadata.structs.push(StructMetadata::new(
+            "User".to_string(),
+            "#[schema(split)] struct User { #[schema(read_only)] id: i32, name: String, #[schema(write_only)] password: String }"
+                .to_string(),
+        ));
+        metadata.routes.push(RouteMetadata {
+            method: "POST".to_string(),
+            path: "/users".to_string(),
+            function_name: "create_user".to_string(),
+            module_path: "test::users".to_string(),
+            ..Default::default()
+        });
+        let route_storage = vec![StoredRouteInfo {
+            fn_name: "create_user".to_string(),
+            method: Some("post".to_string()),
+            custom_path: None,
+            error_status: None,
+            tags: None,
+            description: None,
+            summary: None,
+            deprecated: false,
+            fn_item_str:
+                "pub async fn create_user(Json(user): Json<User>) -> Json<User> { Json(user) }"
+                    .to_string(),
+            file_path: None,
+            security: None,
+            limits: None,
+            cache: None,
+            response: None,
+            request_body: None,
+            responses: None,
+            params: None,
+            links: None,
+            request_example: None,
+            response_example: None,
+            status: None,
+            sdk_group: None,
+            operation_id: None,
+            content_type: None,
+            sse_event: None,
+        }];
+
+        let doc =
+            generate_openapi_doc_with_metadata(None, None, None, &metadata, None, &route_storage);
+        let json = serde_json::to_value(&doc).unwrap();
+        let schemas = &json["components"]["schemas"];
+        assert!(schemas.get("User").is_none());
+        assert_eq!(
+            schemas["UserRequest"]["required"],
+            serde_json::json!(["name", "password"])
+        );
+        assert!(schemas["UserRequest"]["properties"].get("id").is_none());
+        assert_eq!(
+            schemas["UserResponse"]["required"],
+            serde_json::json!(["id", "name"])
+        );
+        assert!(
+            schemas["UserResponse"]["properties"]
+                .get("password")
+                .is_none()
+        );
+        let operation = &json["paths"]["/users"]["post"];
+        assert_eq!(
+            operation["requestBody"]["content"]["application/json"]["schema"]["$ref"],
+            "#/components/schemas/UserRequest"
+        );
+        assert_eq!(
+            operation["responses"]["200"]["content"]["application/json"]["schema"]["$ref"],
+            "#/components/schemas/UserResponse"
+        );
+    }
+
+    #[test]
+    fn test_generate_openapi_serde_default_request_only() {
+        let mut metadata = CollectedMetadata::new();
+        for (name, definition) in [
+            (
+                "CreateMemo",
+                "struct CreateMemo { title: String, #[serde(default)] pinned: bool, #[serde(flatten)] meta: Meta }",
+            ),
+            ("Meta", "struct Meta { tag: String }"),
+            (
+                "Memo",
+                "struct Memo { id: i32, #[serde(default)] pinned: bool }",
+            ),
+            (
+                "Draft",
+                "#[schema(split)] struct Draft { title: String, #[serde(default)] pinned: bool }",
+            ),
+        ] {
+            metadata.structs.push(StructMetadata::new(
+                name.to_string(),
+                definition.to_string(),
+            ));
+        }
+        let route_storage: Vec<StoredRouteInfo> = [
+            (
+                "create_memo",
+                "pub async fn create_memo(Json(memo): Json<CreateMemo>) -> Json<Memo> { todo!() }",
+            ),
+            (
+                "save_draft",
+                "pub async fn save_draft(Json(draft): Json<Draft>) -> Json<Draft> { Json(draft) }",
+            ),
+        ]
+        .into_iter()
+        .map(|(fn_name, fn_item_str)| {
+            metadata.routes.push(RouteMetadata {
+                method: "POST".to_string(),
+                path: format!("/{fn_name}"),
+                function_name: fn_name.to_string(),
+                module_path: "test::memos".to_string(),
+                ..Default::default()
+            });
+            StoredRouteInfo {
+                fn_name: fn_name.to_string(),
+                method: Some("post".to_string()),
+                custom_path: None,
+                error_status: None,
+                tags: None,
+                description: None,
+                summary: None,
+                deprecated: false,
+                fn_item_str: fn_item_str.to_string(),
+                file_path: None,
+                security: None,
+                limits: None,
+                cache: None,
+                response: None,
+                request_body: None,
+                responses: None,
+                params: None,
+                links: None,
+                request_example: None,
+                response_example: None,
+                status: None,
+                sdk_group: None,
+                operation_id: None,
+                content_type: None,
+                sse_event: None,
+            }
+        })
+        .collect();
+
+        let doc =
+            generate_openapi_doc_with_metadata(None, None, None, &metadata, None, &route_storage);
+        let json = serde_json::to_value(&doc).unwrap();
+        let schemas = &json["components"]["schemas"];
+        // Clients may leave `pinned` out of a request, but responses always carry it
+        assert_eq!(
+            schemas["CreateMemo"]["allOf"][0]["required"],
+            serde_json::json!(["title"])
+        );
+        assert_eq!(
+            schemas["Memo"]["required"],
+            serde_json::json!(["id", "pinned"])
+        );
+        assert_eq!(
+            schemas["DraftRequest"]["required"],
+            serde_json::json!(["title"])
+        );
+        assert_eq!(
+            schemas["DraftResponse"]["required"],
+            serde_json::json!(["title", "pinned"])
+        );
+    }
+
+    #[test]
+    fn test_generate_openapi_schema_examples() {
+        let mut metadata = CollectedMetadata::new();
+        metadata.structs.push(StructMetadata::new(
+            "User".to_string(),
+            r#"#[schema(examples(minimal = json!({ "id": 1 }), full = json!({ "id": 2, "name": "Neo" })))]
+            struct User { id: i32, name: Option<String> }"#
+                .to_string(),
+        ));
+        metadata.routes.push(RouteMetadata {
+            method: "POST".to_string(),
+            path: "/users".to_string(),
+            function_name: "create_user".to_string(),
+            module_path: "test::users".to_string(),
+            ..Default::default()
+        });
+        let route_storage = vec![StoredRouteInfo {
+            fn_name: "create_user".to_string(),
+            method: Some("post".to_string()),
+            custom_path: None,
+            error_status: None,
+            tags: None,
+            description: None,
+            summary: None,
+            deprecated: false,
+            fn_item_str:
+                "pub async fn create_user(Json(user): Json<User>) -> Json<User> { Json(user) }"
+                    .to_string(),
+            file_path: None,
+            security: None,
+            limits: None,
+            cache: None,
+            response: None,
+            request_body: None,
+            responses: None,
+            params: None,
+            links: None,
+            request_example: None,
+            response_example: None,
+            status: None,
+            sdk_group: None,
+            operation_id: None,
+            content_type: None,
+            sse_event: None,
+        }];
+
+        let doc =
+            generate_openapi_doc_with_metadata(None, None, None, &metadata, None, &route_storage);
+        let json = serde_json::to_value(&doc).unwrap();
+        assert_eq!(
+            json["components"]["schemas"]["User"]["examples"],
+            serde_json::json!([{ "id": 1 }, { "id": 2, "name": "Neo" }])
+        );
+        assert_eq!(
+            json["components"]["examples"],
+            serde_json::json!({
+                "User.full": { "value": { "id": 2, "name": "Neo" } },
+                "User.minimal": { "value": { "id": 1 } },
+            })
+        );
+        let expected = serde_json::json!({
+            "full": { "$ref": "#/components/examples/User.full" },
+            "minimal": { "$ref": "#/components/examples/User.minimal" },
+        });
+        let operation = &json["paths"]["/users"]["post"];
+        assert_eq!(
+            operation["requestBody"]["content"]["application/json"]["examples"],
+            expected
+        );
+        assert_eq!(
+            operation["responses"]["200"]["content"]["application/json"]["examples"],
+            expected
+        );
+    }
+
+    #[test]
+    fn test_generate_openapi_component_titles() {
+        let mut metadata = CollectedMetadata::new();
+        for (name, definition) in [
+            ("User", "struct User { id: i32 }"),
+            (
+                "user_profile",
+                "#[schema(name = \"user_profile\")] struct UserProfile { bio: String }",
+            ),
+            (
+                "Status",
+                "#[schema(title = \"Account status\")] enum Status { Active, Banned }",
+            ),
+        ] {
+            metadata.structs.push(StructMetadata::new(
+                name.to_string(),
+                definition.to_string(),
+            ));
+        }
+
+        let doc = generate_openapi_doc_with_metadata(None, None, None, &metadata, None, &[]);
+        let schemas = doc.components.unwrap().schemas.unwrap();
+
+        assert_eq!(schemas["User"].title.as_deref(), Some("User"));
+        assert_eq!(
+            schemas["user_profile"].title.as_deref(),
+            Some("UserProfile")
+        );
+        assert_eq!(schemas["Status"].title.as_deref(), Some("Account status"));
+    }
+
+    #[test]
+    fn test_generate_openapi_component_xml() {
+        let mut metadata = CollectedMetadata::new();
+        metadata.structs.push(StructMetadata::new(
+            "Pet".to_string(),
+            r#"#[schema(xml(name = "pet", prefix = "ex"))] struct Pet { #[schema(xml(attribute))] id: i32 }"#
+                .to_string(),
+        ));
+
+        let doc = generate_openapi_doc_with_metadata(None, None, None, &metadata, None, &[]);
+        let pet = serde_json::to_value(&doc.components.unwrap().schemas.unwrap()["Pet"]).unwrap();
+
+        assert_eq!(
+            pet["xml"],
+            serde_json::json!({ "name": "pet", "prefix": "ex" })
+        );
+        assert_eq!(
+            pet["properties"]["id"]["xml"],
+            serde_json::json!({ "attribute": true })
+        );
+    }
+
+    #[test]
+    fn test_build_component_aliases_skips_ambiguous_and_taken_names() {
+        let mut metadata = CollectedMetadata::new();
+        for (name, definition) in [
+            ("UserV1", "struct User {}"),
+            ("UserV2", "struct User {}"),
+            ("Order", "struct Order {}"),
+            ("Item", "struct Order {}"),
+            ("account", "struct Account {}"),
+        ] {
+            metadata.structs.push(StructMetadata::new(
+                name.to_string(),
+                definition.to_string(),
+            ));
+        }
+        let parsed = build_parsed_definitions(&metadata);
+
+        let aliases = build_component_aliases(&metadata, &parsed);
+        assert_eq!(
+            aliases,
+            HashMap::from([("Account".to_string(), "account".to_string())])
+        );
+    }
+
     #[test]
     fn test_generate_openapi_multiple_routes() {
         let temp_dir = TempDir::new().expect("Failed to create temp dir");
@@ -807,9 +1816,7 @@ pub fn create_user() -> String {
             module_path: "test::users".to_string(),
             file_path: route1_file.to_string_lossy().to_string(),
             signature: "fn get_users() -> String".to_string(),
-            error_status: None,
-            tags: None,
-            description: None,
+            ..Default::default()
         });
         metadata.routes.push(RouteMetadata {
             method: "POST".to_string(),
@@ -818,9 +1825,7 @@ pub fn create_user() -> String {
             module_path: "test::create_user".to_string(),
             file_path: route2_file.to_string_lossy().to_string(),
             signature: "fn create_user() -> String".to_string(),
-            error_status: None,
-            tags: None,
-            description: None,
+            ..Default::default()
         });
 
         let doc = generate_openapi_doc_with_metadata(None, None, None, &metadata, None, &[]);
@@ -842,9 +1847,7 @@ pub fn create_user() -> String {
             module_path: "test::users".to_string(),
             file_path: "/nonexistent/route.rs".to_string(),
             signature: "fn get_users() -> String".to_string(),
-            error_status: None,
-            tags: None,
-            description: None,
+            ..Default::default()
         }),
         false, // struct should not be added
         false, // route should not be added
@@ -858,9 +1861,7 @@ pub fn create_user() -> String {
             module_path: "test::users".to_string(),
             file_path: String::new(), // Will be set to temp file with invalid syntax
             signature: "fn get_users() -> String".to_string(),
-            error_status: None,
-            tags: None,
-            description: None,
+            ..Default::default()
         }),
         false, // struct should not be added
         false, // route should not be added
@@ -914,6 +1915,44 @@ pub fn create_user() -> String {
         drop(temp_dir);
     }
 
+    #[test]
+    fn test_generate_openapi_with_security_alternatives() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let route_content = r#"
+pub fn get_users() -> String {
+    "users".to_string()
+}
+"#;
+        let route_file = create_temp_file(&temp_dir, "users.rs", route_content);
+
+        let mut metadata = CollectedMetadata::new();
+        metadata.routes.push(RouteMetadata {
+            method: "GET".to_string(),
+            path: "/users".to_string(),
+            function_name: "get_users".to_string(),
+            module_path: "test::users".to_string(),
+            file_path: route_file.to_string_lossy().to_string(),
+            signature: "fn get_users() -> String".to_string(),
+            security: Some(vec![
+                [("bearer".to_string(), vec![])].into(),
+                [
+                    ("api_key".to_string(), vec![]),
+                    ("client_id".to_string(), vec![]),
+                ]
+                .into(),
+            ]),
+            ..Default::default()
+        });
+
+        let doc = generate_openapi_doc_with_metadata(None, None, None, &metadata, None, &[]);
+
+        let operation = doc.paths["/users"].get.as_ref().unwrap();
+        assert_eq!(
+            serde_json::to_value(&operation.security).unwrap(),
+            serde_json::json!([{ "bearer": [] }, { "api_key": [], "client_id": [] }])
+        );
+    }
+
     #[test]
     fn test_generate_openapi_with_tags_and_description() {
         let temp_dir = TempDir::new().expect("Failed to create temp dir");
@@ -935,13 +1974,18 @@ pub fn get_users() -> String {
             error_status: Some(vec![404]),
             tags: Some(vec!["users".to_string(), "admin".to_string()]),
             description: Some("Get all users".to_string()),
+            summary: Some("List users".to_string()),
+            deprecated: true,
+            ..Default::default()
         });
 
         let doc = generate_openapi_doc_with_metadata(None, None, None, &metadata, None, &[]);
 
-        // Check route has description
+        // Check route has summary and description
         let path_item = doc.paths.get("/users").unwrap();
         let operation = path_item.get.as_ref().unwrap();
+        assert_eq!(operation.summary, Some("List users".to_string()));
+        assert_eq!(operation.deprecated, Some(true));
         assert_eq!(operation.description, Some("Get all users".to_string()));
 
         // Check tags are collected
@@ -1207,9 +2251,7 @@ pub fn get_user() -> User {
             module_path: "test::user".to_string(),
             file_path: route_file.to_string_lossy().to_string(),
             signature: "fn get_user() -> User".to_string(),
-            error_status: None,
-            tags: None,
-            description: None,
+            ..Default::default()
         });
 
         let doc = generate_openapi_doc_with_metadata(None, None, None, &metadata, None, &[]);
@@ -1253,9 +2295,7 @@ pub fn get_config() -> Config {
             module_path: "test::config".to_string(),
             file_path: route_file.to_string_lossy().to_string(),
             signature: "fn get_config() -> Config".to_string(),
-            error_status: None,
-            tags: None,
-            description: None,
+            ..Default::default()
         });
 
         let doc = generate_openapi_doc_with_metadata(None, None, None, &metadata, None, &[]);
@@ -1313,9 +2353,7 @@ pub fn get_user() -> User {
             module_path: "test::users".to_string(),
             file_path: route1_file.to_string_lossy().to_string(),
             signature: "fn get_users() -> Vec<User>".to_string(),
-            error_status: None,
-            tags: None,
-            description: None,
+            ..Default::default()
         });
         metadata.routes.push(RouteMetadata {
             method: "GET".to_string(),
@@ -1324,9 +2362,7 @@ pub fn get_user() -> User {
             module_path: "test::user".to_string(),
             file_path: route2_file.to_string_lossy().to_string(),
             signature: "fn get_user() -> User".to_string(),
-            error_status: None,
-            tags: None,
-            description: None,
+            ..Default::default()
         });
 
         let doc = generate_openapi_doc_with_metadata(None, None, None, &metadata, None, &[]);
@@ -1463,9 +2499,7 @@ pub fn get_users() -> String {
             module_path: "test::users".to_string(),
             file_path: route_file.to_string_lossy().to_string(),
             signature: "fn get_users() -> String".to_string(),
-            error_status: None,
-            tags: None,
-            description: None,
+            ..Default::default()
         });
 
         let doc = generate_openapi_doc_with_metadata(None, None, None, &metadata, None, &[]);
@@ -1503,9 +2537,7 @@ pub fn create_users() -> String {
             module_path: "test::users".to_string(),
             file_path: file_path.clone(),
             signature: "fn get_users() -> String".to_string(),
-            error_status: None,
-            tags: None,
-            description: None,
+            ..Default::default()
         });
         // Valid method route
         metadata.routes.push(RouteMetadata {
@@ -1515,9 +2547,7 @@ pub fn create_users() -> String {
             module_path: "test::users".to_string(),
             file_path,
             signature: "fn create_users() -> String".to_string(),
-            error_status: None,
-            tags: None,
-            description: None,
+            ..Default::default()
         });
 
         let doc = generate_openapi_doc_with_metadata(None, None, None, &metadata, None, &[]);
@@ -1558,9 +2588,9 @@ pub fn create_users() -> String {
 
     #[test]
     fn test_set_property_default_on_inline_schema() {
-        use vespera_core::schema::{Schema, SchemaRef};
+        use vespera_core::schema::{Properties, Schema, SchemaRef};
 
-        let mut properties = BTreeMap::new();
+        let mut properties = Properties::new();
         let mut schema = Schema::object();
         schema.default = None;
         properties.insert("name".to_string(), SchemaRef::Inline(Box::new(schema)));
@@ -1583,9 +2613,9 @@ pub fn create_users() -> String {
 
     #[test]
     fn test_set_property_default_does_not_overwrite_existing() {
-        use vespera_core::schema::{Schema, SchemaRef};
+        use vespera_core::schema::{Properties, Schema, SchemaRef};
 
-        let mut properties = BTreeMap::new();
+        let mut properties = Properties::new();
         let mut schema = Schema::object();
         schema.default = Some(serde_json::Value::String("existing".to_string()));
         properties.insert("name".to_string(), SchemaRef::Inline(Box::new(schema)));
@@ -1609,9 +2639,9 @@ pub fn create_users() -> String {
 
     #[test]
     fn test_set_property_default_skips_ref_schema() {
-        use vespera_core::schema::{Reference, SchemaRef};
+        use vespera_core::schema::{Properties, Reference, SchemaRef};
 
-        let mut properties = BTreeMap::new();
+        let mut properties = Properties::new();
         properties.insert(
             "user".to_string(),
             SchemaRef::Ref(Reference::schema("User")),
@@ -1632,7 +2662,9 @@ pub fn create_users() -> String {
 
     #[test]
     fn test_set_property_default_skips_missing_property() {
-        let mut properties = BTreeMap::new();
+        use vespera_core::schema::Properties;
+
+        let mut properties = Properties::new();
 
         // Should silently no-op (property doesn't exist)
         set_property_default(
@@ -1691,14 +2723,14 @@ pub fn create_users() -> String {
 
     #[test]
     fn test_process_default_functions_with_schema_default_attr() {
-        use vespera_core::schema::{Schema, SchemaRef};
+        use vespera_core::schema::{Properties, Schema, SchemaRef};
 
         let file_ast: syn::File = syn::parse_str("").unwrap();
         let struct_item: syn::ItemStruct =
             syn::parse_str(r#"pub struct Test { #[schema(default = "100")] pub count: i32 }"#)
                 .unwrap();
         let mut schema = Schema::object();
-        let props = schema.properties.get_or_insert_with(BTreeMap::new);
+        let props = schema.properties.get_or_insert_with(Properties::new);
         props.insert(
             "count".to_string(),
             SchemaRef::Inline(Box::new(Schema::integer())),
@@ -1713,6 +2745,30 @@ pub fn create_users() -> String {
         }
     }
 
+    #[test]
+    fn test_process_default_functions_with_schema_default_attr_string_property() {
+        use vespera_core::schema::{Properties, Schema, SchemaRef};
+
+        let file_ast: syn::File = syn::parse_str("").unwrap();
+        let struct_item: syn::ItemStruct = syn::parse_str(
+            r#"pub struct Test { #[schema(default = "0.7")] pub temperature: Decimal }"#,
+        )
+        .unwrap();
+        let mut schema = Schema::object();
+        let props = schema.properties.get_or_insert_with(Properties::new);
+        props.insert(
+            "temperature".to_string(),
+            SchemaRef::Inline(Box::new(Schema::string())),
+        );
+        process_default_functions(&struct_item, &file_ast, &mut schema, &BTreeMap::new());
+        let Some(SchemaRef::Inline(prop_schema)) =
+            schema.properties.as_ref().unwrap().get("temperature")
+        else {
+            panic!("Expected inline schema with default");
+        };
+        assert_eq!(prop_schema.default, Some(serde_json::json!("0.7")));
+    }
+
     #[test]
     fn test_generate_openapi_route_function_not_in_ast() {
         let temp_dir = TempDir::new().expect("Failed to create temp dir");
@@ -1727,9 +2783,7 @@ pub fn create_users() -> String {
             module_path: "test::users".to_string(),
             file_path: route_file.to_string_lossy().to_string(),
             signature: "fn get_users() -> String".to_string(),
-            error_status: None,
-            tags: None,
-            description: None,
+            ..Default::default()
         });
 
         let doc = generate_openapi_doc_with_metadata(None, None, None, &metadata, None, &[]);
@@ -1757,9 +2811,7 @@ pub fn get_users() -> String {
             module_path: "test::users".to_string(),
             file_path: route_file.to_string_lossy().to_string(),
             signature: "fn get_users() -> String".to_string(),
-            error_status: None,
-            tags: None,
-            description: None,
+            ..Default::default()
         });
 
         // Provide route_storage with matching fn_name -> exercises fast path (line 155)
@@ -1772,6 +2824,23 @@ pub fn get_users() -> String {
             description: None,
             fn_item_str: "pub fn get_users() -> String { \"users\".to_string() }".to_string(),
             file_path: None,
+            security: None,
+            limits: None,
+            cache: None,
+            response: None,
+            request_body: None,
+            responses: None,
+            params: None,
+            links: None,
+            request_example: None,
+            response_example: None,
+            status: None,
+            sdk_group: None,
+            operation_id: None,
+            content_type: None,
+            sse_event: None,
+            summary: None,
+            deprecated: false,
         }];
 
         let doc =
@@ -1781,7 +2850,7 @@ pub fn get_users() -> String {
         let path_item = doc.paths.get("/users").unwrap();
         assert!(path_item.get.is_some());
         let operation = path_item.get.as_ref().unwrap();
-        assert_eq!(operation.operation_id, Some("get_users".to_string()));
+        assert_eq!(operation.operation_id, Some("users_get_users".to_string()));
     }
 
     #[test]
@@ -1811,9 +2880,7 @@ pub fn get_config() -> Config { Config { count: 0, name: String::new() } }
             module_path: "test::config".to_string(),
             file_path: route_file.to_string_lossy().to_string(),
             signature: "fn get_config() -> Config".to_string(),
-            error_status: None,
-            tags: None,
-            description: None,
+            ..Default::default()
         });
 
         let doc = generate_openapi_doc_with_metadata(None, None, None, &metadata, None, &[]);
@@ -0,0 +1,28 @@
+mod is_keyword_type;
+mod operation;
+mod parameters;
+mod path;
+mod request_body;
+mod response;
+mod schema;
+mod type_alias;
+pub use operation::build_operation_from_function;
+pub use parameters::ignored_parameter_reason;
+pub use path::extract_path_parameters;
+pub use request_body::parse_request_body;
+pub use response::{
+    BINARY_MEDIA_TYPE, EVENT_STREAM_MEDIA_TYPE, infer_success_status, parse_return_type,
+    returns_status_tuple, set_success_status, with_opaque_error_response,
+};
+pub use schema::{
+    GenericNaming, SerdeEnumRepr, apply_field_constraints, apply_field_content,
+    collect_defaulted_types, defaulted_fields, extract_default, extract_enum_repr,
+    extract_field_access, extract_field_format, extract_field_rename, extract_field_scope,
+    extract_flatten, extract_other, extract_rename_all, extract_schema_dialect,
+    extract_schema_examples, extract_schema_name_for, extract_schema_split, extract_schema_title,
+    extract_schema_xml, extract_skip, extract_skip_serializing_if, extract_untagged,
+    is_absolute_uri, parse_enum_to_schema, parse_json_value, parse_struct_to_schema,
+    parse_type_to_schema_ref, rename_field, strip_raw_prefix_owned, take_instantiations,
+    with_generic_naming, with_schema_scope, with_strict_nullability,
+};
+pub use type_alias::{find_crate_type_alias, find_type_alias, resolve_return_type_alias};
@@ -2,7 +2,9 @@ use std::cell::OnceCell;
 use std::collections::{BTreeMap, HashSet};
 
 use syn::{FnArg, PatType, Type};
-use vespera_core::route::{MediaType, Operation, Parameter, ParameterLocation, Response};
+use vespera_core::route::{
+    MediaType, Operation, Parameter, ParameterLocation, ParameterRef, Response,
+};
 
 use super::{
     parameters::parse_function_parameter, path::extract_path_parameters,
@@ -65,6 +67,8 @@ pub fn build_operation_from_function(
                                 struct_definitions,
                             )),
                             example: None,
+                            style: None,
+                            explode: None,
                         });
                     } else {
                         // If tuple doesn't have enough elements, use String as default
@@ -80,6 +84,8 @@ pub fn build_operation_from_function(
                                 struct_definitions,
                             )),
                             example: None,
+                            style: None,
+                            explode: None,
                         });
                     }
                 }
@@ -97,6 +103,8 @@ pub fn build_operation_from_function(
                             struct_definitions,
                         )),
                         example: None,
+                        style: None,
+                        explode: None,
                     });
                 } else {
                     // Multiple path parameters but single type - use String for all
@@ -112,6 +120,8 @@ pub fn build_operation_from_function(
                                 struct_definitions,
                             )),
                             example: None,
+                            style: None,
+                            explode: None,
                         });
                     }
                 }
@@ -130,6 +140,8 @@ pub fn build_operation_from_function(
                         struct_definitions,
                     )),
                     example: None,
+                    style: None,
+                    explode: None,
                 });
             }
         }
@@ -140,7 +152,7 @@ pub fn build_operation_from_function(
 
     // Parse function parameters (skip Path extractor as we already handled it)
     for input in &sig.inputs {
-        // Check if it's a request body (Json<T>)
+        // Check if it's a request body (Json<T>, Form<T>, ...)
         if let Some(body) = parse_request_body(input, known_schemas, struct_definitions) {
             request_body = Some(body);
         } else {
@@ -205,6 +217,7 @@ pub fn build_operation_from_function(
                         description: "Error response".to_string(),
                         headers: None,
                         content: Some(err_content),
+                        links: None,
                     }
                 });
             }
@@ -219,11 +232,13 @@ pub fn build_operation_from_function(
         parameters: if parameters.is_empty() {
             None
         } else {
-            Some(parameters)
+            Some(parameters.into_iter().map(ParameterRef::from).collect())
         },
         request_body,
         responses,
+        deprecated: None,
         security: None,
+        extensions: BTreeMap::new(),
     }
 }
 
@@ -236,8 +251,12 @@ mod tests {
 
     use super::*;
 
-    fn param_schema_type(param: &Parameter) -> Option<SchemaType> {
-        match param.schema.as_ref()? {
+    fn inline(param: &ParameterRef) -> &Parameter {
+        param.as_inline().expect("inline parameter expected")
+    }
+
+    fn param_schema_type(param: &ParameterRef) -> Option<SchemaType> {
+        match inline(param).schema.as_ref()? {
             SchemaRef::Inline(schema) => schema.schema_type.clone(),
             SchemaRef::Ref(_) => None,
         }
@@ -308,7 +327,7 @@ mod tests {
             Some(params) => {
                 assert_eq!(params.len(), expected.len());
                 for (param, exp) in params.iter().zip(expected) {
-                    assert_eq!(param.name, exp.name);
+                    assert_eq!(inline(param).name, exp.name);
                     assert_eq!(param_schema_type(param), exp.schema);
                 }
             }
@@ -376,6 +395,14 @@ mod tests {
         Some(ExpectedBody { content_type: "text/plain", schema: Some(SchemaType::String) }),
         vec![ExpectedResp { status: "200", schema: Some(SchemaType::String) }]
     )]
+    #[case(
+        "fn login(Form(body): Form<Login>) -> String",
+        "/login",
+        None::<&[u16]>,
+        vec![],
+        Some(ExpectedBody { content_type: "application/x-www-form-urlencoded", schema: None }),
+        vec![ExpectedResp { status: "200", schema: Some(SchemaType::String) }]
+    )]
     #[case(
         "fn get(Path(params): Path<(i32,)>) -> String",
         "/users/{id}/{name}",
@@ -506,7 +533,7 @@ mod tests {
         // Should have exactly 1 path parameter with Integer type
         let params = op.parameters.as_ref().expect("parameters expected");
         assert_eq!(params.len(), 1);
-        assert_eq!(params[0].name, "id");
+        assert_eq!(inline(&params[0]).name, "id");
         assert_eq!(param_schema_type(&params[0]), Some(SchemaType::Integer));
     }
 
@@ -521,7 +548,7 @@ mod tests {
 
         let params = op.parameters.as_ref().expect("parameters expected");
         assert_eq!(params.len(), 1);
-        assert_eq!(params[0].name, "user_id");
+        assert_eq!(inline(&params[0]).name, "user_id");
         assert_eq!(param_schema_type(&params[0]), Some(SchemaType::String));
     }
 
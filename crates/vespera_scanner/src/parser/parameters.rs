@@ -2,12 +2,13 @@ use std::collections::{HashMap, HashSet};
 
 use syn::{FnArg, Pat, PatType, Type};
 use vespera_core::{
-    route::{Parameter, ParameterLocation},
-    schema::{Schema, SchemaRef},
+    route::{Parameter, ParameterLocation, ParameterStyle},
+    schema::{Reference, Schema, SchemaRef},
 };
 
 use super::schema::{
-    extract_field_rename, extract_rename_all, is_primitive_type, parse_struct_to_schema,
+    apply_field_constraints, apply_field_content, extract_default, extract_field_rename,
+    extract_rename_all, is_primitive_type, parse_struct_to_schema,
     parse_type_to_schema_ref_with_schemas, rename_field,
 };
 use crate::schema_macro::type_utils::{
@@ -19,6 +20,46 @@ fn is_primitive_or_like(ty: &Type) -> bool {
     is_primitive_type(ty) || utils_is_primitive_like(ty)
 }
 
+/// Mark query parameters whose field is a struct or map, also inside `Option`, as
+/// `deepObject` (`filter[name]=x`). Arrays keep the default `form` style with `explode`.
+///
+/// Used for `QsQuery<T>`, whose `serde_qs` decoding uses bracket notation for nesting.
+fn mark_deep_object_parameters(
+    fields: &mut [(Parameter, Type)],
+    struct_definitions: &HashMap<String, String>,
+) {
+    for (parameter, ty) in fields {
+        if is_nested_object_type(ty, struct_definitions) {
+            parameter.style = Some(ParameterStyle::DeepObject);
+            parameter.explode = Some(true);
+        }
+    }
+}
+
+/// Whether `ty` (or `T` of `Option<T>`) is a map or a known struct.
+fn is_nested_object_type(ty: &Type, struct_definitions: &HashMap<String, String>) -> bool {
+    let Type::Path(type_path) = ty else {
+        return false;
+    };
+    let Some(segment) = type_path.path.segments.last() else {
+        return false;
+    };
+    if segment.ident == "Option" {
+        let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+            return false;
+        };
+        return matches!(
+            args.args.first(),
+            Some(syn::GenericArgument::Type(inner))
+                if is_nested_object_type(inner, struct_definitions)
+        );
+    }
+    utils_is_map_type(ty)
+        || struct_definitions
+            .get(&segment.ident.to_string())
+            .is_some_and(|definition| syn::parse_str::<syn::ItemStruct>(definition).is_ok())
+}
+
 /// Convert `SchemaRef` for query parameters, adding nullable flag if optional.
 /// Preserves `$ref` for known types (e.g. enums) — only wraps with nullable when optional.
 fn convert_to_inline_schema(field_schema: SchemaRef, is_optional: bool) -> SchemaRef {
@@ -103,6 +144,8 @@ pub fn parse_function_parameter(
                                 required: Some(false),
                                 schema: Some(SchemaRef::Inline(Box::new(Schema::string()))),
                                 example: None,
+                                style: None,
+                                explode: None,
                             }]);
                         }
                     }
@@ -147,6 +190,8 @@ pub fn parse_function_parameter(
                                                     ),
                                                 ),
                                                 example: None,
+                                                style: None,
+                                                explode: None,
                                             });
                                         }
                                     }
@@ -172,6 +217,8 @@ pub fn parse_function_parameter(
                                             struct_definitions,
                                         )),
                                         example: None,
+                                        style: None,
+                                        explode: None,
                                     }]);
                                 }
                             }
@@ -219,9 +266,44 @@ pub fn parse_function_parameter(
                                         struct_definitions,
                                     )),
                                     example: None,
+                                    style: None,
+                                    explode: None,
                                 }]);
                             }
                         }
+                        "QsQuery" => {
+                            // QsQuery<T> extractor (serde_qs) - struct fields, nested ones as deepObject
+                            if let syn::PathArguments::AngleBracketed(args) = &segment.arguments
+                                && let Some(syn::GenericArgument::Type(inner_ty)) =
+                                    args.args.first()
+                                && let Some(mut fields) =
+                                    query_struct_fields(inner_ty, known_schemas, struct_definitions)
+                            {
+                                mark_deep_object_parameters(&mut fields, struct_definitions);
+                                return Some(
+                                    fields.into_iter().map(|(parameter, _)| parameter).collect(),
+                                );
+                            }
+                            return None;
+                        }
+                        "Cookies" => {
+                            // Cookies<T> extractor (vespera::Cookies) - struct fields as cookie parameters
+                            if let syn::PathArguments::AngleBracketed(args) = &segment.arguments
+                                && let Some(syn::GenericArgument::Type(inner_ty)) =
+                                    args.args.first()
+                                && let Some(mut struct_params) = parse_query_struct_to_parameters(
+                                    inner_ty,
+                                    known_schemas,
+                                    struct_definitions,
+                                )
+                            {
+                                for parameter in &mut struct_params {
+                                    parameter.r#in = ParameterLocation::Cookie;
+                                }
+                                return Some(struct_params);
+                            }
+                            return None;
+                        }
                         "Header" => {
                             // Header<T> extractor
                             if let syn::PathArguments::AngleBracketed(args) = &segment.arguments
@@ -243,6 +325,8 @@ pub fn parse_function_parameter(
                                         struct_definitions,
                                     )),
                                     example: None,
+                                    style: None,
+                                    explode: None,
                                 }]);
                             }
                         }
@@ -256,9 +340,11 @@ pub fn parse_function_parameter(
                                 required: Some(true),
                                 schema: Some(SchemaRef::Inline(Box::new(Schema::string()))),
                                 example: None,
+                                style: None,
+                                explode: None,
                             }]);
                         }
-                        "Json" | "Form" | "TypedMultipart" | "Multipart" => {
+                        "Json" | "Xml" | "Form" | "TypedMultipart" | "Multipart" => {
                             // These extractors are handled as RequestBody
                             return None;
                         }
@@ -280,6 +366,8 @@ pub fn parse_function_parameter(
                         struct_definitions,
                     )),
                     example: None,
+                    style: None,
+                    explode: None,
                 }]);
             }
 
@@ -289,6 +377,83 @@ pub fn parse_function_parameter(
     }
 }
 
+/// Whether `pat` is `name` or `Extractor(name)`, the patterns `parse_function_parameter` can name.
+fn is_named_pattern(pat: &Pat) -> bool {
+    match pat {
+        Pat::Ident(_) => true,
+        Pat::TupleStruct(tuple_struct) => {
+            tuple_struct.elems.len() == 1 && matches!(tuple_struct.elems[0], Pat::Ident(_))
+        }
+        _ => false,
+    }
+}
+
+/// Why `parse_function_parameter` drops an argument that looks like it should be documented.
+///
+/// Only covers what the signature alone decides; arguments that are not parameters
+/// (`State`, `Json`, custom extractors, ...) return `None`.
+pub fn ignored_parameter_reason(
+    arg: &FnArg,
+    path_param_set: &HashSet<String>,
+) -> Option<&'static str> {
+    let FnArg::Typed(PatType { pat, ty, .. }) = arg else {
+        return None;
+    };
+    let Type::Path(type_path) = ty.as_ref() else {
+        return None;
+    };
+    let segment = type_path.path.segments.last()?;
+    let ident = segment.ident.to_string();
+    let inner_ty = match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) => match args.args.first() {
+            Some(syn::GenericArgument::Type(inner_ty)) => Some(inner_ty),
+            _ => None,
+        },
+        _ => None,
+    };
+    let is_optional_typed_header = ident == "Option"
+        && inner_ty.is_some_and(|inner_ty| match inner_ty {
+            Type::Path(inner) => inner
+                .path
+                .segments
+                .last()
+                .is_some_and(|s| s.ident == "TypedHeader"),
+            _ => false,
+        });
+    let is_parameter_extractor = is_optional_typed_header
+        || matches!(
+            ident.as_str(),
+            "Query" | "QsQuery" | "Cookies" | "Header" | "TypedHeader"
+        );
+    let is_path_param_name = match pat.as_ref() {
+        Pat::Ident(pat_ident) => path_param_set.contains(&pat_ident.ident.to_string()),
+        _ => false,
+    };
+
+    if is_parameter_extractor && !is_named_pattern(pat) {
+        return Some(
+            "its pattern is neither `name` nor `Extractor(name)`; bind it to a name and destructure it inside the handler",
+        );
+    }
+    match (ident.as_str(), inner_ty) {
+        ("Query" | "QsQuery" | "Cookies", Some(inner_ty)) if utils_is_map_type(inner_ty) => {
+            Some("a map has no fixed keys to document; use a struct with named fields")
+        }
+        ("Query" | "QsQuery" | "Cookies", Some(inner_ty)) if is_primitive_or_like(inner_ty) => {
+            Some("a primitive has no field names to document; use a struct with named fields")
+        }
+        ("Header", Some(inner_ty)) if is_primitive_or_like(inner_ty) => {
+            Some("a primitive header has no name to document; use `TypedHeader`")
+        }
+        // `String` is a plain-text request body
+        ("String" | "str", _) => None,
+        _ if !is_parameter_extractor && !is_path_param_name && is_primitive_type(ty) => Some(
+            "a bare type has no extractor, so its location cannot be inferred; wrap it in `Path`, `Query` or `TypedHeader`",
+        ),
+        _ => None,
+    }
+}
+
 fn is_known_type(
     ty: &Type,
     known_schemas: &HashSet<String>,
@@ -319,7 +484,7 @@ fn is_known_type(
         // Check for generic types like Vec<T>, Option<T> - recursively check inner type
         if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
             match ident_str.as_str() {
-                "Vec" | "HashSet" | "BTreeSet" | "Option" => {
+                "Vec" | "HashSet" | "BTreeSet" | "Option" | "EnumParam" => {
                     if let Some(syn::GenericArgument::Type(inner_ty)) = args.args.first() {
                         return is_known_type(inner_ty, known_schemas, struct_definitions);
                     }
@@ -339,6 +504,16 @@ fn parse_query_struct_to_parameters(
     known_schemas: &HashSet<String>,
     struct_definitions: &HashMap<String, String>,
 ) -> Option<Vec<Parameter>> {
+    query_struct_fields(ty, known_schemas, struct_definitions)
+        .map(|fields| fields.into_iter().map(|(parameter, _)| parameter).collect())
+}
+
+/// [`parse_query_struct_to_parameters`], keeping the Rust type of each parameter's field.
+fn query_struct_fields(
+    ty: &Type,
+    known_schemas: &HashSet<String>,
+    struct_definitions: &HashMap<String, String>,
+) -> Option<Vec<(Parameter, Type)>> {
     // Check if it's a known struct
     if let Type::Path(type_path) = ty {
         let path = &type_path.path;
@@ -359,6 +534,8 @@ fn parse_query_struct_to_parameters(
 
             // Extract rename_all attribute from struct
             let rename_all = extract_rename_all(&struct_item.attrs);
+            // Struct-level `#[serde(default)]` fills in every missing parameter
+            let container_default = extract_default(&struct_item.attrs).is_some();
 
             if let syn::Fields::Named(fields_named) = &struct_item.fields {
                 for field in &fields_named.named {
@@ -395,8 +572,7 @@ fn parse_query_struct_to_parameters(
                     // Convert ref to inline if needed (Query parameters should not use refs)
                     // If it's a ref to a known struct, get the struct definition and inline it
                     if let SchemaRef::Ref(ref_ref) = &field_schema
-                        && let Some(type_name) =
-                            ref_ref.ref_path.strip_prefix("#/components/schemas/")
+                        && let Some(type_name) = Reference::schema_name(&ref_ref.ref_path)
                         && let Some(struct_def) = struct_definitions.get(type_name)
                         && let Ok(nested_struct_item) =
                             syn::parse_str::<syn::ItemStruct>(struct_def)
@@ -410,18 +586,28 @@ fn parse_query_struct_to_parameters(
                         field_schema = SchemaRef::Inline(Box::new(nested_schema));
                     }
 
-                    let final_schema = convert_to_inline_schema(field_schema, is_optional);
-
-                    let required = !is_optional;
-
-                    parameters.push(Parameter {
-                        name: field_name,
-                        r#in: ParameterLocation::Query,
-                        description: None,
-                        required: Some(required),
-                        schema: Some(final_schema),
-                        example: None,
-                    });
+                    let mut final_schema = convert_to_inline_schema(field_schema, is_optional);
+                    apply_field_content(&mut final_schema, &field.attrs);
+                    apply_field_constraints(&mut final_schema, &field.attrs);
+
+                    // Serde fills in a missing `#[serde(default)]` field
+                    let required = !is_optional
+                        && !container_default
+                        && extract_default(&field.attrs).is_none();
+
+                    parameters.push((
+                        Parameter {
+                            name: field_name,
+                            r#in: ParameterLocation::Query,
+                            description: None,
+                            required: Some(required),
+                            schema: Some(final_schema),
+                            example: None,
+                            style: None,
+                            explode: None,
+                        },
+                        field_type.clone(),
+                    ));
                 }
             }
 
@@ -850,6 +1036,74 @@ mod tests {
         assert_eq!(params[1].required, Some(false));
     }
 
+    #[rstest]
+    #[case("", &[true, false])]
+    #[case("#[serde(default)]", &[false, false])]
+    fn test_query_struct_serde_default_not_required(
+        #[case] container_attrs: &str,
+        #[case] required: &[bool],
+    ) {
+        let struct_definitions = HashMap::from([(
+            "ListQuery".to_string(),
+            format!(
+                r#"
+                {container_attrs}
+                pub struct ListQuery {{
+                    pub q: String,
+                    #[serde(default = "default_limit")]
+                    pub limit: u32,
+                }}
+                "#
+            ),
+        )]);
+        let known_schemas = HashSet::from(["ListQuery".to_string()]);
+        let ty: Type = syn::parse_str("ListQuery").unwrap();
+        let params =
+            parse_query_struct_to_parameters(&ty, &known_schemas, &struct_definitions).unwrap();
+        let actual: Vec<bool> = params.iter().map(|p| p.required == Some(true)).collect();
+        assert_eq!(actual, required);
+    }
+
+    #[test]
+    fn test_query_struct_field_format() {
+        let struct_definitions = HashMap::from([(
+            "ContactQuery".to_string(),
+            r#"
+            pub struct ContactQuery {
+                #[schema(format = "email")]
+                pub email: String,
+                #[schema(format = "hostname")]
+                pub host: Option<String>,
+                #[schema(format = "uri")]
+                pub links: Vec<String>,
+            }
+            "#
+            .to_string(),
+        )]);
+        let known_schemas = HashSet::from(["ContactQuery".to_string()]);
+        let ty: Type = syn::parse_str("ContactQuery").unwrap();
+        let params =
+            parse_query_struct_to_parameters(&ty, &known_schemas, &struct_definitions).unwrap();
+        let formats: Vec<Option<String>> = params
+            .iter()
+            .map(|p| match p.schema.as_ref().unwrap() {
+                SchemaRef::Inline(schema) => match schema.items.as_deref() {
+                    Some(SchemaRef::Inline(items)) => items.format.clone(),
+                    _ => schema.format.clone(),
+                },
+                SchemaRef::Ref(_) => None,
+            })
+            .collect();
+        assert_eq!(
+            formats,
+            vec![
+                Some("email".to_string()),
+                Some("hostname".to_string()),
+                Some("uri".to_string()),
+            ]
+        );
+    }
+
     // ======== Tests for uncovered lines ========
 
     #[test]
@@ -1281,4 +1535,145 @@ mod tests {
             _ => panic!("Expected inline schema with ref_path and nullable for Option<Enum>"),
         }
     }
+
+    #[rstest]
+    #[case("filter: Filter", true)]
+    #[case("filter: Option<Filter>", true)]
+    #[case("filter: HashMap<String, String>", true)]
+    #[case("filter: Vec<i32>", false)]
+    #[case("filter: Option<Vec<Filter>>", false)]
+    #[case("filter: Status", false)]
+    #[case("filter: i32", false)]
+    fn test_qs_query_deep_object_fields(#[case] field: &str, #[case] deep_object: bool) {
+        let struct_definitions = HashMap::from([
+            (
+                "SearchParams".to_string(),
+                format!("pub struct SearchParams {{ pub {field}, pub page: i32 }}"),
+            ),
+            (
+                "Filter".to_string(),
+                "pub struct Filter { pub name: String }".to_string(),
+            ),
+            (
+                "Status".to_string(),
+                "pub enum Status { Active, Archived }".to_string(),
+            ),
+        ]);
+        let known_schemas = HashSet::from(["Filter".to_string(), "Status".to_string()]);
+
+        let func: syn::ItemFn =
+            syn::parse_str("fn test(params: QsQuery<SearchParams>) {}").unwrap();
+        let arg = func.sig.inputs.first().unwrap();
+        let params = parse_function_parameter(
+            arg,
+            &[],
+            &HashSet::new(),
+            &known_schemas,
+            &struct_definitions,
+        )
+        .unwrap();
+
+        assert_eq!(params.len(), 2);
+        assert_eq!(params[0].name, "filter");
+        // Arrays keep the default `form` style with `explode`
+        let (style, explode) = if deep_object {
+            (Some(ParameterStyle::DeepObject), Some(true))
+        } else {
+            (None, None)
+        };
+        assert_eq!(params[0].style, style);
+        assert_eq!(params[0].explode, explode);
+        assert_eq!(params[1].style, None);
+    }
+
+    #[test]
+    fn test_qs_query_non_struct_is_ignored() {
+        let func: syn::ItemFn = syn::parse_str("fn test(q: QsQuery<i32>) {}").unwrap();
+        let arg = func.sig.inputs.first().unwrap();
+        assert!(
+            parse_function_parameter(arg, &[], &HashSet::new(), &HashSet::new(), &HashMap::new())
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_cookies_struct_expands_to_cookie_parameters() {
+        let mut struct_definitions = HashMap::new();
+        struct_definitions.insert(
+            "SessionCookies".to_string(),
+            r#"
+            #[serde(rename_all = "camelCase")]
+            pub struct SessionCookies {
+                pub session_id: String,
+                pub theme: Option<String>,
+            }
+            "#
+            .to_string(),
+        );
+
+        let func: syn::ItemFn =
+            syn::parse_str("fn test(Cookies(c): Cookies<SessionCookies>) {}").unwrap();
+        let arg = func.sig.inputs.first().unwrap();
+        let params = parse_function_parameter(
+            arg,
+            &[],
+            &HashSet::new(),
+            &HashSet::new(),
+            &struct_definitions,
+        )
+        .unwrap();
+
+        assert_eq!(params.len(), 2);
+        assert_eq!(params[0].name, "sessionId");
+        assert_eq!(params[0].r#in, ParameterLocation::Cookie);
+        assert_eq!(params[0].required, Some(true));
+        assert_eq!(params[1].name, "theme");
+        assert_eq!(params[1].r#in, ParameterLocation::Cookie);
+        assert_eq!(params[1].required, Some(false));
+    }
+
+    #[rstest]
+    #[case("fn test(c: Cookies<String>) {}")]
+    #[case("fn test(jar: CookieJar) {}")]
+    fn test_cookies_without_named_fields_are_ignored(#[case] func_src: &str) {
+        let func: syn::ItemFn = syn::parse_str(func_src).unwrap();
+        let arg = func.sig.inputs.first().unwrap();
+        assert!(
+            parse_function_parameter(arg, &[], &HashSet::new(), &HashSet::new(), &HashMap::new())
+                .is_none()
+        );
+    }
+
+    #[rstest]
+    #[case("fn test(id: i32) {}", &[], Some("a bare type"))]
+    #[case("fn test(id: i32) {}", &["id"], None)]
+    #[case("fn test(body: String) {}", &[], None)]
+    #[case("fn test(auth: Option<AuthUser>) {}", &[], None)]
+    #[case("fn test(State(state): State<AppState>) {}", &[], None)]
+    #[case("fn test(Json(body): Json<User>) {}", &[], None)]
+    #[case("fn test(Query(q): Query<HashMap<String, String>>) {}", &[], Some("a map"))]
+    #[case("fn test(Query(page): Query<u32>) {}", &[], Some("a primitive has no field names"))]
+    #[case("fn test(QsQuery(ids): QsQuery<Vec<i32>>) {}", &[], Some("a primitive has no field names"))]
+    #[case("fn test(Query(params): Query<Params>) {}", &[], None)]
+    #[case("fn test(Cookies(c): Cookies<HashMap<String, String>>) {}", &[], Some("a map"))]
+    #[case("fn test(Cookies(c): Cookies<Session>) {}", &[], None)]
+    #[case("fn test(Query(Params { page }): Query<Params>) {}", &[], Some("its pattern"))]
+    #[case("fn test(_: TypedHeader<UserAgent>) {}", &[], Some("its pattern"))]
+    #[case("fn test(_: Option<TypedHeader<UserAgent>>) {}", &[], Some("its pattern"))]
+    #[case("fn test(user_agent: TypedHeader<UserAgent>) {}", &[], None)]
+    #[case("fn test(Header(token): Header<String>) {}", &[], Some("a primitive header"))]
+    fn test_ignored_parameter_reason(
+        #[case] func_src: &str,
+        #[case] path_params: &[&str],
+        #[case] expected: Option<&str>,
+    ) {
+        let func: syn::ItemFn = syn::parse_str(func_src).unwrap();
+        let arg = func.sig.inputs.first().unwrap();
+        let path_param_set: HashSet<String> = path_params.iter().map(ToString::to_string).collect();
+        let reason = ignored_parameter_reason(arg, &path_param_set);
+        match expected {
+            Some(expected) => assert!(reason.unwrap().starts_with(expected), "{reason:?}"),
+            None => assert_eq!(reason, None),
+        }
+    }
 }
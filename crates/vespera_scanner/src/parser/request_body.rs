@@ -4,7 +4,10 @@ use syn::{FnArg, PatType, Type};
 use vespera_core::route::{MediaType, RequestBody};
 use vespera_core::schema::{Schema, SchemaRef, SchemaType};
 
-use super::schema::parse_type_to_schema_ref_with_schemas;
+use super::{
+    response::{BINARY_MEDIA_TYPE, is_binary_body},
+    schema::parse_type_to_schema_ref_with_schemas,
+};
 
 fn is_string_like(ty: &Type) -> bool {
     match ty {
@@ -35,7 +38,7 @@ pub fn parse_request_body(
                 let segment = path.segments.last().unwrap();
                 let ident_str = segment.ident.to_string();
 
-                if ident_str == "Json"
+                if (ident_str == "Json" || ident_str == "Xml")
                     && let syn::PathArguments::AngleBracketed(args) = &segment.arguments
                     && let Some(syn::GenericArgument::Type(inner_ty)) = args.args.first()
                 {
@@ -44,9 +47,14 @@ pub fn parse_request_body(
                         known_schemas,
                         struct_definitions,
                     );
+                    let media_type = if ident_str == "Xml" {
+                        "application/xml"
+                    } else {
+                        "application/json"
+                    };
                     let mut content = BTreeMap::new();
                     content.insert(
-                        "application/json".to_string(),
+                        media_type.to_string(),
                         MediaType {
                             schema: Some(schema),
                             example: None,
@@ -134,6 +142,26 @@ pub fn parse_request_body(
                 }
             }
 
+            // Raw bytes (`Bytes`, `Vec<u8>`, `Body`) → application/octet-stream
+            if is_binary_body(ty) {
+                let mut content = BTreeMap::new();
+                content.insert(
+                    BINARY_MEDIA_TYPE.to_string(),
+                    MediaType {
+                        schema: Some(SchemaRef::Inline(Box::new(Schema::binary_with_media_type(
+                            BINARY_MEDIA_TYPE,
+                        )))),
+                        example: None,
+                        examples: None,
+                    },
+                );
+                return Some(RequestBody {
+                    description: None,
+                    required: Some(true),
+                    content,
+                });
+            }
+
             if is_string_like(ty.as_ref()) {
                 let schema =
                     parse_type_to_schema_ref_with_schemas(ty, known_schemas, struct_definitions);
@@ -182,6 +210,7 @@ mod tests {
 
     #[rstest]
     #[case::json("fn test(Json(payload): Json<User>) {}", true, "json")]
+    #[case::xml("fn test(Xml(payload): Xml<User>) {}", true, "xml")]
     #[case::form("fn test(Form(input): Form<User>) {}", true, "form")]
     #[case::string("fn test(just_string: String) {}", true, "string")]
     #[case::str("fn test(just_str: &str) {}", true, "str")]
@@ -193,6 +222,9 @@ mod tests {
         "typed_multipart"
     )]
     #[case::multipart_raw("fn test(multipart: Multipart) {}", true, "multipart_raw")]
+    #[case::bytes("fn test(body: Bytes) {}", true, "bytes")]
+    #[case::vec_u8("fn test(body: Vec<u8>) {}", true, "vec_u8")]
+    #[case::body("fn test(body: axum::body::Body) {}", true, "body")]
     #[case::self_ref("fn test(&self) {}", false, "self_ref")]
     fn test_parse_request_body_cases(
         #[case] func_src: &str,
@@ -1,12 +1,65 @@
-use std::collections::{BTreeMap, HashMap, HashSet};
-
-use syn::{ReturnType, Type};
-use vespera_core::route::{Header, MediaType, Response};
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+};
+
+use proc_macro2::{Delimiter, TokenStream, TokenTree};
+use syn::{ReturnType, Type, parse::Parser};
+use vespera_core::{
+    route::{HeaderRef, MediaType, Response},
+    schema::{Schema, SchemaRef},
+};
 
 use super::schema::parse_type_to_schema_ref_with_schemas;
 use crate::parser::is_keyword_type::{KeywordType, is_keyword_type, is_keyword_type_by_type_path};
 
-/// Unwrap Json<T> to get T
+thread_local! {
+    /// `(status, body type)` documented for opaque error types (`anyhow_error = (...)`).
+    static OPAQUE_ERROR_RESPONSE: RefCell<Option<(u16, Type)>> = const { RefCell::new(None) };
+}
+
+/// Run `f` documenting opaque handler errors as `mapping`.
+///
+/// Opaque errors are `anyhow::Error`, `eyre::Report`, `Box<dyn Error>` and wrapper types
+/// named `*Error` that have no schema (e.g. `struct AppError(anyhow::Error)`). Without a
+/// mapping they keep the default `400` response with a free-form body.
+pub fn with_opaque_error_response<R>(mapping: Option<(u16, &str)>, f: impl FnOnce() -> R) -> R {
+    let mapping = mapping.and_then(|(status, ty)| Some((status, syn::parse_str(ty).ok()?)));
+    let previous = OPAQUE_ERROR_RESPONSE.with(|active| active.replace(mapping));
+    let result = f();
+    OPAQUE_ERROR_RESPONSE.with(|active| *active.borrow_mut() = previous);
+    result
+}
+
+/// Whether `err_ty` carries no documentable body (see [`with_opaque_error_response`]).
+fn is_opaque_error(err_ty: &Type, known_schemas: &HashSet<String>) -> bool {
+    let Type::Path(type_path) = err_ty else {
+        return false;
+    };
+    let Some(last) = type_path.path.segments.last() else {
+        return false;
+    };
+    let name = last.ident.to_string();
+    let qualified = |module: &str| {
+        type_path
+            .path
+            .segments
+            .iter()
+            .any(|segment| segment.ident == module)
+    };
+    match name.as_str() {
+        "Error" if qualified("anyhow") => true,
+        "Report" if qualified("eyre") => true,
+        "Box" => matches!(
+            &last.arguments,
+            syn::PathArguments::AngleBracketed(args)
+                if matches!(args.args.first(), Some(syn::GenericArgument::Type(Type::TraitObject(_))))
+        ),
+        _ => name.ends_with("Error") && !known_schemas.contains(&name),
+    }
+}
+
+/// Unwrap Json<T> (or Xml<T>) to get T
 /// Handles both Json<T> and `vespera::axum::Json`<T> by checking the last segment
 fn unwrap_json(ty: &Type) -> &Type {
     if let Type::Path(type_path) = ty {
@@ -14,7 +67,7 @@ fn unwrap_json(ty: &Type) -> &Type {
         if !path.segments.is_empty() {
             // Check the last segment (handles both Json<T> and vespera::axum::Json<T>)
             let segment = path.segments.last().unwrap();
-            if segment.ident == "Json"
+            if (segment.ident == "Json" || segment.ident == "Xml")
                 && let syn::PathArguments::AngleBracketed(args) = &segment.arguments
                 && let Some(syn::GenericArgument::Type(inner_ty)) = args.args.first()
             {
@@ -25,6 +78,81 @@ fn unwrap_json(ty: &Type) -> &Type {
     ty
 }
 
+/// Media type of raw byte bodies (`Bytes`, `Vec<u8>`, `Body`)
+pub const BINARY_MEDIA_TYPE: &str = "application/octet-stream";
+
+/// Whether `ty` is a raw byte body: `Bytes`, `Vec<u8>` or axum's `Body`.
+pub fn is_binary_body(ty: &Type) -> bool {
+    let Type::Path(type_path) = ty else {
+        return false;
+    };
+    let Some(segment) = type_path.path.segments.last() else {
+        return false;
+    };
+    match &segment.arguments {
+        syn::PathArguments::None => segment.ident == "Bytes" || segment.ident == "Body",
+        syn::PathArguments::AngleBracketed(args) if segment.ident == "Vec" => matches!(
+            args.args.first(),
+            Some(syn::GenericArgument::Type(Type::Path(inner))) if inner.path.is_ident("u8")
+        ),
+        syn::PathArguments::AngleBracketed(_) | syn::PathArguments::Parenthesized(_) => false,
+    }
+}
+
+/// Media type of Server-Sent Events responses (`Sse<S>`)
+pub const EVENT_STREAM_MEDIA_TYPE: &str = "text/event-stream";
+
+/// Whether `ty` is axum's `Sse<S>` responder.
+fn is_sse_body(ty: &Type) -> bool {
+    matches!(ty, Type::Path(type_path) if type_path
+        .path
+        .segments
+        .last()
+        .is_some_and(|segment| segment.ident == "Sse"))
+}
+
+/// Media type of a response body: `application/xml` for an `Xml<T>` responder,
+/// `application/octet-stream` for raw bytes (alone or as the body of a tuple),
+/// `text/event-stream` for `Sse<S>`, `application/json` otherwise.
+fn body_media_type(ty: &Type) -> &'static str {
+    let body = match ty {
+        Type::Tuple(tuple) => tuple.elems.iter().rev().find(|ty| !is_non_body_type(ty)),
+        _ => Some(ty),
+    };
+    match body {
+        Some(Type::Path(type_path))
+            if type_path
+                .path
+                .segments
+                .last()
+                .is_some_and(|segment| segment.ident == "Xml") =>
+        {
+            "application/xml"
+        }
+        Some(ty) if is_binary_body(ty) => BINARY_MEDIA_TYPE,
+        Some(ty) if is_sse_body(ty) => EVENT_STREAM_MEDIA_TYPE,
+        _ => "application/json",
+    }
+}
+
+/// Schema of a response body served as `media_type`: binary for raw bytes,
+/// a plain string for an event stream (until `sse_event = ...` names the payload),
+/// else the schema of `ty`.
+fn body_schema(
+    media_type: &str,
+    ty: &Type,
+    known_schemas: &HashSet<String>,
+    struct_definitions: &HashMap<String, String>,
+) -> SchemaRef {
+    if media_type == BINARY_MEDIA_TYPE {
+        SchemaRef::Inline(Box::new(Schema::binary_with_media_type(BINARY_MEDIA_TYPE)))
+    } else if media_type == EVENT_STREAM_MEDIA_TYPE {
+        SchemaRef::Inline(Box::new(Schema::string()))
+    } else {
+        parse_type_to_schema_ref_with_schemas(ty, known_schemas, struct_definitions)
+    }
+}
+
 /// Extract Ok and Err types from Result<T, E> or Result<Json<T>, E>
 /// Handles both Result and `std::result::Result`, and unwraps references
 fn extract_result_types(ty: &Type) -> Option<(Type, Type)> {
@@ -54,10 +182,9 @@ fn extract_result_types(ty: &Type) -> Option<(Type, Type)> {
         && let (Some(syn::GenericArgument::Type(ok_ty)), Some(syn::GenericArgument::Type(err_ty))) =
             (args.args.first(), args.args.get(1))
     {
-        // Get the last segment (Result) to check for generics
-        // Unwrap Json from Ok type if present
-        let ok_ty_unwrapped = unwrap_json(ok_ty);
-        return Some((ok_ty_unwrapped.clone(), err_ty.clone()));
+        // The Ok type keeps its Json/Xml wrapper so the body's media type can be told;
+        // `extract_ok_payload_and_headers` unwraps it
+        return Some((ok_ty.clone(), err_ty.clone()));
     }
     None
 }
@@ -90,7 +217,7 @@ fn is_non_body_type(ty: &Type) -> bool {
 /// Non-body types (`StatusCode`, `HeaderMap`, `CookieJar`) are filtered out.
 /// The last remaining element is treated as the response body.
 /// Any presence of `HeaderMap` in the tuple marks headers as present.
-fn extract_ok_payload_and_headers(ok_ty: &Type) -> (Type, Option<HashMap<String, Header>>) {
+fn extract_ok_payload_and_headers(ok_ty: &Type) -> (Type, Option<HashMap<String, HeaderRef>>) {
     if let Type::Tuple(tuple) = ok_ty {
         // Find the body type: last element that is NOT a non-body type
         let payload_ty = tuple
@@ -114,7 +241,7 @@ fn extract_ok_payload_and_headers(ok_ty: &Type) -> (Type, Option<HashMap<String,
         }
     }
 
-    (ok_ty.clone(), None)
+    (unwrap_json(ok_ty).clone(), None)
 }
 
 /// Analyze return type and convert to Responses map
@@ -135,6 +262,7 @@ pub fn parse_return_type(
                     description: "Successful response".to_string(),
                     headers: None,
                     content: None,
+                    links: None,
                 },
             );
         }
@@ -142,20 +270,22 @@ pub fn parse_return_type(
             // Check if it's a Result<T, E>
             if let Some((ok_ty, err_ty)) = extract_result_types(ty) {
                 // Handle success response (200)
+                let ok_media_type = body_media_type(&ok_ty);
                 let (ok_payload_ty, ok_headers) = extract_ok_payload_and_headers(&ok_ty);
 
                 // StatusCode alone means no response body — just the HTTP status code
                 let ok_content = if is_keyword_type(&ok_payload_ty, &KeywordType::StatusCode) {
                     None
                 } else {
-                    let ok_schema = parse_type_to_schema_ref_with_schemas(
+                    let ok_schema = body_schema(
+                        ok_media_type,
                         &ok_payload_ty,
                         known_schemas,
                         struct_definitions,
                     );
                     let mut content = BTreeMap::new();
                     content.insert(
-                        "application/json".to_string(),
+                        ok_media_type.to_string(),
                         MediaType {
                             schema: Some(ok_schema),
                             example: None,
@@ -171,15 +301,17 @@ pub fn parse_return_type(
                         description: "Successful response".to_string(),
                         headers: ok_headers,
                         content: ok_content,
+                        links: None,
                     },
                 );
 
                 // Handle error response
-                // Check if error is (StatusCode, E) tuple
-                if let Some((status_code, error_type)) = extract_status_code_tuple(&err_ty) {
-                    // Use the status code from the tuple
+                let opaque_error_response = OPAQUE_ERROR_RESPONSE
+                    .with(|active| active.borrow().clone())
+                    .filter(|_| is_opaque_error(unwrap_json(&err_ty), known_schemas));
+                if let Some((status_code, body_ty)) = opaque_error_response {
                     let err_schema = parse_type_to_schema_ref_with_schemas(
-                        &error_type,
+                        &body_ty,
                         known_schemas,
                         struct_definitions,
                     );
@@ -199,20 +331,52 @@ pub fn parse_return_type(
                             description: "Error response".to_string(),
                             headers: None,
                             content: Some(err_content),
+                            links: None,
+                        },
+                    );
+                } else if let Some((status_code, error_type)) = extract_status_code_tuple(&err_ty) {
+                    // Check if error is (StatusCode, E) tuple
+                    // Use the status code from the tuple
+                    let err_media_type = body_media_type(&err_ty);
+                    let err_schema = body_schema(
+                        err_media_type,
+                        &error_type,
+                        known_schemas,
+                        struct_definitions,
+                    );
+                    let mut err_content = BTreeMap::new();
+                    err_content.insert(
+                        err_media_type.to_string(),
+                        MediaType {
+                            schema: Some(err_schema),
+                            example: None,
+                            examples: None,
+                        },
+                    );
+
+                    responses.insert(
+                        status_code.to_string(),
+                        Response {
+                            description: "Error response".to_string(),
+                            headers: None,
+                            content: Some(err_content),
+                            links: None,
                         },
                     );
                 } else {
                     // Regular error type - use default 400
                     // Unwrap Json if present
                     let err_ty_unwrapped = unwrap_json(&err_ty);
-                    let err_schema = parse_type_to_schema_ref_with_schemas(
+                    let err_media_type = body_media_type(&err_ty);
+                    let err_schema = body_schema(
+                        err_media_type,
                         err_ty_unwrapped,
                         known_schemas,
                         struct_definitions,
                     );
                     let mut err_content = BTreeMap::new();
                     err_content.insert(
-                        "application/json".to_string(),
+                        err_media_type.to_string(),
                         MediaType {
                             schema: Some(err_schema),
                             example: None,
@@ -226,26 +390,25 @@ pub fn parse_return_type(
                             description: "Error response".to_string(),
                             headers: None,
                             content: Some(err_content),
+                            links: None,
                         },
                     );
                 }
             } else {
                 // Not a Result type - regular response
-                // Unwrap Json<T> if present
-                let unwrapped_ty = unwrap_json(ty);
+                // Unwrap Json<T> (and the body of a `(StatusCode, Json<T>)` tuple) if present
+                let (payload_ty, headers) = extract_ok_payload_and_headers(ty);
 
                 // StatusCode alone means no response body
-                let content = if is_keyword_type(unwrapped_ty, &KeywordType::StatusCode) {
+                let content = if is_keyword_type(&payload_ty, &KeywordType::StatusCode) {
                     None
                 } else {
-                    let schema = parse_type_to_schema_ref_with_schemas(
-                        unwrapped_ty,
-                        known_schemas,
-                        struct_definitions,
-                    );
+                    let media_type = body_media_type(ty);
+                    let schema =
+                        body_schema(media_type, &payload_ty, known_schemas, struct_definitions);
                     let mut c = BTreeMap::new();
                     c.insert(
-                        "application/json".to_string(),
+                        media_type.to_string(),
                         MediaType {
                             schema: Some(schema),
                             example: None,
@@ -259,8 +422,9 @@ pub fn parse_return_type(
                     "200".to_string(),
                     Response {
                         description: "Successful response".to_string(),
-                        headers: None,
+                        headers,
                         content,
+                        links: None,
                     },
                 );
             }
@@ -270,6 +434,98 @@ pub fn parse_return_type(
     responses
 }
 
+/// `StatusCode` constants of the 2xx statuses, as named by the `http` crate.
+const SUCCESS_STATUS_CODES: [(&str, u16); 10] = [
+    ("OK", 200),
+    ("CREATED", 201),
+    ("ACCEPTED", 202),
+    ("NON_AUTHORITATIVE_INFORMATION", 203),
+    ("NO_CONTENT", 204),
+    ("RESET_CONTENT", 205),
+    ("PARTIAL_CONTENT", 206),
+    ("MULTI_STATUS", 207),
+    ("ALREADY_REPORTED", 208),
+    ("IM_USED", 226),
+];
+
+/// Whether the success type of `return_type` (`T`, or `T` in `Result<T, E>`) is a tuple
+/// carrying a `StatusCode`, i.e. the handler picks its own success status.
+pub fn returns_status_tuple(return_type: &ReturnType) -> bool {
+    let ReturnType::Type(_, ty) = return_type else {
+        return false;
+    };
+    let ok_ty = extract_result_types(ty).map_or_else(|| ty.as_ref().clone(), |(ok_ty, _)| ok_ty);
+    matches!(&ok_ty, Type::Tuple(tuple) if tuple
+        .elems
+        .iter()
+        .any(|ty| is_keyword_type(ty, &KeywordType::StatusCode)))
+}
+
+/// The success status a handler body sets with `(StatusCode::CREATED, ...)` tuples.
+///
+/// `None` unless the body opens its tuples with exactly one 2xx `StatusCode` constant;
+/// error statuses (`(StatusCode::NOT_FOUND, ...)`) are ignored.
+pub fn infer_success_status(body: &syn::Block) -> Option<u16> {
+    let mut statuses = BTreeSet::new();
+    collect_tuple_statuses(quote::quote!(#body), &mut statuses);
+    let mut statuses = statuses.into_iter();
+    match (statuses.next(), statuses.next()) {
+        (Some(status), None) => Some(status),
+        _ => None,
+    }
+}
+
+/// Collect the 2xx status of every parenthesized group that reads as a tuple starting
+/// with a `StatusCode::*` constant. Groups after an identifier are calls, not tuples.
+fn collect_tuple_statuses(tokens: TokenStream, statuses: &mut BTreeSet<u16>) {
+    let mut previous: Option<TokenTree> = None;
+    for token in tokens {
+        if let TokenTree::Group(group) = &token {
+            let is_call = matches!(&previous, Some(TokenTree::Ident(ident)) if ident != "return");
+            if group.delimiter() == Delimiter::Parenthesis
+                && !is_call
+                && let Some(status) = leading_status_constant(group.stream())
+            {
+                statuses.insert(status);
+            }
+            collect_tuple_statuses(group.stream(), statuses);
+        }
+        previous = Some(token);
+    }
+}
+
+/// `201` for a tuple `StatusCode::CREATED, ...`.
+fn leading_status_constant(tokens: TokenStream) -> Option<u16> {
+    let elems = syn::punctuated::Punctuated::<syn::Expr, syn::Token![,]>::parse_terminated
+        .parse2(tokens)
+        .ok()?;
+    if elems.len() < 2 {
+        return None;
+    }
+    let syn::Expr::Path(expr_path) = elems.first()? else {
+        return None;
+    };
+    let mut segments = expr_path.path.segments.iter().rev();
+    let constant = segments.next()?;
+    if segments.next()?.ident != "StatusCode" {
+        return None;
+    }
+    SUCCESS_STATUS_CODES
+        .iter()
+        .find(|(name, _)| constant.ident == name)
+        .map(|(_, status)| *status)
+}
+
+/// Document the inferred success response under `status` instead of `200`.
+pub fn set_success_status(responses: &mut BTreeMap<String, Response>, status: u16) {
+    if status == 200 || responses.contains_key(&status.to_string()) {
+        return;
+    }
+    if let Some(response) = responses.remove("200") {
+        responses.insert(status.to_string(), response);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
@@ -524,6 +780,42 @@ mod tests {
 
     // ======== Tests for uncovered lines ========
 
+    #[rstest]
+    #[case("-> Result<String, anyhow::Error>", true)]
+    #[case("-> Result<Json<User>, AppError>", true)]
+    #[case("-> Result<String, Box<dyn std::error::Error + Send + Sync>>", true)]
+    #[case("-> Result<String, eyre::Report>", true)]
+    #[case("-> Result<String, ApiError>", false)] // documented with #[derive(Schema)]
+    #[case("-> Result<String, Json<ErrorBody>>", false)]
+    #[case("-> Result<String, (StatusCode, String)>", false)]
+    #[case("-> Result<String, Error>", true)]
+    fn test_parse_return_type_opaque_error(#[case] return_type: &str, #[case] mapped: bool) {
+        let known_schemas: HashSet<String> = ["ApiError", "ErrorBody", "ErrorResponse", "User"]
+            .into_iter()
+            .map(str::to_string)
+            .collect();
+        let return_type = parse_return_type_str(return_type);
+
+        let responses = with_opaque_error_response(Some((500, "ErrorResponse")), || {
+            parse_return_type(&return_type, &known_schemas, &HashMap::new())
+        });
+        assert_eq!(responses.contains_key("500"), mapped, "{responses:?}");
+        assert_eq!(responses.contains_key("400"), !mapped, "{responses:?}");
+        if mapped {
+            let schema = responses["500"].content.as_ref().unwrap()["application/json"]
+                .schema
+                .clone();
+            assert!(matches!(
+                schema,
+                Some(SchemaRef::Ref(r)) if r.ref_path == "#/components/schemas/ErrorResponse"
+            ));
+        }
+
+        // Without a mapping the default 400 response is kept
+        let responses = parse_return_type(&return_type, &known_schemas, &HashMap::new());
+        assert!(responses.contains_key("400"));
+    }
+
     #[test]
     fn test_extract_result_types_non_path_non_ref() {
         // Test line 43: type that's neither Path nor Reference returns None
@@ -803,4 +1095,139 @@ mod tests {
         let json: syn::Type = syn::parse_str("Json<String>").unwrap();
         assert!(!is_non_body_type(&json));
     }
+
+    #[rstest]
+    #[case("-> Xml<User>", "200", "application/xml")]
+    #[case("-> (StatusCode, Xml<User>)", "200", "application/xml")]
+    #[case("-> Result<Xml<User>, Json<ApiError>>", "200", "application/xml")]
+    #[case("-> Result<Xml<User>, Json<ApiError>>", "400", "application/json")]
+    #[case(
+        "-> Result<(HeaderMap, Xml<User>), (StatusCode, Xml<ApiError>)>",
+        "200",
+        "application/xml"
+    )]
+    #[case(
+        "-> Result<(HeaderMap, Xml<User>), (StatusCode, Xml<ApiError>)>",
+        "400",
+        "application/xml"
+    )]
+    #[case("-> Result<Json<User>, ApiError>", "200", "application/json")]
+    fn test_parse_return_type_xml_responder(
+        #[case] return_type_str: &str,
+        #[case] status: &str,
+        #[case] media_type: &str,
+    ) {
+        let known_schemas = HashSet::from(["User".to_string(), "ApiError".to_string()]);
+        let return_type = parse_return_type_str(return_type_str);
+        let responses = parse_return_type(&return_type, &known_schemas, &HashMap::new());
+        let content = responses[status].content.as_ref().unwrap();
+        assert_eq!(content.keys().collect::<Vec<_>>(), vec![media_type]);
+        assert!(matches!(
+            content[media_type].schema,
+            Some(SchemaRef::Ref(_))
+        ));
+    }
+
+    #[rstest]
+    #[case("-> Bytes", "200", "application/octet-stream")]
+    #[case("-> Vec<u8>", "200", "application/octet-stream")]
+    #[case("-> axum::body::Body", "200", "application/octet-stream")]
+    #[case("-> (HeaderMap, Bytes)", "200", "application/octet-stream")]
+    #[case(
+        "-> Result<(StatusCode, HeaderMap, Vec<u8>), ApiError>",
+        "200",
+        "application/octet-stream"
+    )]
+    #[case("-> Json<Vec<u8>>", "200", "application/json")]
+    fn test_parse_return_type_binary(
+        #[case] return_type_str: &str,
+        #[case] status: &str,
+        #[case] media_type: &str,
+    ) {
+        let known_schemas = HashSet::from(["ApiError".to_string()]);
+        let return_type = parse_return_type_str(return_type_str);
+        let responses = parse_return_type(&return_type, &known_schemas, &HashMap::new());
+        let content = responses[status].content.as_ref().unwrap();
+        assert_eq!(content.keys().collect::<Vec<_>>(), vec![media_type]);
+        let Some(SchemaRef::Inline(schema)) = &content[media_type].schema else {
+            panic!("expected inline schema for {return_type_str}");
+        };
+        if media_type == BINARY_MEDIA_TYPE {
+            assert_eq!(schema.format.as_deref(), Some("binary"));
+            assert_eq!(
+                schema.content_media_type.as_deref(),
+                Some(BINARY_MEDIA_TYPE)
+            );
+        } else {
+            assert_eq!(schema.schema_type, Some(SchemaType::Array));
+        }
+    }
+
+    #[rstest]
+    #[case("-> Sse<impl Stream<Item = Result<Event, Infallible>>>", "200")]
+    #[case("-> axum::response::sse::Sse<EventStream>", "200")]
+    #[case("-> Result<Sse<EventStream>, ApiError>", "200")]
+    #[case("-> (HeaderMap, Sse<EventStream>)", "200")]
+    fn test_parse_return_type_sse(#[case] return_type_str: &str, #[case] status: &str) {
+        let known_schemas = HashSet::from(["ApiError".to_string()]);
+        let return_type = parse_return_type_str(return_type_str);
+        let responses = parse_return_type(&return_type, &known_schemas, &HashMap::new());
+        let content = responses[status].content.as_ref().unwrap();
+        assert_eq!(
+            content.keys().collect::<Vec<_>>(),
+            vec![EVENT_STREAM_MEDIA_TYPE]
+        );
+        let Some(SchemaRef::Inline(schema)) = &content[EVENT_STREAM_MEDIA_TYPE].schema else {
+            panic!("expected inline schema for {return_type_str}");
+        };
+        assert_eq!(schema.schema_type, Some(SchemaType::String));
+    }
+
+    #[rstest]
+    #[case("-> (StatusCode, Json<User>)", true)]
+    #[case("-> Result<(StatusCode, Json<User>), ApiError>", true)]
+    #[case(
+        "-> Result<(axum::http::StatusCode, HeaderMap, Json<User>), ApiError>",
+        true
+    )]
+    #[case("-> Json<User>", false)]
+    #[case("-> Result<Json<User>, (StatusCode, Json<ApiError>)>", false)]
+    #[case("", false)]
+    fn test_returns_status_tuple(#[case] return_type: &str, #[case] expected: bool) {
+        assert_eq!(
+            returns_status_tuple(&parse_return_type_str(return_type)),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case("{ (StatusCode::CREATED, Json(user)) }", Some(201))]
+    #[case("{ Ok((axum::http::StatusCode::ACCEPTED, Json(job))) }", Some(202))]
+    #[case("{ return (StatusCode::CREATED, Json(user)); }", Some(201))]
+    #[case(
+        "{ if missing { return Err((StatusCode::NOT_FOUND, Json(e))); } Ok((StatusCode::CREATED, Json(user))) }",
+        Some(201)
+    )]
+    #[case(
+        "{ if created { Ok((StatusCode::CREATED, Json(u))) } else { Ok((StatusCode::OK, Json(u))) } }",
+        None
+    )]
+    #[case("{ respond(StatusCode::CREATED, Json(user)) }", None)]
+    #[case("{ (StatusCode::CREATED) }", None)]
+    #[case("{ Json(user) }", None)]
+    fn test_infer_success_status(#[case] body: &str, #[case] expected: Option<u16>) {
+        let body: syn::Block = syn::parse_str(body).unwrap();
+        assert_eq!(infer_success_status(&body), expected);
+    }
+
+    #[rstest]
+    #[case(201, &["201", "400"])]
+    #[case(200, &["200", "400"])]
+    #[case(400, &["200", "400"])]
+    fn test_set_success_status(#[case] status: u16, #[case] expected: &[&str]) {
+        let return_type = parse_return_type_str("-> Result<(StatusCode, Json<String>), String>");
+        let mut responses = parse_return_type(&return_type, &HashSet::new(), &HashMap::new());
+        set_success_status(&mut responses, status);
+        assert_eq!(responses.keys().collect::<Vec<_>>(), expected);
+    }
 }
@@ -13,18 +13,29 @@
 //! 4. **Untagged** (`#[serde(untagged)]`): `{...fields...}` (no tag)
 //!
 //! Each representation maps to a different `OpenAPI` schema pattern using `oneOf` and optionally `discriminator`.
+//!
+//! Enums serialized as integers (serde_repr's `Serialize_repr`, `#[serde(into = "i32")]`)
+//! are integer schemas listing their discriminants.
+//!
+//! Catch-alls are not over-constrained: a `#[serde(other)]` variant accepts any tag (unit enums
+//! list their values as `examples` instead of `enum`), and a `#[serde(untagged)]` variant is
+//! rendered without its tag (`serde_json::Value` becomes a free-form schema).
 
-use std::collections::{BTreeMap, HashMap, HashSet};
+use std::collections::{HashMap, HashSet};
 
 use syn::Type;
-use vespera_core::schema::{Discriminator, Schema, SchemaRef, SchemaType};
+use vespera_core::schema::{Discriminator, Properties, Schema, SchemaRef, SchemaType};
 
 use super::{
     serde_attrs::{
         SerdeEnumRepr, extract_doc_comment, extract_enum_repr, extract_field_rename,
-        extract_rename_all, rename_field, strip_raw_prefix_owned,
+        extract_flatten, extract_integer_repr, extract_other, extract_rename_all, extract_untagged,
+        rename_field, strip_raw_prefix_owned,
+    },
+    struct_schema::{
+        apply_field_constraints, apply_field_content, field_is_required, flattened_properties,
     },
-    type_schema::parse_type_to_schema_ref,
+    type_schema::{integer_schema, parse_type_to_schema_ref},
 };
 
 /// Parses a Rust enum into an `OpenAPI` Schema.
@@ -47,17 +58,22 @@ pub fn parse_enum_to_schema(
     // Extract enum-level doc comment for schema description
     let enum_description = extract_doc_comment(&enum_item.attrs);
 
+    // Enums serialized as integers ignore names and tags altogether
+    if let Some(integer) = extract_integer_repr(&enum_item.attrs) {
+        return parse_integer_enum_to_schema(enum_item, enum_description, &integer);
+    }
+
     // Extract rename_all attribute from enum
     let rename_all = extract_rename_all(&enum_item.attrs);
 
     // Detect the serde enum representation
     let repr = extract_enum_repr(&enum_item.attrs);
 
-    // Check if all variants are unit variants
+    // Check if all variants are unit variants (a `#[serde(untagged)]` unit variant is `null`)
     let all_unit = enum_item
         .variants
         .iter()
-        .all(|v| matches!(v.fields, syn::Fields::Unit));
+        .all(|v| matches!(v.fields, syn::Fields::Unit) && !extract_untagged(&v.attrs));
 
     // For simple enums (all unit variants) with externally tagged representation (default),
     // they serialize to just the variant name as a string.
@@ -102,12 +118,16 @@ pub fn parse_enum_to_schema(
 }
 
 /// Parse a simple enum (all unit variants) to a string schema with enum values.
+///
+/// With a `#[serde(other)]` catch-all any string deserializes, so the values are
+/// listed as `examples` instead of constraining the schema with `enum`.
 fn parse_unit_enum_to_schema(
     enum_item: &syn::ItemEnum,
     description: Option<String>,
     rename_all: Option<&str>,
 ) -> Schema {
     let mut enum_values = Vec::with_capacity(enum_item.variants.len());
+    let has_other = enum_item.variants.iter().any(|v| extract_other(&v.attrs));
 
     for variant in &enum_item.variants {
         let variant_name = strip_raw_prefix_owned(variant.ident.to_string());
@@ -119,16 +139,135 @@ fn parse_unit_enum_to_schema(
         enum_values.push(serde_json::Value::String(enum_value));
     }
 
+    let values = if enum_values.is_empty() {
+        None
+    } else {
+        Some(enum_values)
+    };
+    let (r#enum, examples) = if has_other {
+        (None, values)
+    } else {
+        (values, None)
+    };
+
     Schema {
         schema_type: Some(SchemaType::String),
         description,
-        r#enum: if enum_values.is_empty() {
-            None
-        } else {
-            Some(enum_values)
-        },
+        r#enum,
+        examples,
+        ..Schema::string()
+    }
+}
+
+/// Parse an enum serialized as the integer type `integer` to an integer schema listing
+/// the discriminants of its variants.
+///
+/// Discriminants follow Rust's rules: an explicit `= N`, otherwise one more than the
+/// previous variant's (starting at 0). When they cannot all be evaluated (a variant with
+/// fields, or a discriminant that is not a literal) the schema is a plain integer, and a
+/// `#[serde(other)]` catch-all lists them as `examples` as with string enums.
+fn parse_integer_enum_to_schema(
+    enum_item: &syn::ItemEnum,
+    description: Option<String>,
+    integer: &str,
+) -> Schema {
+    let has_other = enum_item.variants.iter().any(|v| extract_other(&v.attrs));
+    let values = discriminant_values(enum_item).filter(|values| !values.is_empty());
+    let (r#enum, examples) = if has_other {
+        (None, values)
+    } else {
+        (values, None)
+    };
+
+    Schema {
+        description,
+        format: integer_schema(integer).and_then(|schema| schema.format),
+        r#enum,
+        examples,
+        ..Schema::integer()
+    }
+}
+
+/// Discriminant of every variant of a fieldless enum, `None` if one cannot be evaluated.
+fn discriminant_values(enum_item: &syn::ItemEnum) -> Option<Vec<serde_json::Value>> {
+    let mut next: i128 = 0;
+    let mut values = Vec::with_capacity(enum_item.variants.len());
+    for variant in &enum_item.variants {
+        if !matches!(variant.fields, syn::Fields::Unit) {
+            return None;
+        }
+        if let Some((_, expr)) = &variant.discriminant {
+            next = literal_integer(expr)?;
+        }
+        let value = i64::try_from(next)
+            .map(serde_json::Value::from)
+            .or_else(|_| u64::try_from(next).map(serde_json::Value::from))
+            .ok()?;
+        values.push(value);
+        next = next.checked_add(1)?;
+    }
+    Some(values)
+}
+
+/// Value of an integer literal discriminant (`3`, `-1`, `0x10`, `(4)`).
+fn literal_integer(expr: &syn::Expr) -> Option<i128> {
+    match expr {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Int(int),
+            ..
+        }) => int.base10_parse().ok(),
+        syn::Expr::Unary(syn::ExprUnary {
+            op: syn::UnOp::Neg(_),
+            expr,
+            ..
+        }) => literal_integer(expr).map(|value| -value),
+        syn::Expr::Paren(paren) => literal_integer(&paren.expr),
+        syn::Expr::Group(group) => literal_integer(&group.expr),
+        _ => None,
+    }
+}
+
+/// Schema of the tag value for `variant_key`.
+///
+/// The `#[serde(other)]` variant accepts any tag, so its tag is an unconstrained string.
+fn tag_value_schema(variant: &syn::Variant, variant_key: String) -> SchemaRef {
+    if extract_other(&variant.attrs) {
+        return SchemaRef::Inline(Box::new(Schema::string()));
+    }
+    SchemaRef::Inline(Box::new(Schema {
+        r#enum: Some(vec![serde_json::Value::String(variant_key)]),
         ..Schema::string()
+    }))
+}
+
+/// Schema of an adjacently tagged variant's tag: `variant_key` as a `const`.
+///
+/// As with [`tag_value_schema`], a `#[serde(other)]` variant accepts any tag.
+fn tag_const_schema(variant: &syn::Variant, variant_key: String) -> SchemaRef {
+    if extract_other(&variant.attrs) {
+        return SchemaRef::Inline(Box::new(Schema::string()));
     }
+    SchemaRef::Inline(Box::new(Schema {
+        r#const: Some(serde_json::Value::String(variant_key)),
+        ..Schema::string()
+    }))
+}
+
+/// Whether `ty` is `serde_json::Value` (or a bare `Value` that is not a known schema).
+fn is_json_value(ty: &Type, known_schemas: &HashSet<String>) -> bool {
+    let Type::Path(type_path) = ty else {
+        return false;
+    };
+    let Some(last) = type_path.path.segments.last() else {
+        return false;
+    };
+    last.ident == "Value"
+        && ((type_path.path.segments.len() == 1 && !known_schemas.contains("Value"))
+            || type_path
+                .path
+                .segments
+                .iter()
+                .any(|segment| segment.ident == "serde_json"))
 }
 
 /// Get the variant key (name after rename transformations)
@@ -145,12 +284,25 @@ fn build_struct_variant_properties(
     variant_attrs: &[syn::Attribute],
     known_schemas: &HashSet<String>,
     struct_definitions: &HashMap<String, String>,
-) -> (BTreeMap<String, SchemaRef>, Vec<String>) {
-    let mut variant_properties = BTreeMap::new();
+) -> (Properties, Vec<String>) {
+    let mut variant_properties = Properties::new();
     let mut variant_required = Vec::with_capacity(fields_named.named.len());
     let variant_rename_all = extract_rename_all(variant_attrs);
 
     for field in &fields_named.named {
+        // `#[serde(flatten)]` writes the field's own fields into the variant object
+        if extract_flatten(&field.attrs)
+            && let Some((flattened, flattened_required)) = flattened_properties(
+                &parse_type_to_schema_ref(&field.ty, known_schemas, struct_definitions),
+                known_schemas,
+                struct_definitions,
+            )
+        {
+            variant_properties.extend(flattened);
+            variant_required.extend(flattened_required);
+            continue;
+        }
+
         let rust_field_name = field.ident.as_ref().map_or_else(
             || "unknown".to_string(),
             |i| strip_raw_prefix_owned(i.to_string()),
@@ -190,22 +342,13 @@ fn build_struct_variant_properties(
             }
         }
 
-        variant_properties.insert(field_name.clone(), schema_ref);
-
-        // Check if field is Option<T>
-        let is_optional = matches!(
-            field_type,
-            Type::Path(type_path)
-                if type_path
-                    .path
-                    .segments
-                    .first()
-                    .is_some_and(|s| s.ident == "Option")
-        );
+        apply_field_content(&mut schema_ref, &field.attrs);
+        apply_field_constraints(&mut schema_ref, &field.attrs);
 
-        if !is_optional {
-            variant_required.push(field_name);
+        if field_is_required(field, &mut schema_ref) {
+            variant_required.push(field_name.clone());
         }
+        variant_properties.insert(field_name, schema_ref);
     }
 
     (variant_properties, variant_required)
@@ -286,6 +429,15 @@ fn parse_externally_tagged_enum(
     let mut one_of_schemas = Vec::with_capacity(enum_item.variants.len());
 
     for variant in &enum_item.variants {
+        if extract_untagged(&variant.attrs) {
+            one_of_schemas.push(untagged_variant_schema(
+                variant,
+                rename_all,
+                known_schemas,
+                struct_definitions,
+            ));
+            continue;
+        }
         let variant_key = get_variant_key(variant, rename_all);
         let variant_description = extract_doc_comment(&variant.attrs);
 
@@ -321,7 +473,7 @@ fn parse_externally_tagged_enum(
                     }))
                 };
 
-                let mut properties = BTreeMap::new();
+                let mut properties = Properties::new();
                 properties.insert(variant_key.clone(), data_schema);
 
                 Schema {
@@ -355,7 +507,7 @@ fn parse_externally_tagged_enum(
                     ..Schema::object()
                 };
 
-                let mut properties = BTreeMap::new();
+                let mut properties = Properties::new();
                 properties.insert(
                     variant_key.clone(),
                     SchemaRef::Inline(Box::new(inner_struct_schema)),
@@ -387,7 +539,8 @@ fn parse_externally_tagged_enum(
 
 /// Parse internally tagged enum: `{"tag": "VariantName", ...fields...}`
 /// Uses `OpenAPI` discriminator for the tag field.
-/// Note: serde only allows struct and unit variants for internally tagged enums.
+/// Note: serde only allows struct and unit variants, and newtype variants of a struct or
+/// map (the tag is written into its object), for internally tagged enums.
 fn parse_internally_tagged_enum(
     enum_item: &syn::ItemEnum,
     description: Option<String>,
@@ -401,20 +554,23 @@ fn parse_internally_tagged_enum(
     let tag_string = tag.to_string();
 
     for variant in &enum_item.variants {
+        if extract_untagged(&variant.attrs) {
+            one_of_schemas.push(untagged_variant_schema(
+                variant,
+                rename_all,
+                known_schemas,
+                struct_definitions,
+            ));
+            continue;
+        }
         let variant_key = get_variant_key(variant, rename_all);
         let variant_description = extract_doc_comment(&variant.attrs);
 
         let variant_schema = match &variant.fields {
             syn::Fields::Unit => {
                 // Unit variant: {"tag": "VariantName"}
-                let mut properties = BTreeMap::new();
-                properties.insert(
-                    tag_string.clone(),
-                    SchemaRef::Inline(Box::new(Schema {
-                        r#enum: Some(vec![serde_json::Value::String(variant_key.clone())]),
-                        ..Schema::string()
-                    })),
-                );
+                let mut properties = Properties::new();
+                properties.insert(tag_string.clone(), tag_value_schema(variant, variant_key));
 
                 Schema {
                     description: variant_description,
@@ -433,8 +589,9 @@ fn parse_internally_tagged_enum(
                     struct_definitions,
                 );
 
-                // Add the tag field
-                properties.insert(
+                // Add the tag field first, as serde writes it
+                properties.shift_insert(
+                    0,
                     tag_string.clone(),
                     SchemaRef::Inline(Box::new(Schema {
                         r#enum: Some(vec![serde_json::Value::String(variant_key.clone())]),
@@ -450,9 +607,38 @@ fn parse_internally_tagged_enum(
                     ..Schema::object()
                 }
             }
+            syn::Fields::Unnamed(fields_unnamed) if fields_unnamed.unnamed.len() == 1 => {
+                // Newtype variant: {"tag": "VariantName", ...inner object fields...}
+                let inner = parse_type_to_schema_ref(
+                    &fields_unnamed.unnamed[0].ty,
+                    known_schemas,
+                    struct_definitions,
+                );
+                // serde rejects a newtype of anything but an object at runtime
+                if let SchemaRef::Inline(schema) = &inner
+                    && schema.schema_type != Some(SchemaType::Object)
+                {
+                    continue;
+                }
+
+                let mut properties = Properties::new();
+                properties.insert(tag_string.clone(), tag_value_schema(variant, variant_key));
+
+                Schema {
+                    description: variant_description,
+                    all_of: Some(vec![
+                        inner,
+                        SchemaRef::Inline(Box::new(Schema {
+                            properties: Some(properties),
+                            required: Some(vec![tag_string.clone()]),
+                            ..Schema::object()
+                        })),
+                    ]),
+                    ..Default::default()
+                }
+            }
             syn::Fields::Unnamed(_) => {
-                // Tuple/newtype variants are not supported with internally tagged enums in serde
-                // Generate a warning schema or skip
+                // Tuple variants are not supported with internally tagged enums in serde
                 continue;
             }
         };
@@ -477,7 +663,8 @@ fn parse_internally_tagged_enum(
 }
 
 /// Parse adjacently tagged enum: `{"tag": "VariantName", "content": {...}}`
-/// Uses `OpenAPI` discriminator for the tag field.
+/// Each variant is an object with a `const` tag and, unless it is a unit variant, the
+/// payload under the content key. Uses `OpenAPI` discriminator for the tag field.
 fn parse_adjacently_tagged_enum(
     enum_item: &syn::ItemEnum,
     description: Option<String>,
@@ -493,20 +680,23 @@ fn parse_adjacently_tagged_enum(
     let content_string = content.to_string();
 
     for variant in &enum_item.variants {
+        if extract_untagged(&variant.attrs) {
+            one_of_schemas.push(untagged_variant_schema(
+                variant,
+                rename_all,
+                known_schemas,
+                struct_definitions,
+            ));
+            continue;
+        }
         let variant_key = get_variant_key(variant, rename_all);
         let variant_description = extract_doc_comment(&variant.attrs);
 
-        let mut properties = BTreeMap::new();
+        let mut properties = Properties::new();
         let mut required = vec![tag_string.clone()];
 
         // Add the tag field
-        properties.insert(
-            tag_string.clone(),
-            SchemaRef::Inline(Box::new(Schema {
-                r#enum: Some(vec![serde_json::Value::String(variant_key.clone())]),
-                ..Schema::string()
-            })),
-        );
+        properties.insert(tag_string.clone(), tag_const_schema(variant, variant_key));
 
         // Add the content field if variant has data
         if let Some(data_schema) =
@@ -554,93 +744,122 @@ fn parse_untagged_enum(
     let mut one_of_schemas = Vec::with_capacity(enum_item.variants.len());
 
     for variant in &enum_item.variants {
-        let variant_description = extract_doc_comment(&variant.attrs);
+        one_of_schemas.push(untagged_variant_schema(
+            variant,
+            rename_all,
+            known_schemas,
+            struct_definitions,
+        ));
+    }
 
-        let variant_schema = match &variant.fields {
-            syn::Fields::Unit => {
-                // Unit variant in untagged enum: null
-                Schema {
-                    description: variant_description,
-                    schema_type: Some(SchemaType::Null),
-                    ..Default::default()
-                }
+    Schema {
+        schema_type: None,
+        description,
+        one_of: if one_of_schemas.is_empty() {
+            None
+        } else {
+            Some(one_of_schemas)
+        },
+        ..Default::default()
+    }
+}
+
+/// Schema of a variant's data without any tag.
+///
+/// Used for `#[serde(untagged)]` enums and for `#[serde(untagged)]` variants of tagged
+/// enums. A `serde_json::Value` catch-all maps to a free-form schema, and a newtype
+/// variant of a registered type to a bare `$ref` (wrapped in `allOf` only to carry
+/// the variant's doc comment).
+fn untagged_variant_schema(
+    variant: &syn::Variant,
+    rename_all: Option<&str>,
+    known_schemas: &HashSet<String>,
+    struct_definitions: &HashMap<String, String>,
+) -> SchemaRef {
+    let variant_description = extract_doc_comment(&variant.attrs);
+
+    if let syn::Fields::Unnamed(fields_unnamed) = &variant.fields
+        && fields_unnamed.unnamed.len() == 1
+        && is_json_value(&fields_unnamed.unnamed[0].ty, known_schemas)
+    {
+        return SchemaRef::Inline(Box::new(Schema {
+            description: variant_description,
+            ..Default::default()
+        }));
+    }
+
+    let schema = match &variant.fields {
+        syn::Fields::Unit => {
+            // Unit variant in untagged enum: null
+            Schema {
+                description: variant_description,
+                schema_type: Some(SchemaType::Null),
+                ..Default::default()
             }
-            syn::Fields::Unnamed(fields_unnamed) => {
-                if fields_unnamed.unnamed.len() == 1 {
-                    // Single field tuple variant - just the inner type
-                    let inner_type = &fields_unnamed.unnamed[0].ty;
-                    let mut schema = match parse_type_to_schema_ref(
-                        inner_type,
-                        known_schemas,
-                        struct_definitions,
-                    ) {
+        }
+        syn::Fields::Unnamed(fields_unnamed) => {
+            if fields_unnamed.unnamed.len() == 1 {
+                // Single field tuple variant - just the inner type
+                let inner_type = &fields_unnamed.unnamed[0].ty;
+                let mut schema =
+                    match parse_type_to_schema_ref(inner_type, known_schemas, struct_definitions) {
+                        SchemaRef::Ref(r) if variant_description.is_none() => {
+                            return SchemaRef::Ref(r);
+                        }
                         SchemaRef::Inline(s) => *s,
                         SchemaRef::Ref(r) => Schema {
                             all_of: Some(vec![SchemaRef::Ref(r)]),
                             ..Default::default()
                         },
                     };
-                    schema.description = variant_description.or(schema.description);
-                    schema
-                } else {
-                    // Multiple fields - array with prefixItems
-                    let mut tuple_item_schemas = Vec::with_capacity(fields_unnamed.unnamed.len());
-                    for field in &fields_unnamed.unnamed {
-                        let field_schema =
-                            parse_type_to_schema_ref(&field.ty, known_schemas, struct_definitions);
-                        tuple_item_schemas.push(field_schema);
-                    }
-                    let tuple_len = tuple_item_schemas.len();
-                    Schema {
-                        description: variant_description,
-                        prefix_items: Some(tuple_item_schemas),
-                        min_items: Some(tuple_len),
-                        max_items: Some(tuple_len),
-                        items: None,
-                        ..Schema::new(SchemaType::Array)
-                    }
+                schema.description = variant_description.or(schema.description);
+                schema
+            } else {
+                // Multiple fields - array with prefixItems
+                let mut tuple_item_schemas = Vec::with_capacity(fields_unnamed.unnamed.len());
+                for field in &fields_unnamed.unnamed {
+                    let field_schema =
+                        parse_type_to_schema_ref(&field.ty, known_schemas, struct_definitions);
+                    tuple_item_schemas.push(field_schema);
                 }
-            }
-            syn::Fields::Named(fields_named) => {
-                // Struct variant - just the object with fields
-                let (properties, required) = build_struct_variant_properties(
-                    fields_named,
-                    rename_all,
-                    &variant.attrs,
-                    known_schemas,
-                    struct_definitions,
-                );
-
+                let tuple_len = tuple_item_schemas.len();
                 Schema {
                     description: variant_description,
-                    properties: if properties.is_empty() {
-                        None
-                    } else {
-                        Some(properties)
-                    },
-                    required: if required.is_empty() {
-                        None
-                    } else {
-                        Some(required)
-                    },
-                    ..Schema::object()
+                    prefix_items: Some(tuple_item_schemas),
+                    min_items: Some(tuple_len),
+                    max_items: Some(tuple_len),
+                    items: None,
+                    ..Schema::new(SchemaType::Array)
                 }
             }
-        };
-
-        one_of_schemas.push(SchemaRef::Inline(Box::new(variant_schema)));
-    }
+        }
+        syn::Fields::Named(fields_named) => {
+            // Struct variant - just the object with fields
+            let (properties, required) = build_struct_variant_properties(
+                fields_named,
+                rename_all,
+                &variant.attrs,
+                known_schemas,
+                struct_definitions,
+            );
 
-    Schema {
-        schema_type: None,
-        description,
-        one_of: if one_of_schemas.is_empty() {
-            None
-        } else {
-            Some(one_of_schemas)
-        },
-        ..Default::default()
-    }
+            Schema {
+                description: variant_description,
+                properties: if properties.is_empty() {
+                    None
+                } else {
+                    Some(properties)
+                },
+                required: if required.is_empty() {
+                    None
+                } else {
+                    Some(required)
+                },
+                ..Schema::object()
+            }
+        }
+    };
+    SchemaRef::Inline(Box::new(schema))
 }
 
 #[cfg(test)]
@@ -1260,6 +1479,50 @@ mod tests {
             }
         }
 
+        #[test]
+        fn test_internally_tagged_enum_flattened_fields() {
+            let enum_item: syn::ItemEnum = syn::parse_str(
+                r#"
+                #[serde(tag = "kind")]
+                enum Event {
+                    Created {
+                        id: i32,
+                        #[serde(flatten)]
+                        audit: Audit,
+                    },
+                }
+                "#,
+            )
+            .unwrap();
+            let struct_defs = HashMap::from([
+                (
+                    "Audit".to_string(),
+                    "struct Audit { actor: String, #[serde(flatten)] source: Source }".to_string(),
+                ),
+                (
+                    "Source".to_string(),
+                    "struct Source { ip: String, agent: Option<String> }".to_string(),
+                ),
+            ]);
+            let known = HashSet::from(["Audit".to_string(), "Source".to_string()]);
+
+            let schema = parse_enum_to_schema(&enum_item, &known, &struct_defs);
+
+            let one_of = schema.one_of.expect("one_of missing");
+            let SchemaRef::Inline(created) = &one_of[0] else {
+                panic!("Expected inline schema");
+            };
+            let props = created.properties.as_ref().expect("properties missing");
+            assert_eq!(
+                props.keys().collect::<Vec<_>>(),
+                ["kind", "id", "actor", "ip", "agent"]
+            );
+            assert_eq!(
+                created.required,
+                Some(["kind", "id", "actor", "ip"].map(String::from).to_vec())
+            );
+        }
+
         #[test]
         fn test_internally_tagged_enum_with_rename_all() {
             let enum_item: syn::ItemEnum = syn::parse_str(
@@ -1403,6 +1666,41 @@ mod tests {
             }
         }
 
+        #[test]
+        fn test_adjacently_tagged_enum_const_tag() {
+            let enum_item: syn::ItemEnum = syn::parse_str(
+                r#"
+                #[serde(tag = "t", content = "c", rename_all = "snake_case")]
+                enum Shape {
+                    Circle(f64),
+                    Empty,
+                }
+                "#,
+            )
+            .unwrap();
+
+            let schema = parse_enum_to_schema(&enum_item, &HashSet::new(), &HashMap::new());
+
+            assert_eq!(
+                serde_json::to_value(&schema.one_of).unwrap(),
+                serde_json::json!([
+                    {
+                        "type": "object",
+                        "properties": {
+                            "t": { "type": "string", "const": "circle" },
+                            "c": { "type": "number", "format": "double" }
+                        },
+                        "required": ["t", "c"]
+                    },
+                    {
+                        "type": "object",
+                        "properties": { "t": { "type": "string", "const": "empty" } },
+                        "required": ["t"]
+                    }
+                ])
+            );
+        }
+
         // Untagged enum tests
         #[test]
         fn test_untagged_enum_basic() {
@@ -1595,6 +1893,70 @@ mod tests {
             });
         }
 
+        #[test]
+        fn test_struct_variant_field_format() {
+            let enum_item: syn::ItemEnum = syn::parse_str(
+                r#"
+                #[serde(tag = "kind")]
+                enum Contact {
+                    Email {
+                        #[schema(format = "email")]
+                        address: String,
+                    },
+                    Web {
+                        #[schema(format = "uri")]
+                        url: Option<String>,
+                    },
+                }
+                "#,
+            )
+            .unwrap();
+
+            let schema = parse_enum_to_schema(&enum_item, &HashSet::new(), &HashMap::new());
+
+            let value = serde_json::to_value(schema.one_of.expect("one_of missing")).unwrap();
+            assert_eq!(value[0]["properties"]["address"]["format"], "email");
+            assert_eq!(value[1]["properties"]["url"]["format"], "uri");
+        }
+
+        #[test]
+        fn test_internally_tagged_enum_newtype_struct_variant() {
+            let enum_item: syn::ItemEnum = syn::parse_str(
+                r#"
+                #[serde(tag = "commentableType")]
+                enum Commentable {
+                    #[serde(rename = "post")]
+                    Post(Box<Post>),
+                    Photo(Photo),
+                }
+                "#,
+            )
+            .unwrap();
+            let known_schemas = HashSet::from(["Post".to_string(), "Photo".to_string()]);
+
+            let schema = parse_enum_to_schema(&enum_item, &known_schemas, &HashMap::new());
+
+            let one_of = schema.one_of.expect("one_of missing");
+            assert_eq!(one_of.len(), 2);
+            let value = serde_json::to_value(&one_of[0]).unwrap();
+            assert_eq!(
+                value["allOf"][0]["$ref"], "#/components/schemas/Post",
+                "{value}"
+            );
+            assert_eq!(
+                value["allOf"][1]["properties"]["commentableType"]["enum"],
+                serde_json::json!(["post"])
+            );
+            assert_eq!(
+                value["allOf"][1]["required"],
+                serde_json::json!(["commentableType"])
+            );
+            assert_eq!(
+                schema.discriminator.unwrap().property_name,
+                "commentableType"
+            );
+        }
+
         // Edge case: Internally tagged enum with tuple variant
         #[test]
         fn test_internally_tagged_skips_tuple_variant() {
@@ -1636,6 +1998,8 @@ mod tests {
                 #[serde(untagged)]
                 enum Payload {
                     User(UserData),
+                    /// An admin
+                    Admin(UserData),
                     Simple(String),
                 }
                 ",
@@ -1651,27 +2015,27 @@ mod tests {
             assert!(schema.discriminator.is_none());
 
             let one_of = schema.one_of.expect("one_of missing");
-            assert_eq!(one_of.len(), 2);
+            assert_eq!(one_of.len(), 3);
 
-            // First variant (UserData) should have all_of with a $ref since it's a known schema
-            if let SchemaRef::Inline(user_variant) = &one_of[0] {
-                // The schema should have all_of containing the reference
-                let all_of = user_variant
-                    .all_of
-                    .as_ref()
-                    .expect("all_of missing for known schema ref");
-                assert_eq!(all_of.len(), 1);
-                if let SchemaRef::Ref(reference) = &all_of[0] {
-                    assert!(reference.ref_path.contains("UserData"));
-                } else {
-                    panic!("Expected SchemaRef::Ref inside all_of");
-                }
-            } else {
+            // First variant (UserData) is a bare $ref, with no wrapper schema
+            let SchemaRef::Ref(reference) = &one_of[0] else {
+                panic!("Expected SchemaRef::Ref");
+            };
+            assert_eq!(reference.ref_path, "#/components/schemas/UserData");
+
+            // A documented variant wraps the $ref in all_of to carry its description
+            let SchemaRef::Inline(admin_variant) = &one_of[1] else {
                 panic!("Expected inline schema");
-            }
+            };
+            assert_eq!(admin_variant.description.as_deref(), Some("An admin"));
+            let all_of = admin_variant
+                .all_of
+                .as_ref()
+                .expect("all_of missing for documented known schema ref");
+            assert!(matches!(&all_of[..], [SchemaRef::Ref(r)] if r.ref_path.contains("UserData")));
 
-            // Second variant (String) should be inline string schema directly
-            if let SchemaRef::Inline(simple_variant) = &one_of[1] {
+            // Last variant (String) should be inline string schema directly
+            if let SchemaRef::Inline(simple_variant) = &one_of[2] {
                 assert_eq!(simple_variant.schema_type, Some(SchemaType::String));
             } else {
                 panic!("Expected inline schema");
@@ -1733,5 +2097,165 @@ mod tests {
                 assert_debug_snapshot!(schema);
             });
         }
+
+        #[test]
+        fn test_unit_enum_with_other_drops_enum_keyword() {
+            let enum_item: syn::ItemEnum = syn::parse_str(
+                r#"
+                #[serde(rename_all = "lowercase")]
+                enum Plan {
+                    Free,
+                    Pro,
+                    #[serde(other)]
+                    Unknown,
+                }
+                "#,
+            )
+            .unwrap();
+
+            let schema = parse_enum_to_schema(&enum_item, &HashSet::new(), &HashMap::new());
+
+            assert_eq!(schema.schema_type, Some(SchemaType::String));
+            assert!(schema.r#enum.is_none());
+            assert_eq!(
+                schema.examples,
+                Some(vec![
+                    serde_json::json!("free"),
+                    serde_json::json!("pro"),
+                    serde_json::json!("unknown"),
+                ])
+            );
+        }
+
+        #[rstest]
+        #[case(r#"#[serde(tag = "type")]"#)]
+        #[case(r#"#[serde(tag = "type", content = "data")]"#)]
+        fn test_tagged_enum_other_variant_accepts_any_tag(#[case] repr: &str) {
+            let enum_item: syn::ItemEnum = syn::parse_str(&format!(
+                r"
+                {repr}
+                enum Event {{
+                    Created {{ id: i32 }},
+                    #[serde(other)]
+                    Unknown,
+                }}
+                "
+            ))
+            .unwrap();
+
+            let schema = parse_enum_to_schema(&enum_item, &HashSet::new(), &HashMap::new());
+            let one_of = schema.one_of.expect("one_of missing");
+            let tag_of = |variant: &SchemaRef| {
+                let SchemaRef::Inline(variant) = variant else {
+                    panic!("expected inline variant");
+                };
+                let SchemaRef::Inline(tag) = &variant.properties.as_ref().unwrap()["type"] else {
+                    panic!("expected inline tag");
+                };
+                // Adjacently tagged enums describe a single tag with `const`
+                tag.r#enum
+                    .clone()
+                    .or_else(|| tag.r#const.clone().map(|tag| vec![tag]))
+            };
+            assert_eq!(tag_of(&one_of[0]), Some(vec![serde_json::json!("Created")]));
+            assert_eq!(tag_of(&one_of[1]), None);
+        }
+
+        #[rstest]
+        #[case("")]
+        #[case(r#"#[serde(tag = "type")]"#)]
+        #[case(r#"#[serde(tag = "type", content = "data")]"#)]
+        fn test_untagged_catch_all_variant_is_free_form(#[case] repr: &str) {
+            let enum_item: syn::ItemEnum = syn::parse_str(&format!(
+                r"
+                {repr}
+                enum Message {{
+                    Ping {{ seq: i32 }},
+                    /// Anything else, passed through untouched
+                    #[serde(untagged)]
+                    Other(serde_json::Value),
+                }}
+                "
+            ))
+            .unwrap();
+
+            let schema = parse_enum_to_schema(&enum_item, &HashSet::new(), &HashMap::new());
+            let one_of = schema.one_of.expect("one_of missing");
+            assert_eq!(one_of.len(), 2);
+            let SchemaRef::Inline(other) = &one_of[1] else {
+                panic!("expected inline catch-all");
+            };
+            assert_eq!(
+                serde_json::to_value(other).unwrap(),
+                serde_json::json!({ "description": "Anything else, passed through untouched" })
+            );
+        }
+
+        #[test]
+        fn test_untagged_unit_variant_in_unit_enum() {
+            let enum_item: syn::ItemEnum = syn::parse_str(
+                r"
+                enum Level {
+                    Low,
+                    High,
+                    #[serde(untagged)]
+                    Unset,
+                }
+                ",
+            )
+            .unwrap();
+
+            let schema = parse_enum_to_schema(&enum_item, &HashSet::new(), &HashMap::new());
+            let one_of = schema.one_of.expect("one_of missing");
+            assert_eq!(one_of.len(), 3);
+            let SchemaRef::Inline(unset) = &one_of[2] else {
+                panic!("expected inline variant");
+            };
+            assert_eq!(unset.schema_type, Some(SchemaType::Null));
+        }
+
+        #[rstest]
+        #[case(
+            "#[derive(Serialize_repr)] #[repr(u8)] enum Level { Low = 1, Mid, High = 10 }",
+            serde_json::json!({ "type": "integer", "format": "uint8", "enum": [1, 2, 10] })
+        )]
+        #[case(
+            "#[derive(serde_repr::Deserialize_repr)] #[repr(C, i16)] enum Delta { Down = -1, Zero, Up }",
+            serde_json::json!({ "type": "integer", "format": "int16", "enum": [-1, 0, 1] })
+        )]
+        #[case(
+            "#[derive(Serialize_repr)] enum Step { First, Second }",
+            serde_json::json!({ "type": "integer", "enum": [0, 1] })
+        )]
+        #[case(
+            r#"#[serde(into = "i32", rename_all = "lowercase")] enum Code { Ok = 0x10, Err }"#,
+            serde_json::json!({ "type": "integer", "format": "int32", "enum": [16, 17] })
+        )]
+        #[case(
+            "#[derive(Serialize_repr)] #[repr(u8)] enum Plan { Free, Pro, #[serde(other)] Unknown }",
+            serde_json::json!({ "type": "integer", "format": "uint8", "examples": [0, 1, 2] })
+        )]
+        #[case(
+            "#[derive(Serialize_repr)] #[repr(u32)] enum Flag { A = BASE, B }",
+            serde_json::json!({ "type": "integer", "format": "uint32" })
+        )]
+        #[case(
+            r#"#[serde(into = "i64")] enum Shape { Circle(f64), Square }"#,
+            serde_json::json!({ "type": "integer", "format": "int64" })
+        )]
+        fn test_integer_repr_enum(#[case] source: &str, #[case] expected: serde_json::Value) {
+            let enum_item: syn::ItemEnum = syn::parse_str(source).unwrap();
+            let schema = parse_enum_to_schema(&enum_item, &HashSet::new(), &HashMap::new());
+            assert_eq!(serde_json::to_value(schema).unwrap(), expected);
+        }
+
+        #[rstest]
+        #[case(r#"#[serde(into = "String")] enum Status { Active }"#)]
+        #[case("#[derive(Serialize)] #[repr(u8)] enum Status { Active = 1 }")]
+        fn test_non_integer_repr_enum_is_string(#[case] source: &str) {
+            let enum_item: syn::ItemEnum = syn::parse_str(source).unwrap();
+            let schema = parse_enum_to_schema(&enum_item, &HashSet::new(), &HashMap::new());
+            assert_eq!(schema.schema_type, Some(SchemaType::String));
+        }
     }
 }
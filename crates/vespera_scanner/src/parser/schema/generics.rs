@@ -1,9 +1,161 @@
 //! Generic type resolution and substitution for `OpenAPI` schema generation.
 //!
 //! This module handles the substitution of generic type parameters with concrete types
-//! when generating schemas for generic structs like `Wrapper<T>`.
+//! when generating schemas for generic structs like `Wrapper<T>` and generic enums like
+//! `ApiResult<T>`.
+
+use std::{
+    cell::{Cell, RefCell},
+    collections::BTreeMap,
+};
 
 use syn::Type;
+use vespera_core::schema::Schema;
+
+use super::serde_attrs::{capitalize_first, extract_schema_name_for};
+
+/// Component naming of generic instantiations (`vespera!(generic_names = "...")`).
+///
+/// Without it, instantiations such as `Page<User>` are inlined where they are used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GenericNaming {
+    /// `Page_User`, `Page_Vec_User`
+    Underscore,
+    /// `PageUser`, `PageVecUser`
+    Concat,
+}
+
+thread_local! {
+    static GENERIC_NAMING: Cell<Option<GenericNaming>> = const { Cell::new(None) };
+
+    /// Components registered for generic instantiations, by component name.
+    static INSTANTIATIONS: RefCell<BTreeMap<String, Schema>> = const { RefCell::new(BTreeMap::new()) };
+}
+
+/// Run `f` registering generic instantiations as components named by `naming`.
+pub fn with_generic_naming<R>(naming: Option<GenericNaming>, f: impl FnOnce() -> R) -> R {
+    let previous = GENERIC_NAMING.with(|active| active.replace(naming));
+    let result = f();
+    GENERIC_NAMING.with(|active| active.set(previous));
+    result
+}
+
+/// Naming of generic instantiations, `None` when they are inlined.
+pub(super) fn generic_naming() -> Option<GenericNaming> {
+    GENERIC_NAMING.with(Cell::get)
+}
+
+/// Register the component `name` built by `build` unless it is already registered.
+///
+/// The name is reserved before `build` runs, so a recursive instantiation refers to
+/// itself. Returns `false`, registering nothing, when `build` fails.
+pub(super) fn register_instantiation(name: &str, build: impl FnOnce() -> Option<Schema>) -> bool {
+    let registered = INSTANTIATIONS.with(|components| {
+        let mut components = components.borrow_mut();
+        if components.contains_key(name) {
+            return true;
+        }
+        components.insert(name.to_string(), Schema::default());
+        false
+    });
+    if registered {
+        return true;
+    }
+    let schema = build();
+    INSTANTIATIONS.with(|components| {
+        let mut components = components.borrow_mut();
+        if let Some(schema) = schema {
+            components.insert(name.to_string(), schema);
+            true
+        } else {
+            components.remove(name);
+            false
+        }
+    })
+}
+
+/// Take the components registered for generic instantiations so far.
+pub fn take_instantiations() -> BTreeMap<String, Schema> {
+    INSTANTIATIONS.with(|components| std::mem::take(&mut *components.borrow_mut()))
+}
+
+/// Component name of the generic type `base` instantiated with `args`.
+///
+/// A matching `#[schema(name_for(...))]` entry of the definition wins over `naming`.
+pub(super) fn instantiation_name(
+    base: &str,
+    attrs: &[syn::Attribute],
+    args: &[&Type],
+    naming: GenericNaming,
+) -> String {
+    let mut arg_parts = Vec::new();
+    for arg in args {
+        type_name_parts(arg, &mut arg_parts);
+    }
+    let overridden = extract_schema_name_for(attrs)
+        .unwrap_or_default()
+        .into_iter()
+        .find_map(|(key, name)| {
+            let mut key_parts = Vec::new();
+            match &key {
+                Type::Tuple(tuple) if args.len() > 1 => {
+                    tuple
+                        .elems
+                        .iter()
+                        .for_each(|elem| type_name_parts(elem, &mut key_parts));
+                }
+                _ => type_name_parts(&key, &mut key_parts),
+            }
+            (key_parts == arg_parts).then_some(name)
+        });
+    overridden.unwrap_or_else(|| {
+        let parts = std::iter::once(base.to_string()).chain(arg_parts);
+        match naming {
+            GenericNaming::Underscore => parts.collect::<Vec<_>>().join("_"),
+            GenericNaming::Concat => parts.map(|part| capitalize_first(&part)).collect(),
+        }
+    })
+}
+
+/// Name segments of `ty`: each path's last segment followed by its type arguments
+/// (`Vec<models::User>` → `["Vec", "User"]`).
+fn type_name_parts(ty: &Type, parts: &mut Vec<String>) {
+    match ty {
+        Type::Path(type_path) => {
+            let Some(segment) = type_path.path.segments.last() else {
+                return;
+            };
+            parts.push(segment.ident.to_string());
+            if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
+                for arg in &args.args {
+                    if let syn::GenericArgument::Type(ty) = arg {
+                        type_name_parts(ty, parts);
+                    }
+                }
+            }
+        }
+        Type::Reference(reference) => type_name_parts(&reference.elem, parts),
+        Type::Paren(paren) => type_name_parts(&paren.elem, parts),
+        Type::Group(group) => type_name_parts(&group.elem, parts),
+        Type::Array(array) => {
+            parts.push("Array".to_string());
+            type_name_parts(&array.elem, parts);
+        }
+        Type::Slice(slice) => {
+            parts.push("Array".to_string());
+            type_name_parts(&slice.elem, parts);
+        }
+        Type::Tuple(tuple) if tuple.elems.is_empty() => parts.push("Unit".to_string()),
+        Type::Tuple(tuple) => {
+            parts.push("Tuple".to_string());
+            tuple
+                .elems
+                .iter()
+                .for_each(|elem| type_name_parts(elem, parts));
+        }
+        _ => parts.push("Value".to_string()),
+    }
+}
 
 /// Substitutes generic type parameters with concrete types in a given type.
 ///
@@ -134,12 +286,77 @@ pub fn substitute_type(ty: &Type, generic_params: &[String], concrete_types: &[&
     }
 }
 
+/// Substitutes generic type parameters in every field of a struct or enum variant.
+pub fn substitute_fields(
+    fields: &mut syn::Fields,
+    generic_params: &[String],
+    concrete_types: &[&Type],
+) {
+    for field in fields.iter_mut() {
+        field.ty = substitute_type(&field.ty, generic_params, concrete_types);
+    }
+}
+
+/// Names of the type parameters declared by `generics`, in declaration order.
+pub fn type_param_names(generics: &syn::Generics) -> Vec<String> {
+    generics
+        .type_params()
+        .map(|type_param| type_param.ident.to_string())
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use rstest::rstest;
 
     use super::*;
 
+    #[rstest]
+    #[case("", &["User"], GenericNaming::Underscore, "Page_User")]
+    #[case("", &["User"], GenericNaming::Concat, "PageUser")]
+    #[case("", &["Vec<models::User>"], GenericNaming::Underscore, "Page_Vec_User")]
+    #[case("", &["i32", "&str"], GenericNaming::Concat, "PageI32Str")]
+    #[case("", &["(String, [u8; 4])"], GenericNaming::Underscore, "Page_Tuple_String_Array_u8")]
+    #[case(r#"#[schema(name_for(User = "UserPage"))]"#, &["models::User"], GenericNaming::Underscore, "UserPage")]
+    #[case(r#"#[schema(name_for(User = "UserPage"))]"#, &["Order"], GenericNaming::Underscore, "Page_Order")]
+    #[case(r#"#[schema(name_for((User, Order) = "Orders"))]"#, &["User", "Order"], GenericNaming::Concat, "Orders")]
+    fn test_instantiation_name(
+        #[case] attrs: &str,
+        #[case] args: &[&str],
+        #[case] naming: GenericNaming,
+        #[case] expected: &str,
+    ) {
+        let item: syn::DeriveInput =
+            syn::parse_str(&format!("{attrs} struct Page<T>(T);")).unwrap();
+        let args: Vec<Type> = args
+            .iter()
+            .map(|arg| syn::parse_str(arg).unwrap())
+            .collect();
+        let args: Vec<&Type> = args.iter().collect();
+        assert_eq!(
+            instantiation_name("Page", &item.attrs, &args, naming),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_register_instantiation() {
+        take_instantiations();
+        assert!(register_instantiation("Page_User", || {
+            // Reserved while it is built, so recursive uses just refer to it
+            assert!(register_instantiation("Page_User", || unreachable!()));
+            Some(Schema::object())
+        }));
+        assert!(!register_instantiation("Page_Broken", || None));
+        let registered = take_instantiations();
+        assert_eq!(registered.keys().collect::<Vec<_>>(), vec!["Page_User"]);
+        assert_eq!(
+            registered["Page_User"].schema_type,
+            Schema::object().schema_type
+        );
+        assert!(take_instantiations().is_empty());
+    }
+
     #[rstest]
     #[case("$invalid", "String")]
     fn test_substitute_type_parse_failure_uses_original(
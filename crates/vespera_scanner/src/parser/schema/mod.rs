@@ -37,11 +37,18 @@ mod type_schema;
 
 // Re-export public API
 pub use enum_schema::parse_enum_to_schema;
+pub use generics::{GenericNaming, substitute_type, take_instantiations, with_generic_naming};
 pub use serde_attrs::{
-    extract_default, extract_field_rename, extract_rename_all, extract_skip,
-    extract_skip_serializing_if, rename_field, strip_raw_prefix_owned,
+    SerdeEnumRepr, extract_default, extract_enum_repr, extract_field_access, extract_field_format,
+    extract_field_rename, extract_field_scope, extract_flatten, extract_other, extract_rename_all,
+    extract_schema_dialect, extract_schema_examples, extract_schema_name_for, extract_schema_split,
+    extract_schema_title, extract_schema_xml, extract_skip, extract_skip_serializing_if,
+    extract_untagged, is_absolute_uri, parse_json_value, rename_field, strip_raw_prefix_owned,
 };
-pub use struct_schema::parse_struct_to_schema;
-pub use type_schema::parse_type_to_schema_ref;
+pub use struct_schema::{
+    apply_field_constraints, apply_field_content, defaulted_fields, parse_struct_to_schema,
+    with_schema_scope, with_strict_nullability,
+};
+pub use type_schema::{collect_defaulted_types, parse_type_to_schema_ref};
 // Re-export for internal use within parser module
 pub use type_schema::{is_primitive_type, parse_type_to_schema_ref_with_schemas};
@@ -3,6 +3,8 @@
 //! This module provides functions to extract serde attributes from Rust types
 //! to properly generate `OpenAPI` schemas that respect serialization rules.
 
+use vespera_core::schema::Xml;
+
 /// Extract doc comments from attributes.
 /// Returns concatenated doc comment string or None if no doc comments.
 pub fn extract_doc_comment(attrs: &[syn::Attribute]) -> Option<String> {
@@ -200,6 +202,832 @@ pub fn extract_schema_ref_override(attrs: &[syn::Attribute]) -> Option<(String,
     })
 }
 
+/// Whether the container is marked `#[serde(deny_unknown_fields)]`.
+pub fn extract_deny_unknown_fields(attrs: &[syn::Attribute]) -> bool {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("serde"))
+        .filter_map(|attr| attr.meta.require_list().ok())
+        .any(|list| {
+            list.tokens.clone().into_iter().any(|token| {
+                matches!(token, proc_macro2::TokenTree::Ident(ident) if ident == "deny_unknown_fields")
+            })
+        })
+}
+
+/// Value of `#[schema(additional_properties = ...)]` on a struct.
+#[derive(Clone)]
+pub enum AdditionalProperties {
+    /// `additional_properties = false` / `= true`
+    Allowed(bool),
+    /// `additional_properties = SomeType`: extra properties must match the type's schema
+    Schema(Box<syn::Type>),
+}
+
+/// Extract `#[schema(additional_properties = false)]` or `= SomeType` from container attributes.
+pub fn extract_additional_properties(attrs: &[syn::Attribute]) -> Option<AdditionalProperties> {
+    attrs.iter().find_map(|attr| {
+        if !attr.path().is_ident("schema") {
+            return None;
+        }
+
+        let mut additional = None;
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("additional_properties") {
+                let value = meta.value()?;
+                additional = Some(if value.peek(syn::LitBool) {
+                    AdditionalProperties::Allowed(value.parse::<syn::LitBool>()?.value)
+                } else {
+                    AdditionalProperties::Schema(Box::new(value.parse()?))
+                });
+            } else if meta.input.peek(syn::Token![=]) {
+                let _: syn::Expr = meta.value()?.parse()?;
+            }
+            Ok(())
+        });
+        additional
+    })
+}
+
+/// Extract `#[schema(title = "...")]` from container attributes.
+pub fn extract_schema_title(attrs: &[syn::Attribute]) -> Option<String> {
+    attrs.iter().find_map(|attr| {
+        if !attr.path().is_ident("schema") {
+            return None;
+        }
+
+        let mut title = None;
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("title") {
+                let lit: syn::LitStr = meta.value()?.parse()?;
+                title = Some(lit.value());
+            } else if meta.input.peek(syn::Token![=]) {
+                let _: syn::Expr = meta.value()?.parse()?;
+            }
+            Ok(())
+        });
+        title
+    })
+}
+
+/// Extract `#[schema(name_for(User = "UserPage", (User, Order) = "UserOrderPair"))]` from
+/// the container attributes of a generic type: component names of its instantiations.
+///
+/// Keys are the type arguments, written as a tuple when the type has several parameters.
+pub fn extract_schema_name_for(attrs: &[syn::Attribute]) -> syn::Result<Vec<(syn::Type, String)>> {
+    let mut names = Vec::new();
+    for attr in attrs {
+        if !attr.path().is_ident("schema") {
+            continue;
+        }
+        let mut result = Ok(());
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name_for") {
+                match parse_name_for(meta.input) {
+                    Ok(entries) => names.extend(entries),
+                    Err(err) => {
+                        result = Err(err.clone());
+                        return Err(err);
+                    }
+                }
+            } else if meta.input.peek(syn::Token![=]) {
+                meta.value()?.parse::<syn::Expr>()?;
+            } else if meta.input.peek(syn::token::Paren) {
+                let _nested;
+                syn::parenthesized!(_nested in meta.input);
+            }
+            Ok(())
+        });
+        result?;
+    }
+    Ok(names)
+}
+
+/// Parse the `(Type = "Name", ...)` list of `name_for`.
+fn parse_name_for(input: syn::parse::ParseStream) -> syn::Result<Vec<(syn::Type, String)>> {
+    let content;
+    syn::parenthesized!(content in input);
+    let mut entries = Vec::new();
+    while !content.is_empty() {
+        let ty: syn::Type = content.parse()?;
+        content.parse::<syn::Token![=]>()?;
+        let lit: syn::LitStr = content.parse()?;
+        if !crate::schema_impl::is_valid_component_key(&lit.value()) {
+            return Err(syn::Error::new(
+                lit.span(),
+                format!(
+                    "invalid schema name \"{}\": component names may only contain ASCII letters, digits, '.', '-' and '_'.",
+                    lit.value()
+                ),
+            ));
+        }
+        entries.push((ty, lit.value()));
+        if !content.is_empty() {
+            content.parse::<syn::Token![,]>()?;
+        }
+    }
+    Ok(entries)
+}
+
+/// Whether `uri` is an absolute URI (`scheme:rest`), as `$schema` and `jsonSchemaDialect` require.
+pub fn is_absolute_uri(uri: &str) -> bool {
+    let Some((scheme, rest)) = uri.split_once(':') else {
+        return false;
+    };
+    scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+        && scheme
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+        && !rest.is_empty()
+        && !uri.chars().any(char::is_whitespace)
+}
+
+/// Extract `#[schema(dialect = "https://json-schema.org/draft/2020-12/schema")]` from
+/// container attributes, the `$schema` of the component.
+pub fn extract_schema_dialect(attrs: &[syn::Attribute]) -> syn::Result<Option<String>> {
+    let mut dialect = None;
+    for attr in attrs {
+        if !attr.path().is_ident("schema") {
+            continue;
+        }
+        let mut result = Ok(());
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("dialect") {
+                let lit: syn::LitStr = meta.value()?.parse()?;
+                if is_absolute_uri(&lit.value()) {
+                    dialect = Some(lit.value());
+                } else {
+                    result = Err(syn::Error::new(
+                        lit.span(),
+                        format!(
+                            "invalid schema dialect `{}`: expected an absolute URI such as \"https://json-schema.org/draft/2020-12/schema\"",
+                            lit.value()
+                        ),
+                    ));
+                }
+            } else if meta.input.peek(syn::Token![=]) {
+                meta.value()?.parse::<syn::Expr>()?;
+            } else if meta.input.peek(syn::token::Paren) {
+                let _nested;
+                syn::parenthesized!(_nested in meta.input);
+            }
+            Ok(())
+        });
+        result?;
+    }
+    Ok(dialect)
+}
+
+/// Extract `#[schema(content_encoding = "base64", content_media_type = "image/png")]`
+/// from field attributes as `(content_encoding, content_media_type)`.
+pub fn extract_field_content(attrs: &[syn::Attribute]) -> (Option<String>, Option<String>) {
+    let mut encoding = None;
+    let mut media_type = None;
+    for attr in attrs {
+        if !attr.path().is_ident("schema") {
+            continue;
+        }
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("content_encoding") {
+                let lit: syn::LitStr = meta.value()?.parse()?;
+                encoding = Some(lit.value());
+            } else if meta.path.is_ident("content_media_type") {
+                let lit: syn::LitStr = meta.value()?.parse()?;
+                media_type = Some(lit.value());
+            } else if meta.input.peek(syn::Token![=]) {
+                meta.value()?.parse::<syn::Expr>()?;
+            }
+            Ok(())
+        });
+    }
+    (encoding, media_type)
+}
+
+/// Extract `#[schema(format = "ulid")]` from field attributes.
+pub fn extract_field_format(attrs: &[syn::Attribute]) -> Option<String> {
+    attrs.iter().find_map(|attr| {
+        if !attr.path().is_ident("schema") {
+            return None;
+        }
+
+        let mut format = None;
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("format") {
+                let lit: syn::LitStr = meta.value()?.parse()?;
+                format = Some(lit.value());
+            } else if meta.input.peek(syn::Token![=]) {
+                meta.value()?.parse::<syn::Expr>()?;
+            }
+            Ok(())
+        });
+        format
+    })
+}
+
+/// Extract the serde_with adapter of a field: `T` of `#[serde_as(as = "T")]`, or of the
+/// `#[serde(with = "::serde_with::As::<T>")]` that the `#[serde_as]` macro rewrites it to.
+pub fn extract_serde_as(attrs: &[syn::Attribute]) -> Option<syn::Type> {
+    attrs.iter().find_map(|attr| {
+        let mut adapter = None;
+        if attr.path().is_ident("serde_as") {
+            let _ = attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("as") {
+                    let lit: syn::LitStr = meta.value()?.parse()?;
+                    adapter = lit.parse().ok();
+                } else if meta.input.peek(syn::Token![=]) {
+                    meta.value()?.parse::<syn::Expr>()?;
+                }
+                Ok(())
+            });
+        } else if attr.path().is_ident("serde") {
+            let _ = attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("with") || meta.path.is_ident("serialize_with") {
+                    let lit: syn::LitStr = meta.value()?.parse()?;
+                    adapter = lit.parse::<syn::ExprPath>().ok().and_then(|path| {
+                        path.path.segments.into_iter().find_map(|segment| {
+                            let syn::PathArguments::AngleBracketed(args) = segment.arguments else {
+                                return None;
+                            };
+                            if segment.ident != "As" {
+                                return None;
+                            }
+                            args.args.into_iter().find_map(|arg| match arg {
+                                syn::GenericArgument::Type(ty) => Some(ty),
+                                _ => None,
+                            })
+                        })
+                    });
+                } else if meta.input.peek(syn::Token![=]) {
+                    meta.value()?.parse::<syn::Expr>()?;
+                } else if meta.input.peek(syn::token::Paren) {
+                    meta.parse_nested_meta(|_| Ok(()))?;
+                }
+                Ok(())
+            });
+        }
+        adapter
+    })
+}
+
+/// Extract `#[schema(example = "alice@example.com")]` from field attributes.
+///
+/// The value uses `json!` syntax; objects need the `json!` wrapper
+/// (`example = json!({ "city": "Seoul" })`).
+pub fn extract_field_example(attrs: &[syn::Attribute]) -> Option<serde_json::Value> {
+    attrs.iter().find_map(|attr| {
+        if !attr.path().is_ident("schema") {
+            return None;
+        }
+
+        let mut example = None;
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("example") {
+                example = Some(parse_json_value(meta.value()?)?);
+            } else if meta.input.peek(syn::Token![=]) {
+                meta.value()?.parse::<syn::Expr>()?;
+            } else if meta.input.peek(syn::token::Paren) {
+                let _nested;
+                syn::parenthesized!(_nested in meta.input);
+            }
+            Ok(())
+        });
+        example
+    })
+}
+
+/// Validation keywords from `#[schema(minimum = 1, max_length = 64, pattern = "^[a-z]+$")]`.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct FieldConstraints {
+    pub minimum: Option<f64>,
+    pub maximum: Option<f64>,
+    pub multiple_of: Option<f64>,
+    pub min_length: Option<usize>,
+    pub max_length: Option<usize>,
+    pub pattern: Option<String>,
+    pub min_items: Option<usize>,
+    pub max_items: Option<usize>,
+    /// garde `length(min, max)`: `minLength`/`maxLength` of a string, `minItems`/`maxItems` of a list
+    pub min_size: Option<usize>,
+    pub max_size: Option<usize>,
+    /// garde `email`, `url`, `ipv4`, `ipv6`
+    pub format: Option<String>,
+    /// garde `inner(...)`: constraints on the items of a list
+    pub inner: Option<Box<Self>>,
+}
+
+/// Parse a possibly negative integer or float literal.
+fn parse_number(input: syn::parse::ParseStream) -> syn::Result<f64> {
+    let negative = input.peek(syn::Token![-]);
+    if negative {
+        input.parse::<syn::Token![-]>()?;
+    }
+    let n = match input.parse::<syn::Lit>()? {
+        syn::Lit::Int(i) => i.base10_parse::<f64>()?,
+        syn::Lit::Float(f) => f.base10_parse::<f64>()?,
+        other => return Err(syn::Error::new_spanned(other, "expected a number")),
+    };
+    Ok(if negative { -n } else { n })
+}
+
+/// Extract `#[schema(minimum = 1, maximum = 100, min_length = 3, pattern = "^[a-z]+$")]`
+/// from field attributes.
+///
+/// Also accepts `multiple_of`, `max_length`, `min_items` and `max_items`.
+pub fn extract_field_constraints(attrs: &[syn::Attribute]) -> FieldConstraints {
+    let mut constraints = FieldConstraints::default();
+    for attr in attrs {
+        if !attr.path().is_ident("schema") {
+            continue;
+        }
+        let _ = attr.parse_nested_meta(|meta| {
+            let Some(key) = meta.path.get_ident().map(ToString::to_string) else {
+                return Ok(());
+            };
+            match key.as_str() {
+                "minimum" => constraints.minimum = Some(parse_number(meta.value()?)?),
+                "maximum" => constraints.maximum = Some(parse_number(meta.value()?)?),
+                "multiple_of" => constraints.multiple_of = Some(parse_number(meta.value()?)?),
+                "min_length" | "max_length" | "min_items" | "max_items" => {
+                    let lit: syn::LitInt = meta.value()?.parse()?;
+                    let n = Some(lit.base10_parse::<usize>()?);
+                    match key.as_str() {
+                        "min_length" => constraints.min_length = n,
+                        "max_length" => constraints.max_length = n,
+                        "min_items" => constraints.min_items = n,
+                        _ => constraints.max_items = n,
+                    }
+                }
+                "pattern" => {
+                    let lit: syn::LitStr = meta.value()?.parse()?;
+                    constraints.pattern = Some(lit.value());
+                }
+                _ if meta.input.peek(syn::Token![=]) => {
+                    meta.value()?.parse::<syn::Expr>()?;
+                }
+                _ if meta.input.peek(syn::token::Paren) => {
+                    let _nested;
+                    syn::parenthesized!(_nested in meta.input);
+                }
+                _ => {}
+            }
+            Ok(())
+        });
+    }
+    constraints
+}
+
+/// The value of an integer or float literal, possibly negated.
+fn literal_number(expr: &syn::Expr) -> Option<f64> {
+    match expr {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Int(i),
+            ..
+        }) => i.base10_parse().ok(),
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Float(f),
+            ..
+        }) => f.base10_parse().ok(),
+        syn::Expr::Unary(syn::ExprUnary {
+            op: syn::UnOp::Neg(_),
+            expr,
+            ..
+        }) => literal_number(expr).map(|n| -n),
+        _ => None,
+    }
+}
+
+/// The value of a non-negative integer literal.
+fn literal_size(expr: &syn::Expr) -> Option<usize> {
+    match expr {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Int(i),
+            ..
+        }) => i.base10_parse().ok(),
+        _ => None,
+    }
+}
+
+/// Read one garde rule (`length(min = 1)`, `range(max = 10)`, `email`, `inner(...)`, ...).
+///
+/// Bounds given as constants or expressions rather than literals are left out.
+fn parse_garde_rule(
+    meta: &syn::meta::ParseNestedMeta,
+    constraints: &mut FieldConstraints,
+) -> syn::Result<()> {
+    let Some(rule) = meta.path.get_ident().map(ToString::to_string) else {
+        return Ok(());
+    };
+    match rule.as_str() {
+        "length" | "range" => meta.parse_nested_meta(|bound| {
+            if !bound.input.peek(syn::Token![=]) {
+                // Length modes: `chars`, `bytes`, `graphemes`, ...
+                return Ok(());
+            }
+            let value: syn::Expr = bound.value()?.parse()?;
+            let Some(key) = bound.path.get_ident().map(ToString::to_string) else {
+                return Ok(());
+            };
+            if rule == "length" {
+                let size = literal_size(&value);
+                match key.as_str() {
+                    "min" => constraints.min_size = size,
+                    "max" => constraints.max_size = size,
+                    "equal" => (constraints.min_size, constraints.max_size) = (size, size),
+                    _ => {}
+                }
+            } else {
+                let number = literal_number(&value);
+                match key.as_str() {
+                    "min" => constraints.minimum = number,
+                    "max" => constraints.maximum = number,
+                    "equal" => (constraints.minimum, constraints.maximum) = (number, number),
+                    _ => {}
+                }
+            }
+            Ok(())
+        })?,
+        "pattern" => {
+            let content;
+            syn::parenthesized!(content in meta.input);
+            if content.peek(syn::LitStr) {
+                let lit: syn::LitStr = content.parse()?;
+                constraints.pattern = Some(lit.value());
+            }
+            content.parse::<proc_macro2::TokenStream>()?;
+        }
+        "email" => constraints.format = Some("email".to_string()),
+        "url" => constraints.format = Some("uri".to_string()),
+        "ipv4" | "ipv6" => constraints.format = Some(rule),
+        "inner" => {
+            let inner = constraints.inner.get_or_insert_with(Box::default);
+            meta.parse_nested_meta(|rule| parse_garde_rule(&rule, inner))?;
+        }
+        _ if meta.input.peek(syn::token::Paren) => {
+            let content;
+            syn::parenthesized!(content in meta.input);
+            content.parse::<proc_macro2::TokenStream>()?;
+        }
+        _ if meta.input.peek(syn::Token![=]) => {
+            meta.value()?.parse::<syn::Expr>()?;
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Extract the constraints of `garde` validation rules on a field
+/// (`#[garde(length(min = 1, max = 64))]`, `#[garde(range(min = 0))]`, `#[garde(email)]`, ...).
+///
+/// Understands `length`, `range`, `pattern("...")`, `email`, `url`, `ipv4`, `ipv6` and
+/// `inner(...)`; other rules are not expressible in JSON Schema and are skipped.
+pub fn extract_garde_constraints(attrs: &[syn::Attribute]) -> FieldConstraints {
+    let mut constraints = FieldConstraints::default();
+    for attr in attrs {
+        if !attr.path().is_ident("garde") {
+            continue;
+        }
+        let _ = attr.parse_nested_meta(|meta| parse_garde_rule(&meta, &mut constraints));
+    }
+    constraints
+}
+
+/// Extract `#[schema(xml(name = "...", namespace = "...", prefix = "...", attribute, wrapped))]`.
+///
+/// Used on containers (the component's XML element) and on fields (the property's
+/// element or attribute).
+pub fn extract_schema_xml(attrs: &[syn::Attribute]) -> Option<Xml> {
+    attrs.iter().find_map(|attr| {
+        if !attr.path().is_ident("schema") {
+            return None;
+        }
+
+        let mut xml = None::<Xml>;
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("xml") {
+                let xml = xml.get_or_insert_with(Xml::default);
+                meta.parse_nested_meta(|inner| {
+                    if inner.path.is_ident("attribute") {
+                        xml.attribute = Some(true);
+                    } else if inner.path.is_ident("wrapped") {
+                        xml.wrapped = Some(true);
+                    } else {
+                        let slot = if inner.path.is_ident("name") {
+                            &mut xml.name
+                        } else if inner.path.is_ident("namespace") {
+                            &mut xml.namespace
+                        } else if inner.path.is_ident("prefix") {
+                            &mut xml.prefix
+                        } else {
+                            return Err(inner.error(
+                                "xml: expected `name`, `namespace`, `prefix`, `attribute` or `wrapped`",
+                            ));
+                        };
+                        let lit: syn::LitStr = inner.value()?.parse()?;
+                        *slot = Some(lit.value());
+                    }
+                    Ok(())
+                })?;
+            } else if meta.input.peek(syn::Token![=]) {
+                meta.value()?.parse::<syn::Expr>()?;
+            }
+            Ok(())
+        });
+        xml
+    })
+}
+
+/// Extract `#[schema(examples(minimal = json!({...}), full = json!({...})))]` from
+/// container attributes as `(name, value)` pairs in declaration order.
+///
+/// Values use `json!` syntax; the `json!` wrapper itself is optional.
+pub fn extract_schema_examples(
+    attrs: &[syn::Attribute],
+) -> syn::Result<Vec<(String, serde_json::Value)>> {
+    let mut examples: Vec<(String, serde_json::Value)> = Vec::new();
+    for attr in attrs {
+        if !attr.path().is_ident("schema") {
+            continue;
+        }
+        let mut result = Ok(());
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("examples") {
+                let parsed = meta.parse_nested_meta(|example| {
+                    let Some(name) = example.path.get_ident().map(ToString::to_string) else {
+                        return Err(example.error("examples: expected an example name"));
+                    };
+                    if examples.iter().any(|(existing, _)| *existing == name) {
+                        return Err(example.error(format!("examples: duplicate example `{name}`")));
+                    }
+                    let value = parse_json_value(example.value()?)?;
+                    examples.push((name, value));
+                    Ok(())
+                });
+                if let Err(err) = parsed {
+                    result = Err(err.clone());
+                    return Err(err);
+                }
+            } else if meta.input.peek(syn::Token![=]) {
+                meta.value()?.parse::<syn::Expr>()?;
+            } else if meta.input.peek(syn::token::Paren) {
+                let _nested;
+                syn::parenthesized!(_nested in meta.input);
+            }
+            Ok(())
+        });
+        result?;
+    }
+    Ok(examples)
+}
+
+/// Parse a value written in `json!` syntax (`{ "id": 1, "tags": ["a"] }`, `-1.5`, `null`).
+pub fn parse_json_value(input: syn::parse::ParseStream) -> syn::Result<serde_json::Value> {
+    use serde_json::Value;
+
+    if input.peek(syn::Ident) && input.peek2(syn::Token![!]) {
+        let mac: syn::Macro = input.parse()?;
+        if !mac.path.is_ident("json") {
+            return Err(syn::Error::new_spanned(
+                &mac.path,
+                "examples: expected `json!(...)`",
+            ));
+        }
+        return mac.parse_body_with(parse_json_value);
+    }
+    if input.peek(syn::token::Brace) {
+        let content;
+        syn::braced!(content in input);
+        let mut object = serde_json::Map::new();
+        while !content.is_empty() {
+            let key: syn::LitStr = content.parse()?;
+            content.parse::<syn::Token![:]>()?;
+            object.insert(key.value(), parse_json_value(&content)?);
+            if content.is_empty() {
+                break;
+            }
+            content.parse::<syn::Token![,]>()?;
+        }
+        return Ok(Value::Object(object));
+    }
+    if input.peek(syn::token::Bracket) {
+        let content;
+        syn::bracketed!(content in input);
+        let mut array = Vec::new();
+        while !content.is_empty() {
+            array.push(parse_json_value(&content)?);
+            if content.is_empty() {
+                break;
+            }
+            content.parse::<syn::Token![,]>()?;
+        }
+        return Ok(Value::Array(array));
+    }
+    if input.peek(syn::Ident) {
+        let ident: syn::Ident = input.parse()?;
+        return if ident == "null" {
+            Ok(Value::Null)
+        } else {
+            Err(syn::Error::new(
+                ident.span(),
+                "examples: expected a JSON value",
+            ))
+        };
+    }
+
+    let negative = input.parse::<Option<syn::Token![-]>>()?.is_some();
+    let lit: syn::Lit = input.parse()?;
+    match (&lit, negative) {
+        (syn::Lit::Str(s), false) => Ok(Value::String(s.value())),
+        (syn::Lit::Bool(b), false) => Ok(Value::Bool(b.value)),
+        (syn::Lit::Int(i), false) => Ok(i.base10_parse::<u64>()?.into()),
+        (syn::Lit::Int(i), true) => Ok(format!("-{}", i.base10_digits())
+            .parse::<i64>()
+            .map_err(|e| syn::Error::new(i.span(), e))?
+            .into()),
+        (syn::Lit::Float(f), _) => {
+            let n = f.base10_parse::<f64>()?;
+            serde_json::Number::from_f64(if negative { -n } else { n })
+                .map(Value::Number)
+                .ok_or_else(|| syn::Error::new(f.span(), "examples: expected a finite number"))
+        }
+        _ => Err(syn::Error::new_spanned(
+            lit,
+            "examples: expected a JSON value",
+        )),
+    }
+}
+
+/// Flag-set declaration from `#[schema(flags = [...])]` and/or `#[schema(bitflags)]`.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct SchemaFlags {
+    /// Allowed flag names (or integer values).
+    pub names: Vec<serde_json::Value>,
+    /// Serialized in the bitflags 2.x text format (`"READ | WRITE"`) instead of as a list.
+    pub bitflags: bool,
+}
+
+/// Extract `#[schema(flags = ["READ", "WRITE"])]` / `#[schema(bitflags)]` from container attributes.
+pub fn extract_schema_flags(attrs: &[syn::Attribute]) -> Option<SchemaFlags> {
+    let mut flags = None::<SchemaFlags>;
+    for attr in attrs {
+        if !attr.path().is_ident("schema") {
+            continue;
+        }
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("bitflags") {
+                flags.get_or_insert_with(SchemaFlags::default).bitflags = true;
+            } else if meta.path.is_ident("flags") {
+                let array: syn::ExprArray = meta.value()?.parse()?;
+                let names = &mut flags.get_or_insert_with(SchemaFlags::default).names;
+                for elem in array.elems {
+                    match elem {
+                        syn::Expr::Lit(syn::ExprLit {
+                            lit: syn::Lit::Str(s),
+                            ..
+                        }) => names.push(s.value().into()),
+                        syn::Expr::Lit(syn::ExprLit {
+                            lit: syn::Lit::Int(i),
+                            ..
+                        }) => names.push(i.base10_parse::<u64>()?.into()),
+                        other => {
+                            return Err(syn::Error::new_spanned(
+                                other,
+                                "flags: expected string or integer literals",
+                            ));
+                        }
+                    }
+                }
+            } else if meta.input.peek(syn::Token![=]) {
+                meta.value()?.parse::<syn::Expr>()?;
+            }
+            Ok(())
+        });
+    }
+    flags
+}
+
+/// Extract `#[schema(scope = "admin")]` from field attributes.
+///
+/// Scoped fields are omitted from the public spec and only appear in the
+/// per-scope document generated for that scope.
+pub fn extract_field_scope(attrs: &[syn::Attribute]) -> Option<String> {
+    attrs.iter().find_map(|attr| {
+        if !attr.path().is_ident("schema") {
+            return None;
+        }
+
+        let mut scope = None;
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("scope") {
+                let value = meta.value()?;
+                let lit: syn::LitStr = value.parse()?;
+                scope = Some(lit.value());
+            } else if meta.input.peek(syn::Token![=]) {
+                // Consume values of other keys (e.g. `default = "..."`) so parsing continues
+                let _: syn::Expr = meta.value()?.parse()?;
+            }
+            Ok(())
+        });
+        scope
+    })
+}
+
+/// How a field embeds its type, from `#[schema(inline)]` / `#[schema(ref)]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldSchemaMode {
+    /// Expand the referenced component in place.
+    Inline,
+    /// Always emit a `$ref`, even where the type would be inlined.
+    Ref,
+}
+
+/// Extract `#[schema(inline)]` or `#[schema(ref)]` from field attributes.
+pub fn extract_field_schema_mode(attrs: &[syn::Attribute]) -> Option<FieldSchemaMode> {
+    attrs.iter().find_map(|attr| {
+        if !attr.path().is_ident("schema") {
+            return None;
+        }
+
+        let mut mode = None;
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.input.peek(syn::Token![=]) {
+                let _: syn::Expr = meta.value()?.parse()?;
+            } else if meta.path.is_ident("inline") {
+                mode = Some(FieldSchemaMode::Inline);
+            } else if meta.path.is_ident("ref") {
+                mode = Some(FieldSchemaMode::Ref);
+            }
+            Ok(())
+        });
+        mode
+    })
+}
+
+/// Extract `#[schema(deprecated)]` from field attributes.
+pub fn extract_field_deprecated(attrs: &[syn::Attribute]) -> bool {
+    attrs.iter().any(|attr| {
+        if !attr.path().is_ident("schema") {
+            return false;
+        }
+
+        let mut deprecated = false;
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.input.peek(syn::Token![=]) {
+                let _: syn::Expr = meta.value()?.parse()?;
+            } else if meta.path.is_ident("deprecated") {
+                deprecated = true;
+            }
+            Ok(())
+        });
+        deprecated
+    })
+}
+
+/// Extract `#[schema(read_only)]` / `#[schema(write_only)]` from field attributes,
+/// as `(read_only, write_only)`.
+pub fn extract_field_access(attrs: &[syn::Attribute]) -> (bool, bool) {
+    let mut read_only = false;
+    let mut write_only = false;
+    for attr in attrs {
+        if !attr.path().is_ident("schema") {
+            continue;
+        }
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.input.peek(syn::Token![=]) {
+                let _: syn::Expr = meta.value()?.parse()?;
+            } else if meta.path.is_ident("read_only") {
+                read_only = true;
+            } else if meta.path.is_ident("write_only") {
+                write_only = true;
+            }
+            Ok(())
+        });
+    }
+    (read_only, write_only)
+}
+
+/// Extract `#[schema(split)]` from container attributes.
+///
+/// A split struct is published as `{Name}Request` (without its read-only properties)
+/// and `{Name}Response` (without its write-only properties).
+pub fn extract_schema_split(attrs: &[syn::Attribute]) -> bool {
+    attrs.iter().any(|attr| {
+        if !attr.path().is_ident("schema") {
+            return false;
+        }
+
+        let mut split = false;
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.input.peek(syn::Token![=]) {
+                let _: syn::Expr = meta.value()?.parse()?;
+            } else if meta.path.is_ident("split") {
+                split = true;
+            }
+            Ok(())
+        });
+        split
+    })
+}
+
 pub fn extract_field_rename(attrs: &[syn::Attribute]) -> Option<String> {
     // First check serde attrs (higher priority)
     for attr in attrs {
@@ -772,6 +1600,76 @@ pub fn extract_untagged(attrs: &[syn::Attribute]) -> bool {
     false
 }
 
+/// Extract other attribute from serde variant attributes
+/// Returns true if `#[serde(other)]` is present (the catch-all for unknown tags)
+pub fn extract_other(attrs: &[syn::Attribute]) -> bool {
+    attrs.iter().any(|attr| {
+        let mut found = false;
+        if attr.path().is_ident("serde") {
+            let _ = attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("other") {
+                    found = true;
+                } else if meta.input.peek(syn::Token![=]) {
+                    meta.value()?.parse::<syn::Expr>()?;
+                }
+                Ok(())
+            });
+        }
+        found
+    })
+}
+
+/// Integer type an enum is serialized as, `None` if it is not serialized as an integer.
+///
+/// - `#[serde(into = "i32")]` → the `into` type
+/// - `#[derive(Serialize_repr)]` or `#[derive(Deserialize_repr)]` (serde_repr) → the
+///   `#[repr(...)]` type, `isize` without one
+pub fn extract_integer_repr(attrs: &[syn::Attribute]) -> Option<String> {
+    const INTEGERS: [&str; 12] = [
+        "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize",
+    ];
+    let mut into = None;
+    let mut serde_repr = false;
+    let mut repr = None;
+    for attr in attrs {
+        if attr.path().is_ident("serde") {
+            let _ = attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("into") {
+                    let lit: syn::LitStr = meta.value()?.parse()?;
+                    into = Some(lit.value());
+                } else if meta.input.peek(syn::Token![=]) {
+                    meta.value()?.parse::<syn::Expr>()?;
+                } else if meta.input.peek(syn::token::Paren) {
+                    meta.parse_nested_meta(|_| Ok(()))?;
+                }
+                Ok(())
+            });
+        } else if attr.path().is_ident("derive") {
+            let _ = attr.parse_nested_meta(|meta| {
+                serde_repr |= meta.path.segments.last().is_some_and(|segment| {
+                    segment.ident == "Serialize_repr" || segment.ident == "Deserialize_repr"
+                });
+                Ok(())
+            });
+        } else if attr.path().is_ident("repr") {
+            let _ = attr.parse_nested_meta(|meta| {
+                if let Some(ident) = meta.path.get_ident()
+                    && INTEGERS.contains(&ident.to_string().as_str())
+                {
+                    repr = Some(ident.to_string());
+                }
+                Ok(())
+            });
+        }
+    }
+    if let Some(into) = into {
+        return INTEGERS
+            .contains(&into.trim())
+            .then(|| into.trim().to_string());
+    }
+    serde_repr.then(|| repr.unwrap_or_else(|| "isize".to_string()))
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(clippy::option_option)]
@@ -1846,6 +2744,41 @@ mod tests {
             assert_eq!(result, expected, "Failed for: {serde_content}");
         }
 
+        // extract_schema_flags tests
+        #[rstest]
+        #[case(r"#[derive(Debug)]", None)]
+        #[case(
+            r#"#[schema(flags = ["READ", "WRITE"])]"#,
+            Some(SchemaFlags { names: vec!["READ".into(), "WRITE".into()], bitflags: false })
+        )]
+        #[case(
+            r"#[schema(flags = [1, 2, 4])]",
+            Some(SchemaFlags { names: vec![1.into(), 2.into(), 4.into()], bitflags: false })
+        )]
+        #[case(
+            r#"#[schema(name = "Perms", bitflags)] #[schema(flags = ["READ"])]"#,
+            Some(SchemaFlags { names: vec!["READ".into()], bitflags: true })
+        )]
+        fn test_extract_schema_flags(#[case] attrs: &str, #[case] expected: Option<SchemaFlags>) {
+            let item: syn::ItemStruct = syn::parse_str(&format!("{attrs} struct Perms;")).unwrap();
+            assert_eq!(extract_schema_flags(&item.attrs), expected);
+        }
+
+        // extract_other tests
+        #[rstest]
+        #[case(r"other", true)]
+        #[case(r#"rename = "unknown", other"#, true)]
+        #[case(r"untagged", false)]
+        #[case(r"default", false)]
+        fn test_extract_other(#[case] serde_content: &str, #[case] expected: bool) {
+            let attrs = get_enum_attrs(serde_content);
+            assert_eq!(
+                extract_other(&attrs),
+                expected,
+                "Failed for: {serde_content}"
+            );
+        }
+
         // extract_enum_repr comprehensive tests
         #[test]
         fn test_extract_enum_repr_externally_tagged() {
@@ -2128,4 +3061,283 @@ mod tests {
             assert_eq!(result, None);
         }
     }
+
+    #[rstest]
+    #[case(r#"#[schema(scope = "admin")]"#, Some("admin"))]
+    #[case(r#"#[schema(default = "1", scope = "internal")]"#, Some("internal"))]
+    #[case(r#"#[schema(default = "1")]"#, None)]
+    #[case(r#"#[serde(rename = "scope")]"#, None)]
+    fn test_extract_field_scope(#[case] attr_src: &str, #[case] expected: Option<&str>) {
+        let struct_src = format!("struct Foo {{ {attr_src} pub x: i32 }}");
+        let item: syn::ItemStruct = syn::parse_str(&struct_src).unwrap();
+        let field = item.fields.iter().next().unwrap();
+        assert_eq!(extract_field_scope(&field.attrs).as_deref(), expected);
+    }
+
+    #[rstest]
+    #[case("#[schema(inline)]", Some(FieldSchemaMode::Inline))]
+    #[case("#[schema(ref)]", Some(FieldSchemaMode::Ref))]
+    #[case(r#"#[schema(scope = "admin", ref)]"#, Some(FieldSchemaMode::Ref))]
+    #[case(r#"#[schema(ref = "User")]"#, None)]
+    #[case("#[serde(default)]", None)]
+    fn test_extract_field_schema_mode(
+        #[case] attr_src: &str,
+        #[case] expected: Option<FieldSchemaMode>,
+    ) {
+        let struct_src = format!("struct Foo {{ {attr_src} pub x: i32 }}");
+        let item: syn::ItemStruct = syn::parse_str(&struct_src).unwrap();
+        let field = item.fields.iter().next().unwrap();
+        assert_eq!(extract_field_schema_mode(&field.attrs), expected);
+    }
+
+    #[rstest]
+    #[case("#[schema(deprecated)]", true)]
+    #[case(r#"#[schema(scope = "admin", deprecated)]"#, true)]
+    #[case("#[serde(default)]", false)]
+    fn test_extract_field_deprecated(#[case] attr_src: &str, #[case] expected: bool) {
+        let struct_src = format!("struct Foo {{ {attr_src} pub x: i32 }}");
+        let item: syn::ItemStruct = syn::parse_str(&struct_src).unwrap();
+        let field = item.fields.iter().next().unwrap();
+        assert_eq!(extract_field_deprecated(&field.attrs), expected);
+    }
+
+    #[rstest]
+    #[case("#[schema(read_only)]", (true, false))]
+    #[case(r#"#[schema(scope = "admin", write_only)]"#, (false, true))]
+    #[case("#[schema(read_only)] #[schema(write_only)]", (true, true))]
+    #[case("#[serde(default)]", (false, false))]
+    fn test_extract_field_access(#[case] attr_src: &str, #[case] expected: (bool, bool)) {
+        let struct_src = format!("struct Foo {{ {attr_src} pub x: i32 }}");
+        let item: syn::ItemStruct = syn::parse_str(&struct_src).unwrap();
+        let field = item.fields.iter().next().unwrap();
+        assert_eq!(extract_field_access(&field.attrs), expected);
+    }
+
+    #[rstest]
+    #[case("#[schema(split)]", true)]
+    #[case(r#"#[schema(name = "User", split)]"#, true)]
+    #[case("#[schema(deprecated)]", false)]
+    #[case("#[serde(deny_unknown_fields)]", false)]
+    fn test_extract_schema_split(#[case] attr_src: &str, #[case] expected: bool) {
+        let item: syn::ItemStruct = syn::parse_str(&format!("{attr_src} struct Foo;")).unwrap();
+        assert_eq!(extract_schema_split(&item.attrs), expected);
+    }
+
+    #[rstest]
+    #[case(r#"#[schema(title = "User account")]"#, Some("User account"))]
+    #[case(r#"#[schema(name = "user", title = "User")]"#, Some("User"))]
+    #[case(r#"#[schema(name = "user")]"#, None)]
+    #[case(r#"#[serde(rename = "title")]"#, None)]
+    fn test_extract_schema_title(#[case] attr_src: &str, #[case] expected: Option<&str>) {
+        let item: syn::ItemStruct = syn::parse_str(&format!("{attr_src} struct Foo;")).unwrap();
+        assert_eq!(extract_schema_title(&item.attrs).as_deref(), expected);
+    }
+
+    #[rstest]
+    #[case("https://json-schema.org/draft/2020-12/schema", true)]
+    #[case("urn:example:dialect", true)]
+    #[case("draft-07", false)]
+    #[case("https:", false)]
+    #[case("1http://example.com", false)]
+    #[case("https://example.com/my dialect", false)]
+    fn test_is_absolute_uri(#[case] uri: &str, #[case] expected: bool) {
+        assert_eq!(is_absolute_uri(uri), expected);
+    }
+
+    #[rstest]
+    #[case(
+        r#"#[schema(title = "User", dialect = "https://json-schema.org/draft/2019-09/schema")]"#,
+        Ok(Some("https://json-schema.org/draft/2019-09/schema"))
+    )]
+    #[case(r#"#[schema(xml(name = "user"))]"#, Ok(None))]
+    #[case(
+        r#"#[schema(dialect = "draft-07")]"#,
+        Err("invalid schema dialect `draft-07`: expected an absolute URI")
+    )]
+    fn test_extract_schema_dialect(
+        #[case] attr_src: &str,
+        #[case] expected: Result<Option<&str>, &str>,
+    ) {
+        let item: syn::ItemStruct = syn::parse_str(&format!("{attr_src} struct Foo;")).unwrap();
+        match (extract_schema_dialect(&item.attrs), expected) {
+            (Ok(dialect), Ok(expected)) => assert_eq!(dialect.as_deref(), expected),
+            (Err(err), Err(expected)) => assert!(err.to_string().contains(expected), "{err}"),
+            (result, expected) => panic!("expected {expected:?}, got {result:?}"),
+        }
+    }
+
+    #[test]
+    fn test_extract_schema_examples() {
+        let item: syn::ItemStruct = syn::parse_str(
+            r#"#[schema(title = "User", xml(name = "user"), examples(
+                minimal = json!({ "id": 1 }),
+                full = json!({ "id": -2, "score": 0.5, "tags": ["a", "b"], "email": null, "active": true, }),
+                bare = "text"
+            ))] struct Foo;"#,
+        )
+        .unwrap();
+        let examples = extract_schema_examples(&item.attrs).unwrap();
+        assert_eq!(
+            examples,
+            vec![
+                ("minimal".to_string(), serde_json::json!({ "id": 1 })),
+                (
+                    "full".to_string(),
+                    serde_json::json!({ "id": -2, "score": 0.5, "tags": ["a", "b"], "email": null, "active": true })
+                ),
+                ("bare".to_string(), serde_json::json!("text")),
+            ]
+        );
+        let item: syn::ItemStruct =
+            syn::parse_str(r#"#[schema(title = "User")] struct Foo;"#).unwrap();
+        assert!(extract_schema_examples(&item.attrs).unwrap().is_empty());
+    }
+
+    #[rstest]
+    #[case(
+        "#[schema(examples(a = json!(1), a = json!(2)))]",
+        "duplicate example `a`"
+    )]
+    #[case("#[schema(examples(a = vec!(1)))]", "expected `json!(...)`")]
+    #[case(
+        "#[schema(examples(a = json!({ \"id\": undefined })))]",
+        "expected a JSON value"
+    )]
+    #[case("#[schema(examples(a = json!({ id: 1 })))]", "expected string literal")]
+    fn test_extract_schema_examples_errors(#[case] attr_src: &str, #[case] expected: &str) {
+        let item: syn::ItemStruct = syn::parse_str(&format!("{attr_src} struct Foo;")).unwrap();
+        let err = extract_schema_examples(&item.attrs).unwrap_err();
+        assert!(err.to_string().contains(expected), "{err}");
+    }
+
+    #[rstest]
+    #[case("#[serde(deny_unknown_fields)]", true)]
+    #[case(r#"#[serde(rename_all = "camelCase", deny_unknown_fields)]"#, true)]
+    #[case(r#"#[serde(rename(serialize = "deny_unknown_fields"))]"#, false)]
+    #[case(r#"#[schema(name = "deny_unknown_fields")]"#, false)]
+    #[case(r#"#[serde(rename_all = "camelCase")]"#, false)]
+    fn test_extract_deny_unknown_fields(#[case] attr_src: &str, #[case] expected: bool) {
+        let item: syn::ItemStruct = syn::parse_str(&format!("{attr_src} struct Foo;")).unwrap();
+        assert_eq!(extract_deny_unknown_fields(&item.attrs), expected);
+    }
+
+    #[rstest]
+    #[case(
+        r#"#[schema(xml(name = "user", namespace = "https://example.com/ns", prefix = "ex"))]"#,
+        Some(Xml {
+            name: Some("user".to_string()),
+            namespace: Some("https://example.com/ns".to_string()),
+            prefix: Some("ex".to_string()),
+            ..Xml::default()
+        })
+    )]
+    #[case(
+        r#"#[schema(title = "Tags", xml(wrapped, attribute))]"#,
+        Some(Xml { attribute: Some(true), wrapped: Some(true), ..Xml::default() })
+    )]
+    #[case(r#"#[schema(title = "User")]"#, None)]
+    #[case(r#"#[serde(rename = "xml")]"#, None)]
+    fn test_extract_schema_xml(#[case] attr_src: &str, #[case] expected: Option<Xml>) {
+        let item: syn::ItemStruct = syn::parse_str(&format!("{attr_src} struct Foo;")).unwrap();
+        assert_eq!(extract_schema_xml(&item.attrs), expected);
+    }
+
+    #[rstest]
+    #[case(r#"#[schema(format = "ulid")]"#, Some("ulid"))]
+    #[case(r#"#[schema(scope = "admin", format = "iban")]"#, Some("iban"))]
+    #[case(r#"#[schema(scope = "admin")]"#, None)]
+    #[case(r#"#[serde(rename = "format")]"#, None)]
+    fn test_extract_field_format(#[case] attr_src: &str, #[case] expected: Option<&str>) {
+        let item: syn::ItemStruct =
+            syn::parse_str(&format!("struct Foo {{ {attr_src} pub x: String }}")).unwrap();
+        let field = item.fields.iter().next().unwrap();
+        assert_eq!(extract_field_format(&field.attrs).as_deref(), expected);
+    }
+
+    #[rstest]
+    #[case(r#"#[schema(example = "alice@example.com")]"#, Some(serde_json::json!("alice@example.com")))]
+    #[case(r#"#[schema(format = "email", example = -3)]"#, Some(serde_json::json!(-3)))]
+    #[case(r#"#[schema(example = json!({ "city": "Seoul" }), deprecated)]"#, Some(serde_json::json!({ "city": "Seoul" })))]
+    #[case(r#"#[schema(xml(attribute), example = ["a", "b"])]"#, Some(serde_json::json!(["a", "b"])))]
+    #[case(r#"#[schema(format = "email")]"#, None)]
+    fn test_extract_field_example(
+        #[case] attr_src: &str,
+        #[case] expected: Option<serde_json::Value>,
+    ) {
+        let item: syn::ItemStruct =
+            syn::parse_str(&format!("struct Foo {{ {attr_src} pub x: String }}")).unwrap();
+        let field = item.fields.iter().next().unwrap();
+        assert_eq!(extract_field_example(&field.attrs), expected);
+    }
+
+    #[rstest]
+    #[case(
+        r"#[schema(minimum = 1, maximum = 100, multiple_of = 0.5)]",
+        FieldConstraints { minimum: Some(1.0), maximum: Some(100.0), multiple_of: Some(0.5), ..Default::default() }
+    )]
+    #[case(
+        r#"#[schema(format = "email", min_length = 3, max_length = 64, pattern = "^[a-z]+$")]"#,
+        FieldConstraints { min_length: Some(3), max_length: Some(64), pattern: Some("^[a-z]+$".to_string()), ..Default::default() }
+    )]
+    #[case(
+        r"#[schema(xml(attribute), minimum = -10)] #[schema(min_items = 1, max_items = 5)]",
+        FieldConstraints { minimum: Some(-10.0), min_items: Some(1), max_items: Some(5), ..Default::default() }
+    )]
+    #[case(r#"#[serde(rename = "minimum")]"#, FieldConstraints::default())]
+    fn test_extract_field_constraints(#[case] attr_src: &str, #[case] expected: FieldConstraints) {
+        let item: syn::ItemStruct =
+            syn::parse_str(&format!("struct Foo {{ {attr_src} pub x: String }}")).unwrap();
+        let field = item.fields.iter().next().unwrap();
+        assert_eq!(extract_field_constraints(&field.attrs), expected);
+    }
+
+    #[rstest]
+    #[case(
+        "#[garde(ascii, length(chars, min = 3, max = 25))]",
+        FieldConstraints { min_size: Some(3), max_size: Some(25), ..Default::default() }
+    )]
+    #[case(
+        "#[garde(range(min = -1.5, max = MAX_SCORE))]",
+        FieldConstraints { minimum: Some(-1.5), ..Default::default() }
+    )]
+    #[case(
+        r#"#[garde(custom(check_name), pattern(r"^[a-z]+$"))] #[garde(email)]"#,
+        FieldConstraints { pattern: Some("^[a-z]+$".to_string()), format: Some("email".to_string()), ..Default::default() }
+    )]
+    #[case(
+        "#[garde(length(equal = 2), inner(url, length(max = 8)))]",
+        FieldConstraints {
+            min_size: Some(2),
+            max_size: Some(2),
+            inner: Some(Box::new(FieldConstraints { max_size: Some(8), format: Some("uri".to_string()), ..Default::default() })),
+            ..Default::default()
+        }
+    )]
+    #[case("#[garde(pattern(NAME_RE), skip)]", FieldConstraints::default())]
+    #[case("#[schema(min_length = 3)]", FieldConstraints::default())]
+    fn test_extract_garde_constraints(#[case] attr_src: &str, #[case] expected: FieldConstraints) {
+        let item: syn::ItemStruct =
+            syn::parse_str(&format!("struct Foo {{ {attr_src} pub x: String }}")).unwrap();
+        let field = item.fields.iter().next().unwrap();
+        assert_eq!(extract_garde_constraints(&field.attrs), expected);
+    }
+
+    #[rstest]
+    #[case(
+        r#"#[schema(content_encoding = "base64", content_media_type = "image/png")]"#,
+        (Some("base64"), Some("image/png"))
+    )]
+    #[case(r#"#[schema(scope = "admin", content_encoding = "base64")]"#, (Some("base64"), None))]
+    #[case(r#"#[schema(content_media_type = "application/pdf")]"#, (None, Some("application/pdf")))]
+    #[case(r#"#[serde(rename = "content_encoding")]"#, (None, None))]
+    fn test_extract_field_content(
+        #[case] attr_src: &str,
+        #[case] expected: (Option<&str>, Option<&str>),
+    ) {
+        let item: syn::ItemStruct =
+            syn::parse_str(&format!("struct Foo {{ {attr_src} pub x: String }}")).unwrap();
+        let field = item.fields.iter().next().unwrap();
+        let (encoding, media_type) = extract_field_content(&field.attrs);
+        assert_eq!((encoding.as_deref(), media_type.as_deref()), expected);
+    }
 }
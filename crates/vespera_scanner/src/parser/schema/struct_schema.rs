@@ -0,0 +1,1914 @@
+//! Struct to JSON Schema conversion for `OpenAPI` generation.
+//!
+//! This module handles the conversion of Rust structs (as parsed by syn)
+//! into OpenAPI-compatible JSON Schema definitions.
+
+use std::{
+    cell::{Cell, RefCell},
+    collections::{HashMap, HashSet},
+};
+
+use syn::{Fields, Type};
+use vespera_core::schema::{Properties, Reference, Schema, SchemaRef, SchemaType};
+
+use super::{
+    enum_schema::parse_enum_to_schema,
+    serde_attrs::{
+        AdditionalProperties, FieldConstraints, FieldSchemaMode, SchemaFlags,
+        extract_additional_properties, extract_default, extract_deny_unknown_fields,
+        extract_doc_comment, extract_field_access, extract_field_constraints,
+        extract_field_content, extract_field_deprecated, extract_field_example,
+        extract_field_format, extract_field_rename, extract_field_schema_mode, extract_field_scope,
+        extract_flatten, extract_garde_constraints, extract_rename_all, extract_schema_flags,
+        extract_schema_ref_override, extract_schema_xml, extract_serde_as, extract_skip,
+        extract_skip_serializing_if, extract_transparent, rename_field, strip_raw_prefix_owned,
+    },
+    type_schema::{integer_schema, is_primitive_type, parse_type_to_schema_ref},
+};
+
+thread_local! {
+    /// Scope currently being generated (`None` = public spec).
+    static ACTIVE_SCOPE: RefCell<Option<String>> = const { RefCell::new(None) };
+
+    /// Components and generic instantiations being expanded inline, so a type never
+    /// inlines itself and recursive types end in a `$ref`.
+    static INLINING: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+
+    /// Whether `required`/`nullable` follow what serde writes (`strict_nullability = true`).
+    static STRICT_NULLABILITY: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Run `f` with `scope` as the active schema scope.
+///
+/// Fields marked `#[schema(scope = "...")]` are only included in struct schemas
+/// while their scope is active; with `None` (the default) they are omitted.
+pub fn with_schema_scope<R>(scope: Option<&str>, f: impl FnOnce() -> R) -> R {
+    let previous = ACTIVE_SCOPE.with(|active| active.replace(scope.map(str::to_string)));
+    let result = f();
+    ACTIVE_SCOPE.with(|active| *active.borrow_mut() = previous);
+    result
+}
+
+/// Run `f` documenting optional fields by how serde writes them.
+///
+/// By default every `Option<T>` field is optional and nullable. When `strict`, a field with
+/// `skip_serializing_if` is optional and not nullable (it is left out, never `null`),
+/// while any other `Option<T>` field is required and nullable (it is written as `null`).
+pub fn with_strict_nullability<R>(strict: bool, f: impl FnOnce() -> R) -> R {
+    let previous = STRICT_NULLABILITY.with(|active| active.replace(strict));
+    let result = f();
+    STRICT_NULLABILITY.with(|active| active.set(previous));
+    result
+}
+
+/// Whether `field` belongs in `required`, dropping `nullable` from its `schema_ref`
+/// when serde never writes it as `null` (see [`with_strict_nullability`]).
+///
+/// By default required is determined solely by nullability (`Option<T>`): serde writes
+/// `#[serde(default)]` fields, so they stay required here. Schemas only requests use
+/// drop them from `required` afterwards (see [`defaulted_fields`]).
+pub(super) fn field_is_required(field: &syn::Field, schema_ref: &mut SchemaRef) -> bool {
+    let is_optional = matches!(
+        &field.ty,
+        Type::Path(type_path)
+            if type_path
+                .path
+                .segments
+                .first()
+                .is_some_and(|s| s.ident == "Option")
+    );
+    if !STRICT_NULLABILITY.with(Cell::get) {
+        return !is_optional;
+    }
+    // Serde leaves out a field whose `skip_serializing_if` holds,
+    // and writes `None` as `null` otherwise
+    let may_be_absent = extract_skip_serializing_if(&field.attrs);
+    if may_be_absent
+        && is_optional
+        && let SchemaRef::Inline(schema) = schema_ref
+    {
+        schema.nullable = None;
+    }
+    !may_be_absent
+}
+
+/// Properties of `struct_item` that serde fills in when a request leaves them out:
+/// `#[serde(default)]` / `#[serde(default = "path")]` fields, or every field under a
+/// struct-level `#[serde(default)]`.
+///
+/// Empty when strict (see [`with_strict_nullability`]), where `required` follows what
+/// serde writes.
+pub fn defaulted_fields(struct_item: &syn::ItemStruct) -> Vec<String> {
+    let Fields::Named(fields_named) = &struct_item.fields else {
+        return Vec::new();
+    };
+    if STRICT_NULLABILITY.with(Cell::get) {
+        return Vec::new();
+    }
+    let rename_all = extract_rename_all(&struct_item.attrs);
+    let container_default = extract_default(&struct_item.attrs).is_some();
+    fields_named
+        .named
+        .iter()
+        .filter(|field| container_default || extract_default(&field.attrs).is_some())
+        .map(|field| {
+            let rust_field_name = field.ident.as_ref().map_or_else(
+                || "unknown".to_string(),
+                |i| strip_raw_prefix_owned(i.to_string()),
+            );
+            extract_field_rename(&field.attrs)
+                .unwrap_or_else(|| rename_field(&rust_field_name, rename_all.as_deref()))
+        })
+        .collect()
+}
+
+/// Whether a field with the given `#[schema(scope)]` belongs in the current schema.
+fn is_field_in_active_scope(field_scope: &str) -> bool {
+    ACTIVE_SCOPE.with(|active| active.borrow().as_deref() == Some(field_scope))
+}
+
+/// Run `f` to expand the type `key` inline, or return `None` when `key` is already
+/// being expanded further up: the type is recursive and the caller should use a `$ref`.
+pub(super) fn expand_inline<R>(key: &str, f: impl FnOnce() -> R) -> Option<R> {
+    if INLINING.with(|active| active.borrow().iter().any(|n| n == key)) {
+        return None;
+    }
+    INLINING.with(|active| active.borrow_mut().push(key.to_string()));
+    let result = f();
+    INLINING.with(|active| active.borrow_mut().pop());
+    Some(result)
+}
+
+/// Schema of the component `name`, built from its stored definition.
+fn component_schema(
+    name: &str,
+    known_schemas: &HashSet<String>,
+    struct_definitions: &HashMap<String, String>,
+) -> Option<Schema> {
+    let definition = struct_definitions.get(name)?;
+    expand_inline(name, || match syn::parse_str::<syn::Item>(definition) {
+        Ok(syn::Item::Struct(item)) => Some(parse_struct_to_schema(
+            &item,
+            known_schemas,
+            struct_definitions,
+        )),
+        Ok(syn::Item::Enum(item)) => Some(parse_enum_to_schema(
+            &item,
+            known_schemas,
+            struct_definitions,
+        )),
+        _ => None,
+    })
+    .flatten()
+}
+
+/// Add the properties and required names of `schema`, following its `allOf` members.
+fn collect_object_properties(
+    schema: &Schema,
+    properties: &mut Properties,
+    required: &mut Vec<String>,
+    known_schemas: &HashSet<String>,
+    struct_definitions: &HashMap<String, String>,
+) -> Option<()> {
+    if schema.properties.is_none() && schema.all_of.is_none() {
+        return (schema.schema_type == Some(SchemaType::Object)).then_some(());
+    }
+    for (name, property) in schema.properties.iter().flatten() {
+        properties.insert(name.clone(), property.clone());
+    }
+    required.extend(schema.required.iter().flatten().cloned());
+    for member in schema.all_of.iter().flatten() {
+        match member {
+            SchemaRef::Inline(member) => collect_object_properties(
+                member,
+                properties,
+                required,
+                known_schemas,
+                struct_definitions,
+            )?,
+            SchemaRef::Ref(reference) => {
+                let name = Reference::schema_name(&reference.ref_path)?;
+                let component = component_schema(name, known_schemas, struct_definitions)?;
+                collect_object_properties(
+                    &component,
+                    properties,
+                    required,
+                    known_schemas,
+                    struct_definitions,
+                )?;
+            }
+        }
+    }
+    Some(())
+}
+
+/// Properties and required names a `#[serde(flatten)]` field with schema `schema_ref`
+/// writes into its parent object.
+///
+/// An `Option<T>` field contributes the properties of `T` with none required, since
+/// serde writes none of them for `None`. `None` when the field is not a struct component.
+pub(super) fn flattened_properties(
+    schema_ref: &SchemaRef,
+    known_schemas: &HashSet<String>,
+    struct_definitions: &HashMap<String, String>,
+) -> Option<(Properties, Vec<String>)> {
+    let (ref_path, optional) = match schema_ref {
+        SchemaRef::Ref(reference) => (reference.ref_path.as_str(), false),
+        // `Option<T>` wraps the `$ref` of `T` in a nullable schema
+        SchemaRef::Inline(schema) => (schema.ref_path.as_deref()?, schema.nullable == Some(true)),
+    };
+    let name = Reference::schema_name(ref_path)?;
+    let component = component_schema(name, known_schemas, struct_definitions)?;
+    let mut properties = Properties::new();
+    let mut required = Vec::new();
+    collect_object_properties(
+        &component,
+        &mut properties,
+        &mut required,
+        known_schemas,
+        struct_definitions,
+    )?;
+    if optional {
+        required.clear();
+    }
+    Some((properties, required))
+}
+
+/// `#[schema(inline)]`: replace `$ref`s to components with the components' schemas.
+///
+/// Follows `Option`, collections and compositions, but leaves `$ref`s inside the
+/// inlined component alone.
+fn inline_component_refs(
+    schema_ref: &mut SchemaRef,
+    known_schemas: &HashSet<String>,
+    struct_definitions: &HashMap<String, String>,
+) {
+    match schema_ref {
+        SchemaRef::Ref(reference) => {
+            if let Some(component) = Reference::schema_name(&reference.ref_path)
+                .and_then(|name| component_schema(name, known_schemas, struct_definitions))
+            {
+                *schema_ref = SchemaRef::Inline(Box::new(component));
+            }
+        }
+        SchemaRef::Inline(schema) => {
+            // `Option<T>` wraps the `$ref` of `T` in a nullable schema
+            if let Some(mut component) = schema
+                .ref_path
+                .as_deref()
+                .and_then(Reference::schema_name)
+                .and_then(|name| component_schema(name, known_schemas, struct_definitions))
+            {
+                component.nullable = schema.nullable.or(component.nullable);
+                component.description = schema.description.take().or(component.description);
+                **schema = component;
+                return;
+            }
+            if let Some(items) = &mut schema.items {
+                inline_component_refs(items, known_schemas, struct_definitions);
+            }
+            for variants in [&mut schema.all_of, &mut schema.any_of, &mut schema.one_of]
+                .into_iter()
+                .flatten()
+            {
+                for variant in variants {
+                    inline_component_refs(variant, known_schemas, struct_definitions);
+                }
+            }
+        }
+    }
+}
+
+/// `#[schema(ref)]`: a `$ref` to the component named after `ty`, keeping the
+/// `Option`/collection wrappers around it. `None` for primitives.
+fn forced_component_ref(ty: &Type) -> Option<SchemaRef> {
+    let Type::Path(type_path) = ty else {
+        return match ty {
+            Type::Reference(reference) => forced_component_ref(&reference.elem),
+            _ => None,
+        };
+    };
+    let segment = type_path.path.segments.last()?;
+    let inner = match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) => args.args.iter().find_map(|arg| match arg {
+            syn::GenericArgument::Type(inner) => Some(inner),
+            _ => None,
+        }),
+        _ => None,
+    };
+    match (segment.ident.to_string().as_str(), inner) {
+        ("Box", Some(inner)) => forced_component_ref(inner),
+        ("Vec", Some(inner)) => Some(SchemaRef::Inline(Box::new(Schema::array(
+            forced_component_ref(inner)?,
+        )))),
+        ("HashSet" | "BTreeSet", Some(inner)) => {
+            let mut schema = Schema::array(forced_component_ref(inner)?);
+            schema.unique_items = Some(true);
+            Some(SchemaRef::Inline(Box::new(schema)))
+        }
+        ("Option", Some(inner)) => Some(match forced_component_ref(inner)? {
+            SchemaRef::Ref(reference) => SchemaRef::Inline(Box::new(Schema {
+                ref_path: Some(reference.ref_path),
+                schema_type: None,
+                nullable: Some(true),
+                ..Schema::new(SchemaType::Object)
+            })),
+            SchemaRef::Inline(mut schema) => {
+                schema.nullable = Some(true);
+                SchemaRef::Inline(schema)
+            }
+        }),
+        _ if is_primitive_type(ty) => None,
+        (name, _) => Some(SchemaRef::Ref(Reference::schema(name))),
+    }
+}
+
+/// Apply `#[schema(format = "...", content_encoding = "...", content_media_type = "...")]`
+/// to the string schema of a field (or to the items of a list of strings).
+///
+/// A serde_with adapter (`#[serde_as(as = "DisplayFromStr")]`) is applied first, so the
+/// schema matches what goes over the wire before the explicit keywords are added.
+pub fn apply_field_content(schema_ref: &mut SchemaRef, attrs: &[syn::Attribute]) {
+    if let Some(adapter) = extract_serde_as(attrs) {
+        apply_serde_as(schema_ref, &adapter);
+    }
+    let (encoding, media_type) = extract_field_content(attrs);
+    let format = extract_field_format(attrs);
+    if encoding.is_none() && media_type.is_none() && format.is_none() {
+        return;
+    }
+    let SchemaRef::Inline(schema) = schema_ref else {
+        return;
+    };
+    let target = if schema.schema_type == Some(SchemaType::Array)
+        && let Some(SchemaRef::Inline(items)) = schema.items.as_deref_mut()
+    {
+        items
+    } else {
+        schema
+    };
+    if format.is_some() {
+        target.format = format;
+    }
+    if encoding.is_some() || media_type.is_some() {
+        target.content_encoding = encoding;
+        target.content_media_type = media_type;
+    }
+}
+
+/// Replace the parts of `schema_ref` a serde_with `adapter` changes on the wire.
+///
+/// `DisplayFromStr` writes a string, `Base64` a base64 string, and the `Duration*` and
+/// `Timestamp*` adapters a number (integer unless the `WithFrac` variants or `f64` are
+/// chosen) or a string. `Option<A>`, list and map adapters apply `A` to the optional
+/// value, items and values; `_`, `Same` and unknown adapters leave the schema as is.
+fn apply_serde_as(schema_ref: &mut SchemaRef, adapter: &Type) {
+    let Type::Path(type_path) = adapter else {
+        if let Type::Array(array) = adapter {
+            apply_serde_as_items(schema_ref, &array.elem);
+        } else if let Type::Slice(slice) = adapter {
+            apply_serde_as_items(schema_ref, &slice.elem);
+        }
+        return;
+    };
+    let Some(segment) = type_path.path.segments.last() else {
+        return;
+    };
+    let args: Vec<&Type> = match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) => args
+            .args
+            .iter()
+            .filter_map(|arg| match arg {
+                syn::GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+    let name = segment.ident.to_string();
+    match (name.as_str(), args.as_slice()) {
+        ("Option" | "Box", [inner]) => apply_serde_as(schema_ref, inner),
+        ("Vec" | "VecDeque" | "HashSet" | "BTreeSet", [inner]) => {
+            apply_serde_as_items(schema_ref, inner);
+        }
+        ("HashMap" | "BTreeMap", [_, value]) => {
+            if let SchemaRef::Inline(schema) = schema_ref
+                && let Some(additional) = &schema.additional_properties
+                && let Ok(mut value_schema) =
+                    serde_json::from_value::<SchemaRef>(additional.clone())
+            {
+                apply_serde_as(&mut value_schema, value);
+                schema.additional_properties = serde_json::to_value(value_schema).ok();
+            }
+        }
+        _ => {
+            if let Some(wire) = serde_as_wire_schema(&name, args.first().copied()) {
+                let (description, nullable) = match schema_ref {
+                    SchemaRef::Inline(schema) => (schema.description.take(), schema.nullable),
+                    SchemaRef::Ref(_) => (None, None),
+                };
+                *schema_ref = SchemaRef::Inline(Box::new(Schema {
+                    description,
+                    nullable,
+                    ..wire
+                }));
+            }
+        }
+    }
+}
+
+/// Apply `adapter` to the items of a list schema.
+fn apply_serde_as_items(schema_ref: &mut SchemaRef, adapter: &Type) {
+    if let SchemaRef::Inline(schema) = schema_ref
+        && let Some(items) = schema.items.as_deref_mut()
+    {
+        apply_serde_as(items, adapter);
+    }
+}
+
+/// Wire schema of a serde_with leaf adapter, `None` for adapters that keep the schema.
+fn serde_as_wire_schema(name: &str, format: Option<&Type>) -> Option<Schema> {
+    let number = |default: &str| {
+        let format =
+            format.map_or_else(|| default.to_string(), |ty| quote::quote!(#ty).to_string());
+        match format.as_str() {
+            "String" => Schema::string(),
+            "f32" | "f64" => Schema {
+                format: Some(if format == "f32" { "float" } else { "double" }.to_string()),
+                ..Schema::number()
+            },
+            integer => integer_schema(integer).unwrap_or_else(Schema::integer),
+        }
+    };
+    match name {
+        "DisplayFromStr" => Some(Schema::string()),
+        "Base64" => Some(Schema {
+            content_encoding: Some("base64".to_string()),
+            ..Schema::string()
+        }),
+        "DurationSeconds"
+        | "DurationMilliSeconds"
+        | "DurationMicroSeconds"
+        | "DurationNanoSeconds" => Some(number("u64")),
+        "TimestampSeconds"
+        | "TimestampMilliSeconds"
+        | "TimestampMicroSeconds"
+        | "TimestampNanoSeconds" => Some(number("i64")),
+        _ if name.ends_with("WithFrac")
+            && (name.starts_with("Duration") || name.starts_with("Timestamp")) =>
+        {
+            Some(number("f64"))
+        }
+        _ => None,
+    }
+}
+
+/// Apply `#[schema(minimum = 1, max_length = 64, pattern = "...", min_items = 1, ...)]`
+/// validation keywords, and those of `#[garde(...)]` rules, to the schema of a field.
+///
+/// `#[schema(...)]` keywords take precedence over garde's. Number and string keywords on a
+/// list apply to its items, `min_items`/`max_items` and garde's `length` to the list itself.
+/// A `$ref` is wrapped in `allOf` so the keywords can sit next to it.
+pub fn apply_field_constraints(schema_ref: &mut SchemaRef, attrs: &[syn::Attribute]) {
+    let garde = extract_garde_constraints(attrs);
+    let constraints = extract_field_constraints(attrs);
+    if garde == FieldConstraints::default() && constraints == FieldConstraints::default() {
+        return;
+    }
+    if let SchemaRef::Ref(reference) = schema_ref {
+        let all_of = vec![SchemaRef::Ref(reference.clone())];
+        *schema_ref = SchemaRef::Inline(Box::new(Schema {
+            all_of: Some(all_of),
+            ..Default::default()
+        }));
+    }
+    let SchemaRef::Inline(schema) = schema_ref else {
+        return;
+    };
+    apply_constraints(schema, garde);
+    apply_constraints(schema, constraints);
+}
+
+/// Apply one set of constraints, replacing the keywords it sets.
+fn apply_constraints(schema: &mut Schema, constraints: FieldConstraints) {
+    let is_array = schema.schema_type == Some(SchemaType::Array);
+    if is_array {
+        schema.min_items = constraints.min_size.or(schema.min_items);
+        schema.max_items = constraints.max_size.or(schema.max_items);
+    }
+    schema.min_items = constraints.min_items.or(schema.min_items);
+    schema.max_items = constraints.max_items.or(schema.max_items);
+    if let Some(inner) = constraints.inner
+        && is_array
+        && let Some(SchemaRef::Inline(items)) = schema.items.as_deref_mut()
+    {
+        apply_constraints(items, *inner);
+    }
+    let target = if is_array && let Some(SchemaRef::Inline(items)) = schema.items.as_deref_mut() {
+        items
+    } else {
+        schema
+    };
+    if !is_array {
+        target.min_length = constraints.min_size.or(target.min_length);
+        target.max_length = constraints.max_size.or(target.max_length);
+    }
+    target.minimum = constraints.minimum.or(target.minimum);
+    target.maximum = constraints.maximum.or(target.maximum);
+    target.multiple_of = constraints.multiple_of.or(target.multiple_of);
+    target.min_length = constraints.min_length.or(target.min_length);
+    target.max_length = constraints.max_length.or(target.max_length);
+    target.pattern = constraints.pattern.or(target.pattern.take());
+    if target.format.is_none() {
+        target.format = constraints.format;
+    }
+}
+
+/// Schema of a flag set.
+///
+/// `#[schema(flags = [...])]` alone describes a list of unique flags; with `bitflags`
+/// (set automatically for `bitflags!` types) it is the `"READ | WRITE"` string format
+/// of the bitflags serde feature, matched by a pattern when the names are known.
+fn flags_schema(flags: SchemaFlags, description: Option<String>) -> Schema {
+    if !flags.bitflags {
+        let item_type = if flags.names.iter().all(serde_json::Value::is_u64) {
+            SchemaType::Integer
+        } else {
+            SchemaType::String
+        };
+        return Schema {
+            description,
+            items: Some(Box::new(SchemaRef::Inline(Box::new(Schema {
+                r#enum: (!flags.names.is_empty()).then_some(flags.names),
+                ..Schema::new(item_type)
+            })))),
+            unique_items: Some(true),
+            ..Schema::new(SchemaType::Array)
+        };
+    }
+
+    let names: Vec<String> = flags
+        .names
+        .iter()
+        .map(|name| {
+            name.as_str()
+                .map_or_else(|| name.to_string(), str::to_string)
+        })
+        .collect();
+    if names.is_empty() {
+        return Schema {
+            description: description.or_else(|| Some("Flag names separated by ` | `.".to_string())),
+            ..Schema::string()
+        };
+    }
+    let listed = names
+        .iter()
+        .map(|name| format!("`{name}`"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let note = format!("Flags separated by ` | `: {listed}.");
+    let flag = format!(
+        "({}|0x[0-9a-fA-F]+)",
+        names
+            .iter()
+            .map(|name| regex_escape(name))
+            .collect::<Vec<_>>()
+            .join("|")
+    );
+    Schema {
+        description: Some(match description {
+            Some(description) => format!("{description}\n\n{note}"),
+            None => note,
+        }),
+        pattern: Some(format!("^({flag}( \\| {flag})*)?$")),
+        ..Schema::string()
+    }
+}
+
+fn regex_escape(value: &str) -> String {
+    value
+        .chars()
+        .flat_map(|c| {
+            let escape = !(c.is_alphanumeric() || c == '_');
+            escape.then_some('\\').into_iter().chain(std::iter::once(c))
+        })
+        .collect()
+}
+
+/// Parses a Rust struct into an `OpenAPI` Schema.
+///
+/// This function extracts:
+/// - Field names and types as properties
+/// - Required fields (non-Option types without defaults)
+/// - Doc comments as descriptions
+/// - Serde attributes (rename, `rename_all`, skip, default)
+///
+/// # Arguments
+/// * `struct_item` - The parsed struct from syn
+/// * `known_schemas` - Map of known schema names for reference resolution
+/// * `struct_definitions` - Map of struct names to their source code (for generics)
+#[allow(clippy::too_many_lines)]
+pub fn parse_struct_to_schema(
+    struct_item: &syn::ItemStruct,
+    known_schemas: &HashSet<String>,
+    struct_definitions: &HashMap<String, String>,
+) -> Schema {
+    let mut properties = Properties::new();
+    let mut required = Vec::with_capacity(8);
+    let mut flattened_refs: Vec<SchemaRef> = Vec::new();
+
+    // Extract struct-level doc comment for schema description
+    let struct_description = extract_doc_comment(&struct_item.attrs);
+
+    if let Some((schema_name, nullable)) = extract_schema_ref_override(&struct_item.attrs) {
+        return Schema {
+            ref_path: Some(Reference::schema(&schema_name).ref_path),
+            nullable: nullable.then_some(true),
+            description: struct_description,
+            ..Default::default()
+        };
+    }
+
+    if let Some(flags) = extract_schema_flags(&struct_item.attrs) {
+        return flags_schema(flags, struct_description);
+    }
+
+    // Transparent single-field wrappers and newtypes (`struct UserId(i64)`, which serde
+    // writes as the inner value) use the inner field schema directly.
+    let transparent = extract_transparent(&struct_item.attrs);
+    let inner_field_ty = match &struct_item.fields {
+        Fields::Named(fields_named) if transparent && fields_named.named.len() == 1 => {
+            fields_named.named.first().map(|field| &field.ty)
+        }
+        Fields::Unnamed(fields_unnamed) if fields_unnamed.unnamed.len() == 1 => {
+            fields_unnamed.unnamed.first().map(|field| &field.ty)
+        }
+        _ => None,
+    };
+
+    if let Some(field_ty) = inner_field_ty {
+        let schema_ref = parse_type_to_schema_ref(field_ty, known_schemas, struct_definitions);
+        return match schema_ref {
+            SchemaRef::Inline(mut schema) => {
+                if schema.description.is_none() {
+                    schema.description = struct_description;
+                }
+                *schema
+            }
+            SchemaRef::Ref(reference) => Schema {
+                description: struct_description,
+                all_of: Some(vec![SchemaRef::Ref(reference)]),
+                ..Default::default()
+            },
+        };
+    }
+
+    // Extract rename_all attribute from struct
+    let rename_all = extract_rename_all(&struct_item.attrs);
+
+    match &struct_item.fields {
+        Fields::Named(fields_named) => {
+            for field in &fields_named.named {
+                // Check if field should be skipped
+                if extract_skip(&field.attrs) {
+                    continue;
+                }
+
+                // Scoped fields only appear in the document generated for that scope
+                if let Some(scope) = extract_field_scope(&field.attrs)
+                    && !is_field_in_active_scope(&scope)
+                {
+                    continue;
+                }
+
+                // Check if field should be flattened
+                if extract_flatten(&field.attrs) {
+                    // Get the schema ref for the flattened field type
+                    let field_type = &field.ty;
+                    let schema_ref =
+                        parse_type_to_schema_ref(field_type, known_schemas, struct_definitions);
+
+                    // `Option<T>`: serde writes T's fields or none of them, so they are
+                    // merged in as optional properties rather than a required `allOf` member
+                    if let SchemaRef::Inline(schema) = &schema_ref
+                        && schema.ref_path.is_some()
+                        && schema.nullable == Some(true)
+                        && let Some((flattened, _)) =
+                            flattened_properties(&schema_ref, known_schemas, struct_definitions)
+                    {
+                        properties.extend(flattened);
+                        continue;
+                    }
+
+                    // Add to flattened refs for allOf composition
+                    flattened_refs.push(schema_ref);
+                    continue;
+                }
+
+                let rust_field_name = field.ident.as_ref().map_or_else(
+                    || "unknown".to_string(),
+                    |i| strip_raw_prefix_owned(i.to_string()),
+                );
+
+                // Check for field-level rename attribute first (takes precedence)
+                let field_name = extract_field_rename(&field.attrs)
+                    .unwrap_or_else(|| rename_field(&rust_field_name, rename_all.as_deref()));
+
+                let field_type = &field.ty;
+
+                let mut schema_ref =
+                    parse_type_to_schema_ref(field_type, known_schemas, struct_definitions);
+
+                // `#[schema(inline)]` / `#[schema(ref)]` override the automatic choice
+                match extract_field_schema_mode(&field.attrs) {
+                    Some(FieldSchemaMode::Inline) => {
+                        inline_component_refs(&mut schema_ref, known_schemas, struct_definitions);
+                    }
+                    Some(FieldSchemaMode::Ref) => {
+                        if let Some(forced) = forced_component_ref(field_type) {
+                            schema_ref = forced;
+                        }
+                    }
+                    None => {}
+                }
+
+                // Extract doc comment from field and set as description
+                if let Some(doc) = extract_doc_comment(&field.attrs) {
+                    match &mut schema_ref {
+                        SchemaRef::Inline(schema) => {
+                            schema.description = Some(doc);
+                        }
+                        SchemaRef::Ref(_) => {
+                            // For $ref schemas, we need to wrap in an allOf to add description
+                            // OpenAPI 3.1 allows siblings to $ref, so we can add description directly
+                            // by converting to inline schema with description + allOf[$ref]
+                            let ref_schema = std::mem::replace(
+                                &mut schema_ref,
+                                SchemaRef::Inline(Box::new(Schema::object())),
+                            );
+                            if let SchemaRef::Ref(reference) = ref_schema {
+                                schema_ref = SchemaRef::Inline(Box::new(Schema {
+                                    description: Some(doc),
+                                    all_of: Some(vec![SchemaRef::Ref(reference)]),
+                                    ..Default::default()
+                                }));
+                            }
+                        }
+                    }
+                }
+
+                apply_field_content(&mut schema_ref, &field.attrs);
+                apply_field_constraints(&mut schema_ref, &field.attrs);
+
+                // `#[schema(xml(...))]` describes the property's XML element/attribute
+                if let Some(xml) = extract_schema_xml(&field.attrs) {
+                    match &mut schema_ref {
+                        SchemaRef::Inline(schema) => schema.xml = Some(xml),
+                        SchemaRef::Ref(reference) => {
+                            schema_ref = SchemaRef::Inline(Box::new(Schema {
+                                xml: Some(xml),
+                                all_of: Some(vec![SchemaRef::Ref(reference.clone())]),
+                                ..Default::default()
+                            }));
+                        }
+                    }
+                }
+
+                // `#[schema(deprecated)]` marks the property `deprecated: true`
+                if extract_field_deprecated(&field.attrs) {
+                    match &mut schema_ref {
+                        SchemaRef::Inline(schema) => schema.deprecated = Some(true),
+                        SchemaRef::Ref(reference) => {
+                            schema_ref = SchemaRef::Inline(Box::new(Schema {
+                                deprecated: Some(true),
+                                all_of: Some(vec![SchemaRef::Ref(reference.clone())]),
+                                ..Default::default()
+                            }));
+                        }
+                    }
+                }
+
+                // `#[schema(read_only)]` / `#[schema(write_only)]` mark server-assigned and
+                // input-only properties
+                let (read_only, write_only) = extract_field_access(&field.attrs);
+                if read_only || write_only {
+                    let read_only = read_only.then_some(true);
+                    let write_only = write_only.then_some(true);
+                    match &mut schema_ref {
+                        SchemaRef::Inline(schema) => {
+                            schema.read_only = read_only;
+                            schema.write_only = write_only;
+                        }
+                        SchemaRef::Ref(reference) => {
+                            schema_ref = SchemaRef::Inline(Box::new(Schema {
+                                read_only,
+                                write_only,
+                                all_of: Some(vec![SchemaRef::Ref(reference.clone())]),
+                                ..Default::default()
+                            }));
+                        }
+                    }
+                }
+
+                // `#[schema(example = ...)]` documents a sample value of the property
+                if let Some(example) = extract_field_example(&field.attrs) {
+                    match &mut schema_ref {
+                        SchemaRef::Inline(schema) => schema.examples = Some(vec![example]),
+                        SchemaRef::Ref(reference) => {
+                            schema_ref = SchemaRef::Inline(Box::new(Schema {
+                                examples: Some(vec![example]),
+                                all_of: Some(vec![SchemaRef::Ref(reference.clone())]),
+                                ..Default::default()
+                            }));
+                        }
+                    }
+                }
+
+                if field_is_required(field, &mut schema_ref) {
+                    required.push(field_name.clone());
+                }
+
+                properties.insert(field_name, schema_ref);
+            }
+        }
+        Fields::Unnamed(fields_unnamed) => {
+            // serde writes tuple structs as arrays, leaving out skipped fields
+            let tuple_item_schemas: Vec<SchemaRef> = fields_unnamed
+                .unnamed
+                .iter()
+                .filter(|field| !extract_skip(&field.attrs))
+                .map(|field| parse_type_to_schema_ref(&field.ty, known_schemas, struct_definitions))
+                .collect();
+            let tuple_len = tuple_item_schemas.len();
+            return Schema {
+                description: struct_description,
+                prefix_items: Some(tuple_item_schemas),
+                min_items: Some(tuple_len),
+                max_items: Some(tuple_len),
+                ..Schema::new(SchemaType::Array)
+            };
+        }
+        Fields::Unit => {
+            // Unit structs have no fields
+        }
+    }
+
+    // If there are flattened fields, use allOf composition
+    if flattened_refs.is_empty() {
+        // No flattened fields - return normal schema
+        Schema {
+            schema_type: Some(SchemaType::Object),
+            description: struct_description,
+            additional_properties: match extract_additional_properties(&struct_item.attrs) {
+                Some(AdditionalProperties::Allowed(allowed)) => {
+                    Some(serde_json::Value::Bool(allowed))
+                }
+                Some(AdditionalProperties::Schema(ty)) => serde_json::to_value(
+                    parse_type_to_schema_ref(&ty, known_schemas, struct_definitions),
+                )
+                .ok(),
+                None => extract_deny_unknown_fields(&struct_item.attrs)
+                    .then_some(serde_json::Value::Bool(false)),
+            },
+            properties: if properties.is_empty() {
+                None
+            } else {
+                Some(properties)
+            },
+            required: if required.is_empty() {
+                None
+            } else {
+                Some(required)
+            },
+            ..Schema::object()
+        }
+    } else {
+        // Create the inline schema for non-flattened properties
+        let inline_schema = Schema {
+            schema_type: Some(SchemaType::Object),
+            properties: if properties.is_empty() {
+                None
+            } else {
+                Some(properties)
+            },
+            required: if required.is_empty() {
+                None
+            } else {
+                Some(required)
+            },
+            ..Schema::object()
+        };
+
+        // Build allOf: [inline_schema, ...flattened_refs]
+        let mut all_of = vec![SchemaRef::Inline(Box::new(inline_schema))];
+        all_of.extend(flattened_refs);
+
+        Schema {
+            description: struct_description,
+            all_of: Some(all_of),
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_struct_to_schema_deprecated_fields() {
+        let struct_item: syn::ItemStruct = syn::parse_str(
+            "struct User { #[schema(deprecated)] nickname: String, #[schema(deprecated)] team: Team, id: i32 }",
+        )
+        .unwrap();
+        let known = HashSet::from(["Team".to_string()]);
+        let schema = parse_struct_to_schema(&struct_item, &known, &HashMap::new());
+        let properties = schema.properties.unwrap();
+        let SchemaRef::Inline(nickname) = &properties["nickname"] else {
+            panic!("expected inline schema");
+        };
+        assert_eq!(nickname.deprecated, Some(true));
+        let SchemaRef::Inline(team) = &properties["team"] else {
+            panic!("expected inline schema");
+        };
+        assert_eq!(team.deprecated, Some(true));
+        assert!(team.all_of.is_some());
+        let SchemaRef::Inline(id) = &properties["id"] else {
+            panic!("expected inline schema");
+        };
+        assert_eq!(id.deprecated, None);
+    }
+
+    #[test]
+    fn test_parse_struct_to_schema_read_write_only_fields() {
+        let struct_item: syn::ItemStruct = syn::parse_str(
+            "struct User { #[schema(read_only)] id: i32, #[schema(write_only)] password: String, #[schema(read_only)] team: Team, name: String }",
+        )
+        .unwrap();
+        let known = HashSet::from(["Team".to_string()]);
+        let schema = parse_struct_to_schema(&struct_item, &known, &HashMap::new());
+        let properties = schema.properties.unwrap();
+        let SchemaRef::Inline(id) = &properties["id"] else {
+            panic!("expected inline schema");
+        };
+        assert_eq!((id.read_only, id.write_only), (Some(true), None));
+        let SchemaRef::Inline(password) = &properties["password"] else {
+            panic!("expected inline schema");
+        };
+        assert_eq!(
+            (password.read_only, password.write_only),
+            (None, Some(true))
+        );
+        let SchemaRef::Inline(team) = &properties["team"] else {
+            panic!("expected inline schema");
+        };
+        assert_eq!(team.read_only, Some(true));
+        assert!(team.all_of.is_some());
+        let SchemaRef::Inline(name) = &properties["name"] else {
+            panic!("expected inline schema");
+        };
+        assert_eq!((name.read_only, name.write_only), (None, None));
+    }
+
+    #[test]
+    fn test_parse_struct_to_schema_field_examples() {
+        let struct_item: syn::ItemStruct = syn::parse_str(
+            r#"struct User { #[schema(example = "alice@example.com")] email: String, #[schema(example = json!({ "id": 1 }))] team: Team, id: i32 }"#,
+        )
+        .unwrap();
+        let known = HashSet::from(["Team".to_string()]);
+        let schema = parse_struct_to_schema(&struct_item, &known, &HashMap::new());
+        let properties = schema.properties.unwrap();
+        let SchemaRef::Inline(email) = &properties["email"] else {
+            panic!("expected inline schema");
+        };
+        assert_eq!(
+            email.examples,
+            Some(vec![serde_json::json!("alice@example.com")])
+        );
+        let SchemaRef::Inline(team) = &properties["team"] else {
+            panic!("expected inline schema");
+        };
+        assert_eq!(team.examples, Some(vec![serde_json::json!({ "id": 1 })]));
+        assert!(team.all_of.is_some());
+        let SchemaRef::Inline(id) = &properties["id"] else {
+            panic!("expected inline schema");
+        };
+        assert_eq!(id.examples, None);
+    }
+
+    #[test]
+    fn test_parse_struct_to_schema_field_constraints() {
+        let struct_item: syn::ItemStruct = syn::parse_str(
+            r#"
+            struct User {
+                #[schema(minimum = 1, maximum = 100)]
+                age: Option<i32>,
+                #[schema(min_length = 3, pattern = "^[a-z]+$")]
+                name: String,
+                #[schema(min_items = 1, max_length = 16)]
+                tags: Vec<String>,
+                #[schema(maximum = 10)]
+                team: Team,
+                size: u128,
+            }
+            "#,
+        )
+        .unwrap();
+        let known = HashSet::from(["Team".to_string()]);
+        let schema = parse_struct_to_schema(&struct_item, &known, &HashMap::new());
+        let value = serde_json::to_value(schema.properties.unwrap()).unwrap();
+
+        assert_eq!(value["age"]["minimum"], 1);
+        assert_eq!(value["age"]["maximum"], 100);
+        assert_eq!(value["name"]["minLength"], 3);
+        assert_eq!(value["name"]["pattern"], "^[a-z]+$");
+        assert_eq!(value["tags"]["minItems"], 1);
+        assert_eq!(value["tags"]["items"]["maxLength"], 16);
+        assert_eq!(value["team"]["maximum"], 10);
+        assert_eq!(
+            value["team"]["allOf"][0]["$ref"],
+            "#/components/schemas/Team"
+        );
+        assert_eq!(value["size"]["minimum"], 0);
+        assert!(value["size"].get("maximum").is_none());
+    }
+
+    #[test]
+    fn test_parse_struct_to_schema_garde_constraints() {
+        let struct_item: syn::ItemStruct = syn::parse_str(
+            r#"
+            struct User {
+                #[garde(length(min = 3, max = 25), pattern("^[a-z]+$"))]
+                name: String,
+                #[garde(email)]
+                #[schema(max_length = 64)]
+                email: Option<String>,
+                #[garde(range(min = 18, max = 150))]
+                #[schema(maximum = 130)]
+                age: u8,
+                #[garde(length(max = 5), inner(length(min = 1)))]
+                tags: Vec<String>,
+                #[garde(dive)]
+                team: Team,
+            }
+            "#,
+        )
+        .unwrap();
+        let known = HashSet::from(["Team".to_string()]);
+        let schema = parse_struct_to_schema(&struct_item, &known, &HashMap::new());
+        let value = serde_json::to_value(schema.properties.unwrap()).unwrap();
+
+        assert_eq!(value["name"]["minLength"], 3);
+        assert_eq!(value["name"]["maxLength"], 25);
+        assert_eq!(value["name"]["pattern"], "^[a-z]+$");
+        assert_eq!(value["email"]["format"], "email");
+        assert_eq!(value["email"]["maxLength"], 64);
+        assert_eq!(value["age"]["minimum"], 18);
+        assert_eq!(value["age"]["maximum"], 130);
+        assert_eq!(value["tags"]["maxItems"], 5);
+        assert!(value["tags"].get("maxLength").is_none());
+        assert_eq!(value["tags"]["items"]["minLength"], 1);
+        assert_eq!(value["team"]["$ref"], "#/components/schemas/Team");
+    }
+
+    #[rstest]
+    #[case("#[serde(deny_unknown_fields)]", Some(serde_json::Value::Bool(false)))]
+    #[case("", None)]
+    #[case(
+        "#[schema(additional_properties = false)]",
+        Some(serde_json::Value::Bool(false))
+    )]
+    #[case(
+        "#[serde(deny_unknown_fields)] #[schema(additional_properties = true)]",
+        Some(serde_json::Value::Bool(true))
+    )]
+    #[case(
+        "#[schema(additional_properties = String)]",
+        Some(serde_json::json!({ "type": "string" }))
+    )]
+    #[case(
+        "#[schema(additional_properties = Team)]",
+        Some(serde_json::json!({ "$ref": "#/components/schemas/Team" }))
+    )]
+    fn test_parse_struct_to_schema_deny_unknown_fields(
+        #[case] attr_src: &str,
+        #[case] expected: Option<serde_json::Value>,
+    ) {
+        let struct_item: syn::ItemStruct =
+            syn::parse_str(&format!("{attr_src} struct User {{ id: i32 }}")).unwrap();
+        let known = HashSet::from(["Team".to_string()]);
+        let schema = parse_struct_to_schema(&struct_item, &known, &HashMap::new());
+        assert_eq!(schema.additional_properties, expected);
+    }
+
+    #[test]
+    fn test_parse_struct_to_schema_field_content_encoding() {
+        let struct_item: syn::ItemStruct = syn::parse_str(
+            r#"
+            struct Upload {
+                #[schema(content_encoding = "base64", content_media_type = "image/png")]
+                avatar: Option<String>,
+                #[schema(content_encoding = "base64")]
+                attachments: Vec<String>,
+            }
+        "#,
+        )
+        .unwrap();
+        let schema = parse_struct_to_schema(&struct_item, &HashSet::new(), &HashMap::new());
+        let value = serde_json::to_value(&schema).unwrap();
+        assert_eq!(value["properties"]["avatar"]["contentEncoding"], "base64");
+        assert_eq!(
+            value["properties"]["avatar"]["contentMediaType"],
+            "image/png"
+        );
+        assert_eq!(
+            value["properties"]["attachments"]["items"]["contentEncoding"],
+            "base64"
+        );
+        assert!(
+            value["properties"]["attachments"]
+                .get("contentEncoding")
+                .is_none()
+        );
+    }
+
+    #[rstest]
+    #[case(r#"#[serde_as(as = "DisplayFromStr")] id: u64"#, serde_json::json!({ "type": "string" }))]
+    #[case(
+        r#"#[serde_as(as = "Option<DisplayFromStr>")] id: Option<u64>"#,
+        serde_json::json!({ "type": "string", "nullable": true })
+    )]
+    #[case(
+        r#"#[serde_as(as = "Vec<DisplayFromStr>")] ids: Vec<u64>"#,
+        serde_json::json!({ "type": "array", "items": { "type": "string" } })
+    )]
+    #[case(
+        r#"#[serde_as(as = "HashMap<_, DisplayFromStr>")] counts: HashMap<String, u64>"#,
+        serde_json::json!({
+            "type": "object",
+            "properties": {},
+            "required": [],
+            "additionalProperties": { "type": "string" }
+        })
+    )]
+    #[case(
+        r#"#[serde_as(as = "serde_with::base64::Base64")] data: Vec<u8>"#,
+        serde_json::json!({ "type": "string", "contentEncoding": "base64" })
+    )]
+    #[case(
+        r#"#[serde_as(as = "DurationSeconds<u64>")] ttl: std::time::Duration"#,
+        serde_json::json!({ "type": "integer", "format": "uint64", "minimum": 0 })
+    )]
+    #[case(
+        r#"#[serde_as(as = "DurationSeconds<f64>")] ttl: Duration"#,
+        serde_json::json!({ "type": "number", "format": "double" })
+    )]
+    #[case(
+        r#"#[serde_as(as = "DurationSeconds<String>")] ttl: Duration"#,
+        serde_json::json!({ "type": "string" })
+    )]
+    #[case(
+        r#"#[serde_as(as = "DurationSecondsWithFrac")] ttl: Duration"#,
+        serde_json::json!({ "type": "number", "format": "double" })
+    )]
+    #[case(
+        r#"#[serde_as(as = "TimestampSeconds")] at: SystemTime"#,
+        serde_json::json!({ "type": "integer", "format": "int64" })
+    )]
+    // What `#[serde_as]` rewrites the attribute to before other derives see it
+    #[case(
+        r#"#[serde(with = ":: serde_with :: As :: < DisplayFromStr >")] id: i64"#,
+        serde_json::json!({ "type": "string" })
+    )]
+    // Explicit keywords still apply on top
+    #[case(
+        r#"/// Owner ID
+        #[serde_as(as = "DisplayFromStr")] #[schema(format = "int64")] id: i64"#,
+        serde_json::json!({ "type": "string", "format": "int64", "description": "Owner ID" })
+    )]
+    #[case(r#"#[serde_as(as = "_")] id: u64"#, serde_json::json!({ "type": "integer", "format": "uint64", "minimum": 0 }))]
+    fn test_parse_struct_to_schema_serde_as(
+        #[case] field: &str,
+        #[case] expected: serde_json::Value,
+    ) {
+        let struct_item: syn::ItemStruct =
+            syn::parse_str(&format!("struct Wire {{ {field} }}")).unwrap();
+        let schema = parse_struct_to_schema(&struct_item, &HashSet::new(), &HashMap::new());
+        let value = serde_json::to_value(&schema).unwrap();
+        let property = value["properties"]
+            .as_object()
+            .unwrap()
+            .values()
+            .next()
+            .unwrap();
+        assert_eq!(property, &expected);
+    }
+
+    #[test]
+    fn test_parse_struct_to_schema_field_format() {
+        let struct_item: syn::ItemStruct = syn::parse_str(
+            r#"
+            struct Account {
+                #[schema(format = "ulid")]
+                id: String,
+                #[schema(format = "iban")]
+                iban: Option<String>,
+                #[schema(format = "ulid")]
+                related: Vec<String>,
+            }
+        "#,
+        )
+        .unwrap();
+        let schema = parse_struct_to_schema(&struct_item, &HashSet::new(), &HashMap::new());
+        let value = serde_json::to_value(&schema).unwrap();
+        assert_eq!(value["properties"]["id"]["format"], "ulid");
+        assert_eq!(value["properties"]["iban"]["format"], "iban");
+        assert_eq!(value["properties"]["related"]["items"]["format"], "ulid");
+        assert!(value["properties"]["related"].get("format").is_none());
+    }
+
+    #[test]
+    fn test_parse_struct_to_schema_field_xml() {
+        let struct_item: syn::ItemStruct = syn::parse_str(
+            r#"
+            struct Pet {
+                #[schema(xml(attribute))]
+                id: i32,
+                #[schema(xml(name = "tag", wrapped))]
+                tags: Vec<String>,
+                #[schema(xml(name = "owner"))]
+                owner: User,
+            }
+        "#,
+        )
+        .unwrap();
+        let known_schemas = HashSet::from(["User".to_string()]);
+        let schema = parse_struct_to_schema(&struct_item, &known_schemas, &HashMap::new());
+        let value = serde_json::to_value(&schema).unwrap();
+        assert_eq!(
+            value["properties"]["id"]["xml"],
+            serde_json::json!({ "attribute": true })
+        );
+        assert_eq!(
+            value["properties"]["tags"]["xml"],
+            serde_json::json!({ "name": "tag", "wrapped": true })
+        );
+        assert_eq!(
+            value["properties"]["owner"],
+            serde_json::json!({
+                "xml": { "name": "owner" },
+                "allOf": [{ "$ref": "#/components/schemas/User" }]
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_struct_to_schema_required_optional() {
+        let struct_item: syn::ItemStruct = syn::parse_str(
+            r"
+            struct User {
+                id: i32,
+                name: Option<String>,
+            }
+        ",
+        )
+        .unwrap();
+        let schema = parse_struct_to_schema(&struct_item, &HashSet::new(), &HashMap::new());
+        let props = schema.properties.as_ref().unwrap();
+        assert!(props.contains_key("id"));
+        assert!(props.contains_key("name"));
+        assert!(
+            schema
+                .required
+                .as_ref()
+                .unwrap()
+                .contains(&"id".to_string())
+        );
+        assert!(
+            !schema
+                .required
+                .as_ref()
+                .unwrap()
+                .contains(&"name".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_struct_to_schema_rename_all_and_field_rename() {
+        let struct_item: syn::ItemStruct = syn::parse_str(
+            r#"
+            #[serde(rename_all = "camelCase")]
+            struct Profile {
+                #[serde(rename = "id")]
+                user_id: i32,
+                display_name: Option<String>,
+            }
+        "#,
+        )
+        .unwrap();
+
+        let schema = parse_struct_to_schema(&struct_item, &HashSet::new(), &HashMap::new());
+        let props = schema.properties.as_ref().expect("props missing");
+        assert!(props.contains_key("id")); // field-level rename wins
+        assert!(props.contains_key("displayName")); // rename_all applied
+        let required = schema.required.as_ref().expect("required missing");
+        assert!(required.contains(&"id".to_string()));
+        assert!(!required.contains(&"displayName".to_string())); // Option makes it optional
+    }
+
+    #[rstest]
+    #[case("struct Wrapper(i32);")]
+    #[case("struct Empty;")]
+    fn test_parse_struct_to_schema_tuple_and_unit_structs(#[case] struct_src: &str) {
+        let struct_item: syn::ItemStruct = syn::parse_str(struct_src).unwrap();
+        let schema = parse_struct_to_schema(&struct_item, &HashSet::new(), &HashMap::new());
+        assert!(schema.properties.is_none());
+        assert!(schema.required.is_none());
+    }
+
+    #[test]
+    fn test_parse_struct_to_schema_serde_transparent_named_wrapper_uses_inner_schema() {
+        let struct_item: syn::ItemStruct = syn::parse_str(
+            r"
+            #[serde(transparent)]
+            struct Wrapper {
+                value: Box<String>,
+            }
+        ",
+        )
+        .unwrap();
+
+        let schema = parse_struct_to_schema(&struct_item, &HashSet::new(), &HashMap::new());
+        assert_eq!(schema.schema_type, Some(SchemaType::String));
+        assert!(schema.properties.is_none());
+    }
+
+    #[rstest]
+    #[case(
+        r#"#[schema(flags = ["READ", "WRITE"])] struct Perms(u8);"#,
+        serde_json::json!({
+            "type": "array",
+            "items": { "type": "string", "enum": ["READ", "WRITE"] },
+            "uniqueItems": true
+        })
+    )]
+    #[case(
+        r"#[schema(flags = [1, 2, 4])] struct Perms(u8);",
+        serde_json::json!({
+            "type": "array",
+            "items": { "type": "integer", "enum": [1, 2, 4] },
+            "uniqueItems": true
+        })
+    )]
+    #[case(
+        r"#[schema(bitflags)] struct Perms;",
+        serde_json::json!({ "type": "string", "description": "Flag names separated by ` | `." })
+    )]
+    #[case(
+        r#"#[schema(bitflags, flags = ["A.B"])] struct Perms;"#,
+        serde_json::json!({
+            "type": "string",
+            "description": "Flags separated by ` | `: `A.B`.",
+            "pattern": "^((A\\.B|0x[0-9a-fA-F]+)( \\| (A\\.B|0x[0-9a-fA-F]+))*)?$"
+        })
+    )]
+    fn test_parse_struct_to_schema_flags(
+        #[case] source: &str,
+        #[case] expected: serde_json::Value,
+    ) {
+        let struct_item: syn::ItemStruct = syn::parse_str(source).unwrap();
+        let schema = parse_struct_to_schema(&struct_item, &HashSet::new(), &HashMap::new());
+        assert_eq!(serde_json::to_value(&schema).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_parse_struct_to_schema_schema_ref_override() {
+        let struct_item: syn::ItemStruct = syn::parse_str(
+            r#"
+            #[schema(ref = "UserSchema", nullable)]
+            struct Wrapper {
+                value: Option<String>,
+            }
+        "#,
+        )
+        .unwrap();
+
+        let schema = parse_struct_to_schema(&struct_item, &HashSet::new(), &HashMap::new());
+        assert_eq!(
+            schema.ref_path.as_deref(),
+            Some("#/components/schemas/UserSchema")
+        );
+        assert_eq!(schema.nullable, Some(true));
+    }
+
+    // Test struct with skip field
+    #[test]
+    fn test_parse_struct_to_schema_with_skip_field() {
+        let struct_item: syn::ItemStruct = syn::parse_str(
+            r"
+            struct User {
+                id: i32,
+                #[serde(skip)]
+                internal_data: String,
+                name: String,
+            }
+        ",
+        )
+        .unwrap();
+        let schema = parse_struct_to_schema(&struct_item, &HashSet::new(), &HashMap::new());
+        let props = schema.properties.as_ref().unwrap();
+        assert!(props.contains_key("id"));
+        assert!(props.contains_key("name"));
+        assert!(!props.contains_key("internal_data")); // Should be skipped
+    }
+
+    // Test struct with default and skip_serializing_if
+    // Required is determined solely by nullability (Option<T>), not by defaults.
+    #[test]
+    fn test_parse_struct_to_schema_with_default_fields() {
+        let struct_item: syn::ItemStruct = syn::parse_str(
+            r#"
+            struct Config {
+                required_field: i32,
+                #[serde(default)]
+                with_default: String,
+                #[serde(skip_serializing_if = "Option::is_none")]
+                maybe_skip: Option<i32>,
+            }
+        "#,
+        )
+        .unwrap();
+        let schema = parse_struct_to_schema(&struct_item, &HashSet::new(), &HashMap::new());
+        let props = schema.properties.as_ref().unwrap();
+        assert!(props.contains_key("required_field"));
+        assert!(props.contains_key("with_default"));
+        assert!(props.contains_key("maybe_skip"));
+
+        let required = schema.required.as_ref().unwrap();
+        assert!(required.contains(&"required_field".to_string()));
+        // Non-nullable fields are always required, even with #[serde(default)]
+        assert!(required.contains(&"with_default".to_string()));
+        // Option<T> fields are not required (nullable)
+        assert!(!required.contains(&"maybe_skip".to_string()));
+    }
+
+    #[rstest]
+    #[case("", false, &["limit", "pageSize"])]
+    #[case("#[serde(default)]", false, &["q", "limit", "pageSize"])]
+    #[case("#[serde(default)]", true, &[])]
+    fn test_defaulted_fields(
+        #[case] container_attrs: &str,
+        #[case] strict: bool,
+        #[case] expected: &[&str],
+    ) {
+        let struct_item: syn::ItemStruct = syn::parse_str(&format!(
+            r#"
+            #[serde(rename_all = "camelCase")]
+            {container_attrs}
+            struct ListQuery {{
+                q: String,
+                #[serde(default)]
+                limit: u32,
+                #[serde(default = "default_page_size")]
+                page_size: u32,
+            }}
+        "#
+        ))
+        .unwrap();
+        let fields = with_strict_nullability(strict, || defaulted_fields(&struct_item));
+        assert_eq!(fields, expected);
+    }
+
+    #[rstest]
+    #[case(false, &["id", "tags"], &["nickname", "note", "manager"])]
+    #[case(true, &["id", "note"], &["note"])]
+    fn test_parse_struct_to_schema_strict_nullability(
+        #[case] strict: bool,
+        #[case] required: &[&str],
+        #[case] nullable: &[&str],
+    ) {
+        let struct_item: syn::ItemStruct = syn::parse_str(
+            r#"
+            struct Profile {
+                id: i32,
+                #[serde(skip_serializing_if = "Option::is_none")]
+                nickname: Option<String>,
+                note: Option<String>,
+                #[serde(skip_serializing_if = "Option::is_none")]
+                manager: Option<User>,
+                #[serde(skip_serializing_if = "Vec::is_empty")]
+                tags: Vec<String>,
+            }
+        "#,
+        )
+        .unwrap();
+        let known_schemas = HashSet::from(["User".to_string()]);
+        let schema = with_strict_nullability(strict, || {
+            parse_struct_to_schema(&struct_item, &known_schemas, &HashMap::new())
+        });
+        assert_eq!(schema.required.as_deref().unwrap(), required);
+        let value = serde_json::to_value(schema.properties.as_ref().unwrap()).unwrap();
+        for name in ["nickname", "note", "manager", "tags"] {
+            assert_eq!(
+                value[name]["nullable"] == true,
+                nullable.contains(&name),
+                "{name}: {}",
+                value[name]
+            );
+        }
+        assert_eq!(value["manager"]["$ref"], "#/components/schemas/User");
+    }
+
+    // Tests for struct with doc comments
+    #[test]
+    fn test_parse_struct_to_schema_with_description() {
+        let struct_src = r"
+            /// User struct description
+            struct User {
+                /// User ID
+                id: i32,
+                /// User name
+                name: String,
+            }
+        ";
+        let struct_item: syn::ItemStruct = syn::parse_str(struct_src).unwrap();
+        let schema = parse_struct_to_schema(&struct_item, &HashSet::new(), &HashMap::new());
+        assert_eq!(
+            schema.description,
+            Some("User struct description".to_string())
+        );
+        // Check field descriptions
+        let props = schema.properties.unwrap();
+        if let SchemaRef::Inline(id_schema) = props.get("id").unwrap() {
+            assert_eq!(id_schema.description, Some("User ID".to_string()));
+        }
+        if let SchemaRef::Inline(name_schema) = props.get("name").unwrap() {
+            assert_eq!(name_schema.description, Some("User name".to_string()));
+        }
+    }
+
+    #[test]
+    fn test_parse_struct_to_schema_field_with_ref_and_description() {
+        let struct_src = r"
+            struct Container {
+                /// The user reference
+                user: User,
+            }
+        ";
+        let struct_item: syn::ItemStruct = syn::parse_str(struct_src).unwrap();
+        let mut struct_defs = HashMap::new();
+        struct_defs.insert("User".to_string(), "struct User { id: i32 }".to_string());
+        let mut known = HashSet::new();
+        known.insert("User".to_string());
+        let schema = parse_struct_to_schema(&struct_item, &known, &struct_defs);
+        let props = schema.properties.unwrap();
+        // Field with $ref and description should use allOf
+        if let SchemaRef::Inline(user_schema) = props.get("user").unwrap() {
+            assert_eq!(
+                user_schema.description,
+                Some("The user reference".to_string())
+            );
+            assert!(user_schema.all_of.is_some());
+        }
+    }
+
+    #[test]
+    fn test_parse_struct_to_schema_description_strips_slash_prefix() {
+        // When doc attributes have "/ " prefix (without leading space), descriptions should be clean.
+        // This can happen in certain TokenStream roundtrip scenarios.
+        let struct_item: syn::ItemStruct = syn::parse_str(
+            r#"
+            #[doc = "/ Struct description"]
+            struct Admin {
+                #[doc = "/ Field description"]
+                id: i32,
+            }
+        "#,
+        )
+        .unwrap();
+        let schema = parse_struct_to_schema(&struct_item, &HashSet::new(), &HashMap::new());
+        assert_eq!(schema.description, Some("Struct description".to_string()));
+        let props = schema.properties.unwrap();
+        if let SchemaRef::Inline(id_schema) = props.get("id").unwrap() {
+            assert_eq!(id_schema.description, Some("Field description".to_string()));
+        }
+    }
+
+    #[test]
+    fn test_parse_struct_to_schema_with_flatten() {
+        let struct_item: syn::ItemStruct = syn::parse_str(
+            r"
+            struct UserListRequest {
+                filter: String,
+                #[serde(flatten)]
+                pagination: Pagination,
+            }
+        ",
+        )
+        .unwrap();
+
+        let mut struct_defs = HashMap::new();
+        struct_defs.insert(
+            "Pagination".to_string(),
+            "struct Pagination { page: i32 }".to_string(),
+        );
+        let mut known = HashSet::new();
+        known.insert("Pagination".to_string());
+
+        let schema = parse_struct_to_schema(&struct_item, &known, &struct_defs);
+
+        // Should have allOf
+        assert!(
+            schema.all_of.is_some(),
+            "Schema should have allOf for flatten"
+        );
+        let all_of = schema.all_of.as_ref().unwrap();
+        assert_eq!(all_of.len(), 2, "allOf should have 2 elements");
+
+        // First element should be the object with non-flattened properties
+        if let SchemaRef::Inline(obj_schema) = &all_of[0] {
+            let props = obj_schema.properties.as_ref().unwrap();
+            assert!(props.contains_key("filter"), "Should have filter property");
+            assert!(
+                !props.contains_key("pagination"),
+                "Should NOT have pagination property"
+            );
+        } else {
+            panic!("First allOf element should be inline schema");
+        }
+
+        // Second element should be $ref to Pagination
+        if let SchemaRef::Ref(reference) = &all_of[1] {
+            assert_eq!(reference.ref_path, "#/components/schemas/Pagination");
+        } else {
+            panic!("Second allOf element should be $ref");
+        }
+    }
+
+    #[test]
+    fn test_parse_struct_to_schema_with_multiple_flatten() {
+        let struct_item: syn::ItemStruct = syn::parse_str(
+            r"
+            struct Combined {
+                name: String,
+                #[serde(flatten)]
+                pagination: Pagination,
+                #[serde(flatten)]
+                metadata: Metadata,
+            }
+        ",
+        )
+        .unwrap();
+
+        let mut struct_defs = HashMap::new();
+        struct_defs.insert("Pagination".to_string(), "struct Pagination {}".to_string());
+        struct_defs.insert("Metadata".to_string(), "struct Metadata {}".to_string());
+        let mut known = HashSet::new();
+        known.insert("Pagination".to_string());
+        known.insert("Metadata".to_string());
+
+        let schema = parse_struct_to_schema(&struct_item, &known, &struct_defs);
+
+        assert!(schema.all_of.is_some());
+        let all_of = schema.all_of.as_ref().unwrap();
+        assert_eq!(
+            all_of.len(),
+            3,
+            "allOf should have 3 elements (1 inline + 2 refs)"
+        );
+    }
+
+    #[test]
+    fn test_parse_struct_to_schema_with_optional_flatten() {
+        let struct_item: syn::ItemStruct = syn::parse_str(
+            r"
+            struct Search {
+                query: String,
+                #[serde(flatten)]
+                pagination: Option<Pagination>,
+            }
+        ",
+        )
+        .unwrap();
+        let struct_defs = HashMap::from([(
+            "Pagination".to_string(),
+            "struct Pagination { page: i32, per_page: Option<i32> }".to_string(),
+        )]);
+        let known = HashSet::from(["Pagination".to_string()]);
+
+        let schema = parse_struct_to_schema(&struct_item, &known, &struct_defs);
+
+        assert!(schema.all_of.is_none());
+        let properties = schema.properties.unwrap();
+        assert_eq!(
+            properties.keys().collect::<Vec<_>>(),
+            ["query", "page", "per_page"]
+        );
+        assert_eq!(schema.required, Some(vec!["query".to_string()]));
+    }
+
+    #[test]
+    fn test_parse_struct_to_schema_no_flatten() {
+        // Existing struct without flatten should NOT use allOf
+        let struct_item: syn::ItemStruct = syn::parse_str(
+            r"
+            struct Simple {
+                name: String,
+                age: i32,
+            }
+        ",
+        )
+        .unwrap();
+
+        let schema = parse_struct_to_schema(&struct_item, &HashSet::new(), &HashMap::new());
+        assert!(
+            schema.all_of.is_none(),
+            "Simple struct should not have allOf"
+        );
+        assert!(schema.properties.is_some());
+    }
+
+    #[test]
+    fn test_parse_struct_to_schema_transparent_tuple_wrapper_uses_ref_schema() {
+        let struct_item: syn::ItemStruct = syn::parse_str(
+            r"
+            #[serde(transparent)]
+            struct Wrapper(User);
+        ",
+        )
+        .unwrap();
+
+        let mut struct_defs = HashMap::new();
+        struct_defs.insert("User".to_string(), "struct User { id: i32 }".to_string());
+        let mut known = HashSet::new();
+        known.insert("User".to_string());
+
+        let schema = parse_struct_to_schema(&struct_item, &known, &struct_defs);
+        assert!(schema.all_of.is_some());
+        let all_of = schema.all_of.unwrap();
+        assert_eq!(all_of.len(), 1);
+        match &all_of[0] {
+            SchemaRef::Ref(reference) => {
+                assert_eq!(reference.ref_path, "#/components/schemas/User");
+            }
+            SchemaRef::Inline(_) => {
+                panic!("expected $ref wrapper for transparent tuple known schema")
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_struct_to_schema_transparent_multi_field_tuple_is_array() {
+        let struct_item: syn::ItemStruct = syn::parse_str(
+            r"
+            #[serde(transparent)]
+            struct Wrapper(String, String);
+        ",
+        )
+        .unwrap();
+
+        let schema = parse_struct_to_schema(&struct_item, &HashSet::new(), &HashMap::new());
+        assert_eq!(schema.schema_type, Some(SchemaType::Array));
+        assert_eq!(schema.prefix_items.map(|items| items.len()), Some(2));
+        assert!(schema.properties.is_none());
+    }
+
+    #[rstest]
+    #[case("struct UserId(i64);", SchemaType::Integer)]
+    #[case("/// A user name\nstruct Name(pub String);", SchemaType::String)]
+    fn test_parse_struct_to_schema_newtype_uses_inner_schema(
+        #[case] src: &str,
+        #[case] expected: SchemaType,
+    ) {
+        let struct_item: syn::ItemStruct = syn::parse_str(src).unwrap();
+        let schema = parse_struct_to_schema(&struct_item, &HashSet::new(), &HashMap::new());
+        assert_eq!(schema.schema_type, Some(expected));
+        assert!(schema.properties.is_none());
+    }
+
+    #[test]
+    fn test_parse_struct_to_schema_tuple_struct_prefix_items() {
+        let struct_item: syn::ItemStruct = syn::parse_str(
+            r"
+            /// A point
+            struct Point(f64, f64, #[serde(skip)] Cache, Label);
+        ",
+        )
+        .unwrap();
+        let known = HashSet::from(["Label".to_string()]);
+
+        let schema = parse_struct_to_schema(&struct_item, &known, &HashMap::new());
+        assert_eq!(schema.schema_type, Some(SchemaType::Array));
+        assert_eq!(schema.description.as_deref(), Some("A point"));
+        assert_eq!((schema.min_items, schema.max_items), (Some(3), Some(3)));
+        let items = schema.prefix_items.unwrap();
+        assert!(
+            matches!(&items[0], SchemaRef::Inline(s) if s.schema_type == Some(SchemaType::Number))
+        );
+        assert!(
+            matches!(&items[2], SchemaRef::Ref(r) if r.ref_path == "#/components/schemas/Label")
+        );
+    }
+
+    #[test]
+    fn test_parse_struct_to_schema_scoped_fields() {
+        let struct_item: syn::ItemStruct = syn::parse_str(
+            r#"
+            struct User {
+                id: i32,
+                #[schema(scope = "admin")]
+                salary: i64,
+                #[schema(scope = "internal")]
+                notes: String,
+            }
+        "#,
+        )
+        .unwrap();
+
+        let public = parse_struct_to_schema(&struct_item, &HashSet::new(), &HashMap::new());
+        let props = public.properties.as_ref().unwrap();
+        assert!(props.contains_key("id"));
+        assert!(!props.contains_key("salary"));
+        assert!(!props.contains_key("notes"));
+        assert_eq!(public.required, Some(vec!["id".to_string()]));
+
+        let admin = with_schema_scope(Some("admin"), || {
+            parse_struct_to_schema(&struct_item, &HashSet::new(), &HashMap::new())
+        });
+        let props = admin.properties.as_ref().unwrap();
+        assert!(props.contains_key("salary"));
+        assert!(!props.contains_key("notes"));
+
+        // Scope is restored after the closure
+        let after = parse_struct_to_schema(&struct_item, &HashSet::new(), &HashMap::new());
+        assert!(!after.properties.unwrap().contains_key("salary"));
+    }
+
+    #[test]
+    fn test_parse_struct_to_schema_field_inline_and_ref() {
+        let known_schemas = HashSet::from([
+            "Address".to_string(),
+            "Page".to_string(),
+            "Node".to_string(),
+        ]);
+        let struct_definitions = HashMap::from([
+            (
+                "Address".to_string(),
+                "struct Address { city: String }".to_string(),
+            ),
+            (
+                "Page".to_string(),
+                "struct Page<T> { items: Vec<T> }".to_string(),
+            ),
+            (
+                "Node".to_string(),
+                "struct Node { #[schema(inline)] next: Option<Box<Node>> }".to_string(),
+            ),
+        ]);
+        let struct_item: syn::ItemStruct = syn::parse_str(
+            r"
+            struct User {
+                #[schema(inline)]
+                home: Address,
+                #[schema(inline)]
+                previous: Option<Address>,
+                #[schema(inline)]
+                others: Vec<Address>,
+                work: Address,
+                #[schema(ref)]
+                friends: Option<Page<User>>,
+                #[schema(ref)]
+                age: u32,
+                #[schema(inline)]
+                node: Node,
+            }
+        ",
+        )
+        .unwrap();
+
+        let schema = parse_struct_to_schema(&struct_item, &known_schemas, &struct_definitions);
+        let props = serde_json::to_value(schema.properties.unwrap()).unwrap();
+        let address = serde_json::json!({
+            "type": "object",
+            "properties": { "city": { "type": "string" } },
+            "required": ["city"],
+        });
+
+        assert_eq!(props["home"], address);
+        assert_eq!(props["previous"]["nullable"], true);
+        assert_eq!(props["previous"]["properties"], address["properties"]);
+        assert_eq!(props["others"]["items"], address);
+        assert_eq!(props["work"]["$ref"], "#/components/schemas/Address");
+        assert_eq!(props["friends"]["$ref"], "#/components/schemas/Page");
+        assert_eq!(props["friends"]["nullable"], true);
+        assert_eq!(props["age"]["type"], "integer");
+        // A type never inlines itself: the recursive field stays a `$ref`
+        assert_eq!(
+            props["node"]["properties"]["next"]["$ref"],
+            "#/components/schemas/Node"
+        );
+    }
+}
@@ -4,10 +4,11 @@
 //! into OpenAPI-compatible JSON Schema references and inline schemas.
 
 use std::{
-    cell::Cell,
-    collections::{HashMap, HashSet},
+    cell::{Cell, RefCell},
+    collections::{BTreeSet, HashMap, HashSet},
 };
 
+use quote::ToTokens;
 use syn::Type;
 use vespera_core::schema::{Reference, Schema, SchemaRef, SchemaType};
 
@@ -17,21 +18,91 @@ const MAX_SCHEMA_RECURSION_DEPTH: usize = 32;
 
 thread_local! {
     static SCHEMA_RECURSION_DEPTH: Cell<usize> = const { Cell::new(0) };
+    static DEFAULTED_TYPES: RefCell<Option<BTreeSet<String>>> = const { RefCell::new(None) };
+}
+
+/// Run `f`, also returning the names of unknown types it documented as plain objects.
+///
+/// `serde_json::Value` is left out: a free-form object is what it means.
+pub fn collect_defaulted_types<R>(f: impl FnOnce() -> R) -> (R, BTreeSet<String>) {
+    let previous = DEFAULTED_TYPES.with(|types| types.replace(Some(BTreeSet::new())));
+    let result = f();
+    let types = DEFAULTED_TYPES.with(|types| types.replace(previous));
+    (result, types.unwrap_or_default())
+}
+
+fn record_defaulted_type(name: &str) {
+    if name != "Value" {
+        DEFAULTED_TYPES.with(|types| {
+            if let Some(types) = types.borrow_mut().as_mut() {
+                types.insert(name.to_string());
+            }
+        });
+    }
 }
 
 use super::{
-    generics::substitute_type,
+    enum_schema::parse_enum_to_schema,
+    generics::{
+        generic_naming, instantiation_name, register_instantiation, substitute_fields,
+        type_param_names,
+    },
     serde_attrs::{capitalize_first, extract_schema_name_from_entity, extract_schema_ref_override},
-    struct_schema::parse_struct_to_schema,
+    struct_schema::{expand_inline, parse_struct_to_schema},
 };
 
-/// Check if a type is a primitive Rust type that maps directly to a JSON Schema type.
-/// Inline integer schema with an OpenAPI format string.
-fn integer_with_format(format: &str) -> SchemaRef {
-    SchemaRef::Inline(Box::new(Schema {
+/// Integer schema of a Rust integer type name, `None` for any other name.
+///
+/// Uses the `OpenAPI` format registry where it has an entry
+/// (<https://spec.openapis.org/registry/format/index.html>). Except for the standard
+/// `int32`/`int64`, the bit width is also spelled out as `minimum`/`maximum` so clients
+/// get realistic ranges. `u64` only gets `minimum`: its maximum is not exact as a JSON number.
+pub(super) fn integer_schema(name: &str) -> Option<Schema> {
+    let (format, minimum, maximum) = match name {
+        "i8" => ("int8", Some(f64::from(i8::MIN)), Some(f64::from(i8::MAX))),
+        "i16" => (
+            "int16",
+            Some(f64::from(i16::MIN)),
+            Some(f64::from(i16::MAX)),
+        ),
+        "i32" => ("int32", None, None),
+        "i64" => ("int64", None, None),
+        "u8" => ("uint8", Some(0.0), Some(f64::from(u8::MAX))),
+        "u16" => ("uint16", Some(0.0), Some(f64::from(u16::MAX))),
+        "u32" => ("uint32", Some(0.0), Some(f64::from(u32::MAX))),
+        "u64" => ("uint64", Some(0.0), None),
+        // No standard format in the registry
+        "i128" | "isize" => return Some(Schema::integer()),
+        "u128" | "usize" => {
+            return Some(Schema {
+                minimum: Some(0.0),
+                ..Schema::integer()
+            });
+        }
+        _ => return None,
+    };
+    Some(Schema {
         format: Some(format.to_string()),
+        minimum,
+        maximum,
         ..Schema::integer()
-    }))
+    })
+}
+
+/// Schema of `NonZeroU32`, `NonZeroI64`, ... given the inner integer name (`u32`, `i64`).
+///
+/// Unsigned types start at `minimum: 1`; signed ones exclude zero with `not: {const: 0}`.
+fn non_zero_schema(inner: &str) -> Option<Schema> {
+    let mut schema = integer_schema(inner)?;
+    if inner.starts_with('u') {
+        schema.minimum = Some(1.0);
+    } else {
+        schema.not = Some(Box::new(SchemaRef::Inline(Box::new(Schema {
+            r#const: Some(serde_json::json!(0)),
+            ..Schema::default()
+        }))));
+    }
+    Some(schema)
 }
 
 /// Inline number schema with an OpenAPI format string.
@@ -50,6 +121,45 @@ fn string_with_format(format: &str) -> SchemaRef {
     }))
 }
 
+/// Build an inline object schema from `(name, schema)` pairs; every property is required.
+fn object_with_properties(properties: Vec<(&str, SchemaRef)>) -> Schema {
+    let mut schema = Schema::object();
+    let required = properties
+        .iter()
+        .map(|(name, _)| (*name).to_string())
+        .collect();
+    schema.properties = Some(
+        properties
+            .into_iter()
+            .map(|(name, prop)| (name.to_string(), prop))
+            .collect(),
+    );
+    schema.required = Some(required);
+    schema
+}
+
+/// Schema of `vespera::bulk::BulkItemResult` or `BulkResult`.
+///
+/// Parsed from their definitions in `vespera_core::bulk`, so the schema is the one
+/// their `#[derive(Schema)]` registers.
+fn bulk_result_schema(
+    name: &str,
+    known_schemas: &HashSet<String>,
+    struct_definitions: &HashMap<String, String>,
+) -> Schema {
+    syn::parse_file(vespera_core::bulk::RESULT_TYPES)
+        .ok()
+        .and_then(|file| {
+            file.items.into_iter().find_map(|item| match item {
+                syn::Item::Struct(item_struct) if item_struct.ident == name => Some(
+                    parse_struct_to_schema(&item_struct, known_schemas, struct_definitions),
+                ),
+                _ => None,
+            })
+        })
+        .unwrap_or_else(Schema::object)
+}
+
 pub fn is_primitive_type(ty: &Type) -> bool {
     match ty {
         Type::Path(type_path) => {
@@ -67,6 +177,141 @@ pub fn is_primitive_type(ty: &Type) -> bool {
     }
 }
 
+/// Inline schema of a generic struct or enum instantiated with concrete type arguments
+/// (`Wrapper<String>`, `ApiResult<User>`).
+///
+/// Returns `None` when the definition cannot be parsed or the argument count does not match.
+fn instantiate_generic_definition(
+    definition: &str,
+    args: &syn::AngleBracketedGenericArguments,
+    known_schemas: &HashSet<String>,
+    struct_definitions: &HashMap<String, String>,
+) -> Option<Schema> {
+    let concrete_types: Vec<&Type> = args
+        .args
+        .iter()
+        .filter_map(|arg| {
+            if let syn::GenericArgument::Type(ty) = arg {
+                Some(ty)
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    match syn::parse_str::<syn::Item>(definition).ok()? {
+        syn::Item::Struct(mut parsed) => {
+            let generic_params = type_param_names(&parsed.generics);
+            if generic_params.len() != concrete_types.len() {
+                return None;
+            }
+            // Substitute generic parameters with concrete types in all fields
+            substitute_fields(&mut parsed.fields, &generic_params, &concrete_types);
+            // Remove generics from the struct (it's now concrete)
+            parsed.generics.params.clear();
+            parsed.generics.where_clause = None;
+            Some(parse_struct_to_schema(
+                &parsed,
+                known_schemas,
+                struct_definitions,
+            ))
+        }
+        syn::Item::Enum(mut parsed) => {
+            let generic_params = type_param_names(&parsed.generics);
+            if generic_params.len() != concrete_types.len() {
+                return None;
+            }
+            for variant in &mut parsed.variants {
+                substitute_fields(&mut variant.fields, &generic_params, &concrete_types);
+            }
+            parsed.generics.params.clear();
+            parsed.generics.where_clause = None;
+            Some(parse_enum_to_schema(
+                &parsed,
+                known_schemas,
+                struct_definitions,
+            ))
+        }
+        _ => None,
+    }
+}
+
+/// Schema of a concrete generic type like `GenericStruct<String>`, built by substituting
+/// the generic parameters of `base_def` with the concrete types.
+///
+/// With a [`GenericNaming`] the instantiation is registered as its own component and
+/// referenced; otherwise it is inlined. An instantiation reached again while it is being
+/// inlined (`Page<T> { next: Option<Page<T>> }`) returns `None`, so the caller refers
+/// back to the generic component instead.
+fn generic_instance_schema(
+    base_name: &str,
+    base_def: &str,
+    args: &syn::AngleBracketedGenericArguments,
+    known_schemas: &HashSet<String>,
+    struct_definitions: &HashMap<String, String>,
+) -> Option<SchemaRef> {
+    let build =
+        || instantiate_generic_definition(base_def, args, known_schemas, struct_definitions);
+    let Some(naming) = generic_naming() else {
+        let key = format!("{base_name}{}", args.to_token_stream());
+        return expand_inline(&key, build)
+            .flatten()
+            .map(|schema| SchemaRef::Inline(Box::new(schema)));
+    };
+    let attrs = syn::parse_str::<syn::DeriveInput>(base_def)
+        .map(|item| item.attrs)
+        .unwrap_or_default();
+    let concrete_types: Vec<&Type> = args
+        .args
+        .iter()
+        .filter_map(|arg| match arg {
+            syn::GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        })
+        .collect();
+    let name = instantiation_name(base_name, &attrs, &concrete_types, naming);
+    register_instantiation(&name, || {
+        build().map(|schema| Schema {
+            title: Some(name.clone()),
+            ..schema
+        })
+    })
+    .then(|| SchemaRef::Ref(Reference::schema(&name)))
+}
+
+/// `propertyNames` of a map keyed by `key_ty`, `None` for plain string keys.
+///
+/// JSON object keys are always strings: serde writes integer keys as decimal strings
+/// (`{"1": ...}`), so integer keys are documented as digit strings. Keys with a string
+/// format (`Uuid`, dates) and enum keys keep their own schema.
+fn map_key_schema(
+    key_ty: &Type,
+    known_schemas: &HashSet<String>,
+    struct_definitions: &HashMap<String, String>,
+) -> Option<SchemaRef> {
+    match parse_type_to_schema_ref(key_ty, known_schemas, struct_definitions) {
+        SchemaRef::Inline(schema) if schema.schema_type == Some(SchemaType::Integer) => {
+            let pattern = if schema.minimum.is_some_and(|minimum| minimum >= 0.0) {
+                "^[0-9]+$"
+            } else {
+                "^-?[0-9]+$"
+            };
+            Some(SchemaRef::Inline(Box::new(Schema {
+                pattern: Some(pattern.to_string()),
+                ..Schema::string()
+            })))
+        }
+        SchemaRef::Inline(schema)
+            if schema.schema_type == Some(SchemaType::String)
+                && (schema.format.is_some() || schema.one_of.is_some()) =>
+        {
+            Some(SchemaRef::Inline(schema))
+        }
+        key @ SchemaRef::Ref(_) => Some(key),
+        SchemaRef::Inline(_) => None,
+    }
+}
+
 /// Converts a Rust type to an `OpenAPI` `SchemaRef`.
 ///
 /// This is the main entry point for type-to-schema conversion.
@@ -126,8 +371,9 @@ fn parse_type_impl(
             // Handle generic types
             if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
                 match ident_str.as_str() {
-                    // Box<T> -> T's schema (Box is just heap allocation, transparent for schema)
-                    "Box" => {
+                    // Box<T> -> T's schema (Box is just heap allocation, transparent for schema).
+                    // `vespera::params::EnumParam<T>` parses `T` from the same values.
+                    "Box" | "EnumParam" => {
                         if let Some(syn::GenericArgument::Type(inner_ty)) = args.args.first() {
                             return parse_type_to_schema_ref(
                                 inner_ty,
@@ -176,7 +422,7 @@ fn parse_type_impl(
                             && let Some(schema_name) = extract_schema_name_from_entity(inner_ty)
                         {
                             return SchemaRef::Inline(Box::new(Schema {
-                                ref_path: Some(format!("#/components/schemas/{schema_name}")),
+                                ref_path: Some(Reference::schema(&schema_name).ref_path),
                                 schema_type: None,
                                 nullable: Some(true),
                                 ..Schema::new(SchemaType::Object)
@@ -190,9 +436,7 @@ fn parse_type_impl(
                         if let Some(syn::GenericArgument::Type(inner_ty)) = args.args.first()
                             && let Some(schema_name) = extract_schema_name_from_entity(inner_ty)
                         {
-                            let inner_ref = SchemaRef::Ref(Reference::new(format!(
-                                "#/components/schemas/{schema_name}"
-                            )));
+                            let inner_ref = SchemaRef::Ref(Reference::schema(&schema_name));
                             return SchemaRef::Inline(Box::new(Schema::array(inner_ref)));
                         }
                         // Fallback: array of generic objects
@@ -200,12 +444,49 @@ fn parse_type_impl(
                             Box::new(Schema::new(SchemaType::Object)),
                         ))));
                     }
+                    // vespera::scope::Scoped<'_, T> -> T's schema (public document shows the public shape)
+                    "Scoped" if !known_schemas.contains("Scoped") => {
+                        if let Some(inner_ty) = args.args.iter().find_map(|arg| match arg {
+                            syn::GenericArgument::Type(ty) => Some(ty),
+                            _ => None,
+                        }) {
+                            return parse_type_to_schema_ref(
+                                inner_ty,
+                                known_schemas,
+                                struct_definitions,
+                            );
+                        }
+                    }
+                    // vespera::bulk::Bulk<T> -> { items: [T] } (unless shadowed by a user schema)
+                    "Bulk" if !known_schemas.contains("Bulk") => {
+                        if let Some(syn::GenericArgument::Type(inner_ty)) = args.args.first() {
+                            let inner_schema = parse_type_to_schema_ref(
+                                inner_ty,
+                                known_schemas,
+                                struct_definitions,
+                            );
+                            return SchemaRef::Inline(Box::new(object_with_properties(vec![(
+                                "items",
+                                SchemaRef::Inline(Box::new(Schema::array(inner_schema))),
+                            )])));
+                        }
+                    }
+                    // std::num::NonZero<u32>
+                    "NonZero" => {
+                        if let Some(syn::GenericArgument::Type(Type::Path(inner))) =
+                            args.args.first()
+                            && let Some(inner) = inner.path.get_ident()
+                            && let Some(schema) = non_zero_schema(&inner.to_string())
+                        {
+                            return SchemaRef::Inline(Box::new(schema));
+                        }
+                    }
                     "HashMap" | "BTreeMap" => {
                         // HashMap<K, V> or BTreeMap<K, V> -> object with additionalProperties
-                        // K is typically String, we use V as the value type
+                        // of V, and propertyNames for keys that are not plain strings
                         if args.args.len() >= 2
                             && let (
-                                Some(syn::GenericArgument::Type(_key_ty)),
+                                Some(syn::GenericArgument::Type(key_ty)),
                                 Some(syn::GenericArgument::Type(value_ty)),
                             ) = (args.args.get(0), args.args.get(1))
                         {
@@ -225,6 +506,12 @@ fn parse_type_impl(
                             return SchemaRef::Inline(Box::new(Schema {
                                 schema_type: Some(SchemaType::Object),
                                 additional_properties: Some(additional_props_value),
+                                property_names: map_key_schema(
+                                    key_ty,
+                                    known_schemas,
+                                    struct_definitions,
+                                )
+                                .map(Box::new),
                                 ..Schema::object()
                             }));
                         }
@@ -234,35 +521,38 @@ fn parse_type_impl(
             }
 
             // Handle primitive types
-            // For standard OpenAPI format types (i32, i64, f32, f64), use `format`
-            // per the OAS 3.1 Data Type Format spec. For non-standard types, fall
-            // back to `minimum`/`maximum` constraints.
+            if let Some(schema) = integer_schema(&ident_str).or_else(|| {
+                ident_str
+                    .strip_prefix("NonZero")
+                    .and_then(|inner| non_zero_schema(&inner.to_ascii_lowercase()))
+            }) {
+                return SchemaRef::Inline(Box::new(schema));
+            }
             match ident_str.as_str() {
-                // Signed integers: use OpenAPI format registry
-                // https://spec.openapis.org/registry/format/index.html
-                "i8" => integer_with_format("int8"),
-                "i16" => integer_with_format("int16"),
-                "i32" => integer_with_format("int32"),
-                "i64" => integer_with_format("int64"),
-                // Unsigned integers: use OpenAPI format registry
-                "u8" => integer_with_format("uint8"),
-                "u16" => integer_with_format("uint16"),
-                "u32" => integer_with_format("uint32"),
-                "u64" => integer_with_format("uint64"),
-                // i128, isize, StatusCode: no standard format in the registry
-                "i128" | "isize" | "StatusCode" => SchemaRef::Inline(Box::new(Schema::integer())),
-                // u128, usize: unsigned with no standard format — use minimum: 0
-                "u128" | "usize" => SchemaRef::Inline(Box::new(Schema {
-                    minimum: Some(0.0),
-                    ..Schema::integer()
-                })),
+                "StatusCode" => SchemaRef::Inline(Box::new(Schema::integer())),
                 "f32" => number_with_format("float"),
                 "f64" => number_with_format("double"),
-                "Decimal" => number_with_format("decimal"),
+                // rust_decimal / bigdecimal serialize as strings unless configured as floats
+                "Decimal" | "BigDecimal" if cfg!(feature = "decimal-number") => {
+                    number_with_format("decimal")
+                }
+                "Decimal" | "BigDecimal" => string_with_format("decimal"),
                 "bool" => SchemaRef::Inline(Box::new(Schema::boolean())),
                 "char" => string_with_format("char"),
-                "Uuid" => string_with_format("uuid"),
-                "String" | "str" => SchemaRef::Inline(Box::new(Schema::string())),
+                // `uuid::Uuid`; without the `uuid` feature it resolves like any other name
+                "Uuid" if cfg!(feature = "uuid") => string_with_format("uuid"),
+                "String" | "str" | "PathBuf" | "SocketAddr" | "SocketAddrV4" | "SocketAddrV6" => {
+                    SchemaRef::Inline(Box::new(Schema::string()))
+                }
+                // std::net addresses serialize as their display strings
+                "Ipv4Addr" => string_with_format("ipv4"),
+                "Ipv6Addr" => string_with_format("ipv6"),
+                "IpAddr" => SchemaRef::Inline(Box::new(Schema {
+                    one_of: Some(vec![string_with_format("ipv4"), string_with_format("ipv6")]),
+                    ..Schema::string()
+                })),
+                // `url::Url`
+                "Url" => string_with_format("uri"),
                 // Date-time types from chrono and time crates
                 "DateTime"
                 | "NaiveDateTime"
@@ -275,13 +565,21 @@ fn parse_type_impl(
                 "NaiveTime" | "Time" => string_with_format("time"),
                 // Duration types
                 "Duration" => string_with_format("duration"),
-                // File upload types (vespera::multipart / tempfile)
+                // File upload types (vespera::multipart / tempfile / bytes)
                 // FieldData<NamedTempFile> → string with binary format
-                "FieldData" | "NamedTempFile" => string_with_format("binary"),
+                "FieldData" | "NamedTempFile" | "Bytes" => string_with_format("binary"),
+                // Bulk operation responses (vespera::bulk), unless shadowed by a user schema
+                "BulkResult" | "BulkItemResult" if !known_schemas.contains(&ident_str) => {
+                    SchemaRef::Inline(Box::new(bulk_result_schema(
+                        &ident_str,
+                        known_schemas,
+                        struct_definitions,
+                    )))
+                }
                 // Standard library types that should not be referenced
                 // Note: HashMap and BTreeMap are handled above in generic types
                 "Vec" | "HashSet" | "BTreeSet" | "Option" | "Result" | "Json" | "Path"
-                | "Query" | "Header" => {
+                | "Query" | "QsQuery" | "Cookies" | "Header" => {
                     // These are not schema types, return object schema
                     SchemaRef::Inline(Box::new(Schema::new(SchemaType::Object)))
                 }
@@ -323,7 +621,7 @@ fn parse_type_impl(
                                 extract_schema_ref_override(&parsed_struct.attrs)
                         {
                             return SchemaRef::Inline(Box::new(Schema {
-                                ref_path: Some(format!("#/components/schemas/{schema_name}")),
+                                ref_path: Some(Reference::schema(&schema_name).ref_path),
                                 schema_type: None,
                                 nullable: nullable.then_some(true),
                                 ..Schema::new(SchemaType::Object)
@@ -331,70 +629,25 @@ fn parse_type_impl(
                         }
 
                         // Check if this is a generic type with type parameters
-                        if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
-                            // This is a concrete generic type like GenericStruct<String>
-                            // Inline the schema by substituting generic parameters with concrete types
-                            if let Some(base_def) = struct_definitions.get(&resolved_name)
-                                && let Ok(mut parsed) = syn::parse_str::<syn::ItemStruct>(base_def)
-                            {
-                                // Extract generic parameter names from the struct definition
-                                let generic_params: Vec<String> = parsed
-                                    .generics
-                                    .params
-                                    .iter()
-                                    .filter_map(|param| {
-                                        if let syn::GenericParam::Type(type_param) = param {
-                                            Some(type_param.ident.to_string())
-                                        } else {
-                                            None
-                                        }
-                                    })
-                                    .collect();
-
-                                // Extract concrete type arguments
-                                let concrete_types: Vec<&Type> = args
-                                    .args
-                                    .iter()
-                                    .filter_map(|arg| {
-                                        if let syn::GenericArgument::Type(ty) = arg {
-                                            Some(ty)
-                                        } else {
-                                            None
-                                        }
-                                    })
-                                    .collect();
-
-                                // Substitute generic parameters with concrete types in all fields
-                                if generic_params.len() == concrete_types.len() {
-                                    if let syn::Fields::Named(fields_named) = &mut parsed.fields {
-                                        for field in &mut fields_named.named {
-                                            field.ty = substitute_type(
-                                                &field.ty,
-                                                &generic_params,
-                                                &concrete_types,
-                                            );
-                                        }
-                                    }
-
-                                    // Remove generics from the struct (it's now concrete)
-                                    parsed.generics.params.clear();
-                                    parsed.generics.where_clause = None;
-
-                                    // Parse the substituted struct to schema (inline)
-                                    let schema = parse_struct_to_schema(
-                                        &parsed,
-                                        known_schemas,
-                                        struct_definitions,
-                                    );
-                                    return SchemaRef::Inline(Box::new(schema));
-                                }
-                            }
+                        if let syn::PathArguments::AngleBracketed(args) = &segment.arguments
+                            && let Some(base_def) = struct_definitions.get(&resolved_name)
+                            && let Some(schema_ref) = generic_instance_schema(
+                                &resolved_name,
+                                base_def,
+                                args,
+                                known_schemas,
+                                struct_definitions,
+                            )
+                        {
+                            return schema_ref;
                         }
-                        // Non-generic type or generic without parameters - use reference
+                        // Non-generic type, generic without parameters or recursive
+                        // instantiation - use reference
                         SchemaRef::Ref(Reference::schema(&resolved_name))
                     } else {
                         // For unknown custom types, return object schema instead of reference
                         // This prevents creating invalid references to non-existent schemas
+                        record_defaulted_type(&resolved_name);
                         SchemaRef::Inline(Box::new(Schema::new(SchemaType::Object)))
                     }
                 }
@@ -418,6 +671,9 @@ mod tests {
     use vespera_core::schema::SchemaType;
 
     use super::*;
+    use crate::parser::schema::generics::{
+        GenericNaming, take_instantiations, with_generic_naming,
+    };
 
     #[rstest]
     #[case("HashMap<String, i32>", Some(SchemaType::Object), true)]
@@ -605,6 +861,103 @@ mod tests {
         }
     }
 
+    #[rstest]
+    #[case("Tree", "struct Tree { children: Vec<Tree> }")]
+    #[case(
+        "Page<User>",
+        "struct Page<T> { items: Vec<T>, next: Option<Box<Page<T>>> }"
+    )]
+    #[case(
+        "Page<User>",
+        "struct Page<T> { items: Vec<T>, first: Option<Node<T>> }"
+    )]
+    fn test_recursive_types_refer_back(#[case] ty: &str, #[case] definition: &str) {
+        let mut definitions = HashMap::from([
+            ("User".to_string(), "struct User { id: i32 }".to_string()),
+            (
+                "Node".to_string(),
+                "struct Node<T> { value: T, page: Page<T> }".to_string(),
+            ),
+        ]);
+        let name = ty.split('<').next().unwrap();
+        definitions.insert(name.to_string(), definition.to_string());
+        let known: HashSet<String> = definitions.keys().cloned().collect();
+
+        let ty: Type = syn::parse_str(ty).unwrap();
+        let json =
+            serde_json::to_string(&parse_type_to_schema_ref(&ty, &known, &definitions)).unwrap();
+        assert!(
+            json.contains(&format!("\"$ref\":\"#/components/schemas/{name}\"")),
+            "{json}"
+        );
+        // Expanded once, not until the depth limit
+        assert!(json.len() < 1000, "{json}");
+    }
+
+    #[test]
+    fn test_generic_instantiations_registered_as_components() {
+        let definitions = HashMap::from([
+            ("User".to_string(), "struct User { id: i32 }".to_string()),
+            (
+                "Page".to_string(),
+                r#"#[schema(name_for(i32 = "Numbers"))]
+                struct Page<T> { items: Vec<T>, next: Option<Box<Page<T>>> }"#
+                    .to_string(),
+            ),
+        ]);
+        let known: HashSet<String> = definitions.keys().cloned().collect();
+
+        take_instantiations();
+        let refs = with_generic_naming(Some(GenericNaming::Underscore), || {
+            ["Page<User>", "Vec<Page<User>>", "Page<i32>"].map(|ty| {
+                let ty: Type = syn::parse_str(ty).unwrap();
+                serde_json::to_value(parse_type_to_schema_ref(&ty, &known, &definitions)).unwrap()
+            })
+        });
+        assert_eq!(refs[0]["$ref"], "#/components/schemas/Page_User");
+        assert_eq!(refs[1]["items"]["$ref"], "#/components/schemas/Page_User");
+        assert_eq!(refs[2]["$ref"], "#/components/schemas/Numbers");
+
+        let instantiations = take_instantiations();
+        assert_eq!(
+            instantiations.keys().collect::<Vec<_>>(),
+            vec!["Numbers", "Page_User"]
+        );
+        let page = serde_json::to_value(&instantiations["Page_User"]).unwrap();
+        assert_eq!(page["title"], "Page_User");
+        assert_eq!(
+            page["properties"]["items"]["items"]["$ref"],
+            "#/components/schemas/User"
+        );
+        assert_eq!(
+            page["properties"]["next"]["$ref"],
+            "#/components/schemas/Page_User"
+        );
+    }
+
+    #[test]
+    fn test_collect_defaulted_types() {
+        let ((), types) = collect_defaulted_types(|| {
+            for ty in [
+                "Vec<External>",
+                "Option<other::Thing>",
+                "serde_json::Value",
+                "User",
+            ] {
+                let ty: Type = syn::parse_str(ty).unwrap();
+                parse_type_to_schema_ref(
+                    &ty,
+                    &HashSet::from(["User".to_string()]),
+                    &HashMap::new(),
+                );
+            }
+        });
+        assert_eq!(
+            types.into_iter().collect::<Vec<_>>(),
+            vec!["External".to_string(), "Thing".to_string()]
+        );
+    }
+
     // Test parse_type_to_schema_ref with qualified path to unknown type
     #[test]
     fn test_parse_type_to_schema_ref_qualified_unknown_type() {
@@ -660,6 +1013,19 @@ mod tests {
         }
     }
 
+    #[rstest]
+    #[case("EnumParam<OrderStatus>")]
+    #[case("vespera::params::EnumParam<OrderStatus>")]
+    fn test_parse_type_to_schema_ref_enum_param(#[case] ty_src: &str) {
+        let known = HashSet::from(["OrderStatus".to_string()]);
+        let ty: Type = syn::parse_str(ty_src).unwrap();
+        let SchemaRef::Ref(reference) = parse_type_to_schema_ref(&ty, &known, &HashMap::new())
+        else {
+            panic!("Expected ref for {ty_src}");
+        };
+        assert_eq!(reference.ref_path, "#/components/schemas/OrderStatus");
+    }
+
     // Coverage tests for HasOne<Entity> handling
     #[test]
     fn test_parse_type_to_schema_ref_has_one_entity() {
@@ -888,6 +1254,142 @@ mod tests {
         }
     }
 
+    #[rstest]
+    #[case("Decimal")]
+    #[case("rust_decimal::Decimal")]
+    #[case("BigDecimal")]
+    #[case("bigdecimal::BigDecimal")]
+    fn test_parse_type_to_schema_ref_decimal(#[case] ty_src: &str) {
+        let ty: Type = syn::parse_str(ty_src).unwrap();
+        let schema_ref = parse_type_to_schema_ref(&ty, &HashSet::new(), &HashMap::new());
+
+        let SchemaRef::Inline(schema) = schema_ref else {
+            panic!("Expected inline schema for {ty_src}");
+        };
+        let expected_type = if cfg!(feature = "decimal-number") {
+            SchemaType::Number
+        } else {
+            SchemaType::String
+        };
+        assert_eq!(schema.schema_type, Some(expected_type));
+        assert_eq!(schema.format, Some("decimal".to_string()));
+    }
+
+    #[rstest]
+    #[case("i8", Some(-128.0), Some(127.0))]
+    #[case("i16", Some(-32768.0), Some(32767.0))]
+    #[case("i32", None, None)]
+    #[case("i64", None, None)]
+    #[case("u8", Some(0.0), Some(255.0))]
+    #[case("u16", Some(0.0), Some(65535.0))]
+    #[case("u32", Some(0.0), Some(4_294_967_295.0))]
+    #[case("u64", Some(0.0), None)]
+    #[case("usize", Some(0.0), None)]
+    #[case("NonZeroU8", Some(1.0), Some(255.0))]
+    #[case("std::num::NonZeroU64", Some(1.0), None)]
+    #[case("NonZero<u32>", Some(1.0), Some(4_294_967_295.0))]
+    #[case("NonZeroI8", Some(-128.0), Some(127.0))]
+    fn test_parse_type_to_schema_ref_integer_bounds(
+        #[case] ty_src: &str,
+        #[case] minimum: Option<f64>,
+        #[case] maximum: Option<f64>,
+    ) {
+        let ty: Type = syn::parse_str(ty_src).unwrap();
+        let schema_ref = parse_type_to_schema_ref(&ty, &HashSet::new(), &HashMap::new());
+
+        let SchemaRef::Inline(schema) = schema_ref else {
+            panic!("Expected inline schema for {ty_src}");
+        };
+        assert_eq!(schema.schema_type, Some(SchemaType::Integer));
+        assert_eq!(schema.minimum, minimum);
+        assert_eq!(schema.maximum, maximum);
+    }
+
+    #[rstest]
+    #[case("NonZeroI64", Some("int64"))]
+    #[case("NonZero<i32>", Some("int32"))]
+    #[case("NonZeroIsize", None)]
+    fn test_parse_type_to_schema_ref_non_zero_signed(
+        #[case] ty_src: &str,
+        #[case] format: Option<&str>,
+    ) {
+        let ty: Type = syn::parse_str(ty_src).unwrap();
+        let schema_ref = parse_type_to_schema_ref(&ty, &HashSet::new(), &HashMap::new());
+
+        let SchemaRef::Inline(schema) = schema_ref else {
+            panic!("Expected inline schema for {ty_src}");
+        };
+        assert_eq!(schema.format.as_deref(), format);
+        let Some(SchemaRef::Inline(not)) = schema.not.as_deref() else {
+            panic!("Expected `not` schema for {ty_src}");
+        };
+        assert_eq!(not.r#const, Some(serde_json::json!(0)));
+    }
+
+    #[rstest]
+    #[case("Ipv4Addr", Some("ipv4"))]
+    #[case("std::net::Ipv6Addr", Some("ipv6"))]
+    #[case("url::Url", Some("uri"))]
+    #[case("Url", Some("uri"))]
+    #[case("PathBuf", None)]
+    #[case("std::net::SocketAddr", None)]
+    fn test_parse_type_to_schema_ref_std_strings(
+        #[case] ty_src: &str,
+        #[case] expected_format: Option<&str>,
+    ) {
+        let ty: Type = syn::parse_str(ty_src).unwrap();
+        let schema_ref = parse_type_to_schema_ref(&ty, &HashSet::new(), &HashMap::new());
+
+        let SchemaRef::Inline(schema) = schema_ref else {
+            panic!("Expected inline schema for {ty_src}");
+        };
+        assert_eq!(schema.schema_type, Some(SchemaType::String));
+        assert_eq!(schema.format.as_deref(), expected_format);
+    }
+
+    #[test]
+    fn test_parse_type_to_schema_ref_ip_addr() {
+        let ty: Type = syn::parse_str("std::net::IpAddr").unwrap();
+        let schema_ref = parse_type_to_schema_ref(&ty, &HashSet::new(), &HashMap::new());
+
+        let SchemaRef::Inline(schema) = schema_ref else {
+            panic!("Expected inline schema for IpAddr");
+        };
+        assert_eq!(schema.schema_type, Some(SchemaType::String));
+        let formats: Vec<_> = schema
+            .one_of
+            .iter()
+            .flatten()
+            .map(|variant| match variant {
+                SchemaRef::Inline(variant) => variant.format.clone(),
+                SchemaRef::Ref(_) => None,
+            })
+            .collect();
+        assert_eq!(
+            formats,
+            vec![Some("ipv4".to_string()), Some("ipv6".to_string())]
+        );
+    }
+
+    #[rstest]
+    #[case("Uuid")]
+    #[case("uuid::Uuid")]
+    #[case("sea_orm::prelude::Uuid")]
+    fn test_parse_type_to_schema_ref_uuid(#[case] ty_src: &str) {
+        let ty: Type = syn::parse_str(ty_src).unwrap();
+        let schema_ref = parse_type_to_schema_ref(&ty, &HashSet::new(), &HashMap::new());
+
+        let SchemaRef::Inline(schema) = schema_ref else {
+            panic!("Expected inline schema for {ty_src}");
+        };
+        if cfg!(feature = "uuid") {
+            assert_eq!(schema.schema_type, Some(SchemaType::String));
+            assert_eq!(schema.format, Some("uuid".to_string()));
+        } else {
+            assert_eq!(schema.schema_type, Some(SchemaType::Object));
+        }
+    }
+
     // Test for qualified chrono types (e.g., chrono::DateTime<Utc>)
     #[test]
     fn test_parse_type_to_schema_ref_qualified_chrono_types() {
@@ -1087,7 +1589,7 @@ mod tests {
         assert!(!is_primitive_type(&ty));
     }
 
-    // ========== Coverage: FieldData / NamedTempFile binary format ==========
+    // ========== Coverage: FieldData / NamedTempFile / Bytes binary format ==========
 
     #[test]
     fn test_parse_type_field_data_binary_format() {
@@ -1113,6 +1615,18 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_type_bytes_binary_format() {
+        let ty: Type = syn::parse_str("Bytes").unwrap();
+        let schema_ref = parse_type_to_schema_ref(&ty, &HashSet::new(), &HashMap::new());
+        if let SchemaRef::Inline(schema) = schema_ref {
+            assert_eq!(schema.schema_type, Some(SchemaType::String));
+            assert_eq!(schema.format, Some("binary".to_string()));
+        } else {
+            panic!("Expected inline schema for Bytes");
+        }
+    }
+
     // ========== Coverage: StatusCode → integer ==========
 
     #[test]
@@ -1248,7 +1762,7 @@ mod tests {
 
     #[test]
     fn test_generic_known_schema_tuple_struct() {
-        // Tuple struct fields are NOT Named → skips field substitution but still inlines
+        // Tuple struct fields are substituted too and the struct is inlined
         let mut known = HashSet::new();
         known.insert("Pair".to_string());
         let mut defs = HashMap::new();
@@ -1256,8 +1770,7 @@ mod tests {
 
         let ty: Type = syn::parse_str("Pair<String>").unwrap();
         let schema_ref = parse_type_to_schema_ref(&ty, &known, &defs);
-        // Tuple struct still gets inlined (generics cleared, parse_struct_to_schema called)
-        // but field types are NOT substituted (no Named fields to iterate)
+        // Tuple struct gets inlined (generics cleared, parse_struct_to_schema called)
         assert!(
             matches!(schema_ref, SchemaRef::Inline(_)),
             "Tuple struct should still inline"
@@ -1278,6 +1791,62 @@ mod tests {
         assert!(matches!(schema_ref, SchemaRef::Ref(_)));
     }
 
+    #[test]
+    fn test_generic_enum_instantiation() {
+        let mut known = HashSet::new();
+        known.insert("ApiResult".to_string());
+        known.insert("User".to_string());
+        known.insert("ErrorBody".to_string());
+        let mut defs = HashMap::new();
+        defs.insert(
+            "ApiResult".to_string(),
+            "enum ApiResult<T> { Ok(T), Err(ErrorBody), Page { items: Vec<T> } }".to_string(),
+        );
+
+        let ty: Type = syn::parse_str("ApiResult<User>").unwrap();
+        let SchemaRef::Inline(schema) = parse_type_to_schema_ref(&ty, &known, &defs) else {
+            panic!("generic enum should be inlined");
+        };
+        assert_eq!(
+            serde_json::to_value(&schema.one_of).unwrap(),
+            serde_json::json!([
+                {
+                    "type": "object",
+                    "properties": { "Ok": { "$ref": "#/components/schemas/User" } },
+                    "required": ["Ok"]
+                },
+                {
+                    "type": "object",
+                    "properties": { "Err": { "$ref": "#/components/schemas/ErrorBody" } },
+                    "required": ["Err"]
+                },
+                {
+                    "type": "object",
+                    "properties": {
+                        "Page": {
+                            "type": "object",
+                            "properties": {
+                                "items": {
+                                    "type": "array",
+                                    "items": { "$ref": "#/components/schemas/User" }
+                                }
+                            },
+                            "required": ["items"]
+                        }
+                    },
+                    "required": ["Page"]
+                }
+            ])
+        );
+
+        // Mismatched argument count falls back to the (generic) component
+        let ty: Type = syn::parse_str("ApiResult<User, i32>").unwrap();
+        assert!(matches!(
+            parse_type_to_schema_ref(&ty, &known, &defs),
+            SchemaRef::Ref(_)
+        ));
+    }
+
     // ========== Coverage: nested generic types ==========
 
     #[test]
@@ -1361,6 +1930,38 @@ mod tests {
         }
     }
 
+    #[rstest]
+    #[case("HashMap<String, i32>", None)]
+    #[case(
+        "HashMap<u32, String>",
+        Some(r#"{"type":"string","pattern":"^[0-9]+$"}"#)
+    )]
+    #[case(
+        "BTreeMap<i64, String>",
+        Some(r#"{"type":"string","pattern":"^-?[0-9]+$"}"#)
+    )]
+    #[case("HashMap<Uuid, String>", Some(r#"{"type":"string","format":"uuid"}"#))]
+    #[case(
+        "HashMap<Role, String>",
+        Some(r##"{"$ref":"#/components/schemas/Role"}"##)
+    )]
+    fn test_map_property_names(#[case] ty_src: &str, #[case] expected: Option<&str>) {
+        let mut known = HashSet::new();
+        known.insert("Role".to_string());
+        let ty: Type = syn::parse_str(ty_src).unwrap();
+        let SchemaRef::Inline(schema) = parse_type_to_schema_ref(&ty, &known, &HashMap::new())
+        else {
+            panic!("Expected inline schema for {ty_src}");
+        };
+        assert!(schema.additional_properties.is_some());
+        let property_names = schema
+            .property_names
+            .map(|names| serde_json::to_value(&names).unwrap());
+        let expected =
+            expected.map(|json| serde_json::from_str::<serde_json::Value>(json).unwrap());
+        assert_eq!(property_names, expected);
+    }
+
     // ========== Coverage: HashMap/BTreeMap with insufficient args ==========
 
     #[test]
@@ -1502,4 +2103,179 @@ mod tests {
             SchemaRef::Ref(_) => panic!("expected inline schema ref override"),
         }
     }
+
+    #[test]
+    fn test_bulk_wraps_items_array() {
+        let mut known = HashSet::new();
+        known.insert("User".to_string());
+        let ty: syn::Type = syn::parse_str("vespera::bulk::Bulk<User>").unwrap();
+        let SchemaRef::Inline(schema) = parse_type_to_schema_ref(&ty, &known, &HashMap::new())
+        else {
+            panic!("expected inline schema");
+        };
+        assert_eq!(schema.schema_type, Some(SchemaType::Object));
+        assert_eq!(schema.required, Some(vec!["items".to_string()]));
+        let items = &schema.properties.as_ref().unwrap()["items"];
+        let SchemaRef::Inline(items) = items else {
+            panic!("expected inline array");
+        };
+        assert_eq!(items.schema_type, Some(SchemaType::Array));
+        assert!(matches!(
+            items.items.as_deref(),
+            Some(SchemaRef::Ref(r)) if r.ref_path == "#/components/schemas/User"
+        ));
+    }
+
+    #[test]
+    fn test_bulk_result_schema() {
+        let ty: syn::Type = syn::parse_str("BulkResult").unwrap();
+        let SchemaRef::Inline(schema) =
+            parse_type_to_schema_ref(&ty, &HashSet::new(), &HashMap::new())
+        else {
+            panic!("expected inline schema");
+        };
+        let props = schema.properties.as_ref().unwrap();
+        assert!(props.contains_key("items"));
+        assert!(props.contains_key("succeeded"));
+        assert!(props.contains_key("failed"));
+        let SchemaRef::Inline(items) = &props["items"] else {
+            panic!("expected inline array");
+        };
+        let Some(SchemaRef::Inline(item)) = items.items.as_deref() else {
+            panic!("expected inline item schema");
+        };
+        let item_props = item.properties.as_ref().unwrap();
+        assert!(item_props.contains_key("status"));
+        assert!(item_props.contains_key("error"));
+        assert_eq!(
+            item.required,
+            Some(vec!["index".to_string(), "status".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_user_defined_bulk_result_takes_precedence() {
+        let mut known = HashSet::new();
+        known.insert("BulkResult".to_string());
+        let ty: syn::Type = syn::parse_str("BulkResult").unwrap();
+        let schema_ref = parse_type_to_schema_ref(&ty, &known, &HashMap::new());
+        assert!(matches!(schema_ref, SchemaRef::Ref(_)));
+    }
+
+    #[test]
+    fn test_scoped_is_transparent() {
+        let mut known = HashSet::new();
+        known.insert("Employee".to_string());
+        let ty: syn::Type = syn::parse_str("Scoped<'_, Employee>").unwrap();
+        let schema_ref = parse_type_to_schema_ref(&ty, &known, &HashMap::new());
+        assert!(matches!(
+            schema_ref,
+            SchemaRef::Ref(r) if r.ref_path == "#/components/schemas/Employee"
+        ));
+    }
+}
+
+/// Property tests over random type trees: the parser recurses over arbitrary user types,
+/// so it must terminate without panicking and always produce a serializable schema.
+#[cfg(test)]
+mod proptests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    const LEAVES: &[&str] = &[
+        "i8",
+        "u64",
+        "i128",
+        "f32",
+        "bool",
+        "char",
+        "String",
+        "str",
+        "NonZeroU16",
+        "NonZeroI32",
+        "Uuid",
+        "Decimal",
+        "IpAddr",
+        "PathBuf",
+        "DateTime<Utc>",
+        "serde_json::Value",
+        "StatusCode",
+        "User",
+        "Node",
+        "crate::models::Pet",
+        "Unknown",
+        "T",
+        "()",
+    ];
+
+    /// Source of a type up to `depth` levels of nesting.
+    fn type_source() -> impl Strategy<Value = String> {
+        let leaf = proptest::sample::select(LEAVES).prop_map(str::to_string);
+        leaf.prop_recursive(6, 48, 2, |inner| {
+            prop_oneof![
+                (
+                    proptest::sample::select(
+                        &[
+                            "Vec", "Option", "Box", "HashSet", "BTreeSet", "Json", "Query", "Path",
+                            "Page", "Wrapper", "NonZero", "Scoped", "Bulk", "Unknown",
+                        ][..]
+                    ),
+                    inner.clone()
+                )
+                    .prop_map(|(wrapper, ty)| format!("{wrapper}<{ty}>")),
+                (
+                    proptest::sample::select(&["HashMap", "BTreeMap", "Result", "Pair"][..]),
+                    inner.clone(),
+                    inner.clone()
+                )
+                    .prop_map(|(wrapper, a, b)| format!("{wrapper}<{a}, {b}>")),
+                inner.clone().prop_map(|ty| format!("&'static {ty}")),
+                inner.clone().prop_map(|ty| format!("[{ty}; 3]")),
+                (inner.clone(), inner).prop_map(|(a, b)| format!("({a}, {b})")),
+            ]
+        })
+    }
+
+    fn schemas() -> (HashSet<String>, HashMap<String, String>) {
+        let definitions: HashMap<String, String> = [
+            ("User", "struct User { id: i32, friends: Vec<User> }"),
+            (
+                "Node",
+                "struct Node { value: Option<Box<Node>>, children: Vec<Node> }",
+            ),
+            ("Pet", "enum Pet { Cat, Dog(String) }"),
+            (
+                "Page",
+                "struct Page<T> { items: Vec<T>, next: Option<Box<Page<T>>> }",
+            ),
+            ("Wrapper", "#[serde(transparent)] struct Wrapper<T>(T);"),
+            ("Pair", "struct Pair<A, B>(A, B);"),
+        ]
+        .into_iter()
+        .map(|(name, definition)| (name.to_string(), definition.to_string()))
+        .collect();
+        (definitions.keys().cloned().collect(), definitions)
+    }
+
+    proptest! {
+        #[test]
+        fn parse_type_never_panics_and_serializes(source in type_source()) {
+            let ty: Type = syn::parse_str(&source).unwrap();
+            let (known, definitions) = schemas();
+            let schema_ref = parse_type_to_schema_ref(&ty, &known, &definitions);
+            prop_assert!(serde_json::to_string(&schema_ref).is_ok());
+            prop_assert_eq!(SCHEMA_RECURSION_DEPTH.with(Cell::get), 0);
+        }
+    }
+
+    #[test]
+    fn deeply_nested_type_is_bounded() {
+        // Well past `MAX_SCHEMA_RECURSION_DEPTH`, within what syn parses on a test thread
+        let source = format!("{}User{}", "Vec<".repeat(40), ">".repeat(40));
+        let ty: Type = syn::parse_str(&source).unwrap();
+        let (known, definitions) = schemas();
+        let schema_ref = parse_type_to_schema_ref(&ty, &known, &definitions);
+        assert!(serde_json::to_string(&schema_ref).is_ok());
+    }
 }
@@ -0,0 +1,186 @@
+//! Crate-local type alias resolution for return-type analysis.
+//!
+//! Handlers commonly return an alias such as `type ApiResult<T> = Result<Json<T>, ApiError>;`.
+//! The response parser only recognises `Result`/`Json` by name, so the alias is expanded
+//! (with its generic arguments substituted) before the return type is analysed.
+
+use std::{collections::HashSet, path::Path};
+
+use syn::{ReturnType, Signature, Type};
+
+use super::schema::substitute_type;
+use crate::schema_macro::file_cache::{get_manifest_dir, get_parsed_file, get_struct_candidates};
+
+/// Aliases of aliases are followed at most this many times.
+const MAX_ALIAS_DEPTH: usize = 8;
+
+/// Find `type {name}` among `items`, descending into inline modules.
+pub fn find_type_alias(items: &[syn::Item], name: &str) -> Option<syn::ItemType> {
+    items.iter().find_map(|item| match item {
+        syn::Item::Type(alias) if alias.ident == name => Some(alias.clone()),
+        syn::Item::Mod(module) => module
+            .content
+            .as_ref()
+            .and_then(|(_, items)| find_type_alias(items, name)),
+        _ => None,
+    })
+}
+
+/// Find `type {name}` anywhere under the crate's `src` directory.
+pub fn find_crate_type_alias(name: &str) -> Option<syn::ItemType> {
+    let src_dir = Path::new(&get_manifest_dir()?).join("src");
+    let mut candidates = get_struct_candidates(&src_dir, &format!("type {name}"));
+    candidates.sort();
+    candidates
+        .iter()
+        .find_map(|path| find_type_alias(&get_parsed_file(path)?.items, name))
+}
+
+/// Expand a crate-local alias used as the return type of `sig`.
+///
+/// Returns `None` when the return type is not an alias `lookup` knows about, so callers
+/// can keep using the original signature.
+pub fn resolve_return_type_alias(
+    sig: &Signature,
+    known_schemas: &HashSet<String>,
+    lookup: impl Fn(&str) -> Option<syn::ItemType>,
+) -> Option<Signature> {
+    let ReturnType::Type(arrow, ty) = &sig.output else {
+        return None;
+    };
+    let resolved = resolve_type_alias(ty, known_schemas, &lookup)?;
+    let mut sig = sig.clone();
+    sig.output = ReturnType::Type(*arrow, Box::new(resolved));
+    Some(sig)
+}
+
+/// Expand `ty` while it names a crate-local alias.
+fn resolve_type_alias(
+    ty: &Type,
+    known_schemas: &HashSet<String>,
+    lookup: &impl Fn(&str) -> Option<syn::ItemType>,
+) -> Option<Type> {
+    let mut current = expand_once(ty, known_schemas, lookup)?;
+    for _ in 1..MAX_ALIAS_DEPTH {
+        match expand_once(&current, known_schemas, lookup) {
+            Some(next) => current = next,
+            None => break,
+        }
+    }
+    Some(current)
+}
+
+fn expand_once(
+    ty: &Type,
+    known_schemas: &HashSet<String>,
+    lookup: &impl Fn(&str) -> Option<syn::ItemType>,
+) -> Option<Type> {
+    let Type::Path(type_path) = ty else {
+        return None;
+    };
+    if type_path.qself.is_some() {
+        return None;
+    }
+    let segments = &type_path.path.segments;
+    // `std::result::Result` and friends are never crate-local; only bare names and
+    // `crate::`/`self::`/`super::` paths are.
+    let crate_local = segments.len() == 1
+        || segments
+            .first()
+            .is_some_and(|s| s.ident == "crate" || s.ident == "self" || s.ident == "super");
+    let segment = segments.last()?;
+    let name = segment.ident.to_string();
+    if !crate_local || known_schemas.contains(&name) {
+        return None;
+    }
+
+    let alias = lookup(&name)?;
+    let args: Vec<&Type> = match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) => args
+            .args
+            .iter()
+            .filter_map(|arg| match arg {
+                syn::GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+    let params: Vec<&syn::TypeParam> = alias.generics.type_params().collect();
+    // More arguments than the alias accepts means the name refers to something else
+    // (e.g. `Result<T, E>` when the crate only defines `type Result<T> = ...`).
+    if args.len() > params.len() {
+        return None;
+    }
+    let concrete = params
+        .iter()
+        .enumerate()
+        .map(|(index, param)| args.get(index).copied().or(param.default.as_ref()))
+        .collect::<Option<Vec<&Type>>>()?;
+    let names: Vec<String> = params.iter().map(|param| param.ident.to_string()).collect();
+    Some(substitute_type(&alias.ty, &names, &concrete))
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::ToTokens;
+    use rstest::rstest;
+
+    use super::*;
+
+    const ALIASES: &str = r"
+        type ApiResult<T> = Result<Json<T>, ApiError>;
+        type Result<T, E = AppError> = std::result::Result<T, E>;
+        type UserResult = ApiResult<User>;
+        mod nested {
+            type Listing<T> = Json<Vec<T>>;
+        }
+    ";
+
+    fn resolve(signature: &str, known: &[&str]) -> Option<String> {
+        let file: syn::File = syn::parse_str(ALIASES).unwrap();
+        let sig: Signature = syn::parse_str(signature).unwrap();
+        let known = known.iter().map(ToString::to_string).collect();
+        resolve_return_type_alias(&sig, &known, |name| find_type_alias(&file.items, name))
+            .map(|sig| sig.output.to_token_stream().to_string())
+    }
+
+    #[rstest]
+    #[case(
+        "fn get() -> ApiResult<User>",
+        Some("-> std :: result :: Result < Json < User > , ApiError >")
+    )]
+    #[case(
+        "fn get() -> crate::ApiResult<User>",
+        Some("-> std :: result :: Result < Json < User > , ApiError >")
+    )]
+    #[case(
+        "fn get() -> Result<Json<User>>",
+        Some("-> std :: result :: Result < Json < User > , AppError >")
+    )]
+    #[case(
+        "fn get() -> Result<Json<User>, Other>",
+        Some("-> std :: result :: Result < Json < User > , Other >")
+    )]
+    #[case(
+        "fn get() -> UserResult",
+        Some("-> std :: result :: Result < Json < User > , ApiError >")
+    )]
+    #[case("fn get() -> Listing<User>", Some("-> Json < Vec < User > >"))]
+    #[case("fn get() -> std::result::Result<Json<User>, ApiError>", None)]
+    #[case("fn get() -> Json<User>", None)]
+    #[case("fn get()", None)]
+    fn test_resolve_return_type_alias(#[case] signature: &str, #[case] expected: Option<&str>) {
+        assert_eq!(resolve(signature, &[]).as_deref(), expected);
+    }
+
+    #[test]
+    fn test_resolve_return_type_alias_prefers_known_schemas() {
+        assert_eq!(resolve("fn get() -> UserResult", &["UserResult"]), None);
+    }
+
+    #[test]
+    fn test_resolve_return_type_alias_rejects_extra_arguments() {
+        assert_eq!(resolve("fn get() -> ApiResult<User, Other>", &[]), None);
+    }
+}
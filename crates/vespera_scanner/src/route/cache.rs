@@ -0,0 +1,220 @@
+//! Cache policy from `#[route(cache = (max_age = 60, public))]`.
+//!
+//! Every success (`2xx`) response documents the resulting `Cache-Control` header, so
+//! the policy is visible in the spec. With the `apply` flag the generated router also
+//! sets the header on responses that do not already carry one:
+//!
+//! ```ignore
+//! #[vespera::route(get, cache = (max_age = 60, public, apply))]
+//! pub async fn list_products() -> Json<Vec<Product>> { ... }
+//! ```
+//!
+//! `max_age` and `s_maxage` take seconds; `public`, `private`, `no_cache`, `no_store`,
+//! `must_revalidate` and `immutable` are flags.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use vespera_core::route::{Header, Operation};
+
+use crate::metadata::RouteCache;
+
+impl RouteCache {
+    /// The `Cache-Control` header value, e.g. `"public, max-age=60"`.
+    pub fn header_value(&self) -> String {
+        let mut directives: Vec<String> = Vec::new();
+        for (set, directive) in [
+            (self.public, "public"),
+            (self.private, "private"),
+            (self.no_cache, "no-cache"),
+            (self.no_store, "no-store"),
+        ] {
+            if set {
+                directives.push(directive.to_string());
+            }
+        }
+        if let Some(max_age) = self.max_age {
+            directives.push(format!("max-age={max_age}"));
+        }
+        if let Some(s_maxage) = self.s_maxage {
+            directives.push(format!("s-maxage={s_maxage}"));
+        }
+        for (set, directive) in [
+            (self.must_revalidate, "must-revalidate"),
+            (self.immutable, "immutable"),
+        ] {
+            if set {
+                directives.push(directive.to_string());
+            }
+        }
+        directives.join(", ")
+    }
+}
+
+impl syn::parse::Parse for RouteCache {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut cache = Self::default();
+        while !input.is_empty() {
+            let ident: syn::Ident = input.parse()?;
+            match ident.to_string().as_str() {
+                key @ ("max_age" | "s_maxage") => {
+                    input.parse::<syn::Token![=]>()?;
+                    let seconds = input.parse::<syn::LitInt>()?.base10_parse::<u64>()?;
+                    if key == "max_age" {
+                        cache.max_age = Some(seconds);
+                    } else {
+                        cache.s_maxage = Some(seconds);
+                    }
+                }
+                "public" => cache.public = true,
+                "private" => cache.private = true,
+                "no_cache" => cache.no_cache = true,
+                "no_store" => cache.no_store = true,
+                "must_revalidate" => cache.must_revalidate = true,
+                "immutable" => cache.immutable = true,
+                "apply" => cache.apply = true,
+                other => {
+                    return Err(syn::Error::new(
+                        ident.span(),
+                        format!(
+                            "cache: unknown key `{other}`. Expected `max_age`, `s_maxage`, `public`, `private`, `no_cache`, `no_store`, `must_revalidate`, `immutable` or `apply`."
+                        ),
+                    ));
+                }
+            }
+
+            if input.peek(syn::Token![,]) {
+                input.parse::<syn::Token![,]>()?;
+            } else {
+                break;
+            }
+        }
+
+        if !input.is_empty() {
+            return Err(input.error("cache: expected `,` between arguments."));
+        }
+        if cache.public && cache.private {
+            return Err(input.error("cache: `public` and `private` are mutually exclusive."));
+        }
+        if cache.header_value().is_empty() {
+            return Err(input.error("cache: expected at least one directive, e.g. `max_age = 60`."));
+        }
+        Ok(cache)
+    }
+}
+
+/// Document the `Cache-Control` header on every success response of `operation`.
+pub fn document_cache(operation: &mut Operation, cache: &RouteCache) {
+    let value = cache.header_value();
+    let header = Header {
+        required: cache.apply.then_some(true),
+        example: Some(value.as_str().into()),
+        ..Header::string(&format!("Cache policy: `{value}`"))
+    };
+    for (status, response) in &mut operation.responses {
+        if status.starts_with('2') {
+            response
+                .headers
+                .get_or_insert_with(Default::default)
+                .insert("Cache-Control".to_string(), header.clone().into());
+        }
+    }
+}
+
+/// Layer setting `Cache-Control` on a method router, for `apply` policies.
+pub fn cache_layer(cache: Option<&RouteCache>) -> Option<TokenStream> {
+    let cache = cache.filter(|cache| cache.apply)?;
+    let value = cache.header_value();
+    Some(quote!(vespera::cache::cache_control(#value)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use rstest::rstest;
+    use vespera_core::route::Response;
+
+    use super::*;
+
+    #[rstest]
+    #[case("max_age = 60, public", "public, max-age=60", false)]
+    #[case("private, no_cache", "private, no-cache", false)]
+    #[case("no_store, apply", "no-store", true)]
+    #[case(
+        "max_age = 31536000, s_maxage = 600, public, immutable, must_revalidate,",
+        "public, max-age=31536000, s-maxage=600, must-revalidate, immutable",
+        false
+    )]
+    fn test_parse_route_cache(#[case] input: &str, #[case] expected: &str, #[case] apply: bool) {
+        let cache: RouteCache = syn::parse_str(input).unwrap();
+        assert_eq!(cache.header_value(), expected);
+        assert_eq!(cache.apply, apply);
+    }
+
+    #[rstest]
+    #[case("", "expected at least one directive")]
+    #[case("apply", "expected at least one directive")]
+    #[case("max_age = \"60\"", "expected integer literal")]
+    #[case("stale = 1", "unknown key `stale`")]
+    #[case("public, private", "mutually exclusive")]
+    #[case("public max_age = 1", "expected `,`")]
+    fn test_parse_route_cache_errors(#[case] input: &str, #[case] expected: &str) {
+        let err = syn::parse_str::<RouteCache>(input).err().unwrap();
+        assert!(err.to_string().contains(expected), "{err}");
+    }
+
+    #[test]
+    fn test_document_cache() {
+        let response = |description: &str| Response {
+            description: description.to_string(),
+            headers: None,
+            content: None,
+            links: None,
+        };
+        let mut operation = Operation {
+            operation_id: None,
+            tags: None,
+            summary: None,
+            description: None,
+            parameters: None,
+            request_body: None,
+            responses: BTreeMap::from([
+                ("200".to_string(), response("Successful response")),
+                ("404".to_string(), response("Error response")),
+            ]),
+            deprecated: None,
+            security: None,
+            extensions: BTreeMap::new(),
+        };
+        let cache: RouteCache = syn::parse_str("max_age = 60, public").unwrap();
+        document_cache(&mut operation, &cache);
+
+        let value = serde_json::to_value(&operation).unwrap();
+        assert_eq!(
+            value["responses"]["200"]["headers"]["Cache-Control"],
+            serde_json::json!({
+                "description": "Cache policy: `public, max-age=60`",
+                "schema": { "type": "string" },
+                "example": "public, max-age=60"
+            })
+        );
+        assert!(value["responses"]["404"].get("headers").is_none());
+    }
+
+    #[test]
+    fn test_cache_layer() {
+        let cache: RouteCache = syn::parse_str("max_age = 60, public").unwrap();
+        assert!(cache_layer(None).is_none());
+        assert!(cache_layer(Some(&cache)).is_none());
+        let tokens = cache_layer(Some(&RouteCache {
+            apply: true,
+            ..cache
+        }))
+        .unwrap()
+        .to_string();
+        assert_eq!(
+            tokens,
+            "vespera :: cache :: cache_control (\"public, max-age=60\")"
+        );
+    }
+}
@@ -0,0 +1,263 @@
+//! Module-level route defaults.
+//!
+//! A route module can declare values shared by all of its routes:
+//!
+//! ```ignore
+//! // src/routes/admin/mod.rs
+//! vespera::defaults!(tags = ["admin"], security = ["bearer"], error_status = [500]);
+//! ```
+//!
+//! An item macro is used instead of `#![vespera::defaults(...)]` because custom inner
+//! attributes are unstable. Defaults apply to the declaring module and every module
+//! below it. Tags and error statuses are merged with the route's own; `security` is
+//! used only when the route has none. Nested defaults are merged outermost first.
+
+use std::collections::BTreeMap;
+
+use crate::{
+    args::DefaultsArgs,
+    metadata::{ModuleDefaults, RouteMetadata},
+};
+
+impl TryFrom<DefaultsArgs> for ModuleDefaults {
+    type Error = syn::Error;
+
+    fn try_from(args: DefaultsArgs) -> syn::Result<Self> {
+        let tags = args
+            .tags
+            .iter()
+            .flat_map(|array| &array.elems)
+            .map(|elem| match elem {
+                syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Str(lit_str),
+                    ..
+                }) => Ok(lit_str.value()),
+                other => Err(syn::Error::new_spanned(
+                    other,
+                    "defaults: tags must be string literals. Use `tags = [\"admin\"]`.",
+                )),
+            })
+            .collect::<syn::Result<_>>()?;
+        let error_status = args
+            .error_status
+            .iter()
+            .flat_map(|array| &array.elems)
+            .map(|elem| match elem {
+                syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Int(lit_int),
+                    ..
+                }) => lit_int.base10_parse::<u16>(),
+                other => Err(syn::Error::new_spanned(
+                    other,
+                    "defaults: error_status must be integer literals. Use `error_status = [500]`.",
+                )),
+            })
+            .collect::<syn::Result<_>>()?;
+        Ok(Self {
+            tags,
+            error_status,
+            security: args.security,
+        })
+    }
+}
+
+impl ModuleDefaults {
+    /// Layer `inner` (a nested module's defaults) on top of `self`.
+    fn extend(&mut self, inner: &Self) {
+        extend_unique(&mut self.tags, &inner.tags);
+        extend_unique(&mut self.error_status, &inner.error_status);
+        if inner.security.is_some() {
+            self.security.clone_from(&inner.security);
+        }
+    }
+
+    /// Merge these defaults into a route.
+    fn apply(&self, route: &mut RouteMetadata) {
+        if !self.tags.is_empty() {
+            let mut tags = self.tags.clone();
+            extend_unique(&mut tags, route.tags.as_deref().unwrap_or_default());
+            route.tags = Some(tags);
+        }
+        if !self.error_status.is_empty() {
+            let mut error_status = route.error_status.take().unwrap_or_default();
+            extend_unique(&mut error_status, &self.error_status);
+            route.error_status = Some(error_status);
+        }
+        if route.security.is_none() {
+            route.security.clone_from(&self.security);
+        }
+    }
+}
+
+fn extend_unique<T: Clone + PartialEq>(target: &mut Vec<T>, values: &[T]) {
+    for value in values {
+        if !target.contains(value) {
+            target.push(value.clone());
+        }
+    }
+}
+
+fn is_defaults_path(path: &syn::Path) -> bool {
+    (path.segments.len() == 2
+        && path.segments[0].ident == "vespera"
+        && path.segments[1].ident == "defaults")
+        || (path.segments.len() == 1 && path.segments[0].ident == "defaults")
+}
+
+/// Find `vespera::defaults!(...)` in a file.
+///
+/// Returns `Ok(None)` when the file declares no defaults, and an error when the
+/// declaration is malformed or repeated.
+pub fn extract_module_defaults(file: &syn::File) -> syn::Result<Option<ModuleDefaults>> {
+    let mut found: Option<ModuleDefaults> = None;
+    for item in &file.items {
+        let syn::Item::Macro(item_macro) = item else {
+            continue;
+        };
+        if !is_defaults_path(&item_macro.mac.path) {
+            continue;
+        }
+        if found.is_some() {
+            return Err(syn::Error::new_spanned(
+                &item_macro.mac.path,
+                "defaults: declared more than once in this module. Merge them into a single `vespera::defaults!(...)`.",
+            ));
+        }
+        found = Some(item_macro.mac.parse_body::<DefaultsArgs>()?.try_into()?);
+    }
+    Ok(found)
+}
+
+/// Merge module defaults (keyed by module path) into every route below each module.
+pub fn apply_module_defaults(
+    routes: &mut [RouteMetadata],
+    defaults: &BTreeMap<String, ModuleDefaults>,
+) {
+    if defaults.is_empty() {
+        return;
+    }
+    for route in routes {
+        let mut applicable: Vec<(&String, &ModuleDefaults)> = defaults
+            .iter()
+            .filter(|(module, _)| {
+                module.is_empty()
+                    || route.module_path == **module
+                    || route
+                        .module_path
+                        .strip_prefix(module.as_str())
+                        .is_some_and(|rest| rest.starts_with("::"))
+            })
+            .collect();
+        if applicable.is_empty() {
+            continue;
+        }
+        applicable.sort_by_key(|(module, _)| module.len());
+        let mut merged = ModuleDefaults::default();
+        for (_, module_defaults) in applicable {
+            merged.extend(module_defaults);
+        }
+        merged.apply(route);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use vespera_core::route::SecurityRequirement;
+
+    use super::*;
+
+    fn route(module_path: &str) -> RouteMetadata {
+        RouteMetadata {
+            method: "get".to_string(),
+            path: "/".to_string(),
+            function_name: "handler".to_string(),
+            module_path: module_path.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn defaults(tags: &[&str], error_status: &[u16], security: Option<&str>) -> ModuleDefaults {
+        ModuleDefaults {
+            tags: tags.iter().map(ToString::to_string).collect(),
+            error_status: error_status.to_vec(),
+            security: security.map(|s| vec![SecurityRequirement::from([(s.to_string(), vec![])])]),
+        }
+    }
+
+    #[rstest]
+    #[case("vespera::defaults!(tags = [\"users\"], error_status = [500]);", Some(defaults(&["users"], &[500], None)))]
+    #[case("defaults!(security = [\"bearer\"]);", Some(defaults(&[], &[], Some("bearer"))))]
+    #[case("vespera::defaults! { tags = [\"admin\"] }", Some(defaults(&["admin"], &[], None)))]
+    #[case("pub fn handler() {}", None)]
+    #[case("other::defaults!(tags = [\"x\"]);", None)]
+    fn test_extract_module_defaults(#[case] code: &str, #[case] expected: Option<ModuleDefaults>) {
+        let file = syn::parse_file(code).unwrap();
+        assert_eq!(extract_module_defaults(&file).unwrap(), expected);
+    }
+
+    #[rstest]
+    #[case("vespera::defaults!(path = \"/x\");", "unknown key")]
+    #[case(
+        "defaults!(tags = [\"a\"]); defaults!(tags = [\"b\"]);",
+        "declared more than once"
+    )]
+    #[case("defaults!(tags = [ADMIN]);", "tags must be string literals")]
+    #[case(
+        "defaults!(error_status = [\"500\"]);",
+        "error_status must be integer literals"
+    )]
+    #[case("defaults!(error_status = [70000]);", "number too large")]
+    fn test_extract_module_defaults_errors(#[case] code: &str, #[case] expected: &str) {
+        let file = syn::parse_file(code).unwrap();
+        let err = extract_module_defaults(&file).unwrap_err();
+        assert!(err.to_string().contains(expected), "{err}");
+    }
+
+    #[test]
+    fn test_apply_module_defaults_nested() {
+        let mut map = BTreeMap::new();
+        map.insert(
+            "routes".to_string(),
+            defaults(&["api"], &[500], Some("bearer")),
+        );
+        map.insert(
+            "routes::admin".to_string(),
+            defaults(&["admin"], &[403], Some("api_key")),
+        );
+
+        let mut admin = route("routes::admin::users");
+        admin.tags = Some(vec!["users".to_string()]);
+        admin.error_status = Some(vec![404, 500]);
+        let mut routes = vec![admin, route("routes::public"), route("routes_other")];
+        routes[1].security = Some(vec![]);
+
+        apply_module_defaults(&mut routes, &map);
+
+        assert_eq!(
+            routes[0].tags,
+            Some(vec![
+                "api".to_string(),
+                "admin".to_string(),
+                "users".to_string()
+            ])
+        );
+        assert_eq!(routes[0].error_status, Some(vec![404, 500, 403]));
+        assert!(routes[0].security.as_ref().unwrap()[0].contains_key("api_key"));
+
+        assert_eq!(routes[1].tags, Some(vec!["api".to_string()]));
+        assert_eq!(routes[1].security, Some(vec![]));
+
+        assert_eq!(routes[2].tags, None);
+        assert_eq!(routes[2].security, None);
+    }
+
+    #[test]
+    fn test_apply_module_defaults_root() {
+        let mut map = BTreeMap::new();
+        map.insert(String::new(), defaults(&["root"], &[], None));
+        let mut routes = vec![route("users")];
+        apply_module_defaults(&mut routes, &map);
+        assert_eq!(routes[0].tags, Some(vec!["root".to_string()]));
+    }
+}
@@ -0,0 +1,269 @@
+//! Per-route request limits from `#[route(limits = (body = "1MB", timeout = "30s"))]`.
+//!
+//! The generated router wraps the handler's method router in tower-http's
+//! `RequestBodyLimitLayer` / `TimeoutLayer`, and the operation records the limits
+//! under the `x-vespera-limits` extension together with `413` / `408` responses.
+//!
+//! Sizes accept `B`, `KB`, `MB` and `GB` (binary multiples, `KiB` etc. also accepted);
+//! durations accept `ms`, `s`, `m` and `h`.
+
+use proc_macro2::{Literal, TokenStream};
+use quote::quote;
+use vespera_core::route::{Operation, Response};
+
+use crate::metadata::RouteLimits;
+
+/// Name of the `OpenAPI` extension carrying the limits.
+pub const LIMITS_EXTENSION: &str = "x-vespera-limits";
+
+/// Split `"30s"` into `(30, "s")`.
+fn split_number(value: &str) -> Option<(u64, &str)> {
+    let value = value.trim();
+    let digits = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    let number = value[..digits].parse().ok()?;
+    Some((number, value[digits..].trim()))
+}
+
+/// Parse a size like `"512KB"` or `"1MB"` into bytes.
+pub fn parse_byte_size(value: &str) -> Option<u64> {
+    let (number, unit) = split_number(value)?;
+    let multiplier: u64 = match unit.to_ascii_lowercase().as_str() {
+        "" | "b" => 1,
+        "k" | "kb" | "kib" => 1 << 10,
+        "m" | "mb" | "mib" => 1 << 20,
+        "g" | "gb" | "gib" => 1 << 30,
+        _ => return None,
+    };
+    number.checked_mul(multiplier).filter(|bytes| *bytes > 0)
+}
+
+/// Parse a duration like `"500ms"` or `"30s"` into milliseconds.
+pub fn parse_duration_ms(value: &str) -> Option<u64> {
+    let (number, unit) = split_number(value)?;
+    let multiplier: u64 = match unit {
+        "ms" => 1,
+        "s" => 1_000,
+        "m" => 60_000,
+        "h" => 3_600_000,
+        _ => return None,
+    };
+    number.checked_mul(multiplier).filter(|ms| *ms > 0)
+}
+
+impl syn::parse::Parse for RouteLimits {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut limits = Self::default();
+        while !input.is_empty() {
+            let ident: syn::Ident = input.parse()?;
+            input.parse::<syn::Token![=]>()?;
+            let lit: syn::LitStr = input.parse()?;
+            match ident.to_string().as_str() {
+                "body" => {
+                    limits.body = Some(parse_byte_size(&lit.value()).ok_or_else(|| {
+                        syn::Error::new(
+                            lit.span(),
+                            "limits: invalid body size. Use a positive number with an optional unit, e.g. \"512KB\" or \"1MB\".",
+                        )
+                    })?);
+                }
+                "timeout" => {
+                    limits.timeout_ms = Some(parse_duration_ms(&lit.value()).ok_or_else(|| {
+                        syn::Error::new(
+                            lit.span(),
+                            "limits: invalid timeout. Use a positive number with a unit, e.g. \"500ms\", \"30s\" or \"2m\".",
+                        )
+                    })?);
+                }
+                other => {
+                    return Err(syn::Error::new(
+                        ident.span(),
+                        format!("limits: unknown key `{other}`. Expected `body` or `timeout`."),
+                    ));
+                }
+            }
+
+            if input.peek(syn::Token![,]) {
+                input.parse::<syn::Token![,]>()?;
+            } else {
+                break;
+            }
+        }
+
+        if !input.is_empty() {
+            return Err(input.error("limits: expected `,` between arguments."));
+        }
+        if limits == Self::default() {
+            return Err(input.error("limits: expected `body` and/or `timeout`."));
+        }
+        Ok(limits)
+    }
+}
+
+/// Record `limits` on an operation as an extension and document the rejections.
+pub fn document_limits(operation: &mut Operation, limits: &RouteLimits) {
+    let mut extension = serde_json::Map::new();
+    if let Some(body) = limits.body {
+        extension.insert("body".to_string(), body.into());
+        operation
+            .responses
+            .entry("413".to_string())
+            .or_insert_with(|| rejection("Payload Too Large"));
+    }
+    if let Some(timeout_ms) = limits.timeout_ms {
+        extension.insert("timeoutMs".to_string(), timeout_ms.into());
+        operation
+            .responses
+            .entry("408".to_string())
+            .or_insert_with(|| rejection("Request Timeout"));
+    }
+    operation
+        .extensions
+        .insert(LIMITS_EXTENSION.to_string(), extension.into());
+}
+
+fn rejection(description: &str) -> Response {
+    Response {
+        description: description.to_string(),
+        headers: None,
+        content: None,
+        links: None,
+    }
+}
+
+/// Layers enforcing `limits` on a method router, innermost first.
+pub fn limit_layers(limits: Option<&RouteLimits>) -> Vec<TokenStream> {
+    let Some(limits) = limits else {
+        return Vec::new();
+    };
+    let mut layers = Vec::new();
+    // Innermost: the timeout needs a `Default` response body, which `body_limit` wraps away
+    if let Some(timeout_ms) = limits.timeout_ms {
+        let timeout_ms = Literal::u64_unsuffixed(timeout_ms);
+        layers.push(quote!(
+            vespera::limits::timeout(::std::time::Duration::from_millis(#timeout_ms))
+        ));
+    }
+    if let Some(body) = limits.body {
+        let body = Literal::u64_unsuffixed(body);
+        // Axum's extractors (`Json`, `Bytes`, ...) stop at their own 2 MB default otherwise
+        layers.push(quote!(vespera::axum::extract::DefaultBodyLimit::max(#body)));
+        layers.push(quote!(vespera::limits::body_limit(#body)));
+    }
+    layers
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case("1024", Some(1024))]
+    #[case("512B", Some(512))]
+    #[case("64KB", Some(64 * 1024))]
+    #[case("1MB", Some(1024 * 1024))]
+    #[case("1 mb", Some(1024 * 1024))]
+    #[case("2GiB", Some(2 * 1024 * 1024 * 1024))]
+    #[case("0MB", None)]
+    #[case("MB", None)]
+    #[case("1TB", None)]
+    #[case("1.5MB", None)]
+    fn test_parse_byte_size(#[case] value: &str, #[case] expected: Option<u64>) {
+        assert_eq!(parse_byte_size(value), expected);
+    }
+
+    #[rstest]
+    #[case("250ms", Some(250))]
+    #[case("30s", Some(30_000))]
+    #[case("2m", Some(120_000))]
+    #[case("1h", Some(3_600_000))]
+    #[case("30", None)]
+    #[case("0s", None)]
+    #[case("30S", None)]
+    fn test_parse_duration_ms(#[case] value: &str, #[case] expected: Option<u64>) {
+        assert_eq!(parse_duration_ms(value), expected);
+    }
+
+    #[rstest]
+    #[case("body = \"1MB\", timeout = \"30s\"", Some(1 << 20), Some(30_000))]
+    #[case("timeout = \"500ms\"", None, Some(500))]
+    #[case("body = \"16KB\",", Some(16 << 10), None)]
+    fn test_parse_route_limits(
+        #[case] input: &str,
+        #[case] body: Option<u64>,
+        #[case] timeout_ms: Option<u64>,
+    ) {
+        let limits: RouteLimits = syn::parse_str(input).unwrap();
+        assert_eq!(limits, RouteLimits { body, timeout_ms });
+    }
+
+    #[rstest]
+    #[case("", "expected `body` and/or `timeout`")]
+    #[case("size = \"1MB\"", "unknown key `size`")]
+    #[case("body = \"lots\"", "invalid body size")]
+    #[case("timeout = \"30\"", "invalid timeout")]
+    #[case("body = \"1MB\" timeout = \"1s\"", "expected `,`")]
+    fn test_parse_route_limits_errors(#[case] input: &str, #[case] expected: &str) {
+        let err = syn::parse_str::<RouteLimits>(input).err().unwrap();
+        assert!(err.to_string().contains(expected), "{err}");
+    }
+
+    #[test]
+    fn test_document_limits() {
+        let mut operation = Operation {
+            operation_id: None,
+            tags: None,
+            summary: None,
+            description: None,
+            parameters: None,
+            request_body: None,
+            responses: BTreeMap::from([("408".to_string(), rejection("Too slow"))]),
+            deprecated: None,
+            security: None,
+            extensions: BTreeMap::new(),
+        };
+        document_limits(
+            &mut operation,
+            &RouteLimits {
+                body: Some(1 << 20),
+                timeout_ms: Some(30_000),
+            },
+        );
+
+        assert_eq!(
+            operation.extensions[LIMITS_EXTENSION],
+            serde_json::json!({ "body": 1_048_576, "timeoutMs": 30_000 })
+        );
+        assert_eq!(operation.responses["413"].description, "Payload Too Large");
+        assert_eq!(operation.responses["408"].description, "Too slow");
+        assert_eq!(
+            serde_json::to_value(&operation).unwrap()[LIMITS_EXTENSION]["body"],
+            1_048_576
+        );
+    }
+
+    #[test]
+    fn test_limit_layers() {
+        assert!(limit_layers(None).is_empty());
+        let layers: Vec<String> = limit_layers(Some(&RouteLimits {
+            body: Some(1024),
+            timeout_ms: Some(500),
+        }))
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+        assert_eq!(
+            layers,
+            [
+                "vespera :: limits :: timeout (:: std :: time :: Duration :: from_millis (500))",
+                "vespera :: axum :: extract :: DefaultBodyLimit :: max (1024)",
+                "vespera :: limits :: body_limit (1024)",
+            ]
+        );
+    }
+}
@@ -0,0 +1,311 @@
+//! Response links from `#[route(links(...))]`.
+//!
+//! A link tells clients which operation a response leads to and how to fill in its
+//! parameters, e.g. that the `id` of a created user is the `id` path parameter of
+//! `GET /users/{id}`:
+//!
+//! ```ignore
+//! #[vespera::route(post, status = 201, links(GetUser = (operation_id = "users_get_user", parameters(id = "$response.body#/id"))))]
+//! pub async fn create_user(Json(req): Json<CreateUser>) -> (StatusCode, Json<User>) { ... }
+//! ```
+//!
+//! Links are attached to every success (`2xx`) response of the route. Parameter and
+//! request body values are `OpenAPI` runtime expressions (`$response.body#/id`,
+//! `$request.path.id`, ...) or literal strings. `vespera!` rejects links whose
+//! `operation_id` names no route.
+
+use std::collections::BTreeMap;
+
+use vespera_core::route::{Link, Operation};
+
+use crate::metadata::RouteLink;
+
+/// Parse a string literal, or an identifier for names that are valid Rust identifiers.
+fn parse_name(input: syn::parse::ParseStream, what: &str) -> syn::Result<String> {
+    use syn::ext::IdentExt;
+
+    let (name, span) = if input.peek(syn::LitStr) {
+        let lit: syn::LitStr = input.parse()?;
+        (lit.value(), lit.span())
+    } else {
+        let ident = input.call(syn::Ident::parse_any)?;
+        (ident.unraw().to_string(), ident.span())
+    };
+    if name.is_empty() {
+        return Err(syn::Error::new(
+            span,
+            format!("links: {what} must not be empty"),
+        ));
+    }
+    Ok(name)
+}
+
+/// Parse the inside of `parameters(id = "$response.body#/id")`.
+fn parse_parameters(input: syn::parse::ParseStream) -> syn::Result<Vec<(String, String)>> {
+    let mut parameters: Vec<(String, String)> = Vec::new();
+    while !input.is_empty() {
+        let span = input.span();
+        let name = parse_name(input, "parameter name")?;
+        if parameters.iter().any(|(existing, _)| *existing == name) {
+            return Err(syn::Error::new(
+                span,
+                format!("links: duplicate parameter `{name}`"),
+            ));
+        }
+        input.parse::<syn::Token![=]>()?;
+        let value: syn::LitStr = input.parse()?;
+        parameters.push((name, value.value()));
+
+        if input.peek(syn::Token![,]) {
+            input.parse::<syn::Token![,]>()?;
+        } else {
+            break;
+        }
+    }
+    if !input.is_empty() {
+        return Err(input.error("links: expected `,`"));
+    }
+    Ok(parameters)
+}
+
+/// `GetUser = (operation_id = "users_get_user", parameters(id = "$response.body#/id"))`
+impl syn::parse::Parse for RouteLink {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let name_span = input.span();
+        let name = parse_name(input, "link name")?;
+        input.parse::<syn::Token![=]>()?;
+        let content;
+        syn::parenthesized!(content in input);
+
+        let mut operation_id: Option<String> = None;
+        let mut parameters: Vec<(String, String)> = Vec::new();
+        let mut request_body: Option<String> = None;
+        let mut description: Option<String> = None;
+        while !content.is_empty() {
+            let ident: syn::Ident = content.parse()?;
+            match ident.to_string().as_str() {
+                "operation_id" => {
+                    content.parse::<syn::Token![=]>()?;
+                    let lit: syn::LitStr = content.parse()?;
+                    operation_id = Some(lit.value());
+                }
+                "parameters" => {
+                    let inner;
+                    syn::parenthesized!(inner in content);
+                    parameters = parse_parameters(&inner)?;
+                }
+                "request_body" => {
+                    content.parse::<syn::Token![=]>()?;
+                    let lit: syn::LitStr = content.parse()?;
+                    request_body = Some(lit.value());
+                }
+                "description" => {
+                    content.parse::<syn::Token![=]>()?;
+                    let lit: syn::LitStr = content.parse()?;
+                    description = Some(lit.value());
+                }
+                other => {
+                    return Err(syn::Error::new(
+                        ident.span(),
+                        format!(
+                            "links: unknown key `{other}`. Expected `operation_id`, `parameters`, `request_body` or `description`."
+                        ),
+                    ));
+                }
+            }
+
+            if content.peek(syn::Token![,]) {
+                content.parse::<syn::Token![,]>()?;
+            } else {
+                break;
+            }
+        }
+        if !content.is_empty() {
+            return Err(content.error("links: expected `,` between arguments."));
+        }
+        let operation_id = operation_id.ok_or_else(|| {
+            syn::Error::new(
+                name_span,
+                format!("links: `{name}` needs `operation_id = \"...\"`"),
+            )
+        })?;
+        Ok(Self {
+            name,
+            operation_id,
+            parameters,
+            request_body,
+            description,
+        })
+    }
+}
+
+/// Parse the inside of `links(GetUser = (...), ListPosts = (...))`.
+pub fn parse_route_links(input: syn::parse::ParseStream) -> syn::Result<Vec<RouteLink>> {
+    let mut links: Vec<RouteLink> = Vec::new();
+    while !input.is_empty() {
+        let span = input.span();
+        let link: RouteLink = input.parse()?;
+        if links.iter().any(|existing| existing.name == link.name) {
+            return Err(syn::Error::new(
+                span,
+                format!("links: duplicate link `{}`", link.name),
+            ));
+        }
+        links.push(link);
+
+        if input.peek(syn::Token![,]) {
+            input.parse::<syn::Token![,]>()?;
+        } else {
+            break;
+        }
+    }
+    if !input.is_empty() {
+        return Err(input.error("links: expected `,`"));
+    }
+    Ok(links)
+}
+
+/// Attach `links` to every success response of `operation`.
+pub fn document_links(operation: &mut Operation, links: &[RouteLink]) {
+    let links: BTreeMap<String, Link> = links
+        .iter()
+        .map(|link| {
+            let parameters = (!link.parameters.is_empty()).then(|| {
+                link.parameters
+                    .iter()
+                    .map(|(name, value)| (name.clone(), value.as_str().into()))
+                    .collect()
+            });
+            (
+                link.name.clone(),
+                Link {
+                    operation_id: Some(link.operation_id.clone()),
+                    parameters,
+                    request_body: link.request_body.as_deref().map(Into::into),
+                    description: link.description.clone(),
+                    ..Link::default()
+                },
+            )
+        })
+        .collect();
+    for (status, response) in &mut operation.responses {
+        if status.starts_with('2') {
+            response.links = Some(links.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use rstest::rstest;
+    use vespera_core::route::Response;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_route_links() {
+        let links = syn::parse::Parser::parse_str(
+            parse_route_links,
+            "GetUser = (operation_id = \"users_get_user\", parameters(id = \"$response.body#/id\", \"X-Trace\" = \"$request.header.X-Trace\")), \
+             ListPosts = (operation_id = \"posts_list\", request_body = \"$response.body\", description = \"Posts of the user\")",
+        )
+        .unwrap();
+        assert_eq!(
+            links,
+            vec![
+                RouteLink {
+                    name: "GetUser".to_string(),
+                    operation_id: "users_get_user".to_string(),
+                    parameters: vec![
+                        ("id".to_string(), "$response.body#/id".to_string()),
+                        ("X-Trace".to_string(), "$request.header.X-Trace".to_string()),
+                    ],
+                    request_body: None,
+                    description: None,
+                },
+                RouteLink {
+                    name: "ListPosts".to_string(),
+                    operation_id: "posts_list".to_string(),
+                    parameters: vec![],
+                    request_body: Some("$response.body".to_string()),
+                    description: Some("Posts of the user".to_string()),
+                },
+            ]
+        );
+    }
+
+    #[rstest]
+    #[case(
+        "GetUser = (parameters(id = \"$response.body#/id\"))",
+        "needs `operation_id"
+    )]
+    #[case(
+        "GetUser = (operation_id = \"a\"), GetUser = (operation_id = \"b\")",
+        "duplicate link `GetUser`"
+    )]
+    #[case(
+        "GetUser = (operation_id = \"a\", parameters(id = \"x\", id = \"y\"))",
+        "duplicate parameter `id`"
+    )]
+    #[case(
+        "GetUser = (operation_id = \"a\", server = \"x\")",
+        "unknown key `server`"
+    )]
+    #[case(
+        "GetUser = (operation_id = \"a\") ListPosts = (operation_id = \"b\")",
+        "expected `,`"
+    )]
+    fn test_parse_route_links_errors(#[case] input: &str, #[case] expected: &str) {
+        let err = syn::parse::Parser::parse_str(parse_route_links, input).unwrap_err();
+        assert!(err.to_string().contains(expected), "{err}");
+    }
+
+    #[test]
+    fn test_document_links() {
+        let response = |description: &str| Response {
+            description: description.to_string(),
+            headers: None,
+            content: None,
+            links: None,
+        };
+        let mut operation = Operation {
+            operation_id: None,
+            tags: None,
+            summary: None,
+            description: None,
+            parameters: None,
+            request_body: None,
+            responses: BTreeMap::from([
+                ("201".to_string(), response("Created")),
+                ("400".to_string(), response("Error response")),
+            ]),
+            deprecated: None,
+            security: None,
+            extensions: BTreeMap::new(),
+        };
+        document_links(
+            &mut operation,
+            &[RouteLink {
+                name: "GetUser".to_string(),
+                operation_id: "users_get_user".to_string(),
+                parameters: vec![("id".to_string(), "$response.body#/id".to_string())],
+                request_body: None,
+                description: None,
+            }],
+        );
+
+        let value = serde_json::to_value(&operation).unwrap();
+        assert_eq!(
+            value["responses"]["201"]["links"],
+            serde_json::json!({
+                "GetUser": {
+                    "operationId": "users_get_user",
+                    "parameters": { "id": "$response.body#/id" }
+                }
+            })
+        );
+        assert!(value["responses"]["400"].get("links").is_none());
+    }
+}
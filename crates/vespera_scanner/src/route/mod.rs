@@ -0,0 +1,17 @@
+mod cache;
+mod defaults;
+mod limits;
+mod links;
+mod overrides;
+mod policy;
+mod tag_rules;
+mod utils;
+
+pub use cache::*;
+pub use defaults::*;
+pub use limits::*;
+pub use links::*;
+pub use overrides::*;
+pub use policy::*;
+pub use tag_rules::*;
+pub use utils::*;
@@ -0,0 +1,510 @@
+//! Explicit documentation from `#[route(response = ..., request_body = ..., responses(...))]`.
+//!
+//! Inference only sees the handler's signature, so a handler that returns
+//! `impl IntoResponse` or delegates to a helper (`return respond(user)`) cannot be
+//! documented from it. These overrides replace inference for one route:
+//!
+//! ```ignore
+//! #[vespera::route(post, request_body = Json<CreateUser>, response = (StatusCode, Json<User>))]
+//! pub async fn create_user(req: Request) -> impl IntoResponse {
+//!     respond(create(req).await)
+//! }
+//! ```
+//!
+//! Both are written the way they would appear in a signature. A `request_body` that is
+//! not a body extractor (`Json`, `Form`, `TypedMultipart`, ...) is documented as JSON.
+//!
+//! `responses(...)` documents one response per status code, for handlers whose
+//! `Result<T, E>` cannot express every outcome:
+//!
+//! ```ignore
+//! #[vespera::route(get, path = "/{id}", responses(200 = Json<User>, 404 = Json<ErrorResponse>, 422 = ValidationError))]
+//! pub async fn get_user(Path(id): Path<i32>) -> Result<Json<User>, AppError> { ... }
+//! ```
+//!
+//! Raw byte bodies (`Bytes`, `Vec<u8>`, `Body`) are documented as
+//! `application/octet-stream`; `content_type = "image/png"` names the actual media type.
+//!
+//! An `Sse<S>` response is documented as a `text/event-stream` string; `sse_event = ChatEvent`
+//! documents the payload each event carries instead.
+//!
+//! `params(id = Uuid, "X-Request-Id" = String)` documents the schema of each named path,
+//! query, header or cookie parameter, for extractors whose field types do not describe the
+//! accepted values (a `String` that must be a UUID, a custom `FromRequestParts` type).
+//!
+//! `request_example = json!({ "name": "alice" })` and `response_example = json!({ "id": 1 })`
+//! set the `example` of the request body and of every success response.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use syn::{Signature, Type};
+use vespera_core::{
+    route::{Operation, ParameterRef},
+    schema::SchemaRef,
+};
+
+use crate::parser::{
+    BINARY_MEDIA_TYPE, EVENT_STREAM_MEDIA_TYPE, parse_request_body, parse_return_type,
+    parse_type_to_schema_ref,
+};
+
+/// `sig` with its return type replaced by the `response = ...` override.
+pub fn override_return_type(sig: &Signature, response: Option<&str>) -> Option<Signature> {
+    let ty: Type = syn::parse_str(response?).ok()?;
+    let mut sig = sig.clone();
+    sig.output = syn::parse_quote!(-> #ty);
+    Some(sig)
+}
+
+/// Replace the inferred request body of `operation` with the `request_body = ...` override.
+pub fn override_request_body(
+    operation: &mut Operation,
+    request_body: &str,
+    known_schemas: &HashSet<String>,
+    struct_definitions: &HashMap<String, String>,
+) {
+    let Ok(ty) = syn::parse_str::<Type>(request_body) else {
+        return;
+    };
+    let arg: syn::FnArg = syn::parse_quote!(body: #ty);
+    let json_arg: syn::FnArg = syn::parse_quote!(body: Json<#ty>);
+    operation.request_body = parse_request_body(&arg, known_schemas, struct_definitions)
+        .or_else(|| parse_request_body(&json_arg, known_schemas, struct_definitions));
+}
+
+/// Replace inferred responses of `operation` with the `responses(...)` overrides.
+///
+/// Each type is documented the way it would be as a return type. Declared statuses
+/// replace inferred ones; declaring any 2xx status also drops the inferred success response.
+pub fn override_responses(
+    operation: &mut Operation,
+    responses: &[(u16, String)],
+    known_schemas: &HashSet<String>,
+    struct_definitions: &HashMap<String, String>,
+) {
+    if responses
+        .iter()
+        .any(|(status, _)| (200..300).contains(status))
+    {
+        operation
+            .responses
+            .retain(|status, _| !status.starts_with('2'));
+    }
+    for (status, ty) in responses {
+        let Ok(ty) = syn::parse_str::<Type>(ty) else {
+            continue;
+        };
+        let Some(mut response) = parse_return_type(
+            &syn::parse_quote!(-> #ty),
+            known_schemas,
+            struct_definitions,
+        )
+        .remove("200") else {
+            continue;
+        };
+        if !(200..300).contains(status) {
+            "Error response".clone_into(&mut response.description);
+        }
+        operation.responses.insert(status.to_string(), response);
+    }
+}
+
+/// Document the raw byte bodies of `operation` as `media_type` (`content_type = "image/png"`)
+/// in place of `application/octet-stream`.
+pub fn override_binary_media_type(operation: &mut Operation, media_type: &str) {
+    let contents = operation
+        .request_body
+        .iter_mut()
+        .map(|body| &mut body.content)
+        .chain(
+            operation
+                .responses
+                .values_mut()
+                .filter_map(|response| response.content.as_mut()),
+        );
+    for content in contents {
+        let Some(mut media) = content.remove(BINARY_MEDIA_TYPE) else {
+            continue;
+        };
+        if let Some(SchemaRef::Inline(schema)) = &mut media.schema {
+            schema.content_media_type = Some(media_type.to_string());
+        }
+        content.insert(media_type.to_string(), media);
+    }
+}
+
+/// Document the events of `operation`'s `text/event-stream` responses as `sse_event`
+/// (`sse_event = ChatEvent`).
+pub fn override_sse_event(
+    operation: &mut Operation,
+    sse_event: &str,
+    known_schemas: &HashSet<String>,
+    struct_definitions: &HashMap<String, String>,
+) {
+    let Ok(ty) = syn::parse_str::<Type>(sse_event) else {
+        return;
+    };
+    let event_schema = parse_type_to_schema_ref(&ty, known_schemas, struct_definitions);
+    let streams = operation
+        .responses
+        .values_mut()
+        .filter_map(|response| response.content.as_mut()?.get_mut(EVENT_STREAM_MEDIA_TYPE));
+    for stream in streams {
+        stream.schema = Some(event_schema.clone());
+    }
+}
+
+/// Replace the inferred schemas of `operation`'s parameters with the `params(...)` overrides.
+///
+/// Parameters are matched by name; names the operation does not document are ignored.
+pub fn override_parameter_schemas(
+    operation: &mut Operation,
+    params: &[(String, String)],
+    known_schemas: &HashSet<String>,
+    struct_definitions: &HashMap<String, String>,
+) {
+    let Some(parameters) = operation.parameters.as_mut() else {
+        return;
+    };
+    for (name, ty) in params {
+        let Ok(ty) = syn::parse_str::<Type>(ty) else {
+            continue;
+        };
+        let schema = parse_type_to_schema_ref(&ty, known_schemas, struct_definitions);
+        for parameter in parameters.iter_mut() {
+            if let ParameterRef::Inline(parameter) = parameter
+                && parameter.name == *name
+            {
+                parameter.schema = Some(schema.clone());
+            }
+        }
+    }
+}
+
+/// Set the `example` of `operation`'s request body and success (`2xx`) response media types.
+pub fn override_examples(
+    operation: &mut Operation,
+    request_example: Option<&serde_json::Value>,
+    response_example: Option<&serde_json::Value>,
+) {
+    if let Some(example) = request_example
+        && let Some(request_body) = operation.request_body.as_mut()
+    {
+        for media_type in request_body.content.values_mut() {
+            media_type.example = Some(example.clone());
+        }
+    }
+    if let Some(example) = response_example {
+        let media_types = operation
+            .responses
+            .iter_mut()
+            .filter(|(status, _)| status.starts_with('2'))
+            .filter_map(|(_, response)| response.content.as_mut())
+            .flat_map(BTreeMap::values_mut);
+        for media_type in media_types {
+            media_type.example = Some(example.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use quote::ToTokens;
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case(Some("Json<User>"), Some("-> Json < User >"))]
+    #[case(
+        Some("Result<Json<User>, Json<ApiError>>"),
+        Some("-> Result < Json < User > , Json < ApiError > >")
+    )]
+    #[case(None, None)]
+    fn test_override_return_type(#[case] response: Option<&str>, #[case] expected: Option<&str>) {
+        let sig: Signature = syn::parse_str("async fn create() -> impl IntoResponse").unwrap();
+        let overridden = override_return_type(&sig, response)
+            .map(|sig| sig.output.to_token_stream().to_string());
+        assert_eq!(overridden.as_deref(), expected);
+    }
+
+    #[test]
+    fn test_override_responses() {
+        let mut operation = Operation {
+            operation_id: None,
+            tags: None,
+            summary: None,
+            description: None,
+            parameters: None,
+            request_body: None,
+            responses: BTreeMap::new(),
+            deprecated: None,
+            security: None,
+            extensions: BTreeMap::new(),
+        };
+        let inferred: syn::ReturnType =
+            syn::parse_str("-> Result<Json<User>, Json<ApiError>>").unwrap();
+        let known_schemas = HashSet::from([
+            "User".to_string(),
+            "ApiError".to_string(),
+            "ValidationError".to_string(),
+        ]);
+        operation.responses = parse_return_type(&inferred, &known_schemas, &HashMap::new());
+        override_responses(
+            &mut operation,
+            &[
+                (201, "Json<User>".to_string()),
+                (404, "Json<ApiError>".to_string()),
+                (422, "ValidationError".to_string()),
+                (204, "StatusCode".to_string()),
+            ],
+            &known_schemas,
+            &HashMap::new(),
+        );
+
+        let responses = serde_json::to_value(&operation.responses).unwrap();
+        let statuses: Vec<_> = responses.as_object().unwrap().keys().cloned().collect();
+        // The inferred 400 stays; the inferred 200 is replaced by the declared 201
+        assert_eq!(statuses, ["201", "204", "400", "404", "422"]);
+        let schema_ref = |status: &str| {
+            responses[status]["content"]["application/json"]["schema"]["$ref"].clone()
+        };
+        assert_eq!(schema_ref("201"), "#/components/schemas/User");
+        assert_eq!(responses["201"]["description"], "Successful response");
+        assert_eq!(schema_ref("404"), "#/components/schemas/ApiError");
+        assert_eq!(responses["404"]["description"], "Error response");
+        assert_eq!(schema_ref("422"), "#/components/schemas/ValidationError");
+        assert!(responses["204"].get("content").is_none());
+    }
+
+    #[rstest]
+    #[case("Json<CreateUser>", "application/json")]
+    #[case("Form<CreateUser>", "application/x-www-form-urlencoded")]
+    #[case("TypedMultipart<CreateUser>", "multipart/form-data")]
+    #[case("CreateUser", "application/json")]
+    fn test_override_request_body(#[case] request_body: &str, #[case] content_type: &str) {
+        let mut operation = Operation {
+            operation_id: None,
+            tags: None,
+            summary: None,
+            description: None,
+            parameters: None,
+            request_body: None,
+            responses: BTreeMap::new(),
+            deprecated: None,
+            security: None,
+            extensions: BTreeMap::new(),
+        };
+        let known_schemas = HashSet::from(["CreateUser".to_string()]);
+        override_request_body(
+            &mut operation,
+            request_body,
+            &known_schemas,
+            &HashMap::new(),
+        );
+
+        let body = serde_json::to_value(operation.request_body.unwrap()).unwrap();
+        assert_eq!(
+            body["content"][content_type]["schema"]["$ref"],
+            "#/components/schemas/CreateUser"
+        );
+    }
+
+    #[test]
+    fn test_override_binary_media_type() {
+        let mut operation = Operation {
+            operation_id: None,
+            tags: None,
+            summary: None,
+            description: None,
+            parameters: None,
+            request_body: None,
+            responses: BTreeMap::new(),
+            deprecated: None,
+            security: None,
+            extensions: BTreeMap::new(),
+        };
+        let known_schemas = HashSet::from(["ApiError".to_string()]);
+        override_request_body(&mut operation, "Bytes", &known_schemas, &HashMap::new());
+        operation.responses = parse_return_type(
+            &syn::parse_str("-> Result<Vec<u8>, Json<ApiError>>").unwrap(),
+            &known_schemas,
+            &HashMap::new(),
+        );
+        override_binary_media_type(&mut operation, "image/png");
+
+        let value = serde_json::to_value(&operation).unwrap();
+        let binary = serde_json::json!({
+            "type": "string",
+            "format": "binary",
+            "contentMediaType": "image/png"
+        });
+        assert_eq!(
+            value["requestBody"]["content"]["image/png"]["schema"],
+            binary
+        );
+        assert_eq!(
+            value["responses"]["200"]["content"]["image/png"]["schema"],
+            binary
+        );
+        assert!(
+            value["responses"]["200"]["content"]
+                .get(BINARY_MEDIA_TYPE)
+                .is_none()
+        );
+        // Non-binary bodies keep their media type
+        assert_eq!(
+            value["responses"]["400"]["content"]["application/json"]["schema"]["$ref"],
+            "#/components/schemas/ApiError"
+        );
+    }
+
+    #[test]
+    fn test_override_sse_event() {
+        let mut operation = Operation {
+            operation_id: None,
+            tags: None,
+            summary: None,
+            description: None,
+            parameters: None,
+            request_body: None,
+            responses: BTreeMap::new(),
+            deprecated: None,
+            security: None,
+            extensions: BTreeMap::new(),
+        };
+        let known_schemas = HashSet::from(["ChatEvent".to_string(), "ApiError".to_string()]);
+        operation.responses = parse_return_type(
+            &syn::parse_str("-> Result<Sse<EventStream>, Json<ApiError>>").unwrap(),
+            &known_schemas,
+            &HashMap::new(),
+        );
+        override_sse_event(&mut operation, "ChatEvent", &known_schemas, &HashMap::new());
+
+        let value = serde_json::to_value(&operation).unwrap();
+        assert_eq!(
+            value["responses"]["200"]["content"][EVENT_STREAM_MEDIA_TYPE]["schema"]["$ref"],
+            "#/components/schemas/ChatEvent"
+        );
+        assert_eq!(
+            value["responses"]["400"]["content"]["application/json"]["schema"]["$ref"],
+            "#/components/schemas/ApiError"
+        );
+    }
+
+    #[test]
+    fn test_override_parameter_schemas() {
+        use vespera_core::route::{Parameter, ParameterLocation};
+
+        let parameter = |name: &str, r#in| {
+            ParameterRef::Inline(Box::new(Parameter {
+                name: name.to_string(),
+                r#in,
+                description: None,
+                required: Some(true),
+                schema: Some(SchemaRef::Inline(Box::new(
+                    vespera_core::schema::Schema::string(),
+                ))),
+                example: None,
+                style: None,
+                explode: None,
+            }))
+        };
+        let mut operation = Operation {
+            operation_id: None,
+            tags: None,
+            summary: None,
+            description: None,
+            parameters: Some(vec![
+                parameter("id", ParameterLocation::Path),
+                parameter("X-Request-Id", ParameterLocation::Header),
+            ]),
+            request_body: None,
+            responses: BTreeMap::new(),
+            deprecated: None,
+            security: None,
+            extensions: BTreeMap::new(),
+        };
+        let known_schemas = HashSet::from(["UserId".to_string()]);
+        override_parameter_schemas(
+            &mut operation,
+            &[
+                ("id".to_string(), "UserId".to_string()),
+                ("missing".to_string(), "i32".to_string()),
+            ],
+            &known_schemas,
+            &HashMap::new(),
+        );
+
+        let value = serde_json::to_value(&operation).unwrap();
+        assert_eq!(
+            value["parameters"][0]["schema"]["$ref"],
+            "#/components/schemas/UserId"
+        );
+        assert_eq!(value["parameters"][1]["schema"]["type"], "string");
+        assert_eq!(value["parameters"].as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_override_examples() {
+        use vespera_core::route::{MediaType, RequestBody, Response};
+
+        let json = || {
+            BTreeMap::from([(
+                "application/json".to_string(),
+                MediaType {
+                    schema: None,
+                    example: None,
+                    examples: None,
+                },
+            )])
+        };
+        let response = |description: &str| Response {
+            description: description.to_string(),
+            headers: None,
+            content: Some(json()),
+            links: None,
+        };
+        let mut operation = Operation {
+            operation_id: None,
+            tags: None,
+            summary: None,
+            description: None,
+            parameters: None,
+            request_body: Some(RequestBody {
+                description: None,
+                required: Some(true),
+                content: json(),
+            }),
+            responses: BTreeMap::from([
+                ("201".to_string(), response("Created")),
+                ("400".to_string(), response("Error response")),
+            ]),
+            deprecated: None,
+            security: None,
+            extensions: BTreeMap::new(),
+        };
+        override_examples(
+            &mut operation,
+            Some(&serde_json::json!({ "name": "alice" })),
+            Some(&serde_json::json!({ "id": 1, "name": "alice" })),
+        );
+
+        let value = serde_json::to_value(&operation).unwrap();
+        assert_eq!(
+            value["requestBody"]["content"]["application/json"]["example"],
+            serde_json::json!({ "name": "alice" })
+        );
+        assert_eq!(
+            value["responses"]["201"]["content"]["application/json"]["example"],
+            serde_json::json!({ "id": 1, "name": "alice" })
+        );
+        assert!(
+            value["responses"]["400"]["content"]["application/json"]
+                .get("example")
+                .is_none()
+        );
+    }
+}
@@ -0,0 +1,103 @@
+//! Authorization policy matrix behind `vespera::policies()`.
+//!
+//! Built from route metadata rather than the `OpenAPI` document, so it is available
+//! even when no spec is written or served. Routes of merged apps are not included.
+
+use vespera_core::{
+    policy::{OperationPolicy, PolicyMatrix},
+    route::{HttpMethod, SecurityRequirement},
+};
+
+use crate::metadata::RouteMetadata;
+
+/// One policy per route, with `default_security` applied to routes without their own.
+pub fn policy_matrix(
+    routes: &[RouteMetadata],
+    default_security: Option<&[SecurityRequirement]>,
+) -> PolicyMatrix {
+    let mut operations: Vec<OperationPolicy> = routes
+        .iter()
+        .filter_map(|route| {
+            let method = HttpMethod::try_from(route.method.as_str()).ok()?;
+            Some(OperationPolicy {
+                operation_id: route.operation_id(),
+                method,
+                path: route.path.clone(),
+                tags: route.tags.clone().unwrap_or_default(),
+                requirements: route
+                    .security
+                    .as_deref()
+                    .or(default_security)
+                    .map(<[SecurityRequirement]>::to_vec)
+                    .unwrap_or_default(),
+            })
+        })
+        .collect();
+    operations.sort_by_key(|policy| {
+        let method_index = HttpMethod::ALL.iter().position(|m| *m == policy.method);
+        (policy.path.clone(), method_index)
+    });
+    PolicyMatrix { operations }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+
+    fn route(
+        method: &str,
+        path: &str,
+        security: Option<Vec<SecurityRequirement>>,
+    ) -> RouteMetadata {
+        RouteMetadata {
+            method: method.to_string(),
+            path: path.to_string(),
+            function_name: format!("{method}_handler"),
+            module_path: "routes::users".to_string(),
+            tags: Some(vec!["users".to_string()]),
+            security,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_policy_matrix() {
+        let admin: SecurityRequirement =
+            BTreeMap::from([("oauth".to_string(), vec!["admin".to_string()])]);
+        let bearer: SecurityRequirement = BTreeMap::from([("bearer".to_string(), vec![])]);
+        let routes = [
+            route("post", "/users", Some(vec![admin.clone()])),
+            route("get", "/users", None),
+            route("get", "/health", Some(vec![])),
+            route("bogus", "/users", None),
+        ];
+
+        let matrix = policy_matrix(&routes, Some(std::slice::from_ref(&bearer)));
+
+        let summary: Vec<_> = matrix
+            .operations
+            .iter()
+            .map(|p| {
+                (
+                    p.operation_id.as_str(),
+                    p.path.as_str(),
+                    p.requirements.clone(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("users_get_handler", "/health", vec![]),
+                ("users_get_handler", "/users", vec![bearer]),
+                ("users_post_handler", "/users", vec![admin]),
+            ]
+        );
+        assert_eq!(matrix.operations[1].tags, vec!["users".to_string()]);
+
+        let matrix = policy_matrix(&routes[1..2], None);
+        assert!(matrix.operations[0].is_public());
+    }
+}
@@ -0,0 +1,113 @@
+//! Path-pattern tag assignment for `vespera!(tag_rules = [...])`.
+//!
+//! Patterns are matched segment by segment against the route path:
+//! - `*` matches exactly one segment (and can be part of a segment, e.g. `v*`)
+//! - `**` matches any number of segments, including none
+//!
+//! So `/admin/**` matches `/admin`, `/admin/users` and `/admin/users/{id}`.
+
+use crate::metadata::RouteMetadata;
+
+/// Whether a single path segment matches a pattern segment containing `*` wildcards.
+fn segment_matches(pattern: &str, segment: &str) -> bool {
+    match pattern.split_once('*') {
+        None => pattern == segment,
+        Some((prefix, rest)) => {
+            let Some(remaining) = segment.strip_prefix(prefix) else {
+                return false;
+            };
+            (0..=remaining.len())
+                .filter(|&i| remaining.is_char_boundary(i))
+                .any(|i| segment_matches(rest, &remaining[i..]))
+        }
+    }
+}
+
+fn segments_match(pattern: &[&str], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((&"**", rest)) => (0..=path.len()).any(|skip| segments_match(rest, &path[skip..])),
+        Some((first, rest)) => path.split_first().is_some_and(|(segment, path_rest)| {
+            segment_matches(first, segment) && segments_match(rest, path_rest)
+        }),
+    }
+}
+
+/// Whether a route path matches a glob pattern like `/admin/**`.
+pub fn path_matches_glob(pattern: &str, path: &str) -> bool {
+    let pattern: Vec<&str> = pattern.split('/').filter(|s| !s.is_empty()).collect();
+    let path: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    segments_match(&pattern, &path)
+}
+
+/// Append the tag of every matching `(pattern, tag)` rule to each route's tags.
+pub fn apply_tag_rules(routes: &mut [RouteMetadata], tag_rules: &[(String, String)]) {
+    if tag_rules.is_empty() {
+        return;
+    }
+    for route in routes {
+        for (pattern, tag) in tag_rules {
+            if !path_matches_glob(pattern, &route.path) {
+                continue;
+            }
+            let tags = route.tags.get_or_insert_with(Vec::new);
+            if !tags.contains(tag) {
+                tags.push(tag.clone());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case("/admin/**", "/admin", true)]
+    #[case("/admin/**", "/admin/users/{id}", true)]
+    #[case("/admin/**", "/administrators", false)]
+    #[case("/admin/*", "/admin/users", true)]
+    #[case("/admin/*", "/admin/users/{id}", false)]
+    #[case("/admin/*", "/admin", false)]
+    #[case("/**/export", "/reports/daily/export", true)]
+    #[case("/**/export", "/export", true)]
+    #[case("/v*/users", "/v2/users", true)]
+    #[case("/v*/users", "/api/users", false)]
+    #[case("/users", "/users", true)]
+    #[case("/users", "/users/{id}", false)]
+    #[case("/**", "/", true)]
+    fn test_path_matches_glob(#[case] pattern: &str, #[case] path: &str, #[case] expected: bool) {
+        assert_eq!(path_matches_glob(pattern, path), expected);
+    }
+
+    #[test]
+    fn test_apply_tag_rules() {
+        let route = |path: &str, tags: Option<Vec<String>>| RouteMetadata {
+            method: "get".to_string(),
+            path: path.to_string(),
+            function_name: "handler".to_string(),
+            tags,
+            ..Default::default()
+        };
+        let mut routes = vec![
+            route("/admin/users", Some(vec!["users".to_string()])),
+            route("/users/{id}", Some(vec!["users".to_string()])),
+            route("/health", None),
+        ];
+        let rules = vec![
+            ("/admin/**".to_string(), "admin".to_string()),
+            ("/users/**".to_string(), "users".to_string()),
+        ];
+
+        apply_tag_rules(&mut routes, &rules);
+
+        assert_eq!(
+            routes[0].tags,
+            Some(vec!["users".to_string(), "admin".to_string()])
+        );
+        assert_eq!(routes[1].tags, Some(vec!["users".to_string()]));
+        assert_eq!(routes[2].tags, None);
+    }
+}
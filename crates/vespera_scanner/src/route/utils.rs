@@ -1,4 +1,10 @@
-use crate::{args::RouteArgs, http::is_http_method};
+use vespera_core::route::SecurityRequirement;
+
+use crate::{
+    args::RouteArgs,
+    http::is_http_method,
+    metadata::{RouteCache, RouteLimits, RouteLink},
+};
 
 /// Extract doc comments from attributes
 /// Returns concatenated doc comment string or None if no doc comments
@@ -33,6 +39,19 @@ pub fn extract_doc_comment(attrs: &[syn::Attribute]) -> Option<String> {
     }
 }
 
+/// Split a doc comment into `(summary, description)`: the first non-empty line,
+/// and the remaining lines with surrounding blank lines removed.
+pub fn split_doc_comment(doc: Option<&str>) -> (Option<String>, Option<String>) {
+    let Some(doc) = doc else {
+        return (None, None);
+    };
+    let mut lines = doc.lines().skip_while(|line| line.trim().is_empty());
+    let summary = lines.next().map(|line| line.trim().to_string());
+    let rest = lines.collect::<Vec<_>>().join("\n");
+    let rest = rest.trim_matches('\n');
+    (summary, (!rest.is_empty()).then(|| rest.to_string()))
+}
+
 #[derive(Debug)]
 pub struct RouteInfo {
     pub method: String,
@@ -40,6 +59,23 @@ pub struct RouteInfo {
     pub error_status: Option<Vec<u16>>,
     pub tags: Option<Vec<String>>,
     pub description: Option<String>,
+    pub summary: Option<String>,
+    pub deprecated: bool,
+    pub security: Option<Vec<SecurityRequirement>>,
+    pub limits: Option<RouteLimits>,
+    pub cache: Option<RouteCache>,
+    pub response: Option<String>,
+    pub request_body: Option<String>,
+    pub responses: Option<Vec<(u16, String)>>,
+    pub params: Option<Vec<(String, String)>>,
+    pub links: Option<Vec<RouteLink>>,
+    pub request_example: Option<serde_json::Value>,
+    pub response_example: Option<serde_json::Value>,
+    pub status: Option<u16>,
+    pub sdk_group: Option<String>,
+    pub operation_id: Option<String>,
+    pub content_type: Option<String>,
+    pub sse_event: Option<String>,
 }
 
 pub fn check_route_by_meta(meta: &syn::Meta) -> bool {
@@ -66,6 +102,7 @@ pub fn check_route_by_meta(meta: &syn::Meta) -> bool {
     }
 }
 
+#[allow(clippy::too_many_lines)]
 pub fn extract_route_info(attrs: &[syn::Attribute]) -> Option<RouteInfo> {
     for attr in attrs {
         // Check if attribute path is "vespera" or "route"
@@ -128,6 +165,44 @@ pub fn extract_route_info(attrs: &[syn::Attribute]) -> Option<RouteInfo> {
                             error_status,
                             tags,
                             description,
+                            security: route_args.security,
+                            limits: route_args.limits,
+                            cache: route_args.cache,
+                            links: route_args.links.clone(),
+                            request_example: route_args.request_example.clone(),
+                            response_example: route_args.response_example.clone(),
+                            response: route_args
+                                .response
+                                .as_ref()
+                                .map(|ty| quote::quote!(#ty).to_string()),
+                            request_body: route_args
+                                .request_body
+                                .as_ref()
+                                .map(|ty| quote::quote!(#ty).to_string()),
+                            responses: route_args.responses.as_ref().map(|responses| {
+                                responses
+                                    .iter()
+                                    .map(|(status, ty)| (*status, quote::quote!(#ty).to_string()))
+                                    .collect()
+                            }),
+                            params: route_args.params.as_ref().map(|params| {
+                                params
+                                    .iter()
+                                    .map(|(name, ty)| {
+                                        (name.clone(), quote::quote!(#ty).to_string())
+                                    })
+                                    .collect()
+                            }),
+                            status: route_args.status,
+                            sdk_group: route_args.sdk_group.as_ref().map(syn::LitStr::value),
+                            operation_id: route_args.operation_id.as_ref().map(syn::LitStr::value),
+                            content_type: route_args.content_type.as_ref().map(syn::LitStr::value),
+                            sse_event: route_args
+                                .sse_event
+                                .as_ref()
+                                .map(|ty| quote::quote!(#ty).to_string()),
+                            summary: route_args.summary.as_ref().map(syn::LitStr::value),
+                            deprecated: route_args.deprecated,
                         });
                     }
                 }
@@ -146,6 +221,23 @@ pub fn extract_route_info(attrs: &[syn::Attribute]) -> Option<RouteInfo> {
                                 error_status: None,
                                 tags: None,
                                 description: None,
+                                security: None,
+                                limits: None,
+                                cache: None,
+                                response: None,
+                                request_body: None,
+                                responses: None,
+                                params: None,
+                                links: None,
+                                request_example: None,
+                                response_example: None,
+                                status: None,
+                                sdk_group: None,
+                                operation_id: None,
+                                content_type: None,
+                                sse_event: None,
+                                summary: None,
+                                deprecated: false,
                             });
                         }
                     }
@@ -158,6 +250,23 @@ pub fn extract_route_info(attrs: &[syn::Attribute]) -> Option<RouteInfo> {
                         error_status: None,
                         tags: None,
                         description: None,
+                        security: None,
+                        limits: None,
+                        cache: None,
+                        response: None,
+                        request_body: None,
+                        responses: None,
+                        params: None,
+                        links: None,
+                        request_example: None,
+                        response_example: None,
+                        status: None,
+                        sdk_group: None,
+                        operation_id: None,
+                        content_type: None,
+                        sse_event: None,
+                        summary: None,
+                        deprecated: false,
                     });
                 }
             }
@@ -386,6 +495,34 @@ mod tests {
         }
     }
 
+    #[rstest]
+    #[case(None, None, None)]
+    #[case(Some("Get users"), Some("Get users"), None)]
+    #[case(
+        Some("Get users\n\nReturns every user.\nPaginated."),
+        Some("Get users"),
+        Some("Returns every user.\nPaginated.")
+    )]
+    #[case(Some("\nGet users\n\n"), Some("Get users"), None)]
+    fn test_split_doc_comment(
+        #[case] doc: Option<&str>,
+        #[case] summary: Option<&str>,
+        #[case] description: Option<&str>,
+    ) {
+        let (s, d) = split_doc_comment(doc);
+        assert_eq!(s.as_deref(), summary);
+        assert_eq!(d.as_deref(), description);
+    }
+
+    #[test]
+    fn test_extract_route_info_with_summary() {
+        let code =
+            r#"#[route(get, summary = "List users", description = "All of them")] fn test() {}"#;
+        let route_info = extract_route_info(&parse_attrs_from_code(code)).unwrap();
+        assert_eq!(route_info.summary, Some("List users".to_string()));
+        assert_eq!(route_info.description, Some("All of them".to_string()));
+    }
+
     // Tests for tags and description in extract_route_info
     #[test]
     fn test_extract_route_info_with_tags() {
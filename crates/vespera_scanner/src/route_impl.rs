@@ -0,0 +1,1070 @@
+//! Route attribute macro implementation.
+//!
+//! This module implements the `#[vespera::route]` attribute macro that validates
+//! and processes handler functions for route registration.
+//!
+//! # Overview
+//!
+//! The `#[route]` attribute is applied to handler functions to:
+//! - Validate that the function is `pub async fn`
+//! - Parse route configuration (HTTP method, path, tags, etc.)
+//! - Mark the function for route discovery by the `vespera!` macro
+//! - Check at compile time that the return type implements `IntoResponse`, so a
+//!   bare custom struct is reported at the handler instead of deep in axum's generics
+//! - Warn about arguments the `OpenAPI` generator cannot document (e.g. `Query<HashMap<..>>`
+//!   or a bare `i32`); with `VESPERA_STRICT=1` these become errors
+//! - Warn when the response cannot be inferred from the return type (`impl IntoResponse`,
+//!   `Response`) and no `response = ...` override is given
+//!
+//! # Route Requirements
+//!
+//! All handler functions must:
+//! - Be public (`pub`)
+//! - Be async (`async fn`)
+//! - Accept standard Axum extractors (Path, Query, Json, etc.)
+//! - Return a response type (Json, String, `StatusCode`, etc.)
+//!
+//! # Key Functions
+//!
+//! - [`validate_route_fn`] - Validate route function signature
+//! - [`process_route_attribute`] - Parse and process the route attribute
+//!
+//! # Example
+//!
+//! ```ignore
+//! #[vespera::route(get, path = "/{id}", tags = ["users"])]
+//! pub async fn get_user(Path(id): Path<u32>) -> Json<User> {
+//!     Json(User { id, name: "Alice".into() })
+//! }
+//! ```
+
+use std::collections::HashSet;
+use std::sync::{LazyLock, Mutex};
+
+use proc_macro2::TokenTree;
+use quote::{ToTokens, quote, quote_spanned};
+use syn::spanned::Spanned;
+use vespera_core::route::SecurityRequirement;
+
+use crate::{
+    args,
+    diagnostics::{Diagnostic, Level},
+    metadata::{RouteCache, RouteLimits, RouteLink},
+    parser::{extract_path_parameters, ignored_parameter_reason},
+};
+/// Metadata stored by `#[route]` for later consumption by `vespera!()`.
+///
+/// Each invocation of `#[route]` pushes one entry into [`ROUTE_STORAGE`].
+/// The `vespera!()` macro reads this storage to supplement file-based route discovery.
+#[derive(Debug, Clone)]
+pub struct StoredRouteInfo {
+    /// Function name (e.g., `"get_user"`)
+    pub fn_name: String,
+    /// HTTP method — stored for Phase 3 (skip file re-parsing)
+    #[allow(dead_code)]
+    pub method: Option<String>,
+    /// Custom path from `path = "/{id}"` — stored for Phase 3
+    #[allow(dead_code)]
+    pub custom_path: Option<String>,
+    /// Additional error status codes from `error_status = [400, 404]`
+    pub error_status: Option<Vec<u16>>,
+    /// Tags for `OpenAPI` grouping from `tags = ["users"]`
+    pub tags: Option<Vec<String>>,
+    /// Description from `description = "Get user by ID"`, else the doc comment after its first line
+    pub description: Option<String>,
+    /// Summary from `summary = "Get user"`, else the doc comment's first line
+    pub summary: Option<String>,
+    /// Whether the route was marked `deprecated`
+    pub deprecated: bool,
+    /// Security alternatives from `security = [["bearer"], ["api_key"]]`
+    pub security: Option<Vec<SecurityRequirement>>,
+    /// Body size and timeout limits from `limits = (body = "1MB", timeout = "30s")`
+    pub limits: Option<RouteLimits>,
+    /// `Cache-Control` policy from `cache = (max_age = 60, public)`
+    pub cache: Option<RouteCache>,
+    /// Return type override from `response = Json<User>`
+    pub response: Option<String>,
+    /// Body extractor override from `request_body = Json<CreateUser>`
+    pub request_body: Option<String>,
+    /// Per-status response overrides from `responses(200 = Json<User>, 404 = Json<ApiError>)`
+    pub responses: Option<Vec<(u16, String)>>,
+    /// Parameter schema overrides from `params(id = Uuid)`
+    pub params: Option<Vec<(String, String)>>,
+    /// Response links from `links(GetUser = (operation_id = "..."))`
+    pub links: Option<Vec<RouteLink>>,
+    /// Request body example from `request_example = json!(...)`
+    pub request_example: Option<serde_json::Value>,
+    /// Success response example from `response_example = json!(...)`
+    pub response_example: Option<serde_json::Value>,
+    /// Success status from `status = 201`
+    pub status: Option<u16>,
+    /// SDK group from `sdk_group = "Users"`
+    pub sdk_group: Option<String>,
+    /// `operationId` override from `operation_id = "listUsers"`
+    pub operation_id: Option<String>,
+    /// Media type of raw byte bodies from `content_type = "image/png"`
+    pub content_type: Option<String>,
+    /// Event payload type from `sse_event = ChatEvent`
+    pub sse_event: Option<String>,
+    /// Source file path from `Span::call_site().local_file()` (requires Rust 1.88+)
+    /// `None` on older Rust — collector falls back to full file parsing.
+    pub file_path: Option<String>,
+    /// Full function item as string for later AST re-parsing (Phase 3)
+    #[allow(dead_code)]
+    pub fn_item_str: String,
+}
+
+/// Global storage for route metadata collected by `#[route]` attribute macros.
+/// Read by `vespera!()` to supplement file-based route discovery.
+pub static ROUTE_STORAGE: LazyLock<Mutex<Vec<StoredRouteInfo>>> =
+    LazyLock::new(|| Mutex::new(Vec::new()));
+
+/// Extract `u16` error status codes from a `syn::ExprArray`.
+fn extract_error_status_codes(arr: &syn::ExprArray) -> Option<Vec<u16>> {
+    let codes: Vec<u16> = arr
+        .elems
+        .iter()
+        .filter_map(|elem| {
+            if let syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Int(lit_int),
+                ..
+            }) = elem
+            {
+                lit_int.base10_parse::<u16>().ok()
+            } else {
+                None
+            }
+        })
+        .collect();
+    if codes.is_empty() { None } else { Some(codes) }
+}
+
+/// Extract `String` tags from a `syn::ExprArray`.
+fn extract_tag_strings(arr: &syn::ExprArray) -> Option<Vec<String>> {
+    let tags: Vec<String> = arr
+        .elems
+        .iter()
+        .filter_map(|elem| {
+            if let syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Str(lit_str),
+                ..
+            }) = elem
+            {
+                Some(lit_str.value())
+            } else {
+                None
+            }
+        })
+        .collect();
+    if tags.is_empty() { None } else { Some(tags) }
+}
+
+/// Validate route function - must be pub and async
+pub fn validate_route_fn(item_fn: &syn::ItemFn) -> Result<(), syn::Error> {
+    if !matches!(item_fn.vis, syn::Visibility::Public(_)) {
+        return Err(Diagnostic::RouteNotPublic.error(
+            item_fn.sig.fn_token.span,
+            "#[route] attribute: function must be public. Add `pub` before `fn`.",
+        ));
+    }
+    if item_fn.sig.asyncness.is_none() {
+        return Err(Diagnostic::RouteNotAsync.error(
+            item_fn.sig.fn_token.span,
+            "#[route] attribute: function must be async. Add `async` before `fn`.",
+        ));
+    }
+    Ok(())
+}
+
+/// Whether a type mentions `impl Trait`, whose concrete type cannot be named.
+fn contains_impl_trait(tokens: proc_macro2::TokenStream) -> bool {
+    mentions_unnameable(tokens, &[])
+}
+
+/// Whether a type mentions `impl Trait` or one of `names`, none of which can be named
+/// outside the handler's signature.
+fn mentions_unnameable(tokens: proc_macro2::TokenStream, names: &[String]) -> bool {
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Ident(ident) => ident == "impl" || names.iter().any(|name| ident == name),
+        TokenTree::Group(group) => mentions_unnameable(group.stream(), names),
+        _ => false,
+    })
+}
+
+/// Compile-time check that the handler's response implements `IntoResponse`.
+///
+/// Declares a marker struct named after the handler so the error names it, then calls
+/// `vespera::assert_handler_response` on the function itself, which also covers
+/// `impl Trait` returns. Generic handlers cannot be referenced without type arguments,
+/// so their return type is checked directly unless it mentions a generic parameter.
+/// Spanned on the return type so a missing `IntoResponse` impl is reported there.
+fn into_response_check(item_fn: &syn::ItemFn) -> proc_macro2::TokenStream {
+    let syn::ReturnType::Type(_, ty) = &item_fn.sig.output else {
+        return proc_macro2::TokenStream::new();
+    };
+    let fn_name = &item_fn.sig.ident;
+    let generics = &item_fn.sig.generics.params;
+    let assertion = if generics.is_empty() {
+        quote_spanned! {ty.span()=>
+            vespera::assert_handler_response::<#fn_name, _, _>(&#fn_name)
+        }
+    } else {
+        let names: Vec<String> = generics
+            .iter()
+            .map(|param| match param {
+                syn::GenericParam::Type(param) => param.ident.to_string(),
+                syn::GenericParam::Const(param) => param.ident.to_string(),
+                syn::GenericParam::Lifetime(param) => param.lifetime.ident.to_string(),
+            })
+            .collect();
+        if mentions_unnameable(ty.to_token_stream(), &names) {
+            return proc_macro2::TokenStream::new();
+        }
+        quote_spanned! {ty.span()=>
+            vespera::assert_route_response::<#fn_name, #ty>()
+        }
+    };
+    let cfgs = cfg_attrs(item_fn);
+    quote_spanned! {ty.span()=>
+        #(#cfgs)*
+        const _: fn() = || {
+            #[allow(non_camel_case_types)]
+            struct #fn_name {}
+            #assertion;
+        };
+    }
+}
+
+/// `#[cfg]` attributes of the handler, repeated on generated items.
+fn cfg_attrs(item_fn: &syn::ItemFn) -> impl Iterator<Item = &syn::Attribute> {
+    item_fn
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("cfg"))
+}
+
+/// Render tokens the way they are written, e.g. `Query<HashMap<String, String>>`.
+fn compact_tokens(tokens: &impl ToTokens) -> String {
+    let text = tokens.to_token_stream().to_string();
+    let chars: Vec<char> = text.chars().collect();
+    let is_word = |c: Option<&char>| c.is_some_and(|c| c.is_alphanumeric() || *c == '_');
+    let mut out = String::with_capacity(text.len());
+    for (i, &c) in chars.iter().enumerate() {
+        if c == ' ' && !(is_word(chars.get(i.wrapping_sub(1))) && is_word(chars.get(i + 1))) {
+            continue;
+        }
+        out.push(c);
+        let is_path_separator = chars.get(i + 1) == Some(&':') || (i > 0 && chars[i - 1] == ':');
+        if c == ',' || (c == ':' && !is_path_separator) {
+            out.push(' ');
+        }
+    }
+    out
+}
+
+/// Diagnose arguments that `OpenAPI` generation silently skips.
+///
+/// Each becomes a warning spanned on the argument (a use of a `#[deprecated]` item,
+/// the only warning a proc macro can raise on stable), an error when denied, or nothing
+/// when allowed.
+fn undocumented_argument_diagnostics(
+    item_fn: &syn::ItemFn,
+    path: Option<&str>,
+    level: Level,
+) -> syn::Result<proc_macro2::TokenStream> {
+    if level == Level::Allow {
+        return Ok(proc_macro2::TokenStream::new());
+    }
+    let path_params = extract_path_parameters(path.unwrap_or_default());
+    let path_param_set: HashSet<String> = path_params.into_iter().collect();
+    let mut warnings = proc_macro2::TokenStream::new();
+    let mut errors: Option<syn::Error> = None;
+    for arg in &item_fn.sig.inputs {
+        let Some(reason) = ignored_parameter_reason(arg, &path_param_set) else {
+            continue;
+        };
+        let message = Diagnostic::UndocumentedArgument.message(format_args!(
+            "vespera: argument `{}` of handler `{}` is not documented in OpenAPI: {reason}.",
+            compact_tokens(arg),
+            item_fn.sig.ident
+        ));
+        if level == Level::Deny {
+            let error = syn::Error::new(arg.span(), message);
+            match &mut errors {
+                Some(errors) => errors.combine(error),
+                None => errors = Some(error),
+            }
+            continue;
+        }
+        let cfgs = cfg_attrs(item_fn);
+        warnings.extend(quote_spanned! {arg.span()=>
+            #(#cfgs)*
+            const _: () = {
+                #[deprecated(note = #message)]
+                struct UndocumentedArgument;
+                let _ = UndocumentedArgument;
+            };
+        });
+    }
+    errors.map_or(Ok(warnings), Err)
+}
+
+/// Why the response of a handler returning `ty` cannot be inferred, if it cannot.
+fn uninferable_response_reason(ty: &syn::Type) -> Option<&'static str> {
+    // `Sse<impl Stream<...>>` is documented as an event stream whatever the stream is
+    let is_sse = matches!(ty, syn::Type::Path(type_path) if type_path
+        .path
+        .segments
+        .last()
+        .is_some_and(|segment| segment.ident == "Sse"));
+    if !is_sse && contains_impl_trait(ty.to_token_stream()) {
+        return Some("`impl Trait` hides the concrete response type");
+    }
+    if let syn::Type::Path(type_path) = ty
+        && type_path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "Response")
+    {
+        return Some("`Response` does not carry a body type");
+    }
+    None
+}
+
+/// Diagnose a return type that response inference has to give up on.
+///
+/// Reported like [`undocumented_argument_diagnostics`]; silenced by an explicit
+/// `response = ...` override or a 2xx entry in `responses(...)`.
+fn uninferable_response_diagnostic(
+    item_fn: &syn::ItemFn,
+    has_override: bool,
+    level: Level,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let syn::ReturnType::Type(_, ty) = &item_fn.sig.output else {
+        return Ok(proc_macro2::TokenStream::new());
+    };
+    let Some(reason) =
+        uninferable_response_reason(ty).filter(|_| !has_override && level != Level::Allow)
+    else {
+        return Ok(proc_macro2::TokenStream::new());
+    };
+    let message = Diagnostic::UninferableResponse.message(format_args!(
+        "vespera: cannot infer the response of handler `{}` for OpenAPI: {reason}. Add `response = ...` to `#[route]`, e.g. `response = Json<User>`.",
+        item_fn.sig.ident
+    ));
+    if level == Level::Deny {
+        return Err(syn::Error::new(ty.span(), message));
+    }
+    let cfgs = cfg_attrs(item_fn);
+    Ok(quote_spanned! {ty.span()=>
+        #(#cfgs)*
+        const _: () = {
+            #[deprecated(note = #message)]
+            struct UninferredResponse;
+            let _ = UninferredResponse;
+        };
+    })
+}
+
+/// What `#[route]` records for `item_fn`: its arguments, with the doc comment filling in
+/// a missing summary and description.
+fn stored_route_info(
+    route_args: args::RouteArgs,
+    item_fn: &syn::ItemFn,
+    fn_item_str: String,
+    file_path: Option<String>,
+) -> StoredRouteInfo {
+    let doc = crate::route::extract_doc_comment(&item_fn.attrs);
+    let (doc_summary, doc_description) = crate::route::split_doc_comment(doc.as_deref());
+
+    StoredRouteInfo {
+        fn_name: item_fn.sig.ident.to_string(),
+        method: route_args.method.as_ref().map(syn::Ident::to_string),
+        custom_path: route_args.path.as_ref().map(syn::LitStr::value),
+        error_status: route_args
+            .error_status
+            .as_ref()
+            .and_then(extract_error_status_codes),
+        tags: route_args.tags.as_ref().and_then(extract_tag_strings),
+        description: route_args
+            .description
+            .as_ref()
+            .map(syn::LitStr::value)
+            .or(doc_description),
+        summary: route_args
+            .summary
+            .as_ref()
+            .map(syn::LitStr::value)
+            .or(doc_summary),
+        deprecated: route_args.deprecated,
+        fn_item_str,
+        file_path,
+        security: route_args.security,
+        limits: route_args.limits,
+        cache: route_args.cache,
+        links: route_args.links,
+        request_example: route_args.request_example,
+        response_example: route_args.response_example,
+        response: route_args
+            .response
+            .as_ref()
+            .map(|ty| ty.to_token_stream().to_string()),
+        request_body: route_args
+            .request_body
+            .as_ref()
+            .map(|ty| ty.to_token_stream().to_string()),
+        responses: route_args.responses.as_ref().map(|responses| {
+            responses
+                .iter()
+                .map(|(status, ty)| (*status, ty.to_token_stream().to_string()))
+                .collect()
+        }),
+        params: route_args.params.as_ref().map(|params| {
+            params
+                .iter()
+                .map(|(name, ty)| (name.clone(), ty.to_token_stream().to_string()))
+                .collect()
+        }),
+        status: route_args.status,
+        sdk_group: route_args.sdk_group.as_ref().map(syn::LitStr::value),
+        operation_id: route_args.operation_id.as_ref().map(syn::LitStr::value),
+        content_type: route_args.content_type.as_ref().map(syn::LitStr::value),
+        sse_event: route_args
+            .sse_event
+            .as_ref()
+            .map(|ty| ty.to_token_stream().to_string()),
+    }
+}
+
+/// The entries `#[route]` would push to [`ROUTE_STORAGE`] for the handlers in `file_asts`,
+/// for callers running outside the build (docs pages regenerating their spec), where
+/// the storage is empty. Sorted by file path, like the collector's output.
+pub fn route_storage_from_files(
+    file_asts: &std::collections::HashMap<String, syn::File>,
+) -> Vec<StoredRouteInfo> {
+    let mut files: Vec<_> = file_asts.iter().collect();
+    files.sort_by_key(|(file_path, _)| *file_path);
+    let mut storage = Vec::new();
+    for (file_path, file) in files {
+        for item in &file.items {
+            let syn::Item::Fn(item_fn) = item else {
+                continue;
+            };
+            let Some(attr) = item_fn
+                .attrs
+                .iter()
+                .find(|attr| crate::route::check_route_by_meta(&attr.meta))
+            else {
+                continue;
+            };
+            let route_args = match &attr.meta {
+                syn::Meta::List(meta_list) => meta_list.parse_args::<args::RouteArgs>(),
+                _ => syn::parse2::<args::RouteArgs>(proc_macro2::TokenStream::new()),
+            };
+            let Ok(route_args) = route_args else {
+                continue;
+            };
+            // `#[route]` sees the function without its own attribute
+            let mut handler = item_fn.clone();
+            handler
+                .attrs
+                .retain(|attr| !crate::route::check_route_by_meta(&attr.meta));
+            storage.push(stored_route_info(
+                route_args,
+                item_fn,
+                handler.to_token_stream().to_string(),
+                Some(file_path.clone()),
+            ));
+        }
+    }
+    storage
+}
+
+/// Process route attribute - extracted for testability
+#[allow(clippy::needless_pass_by_value)]
+pub fn process_route_attribute(
+    attr: proc_macro2::TokenStream,
+    item: proc_macro2::TokenStream,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let route_args = syn::parse2::<args::RouteArgs>(attr)?;
+    let item_fn: syn::ItemFn = syn::parse2(item.clone()).map_err(|e| Diagnostic::RouteNotFunction.error(e.span(), "#[route] attribute: can only be applied to functions, not other items. Move or remove the attribute."))?;
+    validate_route_fn(&item_fn)?;
+    let diagnostics = undocumented_argument_diagnostics(
+        &item_fn,
+        route_args.path.as_ref().map(syn::LitStr::value).as_deref(),
+        Diagnostic::UndocumentedArgument.level(),
+    )?;
+    let has_response_override = route_args.response.is_some()
+        || route_args.responses.as_ref().is_some_and(|responses| {
+            responses
+                .iter()
+                .any(|(status, _)| (200..300).contains(status))
+        });
+    let response_diagnostic = uninferable_response_diagnostic(
+        &item_fn,
+        has_response_override,
+        Diagnostic::UninferableResponse.level(),
+    )?;
+
+    // Store route metadata for later consumption by vespera!() macro
+    let stored = stored_route_info(
+        route_args,
+        &item_fn,
+        item.to_string(),
+        proc_macro2::Span::call_site()
+            .local_file()
+            .map(|p| p.display().to_string()),
+    );
+    ROUTE_STORAGE
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .push(stored);
+
+    let check = into_response_check(&item_fn);
+    Ok(quote!(#item #check #diagnostics #response_diagnostic))
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::quote;
+    use rstest::rstest;
+
+    use super::*;
+
+    // ========== Tests for validate_route_fn ==========
+
+    #[test]
+    fn test_validate_route_fn_not_public() {
+        let item: syn::ItemFn = syn::parse_quote! {
+            async fn private_handler() -> String {
+                "test".to_string()
+            }
+        };
+        let result = validate_route_fn(&item);
+        assert!(result.is_err());
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("function must be public")
+        );
+    }
+
+    #[test]
+    fn test_validate_route_fn_not_async() {
+        let item: syn::ItemFn = syn::parse_quote! {
+            pub fn sync_handler() -> String {
+                "test".to_string()
+            }
+        };
+        let result = validate_route_fn(&item);
+        assert!(result.is_err());
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("function must be async")
+        );
+    }
+
+    #[test]
+    fn test_validate_route_fn_valid() {
+        let item: syn::ItemFn = syn::parse_quote! {
+            pub async fn valid_handler() -> String {
+                "test".to_string()
+            }
+        };
+        let result = validate_route_fn(&item);
+        assert!(result.is_ok());
+    }
+
+    // ========== Tests for process_route_attribute ==========
+
+    #[test]
+    fn test_process_route_attribute_valid() {
+        let attr = quote!(get);
+        let item = quote!(
+            pub async fn handler() -> String {
+                "ok".to_string()
+            }
+        );
+        let result = process_route_attribute(attr, item.clone());
+        assert!(result.is_ok());
+        // Should return the original item followed by the IntoResponse check
+        assert_eq!(
+            result.unwrap().to_string(),
+            quote!(
+                #item
+                const _: fn() = || {
+                    #[allow(non_camel_case_types)]
+                    struct handler {}
+                    vespera::assert_handler_response::<handler, _, _>(&handler);
+                };
+            )
+            .to_string()
+        );
+    }
+
+    #[rstest]
+    #[case(quote!(pub async fn handler() -> Json<User> { todo!() }), Some("vespera :: assert_handler_response :: < handler , _ , _ > (& handler)"))]
+    #[case(quote!(pub async fn handler() -> impl IntoResponse { "ok" }), Some("vespera :: assert_handler_response :: < handler , _ , _ > (& handler)"))]
+    #[case(quote!(pub async fn handler<S: Clone>(state: S) -> Json<User> { todo!() }), Some("vespera :: assert_route_response :: < handler , Json < User > > ()"))]
+    #[case(quote!(pub async fn handler() {}), None)]
+    #[case(quote!(pub async fn handler<T: IntoResponse>() -> T { todo!() }), None)]
+    #[case(quote!(pub async fn handler<T>() -> (StatusCode, Vec<T>) { todo!() }), None)]
+    #[case(quote!(pub async fn handler<T>() -> impl IntoResponse { todo!() }), None)]
+    fn test_into_response_check(
+        #[case] item: proc_macro2::TokenStream,
+        #[case] expected: Option<&str>,
+    ) {
+        let item_fn: syn::ItemFn = syn::parse2(item).unwrap();
+        let check = into_response_check(&item_fn).to_string();
+        match expected {
+            Some(assertion) => assert_eq!(
+                check,
+                format!(
+                    "const _ : fn () = || {{ # [allow (non_camel_case_types)] struct handler {{ }} {assertion} ; }} ;"
+                )
+            ),
+            None => assert!(check.is_empty(), "{check}"),
+        }
+    }
+
+    #[rstest]
+    #[case(quote!(Query(q): Query<HashMap<String, String>>), "Query(q): Query<HashMap<String, String>>")]
+    #[case(quote!(mut id: std::primitive::i32), "mut id: std::primitive::i32")]
+    #[case(quote!(body: &'static str), "body: &'static str")]
+    fn test_compact_tokens(#[case] tokens: proc_macro2::TokenStream, #[case] expected: &str) {
+        assert_eq!(compact_tokens(&tokens), expected);
+    }
+
+    #[test]
+    fn test_undocumented_argument_diagnostics() {
+        let item_fn: syn::ItemFn = syn::parse2(quote!(
+            pub async fn search(
+                Path(id): Path<i32>,
+                Query(filters): Query<HashMap<String, String>>,
+                page: u32,
+            ) -> String {
+                todo!()
+            }
+        ))
+        .unwrap();
+
+        let warnings = undocumented_argument_diagnostics(&item_fn, Some("/{id}"), Level::Warn)
+            .unwrap()
+            .to_string();
+        assert_eq!(warnings.matches("deprecated").count(), 2, "{warnings}");
+        assert!(
+            warnings.contains("argument `Query(filters): Query<HashMap<String, String>>` of handler `search` is not documented in OpenAPI: a map"),
+            "{warnings}"
+        );
+        assert!(warnings.contains("argument `page: u32`"), "{warnings}");
+
+        assert!(warnings.contains("[VESPERA004]"), "{warnings}");
+
+        let err =
+            undocumented_argument_diagnostics(&item_fn, Some("/{id}"), Level::Deny).unwrap_err();
+        assert_eq!(err.into_iter().count(), 2);
+        assert!(
+            undocumented_argument_diagnostics(&item_fn, Some("/{id}"), Level::Allow)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_undocumented_argument_diagnostics_path_param() {
+        let item_fn: syn::ItemFn = syn::parse2(quote!(
+            pub async fn get_item(id: i32) -> String {
+                todo!()
+            }
+        ))
+        .unwrap();
+        assert!(
+            undocumented_argument_diagnostics(&item_fn, Some("/{id}"), Level::Deny)
+                .unwrap()
+                .is_empty()
+        );
+        assert!(undocumented_argument_diagnostics(&item_fn, None, Level::Deny).is_err());
+    }
+
+    #[rstest]
+    #[case(quote!(pub async fn h() -> impl IntoResponse { todo!() }), false, Some("`impl Trait` hides"))]
+    #[case(quote!(pub async fn h() -> axum::response::Response { todo!() }), false, Some("`Response` does not carry"))]
+    #[case(quote!(pub async fn h() -> impl IntoResponse { todo!() }), true, None)]
+    #[case(quote!(pub async fn h() -> Json<User> { todo!() }), false, None)]
+    #[case(quote!(pub async fn h() -> Sse<impl Stream<Item = Result<Event, Infallible>>> { todo!() }), false, None)]
+    #[case(quote!(pub async fn h() {}), false, None)]
+    fn test_uninferable_response_diagnostic(
+        #[case] item: proc_macro2::TokenStream,
+        #[case] has_override: bool,
+        #[case] expected: Option<&str>,
+    ) {
+        let item_fn: syn::ItemFn = syn::parse2(item).unwrap();
+        let warning = uninferable_response_diagnostic(&item_fn, has_override, Level::Warn)
+            .unwrap()
+            .to_string();
+        let strict = uninferable_response_diagnostic(&item_fn, has_override, Level::Deny);
+        if let Some(reason) = expected {
+            assert!(warning.contains("deprecated"), "{warning}");
+            assert!(warning.contains(reason), "{warning}");
+            assert!(warning.contains("Add `response = ...`"), "{warning}");
+            assert!(warning.contains("[VESPERA005]"), "{warning}");
+            assert!(strict.unwrap_err().to_string().contains(reason));
+        } else {
+            assert!(warning.is_empty(), "{warning}");
+            assert!(strict.unwrap().is_empty());
+        }
+    }
+
+    #[test]
+    fn test_route_storage_from_files() {
+        let file: syn::File = syn::parse_quote! {
+            /// List users
+            #[vespera::route(get, path = "/all", response = Json<Vec<User>>)]
+            pub async fn list(req: Request) -> impl IntoResponse { todo!() }
+
+            #[route]
+            pub async fn show() -> String { todo!() }
+
+            pub async fn helper() {}
+        };
+        let storage = route_storage_from_files(&std::collections::HashMap::from([(
+            "src/routes/users.rs".to_string(),
+            file,
+        )]));
+
+        assert_eq!(storage.len(), 2);
+        assert_eq!(storage[0].fn_name, "list");
+        assert_eq!(storage[0].method.as_deref(), Some("get"));
+        assert_eq!(storage[0].custom_path.as_deref(), Some("/all"));
+        assert_eq!(
+            storage[0].response.as_deref(),
+            Some("Json < Vec < User > >")
+        );
+        assert_eq!(storage[0].summary.as_deref(), Some("List users"));
+        assert_eq!(storage[0].file_path.as_deref(), Some("src/routes/users.rs"));
+        assert!(!storage[0].fn_item_str.contains("route"));
+        assert_eq!(storage[1].fn_name, "show");
+        assert_eq!(storage[1].method, None);
+    }
+
+    #[test]
+    fn test_into_response_check_keeps_cfg() {
+        let item_fn: syn::ItemFn = syn::parse2(quote!(
+            #[cfg(feature = "admin")]
+            #[doc = "Admin only"]
+            pub async fn handler() -> String {
+                todo!()
+            }
+        ))
+        .unwrap();
+        let check = into_response_check(&item_fn).to_string();
+        assert!(
+            check.starts_with("# [cfg (feature = \"admin\")] const _"),
+            "{check}"
+        );
+        assert!(!check.contains("doc"), "{check}");
+    }
+
+    #[test]
+    fn test_process_route_attribute_invalid_attr() {
+        let attr = quote!(invalid_method);
+        let item = quote!(
+            pub async fn handler() -> String {
+                "ok".to_string()
+            }
+        );
+        let result = process_route_attribute(attr, item);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_process_route_attribute_not_function() {
+        let attr = quote!(get);
+        let item = quote!(
+            struct NotAFunction;
+        );
+        let result = process_route_attribute(attr, item);
+        assert!(result.is_err());
+        let err = result.unwrap_err().to_string();
+        assert!(err.contains("can only be applied to functions"));
+    }
+
+    #[test]
+    fn test_process_route_attribute_not_public() {
+        let attr = quote!(get);
+        let item = quote!(
+            async fn private_handler() -> String {
+                "ok".to_string()
+            }
+        );
+        let result = process_route_attribute(attr, item);
+        assert!(result.is_err());
+        let err = result.unwrap_err().to_string();
+        assert!(err.contains("function must be public"));
+    }
+
+    #[test]
+    fn test_process_route_attribute_not_async() {
+        let attr = quote!(get);
+        let item = quote!(
+            pub fn sync_handler() -> String {
+                "ok".to_string()
+            }
+        );
+        let result = process_route_attribute(attr, item);
+        assert!(result.is_err());
+        let err = result.unwrap_err().to_string();
+        assert!(err.contains("function must be async"));
+    }
+
+    #[test]
+    fn test_process_route_attribute_with_path() {
+        let attr = quote!(get, path = "/users/{id}");
+        let item = quote!(
+            pub async fn get_user() -> String {
+                "user".to_string()
+            }
+        );
+        let result = process_route_attribute(attr, item);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_process_route_attribute_with_tags() {
+        let attr = quote!(post, tags = ["users", "admin"]);
+        let item = quote!(
+            pub async fn create_user() -> String {
+                "created".to_string()
+            }
+        );
+        let result = process_route_attribute(attr, item);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_process_route_attribute_all_methods() {
+        for method in crate::http::HTTP_METHODS {
+            let attr: proc_macro2::TokenStream = method.parse().unwrap();
+            let item = quote!(
+                pub async fn handler() -> String {
+                    "ok".to_string()
+                }
+            );
+            let result = process_route_attribute(attr, item);
+            assert!(result.is_ok(), "Method {method} should be valid");
+        }
+    }
+
+    // ========== Tests for ROUTE_STORAGE population ==========
+
+    #[test]
+    fn test_route_storage_populated_by_process_route_attribute() {
+        let attr = quote!(
+            get,
+            path = "/{id}",
+            tags = ["users"],
+            description = "Get user by ID",
+            error_status = [404]
+        );
+        let item = quote!(
+            pub async fn get_user_test_storage() -> String {
+                "test".to_string()
+            }
+        );
+        let result = process_route_attribute(attr, item);
+        assert!(result.is_ok());
+
+        // Find our entry by unique fn_name (ROUTE_STORAGE is global, shared across parallel tests)
+        let storage = ROUTE_STORAGE
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+
+        // Find our entry and verify fields
+        let stored = storage
+            .iter()
+            .find(|s| s.fn_name == "get_user_test_storage");
+        assert!(
+            stored.is_some(),
+            "StoredRouteInfo should be in ROUTE_STORAGE"
+        );
+        let stored = stored.unwrap();
+        assert_eq!(stored.method, Some("get".to_string()));
+        assert_eq!(stored.custom_path, Some("/{id}".to_string()));
+        assert_eq!(stored.tags, Some(vec!["users".to_string()]));
+        assert_eq!(stored.description, Some("Get user by ID".to_string()));
+        assert_eq!(stored.error_status, Some(vec![404]));
+        assert!(stored.fn_item_str.contains("get_user_test_storage"));
+    }
+
+    #[test]
+    fn test_route_storage_splits_doc_comment() {
+        let attr = quote!(get, summary = "Fetch user");
+        let item = quote!(
+            /// Get user
+            ///
+            /// Looks the user up by ID.
+            pub async fn get_user_test_doc_split() -> String {
+                "test".to_string()
+            }
+        );
+        assert!(process_route_attribute(attr, item).is_ok());
+
+        let storage = ROUTE_STORAGE
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let stored = storage
+            .iter()
+            .find(|s| s.fn_name == "get_user_test_doc_split")
+            .unwrap();
+        // Explicit summary wins; the rest of the doc comment still becomes the description
+        assert_eq!(stored.summary, Some("Fetch user".to_string()));
+        assert_eq!(
+            stored.description,
+            Some("Looks the user up by ID.".to_string())
+        );
+    }
+
+    #[test]
+    fn test_route_storage_response_overrides() {
+        let attr = quote!(
+            post,
+            request_body = Json<CreateUser>,
+            response = (StatusCode, Json<User>),
+            operation_id = "createUser"
+        );
+        let item = quote!(
+            pub async fn create_user_test_overrides() -> impl IntoResponse {
+                respond().await
+            }
+        );
+        let tokens = process_route_attribute(attr, item).unwrap().to_string();
+        assert!(!tokens.contains("UninferredResponse"), "{tokens}");
+
+        let storage = ROUTE_STORAGE
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let stored = storage
+            .iter()
+            .find(|s| s.fn_name == "create_user_test_overrides")
+            .unwrap();
+        assert_eq!(
+            stored.response.as_deref(),
+            Some("(StatusCode , Json < User >)")
+        );
+        assert_eq!(stored.request_body.as_deref(), Some("Json < CreateUser >"));
+        assert_eq!(stored.operation_id.as_deref(), Some("createUser"));
+    }
+
+    #[test]
+    fn test_route_storage_responses() {
+        let attr = quote!(get, responses(200 = Json<User>, 404 = Json<ErrorResponse>));
+        let item = quote!(
+            pub async fn get_user_test_responses() -> impl IntoResponse {
+                respond().await
+            }
+        );
+        let tokens = process_route_attribute(attr, item).unwrap().to_string();
+        assert!(!tokens.contains("UninferredResponse"), "{tokens}");
+
+        let storage = ROUTE_STORAGE
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let stored = storage
+            .iter()
+            .find(|s| s.fn_name == "get_user_test_responses")
+            .unwrap();
+        assert_eq!(
+            stored.responses,
+            Some(vec![
+                (200, "Json < User >".to_string()),
+                (404, "Json < ErrorResponse >".to_string()),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_route_storage_no_optional_fields() {
+        let attr = quote!();
+        let item = quote!(
+            pub async fn minimal_handler_test() -> String {
+                "test".to_string()
+            }
+        );
+        let result = process_route_attribute(attr, item);
+        assert!(result.is_ok());
+
+        let storage = ROUTE_STORAGE
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+
+        let stored = storage.iter().find(|s| s.fn_name == "minimal_handler_test");
+        assert!(stored.is_some());
+        let stored = stored.unwrap();
+        assert_eq!(stored.method, None);
+        assert_eq!(stored.custom_path, None);
+        assert_eq!(stored.tags, None);
+        assert_eq!(stored.description, None);
+        assert_eq!(stored.error_status, None);
+    }
+
+    #[test]
+    fn test_route_storage_security_alternatives() {
+        let attr = quote!(get, security = [["bearer"], ["api_key", "client_id"]]);
+        let item = quote!(
+            pub async fn secured_handler_test() -> String {
+                "test".to_string()
+            }
+        );
+        assert!(process_route_attribute(attr, item).is_ok());
+
+        let storage = ROUTE_STORAGE
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let stored = storage
+            .iter()
+            .find(|s| s.fn_name == "secured_handler_test")
+            .unwrap();
+        let security = stored.security.as_ref().unwrap();
+        assert_eq!(security.len(), 2);
+        assert!(security[0].contains_key("bearer"));
+        assert_eq!(
+            security[1].keys().collect::<Vec<_>>(),
+            vec!["api_key", "client_id"]
+        );
+    }
+
+    #[test]
+    fn test_extract_error_status_codes_empty() {
+        let arr: syn::ExprArray = syn::parse_quote!([]);
+        assert_eq!(extract_error_status_codes(&arr), None);
+    }
+
+    #[test]
+    fn test_extract_error_status_codes_values() {
+        let arr: syn::ExprArray = syn::parse_quote!([400, 404, 500]);
+        assert_eq!(extract_error_status_codes(&arr), Some(vec![400, 404, 500]));
+    }
+
+    #[test]
+    fn test_extract_tag_strings_empty() {
+        let arr: syn::ExprArray = syn::parse_quote!([]);
+        assert_eq!(extract_tag_strings(&arr), None);
+    }
+
+    #[test]
+    fn test_extract_tag_strings_values() {
+        let arr: syn::ExprArray = syn::parse_quote!(["users", "admin", "api"]);
+        assert_eq!(
+            extract_tag_strings(&arr),
+            Some(vec![
+                "users".to_string(),
+                "admin".to_string(),
+                "api".to_string()
+            ])
+        );
+    }
+}
@@ -0,0 +1,3849 @@
+//! Router code generation and macro input parsing.
+//!
+//! This module contains the core logic for:
+//! - Parsing `vespera!` and `export_app!` macro inputs
+//! - Processing input into validated configuration
+//! - Generating Axum router code from collected metadata
+//!
+//! # Overview
+//!
+//! The vespera macros accept configuration parameters (directory, `OpenAPI` files, etc.)
+//! which are parsed and processed into a normalized form. This module then generates
+//! the `TokenStream` that creates the Axum router with all discovered routes.
+//!
+//! # Key Components
+//!
+//! - [`AutoRouterInput`] - Parsed `vespera!()` macro arguments
+//! - [`ExportAppInput`] - Parsed `export_app!()` macro arguments
+//! - [`process_vespera_input`] - Validate and process vespera! arguments
+//! - [`generate_router_code`] - Generate the router `TokenStream`
+//!
+//! # Macro Parameters
+//!
+//! **vespera!()** accepts:
+//! - `dir` - Route discovery folder (default: "routes")
+//! - `openapi` - Output file path(s) for `OpenAPI` spec
+//! - `title` - API title (`OpenAPI` info.title)
+//! - `version` - API version (`OpenAPI` info.version)
+//! - `docs_url` - Swagger UI endpoint
+//! - `redoc_url` - `ReDoc` endpoint
+//! - `servers` - Array of server configurations
+//! - `merge` - Child vespera apps to merge
+//! - `scopes` - Extra per-scope `OpenAPI` documents for `#[schema(scope = "...")]` fields
+//! - `tag_rules` - `(path_glob, tag)` pairs that tag every matching route
+//! - `auto_methods` - Auto-document `"head"` for GET routes and serve/document `"options"`
+//! - `global_params` - Parameters added to every operation (`accept_language()`, `header("...")`)
+//! - `anyhow_error` - `(status, BodyType)` documented for opaque errors like `anyhow::Error`
+//! - `closed_objects` - Emit `additionalProperties: false` on every object schema
+//! - `security_schemes` - Named schemes registered under `components.securitySchemes`
+//! - `security` - Default security requirement for every operation
+//! - `instrument` - Log operation id, path and response status of every request with `tracing`
+//!   (`OpenTelemetry` semantic-convention span fields with the `otel` feature)
+//! - `api_gateway` - Integration mapping file; writes an AWS API Gateway import document
+//!   (`openapi.apigateway.json`) next to each `openapi` file
+//! - `kubernetes` - Backend service and output paths of Gateway API `HTTPRoute` / `Ingress`
+//!   manifests generated from the route table
+//! - `sdk_groups` - Also write `openapi.sdk.json`, with operations tagged by their `sdk_group`
+//! - `formats` - Custom string formats with optional validation patterns, added as `pattern`
+//!   to `#[schema(format = "...")]` fields and checked at runtime by `vespera::formats`
+//! - `declaration_order` - Keep schema properties in field declaration order instead of
+//!   sorting them alphabetically
+//! - `strict_nullability` - Document fields with `skip_serializing_if` as optional and
+//!   other `Option<T>` fields as required and nullable, the way serde writes them
+//! - `generic_names` - Register generic instantiations as components named `"underscore"`
+//!   (`Page_User`) or `"concat"` (`PageUser`) instead of inlining them
+//! - `path_order` - Order of `paths`: `"alphabetical"` (default), `"tag"` or `"source"`
+//! - `collapse_paths` - Merge paths that differ only in parameter names into one
+//! - `path_param_case` - Rename path parameters to a `rename_all` case, e.g. `"camelCase"`
+//! - `json_schema_dialect` - Document-wide `jsonSchemaDialect`, the default `$schema` of
+//!   every schema (`#[schema(dialect = "...")]` overrides it per component)
+//! - `migrations` - `SeaORM` migration folder (or `.json` entity snapshot); warns about
+//!   entity columns that no migration creates
+//! - `skip_invalid_files` - Leave route files with syntax errors out of the router and
+//!   spec with a warning instead of failing
+//! - `ignore` - Gitignore-style patterns of route folder paths not to scan,
+//!   e.g. `["generated/", "**/*_old.rs"]`
+//!
+//! **`export_app`!()** accepts:
+//! - `dir` - Route discovery folder (default: "routes")
+
+use std::collections::BTreeMap;
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{
+    LitStr, bracketed,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+};
+use vespera_core::{
+    kubernetes::KubernetesConfig,
+    openapi::{PathOrder, Server, ServerVariable},
+    route::{HttpMethod, Parameter, SecurityRequirement},
+    schema::{OAuthFlow, OAuthFlows, SecurityScheme},
+};
+
+use crate::{
+    args::parse_security_requirements,
+    metadata::{CollectedMetadata, CronMetadata},
+    method::http_method_to_token_stream,
+    parser::{GenericNaming, is_absolute_uri},
+    route::{cache_layer, limit_layers},
+};
+
+/// Server configuration for `OpenAPI`
+#[derive(Clone)]
+pub struct ServerConfig {
+    pub url: String,
+    pub description: Option<String>,
+    /// URL template variables, filled in at runtime by `vespera::servers`
+    pub variables: BTreeMap<String, ServerVariable>,
+}
+
+/// `kubernetes = { ... }`: manifest settings and where to write each manifest
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct KubernetesManifests {
+    pub config: KubernetesConfig,
+    /// Output path of the Gateway API `HTTPRoute`
+    pub http_route: Option<String>,
+    /// Output path of the `Ingress`
+    pub ingress: Option<String>,
+}
+
+/// Parameter added to every operation via `global_params`
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum GlobalParam {
+    /// `accept_language()` - optional `Accept-Language` header
+    AcceptLanguage,
+    /// `header("X-Request-Id")` - optional string header
+    Header(String),
+}
+
+impl GlobalParam {
+    /// Key under `components/parameters` and the parameter definition
+    pub fn to_component(&self) -> (String, Parameter) {
+        match self {
+            Self::AcceptLanguage => ("AcceptLanguage".to_string(), Parameter::accept_language()),
+            Self::Header(name) => {
+                // "X-Request-Id" -> "XRequestId"
+                let component_name = name
+                    .split(|c: char| !c.is_ascii_alphanumeric())
+                    .filter(|part| !part.is_empty())
+                    .map(|part| {
+                        let mut chars = part.chars();
+                        chars.next().map_or_else(String::new, |first| {
+                            first.to_ascii_uppercase().to_string() + chars.as_str()
+                        })
+                    })
+                    .collect();
+                (component_name, Parameter::header(name))
+            }
+        }
+    }
+}
+
+/// Input for the `vespera!` macro
+pub struct AutoRouterInput {
+    pub dir: Option<LitStr>,
+    pub openapi: Option<Vec<LitStr>>,
+    pub title: Option<LitStr>,
+    pub version: Option<LitStr>,
+    pub docs_url: Option<LitStr>,
+    pub redoc_url: Option<LitStr>,
+    pub servers: Option<Vec<ServerConfig>>,
+    /// Apps to merge (e.g., [`third::ThirdApp`, `another::AnotherApp`])
+    pub merge: Option<Vec<syn::Path>>,
+    /// Field scopes to generate extra documents for (e.g., `["admin"]`)
+    pub scopes: Option<Vec<LitStr>>,
+    /// Path-glob tag rules (e.g., `[("/admin/**", "admin")]`)
+    pub tag_rules: Option<Vec<(LitStr, LitStr)>>,
+    /// Methods generated automatically (`["head", "options"]`)
+    pub auto_methods: Option<Vec<HttpMethod>>,
+    /// Parameters added to every operation (e.g., `[accept_language()]`)
+    pub global_params: Option<Vec<GlobalParam>>,
+    /// Response documented for opaque handler errors (e.g., `(500, ErrorResponse)`)
+    pub anyhow_error: Option<(syn::LitInt, syn::Type)>,
+    /// Close every object schema with `additionalProperties: false`
+    pub closed_objects: Option<syn::LitBool>,
+    /// Named security schemes (e.g., `{ bearer_auth = bearer("JWT") }`)
+    pub security_schemes: Option<Vec<(String, SecurityScheme)>>,
+    /// Default security requirement (e.g., `["bearer_auth"]`)
+    pub security: Option<Vec<SecurityRequirement>>,
+    /// Wrap every route in a `tracing` layer
+    pub instrument: Option<syn::LitBool>,
+    /// API Gateway integration mapping file (e.g., `"apigateway.json"`)
+    pub api_gateway: Option<LitStr>,
+    /// Kubernetes routing manifests (e.g., `{ name = "api", service = "api", port = 8080, ingress = "ingress.yaml" }`)
+    pub kubernetes: Option<KubernetesManifests>,
+    /// Write an SDK document tagged by `sdk_group`
+    pub sdk_groups: Option<syn::LitBool>,
+    /// Custom string formats (e.g., `{ ulid = "^[0-9A-HJKMNP-TV-Z]{26}$", slug }`)
+    pub formats: Option<BTreeMap<String, Option<String>>>,
+    /// Keep schema properties in declaration order
+    pub declaration_order: Option<syn::LitBool>,
+    /// Tell absent fields from null ones
+    pub strict_nullability: Option<syn::LitBool>,
+    /// Component naming of generic instantiations (`generic_names = "underscore"`)
+    pub generic_names: Option<GenericNaming>,
+    /// Order of `paths` (`path_order = "tag"`)
+    pub path_order: Option<PathOrder>,
+    /// Merge equivalent templated paths (`collapse_paths = true`)
+    pub collapse_paths: Option<syn::LitBool>,
+    /// Case path parameters are renamed to (`path_param_case = "camelCase"`)
+    pub path_param_case: Option<LitStr>,
+    /// `jsonSchemaDialect` of the document (`json_schema_dialect = "https://..."`)
+    pub json_schema_dialect: Option<LitStr>,
+    /// Migration folder checked for schema drift (`migrations = "migration/src"`)
+    pub migrations: Option<LitStr>,
+    /// Skip route files that cannot be parsed (`skip_invalid_files = true`)
+    pub skip_invalid_files: Option<syn::LitBool>,
+    /// Route folder paths not to scan (`ignore = ["generated/"]`)
+    pub ignore: Option<Vec<String>>,
+}
+
+impl Parse for AutoRouterInput {
+    #[allow(clippy::too_many_lines)]
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut dir = None;
+        let mut openapi = None;
+        let mut title = None;
+        let mut version = None;
+        let mut docs_url = None;
+        let mut redoc_url = None;
+        let mut servers = None;
+        let mut merge = None;
+        let mut scopes = None;
+        let mut tag_rules = None;
+        let mut auto_methods = None;
+        let mut global_params = None;
+        let mut anyhow_error = None;
+        let mut closed_objects = None;
+        let mut security_schemes = None;
+        let mut security = None;
+        let mut instrument = None;
+        let mut api_gateway = None;
+        let mut kubernetes = None;
+        let mut sdk_groups = None;
+        let mut formats = None;
+        let mut declaration_order = None;
+        let mut strict_nullability = None;
+        let mut generic_names = None;
+        let mut path_order = None;
+        let mut collapse_paths = None;
+        let mut path_param_case = None;
+        let mut json_schema_dialect = None;
+        let mut migrations = None;
+        let mut skip_invalid_files = None;
+        let mut ignore = None;
+
+        while !input.is_empty() {
+            let lookahead = input.lookahead1();
+
+            if lookahead.peek(syn::Ident) {
+                let ident: syn::Ident = input.parse()?;
+                let ident_str = ident.to_string();
+
+                match ident_str.as_str() {
+                    "dir" => {
+                        input.parse::<syn::Token![=]>()?;
+                        dir = Some(input.parse()?);
+                    }
+                    "openapi" => {
+                        openapi = Some(parse_openapi_values(input)?);
+                    }
+                    "docs_url" => {
+                        input.parse::<syn::Token![=]>()?;
+                        docs_url = Some(input.parse()?);
+                    }
+                    "redoc_url" => {
+                        input.parse::<syn::Token![=]>()?;
+                        redoc_url = Some(input.parse()?);
+                    }
+                    "title" => {
+                        input.parse::<syn::Token![=]>()?;
+                        title = Some(input.parse()?);
+                    }
+                    "version" => {
+                        input.parse::<syn::Token![=]>()?;
+                        version = Some(input.parse()?);
+                    }
+                    "servers" => {
+                        servers = Some(parse_servers_values(input)?);
+                    }
+                    "merge" => {
+                        merge = Some(parse_merge_values(input)?);
+                    }
+                    "scopes" => {
+                        scopes = Some(parse_scopes_values(input)?);
+                    }
+                    "tag_rules" => {
+                        tag_rules = Some(parse_tag_rules_values(input)?);
+                    }
+                    "auto_methods" => {
+                        auto_methods = Some(parse_auto_methods_values(input)?);
+                    }
+                    "global_params" => {
+                        global_params = Some(parse_global_params_values(input)?);
+                    }
+                    "anyhow_error" => {
+                        anyhow_error = Some(parse_anyhow_error_value(input)?);
+                    }
+                    "closed_objects" => {
+                        input.parse::<syn::Token![=]>()?;
+                        closed_objects = Some(input.parse()?);
+                    }
+                    "security_schemes" => {
+                        security_schemes = Some(parse_security_schemes_values(input)?);
+                    }
+                    "security" => {
+                        input.parse::<syn::Token![=]>()?;
+                        security = Some(parse_security_requirements(&input.parse()?)?);
+                    }
+                    "instrument" => {
+                        input.parse::<syn::Token![=]>()?;
+                        instrument = Some(input.parse()?);
+                    }
+                    "api_gateway" => {
+                        input.parse::<syn::Token![=]>()?;
+                        api_gateway = Some(input.parse()?);
+                    }
+                    "kubernetes" => {
+                        kubernetes = Some(parse_kubernetes_value(input)?);
+                    }
+                    "sdk_groups" => {
+                        input.parse::<syn::Token![=]>()?;
+                        sdk_groups = Some(input.parse()?);
+                    }
+                    "formats" => {
+                        formats = Some(parse_formats_value(input)?);
+                    }
+                    "declaration_order" => {
+                        input.parse::<syn::Token![=]>()?;
+                        declaration_order = Some(input.parse()?);
+                    }
+                    "strict_nullability" => {
+                        input.parse::<syn::Token![=]>()?;
+                        strict_nullability = Some(input.parse()?);
+                    }
+                    "generic_names" => {
+                        generic_names = Some(parse_generic_names_value(input)?);
+                    }
+                    "path_order" => {
+                        path_order = Some(parse_path_order_value(input)?);
+                    }
+                    "collapse_paths" => {
+                        input.parse::<syn::Token![=]>()?;
+                        collapse_paths = Some(input.parse()?);
+                    }
+                    "path_param_case" => {
+                        path_param_case = Some(parse_path_param_case_value(input)?);
+                    }
+                    "json_schema_dialect" => {
+                        json_schema_dialect = Some(parse_json_schema_dialect_value(input)?);
+                    }
+                    "migrations" => {
+                        input.parse::<syn::Token![=]>()?;
+                        migrations = Some(input.parse()?);
+                    }
+                    "skip_invalid_files" => {
+                        input.parse::<syn::Token![=]>()?;
+                        skip_invalid_files = Some(input.parse()?);
+                    }
+                    "ignore" => {
+                        ignore = Some(parse_ignore_values(input)?);
+                    }
+                    _ => {
+                        return Err(syn::Error::new(
+                            ident.span(),
+                            format!(
+                                "unknown field: `{ident_str}`. Expected `dir`, `openapi`, `title`, `version`, `docs_url`, `redoc_url`, `servers`, `merge`, `scopes`, `tag_rules`, `auto_methods`, `global_params`, `anyhow_error`, `closed_objects`, `security_schemes`, `security`, `instrument`, `api_gateway`, `kubernetes`, `sdk_groups`, `formats`, `declaration_order`, `strict_nullability`, `generic_names`, `path_order`, `collapse_paths`, `path_param_case`, `json_schema_dialect`, `migrations`, `skip_invalid_files`, or `ignore`"
+                            ),
+                        ));
+                    }
+                }
+            } else if lookahead.peek(syn::LitStr) {
+                // If just a string, treat it as dir (for backward compatibility)
+                dir = Some(input.parse()?);
+            } else {
+                return Err(lookahead.error());
+            }
+
+            if input.peek(syn::Token![,]) {
+                input.parse::<syn::Token![,]>()?;
+            } else {
+                break;
+            }
+        }
+
+        Ok(Self {
+            dir: dir.or_else(|| {
+                std::env::var("VESPERA_DIR")
+                    .map(|f| LitStr::new(&f, Span::call_site()))
+                    .ok()
+            }),
+            openapi: openapi.or_else(|| {
+                std::env::var("VESPERA_OPENAPI")
+                    .map(|f| vec![LitStr::new(&f, Span::call_site())])
+                    .ok()
+            }),
+            title: title.or_else(|| {
+                std::env::var("VESPERA_TITLE")
+                    .map(|f| LitStr::new(&f, Span::call_site()))
+                    .ok()
+            }),
+            version: version
+                .or_else(|| {
+                    std::env::var("VESPERA_VERSION")
+                        .map(|f| LitStr::new(&f, Span::call_site()))
+                        .ok()
+                })
+                .or_else(|| {
+                    std::env::var("CARGO_PKG_VERSION")
+                        .map(|f| LitStr::new(&f, Span::call_site()))
+                        .ok()
+                }),
+            docs_url: docs_url.or_else(|| {
+                std::env::var("VESPERA_DOCS_URL")
+                    .map(|f| LitStr::new(&f, Span::call_site()))
+                    .ok()
+            }),
+            redoc_url: redoc_url.or_else(|| {
+                std::env::var("VESPERA_REDOC_URL")
+                    .map(|f| LitStr::new(&f, Span::call_site()))
+                    .ok()
+            }),
+            servers: servers.or_else(|| {
+                std::env::var("VESPERA_SERVER_URL")
+                    .ok()
+                    .filter(|url| url.starts_with("http://") || url.starts_with("https://"))
+                    .map(|url| {
+                        vec![ServerConfig {
+                            url,
+                            description: std::env::var("VESPERA_SERVER_DESCRIPTION").ok(),
+                            variables: BTreeMap::new(),
+                        }]
+                    })
+            }),
+            merge,
+            scopes,
+            tag_rules,
+            auto_methods,
+            global_params,
+            anyhow_error,
+            closed_objects,
+            security_schemes,
+            security,
+            instrument,
+            api_gateway,
+            kubernetes,
+            sdk_groups,
+            formats,
+            declaration_order,
+            strict_nullability,
+            generic_names,
+            path_order,
+            collapse_paths,
+            path_param_case,
+            json_schema_dialect,
+            migrations,
+            skip_invalid_files,
+            ignore,
+        })
+    }
+}
+
+/// Parse merge values: merge = [`path::to::App`, `another::App`]
+fn parse_merge_values(input: ParseStream) -> syn::Result<Vec<syn::Path>> {
+    input.parse::<syn::Token![=]>()?;
+
+    let content;
+    let _ = bracketed!(content in input);
+    let paths: Punctuated<syn::Path, syn::Token![,]> =
+        content.parse_terminated(syn::Path::parse, syn::Token![,])?;
+    Ok(paths.into_iter().collect())
+}
+
+/// Parse scope values: `scopes = ["admin", "internal"]`
+fn parse_scopes_values(input: ParseStream) -> syn::Result<Vec<LitStr>> {
+    input.parse::<syn::Token![=]>()?;
+
+    let content;
+    let _ = bracketed!(content in input);
+    let entries: Punctuated<LitStr, syn::Token![,]> =
+        content.parse_terminated(syn::parse::ParseBuffer::parse::<LitStr>, syn::Token![,])?;
+    for entry in &entries {
+        let value = entry.value();
+        if value.is_empty()
+            || !value
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        {
+            return Err(syn::Error::new(
+                entry.span(),
+                format!(
+                    "invalid scope: `{value}`. Scopes are used in output file names and must be non-empty ASCII letters, digits, `_` or `-`"
+                ),
+            ));
+        }
+    }
+    Ok(entries.into_iter().collect())
+}
+
+/// Parse tag rules: `tag_rules = [("/admin/**", "admin"), ("/users/**", "users")]`
+fn parse_tag_rules_values(input: ParseStream) -> syn::Result<Vec<(LitStr, LitStr)>> {
+    input.parse::<syn::Token![=]>()?;
+
+    let content;
+    let _ = bracketed!(content in input);
+    let mut rules = Vec::new();
+    while !content.is_empty() {
+        let rule;
+        syn::parenthesized!(rule in content);
+        let pattern: LitStr = rule.parse()?;
+        rule.parse::<syn::Token![,]>()?;
+        let tag: LitStr = rule.parse()?;
+        if !rule.is_empty() {
+            return Err(rule.error("tag rule must be a `(\"/path/glob/**\", \"tag\")` pair"));
+        }
+        if !pattern.value().starts_with('/') {
+            return Err(syn::Error::new(
+                pattern.span(),
+                format!(
+                    "invalid tag rule pattern: `{}`. Patterns match route paths and must start with `/`, e.g. \"/admin/**\"",
+                    pattern.value()
+                ),
+            ));
+        }
+        rules.push((pattern, tag));
+
+        if content.peek(syn::Token![,]) {
+            content.parse::<syn::Token![,]>()?;
+        } else {
+            break;
+        }
+    }
+    if !content.is_empty() {
+        return Err(content.error("expected `,` between tag rules"));
+    }
+    Ok(rules)
+}
+
+/// Parse auto methods: `auto_methods = ["head", "options"]`
+fn parse_auto_methods_values(input: ParseStream) -> syn::Result<Vec<HttpMethod>> {
+    input.parse::<syn::Token![=]>()?;
+
+    let content;
+    let _ = bracketed!(content in input);
+    let entries: Punctuated<LitStr, syn::Token![,]> =
+        content.parse_terminated(syn::parse::ParseBuffer::parse::<LitStr>, syn::Token![,])?;
+    entries
+        .iter()
+        .map(|entry| match HttpMethod::try_from(entry.value().as_str()) {
+            Ok(method @ (HttpMethod::Head | HttpMethod::Options)) => Ok(method),
+            _ => Err(syn::Error::new(
+                entry.span(),
+                format!(
+                    "invalid auto method: `{}`. Only \"head\" and \"options\" can be generated automatically",
+                    entry.value()
+                ),
+            )),
+        })
+        .collect()
+}
+
+/// Parse route folder ignore patterns: `ignore = ["generated/", "**/*_old.rs"]`
+fn parse_ignore_values(input: ParseStream) -> syn::Result<Vec<String>> {
+    input.parse::<syn::Token![=]>()?;
+
+    let content;
+    let _ = bracketed!(content in input);
+    let entries: Punctuated<LitStr, syn::Token![,]> =
+        content.parse_terminated(syn::parse::ParseBuffer::parse::<LitStr>, syn::Token![,])?;
+    entries
+        .iter()
+        .map(|entry| {
+            let pattern = entry.value();
+            if pattern.trim_matches('/').is_empty() {
+                Err(syn::Error::new(
+                    entry.span(),
+                    "invalid ignore pattern: a pattern must name a file or directory",
+                ))
+            } else {
+                Ok(pattern)
+            }
+        })
+        .collect()
+}
+
+/// Parse the generic instantiation naming: `generic_names = "underscore"`
+fn parse_generic_names_value(input: ParseStream) -> syn::Result<GenericNaming> {
+    input.parse::<syn::Token![=]>()?;
+    let value: LitStr = input.parse()?;
+    match value.value().as_str() {
+        "underscore" => Ok(GenericNaming::Underscore),
+        "concat" => Ok(GenericNaming::Concat),
+        other => Err(syn::Error::new(
+            value.span(),
+            format!("invalid generic names: `{other}`. Expected \"underscore\" or \"concat\""),
+        )),
+    }
+}
+
+/// Parse the path order: `path_order = "tag"`
+fn parse_path_order_value(input: ParseStream) -> syn::Result<PathOrder> {
+    input.parse::<syn::Token![=]>()?;
+    let value: LitStr = input.parse()?;
+    match value.value().as_str() {
+        "alphabetical" => Ok(PathOrder::Alphabetical),
+        "tag" => Ok(PathOrder::Tag),
+        "source" => Ok(PathOrder::Source),
+        other => Err(syn::Error::new(
+            value.span(),
+            format!(
+                "invalid path order: `{other}`. Expected \"alphabetical\", \"tag\", or \"source\""
+            ),
+        )),
+    }
+}
+
+/// Cases accepted by `path_param_case`, as spelled in `#[serde(rename_all = "...")]`
+const PATH_PARAM_CASES: [&str; 8] = [
+    "lowercase",
+    "UPPERCASE",
+    "PascalCase",
+    "camelCase",
+    "snake_case",
+    "SCREAMING_SNAKE_CASE",
+    "kebab-case",
+    "SCREAMING-KEBAB-CASE",
+];
+
+/// Parse the path parameter case: `path_param_case = "camelCase"`
+fn parse_path_param_case_value(input: ParseStream) -> syn::Result<LitStr> {
+    input.parse::<syn::Token![=]>()?;
+    let value: LitStr = input.parse()?;
+    if !PATH_PARAM_CASES.contains(&value.value().as_str()) {
+        return Err(syn::Error::new(
+            value.span(),
+            format!(
+                "invalid path parameter case: `{}`. Expected one of {}",
+                value.value(),
+                PATH_PARAM_CASES
+                    .map(|case| format!("\"{case}\""))
+                    .join(", ")
+            ),
+        ));
+    }
+    Ok(value)
+}
+
+/// Parse the document dialect: `json_schema_dialect = "https://json-schema.org/draft/2020-12/schema"`
+fn parse_json_schema_dialect_value(input: ParseStream) -> syn::Result<LitStr> {
+    input.parse::<syn::Token![=]>()?;
+    let value: LitStr = input.parse()?;
+    if !is_absolute_uri(&value.value()) {
+        return Err(syn::Error::new(
+            value.span(),
+            format!(
+                "invalid JSON Schema dialect: `{}`. Expected an absolute URI such as \"https://spec.openapis.org/oas/3.1/dialect/base\"",
+                value.value()
+            ),
+        ));
+    }
+    Ok(value)
+}
+
+/// Parse the opaque error response: `anyhow_error = (500, ErrorResponse)`
+fn parse_anyhow_error_value(input: ParseStream) -> syn::Result<(syn::LitInt, syn::Type)> {
+    input.parse::<syn::Token![=]>()?;
+
+    let content;
+    syn::parenthesized!(content in input);
+    let status: syn::LitInt = content.parse()?;
+    if !status
+        .base10_parse::<u16>()
+        .is_ok_and(|code| (400..=599).contains(&code))
+    {
+        return Err(syn::Error::new(
+            status.span(),
+            "anyhow_error: status must be an error status code (400-599)",
+        ));
+    }
+    content.parse::<syn::Token![,]>()?;
+    let body: syn::Type = content.parse()?;
+    if content.peek(syn::Token![,]) {
+        content.parse::<syn::Token![,]>()?;
+    }
+    if !content.is_empty() {
+        return Err(content.error("anyhow_error: expected `(status, BodyType)`"));
+    }
+    Ok((status, body))
+}
+
+/// Parse security schemes:
+/// `security_schemes = { bearer_auth = bearer("JWT"), api_key = api_key(header = "X-API-Key"), oauth = oauth2 { ... } }`
+fn parse_security_schemes_values(input: ParseStream) -> syn::Result<Vec<(String, SecurityScheme)>> {
+    input.parse::<syn::Token![=]>()?;
+
+    let content;
+    syn::braced!(content in input);
+    let mut schemes: Vec<(String, SecurityScheme)> = Vec::new();
+    while !content.is_empty() {
+        let name: syn::Ident = content.parse()?;
+        content.parse::<syn::Token![=]>()?;
+        if schemes.iter().any(|(existing, _)| name == existing) {
+            return Err(syn::Error::new(
+                name.span(),
+                format!("duplicate security scheme: `{name}`"),
+            ));
+        }
+        let kind: syn::Ident = content.parse()?;
+        let scheme = match kind.to_string().as_str() {
+            "oauth2" => SecurityScheme::oauth2(parse_oauth_flows(&content)?),
+            "basic" => {
+                let args;
+                syn::parenthesized!(args in content);
+                if !args.is_empty() {
+                    return Err(args.error("basic() takes no arguments"));
+                }
+                SecurityScheme::http("basic")
+            }
+            "bearer" => {
+                let args;
+                syn::parenthesized!(args in content);
+                let bearer_format: Option<LitStr> = args.parse()?;
+                if !args.is_empty() {
+                    return Err(args.error("expected `bearer()` or `bearer(\"JWT\")`"));
+                }
+                SecurityScheme::bearer(bearer_format.map(|format| format.value()).as_deref())
+            }
+            "api_key" => {
+                let args;
+                syn::parenthesized!(args in content);
+                let location: syn::Ident = args.parse()?;
+                if !matches!(location.to_string().as_str(), "header" | "query" | "cookie") {
+                    return Err(syn::Error::new(
+                        location.span(),
+                        format!(
+                            "invalid api_key location: `{location}`. Expected `header`, `query`, or `cookie`"
+                        ),
+                    ));
+                }
+                args.parse::<syn::Token![=]>()?;
+                let key_name: LitStr = args.parse()?;
+                if !args.is_empty() {
+                    return Err(args.error("expected `api_key(header = \"X-API-Key\")`"));
+                }
+                SecurityScheme::api_key(key_name.value(), location.to_string())
+            }
+            other => {
+                return Err(syn::Error::new(
+                    kind.span(),
+                    format!(
+                        "unknown security scheme kind: `{other}`. Expected `api_key(...)`, `basic()`, `bearer(...)`, or `oauth2 {{ ... }}`"
+                    ),
+                ));
+            }
+        };
+        schemes.push((name.to_string(), scheme));
+
+        if content.peek(syn::Token![,]) {
+            content.parse::<syn::Token![,]>()?;
+        } else {
+            break;
+        }
+    }
+    if !content.is_empty() {
+        return Err(content.error("expected `,` between security schemes"));
+    }
+    Ok(schemes)
+}
+
+/// Parse Kubernetes manifest settings:
+/// `kubernetes = { name = "users-api", service = "users", port = 8080, gateway = "public", hostnames = ["api.example.com"], ingress_class = "nginx", http_route = "deploy/httproute.yaml", ingress = "deploy/ingress.yaml" }`
+fn parse_kubernetes_value(input: ParseStream) -> syn::Result<KubernetesManifests> {
+    input.parse::<syn::Token![=]>()?;
+
+    let content;
+    let brace = syn::braced!(content in input);
+    let mut manifests = KubernetesManifests::default();
+    let mut port = None;
+    while !content.is_empty() {
+        let key: syn::Ident = content.parse()?;
+        content.parse::<syn::Token![=]>()?;
+        match key.to_string().as_str() {
+            "name" => manifests.config.name = content.parse::<LitStr>()?.value(),
+            "namespace" => manifests.config.namespace = Some(content.parse::<LitStr>()?.value()),
+            "service" => manifests.config.service = content.parse::<LitStr>()?.value(),
+            "port" => port = Some(content.parse::<syn::LitInt>()?.base10_parse::<u16>()?),
+            "gateway" => manifests.config.gateway = Some(content.parse::<LitStr>()?.value()),
+            "hostnames" => {
+                let hosts;
+                bracketed!(hosts in content);
+                manifests.config.hostnames =
+                    Punctuated::<LitStr, syn::Token![,]>::parse_terminated(&hosts)?
+                        .iter()
+                        .map(LitStr::value)
+                        .collect();
+            }
+            "ingress_class" => {
+                manifests.config.ingress_class = Some(content.parse::<LitStr>()?.value());
+            }
+            "http_route" => manifests.http_route = Some(content.parse::<LitStr>()?.value()),
+            "ingress" => manifests.ingress = Some(content.parse::<LitStr>()?.value()),
+            other => {
+                return Err(syn::Error::new(
+                    key.span(),
+                    format!(
+                        "unknown kubernetes field: `{other}`. Expected `name`, `namespace`, `service`, `port`, `gateway`, `hostnames`, `ingress_class`, `http_route`, or `ingress`"
+                    ),
+                ));
+            }
+        }
+
+        if content.peek(syn::Token![,]) {
+            content.parse::<syn::Token![,]>()?;
+        } else {
+            break;
+        }
+    }
+    if !content.is_empty() {
+        return Err(content.error("expected `,` between kubernetes fields"));
+    }
+
+    let Some(port) = port else {
+        return Err(syn::Error::new(
+            brace.span.join(),
+            "kubernetes: `port` is required",
+        ));
+    };
+    manifests.config.port = port;
+    if manifests.config.name.is_empty() || manifests.config.service.is_empty() {
+        return Err(syn::Error::new(
+            brace.span.join(),
+            "kubernetes: `name` and `service` are required",
+        ));
+    }
+    if manifests.http_route.is_none() && manifests.ingress.is_none() {
+        return Err(syn::Error::new(
+            brace.span.join(),
+            "kubernetes: set `http_route` and/or `ingress` to the manifest output path",
+        ));
+    }
+    Ok(manifests)
+}
+
+/// Parse OAuth2 flows: `{ authorization_code = { ... }, client_credentials = { ... } }`
+fn parse_oauth_flows(input: ParseStream) -> syn::Result<OAuthFlows> {
+    let content;
+    let brace = syn::braced!(content in input);
+    let mut flows = OAuthFlows::default();
+    while !content.is_empty() {
+        let flow_name: syn::Ident = content.parse()?;
+        content.parse::<syn::Token![=]>()?;
+        let flow = parse_oauth_flow(&content)?;
+        // (slot, requires authorizationUrl, requires tokenUrl)
+        let (slot, needs_authorization_url, needs_token_url) = match flow_name.to_string().as_str()
+        {
+            "implicit" => (&mut flows.implicit, true, false),
+            "password" => (&mut flows.password, false, true),
+            "client_credentials" => (&mut flows.client_credentials, false, true),
+            "authorization_code" => (&mut flows.authorization_code, true, true),
+            other => {
+                return Err(syn::Error::new(
+                    flow_name.span(),
+                    format!(
+                        "unknown OAuth2 flow: `{other}`. Expected `implicit`, `password`, `client_credentials`, or `authorization_code`"
+                    ),
+                ));
+            }
+        };
+        let missing = if needs_authorization_url && flow.authorization_url.is_none() {
+            Some("authorization_url")
+        } else if needs_token_url && flow.token_url.is_none() {
+            Some("token_url")
+        } else {
+            None
+        };
+        if let Some(field) = missing {
+            return Err(syn::Error::new(
+                flow_name.span(),
+                format!("OAuth2 `{flow_name}` flow requires `{field}`"),
+            ));
+        }
+        *slot = Some(flow);
+
+        if content.peek(syn::Token![,]) {
+            content.parse::<syn::Token![,]>()?;
+        } else {
+            break;
+        }
+    }
+    if !content.is_empty() {
+        return Err(content.error("expected `,` between OAuth2 flows"));
+    }
+    if flows == OAuthFlows::default() {
+        return Err(syn::Error::new(
+            brace.span.join(),
+            "oauth2 security scheme requires at least one flow",
+        ));
+    }
+    Ok(flows)
+}
+
+/// Parse one OAuth2 flow: `{ authorization_url = "...", token_url = "...", scopes = { "read" = "Read access" } }`
+fn parse_oauth_flow(input: ParseStream) -> syn::Result<OAuthFlow> {
+    let content;
+    syn::braced!(content in input);
+    let mut flow = OAuthFlow::default();
+    while !content.is_empty() {
+        let field: syn::Ident = content.parse()?;
+        content.parse::<syn::Token![=]>()?;
+        match field.to_string().as_str() {
+            "authorization_url" => {
+                flow.authorization_url = Some(content.parse::<LitStr>()?.value());
+            }
+            "token_url" => flow.token_url = Some(content.parse::<LitStr>()?.value()),
+            "refresh_url" => flow.refresh_url = Some(content.parse::<LitStr>()?.value()),
+            "scopes" => {
+                let scopes;
+                syn::braced!(scopes in content);
+                while !scopes.is_empty() {
+                    let scope: LitStr = scopes.parse()?;
+                    scopes.parse::<syn::Token![=]>()?;
+                    let description: LitStr = scopes.parse()?;
+                    flow.scopes.insert(scope.value(), description.value());
+                    if scopes.peek(syn::Token![,]) {
+                        scopes.parse::<syn::Token![,]>()?;
+                    } else {
+                        break;
+                    }
+                }
+                if !scopes.is_empty() {
+                    return Err(scopes.error("expected `\"scope\" = \"description\"` pairs"));
+                }
+            }
+            other => {
+                return Err(syn::Error::new(
+                    field.span(),
+                    format!(
+                        "unknown OAuth2 flow field: `{other}`. Expected `authorization_url`, `token_url`, `refresh_url`, or `scopes`"
+                    ),
+                ));
+            }
+        }
+
+        if content.peek(syn::Token![,]) {
+            content.parse::<syn::Token![,]>()?;
+        } else {
+            break;
+        }
+    }
+    if !content.is_empty() {
+        return Err(content.error("expected `,` between OAuth2 flow fields"));
+    }
+    Ok(flow)
+}
+
+/// Parse global parameters: `global_params = [accept_language(), header("X-Request-Id")]`
+fn parse_global_params_values(input: ParseStream) -> syn::Result<Vec<GlobalParam>> {
+    input.parse::<syn::Token![=]>()?;
+
+    let content;
+    let _ = bracketed!(content in input);
+    let mut params = Vec::new();
+    while !content.is_empty() {
+        let ident: syn::Ident = content.parse()?;
+        let args;
+        syn::parenthesized!(args in content);
+        let param = match ident.to_string().as_str() {
+            "accept_language" => {
+                if !args.is_empty() {
+                    return Err(args.error("`accept_language()` takes no arguments"));
+                }
+                GlobalParam::AcceptLanguage
+            }
+            "header" => {
+                let name: LitStr = args.parse()?;
+                if !args.is_empty() {
+                    return Err(args.error("`header(...)` takes a single header name"));
+                }
+                let value = name.value();
+                if value.is_empty()
+                    || !value
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+                {
+                    return Err(syn::Error::new(
+                        name.span(),
+                        format!(
+                            "invalid header name: `{value}`. Header names must be non-empty ASCII letters, digits, `-` or `_`"
+                        ),
+                    ));
+                }
+                GlobalParam::Header(value)
+            }
+            other => {
+                return Err(syn::Error::new(
+                    ident.span(),
+                    format!(
+                        "unknown global parameter: `{other}`. Expected `accept_language()` or `header(\"Header-Name\")`"
+                    ),
+                ));
+            }
+        };
+        params.push(param);
+
+        if content.peek(syn::Token![,]) {
+            content.parse::<syn::Token![,]>()?;
+        } else {
+            break;
+        }
+    }
+    if !content.is_empty() {
+        return Err(content.error("expected `,` between global parameters"));
+    }
+    Ok(params)
+}
+
+fn parse_openapi_values(input: ParseStream) -> syn::Result<Vec<LitStr>> {
+    input.parse::<syn::Token![=]>()?;
+
+    if input.peek(syn::token::Bracket) {
+        let content;
+        let _ = bracketed!(content in input);
+        let entries: Punctuated<LitStr, syn::Token![,]> =
+            content.parse_terminated(syn::parse::ParseBuffer::parse::<LitStr>, syn::Token![,])?;
+        Ok(entries.into_iter().collect())
+    } else {
+        let single: LitStr = input.parse()?;
+        Ok(vec![single])
+    }
+}
+
+/// Validate that a URL starts with http:// or https://
+fn validate_server_url(url: &LitStr) -> syn::Result<String> {
+    let url_value = url.value();
+    if !url_value.starts_with("http://") && !url_value.starts_with("https://") {
+        return Err(syn::Error::new(
+            url.span(),
+            format!(
+                "invalid server URL: `{url_value}`. URL must start with `http://` or `https://`"
+            ),
+        ));
+    }
+    Ok(url_value)
+}
+
+/// Parse server values in various formats:
+/// - `servers = "url"` - single URL
+/// - `servers = ["url1", "url2"]` - multiple URLs (strings only)
+/// - `servers = [("url", "description")]` - tuple format with descriptions
+/// - `servers = [{url = "...", description = "..."}]` - struct-like format
+/// - `servers = {url = "...", description = "..."}` - single server struct-like format
+///
+/// The struct-like format also takes `variables = { region = "us-east-1", env = ["dev", "prod"] }`
+/// (a default, or the allowed values with the first as default).
+fn parse_servers_values(input: ParseStream) -> syn::Result<Vec<ServerConfig>> {
+    use syn::token::{Brace, Paren};
+
+    input.parse::<syn::Token![=]>()?;
+
+    if input.peek(syn::token::Bracket) {
+        // Array format: [...]
+        let content;
+        let _ = bracketed!(content in input);
+
+        let mut servers = Vec::new();
+
+        while !content.is_empty() {
+            if content.peek(Paren) {
+                // Parse tuple: ("url", "description")
+                let tuple_content;
+                syn::parenthesized!(tuple_content in content);
+                let url: LitStr = tuple_content.parse()?;
+                let url_value = validate_server_url(&url)?;
+                let description = if tuple_content.peek(syn::Token![,]) {
+                    tuple_content.parse::<syn::Token![,]>()?;
+                    Some(tuple_content.parse::<LitStr>()?.value())
+                } else {
+                    None
+                };
+                servers.push(ServerConfig {
+                    url: url_value,
+                    description,
+                    variables: BTreeMap::new(),
+                });
+            } else if content.peek(Brace) {
+                // Parse struct-like: {url = "...", description = "..."}
+                let server = parse_server_struct(&content)?;
+                servers.push(server);
+            } else {
+                // Parse simple string: "url"
+                let url: LitStr = content.parse()?;
+                let url_value = validate_server_url(&url)?;
+                servers.push(ServerConfig {
+                    url: url_value,
+                    description: None,
+                    variables: BTreeMap::new(),
+                });
+            }
+
+            if content.peek(syn::Token![,]) {
+                content.parse::<syn::Token![,]>()?;
+            } else {
+                break;
+            }
+        }
+
+        Ok(servers)
+    } else if input.peek(syn::token::Brace) {
+        // Single struct-like format: servers = {url = "...", description = "..."}
+        let server = parse_server_struct(input)?;
+        Ok(vec![server])
+    } else {
+        // Single string: servers = "url"
+        let single: LitStr = input.parse()?;
+        let url_value = validate_server_url(&single)?;
+        Ok(vec![ServerConfig {
+            url: url_value,
+            description: None,
+            variables: BTreeMap::new(),
+        }])
+    }
+}
+
+/// Parse a single server in struct-like format: {url = "...", description = "...", variables = {...}}
+fn parse_server_struct(input: ParseStream) -> syn::Result<ServerConfig> {
+    let content;
+    syn::braced!(content in input);
+
+    let mut url: Option<String> = None;
+    let mut description: Option<String> = None;
+    let mut variables = BTreeMap::new();
+
+    while !content.is_empty() {
+        let ident: syn::Ident = content.parse()?;
+        let ident_str = ident.to_string();
+
+        match ident_str.as_str() {
+            "url" => {
+                content.parse::<syn::Token![=]>()?;
+                let url_lit: LitStr = content.parse()?;
+                url = Some(validate_server_url(&url_lit)?);
+            }
+            "description" => {
+                content.parse::<syn::Token![=]>()?;
+                description = Some(content.parse::<LitStr>()?.value());
+            }
+            "variables" => {
+                content.parse::<syn::Token![=]>()?;
+                variables = parse_server_variables(&content)?;
+            }
+            _ => {
+                return Err(syn::Error::new(
+                    ident.span(),
+                    format!(
+                        "unknown field: `{ident_str}`. Expected `url`, `description`, or `variables`"
+                    ),
+                ));
+            }
+        }
+
+        if content.peek(syn::Token![,]) {
+            content.parse::<syn::Token![,]>()?;
+        } else {
+            break;
+        }
+    }
+
+    let url = url.ok_or_else(|| syn::Error::new(proc_macro2::Span::call_site(), "vespera! macro: server configuration missing required `url` field. Use format: `servers = { url = \"http://localhost:3000\" }` or `servers = { url = \"...\", description = \"...\" }`."))?;
+
+    for name in variables.keys() {
+        if !url.contains(&format!("{{{name}}}")) {
+            return Err(syn::Error::new(
+                proc_macro2::Span::call_site(),
+                format!("server variable `{name}` does not appear in `{url}`"),
+            ));
+        }
+    }
+
+    Ok(ServerConfig {
+        url,
+        description,
+        variables,
+    })
+}
+
+/// Parse server variables: `{ region = "us-east-1", env = ["dev", "prod"] }`
+fn parse_server_variables(input: ParseStream) -> syn::Result<BTreeMap<String, ServerVariable>> {
+    let content;
+    syn::braced!(content in input);
+
+    let mut variables = BTreeMap::new();
+    while !content.is_empty() {
+        let name: syn::Ident = content.parse()?;
+        content.parse::<syn::Token![=]>()?;
+        let variable = if content.peek(syn::token::Bracket) {
+            let values;
+            let brackets = bracketed!(values in content);
+            let allowed: Vec<String> =
+                Punctuated::<LitStr, syn::Token![,]>::parse_terminated(&values)?
+                    .iter()
+                    .map(LitStr::value)
+                    .collect();
+            let Some(default) = allowed.first().cloned() else {
+                return Err(syn::Error::new(
+                    brackets.span.join(),
+                    "server variable needs at least one allowed value",
+                ));
+            };
+            ServerVariable {
+                default,
+                r#enum: Some(allowed),
+                description: None,
+            }
+        } else {
+            ServerVariable {
+                default: content.parse::<LitStr>()?.value(),
+                r#enum: None,
+                description: None,
+            }
+        };
+        variables.insert(name.to_string(), variable);
+
+        if content.peek(syn::Token![,]) {
+            content.parse::<syn::Token![,]>()?;
+        } else {
+            break;
+        }
+    }
+    Ok(variables)
+}
+
+/// Parse custom formats: `formats = { ulid = "^[0-9A-HJKMNP-TV-Z]{26}$", "phone-number" = "^\\+[0-9]+$", slug }`
+///
+/// Names are identifiers or string literals; formats without a pattern are only documented.
+fn parse_formats_value(input: ParseStream) -> syn::Result<BTreeMap<String, Option<String>>> {
+    input.parse::<syn::Token![=]>()?;
+    let content;
+    syn::braced!(content in input);
+
+    let mut formats = BTreeMap::new();
+    while !content.is_empty() {
+        let (name, span) = if content.peek(LitStr) {
+            let lit: LitStr = content.parse()?;
+            (lit.value(), lit.span())
+        } else {
+            let ident: syn::Ident = content.parse()?;
+            (ident.to_string(), ident.span())
+        };
+        if name.trim().is_empty() {
+            return Err(syn::Error::new(span, "formats: name must not be empty"));
+        }
+        let pattern = if content.peek(syn::Token![=]) {
+            content.parse::<syn::Token![=]>()?;
+            Some(content.parse::<LitStr>()?.value())
+        } else {
+            None
+        };
+        if formats.insert(name.clone(), pattern).is_some() {
+            return Err(syn::Error::new(
+                span,
+                format!("formats: duplicate format `{name}`"),
+            ));
+        }
+
+        if content.peek(syn::Token![,]) {
+            content.parse::<syn::Token![,]>()?;
+        } else {
+            break;
+        }
+    }
+    if !content.is_empty() {
+        return Err(content.error("formats: expected `name = \"pattern\"` or `name`"));
+    }
+    Ok(formats)
+}
+
+/// Processed vespera input with extracted values
+#[derive(Default)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct ProcessedVesperaInput {
+    pub folder_name: String,
+    pub openapi_file_names: Vec<String>,
+    pub title: Option<String>,
+    pub version: Option<String>,
+    pub docs_url: Option<String>,
+    pub redoc_url: Option<String>,
+    pub servers: Option<Vec<Server>>,
+    /// Apps to merge (`syn::Path` for code generation)
+    pub merge: Vec<syn::Path>,
+    /// Field scopes with their own `OpenAPI` document (`openapi.{scope}.json`)
+    pub scopes: Vec<String>,
+    /// `(path_glob, tag)` rules applied to every route
+    pub tag_rules: Vec<(String, String)>,
+    /// Automatically generated methods (HEAD documented for GET; OPTIONS served and documented)
+    pub auto_methods: Vec<HttpMethod>,
+    /// Parameters referenced from every operation
+    pub global_params: Vec<GlobalParam>,
+    /// `(status, body type)` documented for opaque handler errors
+    pub anyhow_error: Option<(u16, String)>,
+    /// Whether object schemas get `additionalProperties: false`
+    pub closed_objects: bool,
+    /// Schemes registered under `components.securitySchemes`
+    pub security_schemes: Vec<(String, SecurityScheme)>,
+    /// Document-level security requirement inherited by every operation
+    pub security: Option<Vec<SecurityRequirement>>,
+    /// Whether every route is wrapped in `vespera::instrument::layer`
+    pub instrument: bool,
+    /// API Gateway integration mapping file, exported as `openapi.apigateway.json`
+    pub api_gateway: Option<String>,
+    /// Kubernetes routing manifests written from the route table
+    pub kubernetes: Option<KubernetesManifests>,
+    /// Whether `openapi.sdk.json` is written, tagged by `sdk_group`
+    pub sdk_groups: bool,
+    /// Custom string formats and their optional validation patterns
+    pub formats: BTreeMap<String, Option<String>>,
+    /// Whether schema properties keep their declaration order instead of being sorted
+    pub declaration_order: bool,
+    /// Whether optional fields are documented by whether serde omits them or writes `null`
+    pub strict_nullability: bool,
+    /// Component naming of generic instantiations, `None` to inline them
+    pub generic_names: Option<GenericNaming>,
+    /// Order of `paths`
+    pub path_order: PathOrder,
+    /// Whether paths differing only in parameter names are merged
+    pub collapse_paths: bool,
+    /// `rename_all` case path parameters are renamed to
+    pub path_param_case: Option<String>,
+    /// `jsonSchemaDialect` of the document
+    pub json_schema_dialect: Option<String>,
+    /// Migration folder or entity snapshot checked for schema drift
+    pub migrations: Option<String>,
+    /// Whether route files that cannot be parsed are skipped with a warning
+    pub skip_invalid_files: bool,
+    /// Route folder paths not to scan
+    pub ignore: Vec<String>,
+}
+
+/// Process `AutoRouterInput` into extracted values
+pub fn process_vespera_input(input: AutoRouterInput) -> ProcessedVesperaInput {
+    ProcessedVesperaInput {
+        folder_name: input
+            .dir
+            .map_or_else(|| "routes".to_string(), |f| f.value()),
+        openapi_file_names: input
+            .openapi
+            .unwrap_or_default()
+            .into_iter()
+            .map(|f| f.value())
+            .collect(),
+        title: input.title.map(|t| t.value()),
+        version: input.version.map(|v| v.value()),
+        docs_url: input.docs_url.map(|u| u.value()),
+        redoc_url: input.redoc_url.map(|u| u.value()),
+        servers: input.servers.map(|svrs| {
+            svrs.into_iter()
+                .map(|s| Server {
+                    url: s.url,
+                    description: s.description,
+                    variables: (!s.variables.is_empty()).then(|| s.variables.into_iter().collect()),
+                })
+                .collect()
+        }),
+        merge: input.merge.unwrap_or_default(),
+        scopes: input
+            .scopes
+            .unwrap_or_default()
+            .into_iter()
+            .map(|s| s.value())
+            .collect(),
+        tag_rules: input
+            .tag_rules
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(pattern, tag)| (pattern.value(), tag.value()))
+            .collect(),
+        auto_methods: input.auto_methods.unwrap_or_default(),
+        global_params: input.global_params.unwrap_or_default(),
+        anyhow_error: input.anyhow_error.map(|(status, body)| {
+            (
+                status.base10_parse().unwrap_or(500),
+                quote!(#body).to_string(),
+            )
+        }),
+        closed_objects: input.closed_objects.is_some_and(|flag| flag.value),
+        security_schemes: input.security_schemes.unwrap_or_default(),
+        security: input.security,
+        instrument: input.instrument.is_some_and(|flag| flag.value),
+        api_gateway: input.api_gateway.map(|f| f.value()),
+        kubernetes: input.kubernetes,
+        sdk_groups: input.sdk_groups.is_some_and(|flag| flag.value),
+        formats: input.formats.unwrap_or_default(),
+        declaration_order: input.declaration_order.is_some_and(|flag| flag.value),
+        strict_nullability: input.strict_nullability.is_some_and(|flag| flag.value),
+        generic_names: input.generic_names,
+        path_order: input.path_order.unwrap_or_default(),
+        collapse_paths: input.collapse_paths.is_some_and(|flag| flag.value),
+        path_param_case: input.path_param_case.map(|case| case.value()),
+        json_schema_dialect: input.json_schema_dialect.map(|dialect| dialect.value()),
+        migrations: input.migrations.map(|path| path.value()),
+        skip_invalid_files: input.skip_invalid_files.is_some_and(|flag| flag.value),
+        ignore: input.ignore.unwrap_or_default(),
+    }
+}
+
+/// Input for `export_app`! macro
+pub struct ExportAppInput {
+    /// App name (struct name to generate)
+    pub name: syn::Ident,
+    /// Route directory
+    pub dir: Option<LitStr>,
+}
+
+impl Parse for ExportAppInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name: syn::Ident = input.parse()?;
+
+        let mut dir = None;
+
+        // Parse optional comma and arguments
+        while input.peek(syn::Token![,]) {
+            input.parse::<syn::Token![,]>()?;
+
+            if input.is_empty() {
+                break;
+            }
+
+            let ident: syn::Ident = input.parse()?;
+            let ident_str = ident.to_string();
+
+            match ident_str.as_str() {
+                "dir" => {
+                    input.parse::<syn::Token![=]>()?;
+                    dir = Some(input.parse()?);
+                }
+                _ => {
+                    return Err(syn::Error::new(
+                        ident.span(),
+                        format!("unknown field: `{ident_str}`. Expected `dir`"),
+                    ));
+                }
+            }
+        }
+
+        Ok(Self { name, dir })
+    }
+}
+
+/// Swagger UI HTML template. Contains `{}` format placeholder for the OpenAPI spec JSON.
+const SWAGGER_UI_HTML: &str = r##"<!DOCTYPE html><html lang="en"><head><meta charset="UTF-8"><title>Swagger UI</title><link rel="stylesheet" href="https://unpkg.com/swagger-ui-dist/swagger-ui.css" /></head><body style="margin: 0; padding: 0;"><div id="swagger-ui"></div><script src="https://unpkg.com/swagger-ui-dist/swagger-ui-bundle.js"></script><script src="https://unpkg.com/swagger-ui-dist/swagger-ui-standalone-preset.js"></script><script>const openapiSpec = {};window.onload = () => {{ SwaggerUIBundle({{ spec: openapiSpec, dom_id: "#swagger-ui", presets: [SwaggerUIBundle.presets.apis, SwaggerUIStandalonePreset], layout: "StandaloneLayout" }}); }};</script></body></html>"##;
+
+/// ReDoc HTML template. Contains `{}` format placeholder for the OpenAPI spec JSON.
+const REDOC_HTML: &str = r#"<!DOCTYPE html><html lang="en"><head><meta charset="UTF-8"><title>ReDoc</title><meta name="viewport" content="width=device-width, initial-scale=1"><style>body {{ margin: 0; padding: 0; }}</style><link rel="stylesheet" href="https://unpkg.com/redoc/bundles/redoc.standalone.css" /></head><body><div id="redoc-container"></div><script src="https://unpkg.com/redoc/bundles/redoc.standalone.js"></script><script>const openapiSpec = {};Redoc.init(openapiSpec, {{}}, document.getElementById("redoc-container"));</script></body></html>"#;
+
+/// Generate a documentation route handler (Swagger UI or ReDoc).
+///
+/// When `has_merge` is true, the handler merges specs from child apps at runtime.
+/// When false, it serves the spec directly from the compile-time constant.
+/// Either way, server URL variables are filled in from the environment on first request.
+fn generate_docs_route_tokens(
+    url: &str,
+    html_template: &str,
+    merge_spec_code: &[proc_macro2::TokenStream],
+    has_merge: bool,
+    spec_reload: Option<&proc_macro2::TokenStream>,
+) -> proc_macro2::TokenStream {
+    let method_path = http_method_to_token_stream(HttpMethod::Get);
+
+    if let Some(spec_reload) = spec_reload {
+        // Debug builds render a freshly scanned spec, falling back to the built one
+        let fresh_spec = if has_merge {
+            quote! {
+                let mut merged: vespera::OpenApi = vespera::serde_json::from_str(&spec).unwrap();
+                #(#merge_spec_code)*
+                merged.resolve_server_variables(vespera::servers::from_env);
+                let spec = vespera::serde_json::to_string(&merged).unwrap();
+            }
+        } else {
+            quote! {
+                let spec = vespera::servers::resolve_spec_from_env(&spec);
+            }
+        };
+        let built_html = if has_merge {
+            quote! {
+                static MERGED_SPEC: std::sync::OnceLock<String> = std::sync::OnceLock::new();
+                let spec = MERGED_SPEC.get_or_init(|| {
+                    let mut merged: vespera::OpenApi = vespera::serde_json::from_str(__VESPERA_SPEC).unwrap();
+                    #(#merge_spec_code)*
+                    merged.resolve_server_variables(vespera::servers::from_env);
+                    vespera::serde_json::to_string(&merged).unwrap()
+                });
+                static HTML: std::sync::OnceLock<String> = std::sync::OnceLock::new();
+                let html = HTML.get_or_init(|| {
+                    format!(#html_template, spec)
+                });
+            }
+        } else {
+            quote! {
+                static HTML: std::sync::OnceLock<String> = std::sync::OnceLock::new();
+                let html = HTML.get_or_init(|| {
+                    format!(#html_template, vespera::servers::resolve_spec_from_env(__VESPERA_SPEC))
+                });
+            }
+        };
+        return quote!(
+            .route(#url, #method_path(|| async {
+                #[cfg(debug_assertions)]
+                let reloaded: Option<String> = #spec_reload;
+                #[cfg(not(debug_assertions))]
+                let reloaded: Option<String> = None;
+                if let Some(spec) = reloaded {
+                    #fresh_spec
+                    return vespera::axum::response::Html(std::borrow::Cow::Owned(format!(#html_template, spec)));
+                }
+                #built_html
+                vespera::axum::response::Html(std::borrow::Cow::Borrowed(html.as_str()))
+            }))
+        );
+    }
+
+    if has_merge {
+        quote!(
+            .route(#url, #method_path(|| async {
+                static MERGED_SPEC: std::sync::OnceLock<String> = std::sync::OnceLock::new();
+                let spec = MERGED_SPEC.get_or_init(|| {
+                    let mut merged: vespera::OpenApi = vespera::serde_json::from_str(__VESPERA_SPEC).unwrap();
+                    #(#merge_spec_code)*
+                    merged.resolve_server_variables(vespera::servers::from_env);
+                    vespera::serde_json::to_string(&merged).unwrap()
+                });
+                static HTML: std::sync::OnceLock<String> = std::sync::OnceLock::new();
+                let html = HTML.get_or_init(|| {
+                    format!(#html_template, spec)
+                });
+                vespera::axum::response::Html(html.as_str())
+            }))
+        )
+    } else {
+        quote!(
+            .route(#url, #method_path(|| async {
+                static HTML: std::sync::OnceLock<String> = std::sync::OnceLock::new();
+                let html = HTML.get_or_init(|| {
+                    format!(#html_template, vespera::servers::resolve_spec_from_env(__VESPERA_SPEC))
+                });
+                vespera::axum::response::Html(html.as_str())
+            }))
+        )
+    }
+}
+/// Generate cron scheduler spawn code from collected cron metadata.
+fn generate_cron_scheduler_code(cron_jobs: &[CronMetadata]) -> proc_macro2::TokenStream {
+    if cron_jobs.is_empty() {
+        return quote!();
+    }
+
+    let job_additions: Vec<proc_macro2::TokenStream> = cron_jobs
+        .iter()
+        .map(|cron| {
+            let expression = &cron.expression;
+            let module_path = &cron.module_path;
+            let function_name = &cron.function_name;
+
+            // Build the full path: crate::module::function
+            let mut p: syn::punctuated::Punctuated<syn::PathSegment, syn::Token![::]> =
+                syn::punctuated::Punctuated::new();
+            p.push(syn::PathSegment {
+                ident: syn::Ident::new("crate", Span::call_site()),
+                arguments: syn::PathArguments::None,
+            });
+            p.extend(module_path.split("::").filter_map(|s| {
+                if s.is_empty() {
+                    None
+                } else {
+                    Some(syn::PathSegment {
+                        ident: syn::Ident::new(s, Span::call_site()),
+                        arguments: syn::PathArguments::None,
+                    })
+                }
+            }));
+            let func_ident = syn::Ident::new(function_name, Span::call_site());
+
+            let err_create = format!("vespera: failed to create cron job '{function_name}'");
+            let err_add = format!("vespera: failed to add cron job '{function_name}'");
+
+            quote! {
+                __vespera_cron_scheduler.add(
+                    vespera::tokio_cron_scheduler::Job::new_async(#expression, |_uuid, _l| {
+                        Box::pin(async move {
+                            #p::#func_ident().await;
+                        })
+                    }).expect(#err_create)
+                ).await.expect(#err_add);
+            }
+        })
+        .collect();
+
+    quote! {
+        vespera::tokio::spawn(async move {
+            let mut __vespera_cron_scheduler = vespera::tokio_cron_scheduler::JobScheduler::new().await
+                .expect("vespera: failed to create cron scheduler");
+            #(#job_additions)*
+            __vespera_cron_scheduler.start().await
+                .expect("vespera: failed to start cron scheduler");
+            // Keep scheduler alive forever
+            ::std::future::pending::<()>().await;
+        });
+    }
+}
+
+/// Options of the router generated by [`generate_router_code`].
+#[derive(Default)]
+pub struct RouterCodegenOptions<'a> {
+    /// Swagger UI route
+    pub docs_url: Option<&'a str>,
+    /// `ReDoc` route
+    pub redoc_url: Option<&'a str>,
+    /// Spec JSON served by the docs routes
+    pub spec_tokens: Option<proc_macro2::TokenStream>,
+    /// Apps whose routers and specs are merged in
+    pub merge_apps: &'a [syn::Path],
+    /// Cron jobs started with the router
+    pub cron_jobs: &'a [CronMetadata],
+    /// Answer `OPTIONS` for every path with its allowed methods
+    pub auto_options: bool,
+    /// Wrap each route in `vespera::instrument::layer`
+    pub instrument: bool,
+    /// Expression regenerating the spec for the docs routes in development
+    pub spec_reload: Option<&'a proc_macro2::TokenStream>,
+}
+
+/// Generate Axum router code from collected metadata
+#[allow(clippy::too_many_lines)]
+pub fn generate_router_code(
+    metadata: &CollectedMetadata,
+    options: RouterCodegenOptions<'_>,
+) -> proc_macro2::TokenStream {
+    let RouterCodegenOptions {
+        docs_url,
+        redoc_url,
+        spec_tokens,
+        merge_apps,
+        cron_jobs,
+        auto_options,
+        instrument,
+        spec_reload,
+    } = options;
+    let mut router_nests = Vec::new();
+    let mut path_methods: BTreeMap<&str, Vec<HttpMethod>> = BTreeMap::new();
+
+    for route in &metadata.routes {
+        // Unknown methods are reported by `vespera!` as VESPERA010
+        let Ok(http_method) = HttpMethod::try_from(route.method.as_str()) else {
+            continue;
+        };
+        path_methods
+            .entry(route.path.as_str())
+            .or_default()
+            .push(http_method);
+        let method_path = http_method_to_token_stream(http_method);
+        let path = &route.path;
+        let module_path = &route.module_path;
+        let function_name = &route.function_name;
+
+        let mut p: syn::punctuated::Punctuated<syn::PathSegment, syn::Token![::]> =
+            syn::punctuated::Punctuated::new();
+        p.push(syn::PathSegment {
+            ident: syn::Ident::new("crate", Span::call_site()),
+            arguments: syn::PathArguments::None,
+        });
+        p.extend(module_path.split("::").filter_map(|s| {
+            if s.is_empty() {
+                None
+            } else {
+                Some(syn::PathSegment {
+                    ident: syn::Ident::new(s, Span::call_site()),
+                    arguments: syn::PathArguments::None,
+                })
+            }
+        }));
+        let func_name = syn::Ident::new(function_name, Span::call_site());
+        let mut layers = limit_layers(route.limits.as_ref());
+        layers.extend(cache_layer(route.cache.as_ref()));
+        if instrument {
+            let operation_id = route.operation_id();
+            let tags = route.tags.as_deref().unwrap_or_default();
+            // Outermost, so the logged status includes limit rejections
+            layers.push(quote!(
+                vespera::instrument::layer(#operation_id, #path, &[#(#tags),*])
+            ));
+        }
+        // A single `.layer(...)`: chained calls leave the error type between them ambiguous.
+        // Tuple layers apply their first element outermost.
+        layers.reverse();
+        let layers = match layers.as_slice() {
+            [] => TokenStream::new(),
+            [layer] => quote!(.layer(#layer)),
+            layers => quote!(.layer((#(#layers),*))),
+        };
+        router_nests.push(quote!(
+            .route(#path, #method_path(#p::#func_name)#layers)
+        ));
+    }
+
+    // OPTIONS handlers answering with the `Allow` header
+    if auto_options {
+        for (path, methods) in &path_methods {
+            if methods.contains(&HttpMethod::Options) {
+                continue;
+            }
+            let allow = HttpMethod::allow_header_value(methods);
+            router_nests.push(quote!(
+                .route(#path, vespera::axum::routing::options(|| async {
+                    (
+                        vespera::axum::http::StatusCode::NO_CONTENT,
+                        [(vespera::axum::http::header::ALLOW, #allow)],
+                    )
+                }))
+            ));
+        }
+    }
+
+    // Check if we need to merge specs at runtime
+    let has_merge = !merge_apps.is_empty();
+
+    // Generate merge code once, reuse in both docs_url and redoc_url routes
+    let merge_spec_code: Vec<_> = merge_apps
+        .iter()
+        .map(|app_path| {
+            quote! {
+                if let Ok(other) = vespera::serde_json::from_str::<vespera::OpenApi>(#app_path::OPENAPI_SPEC) {
+                    merged.merge(other, None);
+                }
+            }
+        })
+        .collect();
+
+    if let Some(docs_url) = docs_url {
+        router_nests.push(generate_docs_route_tokens(
+            docs_url,
+            SWAGGER_UI_HTML,
+            &merge_spec_code,
+            has_merge,
+            spec_reload,
+        ));
+    }
+
+    if let Some(redoc_url) = redoc_url {
+        router_nests.push(generate_docs_route_tokens(
+            redoc_url,
+            REDOC_HTML,
+            &merge_spec_code,
+            has_merge,
+            spec_reload,
+        ));
+    }
+
+    let needs_spec_const = spec_tokens.is_some() && (docs_url.is_some() || redoc_url.is_some());
+    let cron_code = generate_cron_scheduler_code(cron_jobs);
+
+    if needs_spec_const {
+        let spec_expr = spec_tokens.unwrap();
+        if merge_apps.is_empty() {
+            quote! {
+                {
+                    const __VESPERA_SPEC: &str = #spec_expr;
+                    #cron_code
+                    vespera::axum::Router::new()
+                        #( #router_nests )*
+                }
+            }
+        } else {
+            quote! {
+                {
+                    const __VESPERA_SPEC: &str = #spec_expr;
+                    #cron_code
+                    vespera::VesperaRouter::new(
+                        vespera::axum::Router::new()
+                            #( #router_nests )*,
+                        vec![#( #merge_apps::router ),*]
+                    )
+                }
+            }
+        }
+    } else if merge_apps.is_empty() {
+        if cron_jobs.is_empty() {
+            quote! {
+                vespera::axum::Router::new()
+                    #( #router_nests )*
+            }
+        } else {
+            quote! {
+                {
+                    #cron_code
+                    vespera::axum::Router::new()
+                        #( #router_nests )*
+                }
+            }
+        }
+    } else {
+        // When merging apps, return VesperaRouter which defers the merge
+        // until with_state() is called. This is necessary because Axum requires
+        // merged routers to have the same state type.
+        if cron_jobs.is_empty() {
+            quote! {
+                vespera::VesperaRouter::new(
+                    vespera::axum::Router::new()
+                        #( #router_nests )*,
+                    vec![#( #merge_apps::router ),*]
+                )
+            }
+        } else {
+            quote! {
+                {
+                    #cron_code
+                    vespera::VesperaRouter::new(
+                        vespera::axum::Router::new()
+                            #( #router_nests )*,
+                        vec![#( #merge_apps::router ),*]
+                    )
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use rstest::rstest;
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::collector::collect_metadata;
+
+    fn create_temp_file(dir: &TempDir, filename: &str, content: &str) -> std::path::PathBuf {
+        let file_path = dir.path().join(filename);
+        if let Some(parent) = file_path.parent() {
+            fs::create_dir_all(parent).expect("Failed to create parent directory");
+        }
+        fs::write(&file_path, content).expect("Failed to write temp file");
+        file_path
+    }
+
+    #[test]
+    fn test_generate_router_code_empty() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let folder_name = "routes";
+
+        let result = generate_router_code(
+            &collect_metadata(temp_dir.path(), folder_name, &[])
+                .unwrap()
+                .0,
+            RouterCodegenOptions::default(),
+        );
+        let code = result.to_string();
+
+        // Should generate empty router
+        // quote! generates "vespera :: axum :: Router :: new ()" format
+        assert!(
+            code.contains("Router") && code.contains("new"),
+            "Code should contain Router::new(), got: {code}"
+        );
+        assert!(
+            !code.contains("route"),
+            "Code should not contain route, got: {code}"
+        );
+
+        drop(temp_dir);
+    }
+
+    #[rstest]
+    #[case::single_get_route(
+        "routes",
+        vec![(
+            "users.rs",
+            r#"
+#[route(get)]
+pub fn get_users() -> String {
+    "users".to_string()
+}
+"#,
+        )],
+        "get",
+        "/users",
+        "routes::users::get_users",
+    )]
+    #[case::single_post_route(
+        "routes",
+        vec![(
+            "create_user.rs",
+            r#"
+#[route(post)]
+pub fn create_user() -> String {
+    "created".to_string()
+}
+"#,
+        )],
+        "post",
+        "/create-user",
+        "routes::create_user::create_user",
+    )]
+    #[case::single_put_route(
+        "routes",
+        vec![(
+            "update_user.rs",
+            r#"
+#[route(put)]
+pub fn update_user() -> String {
+    "updated".to_string()
+}
+"#,
+        )],
+        "put",
+        "/update-user",
+        "routes::update_user::update_user",
+    )]
+    #[case::single_delete_route(
+        "routes",
+        vec![(
+            "delete_user.rs",
+            r#"
+#[route(delete)]
+pub fn delete_user() -> String {
+    "deleted".to_string()
+}
+"#,
+        )],
+        "delete",
+        "/delete-user",
+        "routes::delete_user::delete_user",
+    )]
+    #[case::single_patch_route(
+        "routes",
+        vec![(
+            "patch_user.rs",
+            r#"
+#[route(patch)]
+pub fn patch_user() -> String {
+    "patched".to_string()
+}
+"#,
+        )],
+        "patch",
+        "/patch-user",
+        "routes::patch_user::patch_user",
+    )]
+    #[case::route_with_custom_path(
+        "routes",
+        vec![(
+            "users.rs",
+            r#"
+#[route(get, path = "/api/users")]
+pub fn get_users() -> String {
+    "users".to_string()
+}
+"#,
+        )],
+        "get",
+        "/users/api/users",
+        "routes::users::get_users",
+    )]
+    #[case::nested_module(
+        "routes",
+        vec![(
+            "api/users.rs",
+            r#"
+#[route(get)]
+pub fn get_users() -> String {
+    "users".to_string()
+}
+"#,
+        )],
+        "get",
+        "/api/users",
+        "routes::api::users::get_users",
+    )]
+    #[case::deeply_nested_module(
+        "routes",
+        vec![(
+            "api/v1/users.rs",
+            r#"
+#[route(get)]
+pub fn get_users() -> String {
+    "users".to_string()
+}
+"#,
+        )],
+        "get",
+        "/api/v1/users",
+        "routes::api::v1::users::get_users",
+    )]
+    fn test_generate_router_code_single_route(
+        #[case] folder_name: &str,
+        #[case] files: Vec<(&str, &str)>,
+        #[case] expected_method: &str,
+        #[case] expected_path: &str,
+        #[case] expected_function_path: &str,
+    ) {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+
+        for (filename, content) in files {
+            create_temp_file(&temp_dir, filename, content);
+        }
+
+        let result = generate_router_code(
+            &collect_metadata(temp_dir.path(), folder_name, &[])
+                .unwrap()
+                .0,
+            RouterCodegenOptions::default(),
+        );
+        let code = result.to_string();
+
+        // Check router initialization (quote! generates "vespera :: axum :: Router :: new ()")
+        assert!(
+            code.contains("Router") && code.contains("new"),
+            "Code should contain Router::new(), got: {code}"
+        );
+
+        // Check route method
+        assert!(
+            code.contains(expected_method),
+            "Code should contain method: {expected_method}, got: {code}"
+        );
+
+        // Check route path
+        assert!(
+            code.contains(expected_path),
+            "Code should contain path: {expected_path}, got: {code}"
+        );
+
+        // Check function path (quote! adds spaces, so we check for parts)
+        let function_parts: Vec<&str> = expected_function_path.split("::").collect();
+        for part in &function_parts {
+            if !part.is_empty() {
+                assert!(
+                    code.contains(part),
+                    "Code should contain function part: {part}, got: {code}"
+                );
+            }
+        }
+
+        drop(temp_dir);
+    }
+
+    #[test]
+    fn test_generate_router_code_multiple_routes() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let folder_name = "routes";
+
+        // Create multiple route files
+        create_temp_file(
+            &temp_dir,
+            "users.rs",
+            r#"
+#[route(get)]
+pub fn get_users() -> String {
+    "users".to_string()
+}
+"#,
+        );
+
+        create_temp_file(
+            &temp_dir,
+            "create_user.rs",
+            r#"
+#[route(post)]
+pub fn create_user() -> String {
+    "created".to_string()
+}
+"#,
+        );
+
+        create_temp_file(
+            &temp_dir,
+            "update_user.rs",
+            r#"
+#[route(put)]
+pub fn update_user() -> String {
+    "updated".to_string()
+}
+"#,
+        );
+
+        let result = generate_router_code(
+            &collect_metadata(temp_dir.path(), folder_name, &[])
+                .unwrap()
+                .0,
+            RouterCodegenOptions::default(),
+        );
+        let code = result.to_string();
+
+        // Check router initialization (quote! generates "vespera :: axum :: Router :: new ()")
+        assert!(code.contains("Router") && code.contains("new"));
+
+        // Check all routes are present
+        assert!(code.contains("get_users"));
+        assert!(code.contains("create_user"));
+        assert!(code.contains("update_user"));
+
+        // Check methods
+        assert!(code.contains("get"));
+        assert!(code.contains("post"));
+        assert!(code.contains("put"));
+
+        // Count route calls (quote! generates ". route (" with spaces)
+        // Count occurrences of ". route (" pattern
+        let route_count = code.matches(". route (").count();
+        assert_eq!(
+            route_count, 3,
+            "Should have 3 route calls, got: {route_count}, code: {code}"
+        );
+
+        drop(temp_dir);
+    }
+
+    #[test]
+    fn test_generate_router_code_same_path_different_methods() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let folder_name = "routes";
+
+        // Create routes with same path but different methods
+        create_temp_file(
+            &temp_dir,
+            "users.rs",
+            r#"
+#[route(get)]
+pub fn get_users() -> String {
+    "users".to_string()
+}
+
+#[route(post)]
+pub fn create_users() -> String {
+    "created".to_string()
+}
+"#,
+        );
+
+        let result = generate_router_code(
+            &collect_metadata(temp_dir.path(), folder_name, &[])
+                .unwrap()
+                .0,
+            RouterCodegenOptions::default(),
+        );
+        let code = result.to_string();
+
+        // Check router initialization (quote! generates "vespera :: axum :: Router :: new ()")
+        assert!(code.contains("Router") && code.contains("new"));
+
+        // Check both routes are present
+        assert!(code.contains("get_users"));
+        assert!(code.contains("create_users"));
+
+        // Check methods
+        assert!(code.contains("get"));
+        assert!(code.contains("post"));
+
+        // Should have 2 routes (quote! generates ". route (" with spaces)
+        let route_count = code.matches(". route (").count();
+        assert_eq!(
+            route_count, 2,
+            "Should have 2 routes, got: {route_count}, code: {code}"
+        );
+
+        drop(temp_dir);
+    }
+
+    #[test]
+    fn test_generate_router_code_all_methods() {
+        use std::fmt::Write;
+
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let mut content = String::new();
+        for method in crate::http::HTTP_METHODS {
+            writeln!(
+                content,
+                "#[route({method})]\npub fn {method}_item() -> String {{ \"{method}\".to_string() }}\n"
+            )
+            .unwrap();
+        }
+        create_temp_file(&temp_dir, "items.rs", &content);
+
+        let result = generate_router_code(
+            &collect_metadata(temp_dir.path(), "routes", &[]).unwrap().0,
+            RouterCodegenOptions::default(),
+        );
+        let code = result.to_string();
+
+        for method in crate::http::HTTP_METHODS {
+            assert!(
+                code.contains(&format!(
+                    "routing :: {method} (crate :: routes :: items :: {method}_item)"
+                )),
+                "Code should route {method} to {method}_item, got: {code}"
+            );
+        }
+        assert_eq!(code.matches(". route (").count(), 8, "code: {code}");
+
+        drop(temp_dir);
+    }
+
+    #[test]
+    fn test_generate_router_code_with_mod_rs() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let folder_name = "routes";
+
+        // Create mod.rs file
+        create_temp_file(
+            &temp_dir,
+            "mod.rs",
+            r#"
+#[route(get)]
+pub fn index() -> String {
+    "index".to_string()
+}
+"#,
+        );
+
+        let result = generate_router_code(
+            &collect_metadata(temp_dir.path(), folder_name, &[])
+                .unwrap()
+                .0,
+            RouterCodegenOptions::default(),
+        );
+        let code = result.to_string();
+
+        // Check router initialization (quote! generates "vespera :: axum :: Router :: new ()")
+        assert!(code.contains("Router") && code.contains("new"));
+
+        // Check route is present
+        assert!(code.contains("index"));
+
+        // Path should be / (mod.rs maps to root, segments is empty)
+        // quote! generates "\"/\""
+        assert!(code.contains("\"/\""));
+
+        drop(temp_dir);
+    }
+
+    #[test]
+    fn test_generate_router_code_empty_folder_name() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let folder_name = "";
+
+        create_temp_file(
+            &temp_dir,
+            "users.rs",
+            r#"
+#[route(get)]
+pub fn get_users() -> String {
+    "users".to_string()
+}
+"#,
+        );
+
+        let result = generate_router_code(
+            &collect_metadata(temp_dir.path(), folder_name, &[])
+                .unwrap()
+                .0,
+            RouterCodegenOptions::default(),
+        );
+        let code = result.to_string();
+
+        // Check router initialization (quote! generates "vespera :: axum :: Router :: new ()")
+        assert!(code.contains("Router") && code.contains("new"));
+
+        // Check route is present
+        assert!(code.contains("get_users"));
+
+        // Module path should not have double colons
+        assert!(!code.contains("::users::users"));
+
+        drop(temp_dir);
+    }
+
+    // ========== Tests for parsing functions ==========
+
+    #[test]
+    fn test_parse_openapi_values_single() {
+        // Test that single string openapi value parses correctly via AutoRouterInput
+        let tokens = quote::quote!(openapi = "openapi.json");
+        let input: AutoRouterInput = syn::parse2(tokens).unwrap();
+        let openapi = input.openapi.unwrap();
+        assert_eq!(openapi.len(), 1);
+        assert_eq!(openapi[0].value(), "openapi.json");
+    }
+
+    #[test]
+    fn test_parse_openapi_values_array() {
+        // Test that array openapi value parses correctly via AutoRouterInput
+        let tokens = quote::quote!(openapi = ["openapi.json", "api.json"]);
+        let input: AutoRouterInput = syn::parse2(tokens).unwrap();
+        let openapi = input.openapi.unwrap();
+        assert_eq!(openapi.len(), 2);
+        assert_eq!(openapi[0].value(), "openapi.json");
+        assert_eq!(openapi[1].value(), "api.json");
+    }
+
+    #[test]
+    fn test_validate_server_url_valid_http() {
+        let lit = LitStr::new("http://localhost:3000", Span::call_site());
+        let result = validate_server_url(&lit);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), "http://localhost:3000");
+    }
+
+    #[test]
+    fn test_validate_server_url_valid_https() {
+        let lit = LitStr::new("https://api.example.com", Span::call_site());
+        let result = validate_server_url(&lit);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), "https://api.example.com");
+    }
+
+    #[test]
+    fn test_validate_server_url_invalid() {
+        let lit = LitStr::new("ftp://example.com", Span::call_site());
+        let result = validate_server_url(&lit);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_validate_server_url_no_scheme() {
+        let lit = LitStr::new("example.com", Span::call_site());
+        let result = validate_server_url(&lit);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_auto_router_input_parse_dir_only() {
+        let tokens = quote::quote!(dir = "api");
+        let input: AutoRouterInput = syn::parse2(tokens).unwrap();
+        assert_eq!(input.dir.unwrap().value(), "api");
+        assert!(input.openapi.is_none());
+    }
+
+    #[test]
+    fn test_auto_router_input_parse_string_as_dir() {
+        let tokens = quote::quote!("routes");
+        let input: AutoRouterInput = syn::parse2(tokens).unwrap();
+        assert_eq!(input.dir.unwrap().value(), "routes");
+    }
+
+    #[test]
+    fn test_auto_router_input_parse_openapi_single() {
+        let tokens = quote::quote!(openapi = "openapi.json");
+        let input: AutoRouterInput = syn::parse2(tokens).unwrap();
+        let openapi = input.openapi.unwrap();
+        assert_eq!(openapi.len(), 1);
+        assert_eq!(openapi[0].value(), "openapi.json");
+    }
+
+    #[test]
+    fn test_auto_router_input_parse_openapi_array() {
+        let tokens = quote::quote!(openapi = ["a.json", "b.json"]);
+        let input: AutoRouterInput = syn::parse2(tokens).unwrap();
+        let openapi = input.openapi.unwrap();
+        assert_eq!(openapi.len(), 2);
+    }
+
+    #[test]
+    fn test_auto_router_input_parse_title_version() {
+        let tokens = quote::quote!(title = "My API", version = "2.0.0");
+        let input: AutoRouterInput = syn::parse2(tokens).unwrap();
+        assert_eq!(input.title.unwrap().value(), "My API");
+        assert_eq!(input.version.unwrap().value(), "2.0.0");
+    }
+
+    #[test]
+    fn test_auto_router_input_parse_docs_redoc() {
+        let tokens = quote::quote!(docs_url = "/docs", redoc_url = "/redoc");
+        let input: AutoRouterInput = syn::parse2(tokens).unwrap();
+        assert_eq!(input.docs_url.unwrap().value(), "/docs");
+        assert_eq!(input.redoc_url.unwrap().value(), "/redoc");
+    }
+
+    #[test]
+    fn test_auto_router_input_parse_servers_single() {
+        let tokens = quote::quote!(servers = "http://localhost:3000");
+        let input: AutoRouterInput = syn::parse2(tokens).unwrap();
+        let servers = input.servers.unwrap();
+        assert_eq!(servers.len(), 1);
+        assert_eq!(servers[0].url, "http://localhost:3000");
+        assert!(servers[0].description.is_none());
+    }
+
+    #[test]
+    fn test_auto_router_input_parse_servers_array_strings() {
+        let tokens = quote::quote!(servers = ["http://localhost:3000", "https://api.example.com"]);
+        let input: AutoRouterInput = syn::parse2(tokens).unwrap();
+        let servers = input.servers.unwrap();
+        assert_eq!(servers.len(), 2);
+    }
+
+    #[test]
+    fn test_auto_router_input_parse_servers_tuple() {
+        let tokens = quote::quote!(servers = [("http://localhost:3000", "Development")]);
+        let input: AutoRouterInput = syn::parse2(tokens).unwrap();
+        let servers = input.servers.unwrap();
+        assert_eq!(servers.len(), 1);
+        assert_eq!(servers[0].url, "http://localhost:3000");
+        assert_eq!(servers[0].description, Some("Development".to_string()));
+    }
+
+    #[test]
+    fn test_auto_router_input_parse_servers_struct() {
+        let tokens =
+            quote::quote!(servers = [{ url = "http://localhost:3000", description = "Dev" }]);
+        let input: AutoRouterInput = syn::parse2(tokens).unwrap();
+        let servers = input.servers.unwrap();
+        assert_eq!(servers.len(), 1);
+        assert_eq!(servers[0].url, "http://localhost:3000");
+        assert_eq!(servers[0].description, Some("Dev".to_string()));
+    }
+
+    #[test]
+    fn test_auto_router_input_parse_servers_single_struct() {
+        let tokens = quote::quote!(servers = { url = "https://api.example.com" });
+        let input: AutoRouterInput = syn::parse2(tokens).unwrap();
+        let servers = input.servers.unwrap();
+        assert_eq!(servers.len(), 1);
+        assert_eq!(servers[0].url, "https://api.example.com");
+    }
+
+    #[test]
+    fn test_auto_router_input_parse_unknown_field() {
+        let tokens = quote::quote!(unknown_field = "value");
+        let result: syn::Result<AutoRouterInput> = syn::parse2(tokens);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_auto_router_input_parse_all_fields() {
+        let tokens = quote::quote!(
+            dir = "api",
+            openapi = "openapi.json",
+            title = "Test API",
+            version = "1.0.0",
+            docs_url = "/docs",
+            redoc_url = "/redoc",
+            servers = "http://localhost:3000"
+        );
+        let input: AutoRouterInput = syn::parse2(tokens).unwrap();
+        assert!(input.dir.is_some());
+        assert!(input.openapi.is_some());
+        assert!(input.title.is_some());
+        assert!(input.version.is_some());
+        assert!(input.docs_url.is_some());
+        assert!(input.redoc_url.is_some());
+        assert!(input.servers.is_some());
+    }
+
+    #[test]
+    fn test_generate_router_code_with_docs() {
+        let metadata = CollectedMetadata::new();
+        let spec = r#"{"openapi":"3.1.0"}"#;
+
+        let result = generate_router_code(
+            &metadata,
+            RouterCodegenOptions {
+                docs_url: Some("/docs"),
+                spec_tokens: Some(quote::quote!(#spec)),
+                ..Default::default()
+            },
+        );
+        let code = result.to_string();
+
+        assert!(code.contains("/docs"));
+        assert!(code.contains("swagger-ui"));
+        assert!(code.contains("__VESPERA_SPEC"));
+        assert!(code.contains("OnceLock"));
+        assert!(code.contains("vespera :: servers :: resolve_spec_from_env (__VESPERA_SPEC)"));
+    }
+
+    #[test]
+    fn test_generate_router_code_with_redoc() {
+        let metadata = CollectedMetadata::new();
+        let spec = r#"{"openapi":"3.1.0"}"#;
+
+        let result = generate_router_code(
+            &metadata,
+            RouterCodegenOptions {
+                redoc_url: Some("/redoc"),
+                spec_tokens: Some(quote::quote!(#spec)),
+                ..Default::default()
+            },
+        );
+        let code = result.to_string();
+
+        assert!(code.contains("/redoc"));
+        assert!(code.contains("redoc"));
+        assert!(code.contains("__VESPERA_SPEC"));
+        assert!(code.contains("OnceLock"));
+    }
+
+    #[test]
+    fn test_generate_router_code_with_both_docs() {
+        let metadata = CollectedMetadata::new();
+        let spec = r#"{"openapi":"3.1.0"}"#;
+
+        let result = generate_router_code(
+            &metadata,
+            RouterCodegenOptions {
+                docs_url: Some("/docs"),
+                redoc_url: Some("/redoc"),
+                spec_tokens: Some(quote::quote!(#spec)),
+                ..Default::default()
+            },
+        );
+        let code = result.to_string();
+
+        assert!(code.contains("/docs"));
+        assert!(code.contains("/redoc"));
+        assert!(code.contains("__VESPERA_SPEC"));
+    }
+
+    #[test]
+    fn test_swagger_html_template_renders_valid_quotes() {
+        assert!(
+            !SWAGGER_UI_HTML.contains(r#"\""#),
+            "Swagger template should not contain literal backslash-quotes: {SWAGGER_UI_HTML}"
+        );
+        assert!(
+            SWAGGER_UI_HTML.contains(r#"href="https://unpkg.com/swagger-ui-dist/swagger-ui.css""#)
+        );
+        assert!(
+            SWAGGER_UI_HTML
+                .contains(r#"src="https://unpkg.com/swagger-ui-dist/swagger-ui-bundle.js""#)
+        );
+        assert!(SWAGGER_UI_HTML.contains(r##"dom_id: "#swagger-ui""##));
+    }
+
+    #[test]
+    fn test_redoc_html_template_renders_valid_quotes() {
+        assert!(
+            !REDOC_HTML.contains(r#"\""#),
+            "ReDoc template should not contain literal backslash-quotes: {REDOC_HTML}"
+        );
+        assert!(
+            REDOC_HTML.contains(r#"href="https://unpkg.com/redoc/bundles/redoc.standalone.css""#)
+        );
+        assert!(
+            REDOC_HTML.contains(r#"src="https://unpkg.com/redoc/bundles/redoc.standalone.js""#)
+        );
+        assert!(REDOC_HTML.contains(r#"document.getElementById("redoc-container")"#));
+    }
+
+    #[test]
+    fn test_parse_server_struct_url_only() {
+        // Test server struct parsing via AutoRouterInput
+        let tokens = quote::quote!(servers = { url = "http://localhost:3000" });
+        let input: AutoRouterInput = syn::parse2(tokens).unwrap();
+        let servers = input.servers.unwrap();
+        assert_eq!(servers.len(), 1);
+        assert_eq!(servers[0].url, "http://localhost:3000");
+        assert!(servers[0].description.is_none());
+    }
+
+    #[test]
+    fn test_parse_server_struct_with_description() {
+        let tokens =
+            quote::quote!(servers = { url = "http://localhost:3000", description = "Local" });
+        let input: AutoRouterInput = syn::parse2(tokens).unwrap();
+        let servers = input.servers.unwrap();
+        assert_eq!(servers[0].description, Some("Local".to_string()));
+    }
+
+    #[test]
+    fn test_parse_server_struct_with_variables() {
+        let tokens = quote::quote!(servers = {
+            url = "https://{env}.{region}.example.com",
+            variables = { region = "us-east-1", env = ["dev", "prod"] }
+        });
+        let input: AutoRouterInput = syn::parse2(tokens).unwrap();
+        let server = &process_vespera_input(input).servers.unwrap()[0];
+        let variables = server.variables.as_ref().unwrap();
+        assert_eq!(variables["region"].default, "us-east-1");
+        assert_eq!(variables["region"].r#enum, None);
+        assert_eq!(variables["env"].default, "dev");
+        assert_eq!(
+            variables["env"].r#enum,
+            Some(vec!["dev".to_string(), "prod".to_string()])
+        );
+    }
+
+    #[rstest]
+    #[case(
+        quote::quote!(servers = { url = "https://api.example.com", variables = { region = "eu" } }),
+        "server variable `region` does not appear in `https://api.example.com`"
+    )]
+    #[case(
+        quote::quote!(servers = { url = "https://{env}.example.com", variables = { env = [] } }),
+        "server variable needs at least one allowed value"
+    )]
+    fn test_parse_server_variables_errors(
+        #[case] tokens: proc_macro2::TokenStream,
+        #[case] expected: &str,
+    ) {
+        let err = syn::parse2::<AutoRouterInput>(tokens).err().unwrap();
+        assert_eq!(err.to_string(), expected);
+    }
+
+    #[test]
+    fn test_parse_server_struct_unknown_field() {
+        let tokens = quote::quote!(servers = { url = "http://localhost:3000", unknown = "test" });
+        let result: syn::Result<AutoRouterInput> = syn::parse2(tokens);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parse_server_struct_missing_url() {
+        let tokens = quote::quote!(servers = { description = "test" });
+        let result: syn::Result<AutoRouterInput> = syn::parse2(tokens);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parse_servers_tuple_url_only() {
+        let tokens = quote::quote!(servers = [("http://localhost:3000")]);
+        let input: AutoRouterInput = syn::parse2(tokens).unwrap();
+        let servers = input.servers.unwrap();
+        assert_eq!(servers.len(), 1);
+        assert!(servers[0].description.is_none());
+    }
+
+    #[test]
+    fn test_parse_servers_invalid_url() {
+        let tokens = quote::quote!(servers = "invalid-url");
+        let result: syn::Result<AutoRouterInput> = syn::parse2(tokens);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_generate_router_code_unknown_http_method() {
+        // Test lines 337-340: route with unknown HTTP method is skipped in router codegen
+        let mut metadata = CollectedMetadata {
+            routes: Vec::new(),
+            structs: Vec::new(),
+            crons: Vec::new(),
+            module_defaults: std::collections::BTreeMap::new(),
+            invalid_files: Vec::new(),
+        };
+        metadata.routes.push(crate::metadata::RouteMetadata {
+            method: "INVALID".to_string(),
+            path: "/users".to_string(),
+            function_name: "get_users".to_string(),
+            module_path: "routes::users".to_string(),
+            file_path: "dummy.rs".to_string(),
+            signature: "fn get_users() -> String".to_string(),
+            ..Default::default()
+        });
+
+        let result = generate_router_code(&metadata, RouterCodegenOptions::default());
+        let code = result.to_string();
+
+        // Router should be generated but without any route calls
+        assert!(
+            code.contains("Router") && code.contains("new"),
+            "Code should contain Router::new(), got: {code}"
+        );
+        assert!(
+            !code.contains(". route ("),
+            "Route with unknown HTTP method should be skipped, got: {code}"
+        );
+    }
+
+    #[test]
+    fn test_generate_router_code_unknown_method_skipped_valid_kept() {
+        // Test that unknown methods are skipped while valid routes are still generated
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let folder_name = "routes";
+
+        create_temp_file(
+            &temp_dir,
+            "users.rs",
+            r#"
+#[route(get)]
+pub fn get_users() -> String {
+    "users".to_string()
+}
+"#,
+        );
+
+        let (mut metadata, _file_asts) =
+            collect_metadata(temp_dir.path(), folder_name, &[]).unwrap();
+        // Inject an additional route with invalid method
+        metadata.routes.push(crate::metadata::RouteMetadata {
+            method: "CONNECT".to_string(),
+            path: "/invalid".to_string(),
+            function_name: "connect_handler".to_string(),
+            module_path: "routes::invalid".to_string(),
+            file_path: "dummy.rs".to_string(),
+            signature: "fn connect_handler() -> String".to_string(),
+            ..Default::default()
+        });
+
+        let result = generate_router_code(&metadata, RouterCodegenOptions::default());
+        let code = result.to_string();
+
+        // Valid route should be present
+        assert!(
+            code.contains("get_users"),
+            "Valid route should be present, got: {code}"
+        );
+        // Invalid route should be skipped
+        assert!(
+            !code.contains("connect_handler"),
+            "Invalid method route should be skipped, got: {code}"
+        );
+
+        drop(temp_dir);
+    }
+
+    #[test]
+    fn test_auto_router_input_parse_invalid_token() {
+        // Test line 149: neither ident nor string literal triggers lookahead error
+        let tokens = quote::quote!(123);
+        let result: syn::Result<AutoRouterInput> = syn::parse2(tokens);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_auto_router_input_empty() {
+        // Test empty input - should use defaults/env vars
+        let tokens = quote::quote!();
+        let result: syn::Result<AutoRouterInput> = syn::parse2(tokens);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_auto_router_input_multiple_commas() {
+        // Test input with trailing comma
+        let tokens = quote::quote!(dir = "api",);
+        let result: syn::Result<AutoRouterInput> = syn::parse2(tokens);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_auto_router_input_no_comma() {
+        // Test input without comma between fields (should stop at second field)
+        let tokens = quote::quote!(dir = "api" title = "Test");
+        let result: syn::Result<AutoRouterInput> = syn::parse2(tokens);
+        // This should fail or only parse first field
+        assert!(result.is_err());
+    }
+
+    // ========== Tests for process_vespera_input ==========
+
+    #[test]
+    fn test_process_vespera_input_defaults() {
+        let tokens = quote::quote!();
+        let input: AutoRouterInput = syn::parse2(tokens).unwrap();
+        let processed = process_vespera_input(input);
+        assert_eq!(processed.folder_name, "routes");
+        assert!(processed.openapi_file_names.is_empty());
+        assert!(processed.title.is_none());
+        assert!(processed.docs_url.is_none());
+    }
+
+    #[test]
+    fn test_process_vespera_input_all_fields() {
+        let tokens = quote::quote!(
+            dir = "api",
+            openapi = ["openapi.json", "api.json"],
+            title = "My API",
+            version = "1.0.0",
+            docs_url = "/docs",
+            redoc_url = "/redoc",
+            servers = "http://localhost:3000"
+        );
+        let input: AutoRouterInput = syn::parse2(tokens).unwrap();
+        let processed = process_vespera_input(input);
+        assert_eq!(processed.folder_name, "api");
+        assert_eq!(
+            processed.openapi_file_names,
+            vec!["openapi.json", "api.json"]
+        );
+        assert_eq!(processed.title, Some("My API".to_string()));
+        assert_eq!(processed.version, Some("1.0.0".to_string()));
+        assert_eq!(processed.docs_url, Some("/docs".to_string()));
+        assert_eq!(processed.redoc_url, Some("/redoc".to_string()));
+        let servers = processed.servers.unwrap();
+        assert_eq!(servers.len(), 1);
+        assert_eq!(servers[0].url, "http://localhost:3000");
+    }
+
+    #[test]
+    fn test_process_vespera_input_servers_with_description() {
+        let tokens = quote::quote!(
+            servers = [{ url = "https://api.example.com", description = "Production" }]
+        );
+        let input: AutoRouterInput = syn::parse2(tokens).unwrap();
+        let processed = process_vespera_input(input);
+        let servers = processed.servers.unwrap();
+        assert_eq!(servers[0].url, "https://api.example.com");
+        assert_eq!(servers[0].description, Some("Production".to_string()));
+    }
+
+    // ========== Tests for parse_merge_values ==========
+
+    #[test]
+    fn test_parse_merge_values_single() {
+        let tokens = quote::quote!(merge = [some::path::App]);
+        let input: AutoRouterInput = syn::parse2(tokens).unwrap();
+        let merge = input.merge.unwrap();
+        assert_eq!(merge.len(), 1);
+        // Check the path segments
+        let path = &merge[0];
+        let segments: Vec<_> = path.segments.iter().map(|s| s.ident.to_string()).collect();
+        assert_eq!(segments, vec!["some", "path", "App"]);
+    }
+
+    #[test]
+    fn test_parse_merge_values_multiple() {
+        let tokens = quote::quote!(merge = [first::App, second::Other]);
+        let input: AutoRouterInput = syn::parse2(tokens).unwrap();
+        let merge = input.merge.unwrap();
+        assert_eq!(merge.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_merge_values_empty() {
+        let tokens = quote::quote!(merge = []);
+        let input: AutoRouterInput = syn::parse2(tokens).unwrap();
+        let merge = input.merge.unwrap();
+        assert!(merge.is_empty());
+    }
+
+    #[test]
+    fn test_parse_merge_values_with_trailing_comma() {
+        let tokens = quote::quote!(merge = [app::MyApp,]);
+        let input: AutoRouterInput = syn::parse2(tokens).unwrap();
+        let merge = input.merge.unwrap();
+        assert_eq!(merge.len(), 1);
+    }
+
+    // ========== Tests for parse_scopes_values ==========
+
+    #[test]
+    fn test_parse_scopes_values() {
+        let tokens = quote::quote!(scopes = ["admin", "internal-ops"]);
+        let input: AutoRouterInput = syn::parse2(tokens).unwrap();
+        let processed = process_vespera_input(input);
+        assert_eq!(processed.scopes, vec!["admin", "internal-ops"]);
+    }
+
+    #[test]
+    fn test_parse_scopes_values_default_empty() {
+        let tokens = quote::quote!(dir = "routes");
+        let input: AutoRouterInput = syn::parse2(tokens).unwrap();
+        assert!(process_vespera_input(input).scopes.is_empty());
+    }
+
+    #[rstest]
+    #[case(quote::quote!(scopes = [""]))]
+    #[case(quote::quote!(scopes = ["a/b"]))]
+    #[case(quote::quote!(scopes = ["has space"]))]
+    fn test_parse_scopes_values_invalid(#[case] tokens: proc_macro2::TokenStream) {
+        let result: syn::Result<AutoRouterInput> = syn::parse2(tokens);
+        let err = result.err().expect("expected invalid scope error");
+        assert!(err.to_string().contains("invalid scope"));
+    }
+
+    // ========== Tests for parse_tag_rules_values ==========
+
+    #[test]
+    fn test_parse_tag_rules_values() {
+        let tokens = quote::quote!(tag_rules = [("/admin/**", "admin"), ("/users/**", "users"),]);
+        let input: AutoRouterInput = syn::parse2(tokens).unwrap();
+        let processed = process_vespera_input(input);
+        assert_eq!(
+            processed.tag_rules,
+            vec![
+                ("/admin/**".to_string(), "admin".to_string()),
+                ("/users/**".to_string(), "users".to_string()),
+            ]
+        );
+    }
+
+    #[rstest]
+    #[case(quote::quote!(tag_rules = [("admin/**", "admin")]), "must start with `/`")]
+    #[case(quote::quote!(tag_rules = [("/admin/**")]), "expected `,`")]
+    #[case(quote::quote!(tag_rules = [("/admin/**", "admin", "x")]), "tag rule must be")]
+    #[case(quote::quote!(tag_rules = [("/a", "a") ("/b", "b")]), "expected `,` between tag rules")]
+    fn test_parse_tag_rules_values_invalid(
+        #[case] tokens: proc_macro2::TokenStream,
+        #[case] expected: &str,
+    ) {
+        let result: syn::Result<AutoRouterInput> = syn::parse2(tokens);
+        let err = result.err().expect("expected invalid tag rule error");
+        assert!(err.to_string().contains(expected), "{err}");
+    }
+
+    #[test]
+    fn test_generate_router_code_auto_options() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        create_temp_file(
+            &temp_dir,
+            "users.rs",
+            r#"
+#[route(get)]
+pub fn get_users() -> String {
+    "users".to_string()
+}
+
+#[route(post)]
+pub fn create_user() -> String {
+    "created".to_string()
+}
+"#,
+        );
+        let (metadata, _file_asts) = collect_metadata(temp_dir.path(), "routes", &[]).unwrap();
+
+        let code = generate_router_code(
+            &metadata,
+            RouterCodegenOptions {
+                auto_options: true,
+                ..Default::default()
+            },
+        )
+        .to_string();
+        assert!(code.contains("routing :: options"), "got: {code}");
+        assert!(code.contains("\"GET, POST, HEAD, OPTIONS\""), "got: {code}");
+
+        let code = generate_router_code(&metadata, RouterCodegenOptions::default()).to_string();
+        assert!(!code.contains("routing :: options"), "got: {code}");
+    }
+
+    #[test]
+    fn test_generate_router_code_route_limits() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        create_temp_file(
+            &temp_dir,
+            "uploads.rs",
+            r#"
+#[route(post, limits = (body = "1MB", timeout = "30s"))]
+pub fn upload() -> String {
+    "ok".to_string()
+}
+"#,
+        );
+        let (metadata, _file_asts) = collect_metadata(temp_dir.path(), "routes", &[]).unwrap();
+
+        let code = generate_router_code(&metadata, RouterCodegenOptions::default()).to_string();
+        assert!(
+            code.contains(
+                ". layer ((vespera :: limits :: body_limit (1048576) , vespera :: axum :: extract :: DefaultBodyLimit :: max (1048576) , vespera :: limits :: timeout (:: std :: time :: Duration :: from_millis (30000))))"
+            ),
+            "got: {code}"
+        );
+    }
+
+    #[test]
+    fn test_generate_router_code_route_cache() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        create_temp_file(
+            &temp_dir,
+            "products.rs",
+            r#"
+#[route(get, cache = (max_age = 60, public, apply))]
+pub fn list() -> String {
+    "ok".to_string()
+}
+
+#[route(get, path = "/{id}", cache = (no_store))]
+pub fn get(Path(id): Path<i32>) -> String {
+    id.to_string()
+}
+"#,
+        );
+        let (metadata, _file_asts) = collect_metadata(temp_dir.path(), "routes", &[]).unwrap();
+
+        let code = generate_router_code(&metadata, RouterCodegenOptions::default()).to_string();
+        assert_eq!(
+            code.matches("vespera :: cache :: cache_control").count(),
+            1,
+            "got: {code}"
+        );
+        assert!(
+            code.contains(". layer (vespera :: cache :: cache_control (\"public, max-age=60\"))"),
+            "got: {code}"
+        );
+    }
+
+    #[test]
+    fn test_generate_router_code_instrument() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        create_temp_file(
+            &temp_dir,
+            "uploads.rs",
+            r#"
+#[route(post, tags = ["files"], limits = (body = "1MB"))]
+pub fn upload() -> String {
+    "ok".to_string()
+}
+"#,
+        );
+        let (metadata, _file_asts) = collect_metadata(temp_dir.path(), "routes", &[]).unwrap();
+
+        let code = generate_router_code(
+            &metadata,
+            RouterCodegenOptions {
+                instrument: true,
+                ..Default::default()
+            },
+        )
+        .to_string();
+        assert!(
+            code.contains(
+                ". layer ((vespera :: instrument :: layer (\"uploads_upload\" , \"/uploads\" , & [\"files\"]) , vespera :: limits :: body_limit (1048576)"
+            ),
+            "got: {code}"
+        );
+
+        let code = generate_router_code(&metadata, RouterCodegenOptions::default()).to_string();
+        assert!(!code.contains("instrument"), "got: {code}");
+    }
+
+    #[test]
+    fn test_parse_auto_methods_values() {
+        let tokens = quote::quote!(auto_methods = ["head", "OPTIONS"]);
+        let input: AutoRouterInput = syn::parse2(tokens).unwrap();
+        assert_eq!(
+            process_vespera_input(input).auto_methods,
+            vec![HttpMethod::Head, HttpMethod::Options]
+        );
+    }
+
+    #[rstest]
+    #[case(quote::quote!(auto_methods = ["trace"]))]
+    #[case(quote::quote!(auto_methods = ["nope"]))]
+    fn test_parse_auto_methods_values_invalid(#[case] tokens: proc_macro2::TokenStream) {
+        let result: syn::Result<AutoRouterInput> = syn::parse2(tokens);
+        let err = result.err().expect("expected invalid auto method error");
+        assert!(err.to_string().contains("invalid auto method"));
+    }
+
+    #[test]
+    fn test_parse_global_params_values() {
+        let tokens = quote::quote!(global_params = [accept_language(), header("X-Request-Id")]);
+        let input: AutoRouterInput = syn::parse2(tokens).unwrap();
+        let global_params = process_vespera_input(input).global_params;
+        assert_eq!(
+            global_params,
+            vec![
+                GlobalParam::AcceptLanguage,
+                GlobalParam::Header("X-Request-Id".to_string())
+            ]
+        );
+
+        let (name, parameter) = global_params[1].to_component();
+        assert_eq!(name, "XRequestId");
+        assert_eq!(parameter.name, "X-Request-Id");
+        let (name, parameter) = global_params[0].to_component();
+        assert_eq!(name, "AcceptLanguage");
+        assert_eq!(parameter.name, "Accept-Language");
+    }
+
+    #[rstest]
+    #[case(quote::quote!(global_params = [timezone()]), "unknown global parameter")]
+    #[case(quote::quote!(global_params = [accept_language("en")]), "takes no arguments")]
+    #[case(quote::quote!(global_params = [header("Bad Header")]), "invalid header name")]
+    #[case(quote::quote!(global_params = [header("A", "B")]), "single header name")]
+    fn test_parse_global_params_values_invalid(
+        #[case] tokens: proc_macro2::TokenStream,
+        #[case] expected: &str,
+    ) {
+        let result: syn::Result<AutoRouterInput> = syn::parse2(tokens);
+        let err = result
+            .err()
+            .expect("expected invalid global parameter error");
+        assert!(err.to_string().contains(expected), "{err}");
+    }
+
+    #[test]
+    fn test_parse_anyhow_error_value() {
+        let input: AutoRouterInput =
+            syn::parse2(quote::quote!(anyhow_error = (500, crate::ErrorResponse))).unwrap();
+        assert_eq!(
+            process_vespera_input(input).anyhow_error,
+            Some((500, "crate :: ErrorResponse".to_string()))
+        );
+
+        let input: AutoRouterInput = syn::parse2(quote::quote!(dir = "routes")).unwrap();
+        assert!(process_vespera_input(input).anyhow_error.is_none());
+    }
+
+    #[rstest]
+    #[case(quote::quote!(anyhow_error = (200, ErrorResponse)), "error status code")]
+    #[case(quote::quote!(anyhow_error = (500)), "expected `,`")]
+    #[case(quote::quote!(anyhow_error = (500, ErrorResponse, Extra)), "expected `(status, BodyType)`")]
+    fn test_parse_anyhow_error_value_invalid(
+        #[case] tokens: proc_macro2::TokenStream,
+        #[case] expected: &str,
+    ) {
+        let err = syn::parse2::<AutoRouterInput>(tokens).err().unwrap();
+        assert!(err.to_string().contains(expected), "{err}");
+    }
+
+    #[rstest]
+    #[case(quote::quote!(closed_objects = true), true)]
+    #[case(quote::quote!(closed_objects = false), false)]
+    #[case(quote::quote!(dir = "routes"), false)]
+    fn test_parse_closed_objects(#[case] tokens: proc_macro2::TokenStream, #[case] expected: bool) {
+        let input: AutoRouterInput = syn::parse2(tokens).unwrap();
+        assert_eq!(process_vespera_input(input).closed_objects, expected);
+    }
+
+    #[rstest]
+    #[case(quote::quote!(instrument = true), true)]
+    #[case(quote::quote!(instrument = false), false)]
+    #[case(quote::quote!(dir = "routes"), false)]
+    fn test_parse_instrument(#[case] tokens: proc_macro2::TokenStream, #[case] expected: bool) {
+        let input: AutoRouterInput = syn::parse2(tokens).unwrap();
+        assert_eq!(process_vespera_input(input).instrument, expected);
+    }
+
+    #[rstest]
+    #[case(quote::quote!(sdk_groups = true), true)]
+    #[case(quote::quote!(sdk_groups = false), false)]
+    #[case(quote::quote!(dir = "routes"), false)]
+    fn test_parse_sdk_groups(#[case] tokens: proc_macro2::TokenStream, #[case] expected: bool) {
+        let input: AutoRouterInput = syn::parse2(tokens).unwrap();
+        assert_eq!(process_vespera_input(input).sdk_groups, expected);
+    }
+
+    #[rstest]
+    #[case(quote::quote!(declaration_order = true), true)]
+    #[case(quote::quote!(declaration_order = false), false)]
+    #[case(quote::quote!(dir = "routes"), false)]
+    fn test_parse_declaration_order(
+        #[case] tokens: proc_macro2::TokenStream,
+        #[case] expected: bool,
+    ) {
+        let input: AutoRouterInput = syn::parse2(tokens).unwrap();
+        assert_eq!(process_vespera_input(input).declaration_order, expected);
+    }
+
+    #[rstest]
+    #[case(quote::quote!(strict_nullability = true), true)]
+    #[case(quote::quote!(strict_nullability = false), false)]
+    #[case(quote::quote!(dir = "routes"), false)]
+    fn test_parse_strict_nullability(
+        #[case] tokens: proc_macro2::TokenStream,
+        #[case] expected: bool,
+    ) {
+        let input: AutoRouterInput = syn::parse2(tokens).unwrap();
+        assert_eq!(process_vespera_input(input).strict_nullability, expected);
+    }
+
+    #[rstest]
+    #[case(quote::quote!(generic_names = "underscore"), Ok(Some(GenericNaming::Underscore)))]
+    #[case(quote::quote!(generic_names = "concat"), Ok(Some(GenericNaming::Concat)))]
+    #[case(quote::quote!(dir = "routes"), Ok(None))]
+    #[case(quote::quote!(generic_names = "camel"), Err("invalid generic names: `camel`"))]
+    fn test_parse_generic_names(
+        #[case] tokens: proc_macro2::TokenStream,
+        #[case] expected: Result<Option<GenericNaming>, &str>,
+    ) {
+        match (syn::parse2::<AutoRouterInput>(tokens), expected) {
+            (Ok(input), Ok(expected)) => {
+                assert_eq!(process_vespera_input(input).generic_names, expected);
+            }
+            (Err(err), Err(expected)) => assert!(err.to_string().contains(expected), "{err}"),
+            (result, expected) => panic!("expected {expected:?}, got {:?}", result.err()),
+        }
+    }
+
+    #[rstest]
+    #[case(quote::quote!(path_order = "tag"), PathOrder::Tag, false, None)]
+    #[case(quote::quote!(path_order = "source", collapse_paths = true), PathOrder::Source, true, None)]
+    #[case(quote::quote!(path_param_case = "camelCase"), PathOrder::Alphabetical, false, Some("camelCase"))]
+    #[case(quote::quote!(dir = "routes"), PathOrder::Alphabetical, false, None)]
+    fn test_parse_path_layout(
+        #[case] tokens: proc_macro2::TokenStream,
+        #[case] path_order: PathOrder,
+        #[case] collapse_paths: bool,
+        #[case] path_param_case: Option<&str>,
+    ) {
+        let processed = process_vespera_input(syn::parse2(tokens).unwrap());
+        assert_eq!(processed.path_order, path_order);
+        assert_eq!(processed.collapse_paths, collapse_paths);
+        assert_eq!(processed.path_param_case.as_deref(), path_param_case);
+    }
+
+    #[rstest]
+    #[case(quote::quote!(path_order = "random"), "invalid path order")]
+    #[case(quote::quote!(path_param_case = "Title Case"), "invalid path parameter case")]
+    fn test_parse_path_layout_invalid(
+        #[case] tokens: proc_macro2::TokenStream,
+        #[case] expected: &str,
+    ) {
+        let result: syn::Result<AutoRouterInput> = syn::parse2(tokens);
+        let err = result.err().expect("expected invalid path layout error");
+        assert!(err.to_string().contains(expected), "{err}");
+    }
+
+    #[rstest]
+    #[case(
+        quote::quote!(json_schema_dialect = "https://spec.openapis.org/oas/3.1/dialect/base"),
+        Ok(Some("https://spec.openapis.org/oas/3.1/dialect/base"))
+    )]
+    #[case(quote::quote!(dir = "routes"), Ok(None))]
+    #[case(
+        quote::quote!(json_schema_dialect = "2020-12"),
+        Err("invalid JSON Schema dialect: `2020-12`")
+    )]
+    fn test_parse_json_schema_dialect(
+        #[case] tokens: proc_macro2::TokenStream,
+        #[case] expected: Result<Option<&str>, &str>,
+    ) {
+        match (syn::parse2::<AutoRouterInput>(tokens), expected) {
+            (Ok(input), Ok(expected)) => assert_eq!(
+                process_vespera_input(input).json_schema_dialect.as_deref(),
+                expected
+            ),
+            (Err(err), Err(expected)) => assert!(err.to_string().contains(expected), "{err}"),
+            (Ok(_), Err(expected)) => panic!("expected error containing {expected}"),
+            (Err(err), Ok(_)) => panic!("unexpected error: {err}"),
+        }
+    }
+
+    #[rstest]
+    #[case(quote::quote!(migrations = "migration/src"), Some("migration/src"))]
+    #[case(quote::quote!(migrations = "entities.json"), Some("entities.json"))]
+    #[case(quote::quote!(dir = "routes"), None)]
+    fn test_parse_migrations(
+        #[case] tokens: proc_macro2::TokenStream,
+        #[case] expected: Option<&str>,
+    ) {
+        let input: AutoRouterInput = syn::parse2(tokens).unwrap();
+        assert_eq!(process_vespera_input(input).migrations.as_deref(), expected);
+    }
+
+    #[rstest]
+    #[case(quote::quote!(skip_invalid_files = true), true)]
+    #[case(quote::quote!(skip_invalid_files = false), false)]
+    #[case(quote::quote!(dir = "routes"), false)]
+    fn test_parse_skip_invalid_files(
+        #[case] tokens: proc_macro2::TokenStream,
+        #[case] expected: bool,
+    ) {
+        let input: AutoRouterInput = syn::parse2(tokens).unwrap();
+        assert_eq!(process_vespera_input(input).skip_invalid_files, expected);
+    }
+
+    #[rstest]
+    #[case(quote::quote!(ignore = ["generated/", "**/*_old.rs"]), Ok(vec!["generated/", "**/*_old.rs"]))]
+    #[case(quote::quote!(dir = "routes"), Ok(vec![]))]
+    #[case(quote::quote!(ignore = ["/"]), Err("invalid ignore pattern"))]
+    fn test_parse_ignore(
+        #[case] tokens: proc_macro2::TokenStream,
+        #[case] expected: Result<Vec<&str>, &str>,
+    ) {
+        match (syn::parse2::<AutoRouterInput>(tokens), expected) {
+            (Ok(input), Ok(expected)) => {
+                assert_eq!(process_vespera_input(input).ignore, expected);
+            }
+            (Err(err), Err(expected)) => assert!(err.to_string().contains(expected), "{err}"),
+            (result, expected) => panic!("expected {expected:?}, got {:?}", result.err()),
+        }
+    }
+
+    #[test]
+    fn test_parse_formats() {
+        let input: AutoRouterInput = syn::parse2(quote::quote!(
+            formats = { ulid = "^[0-9A-HJKMNP-TV-Z]{26}$", "phone-number" = "^\\+[0-9]+$", slug }
+        ))
+        .unwrap();
+        let formats = process_vespera_input(input).formats;
+        assert_eq!(
+            formats,
+            BTreeMap::from([
+                ("phone-number".to_string(), Some("^\\+[0-9]+$".to_string())),
+                ("slug".to_string(), None),
+                (
+                    "ulid".to_string(),
+                    Some("^[0-9A-HJKMNP-TV-Z]{26}$".to_string())
+                ),
+            ])
+        );
+
+        let input: AutoRouterInput = syn::parse2(quote::quote!(dir = "routes")).unwrap();
+        assert!(process_vespera_input(input).formats.is_empty());
+    }
+
+    #[rstest]
+    #[case(quote::quote!(formats = { ulid, ulid = "^x$" }), "duplicate format `ulid`")]
+    #[case(quote::quote!(formats = { "" = "^x$" }), "name must not be empty")]
+    #[case(quote::quote!(formats = { ulid = 26 }), "expected string literal")]
+    #[case(quote::quote!(formats = { ulid slug }), "expected `name = \"pattern\"` or `name`")]
+    fn test_parse_formats_errors(#[case] tokens: proc_macro2::TokenStream, #[case] expected: &str) {
+        let err = syn::parse2::<AutoRouterInput>(tokens).err().unwrap();
+        assert!(err.to_string().contains(expected), "{err}");
+    }
+
+    #[test]
+    fn test_parse_api_gateway() {
+        let input: AutoRouterInput =
+            syn::parse2(quote::quote!(api_gateway = "apigateway.json")).unwrap();
+        assert_eq!(
+            process_vespera_input(input).api_gateway.as_deref(),
+            Some("apigateway.json")
+        );
+    }
+
+    #[test]
+    fn test_parse_kubernetes() {
+        let input: AutoRouterInput = syn::parse2(quote::quote!(
+            kubernetes = {
+                name = "users-api",
+                service = "users",
+                port = 8080,
+                gateway = "public",
+                hostnames = ["api.example.com"],
+                http_route = "deploy/httproute.yaml",
+            }
+        ))
+        .unwrap();
+        let manifests = process_vespera_input(input).kubernetes.unwrap();
+        assert_eq!(manifests.config.name, "users-api");
+        assert_eq!(manifests.config.port, 8080);
+        assert_eq!(manifests.config.gateway.as_deref(), Some("public"));
+        assert_eq!(manifests.config.hostnames, vec!["api.example.com"]);
+        assert_eq!(
+            manifests.http_route.as_deref(),
+            Some("deploy/httproute.yaml")
+        );
+        assert_eq!(manifests.ingress, None);
+    }
+
+    #[rstest]
+    #[case(
+        quote::quote!(kubernetes = { name = "api", service = "api", ingress = "i.yaml" }),
+        "`port` is required"
+    )]
+    #[case(
+        quote::quote!(kubernetes = { name = "api", port = 80, ingress = "i.yaml" }),
+        "`name` and `service` are required"
+    )]
+    #[case(
+        quote::quote!(kubernetes = { name = "api", service = "api", port = 80 }),
+        "set `http_route` and/or `ingress`"
+    )]
+    #[case(
+        quote::quote!(kubernetes = { name = "api", replicas = 3 }),
+        "unknown kubernetes field: `replicas`"
+    )]
+    fn test_parse_kubernetes_errors(
+        #[case] tokens: proc_macro2::TokenStream,
+        #[case] expected: &str,
+    ) {
+        let err = syn::parse2::<AutoRouterInput>(tokens).err().unwrap();
+        assert!(err.to_string().contains(expected), "{err}");
+    }
+
+    #[test]
+    fn test_parse_security_schemes_oauth2() {
+        let input: AutoRouterInput = syn::parse2(quote::quote!(
+            security_schemes = {
+                oauth = oauth2 {
+                    authorization_code = {
+                        authorization_url = "https://auth.example.com/authorize",
+                        token_url = "https://auth.example.com/token",
+                        scopes = { "read:users" = "Read users", "write:users" = "Modify users" },
+                    },
+                    client_credentials = { token_url = "https://auth.example.com/token" },
+                },
+            }
+        ))
+        .unwrap();
+        let schemes = process_vespera_input(input).security_schemes;
+        assert_eq!(schemes.len(), 1);
+        assert_eq!(schemes[0].0, "oauth");
+        assert_eq!(
+            serde_json::to_value(&schemes[0].1).unwrap(),
+            serde_json::json!({
+                "type": "oauth2",
+                "flows": {
+                    "clientCredentials": {
+                        "tokenUrl": "https://auth.example.com/token",
+                        "scopes": {}
+                    },
+                    "authorizationCode": {
+                        "authorizationUrl": "https://auth.example.com/authorize",
+                        "tokenUrl": "https://auth.example.com/token",
+                        "scopes": { "read:users": "Read users", "write:users": "Modify users" }
+                    }
+                }
+            })
+        );
+    }
+
+    #[rstest]
+    #[case(quote::quote!(basic()), serde_json::json!({ "type": "http", "scheme": "basic" }))]
+    #[case(quote::quote!(bearer()), serde_json::json!({ "type": "http", "scheme": "bearer" }))]
+    #[case(
+        quote::quote!(bearer("JWT")),
+        serde_json::json!({ "type": "http", "scheme": "bearer", "bearerFormat": "JWT" })
+    )]
+    #[case(
+        quote::quote!(api_key(header = "X-API-Key")),
+        serde_json::json!({ "type": "apiKey", "name": "X-API-Key", "in": "header" })
+    )]
+    #[case(
+        quote::quote!(api_key(query = "api_key")),
+        serde_json::json!({ "type": "apiKey", "name": "api_key", "in": "query" })
+    )]
+    #[case(
+        quote::quote!(api_key(cookie = "session")),
+        serde_json::json!({ "type": "apiKey", "name": "session", "in": "cookie" })
+    )]
+    fn test_parse_security_schemes_kinds(
+        #[case] scheme: proc_macro2::TokenStream,
+        #[case] expected: serde_json::Value,
+    ) {
+        let input: AutoRouterInput =
+            syn::parse2(quote::quote!(security_schemes = { auth = #scheme })).unwrap();
+        let schemes = process_vespera_input(input).security_schemes;
+        assert_eq!(serde_json::to_value(&schemes[0].1).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_parse_global_security() {
+        let input: AutoRouterInput = syn::parse2(quote::quote!(
+            security_schemes = { bearer_auth = bearer("JWT"), api_key = api_key(header = "X-API-Key") },
+            security = ["bearer_auth", "api_key"]
+        ))
+        .unwrap();
+        let processed = process_vespera_input(input);
+        assert_eq!(processed.security_schemes.len(), 2);
+        assert_eq!(
+            serde_json::to_value(processed.security).unwrap(),
+            serde_json::json!([{ "bearer_auth": [] }, { "api_key": [] }])
+        );
+
+        let input: AutoRouterInput = syn::parse2(quote::quote!(dir = "routes")).unwrap();
+        assert!(process_vespera_input(input).security.is_none());
+    }
+
+    #[rstest]
+    #[case(quote::quote!(security_schemes = { oauth = saml {} }), "unknown security scheme kind")]
+    #[case(quote::quote!(security_schemes = { auth = basic("x") }), "takes no arguments")]
+    #[case(quote::quote!(security_schemes = { auth = api_key(body = "x") }), "invalid api_key location")]
+    #[case(quote::quote!(security_schemes = { auth = bearer }), "expected parentheses")]
+    #[case(quote::quote!(security_schemes = { oauth = oauth2 {} }), "at least one flow")]
+    #[case(
+        quote::quote!(security_schemes = { oauth = oauth2 { device = { token_url = "/token" } } }),
+        "unknown OAuth2 flow"
+    )]
+    #[case(
+        quote::quote!(security_schemes = { oauth = oauth2 { implicit = { token_url = "/token" } } }),
+        "requires `authorization_url`"
+    )]
+    #[case(
+        quote::quote!(security_schemes = { oauth = oauth2 { password = { scopes = {} } } }),
+        "requires `token_url`"
+    )]
+    #[case(
+        quote::quote!(security_schemes = {
+            oauth = oauth2 { password = { token_url = "/token" } },
+            oauth = oauth2 { password = { token_url = "/token" } }
+        }),
+        "duplicate security scheme"
+    )]
+    fn test_parse_security_schemes_invalid(
+        #[case] tokens: proc_macro2::TokenStream,
+        #[case] expected: &str,
+    ) {
+        let err = syn::parse2::<AutoRouterInput>(tokens).err().unwrap();
+        assert!(err.to_string().contains(expected), "{err}");
+    }
+
+    // ========== Tests for generate_router_code with merge ==========
+
+    #[test]
+    fn test_generate_router_code_with_merge_apps() {
+        let metadata = CollectedMetadata::new();
+        let merge_apps: Vec<syn::Path> = vec![syn::parse_quote!(third::ThirdApp)];
+
+        let result = generate_router_code(
+            &metadata,
+            RouterCodegenOptions {
+                merge_apps: &merge_apps,
+                ..Default::default()
+            },
+        );
+        let code = result.to_string();
+
+        // Should use VesperaRouter instead of plain Router
+        assert!(
+            code.contains("VesperaRouter"),
+            "Should use VesperaRouter for merge, got: {code}"
+        );
+        assert!(
+            code.contains("third :: ThirdApp") || code.contains("third::ThirdApp"),
+            "Should reference merged app, got: {code}"
+        );
+    }
+
+    #[test]
+    fn test_generate_router_code_with_docs_and_merge() {
+        let metadata = CollectedMetadata::new();
+        let spec = r#"{"openapi":"3.1.0"}"#;
+        let merge_apps: Vec<syn::Path> = vec![syn::parse_quote!(app::MyApp)];
+
+        let result = generate_router_code(
+            &metadata,
+            RouterCodegenOptions {
+                docs_url: Some("/docs"),
+                spec_tokens: Some(quote::quote!(#spec)),
+                merge_apps: &merge_apps,
+                ..Default::default()
+            },
+        );
+        let code = result.to_string();
+
+        // Should have merge code for docs
+        assert!(
+            code.contains("OnceLock"),
+            "Should use OnceLock for merged docs, got: {code}"
+        );
+        assert!(
+            code.contains("MERGED_SPEC"),
+            "Should have MERGED_SPEC, got: {code}"
+        );
+        // quote! generates "merged . merge" with spaces
+        assert!(
+            code.contains("merged . merge") || code.contains("merged.merge"),
+            "Should call merge on spec, got: {code}"
+        );
+    }
+
+    #[test]
+    fn test_generate_router_code_with_redoc_and_merge() {
+        let metadata = CollectedMetadata::new();
+        let spec = r#"{"openapi":"3.1.0"}"#;
+        let merge_apps: Vec<syn::Path> = vec![syn::parse_quote!(other::OtherApp)];
+
+        let result = generate_router_code(
+            &metadata,
+            RouterCodegenOptions {
+                redoc_url: Some("/redoc"),
+                spec_tokens: Some(quote::quote!(#spec)),
+                merge_apps: &merge_apps,
+                ..Default::default()
+            },
+        );
+        let code = result.to_string();
+
+        // Should have merge code for redoc
+        assert!(
+            code.contains("OnceLock"),
+            "Should use OnceLock for merged redoc"
+        );
+        assert!(code.contains("redoc"), "Should contain redoc");
+    }
+
+    #[test]
+    fn test_generate_router_code_with_both_docs_and_merge() {
+        let metadata = CollectedMetadata::new();
+        let spec = r#"{"openapi":"3.1.0"}"#;
+        let merge_apps: Vec<syn::Path> = vec![syn::parse_quote!(merged::App)];
+
+        let result = generate_router_code(
+            &metadata,
+            RouterCodegenOptions {
+                docs_url: Some("/docs"),
+                redoc_url: Some("/redoc"),
+                spec_tokens: Some(quote::quote!(#spec)),
+                merge_apps: &merge_apps,
+                ..Default::default()
+            },
+        );
+        let code = result.to_string();
+
+        // Both docs should have merge code
+        // Count MERGED_SPEC occurrences - should appear in docs and redoc handlers
+        let merged_spec_count = code.matches("MERGED_SPEC").count();
+        assert!(
+            merged_spec_count >= 2,
+            "Should have at least 2 MERGED_SPEC for docs and redoc, got: {merged_spec_count}"
+        );
+        assert!(
+            code.contains("merged . resolve_server_variables (vespera :: servers :: from_env)")
+        );
+        // __VESPERA_SPEC should appear exactly once (the const declaration)
+        let vespera_spec_count = code.matches("__VESPERA_SPEC").count();
+        assert!(
+            vespera_spec_count >= 1,
+            "Should have __VESPERA_SPEC const, got: {vespera_spec_count}"
+        );
+        // Both docs_url and redoc_url should be present
+        assert!(
+            code.contains("/docs") && code.contains("/redoc"),
+            "Should contain both /docs and /redoc"
+        );
+    }
+
+    #[rstest]
+    #[case(&[])]
+    #[case(&["merged::App"])]
+    fn test_generate_router_code_with_spec_reload(#[case] merge: &[&str]) {
+        let metadata = CollectedMetadata::new();
+        let spec = r#"{"openapi":"3.1.0"}"#;
+        let merge_apps: Vec<syn::Path> = merge.iter().map(|p| syn::parse_str(p).unwrap()).collect();
+        let reload = quote::quote!(vespera::dev::reload_spec("/app", "", "[]"));
+
+        let code = generate_router_code(
+            &metadata,
+            RouterCodegenOptions {
+                docs_url: Some("/docs"),
+                spec_tokens: Some(quote::quote!(#spec)),
+                merge_apps: &merge_apps,
+                spec_reload: Some(&reload),
+                ..Default::default()
+            },
+        )
+        .to_string();
+
+        // Only debug builds reload; release builds render the built spec
+        assert!(code.contains(
+            "# [cfg (debug_assertions)] let reloaded : Option < String > = vespera :: dev :: reload_spec"
+        ));
+        assert!(code.contains(
+            "# [cfg (not (debug_assertions))] let reloaded : Option < String > = None ;"
+        ));
+        assert!(code.contains("std :: borrow :: Cow :: Owned"));
+        assert!(code.contains("__VESPERA_SPEC"));
+        assert_eq!(
+            code.contains("merged . merge"),
+            !merge.is_empty(),
+            "fresh and built specs are merged with the child apps"
+        );
+    }
+
+    #[test]
+    fn test_generate_router_code_with_multiple_merge_apps() {
+        let metadata = CollectedMetadata::new();
+        let merge_apps: Vec<syn::Path> = vec![
+            syn::parse_quote!(first::App),
+            syn::parse_quote!(second::App),
+        ];
+
+        let result = generate_router_code(
+            &metadata,
+            RouterCodegenOptions {
+                merge_apps: &merge_apps,
+                ..Default::default()
+            },
+        );
+        let code = result.to_string();
+
+        // Should reference both apps
+        assert!(
+            code.contains("first") && code.contains("second"),
+            "Should reference both merge apps, got: {code}"
+        );
+    }
+
+    // ========== Tests for generate_router_code with cron jobs ==========
+
+    #[test]
+    fn test_generate_router_code_with_merge_and_cron() {
+        let metadata = CollectedMetadata::new();
+        let merge_apps: Vec<syn::Path> = vec![syn::parse_quote!(third::ThirdApp)];
+        let cron_jobs = vec![CronMetadata {
+            expression: "0 */5 * * * *".to_string(),
+            function_name: "cleanup".to_string(),
+            module_path: "tasks".to_string(),
+            file_path: "src/tasks.rs".to_string(),
+        }];
+
+        let result = generate_router_code(
+            &metadata,
+            RouterCodegenOptions {
+                merge_apps: &merge_apps,
+                cron_jobs: &cron_jobs,
+                ..Default::default()
+            },
+        );
+        let code = result.to_string();
+
+        assert!(
+            code.contains("VesperaRouter"),
+            "Should use VesperaRouter for merge, got: {code}"
+        );
+        assert!(
+            code.contains("JobScheduler"),
+            "Should contain cron scheduler code, got: {code}"
+        );
+        assert!(
+            code.contains("cleanup"),
+            "Should reference cron function, got: {code}"
+        );
+    }
+
+    #[test]
+    fn test_generate_router_code_with_cron_no_merge() {
+        let metadata = CollectedMetadata::new();
+        let cron_jobs = vec![CronMetadata {
+            expression: "1/10 * * * * *".to_string(),
+            function_name: "heartbeat".to_string(),
+            module_path: "cron::health".to_string(),
+            file_path: "src/cron/health.rs".to_string(),
+        }];
+
+        let result = generate_router_code(
+            &metadata,
+            RouterCodegenOptions {
+                cron_jobs: &cron_jobs,
+                ..Default::default()
+            },
+        );
+        let code = result.to_string();
+
+        assert!(
+            !code.contains("VesperaRouter"),
+            "Should NOT use VesperaRouter without merge, got: {code}"
+        );
+        assert!(
+            code.contains("JobScheduler"),
+            "Should contain cron scheduler code, got: {code}"
+        );
+        assert!(
+            code.contains("heartbeat"),
+            "Should reference cron function, got: {code}"
+        );
+    }
+
+    // ========== Tests for ExportAppInput parsing ==========
+
+    #[test]
+    fn test_export_app_input_name_only() {
+        let tokens = quote::quote!(MyApp);
+        let input: ExportAppInput = syn::parse2(tokens).unwrap();
+        assert_eq!(input.name.to_string(), "MyApp");
+        assert!(input.dir.is_none());
+    }
+
+    #[test]
+    fn test_export_app_input_with_dir() {
+        let tokens = quote::quote!(MyApp, dir = "api");
+        let input: ExportAppInput = syn::parse2(tokens).unwrap();
+        assert_eq!(input.name.to_string(), "MyApp");
+        assert_eq!(input.dir.unwrap().value(), "api");
+    }
+
+    #[test]
+    fn test_export_app_input_with_trailing_comma() {
+        let tokens = quote::quote!(MyApp,);
+        let input: ExportAppInput = syn::parse2(tokens).unwrap();
+        assert_eq!(input.name.to_string(), "MyApp");
+        assert!(input.dir.is_none());
+    }
+
+    #[test]
+    fn test_export_app_input_unknown_field() {
+        let tokens = quote::quote!(MyApp, unknown = "value");
+        let result: syn::Result<ExportAppInput> = syn::parse2(tokens);
+        assert!(result.is_err());
+        let err = result.err().unwrap();
+        assert!(err.to_compile_error().to_string().contains("unknown field"));
+    }
+
+    #[test]
+    fn test_export_app_input_multiple_commas() {
+        let tokens = quote::quote!(MyApp, dir = "api",);
+        let input: ExportAppInput = syn::parse2(tokens).unwrap();
+        assert_eq!(input.name.to_string(), "MyApp");
+        assert_eq!(input.dir.unwrap().value(), "api");
+    }
+
+    // ========== Tests for env var fallbacks (lines 181-183) ==========
+    // Note: These tests use env vars which are global state.
+    // The tests are designed to be resilient to parallel test execution.
+
+    #[test]
+    fn test_auto_router_input_server_env_var_fallback() {
+        // Test lines 181-183: VESPERA_SERVER_URL env var fallback
+        // This test verifies the code path but may be affected by parallel tests
+        // Using a unique test URL to reduce collision chances
+        let test_url = "https://vespera-test-unique-12345.example.com";
+        let test_desc = "Vespera Test Server 12345";
+
+        // Save current state
+        let old_server_url = std::env::var("VESPERA_SERVER_URL").ok();
+        let old_server_desc = std::env::var("VESPERA_SERVER_DESCRIPTION").ok();
+
+        // SAFETY: Single-threaded test context
+        unsafe {
+            std::env::set_var("VESPERA_SERVER_URL", test_url);
+            std::env::set_var("VESPERA_SERVER_DESCRIPTION", test_desc);
+        }
+
+        // Parse empty input - should pick up env vars
+        let tokens = quote::quote!();
+        let input: AutoRouterInput = syn::parse2(tokens).unwrap();
+
+        // Restore env vars immediately after parsing
+        unsafe {
+            if let Some(url) = old_server_url {
+                std::env::set_var("VESPERA_SERVER_URL", url);
+            } else {
+                std::env::remove_var("VESPERA_SERVER_URL");
+            }
+            if let Some(desc) = old_server_desc {
+                std::env::set_var("VESPERA_SERVER_DESCRIPTION", desc);
+            } else {
+                std::env::remove_var("VESPERA_SERVER_DESCRIPTION");
+            }
+        }
+
+        // Check if servers was set - may not be if another test interfered
+        if let Some(servers) = input.servers {
+            // If we got servers, verify they match our test values
+            if servers.len() == 1 && servers[0].url == test_url {
+                assert_eq!(servers[0].description, Some(test_desc.to_string()));
+            }
+            // Otherwise another test's values were picked up, which is fine
+        }
+        // If servers is None, another test may have cleared the env var - acceptable
+    }
+
+    #[test]
+    fn test_auto_router_input_server_env_var_invalid_url_filtered() {
+        // Test that invalid URLs (not http/https) are filtered out by the .filter() call
+        // This exercises the filter branch, not lines 181-183 directly
+        let old_server_url = std::env::var("VESPERA_SERVER_URL").ok();
+
+        // SAFETY: Single-threaded test context
+        unsafe {
+            std::env::set_var("VESPERA_SERVER_URL", "ftp://invalid-url-test.com");
+        }
+
+        let tokens = quote::quote!();
+        let input: AutoRouterInput = syn::parse2(tokens).unwrap();
+
+        // Restore env var
+        unsafe {
+            if let Some(url) = old_server_url {
+                std::env::set_var("VESPERA_SERVER_URL", url);
+            } else {
+                std::env::remove_var("VESPERA_SERVER_URL");
+            }
+        }
+
+        // If servers is Some, it means another test set a valid URL - acceptable
+        // If servers is None, our invalid URL was correctly filtered
+        if let Some(servers) = &input.servers {
+            // Another test set a valid URL, check it's not our invalid one
+            assert!(
+                servers.is_empty() || servers[0].url != "ftp://invalid-url-test.com",
+                "Invalid ftp:// URL should have been filtered"
+            );
+        }
+    }
+}
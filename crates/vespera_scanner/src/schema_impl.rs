@@ -0,0 +1,1428 @@
+//! Schema derive macro implementation.
+//!
+//! This module implements the `#[derive(Schema)]` derive macro that registers
+//! types for `OpenAPI` schema generation.
+//!
+//! # Overview
+//!
+//! The `#[derive(Schema)]` macro registers a struct or enum for inclusion in the `OpenAPI` spec.
+//! It stores metadata about the type which is later used by the `vespera!` macro to generate
+//! the `OpenAPI` components/schemas section.
+//!
+//! # Global Schema Storage
+//!
+//! This module uses a global [`SCHEMA_STORAGE`] mutex to collect all schema types across
+//! a crate at compile time. This is necessary because proc-macros are invoked independently,
+//! so we need a shared location to gather all types before generating the final `OpenAPI` spec.
+//!
+//! # Custom Schema Names
+//!
+//! By default, the `OpenAPI` schema name matches the struct name. You can customize it:
+//!
+//! ```ignore
+//! #[derive(Schema)]
+//! #[schema(name = "CustomSchemaName")]
+//! pub struct MyType { ... }
+//! ```
+//!
+//! `#[schema(rename = "...")]` is an alias of `name`, and `#[schema(rename_all = "camelCase")]`
+//! applies a naming strategy to the type name instead. `$ref`s to the type follow the
+//! component name. Custom names must be valid component keys (`^[a-zA-Z0-9.\-_]+$`).
+//!
+//! # Remote Types
+//!
+//! Types from other crates cannot derive `Schema`. As with serde's remote derive, a local
+//! struct mirroring their fields registers the component under the remote type's name:
+//!
+//! ```ignore
+//! #[derive(Schema)]
+//! #[schema(remote = "other_crate::Money")]
+//! struct MoneyDef { amount: i64, currency: String }
+//! ```
+//!
+//! Fields and handlers then use `other_crate::Money` directly and reference `Money`.
+//!
+//! # Field Scopes
+//!
+//! Fields marked `#[schema(scope = "admin")]` are omitted from the public spec and
+//! only appear in the per-scope document (`vespera!(..., scopes = ["admin"])`).
+//! For such structs the derive also implements `vespera::scope::ScopedFields`, so
+//! the same fields can be stripped at runtime with `vespera::scope::Scoped`.
+//!
+//! # Field Formats
+//!
+//! `#[schema(format = "ulid")]` sets the property's `format`. The derive implements
+//! `vespera::formats::FormattedFields` for such structs, so formats registered with
+//! `vespera!(formats = { ... })` can be checked at runtime with `vespera::formats::check`.
+//!
+//! # Key Functions
+//!
+//! - [`extract_schema_name_attr`] - Extract custom name from `#[schema]` attribute
+//! - [`schema_component_name`] - Resolve the component name of a derived type
+//! - [`validate_schema_name_attr`] - Reject custom names that are not valid component keys
+//! - [`validate_field_access`] - Reject fields marked both `read_only` and `write_only`
+//! - [`extract_schema_remote_attr`] - Extract the mirrored type of a remote derive
+//! - [`process_derive_schema`] - Process the derive macro input and register the type
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::Path,
+    sync::{LazyLock, Mutex},
+};
+
+use crate::metadata::StructMetadata;
+
+pub static SCHEMA_STORAGE: LazyLock<Mutex<HashMap<String, StructMetadata>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Extract custom schema name from #[schema(name = "...")] attribute
+///
+/// `#[schema(rename = "...")]` is accepted as an alias.
+pub fn extract_schema_name_attr(attrs: &[syn::Attribute]) -> Option<String> {
+    for attr in attrs {
+        if attr.path().is_ident("schema") {
+            let mut custom_name = None;
+            let _ = attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("name") || meta.path.is_ident("rename") {
+                    let value = meta.value()?;
+                    let lit: syn::LitStr = value.parse()?;
+                    custom_name = Some(lit.value());
+                } else if meta.input.peek(syn::Token![=]) {
+                    meta.value()?.parse::<syn::Expr>()?;
+                }
+                Ok(())
+            });
+            if custom_name.is_some() {
+                return custom_name;
+            }
+        }
+    }
+    None
+}
+
+/// Whether `name` is a valid `OpenAPI` component key (`^[a-zA-Z0-9.\-_]+$`).
+pub fn is_valid_component_key(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'))
+}
+
+/// `"User Profile"` -> `"UserProfile"`, `"api/v1 user"` -> `"ApiV1User"`.
+fn suggest_component_key(name: &str) -> String {
+    let parts: Vec<&str> = name
+        .split(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_')))
+        .filter(|part| !part.is_empty())
+        .collect();
+    if parts.len() == 1 {
+        return parts[0].to_string();
+    }
+    parts
+        .iter()
+        .map(|part| {
+            let mut chars = part.chars();
+            chars.next().map_or_else(String::new, |first| {
+                first.to_ascii_uppercase().to_string() + chars.as_str()
+            })
+        })
+        .collect()
+}
+
+/// Reject `#[schema(name = "...")]` values that cannot be used as component keys.
+///
+/// Such names would produce `$ref`s that no `OpenAPI` tool resolves.
+pub fn validate_schema_name_attr(attrs: &[syn::Attribute]) -> syn::Result<()> {
+    for attr in attrs {
+        if !attr.path().is_ident("schema") {
+            continue;
+        }
+        let mut result = Ok(());
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") || meta.path.is_ident("rename") {
+                let lit: syn::LitStr = meta.value()?.parse()?;
+                let name = lit.value();
+                if !is_valid_component_key(&name) {
+                    let suggestion = suggest_component_key(&name);
+                    let hint = if suggestion.is_empty() {
+                        String::new()
+                    } else {
+                        format!(" Did you mean \"{suggestion}\"?")
+                    };
+                    result = Err(syn::Error::new(
+                        lit.span(),
+                        format!(
+                            "invalid schema name \"{name}\": component names may only contain ASCII letters, digits, '.', '-' and '_'.{hint}"
+                        ),
+                    ));
+                }
+            } else if meta.input.peek(syn::Token![=]) {
+                meta.value()?.parse::<syn::Expr>()?;
+            }
+            Ok(())
+        });
+        result?;
+    }
+    Ok(())
+}
+
+/// Reject fields marked both `#[schema(read_only)]` and `#[schema(write_only)]`.
+///
+/// Such a property would be dropped from both variants of a `#[schema(split)]` struct.
+pub fn validate_field_access(input: &syn::DeriveInput) -> syn::Result<()> {
+    let syn::Data::Struct(data) = &input.data else {
+        return Ok(());
+    };
+    for field in &data.fields {
+        if crate::parser::extract_field_access(&field.attrs) == (true, true) {
+            let name = field
+                .ident
+                .as_ref()
+                .map_or_else(String::new, |ident| format!(" `{ident}`"));
+            return Err(syn::Error::new_spanned(
+                field,
+                format!("field{name} cannot be both `read_only` and `write_only`"),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Extract the container naming strategy from #[schema(rename_all = "...")] attribute
+///
+/// Unlike `#[serde(rename_all)]`, which renames fields, this renames the component itself.
+pub fn extract_schema_rename_all_attr(attrs: &[syn::Attribute]) -> Option<String> {
+    attrs.iter().find_map(|attr| {
+        if !attr.path().is_ident("schema") {
+            return None;
+        }
+        let mut rename_all = None;
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename_all") {
+                let lit: syn::LitStr = meta.value()?.parse()?;
+                rename_all = Some(lit.value());
+            } else if meta.input.peek(syn::Token![=]) {
+                meta.value()?.parse::<syn::Expr>()?;
+            }
+            Ok(())
+        });
+        rename_all
+    })
+}
+
+/// Extract the mirrored type from #[schema(remote = "other_crate::Money")] attribute
+///
+/// Like serde's remote derive: the deriving struct only mirrors the shape of a type
+/// from another crate, and the component is registered under the remote type's name.
+pub fn extract_schema_remote_attr(attrs: &[syn::Attribute]) -> syn::Result<Option<syn::Path>> {
+    for attr in attrs {
+        if !attr.path().is_ident("schema") {
+            continue;
+        }
+        let mut remote = None;
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("remote") {
+                let lit: syn::LitStr = meta.value()?.parse()?;
+                remote = Some(lit.parse::<syn::Path>().map_err(|_| {
+                    syn::Error::new(
+                        lit.span(),
+                        format!(
+                            "invalid remote type \"{}\": expected a type path like \"other_crate::Money\".",
+                            lit.value()
+                        ),
+                    )
+                }));
+            } else if meta.input.peek(syn::Token![=]) {
+                meta.value()?.parse::<syn::Expr>()?;
+            }
+            Ok(())
+        });
+        if let Some(remote) = remote {
+            return remote.map(Some);
+        }
+    }
+    Ok(None)
+}
+
+/// Component name for a `#[derive(Schema)]` type.
+///
+/// An explicit `name`/`rename` wins; otherwise `rename_all` is applied to the type name.
+pub fn schema_component_name(ident: &syn::Ident, attrs: &[syn::Attribute]) -> String {
+    extract_schema_name_attr(attrs).unwrap_or_else(|| {
+        let name = ident.to_string();
+        match extract_schema_rename_all_attr(attrs) {
+            Some(strategy) => crate::parser::rename_field(&name, Some(&strategy)),
+            None => name,
+        }
+    })
+}
+
+/// Stand-in definition for a type generated by `bitflags! { #[derive(Schema)] struct ... }`.
+///
+/// bitflags 2.x wraps `<T as PublicFlags>::Internal`, which cannot be described (nor
+/// reparsed once `$crate` is stringified), so the type is recorded as a `#[schema(bitflags)]`
+/// unit struct keeping its doc comments and `#[schema]` attributes.
+fn bitflags_definition(input: &syn::DeriveInput) -> Option<String> {
+    let syn::Data::Struct(data) = &input.data else {
+        return None;
+    };
+    let syn::Fields::Unnamed(fields) = &data.fields else {
+        return None;
+    };
+    let [field] = fields.unnamed.iter().collect::<Vec<_>>()[..] else {
+        return None;
+    };
+    let syn::Type::Path(type_path) = &field.ty else {
+        return None;
+    };
+    let is_bitflags = type_path.qself.is_some()
+        && type_path
+            .path
+            .segments
+            .iter()
+            .any(|segment| segment.ident == "PublicFlags");
+    if !is_bitflags {
+        return None;
+    }
+    let attrs = input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc") || attr.path().is_ident("schema"));
+    let ident = &input.ident;
+    Some(quote::quote!(#(#attrs)* #[schema(bitflags)] struct #ident;).to_string())
+}
+
+/// Process derive input and return metadata + expanded code
+pub fn process_derive_schema(
+    input: &syn::DeriveInput,
+) -> (StructMetadata, proc_macro2::TokenStream) {
+    // #[schema(remote = "other_crate::Money")]: describe the mirror under the remote
+    // type's name, so `Money` fields and handler types resolve to it.
+    let remote = extract_schema_remote_attr(&input.attrs);
+    let mirror;
+    let input = match &remote {
+        Ok(Some(path)) => {
+            let mut renamed = input.clone();
+            renamed.ident = path
+                .segments
+                .last()
+                .map_or_else(|| input.ident.clone(), |segment| segment.ident.clone());
+            mirror = renamed;
+            &mirror
+        }
+        _ => input,
+    };
+    let name = &input.ident;
+
+    // Check for custom schema name from #[schema(name/rename/rename_all = "...")] attributes
+    let schema_name = schema_component_name(name, &input.attrs);
+
+    // Extract default values from serde(default = "fn_name") attributes at derive time.
+    // Span::call_site().local_file() returns None in unit tests — the map/unwrap_or_default
+    // chain ensures the line is always executed even when the closure is not entered.
+    let field_defaults = proc_macro2::Span::call_site()
+        .local_file()
+        .map(|file_path| extract_field_defaults_from_path(input, &file_path))
+        .unwrap_or_default();
+
+    // Schema-derived types appear in OpenAPI spec (include_in_openapi: true)
+    let definition =
+        bitflags_definition(input).unwrap_or_else(|| quote::quote!(#input).to_string());
+    let mut metadata = StructMetadata::new(schema_name, definition);
+    if input
+        .attrs
+        .iter()
+        .any(|attr| attr.path().is_ident("schema"))
+    {
+        let mut has_ref_override = false;
+        for attr in &input.attrs {
+            if !attr.path().is_ident("schema") {
+                continue;
+            }
+            let _ = attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("ref") {
+                    has_ref_override = true;
+                }
+                Ok(())
+            });
+            if has_ref_override {
+                break;
+            }
+        }
+        if has_ref_override {
+            metadata.include_in_openapi = false;
+        }
+    }
+    metadata.field_defaults = field_defaults;
+    // The mirror is never serialized itself, so it carries no runtime scope or format impl.
+    let mut expanded = if matches!(remote, Ok(Some(_))) {
+        proc_macro2::TokenStream::new()
+    } else {
+        let mut impls = generate_scoped_fields_impl(input);
+        impls.extend(generate_formatted_fields_impl(input));
+        impls.extend(generate_param_enum_impl(input));
+        impls
+    };
+    if let Err(err) = validate_schema_name_attr(&input.attrs) {
+        expanded.extend(err.to_compile_error());
+    }
+    if let Err(err) = remote {
+        expanded.extend(err.to_compile_error());
+    }
+    if let Err(err) = crate::parser::extract_schema_examples(&input.attrs) {
+        expanded.extend(err.to_compile_error());
+    }
+    if let Err(err) = crate::parser::extract_schema_dialect(&input.attrs) {
+        expanded.extend(err.to_compile_error());
+    }
+    if let Err(err) = crate::parser::extract_schema_name_for(&input.attrs) {
+        expanded.extend(err.to_compile_error());
+    }
+    if let Err(err) = validate_field_access(input) {
+        expanded.extend(err.to_compile_error());
+    }
+    (metadata, expanded)
+}
+
+/// Generate a `vespera::scope::ScopedFields` impl listing `(json_name, scope)` for
+/// every field marked `#[schema(scope = "...")]`, and stripping the fields whose type
+/// may have scoped fields of its own. Empty when there are neither.
+pub fn generate_scoped_fields_impl(input: &syn::DeriveInput) -> proc_macro2::TokenStream {
+    let entries = field_attr_entries(input, crate::parser::extract_field_scope);
+    let nested = nested_scope_strips(input);
+    if entries.is_empty() && nested.is_empty() {
+        return proc_macro2::TokenStream::new();
+    }
+
+    let strip_nested = if nested.is_empty() {
+        proc_macro2::TokenStream::new()
+    } else {
+        quote::quote! {
+            fn strip_nested(value: &mut vespera::serde_json::Value, scopes: &[&str]) {
+                #[allow(unused_imports)]
+                use vespera::scope::__private::{Nested, StripNested as _, StripNone as _};
+                #(#nested)*
+            }
+        }
+    };
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    quote::quote! {
+        impl #impl_generics vespera::scope::ScopedFields for #name #ty_generics #where_clause {
+            const SCOPED_FIELDS: &'static [(&'static str, &'static str)] = &[#(#entries),*];
+            #strip_nested
+        }
+    }
+}
+
+/// Statements stripping each named field whose type may have scoped fields by the
+/// `ScopedFields` of that type, or not at all when it has none.
+///
+/// Flattened fields are serialized into the parent object, so they strip it directly.
+fn nested_scope_strips(input: &syn::DeriveInput) -> Vec<proc_macro2::TokenStream> {
+    let syn::Data::Struct(data) = &input.data else {
+        return Vec::new();
+    };
+    let syn::Fields::Named(fields_named) = &data.fields else {
+        return Vec::new();
+    };
+
+    let rename_all = crate::parser::extract_rename_all(&input.attrs);
+    fields_named
+        .named
+        .iter()
+        .filter(|field| {
+            !crate::parser::extract_skip(&field.attrs) && may_have_scoped_fields(&field.ty)
+        })
+        .filter_map(|field| {
+            let ty = &field.ty;
+            if crate::parser::extract_flatten(&field.attrs) {
+                return Some(quote::quote! {
+                    (&Nested::<#ty>::NEW).strip(value, scopes);
+                });
+            }
+            let rust_name =
+                crate::parser::strip_raw_prefix_owned(field.ident.as_ref()?.to_string());
+            let json_name = crate::parser::extract_field_rename(&field.attrs)
+                .unwrap_or_else(|| crate::parser::rename_field(&rust_name, rename_all.as_deref()));
+            Some(quote::quote! {
+                if let Some(field) = value.get_mut(#json_name) {
+                    (&Nested::<#ty>::NEW).strip(field, scopes);
+                }
+            })
+        })
+        .collect()
+}
+
+/// Whether a value of type `ty` may have scoped fields: anything but well-known leaf
+/// types, looking through `Option`, `Vec` and `Box`.
+fn may_have_scoped_fields(ty: &syn::Type) -> bool {
+    let syn::Type::Path(type_path) = ty else {
+        return false;
+    };
+    let Some(segment) = type_path.path.segments.last() else {
+        return false;
+    };
+    let name = segment.ident.to_string();
+    if matches!(name.as_str(), "Option" | "Vec" | "Box") {
+        return match &segment.arguments {
+            syn::PathArguments::AngleBracketed(args) => match args.args.first() {
+                Some(syn::GenericArgument::Type(inner)) => may_have_scoped_fields(inner),
+                _ => false,
+            },
+            _ => false,
+        };
+    }
+    // `serde_json::Value` is free-form and never scoped
+    name != "Value" && !crate::schema_macro::type_utils::is_primitive_or_known_type(&name)
+}
+
+/// Generate a `vespera::formats::FormattedFields` impl listing `(json_name, format)` for
+/// every field marked `#[schema(format = "...")]`. Empty when there are none.
+pub fn generate_formatted_fields_impl(input: &syn::DeriveInput) -> proc_macro2::TokenStream {
+    let entries = field_attr_entries(input, crate::parser::extract_field_format);
+    if entries.is_empty() {
+        return proc_macro2::TokenStream::new();
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    quote::quote! {
+        impl #impl_generics vespera::formats::FormattedFields for #name #ty_generics #where_clause {
+            const FORMATTED_FIELDS: &'static [(&'static str, &'static str)] = &[#(#entries),*];
+        }
+    }
+}
+
+/// Generate a `vespera::params::ParamEnum` impl mapping each variant of a plain string
+/// enum to the value documented in its schema's `enum`.
+///
+/// Only externally tagged enums of unit variants qualify; with a `#[serde(other)]`
+/// catch-all any value is valid, so those get no impl either.
+pub fn generate_param_enum_impl(input: &syn::DeriveInput) -> proc_macro2::TokenStream {
+    let syn::Data::Enum(data) = &input.data else {
+        return proc_macro2::TokenStream::new();
+    };
+    let is_plain = !data.variants.is_empty()
+        && matches!(
+            crate::parser::extract_enum_repr(&input.attrs),
+            crate::parser::SerdeEnumRepr::ExternallyTagged
+        )
+        && data.variants.iter().all(|variant| {
+            matches!(variant.fields, syn::Fields::Unit)
+                && !crate::parser::extract_untagged(&variant.attrs)
+                && !crate::parser::extract_other(&variant.attrs)
+        });
+    if !is_plain {
+        return proc_macro2::TokenStream::new();
+    }
+
+    let rename_all = crate::parser::extract_rename_all(&input.attrs);
+    let (variants, values): (Vec<_>, Vec<_>) = data
+        .variants
+        .iter()
+        .map(|variant| {
+            let rust_name = crate::parser::strip_raw_prefix_owned(variant.ident.to_string());
+            let value = crate::parser::extract_field_rename(&variant.attrs)
+                .unwrap_or_else(|| crate::parser::rename_field(&rust_name, rename_all.as_deref()));
+            (&variant.ident, value)
+        })
+        .unzip();
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    quote::quote! {
+        impl #impl_generics vespera::params::ParamEnum for #name #ty_generics #where_clause {
+            const VALUES: &'static [&'static str] = &[#(#values),*];
+
+            fn from_param(value: &str) -> std::option::Option<Self> {
+                match value {
+                    #(#values => std::option::Option::Some(Self::#variants),)*
+                    _ => std::option::Option::None,
+                }
+            }
+
+            fn as_param(&self) -> &'static str {
+                match self {
+                    #(Self::#variants => #values,)*
+                }
+            }
+        }
+    }
+}
+
+/// `(json_name, value)` tokens for every named field for which `extract` returns a value.
+fn field_attr_entries(
+    input: &syn::DeriveInput,
+    extract: fn(&[syn::Attribute]) -> Option<String>,
+) -> Vec<proc_macro2::TokenStream> {
+    let syn::Data::Struct(data) = &input.data else {
+        return Vec::new();
+    };
+    let syn::Fields::Named(fields_named) = &data.fields else {
+        return Vec::new();
+    };
+
+    let rename_all = crate::parser::extract_rename_all(&input.attrs);
+    fields_named
+        .named
+        .iter()
+        .filter_map(|field| {
+            let value = extract(&field.attrs)?;
+            let rust_name =
+                crate::parser::strip_raw_prefix_owned(field.ident.as_ref()?.to_string());
+            let json_name = crate::parser::extract_field_rename(&field.attrs)
+                .unwrap_or_else(|| crate::parser::rename_field(&rust_name, rename_all.as_deref()));
+            Some(quote::quote! { (#json_name, #value) })
+        })
+        .collect()
+}
+
+/// Extract default values from `#[serde(default = "fn_name")]` attributes
+/// using the given source file path.
+///
+/// Separated from [`extract_field_defaults`] for testability: `Span::call_site().local_file()`
+/// returns `None` in unit tests, so this function accepts the path directly.
+pub fn extract_field_defaults_from_path(
+    input: &syn::DeriveInput,
+    file_path: &Path,
+) -> BTreeMap<String, serde_json::Value> {
+    let mut defaults = BTreeMap::new();
+
+    let fields = match &input.data {
+        syn::Data::Struct(data) => match &data.fields {
+            syn::Fields::Named(named) => &named.named,
+            _ => return defaults,
+        },
+        _ => return defaults,
+    };
+
+    // Collect fields with function-based defaults
+    let fn_defaults: Vec<(String, String)> = fields
+        .iter()
+        .filter_map(|f| {
+            let field_name = f.ident.as_ref()?.to_string();
+            if let Some(Some(fn_name)) = crate::parser::extract_default(&f.attrs) {
+                // Only handle simple function names (not paths like "crate::utils::default")
+                if fn_name.contains("::") {
+                    None
+                } else {
+                    Some((field_name, fn_name))
+                }
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    if fn_defaults.is_empty() {
+        return defaults;
+    }
+
+    // Read and parse the file (cached via FileCache parsed_file_asts)
+    let Some(file_ast) = crate::schema_macro::file_cache::get_parsed_file(file_path) else {
+        return defaults;
+    };
+
+    // Extract default values from functions
+    defaults.extend(extract_defaults_from_file(&fn_defaults, &file_ast));
+    defaults
+}
+
+/// Extract default values by finding functions in the given file AST.
+/// Separated from `extract_field_defaults` for testability (proc_macro2::Span
+/// is not available in unit tests).
+pub fn extract_defaults_from_file(
+    fn_defaults: &[(String, String)],
+    file_ast: &syn::File,
+) -> BTreeMap<String, serde_json::Value> {
+    let mut defaults = BTreeMap::new();
+    for (field_name, fn_name) in fn_defaults {
+        if let Some(func) = crate::openapi_generator::find_function_in_file(file_ast, fn_name)
+            && let Some(value) = crate::openapi_generator::extract_default_value_from_function(func)
+        {
+            defaults.insert(field_name.clone(), value);
+        }
+    }
+    defaults
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[test]
+    fn test_process_derive_schema_struct() {
+        let input: syn::DeriveInput = syn::parse_quote! {
+            struct User {
+                name: String,
+                age: u32,
+            }
+        };
+        let (metadata, _expanded) = process_derive_schema(&input);
+        assert_eq!(metadata.name, "User");
+        assert!(metadata.definition.contains("struct User"));
+    }
+
+    #[test]
+    fn test_process_derive_schema_enum() {
+        let input: syn::DeriveInput = syn::parse_quote! {
+            enum Status {
+                Active,
+                Inactive,
+            }
+        };
+        let (metadata, _expanded) = process_derive_schema(&input);
+        assert_eq!(metadata.name, "Status");
+        assert!(metadata.definition.contains("enum Status"));
+    }
+
+    #[test]
+    fn test_process_derive_schema_generic() {
+        let input: syn::DeriveInput = syn::parse_quote! {
+            struct Container<T> {
+                value: T,
+            }
+        };
+        let (metadata, _expanded) = process_derive_schema(&input);
+        assert_eq!(metadata.name, "Container");
+    }
+
+    #[test]
+    fn test_extract_schema_name_attr_with_name() {
+        let attrs: Vec<syn::Attribute> = syn::parse_quote! {
+            #[schema(name = "CustomName")]
+        };
+        let result = extract_schema_name_attr(&attrs);
+        assert_eq!(result, Some("CustomName".to_string()));
+    }
+
+    #[test]
+    fn test_extract_schema_name_attr_with_rename() {
+        let attrs: Vec<syn::Attribute> = syn::parse_quote! {
+            #[schema(rename = "user")]
+        };
+        assert_eq!(extract_schema_name_attr(&attrs), Some("user".to_string()));
+    }
+
+    #[rstest]
+    #[case(quote::quote!(), "UserProfile")]
+    #[case(quote::quote!(#[schema(rename_all = "camelCase")]), "userProfile")]
+    #[case(quote::quote!(#[schema(rename_all = "snake_case")]), "user_profile")]
+    #[case(quote::quote!(#[schema(scope = "x", rename_all = "kebab-case")]), "user-profile")]
+    #[case(quote::quote!(#[schema(name = "Profile", rename_all = "camelCase")]), "Profile")]
+    #[case(quote::quote!(#[serde(rename_all = "camelCase")]), "UserProfile")]
+    fn test_schema_component_name(#[case] attrs: proc_macro2::TokenStream, #[case] expected: &str) {
+        let input: syn::DeriveInput = syn::parse_quote! {
+            #attrs
+            struct UserProfile {
+                bio: String,
+            }
+        };
+        assert_eq!(schema_component_name(&input.ident, &input.attrs), expected);
+        let (metadata, _expanded) = process_derive_schema(&input);
+        assert_eq!(metadata.name, expected);
+    }
+
+    #[rstest]
+    #[case("User", true)]
+    #[case("user.v1-Profile_2", true)]
+    #[case("User Profile", false)]
+    #[case("api/User", false)]
+    #[case("Usér", false)]
+    #[case("", false)]
+    fn test_is_valid_component_key(#[case] name: &str, #[case] expected: bool) {
+        assert_eq!(is_valid_component_key(name), expected);
+    }
+
+    #[rstest]
+    #[case("User Profile", "UserProfile")]
+    #[case("api/v1 user", "ApiV1User")]
+    #[case(" User ", "User")]
+    #[case("/", "")]
+    fn test_suggest_component_key(#[case] name: &str, #[case] expected: &str) {
+        assert_eq!(suggest_component_key(name), expected);
+    }
+
+    #[rstest]
+    #[case(quote::quote!(#[schema(name = "User")]), None)]
+    #[case(quote::quote!(#[schema(scope = "x", rename = "user.v1")]), None)]
+    #[case(
+        quote::quote!(#[schema(name = "User Profile")]),
+        Some("invalid schema name \"User Profile\": component names may only contain ASCII letters, digits, '.', '-' and '_'. Did you mean \"UserProfile\"?")
+    )]
+    #[case(
+        quote::quote!(#[schema(rename = "/")]),
+        Some("invalid schema name \"/\": component names may only contain ASCII letters, digits, '.', '-' and '_'.")
+    )]
+    fn test_validate_schema_name_attr(
+        #[case] attrs: proc_macro2::TokenStream,
+        #[case] expected: Option<&str>,
+    ) {
+        let input: syn::DeriveInput = syn::parse_quote! {
+            #attrs
+            struct User;
+        };
+        let result = validate_schema_name_attr(&input.attrs);
+        assert_eq!(result.err().map(|err| err.to_string()).as_deref(), expected);
+
+        let (_metadata, expanded) = process_derive_schema(&input);
+        assert_eq!(
+            expanded.to_string().contains("compile_error"),
+            expected.is_some()
+        );
+    }
+
+    #[test]
+    fn test_process_derive_schema_bitflags() {
+        let input: syn::DeriveInput = syn::parse_quote! {
+            /// File permissions
+            #[derive(Debug, Clone, Copy)]
+            #[schema(flags = ["READ", "WRITE"])]
+            pub struct Perms(<Perms as ::bitflags::__private::PublicFlags>::Internal);
+        };
+        let (metadata, _expanded) = process_derive_schema(&input);
+        let parsed: syn::ItemStruct = syn::parse_str(&metadata.definition).unwrap();
+        assert_eq!(parsed.ident, "Perms");
+        assert!(matches!(parsed.fields, syn::Fields::Unit));
+        assert!(!metadata.definition.contains("derive"));
+        let schema = crate::parser::parse_struct_to_schema(
+            &parsed,
+            &std::collections::HashSet::new(),
+            &HashMap::new(),
+        );
+        assert_eq!(
+            schema.description.as_deref(),
+            Some("File permissions\n\nFlags separated by ` | `: `READ`, `WRITE`.")
+        );
+        assert_eq!(
+            schema.pattern.as_deref(),
+            Some("^((READ|WRITE|0x[0-9a-fA-F]+)( \\| (READ|WRITE|0x[0-9a-fA-F]+))*)?$")
+        );
+
+        let plain: syn::DeriveInput = syn::parse_quote!(
+            struct Meters(u32);
+        );
+        assert!(bitflags_definition(&plain).is_none());
+    }
+
+    #[rstest]
+    #[case(quote::quote!(#[schema(remote = "other_crate::Money")]), "Money")]
+    #[case(quote::quote!(#[schema(remote = "Money", name = "Price")]), "Price")]
+    #[case(
+        quote::quote!(#[schema(rename_all = "camelCase", remote = "rust_decimal::Decimal")]),
+        "decimal"
+    )]
+    fn test_process_derive_schema_remote(
+        #[case] attrs: proc_macro2::TokenStream,
+        #[case] expected: &str,
+    ) {
+        let input: syn::DeriveInput = syn::parse_quote! {
+            #attrs
+            struct MirrorDef {
+                amount: i64,
+                currency: String,
+            }
+        };
+        let (metadata, expanded) = process_derive_schema(&input);
+        assert_eq!(metadata.name, expected);
+        let parsed: syn::ItemStruct = syn::parse_str(&metadata.definition).unwrap();
+        assert_ne!(parsed.ident, "MirrorDef");
+        assert!(expanded.is_empty(), "{expanded}");
+    }
+
+    #[test]
+    fn test_process_derive_schema_invalid_remote() {
+        let input: syn::DeriveInput = syn::parse_quote! {
+            #[schema(remote = "not a path")]
+            struct MoneyDef { amount: i64 }
+        };
+        let (metadata, expanded) = process_derive_schema(&input);
+        assert_eq!(metadata.name, "MoneyDef");
+        assert!(
+            expanded.to_string().contains("invalid remote type"),
+            "{expanded}"
+        );
+    }
+
+    #[test]
+    fn test_process_derive_schema_invalid_name_for() {
+        let input: syn::DeriveInput = syn::parse_quote! {
+            #[schema(name_for(User = "User Page"))]
+            struct Page<T> { items: Vec<T> }
+        };
+        let (_, expanded) = process_derive_schema(&input);
+        assert!(
+            expanded.to_string().contains("invalid schema name"),
+            "{expanded}"
+        );
+    }
+
+    #[test]
+    fn test_process_derive_schema_read_and_write_only_field() {
+        let input: syn::DeriveInput = syn::parse_quote! {
+            #[schema(split)]
+            struct User { #[schema(read_only, write_only)] token: String }
+        };
+        let (_, expanded) = process_derive_schema(&input);
+        assert!(
+            expanded
+                .to_string()
+                .contains("field `token` cannot be both `read_only` and `write_only`"),
+            "{expanded}"
+        );
+    }
+
+    #[test]
+    fn test_extract_schema_name_attr_without_name() {
+        let attrs: Vec<syn::Attribute> = syn::parse_quote! {
+            #[derive(Debug)]
+        };
+        let result = extract_schema_name_attr(&attrs);
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn test_extract_schema_name_attr_empty_schema() {
+        let attrs: Vec<syn::Attribute> = syn::parse_quote! {
+            #[schema]
+        };
+        let result = extract_schema_name_attr(&attrs);
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn test_extract_schema_name_attr_with_other_attrs() {
+        let attrs: Vec<syn::Attribute> = syn::parse_quote! {
+            #[derive(Clone)]
+            #[schema(name = "MySchema")]
+            #[serde(rename_all = "camelCase")]
+        };
+        let result = extract_schema_name_attr(&attrs);
+        assert_eq!(result, Some("MySchema".to_string()));
+    }
+
+    #[test]
+    fn test_process_derive_schema_simple() {
+        let input: syn::DeriveInput = syn::parse_quote! {
+            struct User {
+                id: i32,
+                name: String,
+            }
+        };
+        let (metadata, _tokens) = process_derive_schema(&input);
+        assert_eq!(metadata.name, "User");
+        assert!(metadata.definition.contains("User"));
+    }
+
+    #[test]
+    fn test_process_derive_schema_with_custom_name() {
+        let input: syn::DeriveInput = syn::parse_quote! {
+            #[schema(name = "CustomUserSchema")]
+            struct User {
+                id: i32,
+            }
+        };
+        let (metadata, _) = process_derive_schema(&input);
+        assert_eq!(metadata.name, "CustomUserSchema");
+    }
+
+    #[test]
+    fn test_process_derive_schema_with_generics() {
+        let input: syn::DeriveInput = syn::parse_quote! {
+            struct Container<T> {
+                value: T,
+            }
+        };
+        let (metadata, _tokens) = process_derive_schema(&input);
+        assert_eq!(metadata.name, "Container");
+    }
+
+    #[test]
+    fn test_extract_schema_name_attr_non_name_meta_key() {
+        // #[schema(other = "foo")] — has schema attr but no "name" key
+        let attrs: Vec<syn::Attribute> = syn::parse_quote! {
+            #[schema(other = "foo")]
+        };
+        let result = extract_schema_name_attr(&attrs);
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn test_extract_defaults_from_file_finds_functions() {
+        // Directly tests the extracted function (covers lines 123-131)
+        let file_ast: syn::File = syn::parse_quote! {
+            fn default_count() -> i32 { 42 }
+            fn default_name() -> String { "hello".to_string() }
+        };
+        let fn_defaults = vec![
+            ("count".to_string(), "default_count".to_string()),
+            ("name".to_string(), "default_name".to_string()),
+        ];
+        let result = extract_defaults_from_file(&fn_defaults, &file_ast);
+        assert_eq!(result.get("count"), Some(&serde_json::json!(42)));
+        assert_eq!(result.get("name"), Some(&serde_json::json!("hello")));
+    }
+
+    #[test]
+    fn test_extract_defaults_from_file_missing_function() {
+        // Function not found in AST -> skipped
+        let file_ast: syn::File = syn::parse_quote! {
+            fn other_function() -> i32 { 0 }
+        };
+        let fn_defaults = vec![("count".to_string(), "nonexistent_fn".to_string())];
+        let result = extract_defaults_from_file(&fn_defaults, &file_ast);
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn test_extract_defaults_from_file_non_extractable_value() {
+        // Function exists but returns an assignment statement or block (not directly extractable)
+        let file_ast: syn::File = syn::parse_quote! {
+            fn default_value() -> String {
+                let x = String::new();
+                x  // Assignment before return - block statement
+            }
+        };
+        let fn_defaults = vec![("value".to_string(), "default_value".to_string())];
+        let result = extract_defaults_from_file(&fn_defaults, &file_ast);
+        // Block statements with multiple statements are not extractable
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn test_extract_defaults_from_file_empty_input() {
+        let file_ast: syn::File = syn::parse_quote! {};
+        let fn_defaults: Vec<(String, String)> = vec![];
+        let result = extract_defaults_from_file(&fn_defaults, &file_ast);
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn test_extract_schema_name_attr_multiple_schema_attrs() {
+        // Two #[schema] attrs — first one with name wins
+        let attrs: Vec<syn::Attribute> = syn::parse_quote! {
+            #[schema(name = "First")]
+            #[schema(name = "Second")]
+        };
+        let result = extract_schema_name_attr(&attrs);
+        assert_eq!(result, Some("First".to_string()));
+    }
+
+    #[test]
+    fn test_extract_schema_name_attr_schema_with_unknown_key_value() {
+        // #[schema(other = "x", name = "MyName")] — the unknown key's value is skipped,
+        // so `name` after it is still found (e.g. `remote = "...", name = "..."`)
+        let attrs: Vec<syn::Attribute> = syn::parse_quote! {
+            #[schema(other = "x", name = "MyName")]
+        };
+        let result = extract_schema_name_attr(&attrs);
+        assert_eq!(result, Some("MyName".to_string()));
+    }
+
+    #[test]
+    fn test_extract_schema_name_attr_name_before_unknown() {
+        // name comes FIRST, so it's extracted before the unknown key causes a bail
+        let attrs: Vec<syn::Attribute> = syn::parse_quote! {
+            #[schema(name = "Found", other = "x")]
+        };
+        let result = extract_schema_name_attr(&attrs);
+        // name is parsed successfully; parse_nested_meta may error on `other` but name is already set
+        assert_eq!(result, Some("Found".to_string()));
+    }
+
+    // ========== Coverage: process_derive_schema struct variants ==========
+
+    #[test]
+    fn test_process_derive_schema_unit_struct() {
+        let input: syn::DeriveInput = syn::parse_quote! {
+            struct Unit;
+        };
+        let (metadata, tokens) = process_derive_schema(&input);
+        assert_eq!(metadata.name, "Unit");
+        assert!(metadata.definition.contains("Unit"));
+        assert!(tokens.is_empty(), "Token stream should be empty");
+    }
+
+    #[test]
+    fn test_process_derive_schema_tuple_struct() {
+        let input: syn::DeriveInput = syn::parse_quote! {
+            struct Pair(i32, String);
+        };
+        let (metadata, tokens) = process_derive_schema(&input);
+        assert_eq!(metadata.name, "Pair");
+        assert!(metadata.definition.contains("Pair"));
+        assert!(tokens.is_empty());
+    }
+
+    #[test]
+    fn test_process_derive_schema_empty_struct() {
+        let input: syn::DeriveInput = syn::parse_quote! {
+            struct Empty {}
+        };
+        let (metadata, _) = process_derive_schema(&input);
+        assert_eq!(metadata.name, "Empty");
+    }
+
+    #[test]
+    fn test_process_derive_schema_with_lifetime() {
+        let input: syn::DeriveInput = syn::parse_quote! {
+            struct Ref<'a> {
+                data: &'a str,
+            }
+        };
+        let (metadata, _) = process_derive_schema(&input);
+        assert_eq!(metadata.name, "Ref");
+    }
+
+    #[test]
+    fn test_process_derive_schema_with_serde_attrs() {
+        let input: syn::DeriveInput = syn::parse_quote! {
+            #[serde(rename_all = "camelCase")]
+            struct UserResponse {
+                user_name: String,
+                #[serde(skip)]
+                internal_id: u64,
+            }
+        };
+        let (metadata, _) = process_derive_schema(&input);
+        assert_eq!(metadata.name, "UserResponse");
+        assert!(metadata.definition.contains("camelCase"));
+        assert!(metadata.definition.contains("skip"));
+    }
+
+    // ========== Coverage: metadata field verification ==========
+
+    #[test]
+    fn test_process_derive_schema_include_in_openapi_true() {
+        let input: syn::DeriveInput = syn::parse_quote! {
+            struct Visible { x: i32 }
+        };
+        let (metadata, _) = process_derive_schema(&input);
+        assert!(
+            metadata.include_in_openapi,
+            "Schema-derived types must have include_in_openapi=true"
+        );
+    }
+
+    #[test]
+    fn test_process_derive_schema_definition_contains_fields() {
+        let input: syn::DeriveInput = syn::parse_quote! {
+            struct WithFields {
+                id: u64,
+                name: String,
+                active: bool,
+            }
+        };
+        let (metadata, _) = process_derive_schema(&input);
+        assert!(metadata.definition.contains("id"));
+        assert!(metadata.definition.contains("u64"));
+        assert!(metadata.definition.contains("name"));
+        assert!(metadata.definition.contains("active"));
+        assert!(metadata.definition.contains("bool"));
+    }
+
+    // ========== Coverage: SCHEMA_STORAGE direct usage ==========
+
+    #[test]
+    fn test_schema_storage_insert_and_get() {
+        let storage = SCHEMA_STORAGE.lock().unwrap();
+        let key = "__test_coverage_type__".to_string();
+        // Clean up if previous test left data
+        drop(storage);
+
+        {
+            let mut storage = SCHEMA_STORAGE.lock().unwrap();
+            storage.insert(
+                key.clone(),
+                StructMetadata::new(key.clone(), "struct __test_coverage_type__ {}".to_string()),
+            );
+        }
+
+        {
+            let storage = SCHEMA_STORAGE.lock().unwrap();
+            let meta = storage.get(&key);
+            assert!(meta.is_some(), "Inserted metadata should be retrievable");
+            let meta = meta.unwrap();
+            assert_eq!(meta.name, key);
+            assert!(meta.include_in_openapi);
+        }
+
+        // Cleanup
+        {
+            let mut storage = SCHEMA_STORAGE.lock().unwrap();
+            storage.remove(&key);
+        }
+    }
+
+    #[test]
+    fn test_schema_storage_overwrite() {
+        let key = "__test_overwrite_type__".to_string();
+        {
+            let mut storage = SCHEMA_STORAGE.lock().unwrap();
+            storage.insert(
+                key.clone(),
+                StructMetadata::new(key.clone(), "struct V1 {}".to_string()),
+            );
+            storage.insert(
+                key.clone(),
+                StructMetadata::new(key.clone(), "struct V2 {}".to_string()),
+            );
+        }
+        {
+            let storage = SCHEMA_STORAGE.lock().unwrap();
+            let meta = storage.get(&key).unwrap();
+            assert!(meta.definition.contains("V2"), "Last insert should win");
+        }
+        // Cleanup
+        {
+            let mut storage = SCHEMA_STORAGE.lock().unwrap();
+            storage.remove(&key);
+        }
+    }
+
+    #[test]
+    fn test_extract_field_defaults_from_path_with_default_fn() {
+        // Exercises lines 125-133 (was 118-119, 123-124 before refactor):
+        // get_parsed_file succeeds and extract_defaults_from_file runs.
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("defaults.rs");
+        std::fs::write(
+            &file_path,
+            r#"
+fn default_status() -> String {
+    "active".to_string()
+}
+
+struct Config {
+    #[serde(default = "default_status")]
+    status: String,
+}
+"#,
+        )
+        .unwrap();
+
+        let input: syn::DeriveInput = syn::parse_quote! {
+            struct Config {
+                #[serde(default = "default_status")]
+                status: String,
+            }
+        };
+
+        let defaults = extract_field_defaults_from_path(&input, &file_path);
+        // The function should find default_status and extract its return value
+        assert!(
+            defaults.contains_key("status"),
+            "Should extract default for 'status' field"
+        );
+    }
+
+    #[test]
+    fn test_extract_field_defaults_from_path_file_not_found() {
+        // Exercises the else branch: get_parsed_file returns None for non-existent file
+        let input: syn::DeriveInput = syn::parse_quote! {
+            struct Config {
+                #[serde(default = "default_val")]
+                value: String,
+            }
+        };
+
+        let defaults =
+            extract_field_defaults_from_path(&input, Path::new("/nonexistent/path/foo.rs"));
+        assert!(
+            defaults.is_empty(),
+            "Should return empty defaults when file not found"
+        );
+    }
+
+    #[test]
+    fn test_extract_field_defaults_from_path_no_fn_defaults() {
+        // Exercises the early return: fn_defaults is empty
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("simple.rs");
+        std::fs::write(&file_path, "struct Foo { x: i32 }").unwrap();
+
+        let input: syn::DeriveInput = syn::parse_quote! {
+            struct Foo {
+                x: i32,
+            }
+        };
+
+        let defaults = extract_field_defaults_from_path(&input, &file_path);
+        assert!(defaults.is_empty(), "No serde defaults -> empty result");
+    }
+
+    #[test]
+    fn test_extract_field_defaults_from_path_tuple_struct() {
+        // Exercises line 101: Fields::Named else branch (tuple struct has unnamed fields)
+        let input: syn::DeriveInput = syn::parse_quote! {
+            struct Pair(String, i32);
+        };
+        let defaults = extract_field_defaults_from_path(&input, Path::new("/dummy.rs"));
+        assert!(
+            defaults.is_empty(),
+            "Tuple struct should return empty defaults"
+        );
+    }
+
+    #[test]
+    fn test_extract_field_defaults_from_path_enum() {
+        // Exercises line 103: Data::Struct else branch (enum)
+        let input: syn::DeriveInput = syn::parse_quote! {
+            enum Status { Active, Inactive }
+        };
+        let defaults = extract_field_defaults_from_path(&input, Path::new("/dummy.rs"));
+        assert!(defaults.is_empty(), "Enum should return empty defaults");
+    }
+
+    #[test]
+    fn test_process_derive_schema_ref_override_excludes_openapi() {
+        let input: syn::DeriveInput = syn::parse_quote! {
+            #[derive(Clone)]
+            #[schema(ref = "ExternalUser")]
+            struct UserSchema {
+                id: i32,
+            }
+        };
+
+        let (metadata, tokens) = process_derive_schema(&input);
+        assert_eq!(metadata.name, "UserSchema");
+        assert!(!metadata.include_in_openapi);
+        assert!(tokens.is_empty());
+    }
+
+    #[test]
+    fn test_generate_scoped_fields_impl() {
+        let input: syn::DeriveInput = syn::parse_quote! {
+            #[serde(rename_all = "camelCase")]
+            struct Employee {
+                id: i32,
+                #[schema(scope = "admin")]
+                base_salary: i64,
+                #[schema(scope = "hr")]
+                #[serde(rename = "ssn")]
+                social_security_number: String,
+            }
+        };
+        let (_, expanded) = process_derive_schema(&input);
+        let code = expanded.to_string();
+        assert!(code.contains("vespera :: scope :: ScopedFields for Employee"));
+        assert!(code.contains(r#"("baseSalary" , "admin")"#));
+        assert!(code.contains(r#"("ssn" , "hr")"#));
+        assert!(!code.contains("\"id\""));
+    }
+
+    #[test]
+    fn test_generate_scoped_fields_impl_nested() {
+        let input: syn::DeriveInput = syn::parse_quote! {
+            #[serde(rename_all = "camelCase")]
+            struct Team {
+                id: i32,
+                team_lead: Employee,
+                members: Option<Vec<Employee>>,
+                #[serde(flatten)]
+                audit: Audit,
+                #[serde(skip)]
+                cache: Cache,
+                extra: serde_json::Value,
+            }
+        };
+        let code = generate_scoped_fields_impl(&input).to_string();
+        assert!(code.contains("vespera :: scope :: ScopedFields for Team"));
+        assert!(code.contains(
+            "const SCOPED_FIELDS : & 'static [(& 'static str , & 'static str)] = & [] ;"
+        ));
+        assert!(code.contains(
+            r#"if let Some (field) = value . get_mut ("teamLead") { (& Nested :: < Employee > :: NEW) . strip (field , scopes) ; }"#
+        ));
+        assert!(code.contains("Nested :: < Option < Vec < Employee > > > :: NEW"));
+        assert!(code.contains("(& Nested :: < Audit > :: NEW) . strip (value , scopes) ;"));
+        assert!(!code.contains("Cache"));
+        assert!(!code.contains("\"id\""));
+        assert!(!code.contains("\"extra\""));
+    }
+
+    #[test]
+    fn test_generate_scoped_fields_impl_none() {
+        let input: syn::DeriveInput = syn::parse_quote! {
+            struct Plain { id: i32, tags: Vec<String>, parent: Option<Box<u64>> }
+        };
+        assert!(generate_scoped_fields_impl(&input).is_empty());
+        let input: syn::DeriveInput = syn::parse_quote! {
+            enum Status { Active }
+        };
+        assert!(generate_scoped_fields_impl(&input).is_empty());
+    }
+
+    #[test]
+    fn test_generate_formatted_fields_impl() {
+        let input: syn::DeriveInput = syn::parse_quote! {
+            #[serde(rename_all = "camelCase")]
+            struct Account {
+                #[schema(format = "ulid")]
+                account_id: String,
+                #[schema(scope = "admin", format = "iban")]
+                iban: String,
+                name: String,
+            }
+        };
+        let (_, expanded) = process_derive_schema(&input);
+        let code = expanded.to_string();
+        assert!(code.contains("vespera :: formats :: FormattedFields for Account"));
+        assert!(code.contains(r#"("accountId" , "ulid")"#));
+        assert!(code.contains(r#"("iban" , "iban")"#));
+        assert!(code.contains("vespera :: scope :: ScopedFields for Account"));
+        assert!(!code.contains("\"name\""));
+
+        let input: syn::DeriveInput = syn::parse_quote! {
+            struct Plain { id: String }
+        };
+        assert!(generate_formatted_fields_impl(&input).is_empty());
+    }
+
+    #[test]
+    fn test_generate_param_enum_impl() {
+        let input: syn::DeriveInput = syn::parse_quote! {
+            #[serde(rename_all = "snake_case")]
+            enum OrderStatus {
+                Pending,
+                InTransit,
+                #[serde(rename = "done")]
+                Delivered,
+            }
+        };
+        let (_, expanded) = process_derive_schema(&input);
+        let code = expanded.to_string();
+        assert!(code.contains("vespera :: params :: ParamEnum for OrderStatus"));
+        assert!(code.contains(r#"& ["pending" , "in_transit" , "done"]"#));
+        assert!(
+            code.contains(r#""in_transit" => std :: option :: Option :: Some (Self :: InTransit)"#)
+        );
+        assert!(code.contains(r#"Self :: Delivered => "done""#));
+    }
+
+    #[rstest]
+    #[case(quote::quote!(struct Plain { id: String }))]
+    #[case(quote::quote!(enum Shape { Circle { radius: f64 }, Empty }))]
+    #[case(quote::quote!(#[serde(tag = "type")] enum Kind { A, B }))]
+    #[case(quote::quote!(enum Status { Active, #[serde(other)] Unknown }))]
+    fn test_generate_param_enum_impl_none(#[case] tokens: proc_macro2::TokenStream) {
+        let input: syn::DeriveInput = syn::parse2(tokens).unwrap();
+        assert!(generate_param_enum_impl(&input).is_empty());
+    }
+
+    #[test]
+    fn test_generate_scoped_fields_impl_generic() {
+        let input: syn::DeriveInput = syn::parse_quote! {
+            struct Wrapper<T: Clone> {
+                #[schema(scope = "admin")]
+                secret: T,
+            }
+        };
+        let code = generate_scoped_fields_impl(&input).to_string();
+        assert!(
+            code.contains("impl < T : Clone > vespera :: scope :: ScopedFields for Wrapper < T >")
+        );
+    }
+}
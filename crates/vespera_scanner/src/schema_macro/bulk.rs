@@ -0,0 +1,204 @@
+//! `SeaORM` bulk create/update helpers
+//!
+//! For `schema_type!(CreateUser from Model, bulk)` the generated struct gets:
+//! - `IntoActiveModel<ActiveModel>` — every mapped column is `Set`; fields wrapped in
+//!   `Option` by `partial` are only `Set` when present
+//! - `bulk_create(db, items)` / `bulk_update(db, items)` — insert or update each item,
+//!   collecting per-item outcomes into a `vespera::bulk::BulkResult` keyed by primary key.
+//!   Unique and foreign-key violations become `409` / `422`, other database errors `500`;
+//!   clients get a fixed message and the full `DbErr` is logged
+//!
+//! Route handlers stay plain `#[vespera::route]` functions that call the helpers, so
+//! they are discovered and documented like any other route.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use super::{filter::module_item_path, seaorm::has_sea_orm_primary_key};
+
+/// Generate the `IntoActiveModel` impl and bulk helpers for a `SeaORM` Model source.
+///
+/// `field_mappings` are `(new_field_ident, source_field_ident, wrapped_in_option, is_relation)`;
+/// relation fields have no column and are left out of the `ActiveModel`.
+pub fn generate_bulk_impl(
+    new_type_name: &syn::Ident,
+    parsed_struct: &syn::ItemStruct,
+    source_module_path: &[String],
+    field_mappings: &[(syn::Ident, syn::Ident, bool, bool)],
+) -> TokenStream {
+    let active_model = module_item_path(source_module_path, "ActiveModel");
+
+    let assignments = field_mappings
+        .iter()
+        .filter(|(_, _, _, is_relation)| !is_relation)
+        .map(|(new_ident, source_ident, wrapped, _)| {
+            if *wrapped {
+                quote! {
+                    if let Some(value) = self.#new_ident {
+                        active.#source_ident = sea_orm::ActiveValue::Set(value);
+                    }
+                }
+            } else {
+                quote! {
+                    active.#source_ident = sea_orm::ActiveValue::Set(self.#new_ident);
+                }
+            }
+        });
+
+    let primary_keys: Vec<&syn::Ident> = match &parsed_struct.fields {
+        syn::Fields::Named(fields_named) => fields_named
+            .named
+            .iter()
+            .filter(|field| has_sea_orm_primary_key(&field.attrs))
+            .filter_map(|field| field.ident.as_ref())
+            .collect(),
+        _ => Vec::new(),
+    };
+    let id = match primary_keys.as_slice() {
+        [key] => quote! { model.#key },
+        keys => quote! { (#(model.#keys),*) },
+    };
+
+    quote! {
+        impl sea_orm::IntoActiveModel<#active_model> for #new_type_name {
+            fn into_active_model(self) -> #active_model {
+                let mut active = <#active_model as sea_orm::ActiveModelBehavior>::new();
+                #(#assignments)*
+                active
+            }
+        }
+
+        impl #new_type_name {
+            /// Map a database error to the item status and the message sent to clients.
+            fn classify_bulk_error(
+                err: &sea_orm::DbErr,
+            ) -> (vespera::axum::http::StatusCode, &'static str) {
+                match err.sql_err() {
+                    Some(sea_orm::SqlErr::UniqueConstraintViolation(_)) => (
+                        vespera::axum::http::StatusCode::CONFLICT,
+                        "conflicts with an existing record",
+                    ),
+                    Some(sea_orm::SqlErr::ForeignKeyConstraintViolation(_)) => (
+                        vespera::axum::http::StatusCode::UNPROCESSABLE_ENTITY,
+                        "references a missing record",
+                    ),
+                    _ => (
+                        vespera::axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+                        "internal error",
+                    ),
+                }
+            }
+
+            /// Insert every item, reporting each outcome with the new row's primary key.
+            pub async fn bulk_create<C: sea_orm::ConnectionTrait>(
+                db: &C,
+                items: vespera::bulk::Bulk<Self>,
+            ) -> vespera::bulk::BulkResult {
+                let mut results = Vec::with_capacity(items.len());
+                for item in items {
+                    let active = sea_orm::IntoActiveModel::into_active_model(item);
+                    results.push(
+                        sea_orm::ActiveModelTrait::insert(active, db)
+                            .await
+                            .map(|model| #id),
+                    );
+                }
+                vespera::bulk::BulkResult::from_results_with(results, Self::classify_bulk_error)
+            }
+
+            /// Update every item by primary key, reporting each outcome.
+            ///
+            /// Only the columns of this struct are written; the primary key must be one of them.
+            pub async fn bulk_update<C: sea_orm::ConnectionTrait>(
+                db: &C,
+                items: vespera::bulk::Bulk<Self>,
+            ) -> vespera::bulk::BulkResult {
+                let mut results = Vec::with_capacity(items.len());
+                for item in items {
+                    let active = sea_orm::IntoActiveModel::into_active_model(item);
+                    results.push(
+                        sea_orm::ActiveModelTrait::update(active, db)
+                            .await
+                            .map(|model| #id),
+                    );
+                }
+                vespera::bulk::BulkResult::from_results_with(results, Self::classify_bulk_error)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user_model() -> syn::ItemStruct {
+        syn::parse_quote! {
+            #[sea_orm(table_name = "user")]
+            pub struct Model {
+                #[sea_orm(primary_key)]
+                pub id: i32,
+                pub name: String,
+                pub email: Option<String>,
+            }
+        }
+    }
+
+    fn ident(name: &str) -> syn::Ident {
+        syn::Ident::new(name, proc_macro2::Span::call_site())
+    }
+
+    #[test]
+    fn test_generate_bulk_impl() {
+        let mappings = vec![
+            (ident("id"), ident("id"), false, false),
+            (ident("display_name"), ident("name"), true, false),
+            (ident("memos"), ident("memos"), false, true),
+        ];
+        let code = generate_bulk_impl(
+            &ident("UpdateUser"),
+            &user_model(),
+            &[
+                "crate".to_string(),
+                "models".to_string(),
+                "user".to_string(),
+            ],
+            &mappings,
+        )
+        .to_string();
+        assert!(code.contains(
+            "sea_orm :: IntoActiveModel < crate :: models :: user :: ActiveModel > for UpdateUser"
+        ));
+        assert!(code.contains("active . id = sea_orm :: ActiveValue :: Set (self . id)"));
+        assert!(code.contains(
+            "if let Some (value) = self . display_name { active . name = sea_orm :: ActiveValue :: Set (value) ; }"
+        ));
+        assert!(!code.contains("active . memos"));
+        assert!(code.contains("pub async fn bulk_create"));
+        assert!(code.contains("pub async fn bulk_update"));
+        assert!(code.contains(". map (| model | model . id)"));
+        assert!(
+            code.contains(
+                "BulkResult :: from_results_with (results , Self :: classify_bulk_error)"
+            )
+        );
+        assert!(code.contains("sea_orm :: SqlErr :: UniqueConstraintViolation (_)"));
+        assert!(code.contains("sea_orm :: SqlErr :: ForeignKeyConstraintViolation (_)"));
+        assert!(!code.contains("to_string"));
+    }
+
+    #[test]
+    fn test_generate_bulk_impl_composite_key() {
+        let model: syn::ItemStruct = syn::parse_quote! {
+            pub struct Model {
+                #[sea_orm(primary_key, auto_increment = false)]
+                pub user_id: i32,
+                #[sea_orm(primary_key, auto_increment = false)]
+                pub tag_id: i32,
+            }
+        };
+        let code = generate_bulk_impl(&ident("UserTag"), &model, &[], &[]).to_string();
+        assert!(code.contains("IntoActiveModel < ActiveModel > for UserTag"));
+        assert!(code.contains("(model . user_id , model . tag_id)"));
+    }
+}
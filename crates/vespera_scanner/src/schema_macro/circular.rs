@@ -86,11 +86,14 @@ pub fn analyze_circular_refs(source_module_path: &[String], definition: &str) ->
             has_fk = true;
 
             // --- is_circular_relation_required logic (for ALL FK fields) ---
-            let required = extract_belongs_to_from_field(&field.attrs).is_some_and(|fk| {
-                field_by_name
-                    .get(&fk)
-                    .is_some_and(|f| !is_option_type(&f.ty))
-            });
+            // Required only if every FK column (several for a composite key) is
+            let fk_columns = extract_belongs_to_from_field(&field.attrs);
+            let required = !fk_columns.is_empty()
+                && fk_columns.iter().all(|fk| {
+                    field_by_name
+                        .get(fk)
+                        .is_some_and(|f| !is_option_type(&f.ty))
+                });
             circular_field_required.insert(field_name.clone(), required);
         }
 
@@ -136,13 +139,13 @@ pub fn generate_default_for_relation_field(
         quote! { #field_ident: vec![] }
     } else if ty_str.contains("HasOne<") || ty_str.contains("BelongsTo<") {
         // Check FK field optionality
-        let fk_field = extract_belongs_to_from_field(field_attrs);
-        let is_optional = fk_field.as_ref().is_none_or(|fk| {
-            all_fields.named.iter().any(|f| {
-                f.ident.as_ref().map(std::string::ToString::to_string) == Some(fk.clone())
-                    && is_option_type(&f.ty)
-            })
-        });
+        let fk_columns = extract_belongs_to_from_field(field_attrs);
+        let is_optional = fk_columns.is_empty()
+            || fk_columns.iter().any(|fk| {
+                all_fields.named.iter().any(|f| {
+                    f.ident.as_ref().is_some_and(|ident| ident == fk) && is_option_type(&f.ty)
+                })
+            });
 
         if is_optional {
             // Option<Box<Schema>> -> None
@@ -642,9 +645,8 @@ mod tests {
             .get("user")
             .copied()
             .unwrap_or(false);
-        // Without proper BelongsTo attribute parsing, this returns false
-        // because extract_belongs_to_from_field won't find the FK
-        assert!(!result);
+        // `from = "Column::UserId"` resolves to the required `user_id` field
+        assert!(result);
     }
 
     #[test]
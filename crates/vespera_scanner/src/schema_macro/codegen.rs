@@ -12,9 +12,9 @@ use super::type_utils::is_option_type;
 use crate::{
     metadata::StructMetadata,
     parser::{
-        extract_default, extract_field_rename, extract_rename_all, extract_skip,
-        extract_skip_serializing_if, parse_type_to_schema_ref, rename_field,
-        strip_raw_prefix_owned,
+        apply_field_constraints, apply_field_content, extract_default, extract_field_rename,
+        extract_rename_all, extract_skip, extract_skip_serializing_if, parse_type_to_schema_ref,
+        rename_field, strip_raw_prefix_owned,
     },
 };
 
@@ -72,8 +72,10 @@ pub fn generate_filtered_schema(
             let field_type = &field.ty;
 
             // Generate schema for field type
-            let schema_ref =
+            let mut schema_ref =
                 parse_type_to_schema_ref(field_type, &known_schemas, &struct_definitions);
+            apply_field_content(&mut schema_ref, &field.attrs);
+            apply_field_constraints(&mut schema_ref, &field.attrs);
             let schema_ref_tokens = schema_ref_to_tokens(&schema_ref);
 
             property_tokens.push(quote! {
@@ -103,7 +105,7 @@ pub fn generate_filtered_schema(
 
     quote! {
         {
-            let mut properties = std::collections::BTreeMap::new();
+            let mut properties = vespera::schema::Properties::new();
             #(#property_tokens)*
             vespera::schema::Schema {
                 schema_type: Some(vespera::schema::SchemaType::Object),
@@ -172,6 +174,14 @@ pub fn schema_to_tokens(schema: &Schema) -> TokenStream {
         fields.push(quote! { format: Some(#f.to_string()) });
     }
 
+    // contentEncoding / contentMediaType
+    if let Some(encoding) = &schema.content_encoding {
+        fields.push(quote! { content_encoding: Some(#encoding.to_string()) });
+    }
+    if let Some(media_type) = &schema.content_media_type {
+        fields.push(quote! { content_media_type: Some(#media_type.to_string()) });
+    }
+
     // nullable
     if let Some(n) = schema.nullable {
         fields.push(quote! { nullable: Some(#n) });
@@ -194,7 +204,7 @@ pub fn schema_to_tokens(schema: &Schema) -> TokenStream {
             .collect();
         fields.push(quote! {
             properties: Some({
-                let mut map = std::collections::BTreeMap::new();
+                let mut map = vespera::schema::Properties::new();
                 #(map.insert(#entries.0, #entries.1);)*
                 map
             })
@@ -217,6 +227,36 @@ pub fn schema_to_tokens(schema: &Schema) -> TokenStream {
         fields.push(quote! { maximum: Some(#max) });
     }
 
+    // multipleOf
+    if let Some(multiple_of) = schema.multiple_of {
+        fields.push(quote! { multiple_of: Some(#multiple_of) });
+    }
+
+    // minLength / maxLength / pattern
+    if let Some(min_length) = schema.min_length {
+        fields.push(quote! { min_length: Some(#min_length) });
+    }
+    if let Some(max_length) = schema.max_length {
+        fields.push(quote! { max_length: Some(#max_length) });
+    }
+    if let Some(pattern) = &schema.pattern {
+        fields.push(quote! { pattern: Some(#pattern.to_string()) });
+    }
+
+    // minItems / maxItems
+    if let Some(min_items) = schema.min_items {
+        fields.push(quote! { min_items: Some(#min_items) });
+    }
+    if let Some(max_items) = schema.max_items {
+        fields.push(quote! { max_items: Some(#max_items) });
+    }
+
+    // allOf
+    if let Some(all_of) = &schema.all_of {
+        let variants = all_of.iter().map(schema_ref_to_tokens);
+        fields.push(quote! { all_of: Some(vec![#(#variants),*]) });
+    }
+
     quote! {
         vespera::schema::Schema {
             #(#fields,)*
@@ -384,6 +424,37 @@ mod tests {
         assert!(output.contains("date-time"));
     }
 
+    #[test]
+    fn test_schema_to_tokens_with_content_encoding() {
+        let mut schema = Schema::new(SchemaType::String);
+        schema.content_encoding = Some("base64".to_string());
+        schema.content_media_type = Some("image/png".to_string());
+        let output = schema_to_tokens(&schema).to_string();
+        assert!(output.contains("content_encoding : Some (\"base64\" . to_string ())"));
+        assert!(output.contains("content_media_type : Some (\"image/png\" . to_string ())"));
+    }
+
+    #[test]
+    fn test_generate_filtered_schema_content_encoding() {
+        let struct_item: syn::ItemStruct = syn::parse_str(
+            r#"
+            pub struct Upload {
+                #[schema(content_encoding = "base64")]
+                pub data: String,
+            }
+        "#,
+        )
+        .unwrap();
+        let output = generate_filtered_schema(
+            &struct_item,
+            &HashSet::new(),
+            &HashSet::new(),
+            &HashMap::new(),
+        )
+        .to_string();
+        assert!(output.contains("content_encoding : Some (\"base64\" . to_string ())"));
+    }
+
     #[test]
     fn test_schema_to_tokens_with_nullable() {
         let mut schema = Schema::new(SchemaType::String);
@@ -424,10 +495,10 @@ mod tests {
 
     #[test]
     fn test_schema_to_tokens_with_properties() {
-        use std::collections::BTreeMap;
+        use vespera_core::schema::Properties;
 
         let mut schema = Schema::new(SchemaType::Object);
-        let mut props = BTreeMap::new();
+        let mut props = Properties::new();
         props.insert(
             "name".to_string(),
             SchemaRef::Inline(Box::new(Schema::new(SchemaType::String))),
@@ -463,6 +534,52 @@ mod tests {
         assert!(output.contains("Some"), "should contain Some: {output}");
     }
 
+    #[test]
+    fn test_generate_filtered_schema_with_constraints() {
+        let struct_item: syn::ItemStruct = syn::parse_str(
+            r#"
+            pub struct User {
+                #[schema(min_length = 3, max_length = 32, pattern = "^[a-z]+$")]
+                pub name: String,
+                #[schema(multiple_of = 5, min_items = 1, max_items = 10)]
+                pub scores: Vec<i32>,
+                #[schema(minimum = 1)]
+                pub team: Team,
+            }
+            "#,
+        )
+        .unwrap();
+        let schema_storage = HashMap::from([(
+            "Team".to_string(),
+            StructMetadata::new(
+                "Team".to_string(),
+                "pub struct Team { pub id: i32 }".to_string(),
+            ),
+        )]);
+        let output = generate_filtered_schema(
+            &struct_item,
+            &HashSet::new(),
+            &HashSet::new(),
+            &schema_storage,
+        )
+        .to_string();
+        for expected in [
+            "min_length : Some (3usize)",
+            "max_length : Some (32usize)",
+            "pattern : Some (\"^[a-z]+$\" . to_string ())",
+            "multiple_of : Some (5f64)",
+            "min_items : Some (1usize)",
+            "max_items : Some (10usize)",
+            "minimum : Some (1f64)",
+            "all_of : Some (vec ! [",
+        ] {
+            assert!(
+                output.contains(expected),
+                "should contain {expected}: {output}"
+            );
+        }
+    }
+
     #[test]
     fn test_schema_to_tokens_with_maximum() {
         let mut schema = Schema::new(SchemaType::Integer);
@@ -20,6 +20,9 @@ use super::circular::CircularAnalysis;
 use super::file_lookup::collect_rs_files_recursive;
 use crate::metadata::StructMetadata;
 
+/// `(fk_column, key_column)` pairs of a relation, one per column of the key.
+type FkColumnPairs = Vec<(String, String)>;
+
 /// Internal cache state.
 struct FileCache {
     /// Cached `.rs` file lists per source directory.
@@ -61,7 +64,7 @@ struct FileCache {
     /// `None` values are cached (negative cache) to avoid repeated failed lookups.
     struct_lookup: HashMap<String, Option<StructMetadata>>,
     /// Cached FK column lookups: (schema_path, via_rel) → Option<column_name>.
-    fk_column_lookup: HashMap<(String, String), Option<String>>,
+    fk_column_lookup: HashMap<(String, String), Option<FkColumnPairs>>,
     /// Cached module path extraction from schema paths: path_str → Vec<module segments>.
     module_path_cache: HashMap<String, Vec<String>>,
     /// Cached struct definitions from files: file_path → (mtime, struct_name → definition_string).
@@ -125,6 +128,12 @@ pub fn get_manifest_dir() -> Option<String> {
 /// invocations because `proc_macro2`/`syn` types contain `proc_macro::TokenStream`
 /// bridge handles that become invalid when the invocation that created them ends.
 pub fn get_parsed_file(path: &Path) -> Option<syn::File> {
+    try_parse_file(path).ok()
+}
+
+/// Like [`get_parsed_file`], but says why the file could not be read or parsed
+/// (`line 3, column 9: expected `;``).
+pub fn try_parse_file(path: &Path) -> Result<syn::File, String> {
     FILE_CACHE.with(|cache| {
         let mut cache = cache.borrow_mut();
         parse_file_cached(&mut cache, path)
@@ -136,10 +145,19 @@ pub fn get_parsed_file(path: &Path) -> Option<syn::File> {
 /// Reads file content from the mtime-validated content cache (avoids redundant
 /// disk I/O), then calls `syn::parse_file`. The resulting `syn::File` is NOT
 /// cached — it must be used and dropped within the current proc-macro invocation.
-fn parse_file_cached(cache: &mut FileCache, path: &Path) -> Option<syn::File> {
-    let content = get_file_content_inner(cache, path)?;
+fn parse_file_cached(cache: &mut FileCache, path: &Path) -> Result<syn::File, String> {
+    let content =
+        get_file_content_inner(cache, path).ok_or_else(|| "cannot be read".to_string())?;
     cache.ast_parses += 1;
-    syn::parse_file(&content).ok()
+    syn::parse_file(&content).map_err(|e| {
+        let start = e.span().start();
+        format!("line {}, column {}: {e}", start.line, start.column + 1)
+    })
+}
+
+/// Get file content through the mtime-validated content cache.
+pub fn get_file_content(path: &Path) -> Option<String> {
+    FILE_CACHE.with(|cache| get_file_content_inner(&mut cache.borrow_mut(), path))
 }
 
 /// Get candidate files that likely contain `struct_name`, using cache when available.
@@ -197,7 +215,7 @@ fn ensure_struct_definitions(cache: &mut FileCache, path: &Path) -> bool {
 
     // Cache miss — parse file and extract all struct definitions.
     // Uses parse_file_cached: single syn::parse_file entry point.
-    let Some(file_ast) = parse_file_cached(cache, path) else {
+    let Ok(file_ast) = parse_file_cached(cache, path) else {
         return false;
     };
 
@@ -340,9 +358,9 @@ pub fn get_struct_from_schema_path(path_str: &str) -> Option<StructMetadata> {
 
 /// Get or compute FK column lookup, with caching.
 ///
-/// Wraps `find_fk_column_from_target_entity` with a `HashMap<(String, String), Option<String>>`
-/// cache. Negative results (`None`) are cached to avoid repeated file lookups.
-pub fn get_fk_column(schema_path: &str, via_rel: &str) -> Option<String> {
+/// Wraps `find_fk_column_from_target_entity` with a `(schema_path, via_rel)` keyed cache.
+/// Negative results (`None`) are cached to avoid repeated file lookups.
+pub fn get_fk_column(schema_path: &str, via_rel: &str) -> Option<FkColumnPairs> {
     let key = (schema_path.to_string(), via_rel.to_string());
 
     // 1. Check cache — borrow dropped at end of closure
@@ -251,19 +251,11 @@ fn normalize_name(s: &str) -> String {
         .collect()
 }
 
-/// Recursively collect all `.rs` files in a directory.
+/// Recursively collect all `.rs` files in a directory, skipping hidden entries, `target`
+/// and symlink cycles.
 pub fn collect_rs_files_recursive(dir: &Path, files: &mut Vec<std::path::PathBuf>) {
-    let Ok(entries) = std::fs::read_dir(dir) else {
-        return;
-    };
-
-    for entry in entries.flatten() {
-        let path = entry.path();
-        if path.is_dir() {
-            collect_rs_files_recursive(&path, files);
-        } else if path.extension().is_some_and(|ext| ext == "rs") {
-            files.push(path);
-        }
+    if let Ok(found) = crate::file_utils::collect_rs_files(dir, &[]) {
+        files.extend(found);
     }
 }
 
@@ -351,15 +343,20 @@ pub fn find_struct_from_schema_path(path_str: &str) -> Option<StructMetadata> {
 /// When a `HasMany` relation has `via_rel = "TargetUser"`, this function:
 /// 1. Looks up the target entity file (e.g., notification.rs from schema path)
 /// 2. Finds the field with matching `relation_enum = "TargetUser"`
-/// 3. Extracts and returns the `from` attribute value (e.g., "`target_user_id`")
+/// 3. Extracts and returns the `from` columns paired with the `to` columns they reference
+///    on this side (e.g., `[("target_user_id", "id")]`; several pairs for a composite key)
+///
+/// A single-column key without `to` references `id`.
 ///
 /// Returns None if the target file can't be found or parsed, or if no matching relation exists.
 #[allow(clippy::too_many_lines)]
 pub fn find_fk_column_from_target_entity(
     target_schema_path: &str,
     via_rel: &str,
-) -> Option<String> {
-    use crate::schema_macro::seaorm::{extract_belongs_to_from_field, extract_relation_enum};
+) -> Option<Vec<(String, String)>> {
+    use crate::schema_macro::seaorm::{
+        extract_belongs_to_from_field, extract_belongs_to_to_field, extract_relation_enum,
+    };
 
     // Get CARGO_MANIFEST_DIR to locate src folder (cached to avoid repeated syscalls)
     let manifest_dir = super::file_cache::get_manifest_dir()?;
@@ -403,8 +400,16 @@ pub fn find_fk_column_from_target_entity(
             for field in &fields_named.named {
                 let field_relation_enum = extract_relation_enum(&field.attrs);
                 if field_relation_enum.as_deref() == Some(via_rel) {
-                    // Found the matching field, extract FK column from `from` attribute
-                    return extract_belongs_to_from_field(&field.attrs);
+                    // Found the matching field, extract FK columns from `from` and `to`
+                    let from = extract_belongs_to_from_field(&field.attrs);
+                    let mut to = extract_belongs_to_to_field(&field.attrs);
+                    if to.is_empty() && from.len() == 1 {
+                        to.push("id".to_string());
+                    }
+                    if from.is_empty() || from.len() != to.len() {
+                        return None;
+                    }
+                    return Some(from.into_iter().zip(to).collect());
                 }
             }
         }
@@ -1131,7 +1136,7 @@ pub struct Model {
 
         assert_eq!(
             result,
-            Some("target_user_id".to_string()),
+            Some(vec![("target_user_id".to_string(), "id".to_string())]),
             "Should find FK column 'target_user_id'"
         );
     }
@@ -1171,7 +1176,7 @@ pub struct Model {
 
         assert_eq!(
             result,
-            Some("sender_id".to_string()),
+            Some(vec![("sender_id".to_string(), "id".to_string())]),
             "Should find FK column from mod.rs"
         );
     }
@@ -1397,7 +1402,7 @@ pub struct Model {
             }
         }
 
-        // extract_belongs_to_from_field returns None when no `from` attr
+        // extract_belongs_to_from_field returns no columns when no `from` attr
         assert!(
             result.is_none(),
             "Field without 'from' attribute should return None"
@@ -0,0 +1,380 @@
+//! Filter/sort query struct generation
+//!
+//! Generates a typed query-parameter struct from a Model for
+//! `schema_type!(UserFilter from Model, filter)`.
+//!
+//! For every scalar column the generated struct exposes:
+//! - `{field}` — equality
+//! - `{field}_lt` / `{field}_gt` — range comparison (numeric and date/time columns)
+//! - `{field}_contains` — substring match (`String` columns)
+//!
+//! Plus `sort_by` (a generated `{Name}SortBy` enum with one variant per column)
+//! and `sort_order` (`{Name}SortOrder`: `asc` / `desc`).
+//!
+//! When the source is a `SeaORM` Model, an `apply_to(select)` method is also
+//! generated that translates the filter into `QueryFilter`/`QueryOrder` calls.
+
+use std::collections::HashSet;
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::Type;
+
+use super::{
+    seaorm::convert_seaorm_type_to_chrono,
+    transformation::should_skip_field,
+    type_utils::{is_seaorm_relation_type, snake_to_pascal_case},
+};
+use crate::parser::strip_raw_prefix_owned;
+
+/// Comparison operators supported for a column, derived from its Rust type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterKind {
+    /// Equality only (bool, Uuid, enums, ...)
+    Eq,
+    /// Equality + `_lt` / `_gt` (numbers, dates)
+    Range,
+    /// Equality + `_contains` (strings)
+    Text,
+}
+
+/// Classify a column type into the set of filter operators it supports.
+///
+/// `Option<T>` is unwrapped first, so nullable columns get the same operators as `T`.
+pub fn classify_filter_kind(ty: &Type) -> FilterKind {
+    let inner = unwrap_option(ty);
+    let Type::Path(type_path) = inner else {
+        return FilterKind::Eq;
+    };
+    let Some(segment) = type_path.path.segments.last() else {
+        return FilterKind::Eq;
+    };
+    match segment.ident.to_string().as_str() {
+        "String" => FilterKind::Text,
+        "i8"
+        | "i16"
+        | "i32"
+        | "i64"
+        | "u8"
+        | "u16"
+        | "u32"
+        | "u64"
+        | "f32"
+        | "f64"
+        | "Decimal"
+        | "BigDecimal"
+        | "DateTime"
+        | "DateTimeWithTimeZone"
+        | "DateTimeUtc"
+        | "DateTimeLocal"
+        | "NaiveDateTime"
+        | "NaiveDate"
+        | "NaiveTime"
+        | "Date"
+        | "Time" => FilterKind::Range,
+        _ => FilterKind::Eq,
+    }
+}
+
+/// Return the inner `T` of `Option<T>`, or the type itself.
+fn unwrap_option(ty: &Type) -> &Type {
+    if let Type::Path(type_path) = ty
+        && let Some(segment) = type_path.path.segments.last()
+        && segment.ident == "Option"
+        && let syn::PathArguments::AngleBracketed(args) = &segment.arguments
+        && let Some(syn::GenericArgument::Type(inner)) = args.args.first()
+    {
+        return inner;
+    }
+    ty
+}
+
+/// Build `module::Name` (or bare `Name` when the module path is unknown).
+pub fn module_item_path(source_module_path: &[String], name: &str) -> TokenStream {
+    let item = syn::Ident::new(name, proc_macro2::Span::call_site());
+    if source_module_path.is_empty() {
+        quote! { #item }
+    } else {
+        let segments: Vec<syn::Ident> = source_module_path
+            .iter()
+            .map(|s| syn::Ident::new(s, proc_macro2::Span::call_site()))
+            .collect();
+        quote! { #(#segments)::* :: #item }
+    }
+}
+
+/// Generate the filter struct, its sort enums, and (for `SeaORM` models) `apply_to`.
+#[allow(clippy::too_many_lines, clippy::too_many_arguments)]
+pub fn generate_filter_code(
+    new_type_name: &syn::Ident,
+    parsed_struct: &syn::ItemStruct,
+    source_module_path: &[String],
+    omit_set: &HashSet<String>,
+    pick_set: &HashSet<String>,
+    effective_rename_all: &str,
+    schema_name_attr: &TokenStream,
+    is_seaorm_model: bool,
+) -> TokenStream {
+    let sort_by_name = format_ident!("{}SortBy", new_type_name);
+    let sort_order_name = format_ident!("{}SortOrder", new_type_name);
+
+    let mut field_tokens = Vec::new();
+    let mut sort_variants = Vec::new();
+    let mut filter_stmts = Vec::new();
+    let mut sort_arms = Vec::new();
+
+    let column_path = |variant: &syn::Ident| {
+        let column = module_item_path(source_module_path, "Column");
+        quote! { #column::#variant }
+    };
+
+    if let syn::Fields::Named(fields_named) = &parsed_struct.fields {
+        for field in &fields_named.named {
+            let Some(ident) = field.ident.as_ref() else {
+                continue;
+            };
+            let rust_field_name = strip_raw_prefix_owned(ident.to_string());
+            if should_skip_field(&rust_field_name, omit_set, pick_set)
+                || is_seaorm_relation_type(&field.ty)
+            {
+                continue;
+            }
+
+            let inner_ty = unwrap_option(&field.ty);
+            let value_ty = convert_seaorm_type_to_chrono(inner_ty, source_module_path);
+            let variant = format_ident!("{}", snake_to_pascal_case(&rust_field_name));
+            let column = column_path(&variant);
+            let doc_eq = format!(" Filter by `{rust_field_name}` (equals)");
+
+            field_tokens.push(quote! {
+                #[doc = #doc_eq]
+                #[serde(default)]
+                pub #ident: Option<#value_ty>
+            });
+            filter_stmts.push(quote! {
+                if let Some(value) = &self.#ident {
+                    select = select.filter(#column.eq(value.clone()));
+                }
+            });
+
+            match classify_filter_kind(&field.ty) {
+                FilterKind::Range => {
+                    let lt_ident = format_ident!("{}_lt", rust_field_name);
+                    let gt_ident = format_ident!("{}_gt", rust_field_name);
+                    let less_than_doc = format!(" Filter by `{rust_field_name}` (less than)");
+                    let greater_than_doc = format!(" Filter by `{rust_field_name}` (greater than)");
+                    field_tokens.push(quote! {
+                        #[doc = #less_than_doc]
+                        #[serde(default)]
+                        pub #lt_ident: Option<#value_ty>
+                    });
+                    field_tokens.push(quote! {
+                        #[doc = #greater_than_doc]
+                        #[serde(default)]
+                        pub #gt_ident: Option<#value_ty>
+                    });
+                    filter_stmts.push(quote! {
+                        if let Some(value) = &self.#lt_ident {
+                            select = select.filter(#column.lt(value.clone()));
+                        }
+                        if let Some(value) = &self.#gt_ident {
+                            select = select.filter(#column.gt(value.clone()));
+                        }
+                    });
+                }
+                FilterKind::Text => {
+                    let contains_ident = format_ident!("{}_contains", rust_field_name);
+                    let doc_contains = format!(" Filter by `{rust_field_name}` (contains)");
+                    field_tokens.push(quote! {
+                        #[doc = #doc_contains]
+                        #[serde(default)]
+                        pub #contains_ident: Option<String>
+                    });
+                    filter_stmts.push(quote! {
+                        if let Some(value) = &self.#contains_ident {
+                            select = select.filter(#column.contains(value.as_str()));
+                        }
+                    });
+                }
+                FilterKind::Eq => {}
+            }
+
+            sort_variants.push(quote! { #variant });
+            sort_arms.push(quote! { #sort_by_name::#variant => #column });
+        }
+    }
+
+    let apply_impl = if is_seaorm_model {
+        let entity = module_item_path(source_module_path, "Entity");
+        quote! {
+            impl #new_type_name {
+                /// Apply the filter and sort options to a `SeaORM` select query.
+                pub fn apply_to(&self, select: sea_orm::Select<#entity>) -> sea_orm::Select<#entity> {
+                    use sea_orm::{ColumnTrait as _, QueryFilter as _, QueryOrder as _};
+                    let mut select = select;
+                    #(#filter_stmts)*
+                    if let Some(sort_by) = self.sort_by {
+                        let order = match self.sort_order {
+                            Some(#sort_order_name::Desc) => sea_orm::Order::Desc,
+                            _ => sea_orm::Order::Asc,
+                        };
+                        let column = match sort_by {
+                            #(#sort_arms),*
+                        };
+                        select = select.order_by(column, order);
+                    }
+                    select
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    quote! {
+        #[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, PartialEq, Eq, vespera::Schema)]
+        #[serde(rename_all = #effective_rename_all)]
+        pub enum #sort_by_name {
+            #(#sort_variants),*
+        }
+
+        #[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, vespera::Schema)]
+        #[serde(rename_all = "lowercase")]
+        pub enum #sort_order_name {
+            #[default]
+            Asc,
+            Desc,
+        }
+
+        #[derive(serde::Serialize, serde::Deserialize, Clone, Default, vespera::Schema)]
+        #schema_name_attr
+        #[serde(rename_all = #effective_rename_all)]
+        pub struct #new_type_name {
+            #(#field_tokens,)*
+            /// Column to sort by
+            #[serde(default)]
+            pub sort_by: Option<#sort_by_name>,
+            /// Sort direction (default: `asc`)
+            #[serde(default)]
+            pub sort_order: Option<#sort_order_name>,
+        }
+
+        #apply_impl
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case("i32", FilterKind::Range)]
+    #[case("Option<i64>", FilterKind::Range)]
+    #[case("DateTimeWithTimeZone", FilterKind::Range)]
+    #[case("BigDecimal", FilterKind::Range)]
+    #[case("String", FilterKind::Text)]
+    #[case("Option<String>", FilterKind::Text)]
+    #[case("bool", FilterKind::Eq)]
+    #[case("Uuid", FilterKind::Eq)]
+    #[case("&str", FilterKind::Eq)]
+    fn test_classify_filter_kind(#[case] ty: &str, #[case] expected: FilterKind) {
+        let ty: Type = syn::parse_str(ty).unwrap();
+        assert_eq!(classify_filter_kind(&ty), expected);
+    }
+
+    #[test]
+    fn test_module_item_path() {
+        assert_eq!(module_item_path(&[], "Column").to_string(), "Column");
+        let path = module_item_path(
+            &[
+                "crate".to_string(),
+                "models".to_string(),
+                "user".to_string(),
+            ],
+            "Entity",
+        );
+        assert_eq!(path.to_string(), "crate :: models :: user :: Entity");
+    }
+
+    fn generate(definition: &str, is_seaorm: bool) -> String {
+        let parsed: syn::ItemStruct = syn::parse_str(definition).unwrap();
+        generate_filter_code(
+            &syn::Ident::new("UserFilter", proc_macro2::Span::call_site()),
+            &parsed,
+            &[
+                "crate".to_string(),
+                "models".to_string(),
+                "user".to_string(),
+            ],
+            &HashSet::new(),
+            &HashSet::new(),
+            "camelCase",
+            &quote! {},
+            is_seaorm,
+        )
+        .to_string()
+    }
+
+    #[test]
+    fn test_generate_filter_code_fields_per_kind() {
+        let output = generate(
+            "pub struct Model { pub id: i32, pub name: String, pub active: bool }",
+            false,
+        );
+        assert!(output.contains("pub id : Option < i32 >"));
+        assert!(output.contains("pub id_lt : Option < i32 >"));
+        assert!(output.contains("pub id_gt : Option < i32 >"));
+        assert!(output.contains("pub name_contains : Option < String >"));
+        assert!(!output.contains("name_lt"));
+        assert!(output.contains("pub active : Option < bool >"));
+        assert!(!output.contains("active_lt"));
+        assert!(!output.contains("active_contains"));
+        assert!(output.contains("pub enum UserFilterSortBy"));
+        assert!(output.contains("pub enum UserFilterSortOrder"));
+        assert!(output.contains("pub sort_by : Option < UserFilterSortBy >"));
+        // Not a SeaORM model: no apply_to
+        assert!(!output.contains("apply_to"));
+    }
+
+    #[test]
+    fn test_generate_filter_code_seaorm_apply_to() {
+        let output = generate(
+            "pub struct Model { pub id: i32, pub created_at: DateTimeWithTimeZone, pub user: HasOne<super::user::Entity> }",
+            true,
+        );
+        assert!(output.contains("fn apply_to"));
+        assert!(output.contains("sea_orm :: Select < crate :: models :: user :: Entity >"));
+        assert!(output.contains("crate :: models :: user :: Column :: CreatedAt . gt"));
+        assert!(
+            output.contains("UserFilterSortBy :: Id => crate :: models :: user :: Column :: Id")
+        );
+        assert!(
+            output.contains("vespera :: chrono :: DateTime < vespera :: chrono :: FixedOffset >")
+        );
+        // Relations are not filterable
+        assert!(!output.contains("pub user :"));
+    }
+
+    #[test]
+    fn test_generate_filter_code_respects_omit() {
+        let parsed: syn::ItemStruct =
+            syn::parse_str("pub struct Model { pub id: i32, pub password: String }").unwrap();
+        let omit: HashSet<String> = std::iter::once("password".to_string()).collect();
+        let output = generate_filter_code(
+            &syn::Ident::new("UserFilter", proc_macro2::Span::call_site()),
+            &parsed,
+            &[],
+            &omit,
+            &HashSet::new(),
+            "camelCase",
+            &quote! {},
+            true,
+        )
+        .to_string();
+        assert!(!output.contains("password"));
+        assert!(output.contains("sea_orm :: Select < Entity >"));
+        assert!(output.contains("Column :: Id . eq"));
+    }
+}
@@ -38,11 +38,101 @@ pub fn build_entity_path_from_schema_path(
     quote! { #(#path_idents)::* }
 }
 
+/// Build Column path from Entity path.
+/// e.g., `crate::models::memo::Entity` -> `crate::models::memo::Column`
+fn build_column_path_from_entity_path(entity_path: &TokenStream) -> TokenStream {
+    let entity_path_str = normalize_token_str(entity_path);
+    let path_idents: Vec<syn::Ident> = entity_path_str
+        .split("::")
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            let name = if s == "Entity" { "Column" } else { s };
+            syn::Ident::new(name, proc_macro2::Span::call_site())
+        })
+        .collect();
+
+    quote! { #(#path_idents)::* }
+}
+
+/// Wrap a `HasMany` select in the relation's `#[schema(order_by = ..., limit = ...)]`.
+///
+/// `select` is the query before `.all(db)`. Without either option, or for a count, it is
+/// returned unchanged.
+fn apply_has_many_order_and_limit(
+    rel: &RelationFieldInfo,
+    entity_path: &TokenStream,
+    select: TokenStream,
+) -> TokenStream {
+    if rel.count || (rel.order_by.is_empty() && rel.limit.is_none()) {
+        return select;
+    }
+
+    let column_path = build_column_path_from_entity_path(entity_path);
+    let orders = rel.order_by.iter().map(|(column, descending)| {
+        let column_ident = syn::Ident::new(
+            &snake_to_pascal_case(column),
+            proc_macro2::Span::call_site(),
+        );
+        let order = if *descending {
+            quote! { sea_orm::Order::Desc }
+        } else {
+            quote! { sea_orm::Order::Asc }
+        };
+        quote! { .order_by(#column_path::#column_ident, #order) }
+    });
+    let limit = rel.limit.map(|limit| quote! { .limit(#limit) });
+
+    quote! {
+        {
+            use sea_orm::{QueryOrder as _, QuerySelect as _};
+            #select #(#orders)* #limit
+        }
+    }
+}
+
+/// Load a row of `entity_path` by primary key, through the `ctx` cache of the conversion.
+///
+/// `key` is a reference to the key value, `id` the owned value passed to `find_by_id`.
+fn cached_find_by_id(
+    entity_path: &TokenStream,
+    key: &TokenStream,
+    id: &TokenStream,
+) -> TokenStream {
+    quote! {
+        match ctx.get::<#entity_path, _>(#key) {
+            Some(cached) => cached,
+            None => {
+                let loaded = #entity_path::find_by_id(#id).one(db).await?;
+                ctx.insert::<#entity_path, _>(#key, loaded.clone());
+                loaded
+            }
+        }
+    }
+}
+
+/// Run a `HasMany` select: `.all(db)` for the rows, or a COUNT query for `#[schema(count)]`.
+fn fetch_has_many(rel: &RelationFieldInfo, select: &TokenStream) -> TokenStream {
+    if rel.count {
+        quote! {
+            {
+                use sea_orm::PaginatorTrait as _;
+                #select.count(db).await?
+            }
+        }
+    } else {
+        quote! { #select.all(db).await? }
+    }
+}
+
 /// Generate `from_model` impl for `SeaORM` Model WITH relations (async version).
 ///
 /// When circular references are detected, generates inline struct construction
 /// that excludes circular fields (sets them to default values).
 ///
+/// The body lives in `from_model_with_context`, which loads rows by primary key through
+/// a `vespera::LoaderContext` and passes it on to nested conversions; `from_model` calls
+/// it with a fresh context.
+///
 /// ```ignore
 /// impl NewType {
 ///     pub async fn from_model(
@@ -78,24 +168,23 @@ pub fn generate_from_model_with_relations(
             let field_name = &rel.field_name;
             let entity_path = build_entity_path_from_schema_path(&rel.schema_path, source_module_path);
 
+            // `#[schema(loader = ...)]`: the user function replaces the generated query
+            if let Some(loader) = rel.loader.as_ref().filter(|_| !rel.count) {
+                return quote! {
+                    let #field_name = #loader(&model, db).await?;
+                };
+            }
+
             match rel.relation_type.as_str() {
                 "HasOne" | "BelongsTo" => {
                     // When relation_enum is specified, use the specific Relation variant
-                    // This handles cases where multiple relations point to the same Entity type
-                    if let Some(ref relation_enum_name) = rel.relation_enum {
-                        let relation_variant = syn::Ident::new(relation_enum_name, proc_macro2::Span::call_site());
-
-                        if rel.is_optional {
-                            // Optional FK: load only if FK value exists
-                            if let Some(ref fk_col) = rel.fk_column {
-                                let fk_ident = syn::Ident::new(fk_col, proc_macro2::Span::call_site());
-                                quote! {
-                                    let #field_name = match &model.#fk_ident {
-                                        Some(fk_value) => #entity_path::find_by_id(fk_value.clone()).one(db).await?,
-                                        None => None,
-                                    };
-                                }
-                            } else {
+                    // This handles cases where multiple relations point to the same Entity type.
+                    // Self-relations are always loaded by FK: `find_related` can't tell the
+                    // parent side from the children side of the same table.
+                    if rel.relation_enum.is_some() || rel.is_self_ref {
+                        if rel.fk_columns.is_empty() {
+                            if let Some(ref relation_enum_name) = rel.relation_enum {
+                                let relation_variant = syn::Ident::new(relation_enum_name, proc_macro2::Span::call_site());
                                 // Fallback: use find_related with Relation enum
                                 quote! {
                                     let #field_name = Entity::find_related(Relation::#relation_variant)
@@ -103,23 +192,64 @@ pub fn generate_from_model_with_relations(
                                         .one(db)
                                         .await?;
                                 }
+                            } else {
+                                quote! {
+                                    let #field_name = model.find_related(#entity_path).one(db).await?;
+                                }
                             }
-                        } else {
-                            // Required FK: directly query by FK value
-                            if let Some(ref fk_col) = rel.fk_column {
-                                let fk_ident = syn::Ident::new(fk_col, proc_macro2::Span::call_site());
+                        } else if let [(fk_col, fk_optional)] = rel.fk_columns.as_slice() {
+                            let fk_ident = syn::Ident::new(fk_col, proc_macro2::Span::call_site());
+                            if *fk_optional {
+                                // Optional FK: load only if FK value exists
+                                let find = cached_find_by_id(&entity_path, &quote! { fk_value }, &quote! { fk_value.clone() });
                                 quote! {
-                                    let #field_name = #entity_path::find_by_id(model.#fk_ident.clone()).one(db).await?;
+                                    let #field_name = match &model.#fk_ident {
+                                        Some(fk_value) => #find,
+                                        None => None,
+                                    };
                                 }
                             } else {
-                                // Fallback: use find_related with Relation enum
+                                // Required FK: directly query by FK value
+                                let find = cached_find_by_id(
+                                    &entity_path,
+                                    &quote! { &model.#fk_ident },
+                                    &quote! { model.#fk_ident.clone() },
+                                );
                                 quote! {
-                                    let #field_name = Entity::find_related(Relation::#relation_variant)
-                                        .filter(<Entity as sea_orm::EntityTrait>::PrimaryKey::eq(&model))
-                                        .one(db)
-                                        .await?;
+                                    let #field_name = #find;
                                 }
                             }
+                        } else {
+                            // Composite FK: query by the tuple of FK values, loading only
+                            // if every optional column has a value
+                            let fk_idents: Vec<syn::Ident> = rel
+                                .fk_columns
+                                .iter()
+                                .map(|(col, _)| syn::Ident::new(col, proc_macro2::Span::call_site()))
+                                .collect();
+                            let values: Vec<syn::Ident> = (0..fk_idents.len())
+                                .map(|i| quote::format_ident!("fk_value_{}", i))
+                                .collect();
+                            let patterns: Vec<TokenStream> = rel
+                                .fk_columns
+                                .iter()
+                                .zip(&values)
+                                .map(|((_, optional), value)| {
+                                    if *optional { quote!(Some(#value)) } else { quote!(#value) }
+                                })
+                                .collect();
+                            let find = cached_find_by_id(
+                                &entity_path,
+                                &quote! { &(#(#values,)*) },
+                                &quote! { (#(#values.clone(),)*) },
+                            );
+                            quote! {
+                                let #field_name = match (#(&model.#fk_idents,)*) {
+                                    (#(#patterns,)*) => #find,
+                                    #[allow(unreachable_patterns)]
+                                    _ => None,
+                                };
+                            }
                         }
                     } else {
                         // Standard case: single relation to target entity, use find_related
@@ -129,33 +259,57 @@ pub fn generate_from_model_with_relations(
                     }
                 }
                 "HasMany" => {
-                    // Try via_rel first, fall back to relation_enum as FK source
-                    let fk_rel_source = rel.via_rel.as_ref().or(rel.relation_enum.as_ref());
+                    // Try via_rel first, fall back to relation_enum as FK source.
+                    // Self-relation children reuse the FK declared by the parent side.
+                    let fk_rel_source = rel.via_rel.as_ref().or(rel.relation_enum.as_ref()).or_else(|| {
+                        if rel.is_self_ref {
+                            relation_fields
+                                .iter()
+                                .find(|r| r.is_self_ref && r.relation_type != "HasMany")
+                                .and_then(|r| r.relation_enum.as_ref())
+                        } else {
+                            None
+                        }
+                    });
                     if let Some(via_rel_value) = fk_rel_source {
                         let schema_path_str = normalize_token_str(&rel.schema_path);
-                        if let Some(fk_col_name) = get_fk_column(&schema_path_str, via_rel_value) {
-                            let fk_col_pascal = snake_to_pascal_case(&fk_col_name);
-                            let fk_col_ident = syn::Ident::new(&fk_col_pascal, proc_macro2::Span::call_site());
-
-                            let entity_path_str = normalize_token_str(&entity_path);
-                            let column_path_str = entity_path_str.replace(":: Entity", ":: Column");
-                            let column_path_idents: Vec<syn::Ident> = column_path_str
-                                .split("::")
-                                .filter_map(|s| {
-                                    let trimmed = s.trim();
-                                    if trimmed.is_empty() { None } else { Some(syn::Ident::new(trimmed, proc_macro2::Span::call_site())) }
+                        if let Some(fk_pairs) = get_fk_column(&schema_path_str, via_rel_value) {
+                            let column_path = build_column_path_from_entity_path(&entity_path);
+
+                            // One `target.fk = model.key` condition per FK column
+                            let conditions: Vec<TokenStream> = fk_pairs
+                                .iter()
+                                .map(|(fk_col_name, key_col_name)| {
+                                    let fk_col_pascal = snake_to_pascal_case(fk_col_name);
+                                    let fk_col_ident = syn::Ident::new(&fk_col_pascal, proc_macro2::Span::call_site());
+                                    let key_ident = syn::Ident::new(key_col_name, proc_macro2::Span::call_site());
+                                    quote! {
+                                        #column_path::#fk_col_ident
+                                            .into_column()
+                                            .eq(model.#key_ident.clone())
+                                    }
                                 })
                                 .collect();
-
+                            let condition = if let [condition] = conditions.as_slice() {
+                                quote! { #condition.into_condition() }
+                            } else {
+                                quote! { sea_orm::Condition::all()#(.add(#conditions))* }
+                            };
+
+                            let select = apply_has_many_order_and_limit(
+                                rel,
+                                &entity_path,
+                                quote! { #entity_path::find().filter(#field_name) },
+                            );
+                            let rows = fetch_has_many(rel, &select);
                             quote! {
-                                let #field_name = #(#column_path_idents)::*::#fk_col_ident
-                                    .into_column()
-                                    .eq(model.id.clone())
-                                    .into_condition();
-                                let #field_name = #entity_path::find()
-                                    .filter(#field_name)
-                                    .all(db)
-                                    .await?;
+                                let #field_name = #condition;
+                                let #field_name = #rows;
+                            }
+                        } else if rel.count {
+                            quote! {
+                                // WARNING: Could not find FK column for relation, counting none
+                                let #field_name: u64 = 0;
                             }
                         } else {
                             quote! {
@@ -165,11 +319,61 @@ pub fn generate_from_model_with_relations(
                         }
                     } else {
                         // Standard HasMany - use find_related
+                        let select = apply_has_many_order_and_limit(
+                            rel,
+                            &entity_path,
+                            quote! { model.find_related(#entity_path) },
+                        );
+                        let rows = fetch_has_many(rel, &select);
                         quote! {
-                            let #field_name = model.find_related(#entity_path).all(db).await?;
+                            let #field_name = #rows;
                         }
                     }
                 }
+                "Polymorphic" => {
+                    // Match the type column against each target's tag, then load that
+                    // entity by the id column
+                    let [(type_col, type_optional), (id_col, id_optional)] = rel.fk_columns.as_slice() else {
+                        return quote! {};
+                    };
+                    let type_ident = syn::Ident::new(type_col, proc_macro2::Span::call_site());
+                    let id_ident = syn::Ident::new(id_col, proc_macro2::Span::call_site());
+                    let enum_path = &rel.schema_path;
+                    let discriminant = if *type_optional {
+                        quote! { model.#type_ident.as_deref() }
+                    } else {
+                        quote! { model.#type_ident.as_str() }
+                    };
+                    let id_pattern = if *id_optional { quote! { Some(id) } } else { quote! { id } };
+                    let arms = rel.polymorphic_targets.iter().map(|(tag, target_schema)| {
+                        let tag_pattern = if *type_optional { quote! { Some(#tag) } } else { quote! { #tag } };
+                        let variant = syn::Ident::new(&snake_to_pascal_case(tag), proc_macro2::Span::call_site());
+                        let target_entity = build_entity_path_from_schema_path(target_schema, source_module_path);
+                        // A target with FK relations is built by its own async from_model()
+                        let model_path_str = normalize_token_str(target_schema).replace("::Schema", "::Model");
+                        let target_has_fk = get_struct_from_schema_path(&model_path_str).is_some_and(|model| {
+                            get_circular_analysis(source_module_path, &model.definition).has_fk_relations
+                        });
+                        let convert = if target_has_fk {
+                            quote! { #target_schema::from_model_with_context(r, db, ctx).await? }
+                        } else {
+                            quote! { <#target_schema as From<_>>::from(r) }
+                        };
+                        let find = cached_find_by_id(&target_entity, &quote! { id }, &quote! { id.clone() });
+                        quote! {
+                            (#tag_pattern, #id_pattern) => match #find {
+                                Some(r) => Some(#enum_path::#variant(Box::new(#convert))),
+                                None => None,
+                            },
+                        }
+                    });
+                    quote! {
+                        let #field_name = match (#discriminant, &model.#id_ident) {
+                            #(#arms)*
+                            _ => None,
+                        };
+                    }
+                }
                 _ => quote! {},
             }
         })
@@ -179,7 +383,7 @@ pub fn generate_from_model_with_relations(
     // This is needed when: UserSchema.memos has MemoSchema which has required user: Box<UserSchema>
     // BUT: If the relation uses an inline type (which excludes circular fields), we don't need a parent stub
     let needs_parent_stub = relation_fields.iter().any(|rel| {
-        if rel.relation_type != "HasMany" {
+        if rel.relation_type != "HasMany" || rel.count {
             return false;
         }
         // If using inline type, circular fields are excluded, so no parent stub needed
@@ -217,6 +421,7 @@ pub fn generate_from_model_with_relations(
                         .find(|r| &r.field_name == source_ident)
                     {
                         match rel.relation_type.as_str() {
+                            "HasMany" if rel.count => quote! { #new_ident: #source_ident },
                             "HasMany" => quote! { #new_ident: vec![] },
                             _ if rel.is_optional => quote! { #new_ident: None },
                             // Required single relations in parent stub - this shouldn't happen
@@ -250,6 +455,23 @@ pub fn generate_from_model_with_relations(
             if *is_relation {
                 // Find the relation info for this field
                 if let Some(rel) = relation_by_name.get(source_ident) {
+                    // Polymorphic relation: the load already built the target enum,
+                    // a counted relation the number of rows
+                    if rel.relation_type == "Polymorphic" || rel.count {
+                        return quote! { #new_ident: #source_ident };
+                    }
+
+                    // Self-relation parent: recurse into this same impl, loading the
+                    // ancestor chain up to the root (whose FK is empty)
+                    if rel.is_self_ref && rel.relation_type != "HasMany" {
+                        return quote! {
+                            #new_ident: match #source_ident {
+                                Some(r) => Some(Box::new(Box::pin(Self::from_model_with_context(r, db, ctx)).await?)),
+                                None => None,
+                            }
+                        };
+                    }
+
                     let schema_path = &rel.schema_path;
 
                     // Try to find the related MODEL definition to check for circular refs
@@ -332,17 +554,18 @@ pub fn generate_from_model_with_relations(
                                         if rel.is_optional {
                                             quote! {
                                                 #new_ident: match #source_ident {
-                                                    Some(r) => Some(Box::new(#schema_path::from_model(r, db).await?)),
+                                                    Some(r) => Some(Box::new(#schema_path::from_model_with_context(r, db, ctx).await?)),
                                                     None => None,
                                                 }
                                             }
                                         } else {
                                             quote! {
-                                                #new_ident: Box::new(#schema_path::from_model(
+                                                #new_ident: Box::new(#schema_path::from_model_with_context(
                                                     #source_ident.ok_or_else(|| sea_orm::DbErr::RecordNotFound(
                                                         format!("Required relation '{}' not found", stringify!(#source_ident))
                                                     ))?,
                                                     db,
+                                                    ctx,
                                                 ).await?)
                                             }
                                         }
@@ -429,6 +652,16 @@ pub fn generate_from_model_with_relations(
             pub async fn from_model(
                 model: #source_type,
                 db: &sea_orm::DatabaseConnection,
+            ) -> Result<Self, sea_orm::DbErr> {
+                Self::from_model_with_context(model, db, &mut vespera::LoaderContext::new()).await
+            }
+
+            /// Like `from_model`, sharing rows loaded by primary key through `ctx`.
+            #[allow(unused_variables)]
+            pub async fn from_model_with_context(
+                model: #source_type,
+                db: &sea_orm::DatabaseConnection,
+                ctx: &mut vespera::LoaderContext,
             ) -> Result<Self, sea_orm::DbErr> {
                 use sea_orm::ModelTrait;
 
@@ -446,6 +679,7 @@ pub fn generate_from_model_with_relations(
 
 #[cfg(test)]
 mod tests {
+    use rstest::rstest;
     use serial_test::serial;
 
     use super::*;
@@ -507,8 +741,14 @@ mod tests {
             is_optional,
             inline_type_info: None,
             relation_enum: None,
-            fk_column: None,
+            fk_columns: Vec::new(),
             via_rel: None,
+            is_self_ref: false,
+            polymorphic_targets: Vec::new(),
+            loader: None,
+            order_by: Vec::new(),
+            limit: None,
+            count: false,
         }
     }
 
@@ -1356,7 +1596,7 @@ pub struct Model {
         assert!(output.contains("impl UserSchema"));
         // Non-circular with FK, optional should have match statement with async from_model
         assert!(
-            output.contains("from_model (r , db) . await"),
+            output.contains("from_model_with_context (r , db , ctx) . await"),
             "Should have async from_model: {output}"
         );
     }
@@ -1896,7 +2136,7 @@ pub struct Model {
         schema_path: TokenStream,
         is_optional: bool,
         relation_enum: Option<String>,
-        fk_column: Option<String>,
+        fk_columns: Vec<(String, bool)>,
         via_rel: Option<String>,
     ) -> RelationFieldInfo {
         RelationFieldInfo {
@@ -1906,8 +2146,14 @@ pub struct Model {
             is_optional,
             inline_type_info: None,
             relation_enum,
-            fk_column,
+            fk_columns,
             via_rel,
+            is_self_ref: false,
+            polymorphic_targets: Vec::new(),
+            loader: None,
+            order_by: Vec::new(),
+            limit: None,
+            count: false,
         }
     }
 
@@ -1938,10 +2184,10 @@ pub struct Model {
             "target_user",
             "HasOne",
             quote! { user::Schema },
-            true,                               // optional
-            Some("TargetUser".to_string()),     // relation_enum
-            Some("target_user_id".to_string()), // fk_column
-            None,                               // via_rel
+            true,                                       // optional
+            Some("TargetUser".to_string()),             // relation_enum
+            vec![("target_user_id".to_string(), true)], // fk_columns
+            None,                                       // via_rel
         )];
 
         let source_module_path = vec![
@@ -1975,6 +2221,286 @@ pub struct Model {
         );
     }
 
+    #[rstest]
+    #[case(true, "(Some (fk_value_0) , fk_value_1 ,)")]
+    #[case(false, "(fk_value_0 , fk_value_1 ,)")]
+    fn test_generate_from_model_belongs_to_composite_fk(
+        #[case] optional: bool,
+        #[case] pattern: &str,
+    ) {
+        let new_type_name = syn::Ident::new("MembershipSchema", proc_macro2::Span::call_site());
+        let source_type: Type = syn::parse_str("Model").unwrap();
+        let field_mappings = vec![(
+            syn::Ident::new("user", proc_macro2::Span::call_site()),
+            syn::Ident::new("user", proc_macro2::Span::call_site()),
+            false,
+            true,
+        )];
+        let relation_fields = vec![create_test_relation_info_full(
+            "user",
+            "BelongsTo",
+            quote! { user::Schema },
+            optional,
+            Some("User".to_string()),
+            vec![
+                ("tenant_id".to_string(), optional),
+                ("user_id".to_string(), false),
+            ],
+            None,
+        )];
+
+        let output = generate_from_model_with_relations(
+            &new_type_name,
+            &source_type,
+            &field_mappings,
+            &relation_fields,
+            &["crate".to_string(), "models".to_string()],
+            &HashMap::new(),
+        )
+        .to_string();
+
+        assert!(
+            output.contains("match (& model . tenant_id , & model . user_id ,)"),
+            "Should match on every FK column: {output}"
+        );
+        assert!(output.contains(pattern), "Should bind FK values: {output}");
+        assert!(
+            output.contains("find_by_id ((fk_value_0 . clone () , fk_value_1 . clone () ,))"),
+            "Should query by the composite key: {output}"
+        );
+    }
+
+    #[test]
+    fn test_generate_from_model_belongs_to_cached_by_fk() {
+        let new_type_name = syn::Ident::new("MemoSchema", proc_macro2::Span::call_site());
+        let source_type: Type = syn::parse_str("Model").unwrap();
+        let field_mappings = vec![(
+            syn::Ident::new("user", proc_macro2::Span::call_site()),
+            syn::Ident::new("user", proc_macro2::Span::call_site()),
+            false,
+            true,
+        )];
+        let relation_fields = vec![create_test_relation_info_full(
+            "user",
+            "BelongsTo",
+            quote! { user::Schema },
+            false,
+            Some("User".to_string()),
+            vec![("user_id".to_string(), false)],
+            None,
+        )];
+
+        let output = generate_from_model_with_relations(
+            &new_type_name,
+            &source_type,
+            &field_mappings,
+            &relation_fields,
+            &["crate".to_string(), "models".to_string()],
+            &HashMap::new(),
+        )
+        .to_string();
+
+        assert!(
+            output.contains(
+                "Self :: from_model_with_context (model , db , & mut vespera :: LoaderContext :: new ()) . await"
+            ),
+            "from_model should start a fresh context: {output}"
+        );
+        assert!(
+            output.contains("ctx : & mut vespera :: LoaderContext"),
+            "{output}"
+        );
+        assert!(
+            output.contains("match ctx . get :: < user :: Entity , _ > (& model . user_id)"),
+            "Should look the row up in the context first: {output}"
+        );
+        assert!(
+            output.contains(
+                "ctx . insert :: < user :: Entity , _ > (& model . user_id , loaded . clone ())"
+            ),
+            "Should cache the loaded row: {output}"
+        );
+    }
+
+    #[test]
+    fn test_generate_from_model_self_ref_parent() {
+        let new_type_name = syn::Ident::new("CategorySchema", proc_macro2::Span::call_site());
+        let source_type: Type = syn::parse_str("Model").unwrap();
+        let field_mappings = vec![(
+            syn::Ident::new("parent", proc_macro2::Span::call_site()),
+            syn::Ident::new("parent", proc_macro2::Span::call_site()),
+            false,
+            true,
+        )];
+        let mut parent = create_test_relation_info_full(
+            "parent",
+            "BelongsTo",
+            quote! { crate::models::category::Schema },
+            true,
+            None,
+            vec![("parent_id".to_string(), true)],
+            None,
+        );
+        parent.is_self_ref = true;
+
+        let output = generate_from_model_with_relations(
+            &new_type_name,
+            &source_type,
+            &field_mappings,
+            &[parent],
+            &[
+                "crate".to_string(),
+                "models".to_string(),
+                "category".to_string(),
+            ],
+            &HashMap::new(),
+        )
+        .to_string();
+
+        assert!(
+            output.contains("match & model . parent_id"),
+            "Should load the parent by FK instead of find_related: {output}"
+        );
+        assert!(!output.contains("find_related"), "{output}");
+        assert!(
+            output
+                .contains("Box :: pin (Self :: from_model_with_context (r , db , ctx)) . await ?"),
+            "Should recurse into the same impl for the parent: {output}"
+        );
+    }
+
+    #[rstest]
+    #[case("HasMany", "memos", quote! { crate::models::memo::Schema })]
+    #[case("BelongsTo", "user", quote! { crate::models::user::Schema })]
+    fn test_generate_from_model_custom_loader(
+        #[case] relation_type: &str,
+        #[case] field: &str,
+        #[case] schema_path: TokenStream,
+    ) {
+        let new_type_name = syn::Ident::new("Schema", proc_macro2::Span::call_site());
+        let source_type: Type = syn::parse_str("Model").unwrap();
+        let field_ident = syn::Ident::new(field, proc_macro2::Span::call_site());
+        let field_mappings = vec![(field_ident.clone(), field_ident, false, true)];
+        let mut rel = create_test_relation_info_full(
+            field,
+            relation_type,
+            schema_path,
+            true,
+            Some("Target".to_string()),
+            vec![("user_id".to_string(), true)],
+            Some("Target".to_string()),
+        );
+        rel.loader = Some(syn::parse_str("crate::loaders::load_active").unwrap());
+
+        let output = generate_from_model_with_relations(
+            &new_type_name,
+            &source_type,
+            &field_mappings,
+            &[rel],
+            &["crate".to_string(), "models".to_string()],
+            &HashMap::new(),
+        )
+        .to_string();
+
+        assert!(
+            output.contains(&format!(
+                "let {field} = crate :: loaders :: load_active (& model , db) . await ?"
+            )),
+            "Should call the custom loader: {output}"
+        );
+        assert!(!output.contains("find_related"), "{output}");
+        assert!(!output.contains("find_by_id"), "{output}");
+    }
+
+    #[test]
+    fn test_generate_from_model_has_many_order_by_and_limit() {
+        let new_type_name = syn::Ident::new("Schema", proc_macro2::Span::call_site());
+        let source_type: Type = syn::parse_str("Model").unwrap();
+        let field_ident = syn::Ident::new("memos", proc_macro2::Span::call_site());
+        let field_mappings = vec![(field_ident.clone(), field_ident, false, true)];
+        let mut rel = create_test_relation_info_full(
+            "memos",
+            "HasMany",
+            quote! { crate::models::memo::Schema },
+            false,
+            None,
+            vec![],
+            None,
+        );
+        rel.order_by = vec![("created_at".to_string(), true), ("id".to_string(), false)];
+        rel.limit = Some(20);
+
+        let output = generate_from_model_with_relations(
+            &new_type_name,
+            &source_type,
+            &field_mappings,
+            &[rel],
+            &["crate".to_string(), "models".to_string()],
+            &HashMap::new(),
+        )
+        .to_string();
+
+        assert!(
+            output.contains("use sea_orm :: { QueryOrder as _ , QuerySelect as _ }"),
+            "{output}"
+        );
+        assert!(
+            output.contains(
+                "model . find_related (crate :: models :: memo :: Entity) \
+                 . order_by (crate :: models :: memo :: Column :: CreatedAt , sea_orm :: Order :: Desc) \
+                 . order_by (crate :: models :: memo :: Column :: Id , sea_orm :: Order :: Asc) \
+                 . limit (20u64)"
+            ),
+            "Should order and limit the HasMany query: {output}"
+        );
+    }
+
+    #[test]
+    fn test_generate_from_model_has_many_count() {
+        let new_type_name = syn::Ident::new("Schema", proc_macro2::Span::call_site());
+        let source_type: Type = syn::parse_str("Model").unwrap();
+        let field_mappings = vec![(
+            syn::Ident::new("memos_count", proc_macro2::Span::call_site()),
+            syn::Ident::new("memos", proc_macro2::Span::call_site()),
+            false,
+            true,
+        )];
+        let mut rel = create_test_relation_info_full(
+            "memos",
+            "HasMany",
+            quote! { crate::models::memo::Schema },
+            false,
+            None,
+            vec![],
+            None,
+        );
+        rel.count = true;
+        rel.limit = Some(20);
+
+        let output = generate_from_model_with_relations(
+            &new_type_name,
+            &source_type,
+            &field_mappings,
+            &[rel],
+            &["crate".to_string(), "models".to_string()],
+            &HashMap::new(),
+        )
+        .to_string();
+
+        assert!(
+            output.contains("use sea_orm :: PaginatorTrait as _"),
+            "{output}"
+        );
+        assert!(
+            output
+                .contains("model . find_related (crate :: models :: memo :: Entity) . count (db)"),
+            "Should count instead of loading rows: {output}"
+        );
+        assert!(output.contains("memos_count : memos"), "{output}");
+        assert!(!output.contains(". all (db)"), "{output}");
+        assert!(!output.contains("limit"), "{output}");
+    }
+
     #[test]
     fn test_generate_from_model_has_one_with_relation_enum_optional_no_fk() {
         // Tests for None branch
@@ -2004,7 +2530,7 @@ pub struct Model {
             quote! { user::Schema },
             true,                       // optional
             Some("Author".to_string()), // relation_enum
-            None,                       // NO fk_column
+            Vec::new(),                 // NO fk_columns
             None,                       // via_rel
         )];
 
@@ -2058,10 +2584,10 @@ pub struct Model {
             "post",
             "BelongsTo",
             quote! { post::Schema },
-            false,                       // required
-            Some("Post".to_string()),    // relation_enum
-            Some("post_id".to_string()), // fk_column
-            None,                        // via_rel
+            false,                                // required
+            Some("Post".to_string()),             // relation_enum
+            vec![("post_id".to_string(), false)], // fk_columns
+            None,                                 // via_rel
         )];
 
         let source_module_path = vec![
@@ -2116,7 +2642,7 @@ pub struct Model {
             quote! { user::Schema },
             false,                      // required
             Some("Author".to_string()), // relation_enum
-            None,                       // NO fk_column
+            Vec::new(),                 // NO fk_columns
             None,                       // via_rel
         )];
 
@@ -2199,7 +2725,7 @@ pub struct Model {
             quote! { crate::models::notification::Schema },
             false,
             None,
-            None,
+            vec![],
             Some("TargetUser".to_string()), // via_rel
         )];
 
@@ -2295,7 +2821,7 @@ pub struct Model {
             quote! { crate::models::notification::Schema },
             false,
             None,
-            None,
+            vec![],
             Some("NonExistentRelation".to_string()), // via_rel that won't match
         )];
 
@@ -2382,7 +2908,7 @@ pub struct Model {
             quote! { crate::models::comment::Schema },
             false,
             Some("AuthorComments".to_string()), // relation_enum
-            None,
+            vec![],
             None, // NO via_rel - will use relation_enum as via_rel
         )];
 
@@ -2474,7 +3000,7 @@ pub struct Model {
             quote! { crate::models::post::Schema },
             false,
             Some("NonExistentRelation".to_string()), // relation_enum that won't match
-            None,
+            vec![],
             None, // NO via_rel
         )];
 
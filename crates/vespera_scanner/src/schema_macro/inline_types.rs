@@ -445,8 +445,14 @@ mod tests {
             is_optional: false,
             inline_type_info: None,
             relation_enum: None,
-            fk_column: None,
+            fk_columns: Vec::new(),
             via_rel: None,
+            is_self_ref: false,
+            polymorphic_targets: Vec::new(),
+            loader: None,
+            order_by: Vec::new(),
+            limit: None,
+            count: false,
         };
         let source_module_path = vec![
             "crate".to_string(),
@@ -511,8 +517,14 @@ mod tests {
             is_optional: false,
             inline_type_info: None,
             relation_enum: None,
-            fk_column: None,
+            fk_columns: Vec::new(),
             via_rel: None,
+            is_self_ref: false,
+            polymorphic_targets: Vec::new(),
+            loader: None,
+            order_by: Vec::new(),
+            limit: None,
+            count: false,
         };
         let source_module_path = vec![
             "crate".to_string(),
@@ -546,8 +558,14 @@ mod tests {
             is_optional: false,
             inline_type_info: None,
             relation_enum: None,
-            fk_column: None,
+            fk_columns: Vec::new(),
             via_rel: None,
+            is_self_ref: false,
+            polymorphic_targets: Vec::new(),
+            loader: None,
+            order_by: Vec::new(),
+            limit: None,
+            count: false,
         };
         let source_module_path = vec![
             "crate".to_string(),
@@ -582,8 +600,14 @@ mod tests {
             is_optional: false,
             inline_type_info: None,
             relation_enum: None,
-            fk_column: None,
+            fk_columns: Vec::new(),
             via_rel: None,
+            is_self_ref: false,
+            polymorphic_targets: Vec::new(),
+            loader: None,
+            order_by: Vec::new(),
+            limit: None,
+            count: false,
         };
 
         // Model with relations that should be stripped
@@ -628,8 +652,14 @@ mod tests {
             is_optional: false,
             inline_type_info: None,
             relation_enum: None,
-            fk_column: None,
+            fk_columns: Vec::new(),
             via_rel: None,
+            is_self_ref: false,
+            polymorphic_targets: Vec::new(),
+            loader: None,
+            order_by: Vec::new(),
+            limit: None,
+            count: false,
         };
 
         // Model with serde(skip) field
@@ -670,8 +700,14 @@ mod tests {
             is_optional: false,
             inline_type_info: None,
             relation_enum: None,
-            fk_column: None,
+            fk_columns: Vec::new(),
             via_rel: None,
+            is_self_ref: false,
+            polymorphic_targets: Vec::new(),
+            loader: None,
+            order_by: Vec::new(),
+            limit: None,
+            count: false,
         };
         let source_module_path = vec!["crate".to_string()];
 
@@ -696,8 +732,14 @@ mod tests {
             is_optional: false,
             inline_type_info: None,
             relation_enum: None,
-            fk_column: None,
+            fk_columns: Vec::new(),
             via_rel: None,
+            is_self_ref: false,
+            polymorphic_targets: Vec::new(),
+            loader: None,
+            order_by: Vec::new(),
+            limit: None,
+            count: false,
         };
         let source_module_path = vec![
             "crate".to_string(),
@@ -750,8 +792,14 @@ mod tests {
             is_optional: false,
             inline_type_info: None,
             relation_enum: None,
-            fk_column: None,
+            fk_columns: Vec::new(),
             via_rel: None,
+            is_self_ref: false,
+            polymorphic_targets: Vec::new(),
+            loader: None,
+            order_by: Vec::new(),
+            limit: None,
+            count: false,
         };
         let source_module_path = vec![
             "crate".to_string(),
@@ -803,8 +851,14 @@ mod tests {
             is_optional: false,
             inline_type_info: None,
             relation_enum: None,
-            fk_column: None,
+            fk_columns: Vec::new(),
             via_rel: None,
+            is_self_ref: false,
+            polymorphic_targets: Vec::new(),
+            loader: None,
+            order_by: Vec::new(),
+            limit: None,
+            count: false,
         };
 
         let model_def = r"pub struct Model {
@@ -865,8 +919,14 @@ pub struct Model {
             is_optional: false,
             inline_type_info: None,
             relation_enum: None,
-            fk_column: None,
+            fk_columns: Vec::new(),
             via_rel: None,
+            is_self_ref: false,
+            polymorphic_targets: Vec::new(),
+            loader: None,
+            order_by: Vec::new(),
+            limit: None,
+            count: false,
         };
         let source_module_path = vec![
             "crate".to_string(),
@@ -939,8 +999,14 @@ pub struct Model {
             is_optional: false,
             inline_type_info: None,
             relation_enum: None,
-            fk_column: None,
+            fk_columns: Vec::new(),
             via_rel: None,
+            is_self_ref: false,
+            polymorphic_targets: Vec::new(),
+            loader: None,
+            order_by: Vec::new(),
+            limit: None,
+            count: false,
         };
 
         let source_module_path = vec![
@@ -1005,8 +1071,14 @@ pub struct Model {
             is_optional: false,
             inline_type_info: None,
             relation_enum: None,
-            fk_column: None,
+            fk_columns: Vec::new(),
             via_rel: None,
+            is_self_ref: false,
+            polymorphic_targets: Vec::new(),
+            loader: None,
+            order_by: Vec::new(),
+            limit: None,
+            count: false,
         };
         let source_module_path = vec!["crate".to_string()];
 
@@ -1050,8 +1122,14 @@ pub struct Model {
             is_optional: false,
             inline_type_info: None,
             relation_enum: None,
-            fk_column: None,
+            fk_columns: Vec::new(),
             via_rel: None,
+            is_self_ref: false,
+            polymorphic_targets: Vec::new(),
+            loader: None,
+            order_by: Vec::new(),
+            limit: None,
+            count: false,
         };
 
         let result =
@@ -1082,8 +1160,14 @@ pub struct Model {
             is_optional: false,
             inline_type_info: None,
             relation_enum: None,
-            fk_column: None,
+            fk_columns: Vec::new(),
             via_rel: None,
+            is_self_ref: false,
+            polymorphic_targets: Vec::new(),
+            loader: None,
+            order_by: Vec::new(),
+            limit: None,
+            count: false,
         };
         let source_module_path = vec![
             "crate".to_string(),
@@ -1141,8 +1225,14 @@ pub struct Model {
             is_optional: false,
             inline_type_info: None,
             relation_enum: None,
-            fk_column: None,
+            fk_columns: Vec::new(),
             via_rel: None,
+            is_self_ref: false,
+            polymorphic_targets: Vec::new(),
+            loader: None,
+            order_by: Vec::new(),
+            limit: None,
+            count: false,
         };
 
         // Model with DateTimeWithTimeZone field
@@ -90,6 +90,9 @@ impl Parse for SchemaInput {
 /// Or:     `schema_type!(NewTypeName from SourceType, ignore)` - skip Schema derive
 /// Or:     `schema_type!(NewTypeName from SourceType, name = "CustomName")` - custom `OpenAPI` name
 /// Or:     `schema_type!(NewTypeName from SourceType, rename_all = "camelCase")` - serde `rename_all`
+/// Or:     `schema_type!(NewTypeName from SourceType, filter)` - filter/sort query struct
+/// Or:     `schema_type!(NewTypeName from Model, bulk)` - `SeaORM` bulk create/update helpers
+/// Or:     `schema_type!(NewTypeName from SourceType, polymorphic = [("target": "target_type", "target_id", [("post": super::post::Entity)])])`
 #[allow(clippy::struct_excessive_bools)]
 pub struct SchemaTypeInput {
     /// The new type name to generate
@@ -127,6 +130,61 @@ pub struct SchemaTypeInput {
     /// Whether to omit fields that have database defaults (sea_orm `default_value` or `primary_key`).
     /// Use `omit_default` bare keyword to set this to true.
     pub omit_default: bool,
+    /// Whether to generate a filter/sort query struct (eq/lt/gt/contains per column + `sort_by`).
+    /// Use `filter` bare keyword to set this to true.
+    pub filter: bool,
+    /// Whether to generate `bulk_create`/`bulk_update` for a `SeaORM` Model source.
+    /// Use `bulk` bare keyword to set this to true.
+    pub bulk: bool,
+    /// Relations to one of several entities, picked by a discriminator column.
+    pub polymorphic: Option<Vec<PolymorphicRelation>>,
+    /// Submodule the generated items are emitted into, glob re-exported from the call site.
+    /// Use `module = "schemas"` to set this.
+    pub module: Option<Ident>,
+}
+
+/// A polymorphic relation: `("field": "type_column", "id_column", [("tag": Entity), ...])`.
+///
+/// The row of `id_column` is loaded from the entity whose tag matches `type_column`.
+#[derive(Clone)]
+pub struct PolymorphicRelation {
+    /// Field name in the generated struct
+    pub field_name: String,
+    /// Discriminator column holding the tag of the target entity
+    pub type_column: String,
+    /// Column holding the primary key of the target row
+    pub id_column: String,
+    /// Possible targets: (tag stored in `type_column`, `Entity` path relative to the source model)
+    pub targets: Vec<(String, Type)>,
+}
+
+impl Parse for PolymorphicRelation {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let content;
+        parenthesized!(content in input);
+        let field_name: LitStr = content.parse()?;
+        content.parse::<Token![:]>()?;
+        let type_column: LitStr = content.parse()?;
+        content.parse::<Token![,]>()?;
+        let id_column: LitStr = content.parse()?;
+        content.parse::<Token![,]>()?;
+        let targets_content;
+        let _ = bracketed!(targets_content in content);
+        let targets: Punctuated<AddField, Token![,]> =
+            targets_content.parse_terminated(AddField::parse, Token![,])?;
+        if targets.is_empty() {
+            return Err(syn::Error::new(
+                field_name.span(),
+                "schema_type! macro: `polymorphic` relation needs at least one target, e.g. `[(\"post\": super::post::Entity)]`.",
+            ));
+        }
+        Ok(Self {
+            field_name: field_name.value(),
+            type_column: type_column.value(),
+            id_column: id_column.value(),
+            targets: targets.into_iter().map(|t| (t.name, t.ty)).collect(),
+        })
+    }
 }
 
 /// Mode for the `partial` keyword in `schema_type`!
@@ -209,6 +267,10 @@ impl Parse for SchemaTypeInput {
         let mut rename_all = None;
         let mut multipart = false;
         let mut omit_default = false;
+        let mut filter = false;
+        let mut bulk = false;
+        let mut polymorphic = None;
+        let mut module = None;
 
         // Parse optional parameters
         while input.peek(Token![,]) {
@@ -302,11 +364,41 @@ impl Parse for SchemaTypeInput {
                     // bare `omit_default` - omit fields with database defaults
                     omit_default = true;
                 }
+                "filter" => {
+                    // bare `filter` - generate filter/sort query struct
+                    filter = true;
+                }
+                "bulk" => {
+                    // bare `bulk` - generate SeaORM bulk create/update helpers
+                    bulk = true;
+                }
+                "polymorphic" => {
+                    input.parse::<Token![=]>()?;
+                    let content;
+                    let _ = bracketed!(content in input);
+                    let relations: Punctuated<PolymorphicRelation, Token![,]> =
+                        content.parse_terminated(PolymorphicRelation::parse, Token![,])?;
+                    polymorphic = Some(relations.into_iter().collect());
+                }
+                "module" => {
+                    // module = "schemas" - emit the generated items into `pub mod schemas`
+                    input.parse::<Token![=]>()?;
+                    let module_lit: LitStr = input.parse()?;
+                    module = Some(syn::parse_str::<Ident>(&module_lit.value()).map_err(|_| {
+                        syn::Error::new(
+                            module_lit.span(),
+                            format!(
+                                "`module` must be a module name, got `{}`",
+                                module_lit.value()
+                            ),
+                        )
+                    })?);
+                }
                 _ => {
                     return Err(syn::Error::new(
                         ident.span(),
                         format!(
-                            "unknown parameter: `{ident_str}`. Expected `omit`, `pick`, `rename`, `add`, `clone`, `partial`, `ignore`, `name`, `rename_all`, `multipart`, or `omit_default`"
+                            "unknown parameter: `{ident_str}`. Expected `omit`, `pick`, `rename`, `add`, `clone`, `partial`, `ignore`, `name`, `rename_all`, `multipart`, `omit_default`, `filter`, `bulk`, `polymorphic`, or `module`"
                         ),
                     ));
                 }
@@ -321,6 +413,30 @@ impl Parse for SchemaTypeInput {
             ));
         }
 
+        // Validate: filter generates its own field set
+        if filter && (multipart || partial.is_some() || add.is_some() || rename.is_some()) {
+            return Err(syn::Error::new(
+                input.span(),
+                "schema_type! macro: `filter` cannot be combined with `multipart`, `partial`, `add`, or `rename`. Use `pick`/`omit` to choose the filterable columns.",
+            ));
+        }
+
+        // Validate: bulk helpers convert the generated struct into the source's ActiveModel
+        if bulk && (filter || multipart || add.is_some()) {
+            return Err(syn::Error::new(
+                input.span(),
+                "schema_type! macro: `bulk` cannot be combined with `filter`, `multipart`, or `add`.",
+            ));
+        }
+
+        // Validate: polymorphic relations are loaded by `from_model`, which `multipart` skips
+        if polymorphic.is_some() && (filter || multipart) {
+            return Err(syn::Error::new(
+                input.span(),
+                "schema_type! macro: `polymorphic` cannot be combined with `filter` or `multipart`.",
+            ));
+        }
+
         Ok(Self {
             new_type,
             source_type,
@@ -335,12 +451,18 @@ impl Parse for SchemaTypeInput {
             rename_all,
             multipart,
             omit_default,
+            filter,
+            bulk,
+            polymorphic,
+            module,
         })
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use rstest::rstest;
+
     use super::*;
 
     #[test]
@@ -733,6 +855,96 @@ mod tests {
         assert_eq!(input.pick.unwrap(), vec!["name", "email"]);
     }
 
+    #[test]
+    fn test_parse_schema_type_input_with_filter() {
+        let tokens = quote::quote!(UserFilter from Model, filter, omit = ["password"]);
+        let input: SchemaTypeInput = syn::parse2(tokens).unwrap();
+        assert!(input.filter);
+        assert_eq!(input.omit.unwrap(), vec!["password"]);
+    }
+
+    #[test]
+    fn test_parse_schema_type_input_with_bulk() {
+        let tokens = quote::quote!(CreateUser from Model, bulk, omit_default);
+        let input: SchemaTypeInput = syn::parse2(tokens).unwrap();
+        assert!(input.bulk);
+        assert!(input.omit_default);
+    }
+
+    #[test]
+    fn test_parse_schema_type_input_bulk_with_filter_error() {
+        let tokens = quote::quote!(UserFilter from Model, filter, bulk);
+        let Err(e) = syn::parse2::<SchemaTypeInput>(tokens) else {
+            panic!("expected error");
+        };
+        assert!(e.to_string().contains("`bulk` cannot be combined"));
+    }
+
+    #[test]
+    fn test_parse_schema_type_input_filter_with_partial_error() {
+        let tokens = quote::quote!(UserFilter from Model, filter, partial);
+        let result: syn::Result<SchemaTypeInput> = syn::parse2(tokens);
+        assert!(result.is_err());
+        if let Err(e) = result {
+            assert!(e.to_string().contains("`filter` cannot be combined"));
+        }
+    }
+
+    #[test]
+    fn test_parse_schema_type_input_with_polymorphic() {
+        let tokens = quote::quote!(
+            CommentSchema from Model,
+            polymorphic = [("target": "target_type", "target_id", [
+                ("post": super::post::Entity),
+                ("photo": super::photo::Entity),
+            ])]
+        );
+        let input: SchemaTypeInput = syn::parse2(tokens).unwrap();
+        let relations = input.polymorphic.unwrap();
+        assert_eq!(relations.len(), 1);
+        assert_eq!(relations[0].field_name, "target");
+        assert_eq!(relations[0].type_column, "target_type");
+        assert_eq!(relations[0].id_column, "target_id");
+        let tags: Vec<&str> = relations[0]
+            .targets
+            .iter()
+            .map(|(tag, _)| tag.as_str())
+            .collect();
+        assert_eq!(tags, ["post", "photo"]);
+    }
+
+    #[rstest]
+    #[case(quote::quote!(C from Model, polymorphic = [("target": "target_type", "target_id", [])]), "at least one target")]
+    #[case(quote::quote!(C from Model, multipart, polymorphic = [("target": "t", "i", [("post": post::Entity)])]), "`polymorphic` cannot be combined")]
+    fn test_parse_schema_type_input_polymorphic_errors(
+        #[case] tokens: proc_macro2::TokenStream,
+        #[case] message: &str,
+    ) {
+        let Err(error) = syn::parse2::<SchemaTypeInput>(tokens) else {
+            panic!("expected an error");
+        };
+        assert!(error.to_string().contains(message), "{error}");
+    }
+
+    #[test]
+    fn test_parse_schema_type_input_with_module() {
+        let tokens = quote::quote!(UserSchema from Model, module = "schemas");
+        let input: SchemaTypeInput = syn::parse2(tokens).unwrap();
+        assert_eq!(input.module.unwrap().to_string(), "schemas");
+    }
+
+    #[test]
+    fn test_parse_schema_type_input_module_not_an_ident() {
+        let tokens = quote::quote!(UserSchema from Model, module = "api::schemas");
+        let Err(error) = syn::parse2::<SchemaTypeInput>(tokens) else {
+            panic!("expected an error");
+        };
+        assert!(
+            error.to_string().contains("`module` must be a module name"),
+            "{error}"
+        );
+    }
+
     #[test]
     fn test_parse_schema_type_input_omit_default_defaults_to_false() {
         let tokens = quote::quote!(CreateUser from User);
@@ -0,0 +1,592 @@
+//! Drift check between `SeaORM` entities and their migrations.
+//!
+//! `vespera!(migrations = "migration/src")` reads the `SeaORM` migration files in that
+//! folder and warns about entity columns that no migration creates, such as a field left
+//! on a `Model` after the migration that dropped its column. Schemas generated from such
+//! a `Model` with `schema_type!` would otherwise document a field the database no longer has.
+//!
+//! Migrations are applied in file name order. Each `Table::create()` contributes its
+//! `.col(...)` columns, `Table::alter()` its `add_column`, `rename_column` and
+//! `drop_column` changes, and `Table::drop()` removes the table. Table and column names come
+//! from `#[derive(DeriveIden)]` enums (`User::Table`, `User::Email`, honouring
+//! `#[sea_orm(iden = "...")]`) or `Alias::new("...")`.
+//!
+//! A `.json` file is read as an entity snapshot instead: `{ "user": ["id", "email"] }`.
+
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    path::{Path, PathBuf},
+};
+
+use super::{
+    file_cache::get_parsed_file,
+    file_lookup::{collect_rs_files_recursive, file_path_to_module_path},
+    seaorm::{extract_sea_orm_attr_value, has_sea_orm_ignore},
+    type_utils::{is_seaorm_model, is_seaorm_relation_type},
+};
+use crate::parser::{rename_field, strip_raw_prefix_owned};
+
+/// Columns by table name.
+pub type TableColumns = BTreeMap<String, BTreeSet<String>>;
+
+/// Table and column names of a `DeriveIden` enum.
+struct Iden {
+    table: String,
+    columns: HashMap<String, String>,
+}
+
+/// `#[sea_orm(iden = "...")]` or `#[iden = "..."]` on an iden enum variant.
+fn iden_override(attrs: &[syn::Attribute]) -> Option<String> {
+    extract_sea_orm_attr_value(attrs, "iden").or_else(|| {
+        attrs.iter().find_map(|attr| match &attr.meta {
+            syn::Meta::NameValue(meta) if meta.path.is_ident("iden") => match &meta.value {
+                syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Str(lit),
+                    ..
+                }) => Some(lit.value()),
+                _ => None,
+            },
+            _ => None,
+        })
+    })
+}
+
+/// Whether `attrs` derive `DeriveIden` (or sea-query's `Iden`).
+fn derives_iden(attrs: &[syn::Attribute]) -> bool {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("derive"))
+        .any(|attr| {
+            attr.parse_args_with(
+                syn::punctuated::Punctuated::<syn::Path, syn::Token![,]>::parse_terminated,
+            )
+            .is_ok_and(|paths| {
+                paths.iter().any(|path| {
+                    path.segments.last().is_some_and(|segment| {
+                        segment.ident == "DeriveIden" || segment.ident == "Iden"
+                    })
+                })
+            })
+        })
+}
+
+fn collect_idens(items: &[syn::Item], idens: &mut HashMap<String, Iden>) {
+    for item in items {
+        match item {
+            syn::Item::Enum(item) if derives_iden(&item.attrs) => {
+                let enum_name = item.ident.to_string();
+                let mut table = rename_field(&enum_name, Some("snake_case"));
+                let mut columns = HashMap::new();
+                for variant in &item.variants {
+                    let variant_name = variant.ident.to_string();
+                    let name = iden_override(&variant.attrs);
+                    if variant_name == "Table" {
+                        // `Table` names the table only when overridden, else the enum does
+                        if let Some(name) = name {
+                            table = name;
+                        }
+                    } else {
+                        let name =
+                            name.unwrap_or_else(|| rename_field(&variant_name, Some("snake_case")));
+                        columns.insert(variant_name, name);
+                    }
+                }
+                idens.insert(enum_name, Iden { table, columns });
+            }
+            syn::Item::Mod(syn::ItemMod {
+                content: Some((_, items)),
+                ..
+            }) => collect_idens(items, idens),
+            _ => {}
+        }
+    }
+}
+
+/// A `Table::create()` / `alter()` / `drop()` statement and the calls chained on it.
+struct TableStatement<'a> {
+    kind: String,
+    calls: Vec<(String, Vec<&'a syn::Expr>)>,
+}
+
+/// Flatten `root.a(x).b(y)` into `(root, [("a", [x]), ("b", [y])])`.
+fn method_chain(expr: &syn::ExprMethodCall) -> (&syn::Expr, Vec<(String, Vec<&syn::Expr>)>) {
+    let mut calls = vec![(expr.method.to_string(), expr.args.iter().collect())];
+    let mut receiver = &*expr.receiver;
+    while let syn::Expr::MethodCall(call) = receiver {
+        calls.push((call.method.to_string(), call.args.iter().collect()));
+        receiver = &call.receiver;
+    }
+    calls.reverse();
+    (receiver, calls)
+}
+
+/// `Table::create()` → `Some("create")`.
+fn table_statement_kind(expr: &syn::Expr) -> Option<String> {
+    let syn::Expr::Call(call) = expr else {
+        return None;
+    };
+    let syn::Expr::Path(path) = &*call.func else {
+        return None;
+    };
+    let segments: Vec<String> = path
+        .path
+        .segments
+        .iter()
+        .map(|segment| segment.ident.to_string())
+        .collect();
+    match segments.as_slice() {
+        [.., table, kind] if table == "Table" => Some(kind.clone()),
+        _ => None,
+    }
+}
+
+/// Collect the table statements in `expr`, in source order.
+fn walk_expr<'a>(expr: &'a syn::Expr, statements: &mut Vec<TableStatement<'a>>) {
+    match expr {
+        syn::Expr::MethodCall(call) => {
+            let (root, calls) = method_chain(call);
+            if let Some(kind) = table_statement_kind(root) {
+                statements.push(TableStatement { kind, calls });
+                return;
+            }
+            walk_expr(root, statements);
+            for (_, args) in calls {
+                for arg in args {
+                    walk_expr(arg, statements);
+                }
+            }
+        }
+        syn::Expr::Call(call) => {
+            for arg in &call.args {
+                walk_expr(arg, statements);
+            }
+        }
+        syn::Expr::Await(expr) => walk_expr(&expr.base, statements),
+        syn::Expr::Try(expr) => walk_expr(&expr.expr, statements),
+        syn::Expr::Paren(expr) => walk_expr(&expr.expr, statements),
+        syn::Expr::Reference(expr) => walk_expr(&expr.expr, statements),
+        syn::Expr::Return(expr) => {
+            if let Some(expr) = &expr.expr {
+                walk_expr(expr, statements);
+            }
+        }
+        syn::Expr::Block(expr) => walk_block(&expr.block, statements),
+        syn::Expr::Async(expr) => walk_block(&expr.block, statements),
+        syn::Expr::Closure(expr) => walk_expr(&expr.body, statements),
+        syn::Expr::If(expr) => {
+            walk_block(&expr.then_branch, statements);
+            if let Some((_, else_branch)) = &expr.else_branch {
+                walk_expr(else_branch, statements);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn walk_block<'a>(block: &'a syn::Block, statements: &mut Vec<TableStatement<'a>>) {
+    for stmt in &block.stmts {
+        match stmt {
+            syn::Stmt::Local(local) => {
+                if let Some(init) = &local.init {
+                    walk_expr(&init.expr, statements);
+                }
+            }
+            syn::Stmt::Expr(expr, _) => walk_expr(expr, statements),
+            syn::Stmt::Item(item) => walk_items(std::slice::from_ref(item), statements),
+            syn::Stmt::Macro(_) => {}
+        }
+    }
+}
+
+fn walk_items<'a>(items: &'a [syn::Item], statements: &mut Vec<TableStatement<'a>>) {
+    for item in items {
+        match item {
+            syn::Item::Fn(item) => walk_block(&item.block, statements),
+            syn::Item::Impl(item) => {
+                for impl_item in &item.items {
+                    if let syn::ImplItem::Fn(method) = impl_item {
+                        walk_block(&method.block, statements);
+                    }
+                }
+            }
+            syn::Item::Mod(syn::ItemMod {
+                content: Some((_, items)),
+                ..
+            }) => walk_items(items, statements),
+            _ => {}
+        }
+    }
+}
+
+/// The first table or column name in `expr`: an iden path (`User::Email`), an
+/// `Alias::new("email")` or a string literal.
+fn find_name(expr: &syn::Expr, idens: &HashMap<String, Iden>, table: bool) -> Option<String> {
+    match expr {
+        syn::Expr::Path(path) => {
+            let segments: Vec<String> = path
+                .path
+                .segments
+                .iter()
+                .map(|segment| segment.ident.to_string())
+                .collect();
+            let [.., enum_name, variant] = segments.as_slice() else {
+                return None;
+            };
+            let iden = idens.get(enum_name)?;
+            if table {
+                (variant == "Table").then(|| iden.table.clone())
+            } else {
+                iden.columns.get(variant).cloned()
+            }
+        }
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Str(lit),
+            ..
+        }) => Some(lit.value()),
+        syn::Expr::Call(call) => call
+            .args
+            .iter()
+            .find_map(|arg| find_name(arg, idens, table)),
+        syn::Expr::MethodCall(call) => find_name(&call.receiver, idens, table).or_else(|| {
+            call.args
+                .iter()
+                .find_map(|arg| find_name(arg, idens, table))
+        }),
+        syn::Expr::Reference(expr) => find_name(&expr.expr, idens, table),
+        syn::Expr::Paren(expr) => find_name(&expr.expr, idens, table),
+        _ => None,
+    }
+}
+
+fn apply_statement(
+    statement: &TableStatement,
+    idens: &HashMap<String, Iden>,
+    tables: &mut TableColumns,
+) {
+    let Some(table) = statement
+        .calls
+        .iter()
+        .filter(|(method, _)| method == "table")
+        .find_map(|(_, args)| args.first().and_then(|arg| find_name(arg, idens, true)))
+    else {
+        return;
+    };
+    if statement.kind == "drop" {
+        tables.remove(&table);
+        return;
+    }
+    if statement.kind != "create" && statement.kind != "alter" {
+        return;
+    }
+    let columns = tables.entry(table).or_default();
+    for (method, args) in &statement.calls {
+        let name = |index: usize| args.get(index).and_then(|arg| find_name(arg, idens, false));
+        match method.as_str() {
+            "col" | "add_column" | "add_column_if_not_exists" => {
+                columns.extend(name(0));
+            }
+            "drop_column" => {
+                if let Some(column) = name(0) {
+                    columns.remove(&column);
+                }
+            }
+            "rename_column" => {
+                if let (Some(from), Some(to)) = (name(0), name(1)) {
+                    columns.remove(&from);
+                    columns.insert(to);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Columns created by the migrations in `path`, or listed by the snapshot file `path`.
+pub fn migration_columns(path: &Path) -> Result<TableColumns, String> {
+    if path.extension().is_some_and(|ext| ext == "json") {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("failed to read `{}`: {e}", path.display()))?;
+        return serde_json::from_str(&content).map_err(|e| {
+            format!(
+                "invalid entity snapshot `{}`: {e}. Expected {{ \"table\": [\"column\", ...] }}",
+                path.display()
+            )
+        });
+    }
+    if !path.is_dir() {
+        return Err(format!("migration folder `{}` not found", path.display()));
+    }
+
+    let mut files = Vec::new();
+    collect_rs_files_recursive(path, &mut files);
+    files.sort();
+    let parsed: Vec<syn::File> = files
+        .iter()
+        .filter_map(|file| get_parsed_file(file))
+        .collect();
+
+    let mut idens = HashMap::new();
+    for file in &parsed {
+        collect_idens(&file.items, &mut idens);
+    }
+    let mut tables = TableColumns::new();
+    for file in &parsed {
+        let mut statements = Vec::new();
+        walk_items(&file.items, &mut statements);
+        for statement in &statements {
+            apply_statement(statement, &idens, &mut tables);
+        }
+    }
+    Ok(tables)
+}
+
+/// A `SeaORM` Model found in the crate: its module path, table and columns.
+struct EntityModel {
+    module_path: String,
+    table: String,
+    columns: Vec<String>,
+}
+
+fn collect_models(items: &[syn::Item], module_path: &str, models: &mut Vec<EntityModel>) {
+    for item in items {
+        match item {
+            syn::Item::Struct(item) if is_seaorm_model(item) => {
+                let Some(table) = extract_sea_orm_attr_value(&item.attrs, "table_name") else {
+                    continue;
+                };
+                let columns = item
+                    .fields
+                    .iter()
+                    .filter(|field| {
+                        !is_seaorm_relation_type(&field.ty) && !has_sea_orm_ignore(&field.attrs)
+                    })
+                    .filter_map(|field| {
+                        extract_sea_orm_attr_value(&field.attrs, "column_name").or_else(|| {
+                            field
+                                .ident
+                                .as_ref()
+                                .map(|ident| strip_raw_prefix_owned(ident.to_string()))
+                        })
+                    })
+                    .collect();
+                models.push(EntityModel {
+                    module_path: format!("{module_path}::{}", item.ident),
+                    table,
+                    columns,
+                });
+            }
+            syn::Item::Mod(syn::ItemMod {
+                ident,
+                content: Some((_, items)),
+                ..
+            }) => collect_models(items, &format!("{module_path}::{ident}"), models),
+            _ => {}
+        }
+    }
+}
+
+/// Warnings for the columns of the `SeaORM` Models under `src_dir` that the
+/// migrations (or snapshot) at `migrations` do not create.
+pub fn schema_drift_warnings(src_dir: &Path, migrations: &Path) -> Result<Vec<String>, String> {
+    let tables = migration_columns(migrations)?;
+
+    let mut files: Vec<PathBuf> = Vec::new();
+    collect_rs_files_recursive(src_dir, &mut files);
+    files.sort();
+    let mut models = Vec::new();
+    for file in &files {
+        if let Some(parsed) = get_parsed_file(file) {
+            let module_path = file_path_to_module_path(file, src_dir).join("::");
+            collect_models(&parsed.items, &module_path, &mut models);
+        }
+    }
+
+    let mut warnings = Vec::new();
+    for model in models {
+        let Some(created) = tables.get(&model.table) else {
+            warnings.push(format!(
+                "table `{}` of `{}` is not created by any migration in `{}`",
+                model.table,
+                model.module_path,
+                migrations.display()
+            ));
+            continue;
+        };
+        for column in model.columns.iter().filter(|c| !created.contains(*c)) {
+            warnings.push(format!(
+                "column `{}.{column}` of `{}` is not created by any migration in `{}`; remove the field or add a migration",
+                model.table,
+                model.module_path,
+                migrations.display()
+            ));
+        }
+    }
+    Ok(warnings)
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    const CREATE_USER: &str = r#"
+        use sea_orm_migration::{prelude::*, schema::*};
+
+        pub struct Migration;
+
+        #[async_trait::async_trait]
+        impl MigrationTrait for Migration {
+            async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+                manager
+                    .create_table(
+                        Table::create()
+                            .table(User::Table)
+                            .if_not_exists()
+                            .col(pk_auto(User::Id))
+                            .col(string(User::Name))
+                            .col(ColumnDef::new(User::Email).string().not_null())
+                            .col(ColumnDef::new(Alias::new("nickname")).string())
+                            .to_owned(),
+                    )
+                    .await
+            }
+        }
+
+        #[derive(DeriveIden)]
+        enum User {
+            #[sea_orm(iden = "users")]
+            Table,
+            Id,
+            Name,
+            Email,
+        }
+    "#;
+
+    const ALTER_USER: &str = r#"
+        impl MigrationTrait for Migration {
+            async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+                manager
+                    .alter_table(
+                        Table::alter()
+                            .table(Users::Table)
+                            .add_column(timestamp(Users::CreatedAt))
+                            .drop_column(Users::Nickname)
+                            .rename_column(Users::Name, Users::DisplayName)
+                            .to_owned(),
+                    )
+                    .await?;
+                manager
+                    .drop_table(Table::drop().table(Alias::new("legacy")).to_owned())
+                    .await
+            }
+        }
+
+        #[derive(Iden)]
+        enum Users {
+            Table,
+            CreatedAt,
+            Nickname,
+            Name,
+            DisplayName,
+        }
+    "#;
+
+    fn write(dir: &Path, name: &str, content: &str) {
+        let path = dir.join(name);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn test_migration_columns() {
+        let dir = TempDir::new().unwrap();
+        write(dir.path(), "m20240101_000001_create_user.rs", CREATE_USER);
+        write(dir.path(), "m20240201_000001_alter_user.rs", ALTER_USER);
+        write(
+            dir.path(),
+            "m20230101_000001_create_legacy.rs",
+            r#"
+            fn up() { Table::create().table(Alias::new("legacy")).col(ColumnDef::new(Alias::new("id"))); }
+            "#,
+        );
+
+        let tables = migration_columns(dir.path()).unwrap();
+        assert_eq!(
+            tables,
+            TableColumns::from([(
+                "users".to_string(),
+                ["id", "email", "created_at", "display_name"]
+                    .map(String::from)
+                    .into()
+            )])
+        );
+    }
+
+    #[test]
+    fn test_migration_columns_snapshot() {
+        let dir = TempDir::new().unwrap();
+        write(
+            dir.path(),
+            "entities.json",
+            r#"{ "users": ["id", "email"] }"#,
+        );
+        let tables = migration_columns(&dir.path().join("entities.json")).unwrap();
+        assert_eq!(tables["users"], ["id", "email"].map(String::from).into());
+
+        write(dir.path(), "broken.json", "[]");
+        let err = migration_columns(&dir.path().join("broken.json")).unwrap_err();
+        assert!(err.contains("invalid entity snapshot"), "{err}");
+        let err = migration_columns(&dir.path().join("missing")).unwrap_err();
+        assert!(err.contains("not found"), "{err}");
+    }
+
+    #[test]
+    fn test_schema_drift_warnings() {
+        let migrations = TempDir::new().unwrap();
+        write(
+            migrations.path(),
+            "m20240101_000001_create_user.rs",
+            CREATE_USER,
+        );
+        let src = TempDir::new().unwrap();
+        write(
+            src.path(),
+            "models/user.rs",
+            r#"
+            #[sea_orm::model]
+            #[derive(Clone, Debug, DeriveEntityModel)]
+            #[sea_orm(table_name = "users")]
+            pub struct Model {
+                #[sea_orm(primary_key)]
+                pub id: i32,
+                pub name: String,
+                #[sea_orm(column_name = "email")]
+                pub email_address: String,
+                pub bio: Option<String>,
+                #[sea_orm(ignore)]
+                pub score: i32,
+                pub posts: HasMany<super::post::Entity>,
+            }
+            "#,
+        );
+        write(
+            src.path(),
+            "models/post.rs",
+            r#"
+            #[sea_orm::model]
+            #[sea_orm(table_name = "posts")]
+            pub struct Model {
+                pub id: i32,
+            }
+            "#,
+        );
+
+        let warnings = schema_drift_warnings(src.path(), migrations.path()).unwrap();
+        assert_eq!(warnings.len(), 2, "{warnings:?}");
+        assert!(warnings[0].starts_with("table `posts` of `crate::models::post::Model`"));
+        assert!(warnings[1].starts_with(
+            "column `users.bio` of `crate::models::user::Model` is not created by any migration"
+        ));
+    }
+}
@@ -4,13 +4,16 @@
 //! - `schema!` - Generate Schema value with optional field filtering
 //! - `schema_type!` - Generate new struct type derived from existing type
 
+mod bulk;
 mod circular;
 mod codegen;
 pub mod file_cache;
 mod file_lookup;
+mod filter;
 mod from_model;
 mod inline_types;
 mod input;
+mod migrations;
 mod seaorm;
 mod transformation;
 pub mod type_utils;
@@ -20,19 +23,23 @@ pub use file_cache::print_profile_summary;
 
 use std::collections::{HashMap, HashSet};
 
+use bulk::generate_bulk_impl;
 use codegen::generate_filtered_schema;
 use file_lookup::find_struct_from_path;
+use filter::generate_filter_code;
 use from_model::generate_from_model_with_relations;
 use inline_types::{
     generate_inline_relation_type, generate_inline_relation_type_no_relations,
     generate_inline_type_definition,
 };
 pub use input::{PartialMode, SchemaInput, SchemaTypeInput};
+pub use migrations::schema_drift_warnings;
 use proc_macro2::TokenStream;
 use quote::quote;
 use seaorm::{
     RelationFieldInfo, convert_relation_type_to_schema_with_info, convert_type_with_chrono,
     extract_sea_orm_default_value, has_sea_orm_primary_key, is_sql_function_default,
+    polymorphic_relation_info,
 };
 use transformation::{
     build_omit_set, build_partial_config, build_pick_set, build_rename_map, determine_rename_all,
@@ -46,12 +53,13 @@ use type_utils::{
 };
 use validation::{
     extract_source_field_names, validate_omit_fields, validate_partial_fields,
-    validate_pick_fields, validate_rename_fields,
+    validate_pick_fields, validate_polymorphic_fields, validate_rename_fields,
+    validate_seaorm_columns,
 };
 
 use crate::{
     metadata::StructMetadata,
-    parser::{extract_default, extract_field_rename, strip_raw_prefix_owned},
+    parser::{extract_default, extract_field_rename, rename_field, strip_raw_prefix_owned},
 };
 
 #[cfg(test)]
@@ -379,6 +387,70 @@ pub fn generate_schema_code(
     Ok(schema_tokens)
 }
 
+/// Re-anchor leading `self::`/`super::` paths one module up, for items emitted into the
+/// `module = "..."` submodule.
+fn reanchor_relative_type(ty: &syn::Type) -> syn::Type {
+    let mut ty = ty.clone();
+    reanchor_relative_type_in_place(&mut ty);
+    ty
+}
+
+fn reanchor_relative_type_in_place(ty: &mut syn::Type) {
+    match ty {
+        syn::Type::Path(type_path) if type_path.qself.is_none() => {
+            let path = &mut type_path.path;
+            if path.leading_colon.is_none()
+                && path
+                    .segments
+                    .first()
+                    .is_some_and(|s| s.ident == "self" || s.ident == "super")
+            {
+                if path.segments[0].ident == "self" {
+                    path.segments[0].ident =
+                        syn::Ident::new("super", path.segments[0].ident.span());
+                } else {
+                    path.segments.insert(0, syn::parse_quote!(super));
+                }
+            }
+            for segment in &mut path.segments {
+                if let syn::PathArguments::AngleBracketed(args) = &mut segment.arguments {
+                    for arg in &mut args.args {
+                        if let syn::GenericArgument::Type(inner) = arg {
+                            reanchor_relative_type_in_place(inner);
+                        }
+                    }
+                }
+            }
+        }
+        syn::Type::Reference(reference) => reanchor_relative_type_in_place(&mut reference.elem),
+        syn::Type::Tuple(tuple) => tuple
+            .elems
+            .iter_mut()
+            .for_each(reanchor_relative_type_in_place),
+        syn::Type::Array(array) => reanchor_relative_type_in_place(&mut array.elem),
+        syn::Type::Slice(slice) => reanchor_relative_type_in_place(&mut slice.elem),
+        _ => {}
+    }
+}
+
+/// Emit `tokens` into `pub mod <module>` (seeing the call site's items through `super::*`)
+/// and glob re-export it, so the items are reachable from both paths.
+fn wrap_in_module(module: Option<&syn::Ident>, tokens: TokenStream) -> TokenStream {
+    let Some(module) = module else {
+        return tokens;
+    };
+    quote! {
+        pub mod #module {
+            #[allow(unused_imports)]
+            use super::*;
+
+            #tokens
+        }
+        #[allow(unused_imports)]
+        pub use #module::*;
+    }
+}
+
 /// Generate a new struct type from an existing type with field filtering
 ///
 /// Returns (`TokenStream`, Option<StructMetadata>) where the metadata is returned
@@ -462,6 +534,10 @@ pub fn generate_schema_type_code(
     let source_field_names = extract_source_field_names(&parsed_struct);
 
     // Validate all field references exist in source struct
+    // SeaORM Models get column-aware errors naming what changed in the entity
+    if is_seaorm_model(&parsed_struct) {
+        validate_seaorm_columns(input, &parsed_struct, &source_type_name)?;
+    }
     validate_pick_fields(
         input.pick.as_ref(),
         &source_field_names,
@@ -490,6 +566,12 @@ pub fn generate_schema_type_code(
         &input.source_type,
         &source_type_name,
     )?;
+    validate_polymorphic_fields(
+        input.polymorphic.as_ref(),
+        &source_field_names,
+        &input.source_type,
+        &source_type_name,
+    )?;
 
     // Build filter sets and rename map
     let omit_set = build_omit_set(input.omit.as_ref());
@@ -511,6 +593,32 @@ pub fn generate_schema_type_code(
     // Check if source is a SeaORM Model
     let is_source_seaorm_model = is_seaorm_model(&parsed_struct);
 
+    if input.bulk && !is_source_seaorm_model {
+        return Err(syn::Error::new_spanned(
+            &input.source_type,
+            "schema_type! macro: `bulk` requires a SeaORM Model source.",
+        ));
+    }
+
+    // Filter mode: generate a filter/sort query struct instead of a field-mapped DTO
+    if input.filter {
+        let schema_name_attr = input
+            .schema_name
+            .as_ref()
+            .map_or_else(|| quote! {}, |name| quote! { #[schema(name = #name)] });
+        let tokens = generate_filter_code(
+            &input.new_type,
+            &parsed_struct,
+            &source_module_path,
+            &omit_set,
+            &pick_set,
+            &effective_rename_all,
+            &schema_name_attr,
+            is_source_seaorm_model,
+        );
+        return Ok((wrap_in_module(input.module.as_ref(), tokens), None));
+    }
+
     // Generate new struct with filtered fields
     let new_type_name = &input.new_type;
     let mut field_tokens = Vec::new();
@@ -579,6 +687,32 @@ pub fn generate_schema_type_code(
                         // NEW RULE: HasMany (reverse references) are excluded by default
                         // They can only be included via explicit `pick`
                         if rel_info.relation_type == "HasMany" {
+                            // `#[schema(count)]`: a plain `<field>_count: u64`, included by default
+                            if rel_info.count {
+                                let count_ident = rename_map.get(&rust_field_name).map_or_else(
+                                    || quote::format_ident!("{}_count", rust_field_name),
+                                    |new_name| {
+                                        syn::Ident::new(
+                                            new_name,
+                                            field.ident.as_ref().unwrap().span(),
+                                        )
+                                    },
+                                );
+                                let doc_attrs = extract_doc_attrs(&field.attrs);
+                                let vis = &field.vis;
+                                field_tokens.push(quote! {
+                                    #(#doc_attrs)*
+                                    #vis #count_ident: u64
+                                });
+                                field_mappings.push((
+                                    count_ident,
+                                    field.ident.clone().unwrap(),
+                                    false,
+                                    true,
+                                ));
+                                relation_fields.push(rel_info);
+                                continue;
+                            }
                             // HasMany is only included if explicitly picked
                             if !pick_set.contains(&rust_field_name) {
                                 continue;
@@ -610,7 +744,12 @@ pub fn generate_schema_type_code(
                             }
                         } else {
                             // BelongsTo/HasOne: Include by default
-                            if input.add.is_some()
+                            if rel_info.is_self_ref {
+                                // Self-relation (e.g. `parent`): recurse into the generated
+                                // type itself instead of inlining, so ancestors keep the same shape
+                                let self_field_ty = quote! { Option<Box<#new_type_name>> };
+                                (Box::new(self_field_ty), Some(rel_info))
+                            } else if input.add.is_some()
                                 && let Some((override_field_ty, helper_tokens)) =
                                     maybe_generate_same_file_relation_override(
                                         new_type_name,
@@ -792,10 +931,65 @@ pub fn generate_schema_type_code(
         }
     }
 
+    // Polymorphic relations: an internally tagged enum of the possible targets, tagged by
+    // the type column, which `from_model` fills from the entity the type column names
+    for relation in input.polymorphic.iter().flatten() {
+        let parent_name = input
+            .schema_name
+            .clone()
+            .unwrap_or_else(|| new_type_name.to_string());
+        let enum_ident = syn::Ident::new(
+            &format!(
+                "{parent_name}_{}",
+                snake_to_pascal_case(&relation.field_name)
+            ),
+            proc_macro2::Span::call_site(),
+        );
+        let rel_info =
+            polymorphic_relation_info(relation, &parsed_struct, &source_module_path, &enum_ident)?;
+
+        let tag_name = rename_field(&relation.type_column, Some(effective_rename_all.as_str()));
+        let variants = rel_info
+            .polymorphic_targets
+            .iter()
+            .map(|(tag, schema_path)| {
+                let variant =
+                    syn::Ident::new(&snake_to_pascal_case(tag), proc_macro2::Span::call_site());
+                quote! {
+                    #[serde(rename = #tag)]
+                    #variant(Box<#schema_path>)
+                }
+            });
+        let enum_clone_derive = if input.derive_clone {
+            quote! { Clone, }
+        } else {
+            quote! {}
+        };
+        inline_type_definitions.push(quote! {
+            #[derive(serde::Serialize, serde::Deserialize, #enum_clone_derive vespera::Schema)]
+            #[serde(tag = #tag_name)]
+            pub enum #enum_ident {
+                #(#variants),*
+            }
+        });
+
+        let field_ident = rel_info.field_name.clone();
+        field_tokens.push(quote! {
+            pub #field_ident: Option<#enum_ident>
+        });
+        field_mappings.push((field_ident.clone(), field_ident, false, true));
+        relation_fields.push(rel_info);
+    }
+
     // Add new fields from `add` parameter
     if let Some(ref add_fields) = input.add {
         for (field_name, field_ty) in add_fields {
             let field_ident = syn::Ident::new(field_name, proc_macro2::Span::call_site());
+            let field_ty = if input.module.is_some() {
+                reanchor_relative_type(field_ty)
+            } else {
+                field_ty.clone()
+            };
             field_tokens.push(quote! {
                 pub #field_ident: #field_ty
             });
@@ -832,7 +1026,11 @@ pub fn generate_schema_type_code(
     let has_relation_fields = field_mappings.iter().any(|(_, _, _, is_rel)| *is_rel);
 
     // In multipart mode, skip From and from_model impls entirely
-    let source_type = &input.source_type;
+    let source_type = &if input.module.is_some() {
+        reanchor_relative_type(&input.source_type)
+    } else {
+        input.source_type.clone()
+    };
     let (from_impl, from_model_impl) = if input.multipart {
         (quote! {}, quote! {})
     } else {
@@ -884,6 +1082,18 @@ pub fn generate_schema_type_code(
         (from_impl, from_model_impl)
     };
 
+    // SeaORM bulk create/update helpers (`bulk` keyword)
+    let bulk_impl = if input.bulk {
+        generate_bulk_impl(
+            new_type_name,
+            &parsed_struct,
+            &source_module_path,
+            &field_mappings,
+        )
+    } else {
+        quote! {}
+    };
+
     // Generate the new struct (with inline types for circular relations first)
     let generated_tokens = if input.multipart {
         // Multipart mode: derive Multipart instead of serde
@@ -923,6 +1133,7 @@ pub fn generate_schema_type_code(
 
             #from_impl
             #from_model_impl
+            #bulk_impl
         }
     };
 
@@ -940,7 +1151,10 @@ pub fn generate_schema_type_code(
         StructMetadata::new(custom_name.clone(), struct_def.to_string())
     });
 
-    Ok((generated_tokens, metadata))
+    Ok((
+        wrap_in_module(input.module.as_ref(), generated_tokens),
+        metadata,
+    ))
 }
 
 /// Generate `#[serde(default = "...")]` and `#[schema(default = "...")]` attributes
@@ -1087,7 +1301,7 @@ fn sql_function_default_for_type(original_ty: &syn::Type) -> Option<(TokenStream
             "00000000-0000-0000-0000-000000000000".to_string(),
         )),
         "i8" | "i16" | "i32" | "i64" | "i128" | "isize" | "u8" | "u16" | "u32" | "u64" | "u128"
-        | "usize" | "f32" | "f64" | "Decimal" => {
+        | "usize" | "f32" | "f64" | "Decimal" | "BigDecimal" => {
             Some((quote! { Default::default() }, "0".to_string()))
         }
         "bool" => Some((quote! { Default::default() }, "false".to_string())),
@@ -1098,7 +1312,7 @@ fn sql_function_default_for_type(original_ty: &syn::Type) -> Option<(TokenStream
 
 /// Check if a type is known to implement `FromStr` and can use `.parse().unwrap()`.
 ///
-/// Returns true for primitive types, String, and Decimal.
+/// Returns true for primitive types, String, Decimal and BigDecimal.
 /// Returns false for enums and unknown custom types.
 fn is_parseable_type(ty: &syn::Type) -> bool {
     let syn::Type::Path(type_path) = ty else {
@@ -7,7 +7,10 @@ use proc_macro2::TokenStream;
 use quote::quote;
 use syn::Type;
 
-use super::type_utils::{is_option_type, resolve_type_to_absolute_path};
+use super::{
+    input::PolymorphicRelation,
+    type_utils::{is_option_type, resolve_type_to_absolute_path},
+};
 
 /// Relation field info for generating `from_model` code
 #[derive(Clone)]
@@ -26,11 +29,29 @@ pub struct RelationFieldInfo {
     /// The `relation_enum` attribute value (e.g., "`TargetUser`", "`CreatedByUser`")
     /// When present, indicates multiple relations to the same Entity type exist
     pub relation_enum: Option<String>,
-    /// The FK column name from `from` attribute (e.g., "`user_id`", "`target_user_id`")
-    pub fk_column: Option<String>,
+    /// The FK columns from the `from` attribute (e.g., "`user_id`", or "`tenant_id`" and
+    /// "`user_id`" for a composite key), each with whether it is `Option<T>` on the Model
+    pub fk_columns: Vec<(String, bool)>,
     /// The `via_rel` attribute value for `HasMany` relations (e.g., "`TargetUser`")
     /// This specifies which Relation variant on the TARGET entity to use
     pub via_rel: Option<String>,
+    /// Whether the relation targets the source entity itself (e.g. `parent`/`children`
+    /// on a `category` table whose `parent_id` references its own `id`)
+    pub is_self_ref: bool,
+    /// For a "`Polymorphic`" relation, the possible targets: (tag stored in the type
+    /// column, target Schema path). `fk_columns` then holds the type and id columns.
+    pub polymorphic_targets: Vec<(String, TokenStream)>,
+    /// The `#[schema(loader = ...)]` function that loads this relation in `from_model`
+    /// instead of `find_related`, called as `loader(&model, db)`
+    pub loader: Option<syn::Path>,
+    /// The `#[schema(order_by = "created_at desc")]` columns of a `HasMany` relation,
+    /// each with whether it sorts descending, applied in order
+    pub order_by: Vec<(String, bool)>,
+    /// The `#[schema(limit = 20)]` cap on the rows a `HasMany` relation loads
+    pub limit: Option<u64>,
+    /// `#[schema(count)]` on a `HasMany` relation: only the number of related rows is
+    /// loaded, with a COUNT query, into a `<field>_count: u64` field
+    pub count: bool,
 }
 
 /// Convert `SeaORM` datetime types to chrono equivalents.
@@ -135,7 +156,7 @@ pub fn convert_type_with_chrono(ty: &Type, source_module_path: &[String]) -> Tok
 
 /// Extract a named string value from a `sea_orm` attribute.
 /// Shared helper for `extract_belongs_to_from_field`, `extract_relation_enum`, and `extract_via_rel`.
-fn extract_sea_orm_attr_value(attrs: &[syn::Attribute], attr_name: &str) -> Option<String> {
+pub fn extract_sea_orm_attr_value(attrs: &[syn::Attribute], attr_name: &str) -> Option<String> {
     attrs.iter().find_map(|attr| {
         if !attr.path().is_ident("sea_orm") {
             return None;
@@ -164,11 +185,44 @@ fn extract_sea_orm_attr_value(attrs: &[syn::Attribute], attr_name: &str) -> Opti
     })
 }
 
-/// Extract the "from" field name from a `sea_orm` `belongs_to` attribute.
-/// e.g., `#[sea_orm(belongs_to, from = "user_id", to = "id")]` -> `Some("user_id")`
+/// Split a relation key into its column names.
+/// e.g., `"user_id"` -> `["user_id"]`, `"(tenant_id, user_id)"` -> `["tenant_id", "user_id"]`
+///
+/// Column paths such as `Column::TenantId` are reduced to their `snake_case` field name.
+pub fn parse_key_columns(value: &str) -> Vec<String> {
+    value
+        .trim()
+        .trim_start_matches('(')
+        .trim_end_matches(')')
+        .split(',')
+        .map(|column| column.rsplit("::").next().unwrap_or_default().trim())
+        .filter(|column| !column.is_empty())
+        .map(|column| {
+            if column.starts_with(|c: char| c.is_ascii_uppercase()) {
+                crate::parser::rename_field(column, Some("snake_case"))
+            } else {
+                column.to_string()
+            }
+        })
+        .collect()
+}
+
+/// Extract the "from" field names from a `sea_orm` `belongs_to` attribute.
+/// e.g., `#[sea_orm(belongs_to, from = "user_id", to = "id")]` -> `["user_id"]`
 /// Also handles: `#[sea_orm(belongs_to = "Entity", from = "user_id", to = "id")]`
-pub fn extract_belongs_to_from_field(attrs: &[syn::Attribute]) -> Option<String> {
+/// and composite keys: `#[sea_orm(belongs_to, from = "(tenant_id, user_id)", to = "(tenant_id, id)")]`
+pub fn extract_belongs_to_from_field(attrs: &[syn::Attribute]) -> Vec<String> {
     extract_sea_orm_attr_value(attrs, "from")
+        .map(|value| parse_key_columns(&value))
+        .unwrap_or_default()
+}
+
+/// Extract the "to" field names from a `sea_orm` relation attribute, the columns on the
+/// other side of the `from` columns, in the same order.
+pub fn extract_belongs_to_to_field(attrs: &[syn::Attribute]) -> Vec<String> {
+    extract_sea_orm_attr_value(attrs, "to")
+        .map(|value| parse_key_columns(&value))
+        .unwrap_or_default()
 }
 
 /// Extract the "`relation_enum`" value from a `sea_orm` attribute.
@@ -189,6 +243,86 @@ pub fn extract_via_rel(attrs: &[syn::Attribute]) -> Option<String> {
     extract_sea_orm_attr_value(attrs, "via_rel")
 }
 
+/// Extract the `#[schema(loader = load_active_memos)]` function of a relation field.
+///
+/// The function is called as `loader(&model, db).await?` and returns what `find_related`
+/// would: `Result<Vec<Model>, DbErr>` for `HasMany`, `Result<Option<Model>, DbErr>` otherwise.
+pub fn extract_relation_loader(attrs: &[syn::Attribute]) -> Option<syn::Path> {
+    attrs.iter().find_map(|attr| {
+        if !attr.path().is_ident("schema") {
+            return None;
+        }
+
+        let mut loader = None;
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("loader") {
+                loader = Some(meta.value()?.parse::<syn::Path>()?);
+            } else if meta.input.peek(syn::Token![=]) {
+                meta.value()?.parse::<syn::Expr>()?;
+            }
+            Ok(())
+        });
+        loader
+    })
+}
+
+/// Extract `#[schema(order_by = "created_at desc, id", limit = 20)]` of a `HasMany` field.
+///
+/// Each comma-separated `order_by` entry is a snake_case column optionally followed by
+/// `asc` (the default) or `desc`; entries with any other direction are ignored.
+pub fn extract_relation_order(attrs: &[syn::Attribute]) -> (Vec<(String, bool)>, Option<u64>) {
+    let mut order_by = Vec::new();
+    let mut limit = None;
+    for attr in attrs {
+        if !attr.path().is_ident("schema") {
+            continue;
+        }
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("order_by") {
+                let lit: syn::LitStr = meta.value()?.parse()?;
+                order_by.extend(lit.value().split(',').filter_map(|entry| {
+                    let mut parts = entry.split_whitespace();
+                    let column = parts.next()?.to_string();
+                    let descending = match parts.next() {
+                        None => false,
+                        Some(dir) if dir.eq_ignore_ascii_case("asc") => false,
+                        Some(dir) if dir.eq_ignore_ascii_case("desc") => true,
+                        Some(_) => return None,
+                    };
+                    parts.next().is_none().then_some((column, descending))
+                }));
+            } else if meta.path.is_ident("limit") {
+                let lit: syn::LitInt = meta.value()?.parse()?;
+                limit = Some(lit.base10_parse()?);
+            } else if meta.input.peek(syn::Token![=]) {
+                meta.value()?.parse::<syn::Expr>()?;
+            }
+            Ok(())
+        });
+    }
+    (order_by, limit)
+}
+
+/// Whether a relation field is marked `#[schema(count)]`.
+pub fn extract_relation_count(attrs: &[syn::Attribute]) -> bool {
+    attrs.iter().any(|attr| {
+        if !attr.path().is_ident("schema") {
+            return false;
+        }
+
+        let mut count = false;
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("count") {
+                count = true;
+            } else if meta.input.peek(syn::Token![=]) {
+                meta.value()?.parse::<syn::Expr>()?;
+            }
+            Ok(())
+        });
+        count
+    })
+}
+
 /// Extract `default_value` from a `sea_orm` attribute.
 /// e.g., `#[sea_orm(default_value = 0.7)]` -> `Some("0.7")`
 /// e.g., `#[sea_orm(default_value = "active")]` -> `Some("active")`
@@ -261,6 +395,32 @@ pub fn has_sea_orm_primary_key(attrs: &[syn::Attribute]) -> bool {
     false
 }
 
+/// Check if a field has `#[sea_orm(ignore)]`, i.e. is not a database column.
+pub fn has_sea_orm_ignore(attrs: &[syn::Attribute]) -> bool {
+    attrs.iter().any(|attr| {
+        attr.path().is_ident("sea_orm")
+            && attr.meta.require_list().is_ok_and(|list| {
+                list.tokens.clone().into_iter().any(|token| {
+                    matches!(token, proc_macro2::TokenTree::Ident(ident) if ident == "ignore")
+                })
+            })
+    })
+}
+
+/// Pair each FK column with whether it is optional in `struct_item`.
+pub fn fk_columns_in_struct(
+    struct_item: &syn::ItemStruct,
+    columns: Vec<String>,
+) -> Vec<(String, bool)> {
+    columns
+        .into_iter()
+        .map(|column| {
+            let optional = is_field_optional_in_struct(struct_item, &column);
+            (column, optional)
+        })
+        .collect()
+}
+
 /// Check if a field in the struct is optional (Option<T>).
 pub fn is_field_optional_in_struct(struct_item: &syn::ItemStruct, field_name: &str) -> bool {
     if let syn::Fields::Named(fields_named) = &struct_item.fields {
@@ -275,6 +435,119 @@ pub fn is_field_optional_in_struct(struct_item: &syn::ItemStruct, field_name: &s
     false
 }
 
+/// Resolve the path of a related `Entity` to the absolute path of its `Schema`.
+///
+/// `super::` is relative to `source_module_path`, `crate::` paths are already absolute,
+/// a bare `Entity` (or `self::Entity`) is the source module itself, and any other path is
+/// relative to the parent module (a sibling entity).
+pub fn resolve_relation_schema_path(
+    segments: &[String],
+    source_module_path: &[String],
+) -> Vec<String> {
+    if !segments.is_empty() && segments[0] == "super" {
+        let super_count = segments.iter().take_while(|s| *s == "super").count();
+        let parent_path_len = source_module_path.len().saturating_sub(super_count);
+        let mut abs = Vec::with_capacity(parent_path_len + segments.len() - super_count);
+        abs.extend_from_slice(&source_module_path[..parent_path_len]);
+        for seg in segments.iter().skip(super_count) {
+            if seg == "Entity" {
+                abs.push("Schema".to_string());
+            } else {
+                abs.push(seg.clone());
+            }
+        }
+        abs
+    } else if segments.len() == 1 || (!segments.is_empty() && segments[0] == "self") {
+        // `Entity` / `self::Entity` -> the source module itself (self-relation)
+        let mut abs = source_module_path.to_vec();
+        for seg in segments.iter().filter(|s| *s != "self") {
+            if seg == "Entity" {
+                abs.push("Schema".to_string());
+            } else {
+                abs.push(seg.clone());
+            }
+        }
+        abs
+    } else if !segments.is_empty() && segments[0] == "crate" {
+        segments
+            .iter()
+            .map(|s| {
+                if s == "Entity" {
+                    "Schema".to_string()
+                } else {
+                    s.clone()
+                }
+            })
+            .collect()
+    } else {
+        let parent_path_len = source_module_path.len().saturating_sub(1);
+        let mut abs = Vec::with_capacity(parent_path_len + segments.len());
+        abs.extend_from_slice(&source_module_path[..parent_path_len]);
+        for seg in segments {
+            if seg == "Entity" {
+                abs.push("Schema".to_string());
+            } else {
+                abs.push(seg.clone());
+            }
+        }
+        abs
+    }
+}
+
+/// Relation info of a `polymorphic` relation, whose targets are the variants of `enum_ident`.
+pub fn polymorphic_relation_info(
+    relation: &PolymorphicRelation,
+    parsed_struct: &syn::ItemStruct,
+    source_module_path: &[String],
+    enum_ident: &syn::Ident,
+) -> syn::Result<RelationFieldInfo> {
+    let polymorphic_targets = relation
+        .targets
+        .iter()
+        .map(|(tag, ty)| {
+            let Type::Path(type_path) = ty else {
+                return Err(syn::Error::new_spanned(
+                    ty,
+                    "polymorphic target must be an entity path, e.g. `super::post::Entity`",
+                ));
+            };
+            let segments: Vec<String> = type_path
+                .path
+                .segments
+                .iter()
+                .map(|s| s.ident.to_string())
+                .collect();
+            let path_idents: Vec<syn::Ident> =
+                resolve_relation_schema_path(&segments, source_module_path)
+                    .iter()
+                    .map(|s| syn::Ident::new(s, proc_macro2::Span::call_site()))
+                    .collect();
+            Ok((tag.clone(), quote! { #(#path_idents)::* }))
+        })
+        .collect::<syn::Result<_>>()?;
+
+    Ok(RelationFieldInfo {
+        field_name: syn::Ident::new(&relation.field_name, proc_macro2::Span::call_site()),
+        relation_type: "Polymorphic".to_string(),
+        schema_path: quote! { #enum_ident },
+        // The row may be gone, or the type column may hold an unknown tag
+        is_optional: true,
+        inline_type_info: None,
+        relation_enum: None,
+        fk_columns: fk_columns_in_struct(
+            parsed_struct,
+            vec![relation.type_column.clone(), relation.id_column.clone()],
+        ),
+        via_rel: None,
+        is_self_ref: false,
+        polymorphic_targets,
+        loader: None,
+        order_by: Vec::new(),
+        limit: None,
+        count: false,
+    })
+}
+
 /// Convert a `SeaORM` relation type to a Schema type AND return relation info.
 ///
 /// - `#[sea_orm(has_one)]` -> Always `Option<Box<Schema>>`
@@ -327,43 +600,7 @@ pub fn convert_relation_type_to_schema_with_info(
         .collect();
 
     // Convert path to absolute, resolving `super::` relative to source module
-    let absolute_segments: Vec<String> = if !segments.is_empty() && segments[0] == "super" {
-        let super_count = segments.iter().take_while(|s| *s == "super").count();
-        let parent_path_len = source_module_path.len().saturating_sub(super_count);
-        let mut abs = Vec::with_capacity(parent_path_len + segments.len() - super_count);
-        abs.extend_from_slice(&source_module_path[..parent_path_len]);
-        for seg in segments.iter().skip(super_count) {
-            if seg == "Entity" {
-                abs.push("Schema".to_string());
-            } else {
-                abs.push(seg.clone());
-            }
-        }
-        abs
-    } else if !segments.is_empty() && segments[0] == "crate" {
-        segments
-            .iter()
-            .map(|s| {
-                if s == "Entity" {
-                    "Schema".to_string()
-                } else {
-                    s.clone()
-                }
-            })
-            .collect()
-    } else {
-        let parent_path_len = source_module_path.len().saturating_sub(1);
-        let mut abs = Vec::with_capacity(parent_path_len + segments.len());
-        abs.extend_from_slice(&source_module_path[..parent_path_len]);
-        for seg in &segments {
-            if seg == "Entity" {
-                abs.push("Schema".to_string());
-            } else {
-                abs.push(seg.clone());
-            }
-        }
-        abs
-    };
+    let absolute_segments = resolve_relation_schema_path(&segments, source_module_path);
 
     // Build the absolute path as tokens
     let path_idents: Vec<syn::Ident> = absolute_segments
@@ -372,17 +609,28 @@ pub fn convert_relation_type_to_schema_with_info(
         .collect();
     let schema_path = quote! { #(#path_idents)::* };
 
+    // A relation back to the source entity (parent/children on one table)
+    let is_self_ref = absolute_segments.len() == source_module_path.len() + 1
+        && absolute_segments.starts_with(source_module_path)
+        && absolute_segments.last().is_some_and(|s| s == "Schema");
+
+    let loader = extract_relation_loader(field_attrs);
+
     // Convert based on relation type
     match ident_str.as_str() {
         "HasOne" => {
             // HasOne -> Check FK field to determine optionality
             // If FK is Option<T> -> relation is optional: Option<Box<Schema>>
             // If FK is required -> relation is required: Box<Schema>
-            let fk_field = extract_belongs_to_from_field(field_attrs);
+            // A composite FK is optional as soon as one of its columns is
+            let fk_columns =
+                fk_columns_in_struct(parsed_struct, extract_belongs_to_from_field(field_attrs));
             let relation_enum = extract_relation_enum(field_attrs);
-            let is_optional = fk_field
-                .as_ref()
-                .is_none_or(|f| is_field_optional_in_struct(parsed_struct, f)); // Default to optional if we can't determine
+            // A self-relation always terminates somewhere (the root has no parent),
+            // so it is optional regardless of the FK column
+            let is_optional = is_self_ref
+                || fk_columns.is_empty()
+                || fk_columns.iter().any(|(_, optional)| *optional); // Default to optional if we can't determine
 
             let converted = if is_optional {
                 quote! { Option<Box<#schema_path>> }
@@ -396,14 +644,22 @@ pub fn convert_relation_type_to_schema_with_info(
                 is_optional,
                 inline_type_info: None, // Will be populated later if circular
                 relation_enum,
-                fk_column: fk_field,
+                fk_columns,
                 via_rel: None, // Not used for HasOne
+                is_self_ref,
+                polymorphic_targets: Vec::new(),
+                loader,
+                order_by: Vec::new(),
+                limit: None,
+                count: false,
             };
             Some((converted, info))
         }
         "HasMany" => {
             let relation_enum = extract_relation_enum(field_attrs);
             let via_rel = extract_via_rel(field_attrs);
+            let (order_by, limit) = extract_relation_order(field_attrs);
+            let count = extract_relation_count(field_attrs);
             let converted = quote! { Vec<#schema_path> };
             let info = RelationFieldInfo {
                 field_name,
@@ -412,8 +668,14 @@ pub fn convert_relation_type_to_schema_with_info(
                 is_optional: false,
                 inline_type_info: None, // Will be populated later if circular
                 relation_enum,
-                fk_column: None, // HasMany doesn't have FK on this side
-                via_rel,         // Used to find FK on target entity
+                fk_columns: Vec::new(), // HasMany doesn't have FK on this side
+                via_rel,                // Used to find FK on target entity
+                is_self_ref,
+                polymorphic_targets: Vec::new(),
+                loader,
+                order_by,
+                limit,
+                count,
             };
             Some((converted, info))
         }
@@ -421,11 +683,15 @@ pub fn convert_relation_type_to_schema_with_info(
             // BelongsTo -> Check FK field to determine optionality
             // If FK is Option<T> -> relation is optional: Option<Box<Schema>>
             // If FK is required -> relation is required: Box<Schema>
-            let fk_field = extract_belongs_to_from_field(field_attrs);
+            // A composite FK is optional as soon as one of its columns is
+            let fk_columns =
+                fk_columns_in_struct(parsed_struct, extract_belongs_to_from_field(field_attrs));
             let relation_enum = extract_relation_enum(field_attrs);
-            let is_optional = fk_field
-                .as_ref()
-                .is_none_or(|f| is_field_optional_in_struct(parsed_struct, f)); // Default to optional if we can't determine
+            // A self-relation always terminates somewhere (the root has no parent),
+            // so it is optional regardless of the FK column
+            let is_optional = is_self_ref
+                || fk_columns.is_empty()
+                || fk_columns.iter().any(|(_, optional)| *optional); // Default to optional if we can't determine
 
             let converted = if is_optional {
                 quote! { Option<Box<#schema_path>> }
@@ -439,8 +705,14 @@ pub fn convert_relation_type_to_schema_with_info(
                 is_optional,
                 inline_type_info: None, // Will be populated later if circular
                 relation_enum,
-                fk_column: fk_field,
+                fk_columns,
                 via_rel: None, // Not used for BelongsTo
+                is_self_ref,
+                polymorphic_targets: Vec::new(),
+                loader,
+                order_by: Vec::new(),
+                limit: None,
+                count: false,
             };
             Some((converted, info))
         }
@@ -530,7 +802,7 @@ mod tests {
             #[sea_orm(belongs_to, from = "user_id", to = "id")]
         )];
         let result = extract_belongs_to_from_field(&attrs);
-        assert_eq!(result, Some("user_id".to_string()));
+        assert_eq!(result, vec!["user_id".to_string()]);
     }
 
     #[test]
@@ -539,20 +811,45 @@ mod tests {
             #[sea_orm(belongs_to, to = "id")]
         )];
         let result = extract_belongs_to_from_field(&attrs);
-        assert_eq!(result, None);
+        assert!(result.is_empty());
     }
 
     #[test]
     fn test_extract_belongs_to_from_field_no_sea_orm_attr() {
         let attrs: Vec<syn::Attribute> = vec![syn::parse_quote!(#[serde(skip)])];
         let result = extract_belongs_to_from_field(&attrs);
-        assert_eq!(result, None);
+        assert!(result.is_empty());
     }
 
     #[test]
     fn test_extract_belongs_to_from_field_empty_attrs() {
         let result = extract_belongs_to_from_field(&[]);
-        assert_eq!(result, None);
+        assert!(result.is_empty());
+    }
+
+    #[rstest]
+    #[case("user_id", &["user_id"])]
+    #[case("(tenant_id, user_id)", &["tenant_id", "user_id"])]
+    #[case("(Column::TenantId, Column::UserId)", &["tenant_id", "user_id"])]
+    #[case(" ( a ,b, ) ", &["a", "b"])]
+    #[case("", &[])]
+    fn test_parse_key_columns(#[case] value: &str, #[case] expected: &[&str]) {
+        assert_eq!(parse_key_columns(value), expected);
+    }
+
+    #[test]
+    fn test_extract_belongs_to_composite_key() {
+        let attrs: Vec<syn::Attribute> = vec![syn::parse_quote!(
+            #[sea_orm(belongs_to, from = "(tenant_id, user_id)", to = "(tenant_id, id)")]
+        )];
+        assert_eq!(
+            extract_belongs_to_from_field(&attrs),
+            vec!["tenant_id".to_string(), "user_id".to_string()]
+        );
+        assert_eq!(
+            extract_belongs_to_to_field(&attrs),
+            vec!["tenant_id".to_string(), "id".to_string()]
+        );
     }
 
     #[test]
@@ -586,6 +883,69 @@ mod tests {
         assert_eq!(result, None);
     }
 
+    #[rstest]
+    #[case("#[schema(loader = load_active_memos)]", Some("load_active_memos"))]
+    #[case(
+        "#[schema(description = \"x\", loader = crate::loaders::memos)]",
+        Some("crate :: loaders :: memos")
+    )]
+    #[case("#[sea_orm(has_many)]", None)]
+    #[case("#[schema(format = \"email\")]", None)]
+    fn test_extract_relation_loader(#[case] attr: &str, #[case] expected: Option<&str>) {
+        let struct_item: syn::ItemStruct =
+            syn::parse_str(&format!("struct Model {{ {attr} memos: HasMany<Entity> }}")).unwrap();
+        let field = struct_item.fields.iter().next().unwrap();
+        let loader = extract_relation_loader(&field.attrs).map(|path| quote!(#path).to_string());
+        assert_eq!(loader.as_deref(), expected);
+    }
+
+    #[rstest]
+    #[case(
+        "#[schema(order_by = \"created_at desc\", limit = 20)]",
+        vec![("created_at", true)],
+        Some(20)
+    )]
+    #[case(
+        "#[schema(order_by = \"priority DESC, id\")]",
+        vec![("priority", true), ("id", false)],
+        None
+    )]
+    #[case(
+        "#[schema(order_by = \"title asc, created_at sideways\")]",
+        vec![("title", false)],
+        None
+    )]
+    #[case("#[schema(loader = load_memos, limit = 5)]", vec![], Some(5))]
+    #[case("#[sea_orm(has_many)]", vec![], None)]
+    fn test_extract_relation_order(
+        #[case] attr: &str,
+        #[case] expected_order: Vec<(&str, bool)>,
+        #[case] expected_limit: Option<u64>,
+    ) {
+        let struct_item: syn::ItemStruct =
+            syn::parse_str(&format!("struct Model {{ {attr} memos: HasMany<Entity> }}")).unwrap();
+        let field = struct_item.fields.iter().next().unwrap();
+        let (order_by, limit) = extract_relation_order(&field.attrs);
+        let expected_order: Vec<(String, bool)> = expected_order
+            .into_iter()
+            .map(|(column, desc)| (column.to_string(), desc))
+            .collect();
+        assert_eq!(order_by, expected_order);
+        assert_eq!(limit, expected_limit);
+    }
+
+    #[rstest]
+    #[case("#[schema(count)]", true)]
+    #[case("#[schema(order_by = \"id\", count)]", true)]
+    #[case("#[schema(limit = 5)]", false)]
+    #[case("#[sea_orm(has_many)]", false)]
+    fn test_extract_relation_count(#[case] attr: &str, #[case] expected: bool) {
+        let struct_item: syn::ItemStruct =
+            syn::parse_str(&format!("struct Model {{ {attr} memos: HasMany<Entity> }}")).unwrap();
+        let field = struct_item.fields.iter().next().unwrap();
+        assert_eq!(extract_relation_count(&field.attrs), expected);
+    }
+
     #[test]
     fn test_is_field_optional_in_struct_optional() {
         let struct_item: syn::ItemStruct = syn::parse_str(
@@ -868,6 +1228,44 @@ mod tests {
         assert!(tokens.to_string().contains("Option"));
     }
 
+    #[rstest]
+    #[case("BelongsTo<Entity>", true)]
+    #[case("BelongsTo<self::Entity>", true)]
+    #[case("BelongsTo<super::category::Entity>", true)]
+    #[case("BelongsTo<super::user::Entity>", false)]
+    fn test_convert_relation_type_to_schema_with_info_self_ref(
+        #[case] ty: &str,
+        #[case] is_self_ref: bool,
+    ) {
+        let ty: syn::Type = syn::parse_str(ty).unwrap();
+        // Required FK: a self-relation is still optional, the root has no parent
+        let struct_item = make_test_struct("struct Model { id: i32, parent_id: i32 }");
+        let attrs: Vec<syn::Attribute> =
+            vec![syn::parse_quote!(#[sea_orm(belongs_to, from = "parent_id", to = "id")])];
+        let field_name = syn::Ident::new("parent", proc_macro2::Span::call_site());
+        let module_path = vec![
+            "crate".to_string(),
+            "models".to_string(),
+            "category".to_string(),
+        ];
+        let (tokens, info) = convert_relation_type_to_schema_with_info(
+            &ty,
+            &attrs,
+            &struct_item,
+            &module_path,
+            field_name,
+        )
+        .unwrap();
+        assert_eq!(info.is_self_ref, is_self_ref);
+        assert_eq!(info.is_optional, is_self_ref);
+        if is_self_ref {
+            assert_eq!(
+                tokens.to_string(),
+                "Option < Box < crate :: models :: category :: Schema >>"
+            );
+        }
+    }
+
     #[test]
     fn test_convert_relation_type_to_schema_with_info_has_many() {
         let ty: syn::Type = syn::parse_str("HasMany<memo::Entity>").unwrap();
@@ -944,6 +1342,38 @@ mod tests {
         assert!(!tokens.to_string().contains("Option"));
     }
 
+    #[rstest]
+    #[case("struct Model { tenant_id: i32, user_id: i32 }", false)]
+    #[case("struct Model { tenant_id: i32, user_id: Option<i32> }", true)]
+    fn test_convert_relation_type_to_schema_with_info_belongs_to_composite(
+        #[case] model: &str,
+        #[case] optional: bool,
+    ) {
+        let ty: syn::Type = syn::parse_str("BelongsTo<user::Entity>").unwrap();
+        let struct_item = make_test_struct(model);
+        let attrs: Vec<syn::Attribute> = vec![syn::parse_quote!(
+            #[sea_orm(belongs_to, from = "(tenant_id, user_id)", to = "(tenant_id, id)")]
+        )];
+        let field_name = syn::Ident::new("user", proc_macro2::Span::call_site());
+        let (tokens, info) = convert_relation_type_to_schema_with_info(
+            &ty,
+            &attrs,
+            &struct_item,
+            &["crate".to_string(), "models".to_string()],
+            field_name,
+        )
+        .unwrap();
+        assert_eq!(info.is_optional, optional);
+        assert_eq!(tokens.to_string().contains("Option"), optional);
+        assert_eq!(
+            info.fk_columns,
+            vec![
+                ("tenant_id".to_string(), false),
+                ("user_id".to_string(), optional)
+            ]
+        );
+    }
+
     #[test]
     fn test_convert_relation_type_to_schema_with_info_unknown_relation() {
         let ty: syn::Type = syn::parse_str("SomeOtherType<user::Entity>").unwrap();
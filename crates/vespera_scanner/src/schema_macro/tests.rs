@@ -4,6 +4,7 @@
 
 use std::collections::HashMap;
 
+use rstest::rstest;
 use serial_test::serial;
 
 use super::*;
@@ -340,8 +341,24 @@ fn test_generate_schema_type_code_no_from_impl_with_add() {
 #[test]
 fn test_is_parseable_type_primitives() {
     for ty_str in &[
-        "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize",
-        "f32", "f64", "bool", "String", "Decimal",
+        "i8",
+        "i16",
+        "i32",
+        "i64",
+        "i128",
+        "isize",
+        "u8",
+        "u16",
+        "u32",
+        "u64",
+        "u128",
+        "usize",
+        "f32",
+        "f64",
+        "bool",
+        "String",
+        "Decimal",
+        "BigDecimal",
     ] {
         let ty: syn::Type = syn::parse_str(ty_str).unwrap();
         assert!(is_parseable_type(&ty), "{ty_str} should be parseable");
@@ -627,6 +644,74 @@ fn test_generate_schema_type_code_with_omit_default() {
     assert!(output.contains("name"), "name should remain: {output}");
 }
 
+#[test]
+fn test_generate_schema_type_code_with_filter() {
+    let storage = to_storage(vec![create_test_struct_metadata(
+        "Model",
+        r#"#[sea_orm(table_name = "users")]
+            pub struct Model {
+                #[sea_orm(primary_key)]
+                pub id: i32,
+                pub name: String,
+                pub password: String,
+            }"#,
+    )]);
+
+    let tokens = quote!(UserFilter from Model, filter, omit = ["password"]);
+    let input: SchemaTypeInput = syn::parse2(tokens).unwrap();
+    let (tokens, metadata) = generate_schema_type_code(&input, &storage).unwrap();
+    let output = tokens.to_string();
+
+    assert!(metadata.is_none());
+    assert!(output.contains("pub struct UserFilter"), "{output}");
+    assert!(output.contains("id_gt"), "{output}");
+    assert!(output.contains("name_contains"), "{output}");
+    assert!(output.contains("pub enum UserFilterSortBy"), "{output}");
+    assert!(output.contains("fn apply_to"), "{output}");
+    assert!(!output.contains("password"), "{output}");
+    // Filter mode does not generate a From impl
+    assert!(!output.contains("impl From"), "{output}");
+}
+
+#[test]
+fn test_generate_schema_type_code_with_bulk() {
+    let storage = to_storage(vec![create_test_struct_metadata(
+        "Model",
+        r#"#[sea_orm(table_name = "users")]
+            pub struct Model {
+                #[sea_orm(primary_key)]
+                pub id: i32,
+                pub name: String,
+            }"#,
+    )]);
+
+    let tokens = quote!(CreateUser from Model, omit_default, bulk);
+    let input: SchemaTypeInput = syn::parse2(tokens).unwrap();
+    let (tokens, _) = generate_schema_type_code(&input, &storage).unwrap();
+    let output = tokens.to_string();
+
+    assert!(output.contains("pub struct CreateUser"), "{output}");
+    assert!(output.contains("sea_orm :: IntoActiveModel"), "{output}");
+    assert!(output.contains("fn bulk_create"), "{output}");
+    assert!(output.contains("fn bulk_update"), "{output}");
+    assert!(!output.contains("active . id"), "{output}");
+}
+
+#[test]
+fn test_generate_schema_type_code_bulk_requires_seaorm_model() {
+    let storage = to_storage(vec![create_test_struct_metadata(
+        "User",
+        "pub struct User { pub id: i32 }",
+    )]);
+
+    let tokens = quote!(CreateUser from User, bulk);
+    let input: SchemaTypeInput = syn::parse2(tokens).unwrap();
+    let Err(e) = generate_schema_type_code(&input, &storage) else {
+        panic!("expected error");
+    };
+    assert!(e.to_string().contains("`bulk` requires a SeaORM Model"));
+}
+
 // ============================================================
 // Coverage: SQL function default with existing serde default (line 554)
 // ============================================================
@@ -878,6 +963,47 @@ fn test_generate_schema_type_code_partial_from_impl_wraps_some() {
     assert!(output.contains("Some (source . name)"));
 }
 
+#[test]
+fn test_generate_schema_type_code_with_module() {
+    let storage = to_storage(vec![create_test_struct_metadata(
+        "User",
+        "pub struct User { pub id: i32, pub name: String }",
+    )]);
+
+    let tokens = quote!(UserSchema from User, add = [("team": super::Team)], module = "schemas");
+    let input: SchemaTypeInput = syn::parse2(tokens).unwrap();
+    let (tokens, _metadata) = generate_schema_type_code(&input, &storage).unwrap();
+    let output = tokens.to_string();
+
+    assert!(
+        output
+            .starts_with("pub mod schemas { # [allow (unused_imports)] use super :: * ; # [derive"),
+        "{output}"
+    );
+    assert!(output.contains("pub struct UserSchema"), "{output}");
+    assert!(
+        output.contains("pub team : super :: super :: Team"),
+        "{output}"
+    );
+    assert!(
+        output.ends_with("# [allow (unused_imports)] pub use schemas :: * ;"),
+        "{output}"
+    );
+}
+
+#[rstest]
+#[case("Model", "Model")]
+#[case("crate::models::user::Model", "crate :: models :: user :: Model")]
+#[case("self::Model", "super :: Model")]
+#[case("super::user::Model", "super :: super :: user :: Model")]
+#[case("Option<Vec<super::Tag>>", "Option < Vec < super :: super :: Tag > >")]
+#[case("(self::A, &super::B)", "(super :: A , & super :: super :: B)")]
+fn test_reanchor_relative_type(#[case] ty_src: &str, #[case] expected: &str) {
+    let ty: syn::Type = syn::parse_str(ty_src).unwrap();
+    let reanchored = reanchor_relative_type(&ty);
+    assert_eq!(quote!(#reanchored).to_string(), expected);
+}
+
 #[test]
 fn test_generate_schema_type_code_preserves_struct_doc() {
     let input = SchemaTypeInput {
@@ -894,6 +1020,10 @@ fn test_generate_schema_type_code_preserves_struct_doc() {
         rename_all: None,
         multipart: false,
         omit_default: false,
+        filter: false,
+        bulk: false,
+        polymorphic: None,
+        module: None,
     };
     let struct_def = StructMetadata {
         name: "User".to_string(),
@@ -1246,6 +1376,29 @@ fn test_generate_schema_type_code_has_many_excluded_by_default() {
     assert!(output.contains("name"));
 }
 
+#[test]
+fn test_generate_schema_type_code_has_many_count() {
+    // `#[schema(count)]` turns the HasMany into a `<field>_count: u64`, included by default
+    let storage = to_storage(vec![create_test_struct_metadata(
+        "Model",
+        r#"#[sea_orm(table_name = "users")]
+            pub struct Model {
+                pub id: i32,
+                pub name: String,
+                #[schema(count)]
+                pub memos: HasMany<super::memo::Entity>
+            }"#,
+    )]);
+
+    let tokens = quote!(UserSchema from Model);
+    let input: SchemaTypeInput = syn::parse2(tokens).unwrap();
+    let (tokens, _metadata) = generate_schema_type_code(&input, &storage).unwrap();
+    let output = tokens.to_string();
+    assert!(output.contains("pub memos_count : u64"), "{output}");
+    assert!(!output.contains("Vec <"), "{output}");
+    assert!(output.contains("memos_count : memos"), "{output}");
+}
+
 // Test for relation conversion failure skip
 
 #[test]
@@ -1329,6 +1482,84 @@ fn test_generate_schema_type_code_has_one_relation() {
     assert!(output.contains("profile"));
 }
 
+#[test]
+fn test_generate_schema_type_code_polymorphic_relation() {
+    let storage = to_storage(vec![create_test_struct_metadata(
+        "Model",
+        r#"#[sea_orm(table_name = "comments")]
+            pub struct Model {
+                pub id: i32,
+                pub target_type: String,
+                pub target_id: Option<i32>,
+            }"#,
+    )]);
+
+    let tokens = quote!(CommentSchema from Model, polymorphic = [("target": "target_type", "target_id", [
+        ("post": crate::models::post::Entity),
+        ("photo": crate::models::photo::Entity),
+    ])]);
+    let input: SchemaTypeInput = syn::parse2(tokens).unwrap();
+    let (tokens, _metadata) = generate_schema_type_code(&input, &storage).unwrap();
+    let output = tokens.to_string();
+
+    assert!(
+        output.contains("# [serde (tag = \"targetType\")] pub enum CommentSchema_Target"),
+        "{output}"
+    );
+    assert!(
+        output.contains(
+            "# [serde (rename = \"post\")] Post (Box < crate :: models :: post :: Schema >)"
+        ),
+        "{output}"
+    );
+    assert!(
+        output.contains("pub target : Option < CommentSchema_Target >"),
+        "{output}"
+    );
+    assert!(
+        output.contains("match (model . target_type . as_str () , & model . target_id)"),
+        "{output}"
+    );
+    assert!(
+        output.contains("(\"photo\" , Some (id)) => match match ctx . get :: < crate :: models :: photo :: Entity , _ > (id)"),
+        "{output}"
+    );
+    assert!(
+        output.contains("crate :: models :: photo :: Entity :: find_by_id (id . clone ())"),
+        "{output}"
+    );
+    assert!(
+        output.contains("CommentSchema_Target :: Photo (Box :: new"),
+        "{output}"
+    );
+    assert!(output.contains("target : target"), "{output}");
+}
+
+#[test]
+fn test_generate_schema_type_code_polymorphic_missing_column() {
+    let storage = to_storage(vec![create_test_struct_metadata(
+        "Model",
+        r#"#[sea_orm(table_name = "comments")]
+            pub struct Model {
+                pub id: i32,
+                pub target_type: String,
+            }"#,
+    )]);
+
+    let tokens = quote!(CommentSchema from Model, polymorphic = [("target": "target_type", "target_id", [
+        ("post": crate::models::post::Entity),
+    ])]);
+    let input: SchemaTypeInput = syn::parse2(tokens).unwrap();
+    let Err(error) = generate_schema_type_code(&input, &storage) else {
+        panic!("expected an error");
+    };
+    assert!(
+        error
+            .to_string()
+            .contains("column `target_id` does not exist")
+    );
+}
+
 // Test for relation fields push into relation_fields
 
 #[test]
@@ -1562,6 +1793,68 @@ pub struct Model {
     assert!(output.contains("Vec <"));
 }
 
+#[test]
+#[serial]
+fn test_generate_schema_type_code_self_ref_parent_and_children() {
+    use tempfile::TempDir;
+
+    let temp_dir = TempDir::new().unwrap();
+    let models_dir = temp_dir.path().join("src").join("models");
+    std::fs::create_dir_all(&models_dir).unwrap();
+
+    let category_model = r#"
+#[sea_orm(table_name = "categories")]
+pub struct Model {
+    pub id: i32,
+    pub name: String,
+    pub parent_id: Option<i32>,
+    #[sea_orm(self_ref, relation_enum = "Parent", from = "parent_id", to = "id")]
+    pub parent: HasOne<Entity>,
+    #[sea_orm(self_ref, via_rel = "Parent")]
+    pub children: HasMany<Entity>,
+}
+"#;
+    std::fs::write(models_dir.join("category.rs"), category_model).unwrap();
+
+    let original_manifest_dir = std::env::var("CARGO_MANIFEST_DIR").ok();
+    // SAFETY: This is a test that runs single-threaded
+    unsafe { std::env::set_var("CARGO_MANIFEST_DIR", temp_dir.path()) };
+
+    let tokens = quote!(CategorySchema from crate::models::category::Model, pick = ["id", "name", "parent", "children"]);
+    let input: SchemaTypeInput = syn::parse2(tokens).unwrap();
+    let result = generate_schema_type_code(&input, &HashMap::new());
+
+    // SAFETY: This is a test that runs single-threaded
+    unsafe {
+        if let Some(dir) = original_manifest_dir {
+            std::env::set_var("CARGO_MANIFEST_DIR", dir);
+        } else {
+            std::env::remove_var("CARGO_MANIFEST_DIR");
+        }
+    }
+
+    let (tokens, _metadata) = result.unwrap();
+    let output = tokens.to_string();
+    // Parent recurses into the generated type itself
+    assert!(
+        output.contains("pub parent : Option < Box < CategorySchema >>"),
+        "{output}"
+    );
+    // Children are one level of flat inline items
+    assert!(
+        output.contains("pub children : Vec < CategorySchema_Children >"),
+        "{output}"
+    );
+    assert!(
+        output.contains("Box :: pin (Self :: from_model"),
+        "{output}"
+    );
+    assert!(
+        output.contains("Column :: ParentId . into_column () . eq (model . id . clone ())"),
+        "{output}"
+    );
+}
+
 #[test]
 #[serial]
 fn test_generate_schema_type_code_has_many_explicit_pick_file_not_found() {
@@ -1694,8 +1987,14 @@ fn test_maybe_generate_same_file_relation_override_returns_none_when_dto_is_miss
         is_optional: true,
         inline_type_info: None,
         relation_enum: None,
-        fk_column: None,
+        fk_columns: Vec::new(),
         via_rel: None,
+        is_self_ref: false,
+        polymorphic_targets: Vec::new(),
+        loader: None,
+        order_by: Vec::new(),
+        limit: None,
+        count: false,
     };
 
     let storage: HashMap<String, StructMetadata> = HashMap::new();
@@ -1716,8 +2015,14 @@ fn test_maybe_generate_same_file_relation_override_returns_none_for_invalid_mode
         is_optional: true,
         inline_type_info: None,
         relation_enum: None,
-        fk_column: None,
+        fk_columns: Vec::new(),
         via_rel: None,
+        is_self_ref: false,
+        polymorphic_targets: Vec::new(),
+        loader: None,
+        order_by: Vec::new(),
+        limit: None,
+        count: false,
     };
 
     let storage = to_storage(vec![create_test_struct_metadata(
@@ -2045,7 +2350,7 @@ pub struct Model {
     let fk_field = extract_belongs_to_from_field(&user_field.attrs);
     assert_eq!(
         fk_field,
-        Some("user_id".to_string()),
+        vec!["user_id".to_string()],
         "Should extract FK field from attribute"
     );
 
@@ -2087,7 +2392,7 @@ fn test_extract_belongs_to_from_field_with_equals_value() {
     let result1 = extract_belongs_to_from_field(&attrs1);
     assert_eq!(
         result1,
-        Some("user_id".to_string()),
+        vec!["user_id".to_string()],
         "Flag style should work"
     );
 
@@ -2098,7 +2403,7 @@ fn test_extract_belongs_to_from_field_with_equals_value() {
     let result2 = extract_belongs_to_from_field(&attrs2);
     assert_eq!(
         result2,
-        Some("user_id".to_string()),
+        vec!["user_id".to_string()],
         "Value style should also work"
     );
 }
@@ -12,8 +12,24 @@ use syn::{GenericArgument, PathArguments, Type};
 /// Note: `"str"` is intentionally excluded — only `is_primitive_type()` considers `str`,
 /// since it appears in parser contexts but not in schema_macro type parsing.
 pub const PRIMITIVE_TYPE_NAMES: &[&str] = &[
-    "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize", "f32",
-    "f64", "bool", "String", "Decimal",
+    "i8",
+    "i16",
+    "i32",
+    "i64",
+    "i128",
+    "isize",
+    "u8",
+    "u16",
+    "u32",
+    "u64",
+    "u128",
+    "usize",
+    "f32",
+    "f64",
+    "bool",
+    "String",
+    "Decimal",
+    "BigDecimal",
 ];
 
 /// Normalize a `TokenStream` or `Type` to a compact string by removing all whitespace.
@@ -99,10 +115,12 @@ pub fn is_seaorm_model(struct_item: &syn::ItemStruct) -> bool {
 
 /// Check if a type name is a primitive or well-known type that doesn't need path resolution.
 pub fn is_primitive_or_known_type(name: &str) -> bool {
-    matches!(
-        name,
-        // Rust primitives
-        "bool"
+    // std::num::NonZero, NonZeroU32, ...
+    name.starts_with("NonZero")
+        || matches!(
+            name,
+            // Rust primitives
+            "bool"
             | "char"
             | "str"
             | "i8"
@@ -147,11 +165,21 @@ pub fn is_primitive_or_known_type(name: &str) -> bool {
             | "Time"  // SeaORM re-export of chrono::NaiveTime
             // UUID
             | "Uuid"
-            // Decimal (rust_decimal / sea_orm re-export)
+            // Decimal (rust_decimal / sea_orm re-export) and bigdecimal
             | "Decimal"
+            | "BigDecimal"
+            // std / url types that serialize as strings
+            | "IpAddr"
+            | "Ipv4Addr"
+            | "Ipv6Addr"
+            | "SocketAddr"
+            | "SocketAddrV4"
+            | "SocketAddrV6"
+            | "PathBuf"
+            | "Url"
             // Serde JSON
             | "Value"
-    )
+        )
 }
 
 fn resolve_public_type_path(name: &str) -> Option<TokenStream> {
@@ -371,9 +399,13 @@ pub fn get_type_default(ty: &Type) -> Option<serde_json::Value> {
         Type::Path(type_path) => type_path.path.segments.last().and_then(|segment| {
             match segment.ident.to_string().as_str() {
                 "String" => Some(serde_json::Value::String(String::new())),
-                "i8" | "i16" | "i32" | "i64" | "u8" | "u16" | "u32" | "u64" | "Decimal" => {
+                "i8" | "i16" | "i32" | "i64" | "u8" | "u16" | "u32" | "u64" => {
+                    Some(serde_json::Value::Number(serde_json::Number::from(0)))
+                }
+                "Decimal" | "BigDecimal" if cfg!(feature = "decimal-number") => {
                     Some(serde_json::Value::Number(serde_json::Number::from(0)))
                 }
+                "Decimal" | "BigDecimal" => Some(serde_json::Value::String("0".to_string())),
                 "f32" | "f64" => Some(serde_json::Value::Number(
                     serde_json::Number::from_f64(0.0)
                         .unwrap_or_else(|| serde_json::Number::from(0)),
@@ -445,6 +477,10 @@ mod tests {
     #[case("DateTime", true)]
     #[case("Uuid", true)]
     #[case("Decimal", true)]
+    #[case("BigDecimal", true)]
+    #[case("IpAddr", true)]
+    #[case("NonZeroU32", true)]
+    #[case("Url", true)]
     #[case("DateTimeWithTimeZone", true)]
     #[case("CustomType", false)]
     #[case("MyStruct", false)]
@@ -752,7 +788,11 @@ mod tests {
     #[case("i32", Some(serde_json::Value::Number(serde_json::Number::from(0))))]
     #[case(
         "Decimal",
-        Some(serde_json::Value::Number(serde_json::Number::from(0)))
+        Some(if cfg!(feature = "decimal-number") {
+            serde_json::Value::Number(serde_json::Number::from(0))
+        } else {
+            serde_json::Value::String("0".to_string())
+        })
     )]
     #[case("bool", Some(serde_json::Value::Bool(false)))]
     #[case("f64", Some(serde_json::Value::Number(serde_json::Number::from_f64(0.0).unwrap())))]
@@ -15,6 +15,8 @@
 //! - [`validate_omit_fields`] - Ensure all omit fields exist
 //! - [`validate_rename_fields`] - Ensure all rename source fields exist
 //! - [`validate_partial_fields`] - Ensure all partial fields exist
+//! - [`validate_polymorphic_fields`] - Ensure polymorphic type/id columns exist
+//! - [`validate_seaorm_columns`] - Check the arguments against a `SeaORM` Model's columns
 //! - [`extract_source_field_names`] - Extract all field names from a struct
 //!
 //! # Example
@@ -27,7 +29,15 @@
 //! schema_type!(BadSchema from Model, pick = ["nonexistent"]);
 //! ```
 
-use std::collections::HashSet;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+use quote::ToTokens;
+
+use super::{
+    input::{PartialMode, PolymorphicRelation, SchemaTypeInput},
+    seaorm::{extract_sea_orm_default_value, has_sea_orm_ignore, has_sea_orm_primary_key},
+    type_utils::is_seaorm_relation_type,
+};
 
 /// Validates that all fields in `pick` exist in the source struct.
 ///
@@ -110,6 +120,34 @@ pub fn validate_rename_fields(
     Ok(())
 }
 
+/// Validates that the type and id columns of every `polymorphic` relation exist in the source struct.
+///
+/// Returns an error naming the first missing column.
+pub fn validate_polymorphic_fields(
+    relations: Option<&Vec<PolymorphicRelation>>,
+    source_field_names: &HashSet<String>,
+    source_type: &syn::Type,
+    source_type_name: &str,
+) -> Result<(), syn::Error> {
+    for relation in relations.into_iter().flatten() {
+        for column in [&relation.type_column, &relation.id_column] {
+            if !source_field_names.contains(column) {
+                return Err(syn::Error::new_spanned(
+                    source_type,
+                    format!(
+                        "polymorphic relation `{}`: column `{}` does not exist in type `{}`. Available fields: {:?}",
+                        relation.field_name,
+                        column,
+                        source_type_name,
+                        source_field_names.iter().collect::<Vec<_>>()
+                    ),
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
 /// Validates that all fields in `partial` (when specific fields are listed) exist in the source struct.
 ///
 /// Returns an error if any field in `partial` does not exist.
@@ -137,6 +175,137 @@ pub fn validate_partial_fields(
     Ok(())
 }
 
+/// Type tokens without whitespace, for comparing declared types.
+fn type_string(ty: &syn::Type) -> String {
+    ty.to_token_stream()
+        .to_string()
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect()
+}
+
+/// Validates `schema_type!` arguments against the columns of a `SeaORM` Model.
+///
+/// Every field named by `pick`, `omit`, `rename` or `partial` must be a column or a
+/// relation of the entity. `add` must not redeclare a column that is kept (not removed by
+/// `pick`, `omit` or `omit_default`), and `rename` must not collide with one. The errors
+/// name the entity's current columns, so a schema left behind by an entity change fails
+/// at expansion time with the fix spelled out.
+#[allow(clippy::too_many_lines)]
+pub fn validate_seaorm_columns(
+    input: &SchemaTypeInput,
+    parsed_struct: &syn::ItemStruct,
+    source_type_name: &str,
+) -> Result<(), syn::Error> {
+    let syn::Fields::Named(fields_named) = &parsed_struct.fields else {
+        return Ok(());
+    };
+    let mut columns: BTreeMap<String, &syn::Field> = BTreeMap::new();
+    let mut relations: BTreeSet<String> = BTreeSet::new();
+    for field in &fields_named.named {
+        let Some(ident) = &field.ident else {
+            continue;
+        };
+        let name = crate::parser::strip_raw_prefix_owned(ident.to_string());
+        if is_seaorm_relation_type(&field.ty) {
+            relations.insert(name);
+        } else if !has_sea_orm_ignore(&field.attrs) {
+            columns.insert(name, field);
+        }
+    }
+    let error = |message: String| syn::Error::new_spanned(&input.source_type, message);
+    let available = || {
+        let mut available = vec![format!(
+            "columns: {}",
+            columns.keys().cloned().collect::<Vec<_>>().join(", ")
+        )];
+        if !relations.is_empty() {
+            available.push(format!(
+                "relations: {}",
+                relations.iter().cloned().collect::<Vec<_>>().join(", ")
+            ));
+        }
+        available.join("; ")
+    };
+    let all_fields: HashSet<String> = fields_named
+        .named
+        .iter()
+        .filter_map(|field| field.ident.as_ref())
+        .map(|ident| crate::parser::strip_raw_prefix_owned(ident.to_string()))
+        .collect();
+
+    let partial = match &input.partial {
+        Some(PartialMode::Fields(fields)) => Some(fields),
+        _ => None,
+    };
+    let renamed = input
+        .rename
+        .iter()
+        .flatten()
+        .map(|(from, _)| from.clone())
+        .collect::<Vec<_>>();
+    for (argument, names) in [
+        ("pick", input.pick.as_ref()),
+        ("omit", input.omit.as_ref()),
+        ("rename", Some(&renamed)),
+        ("partial", partial),
+    ] {
+        for name in names.into_iter().flatten() {
+            if !all_fields.contains(name) {
+                return Err(error(format!(
+                    "`{argument}` names `{name}`, which is not a column of SeaORM entity `{source_type_name}` ({}). \
+                     If the entity changed, update the `schema_type!` arguments to match.",
+                    available()
+                )));
+            }
+        }
+    }
+
+    let kept = |name: &str| {
+        let omitted_default = input.omit_default
+            && columns.get(name).is_some_and(|field| {
+                extract_sea_orm_default_value(&field.attrs).is_some()
+                    || has_sea_orm_primary_key(&field.attrs)
+            });
+        !omitted_default
+            && !input.omit.iter().flatten().any(|omitted| omitted == name)
+            && input
+                .pick
+                .as_ref()
+                .is_none_or(|picked| picked.iter().any(|p| p == name))
+    };
+    for (from, to) in input.rename.iter().flatten() {
+        if from != to && columns.contains_key(to) && kept(to) {
+            return Err(error(format!(
+                "`rename` maps `{from}` to `{to}`, which is already a column of SeaORM entity `{source_type_name}`. \
+                 Omit `{to}` or choose another name."
+            )));
+        }
+    }
+    for (name, ty) in input.add.iter().flatten() {
+        let Some(column_ty) = columns.get(name).map(|field| &field.ty) else {
+            continue;
+        };
+        if !kept(name) {
+            continue;
+        }
+        return Err(if type_string(column_ty) == type_string(ty) {
+            error(format!(
+                "`add` declares `{name}`, which is already a column of SeaORM entity `{source_type_name}`. \
+                 Remove it from `add`."
+            ))
+        } else {
+            error(format!(
+                "`add` declares `{name}: {}`, but `{name}` is a `{}` column of SeaORM entity `{source_type_name}`. \
+                 Add \"{name}\" to `omit` to replace the column.",
+                ty.to_token_stream(),
+                column_ty.to_token_stream()
+            ))
+        });
+    }
+    Ok(())
+}
+
 /// Extracts all field names from a struct's named fields.
 ///
 /// Returns an empty set for tuple or unit structs.
@@ -158,6 +327,7 @@ pub fn extract_source_field_names(parsed_struct: &syn::ItemStruct) -> HashSet<St
 #[cfg(test)]
 mod tests {
     use quote::quote;
+    use rstest::rstest;
 
     use super::*;
 
@@ -241,6 +411,32 @@ mod tests {
         assert!(err.contains("does not exist"));
     }
 
+    #[rstest]
+    #[case("target_type", "target_id", None)]
+    #[case("kind", "target_id", Some("column `kind` does not exist"))]
+    #[case("target_type", "ref_id", Some("column `ref_id` does not exist"))]
+    fn test_validate_polymorphic_fields(
+        #[case] type_column: &str,
+        #[case] id_column: &str,
+        #[case] error: Option<&str>,
+    ) {
+        let source_fields = create_field_names(&["id", "target_type", "target_id"]);
+        let relations = Some(vec![PolymorphicRelation {
+            field_name: "target".to_string(),
+            type_column: type_column.to_string(),
+            id_column: id_column.to_string(),
+            targets: vec![("post".to_string(), syn::parse_quote!(post::Entity))],
+        }]);
+        let ty: syn::Type = syn::parse2(quote!(Comment)).unwrap();
+
+        let result =
+            validate_polymorphic_fields(relations.as_ref(), &source_fields, &ty, "Comment");
+        match error {
+            None => assert!(result.is_ok()),
+            Some(message) => assert!(result.unwrap_err().to_string().contains(message)),
+        }
+    }
+
     #[test]
     fn test_validate_partial_fields_success() {
         let source_fields = create_field_names(&["id", "name", "email"]);
@@ -263,6 +459,64 @@ mod tests {
         assert!(err.contains("does not exist"));
     }
 
+    const SEAORM_MODEL: &str = r#"
+        #[sea_orm::model]
+        pub struct Model {
+            #[sea_orm(primary_key)]
+            pub id: i32,
+            pub title: String,
+            #[sea_orm(ignore)]
+            pub score: f64,
+            #[sea_orm(belongs_to, from = "user_id", to = "id")]
+            pub user: BelongsTo<super::user::Entity>,
+        }
+    "#;
+
+    fn validate_seaorm(args: &proc_macro2::TokenStream) -> Result<(), syn::Error> {
+        let input: SchemaTypeInput = syn::parse2(quote!(Memo from Model, #args)).unwrap();
+        let parsed_struct: syn::ItemStruct = syn::parse_str(SEAORM_MODEL).unwrap();
+        validate_seaorm_columns(&input, &parsed_struct, "Model")
+    }
+
+    #[rstest::rstest]
+    #[case(quote!(pick = ["id", "title", "user"]))]
+    #[case(quote!(omit = ["score"], rename = [("title", "name")]))]
+    #[case(quote!(omit = ["title"], add = [("title": Option<String>)]))]
+    #[case(quote!(pick = ["id"], add = [("title": i64)]))]
+    #[case(quote!(rename = [("title", "title")], partial = ["title"]))]
+    #[case(quote!(omit_default, add = [("id": String)]))]
+    fn test_validate_seaorm_columns_success(#[case] args: proc_macro2::TokenStream) {
+        assert!(validate_seaorm(&args).is_ok());
+    }
+
+    #[rstest::rstest]
+    #[case(
+        quote!(pick = ["id", "body"]),
+        "`pick` names `body`, which is not a column of SeaORM entity `Model` (columns: id, title; relations: user)"
+    )]
+    #[case(quote!(omit = ["content"]), "`omit` names `content`")]
+    #[case(quote!(rename = [("name", "label")]), "`rename` names `name`")]
+    #[case(quote!(partial = ["body"]), "`partial` names `body`")]
+    #[case(
+        quote!(rename = [("id", "title")]),
+        "`rename` maps `id` to `title`, which is already a column"
+    )]
+    #[case(
+        quote!(add = [("title": String)]),
+        "`add` declares `title`, which is already a column"
+    )]
+    #[case(
+        quote!(add = [("id": String)]),
+        "but `id` is a `i32` column of SeaORM entity `Model`. Add \"id\" to `omit`"
+    )]
+    fn test_validate_seaorm_columns_errors(
+        #[case] args: proc_macro2::TokenStream,
+        #[case] expected: &str,
+    ) {
+        let err = validate_seaorm(&args).unwrap_err().to_string();
+        assert!(err.contains(expected), "{err}");
+    }
+
     #[test]
     fn test_extract_source_field_names_named() {
         let struct_def: syn::ItemStruct =
@@ -0,0 +1,3089 @@
+//! Core implementation of vespera! and `export_app`! macros.
+//!
+//! This module orchestrates the entire macro execution flow:
+//! - Route discovery via filesystem scanning
+//! - `OpenAPI` spec generation
+//! - File I/O for writing `OpenAPI` JSON
+//! - Router code generation
+//!
+//! # Overview
+//!
+//! This is the main orchestrator for the two primary macros:
+//! - `vespera!()` - Generates a complete Axum router with `OpenAPI` spec
+//! - `export_app!()` - Exports a router for merging into parent apps
+//!
+//! The execution flow is:
+//! 1. Parse macro arguments via [`router_codegen`]
+//! 2. Discover routes via [`collector::collect_metadata`]
+//! 3. Generate `OpenAPI` spec via [`openapi_generator`]
+//! 4. Write `OpenAPI` JSON files (if configured)
+//! 5. Generate router code via [`router_codegen::generate_router_code`]
+//!
+//! # Key Functions
+//!
+//! - [`process_vespera_macro`] - Main vespera! macro implementation
+//! - [`process_export_app`] - Main `export_app`! macro implementation
+//! - [`generate_and_write_openapi`] - `OpenAPI` generation and file I/O
+
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    fmt::Write as _,
+    hash::{Hash, Hasher},
+    path::Path,
+};
+
+use proc_macro2::Span;
+use quote::quote;
+
+use serde::{Deserialize, Serialize};
+use vespera_core::{
+    gateway::{ApiGatewayConfig, to_api_gateway},
+    kubernetes,
+    route::HttpMethod,
+};
+
+use crate::{
+    collector::{collect_file_fingerprints, collect_metadata},
+    diagnostics::Diagnostic,
+    error::{MacroResult, err_call_site},
+    file_utils::with_ignore_patterns,
+    metadata::{CollectedMetadata, StructMetadata, stable_hash},
+    openapi_generator::generate_openapi_doc_with_metadata,
+    parser::{
+        collect_defaulted_types, rename_field, with_generic_naming, with_opaque_error_response,
+        with_schema_scope, with_strict_nullability,
+    },
+    route::{apply_module_defaults, apply_tag_rules, policy_matrix},
+    route_impl::{StoredRouteInfo, route_storage_from_files},
+    router_codegen::{
+        GlobalParam, KubernetesManifests, ProcessedVesperaInput, RouterCodegenOptions,
+        generate_router_code, process_vespera_input,
+    },
+    schema_macro::schema_drift_warnings,
+};
+
+/// File name suffix of the API Gateway import document
+const API_GATEWAY_SUFFIX: &str = "apigateway";
+
+/// File name suffix of the document tagged by `sdk_group`
+const SDK_SUFFIX: &str = "sdk";
+
+/// Docs info tuple type alias for cleaner signatures
+pub type DocsInfo = (Option<String>, Option<String>, Option<String>);
+
+/// Cache for avoiding redundant route scanning and OpenAPI generation.
+/// Persisted to `target/vespera/routes.cache` across builds.
+#[derive(Serialize, Deserialize)]
+struct VesperaCache {
+    /// Macro crate version — invalidates cache when macro code changes
+    #[serde(default)]
+    macro_version: String,
+    /// File path → modification time (secs since UNIX_EPOCH)
+    file_fingerprints: HashMap<String, u64>,
+    /// Hash of SCHEMA_STORAGE contents
+    schema_hash: u64,
+    /// Hash of OpenAPI config (title, version, servers, docs_url, etc.)
+    config_hash: u64,
+    /// Cached route/struct metadata
+    metadata: CollectedMetadata,
+    /// Compact JSON for docs embedding (None if docs disabled)
+    spec_json: Option<String>,
+    /// Pretty JSON for file output (None if no openapi file configured)
+    spec_pretty: Option<String>,
+}
+
+/// Compute a deterministic hash of SCHEMA_STORAGE contents.
+fn compute_schema_hash(schema_storage: &HashMap<String, StructMetadata>) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    let mut keys: Vec<&String> = schema_storage.keys().collect();
+    keys.sort();
+    for key in keys {
+        key.hash(&mut hasher);
+        let meta = &schema_storage[key];
+        meta.name.hash(&mut hasher);
+        meta.definition.hash(&mut hasher);
+        meta.include_in_openapi.hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+/// Compute a deterministic hash of OpenAPI config fields.
+fn compute_config_hash(processed: &ProcessedVesperaInput) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    processed.title.hash(&mut hasher);
+    processed.version.hash(&mut hasher);
+    processed.docs_url.hash(&mut hasher);
+    processed.redoc_url.hash(&mut hasher);
+    processed.openapi_file_names.hash(&mut hasher);
+    processed.scopes.hash(&mut hasher);
+    processed.tag_rules.hash(&mut hasher);
+    processed.auto_methods.hash(&mut hasher);
+    processed.global_params.hash(&mut hasher);
+    processed.anyhow_error.hash(&mut hasher);
+    processed.closed_objects.hash(&mut hasher);
+    serde_json::to_string(&processed.security_schemes)
+        .unwrap_or_default()
+        .hash(&mut hasher);
+    processed.security.hash(&mut hasher);
+    // The mapping file's contents, so editing it re-exports the gateway document
+    processed.api_gateway.hash(&mut hasher);
+    processed
+        .api_gateway
+        .as_ref()
+        .and_then(|config_file| std::fs::read_to_string(config_file).ok())
+        .hash(&mut hasher);
+    processed.kubernetes.hash(&mut hasher);
+    processed.sdk_groups.hash(&mut hasher);
+    processed.formats.hash(&mut hasher);
+    processed.declaration_order.hash(&mut hasher);
+    processed.strict_nullability.hash(&mut hasher);
+    processed.generic_names.hash(&mut hasher);
+    processed.path_order.hash(&mut hasher);
+    processed.collapse_paths.hash(&mut hasher);
+    processed.path_param_case.hash(&mut hasher);
+    processed.json_schema_dialect.hash(&mut hasher);
+    if let Some(ref servers) = processed.servers {
+        for s in servers {
+            s.url.hash(&mut hasher);
+            // Sorted, as `variables` is a `HashMap`
+            let variables: Option<std::collections::BTreeMap<_, _>> = s
+                .variables
+                .as_ref()
+                .map(|variables| variables.iter().collect());
+            serde_json::to_string(&variables)
+                .unwrap_or_default()
+                .hash(&mut hasher);
+        }
+    }
+    for merge_path in &processed.merge {
+        quote!(#merge_path).to_string().hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+/// Get the path to the routes cache file.
+fn get_cache_path() -> std::path::PathBuf {
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+    let manifest_path = Path::new(&manifest_dir);
+    find_target_dir(manifest_path)
+        .join("vespera")
+        .join("routes.cache")
+}
+
+/// Try to read and deserialize a cache file. Returns None on any failure.
+fn read_cache(cache_path: &Path) -> Option<VesperaCache> {
+    let content = std::fs::read_to_string(cache_path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Write cache to disk. Failures are silently ignored (cache is best-effort).
+fn write_cache(cache_path: &Path, cache: &VesperaCache) {
+    if let Some(parent) = cache_path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    if let Ok(json) = serde_json::to_string(cache) {
+        let _ = std::fs::write(cache_path, json);
+    }
+}
+
+/// Content hashes of the generated spec, persisted to `vespera/manifest.json` under
+/// `OUT_DIR` (crates with a build script) or `target/vespera/manifest.json` so
+/// downstream generators (TS export, clients) can skip work when nothing changed.
+///
+/// Hashes are [`stable_hash`] values as 16-digit hex strings.
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpecManifest {
+    /// Hash of the spec document, absent when none is written or embedded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spec: Option<String>,
+    /// `operationId` → route hash
+    #[serde(default)]
+    pub routes: BTreeMap<String, String>,
+    /// Schema name → struct hash, for the structs in the spec
+    #[serde(default)]
+    pub schemas: BTreeMap<String, String>,
+}
+
+impl SpecManifest {
+    pub fn new(metadata: &CollectedMetadata, spec: Option<&str>) -> Self {
+        let hex = |hash: u64| format!("{hash:016x}");
+        Self {
+            spec: spec.map(|spec| hex(stable_hash(spec.as_bytes()))),
+            routes: metadata
+                .routes
+                .iter()
+                .map(|route| (route.operation_id(), hex(route.content_hash())))
+                .collect(),
+            schemas: metadata
+                .structs
+                .iter()
+                .filter(|s| s.include_in_openapi)
+                .map(|s| (s.name.clone(), hex(s.content_hash())))
+                .collect(),
+        }
+    }
+}
+
+/// Get the path to the spec manifest file.
+fn get_manifest_path() -> std::path::PathBuf {
+    manifest_path(std::env::var_os("OUT_DIR").as_deref())
+}
+
+/// `{out_dir}/vespera/manifest.json`, or next to the cache when cargo sets no `OUT_DIR`
+/// (the crate has no build script).
+fn manifest_path(out_dir: Option<&std::ffi::OsStr>) -> std::path::PathBuf {
+    out_dir.map_or_else(
+        || get_cache_path().with_file_name("manifest.json"),
+        |out_dir| Path::new(out_dir).join("vespera").join("manifest.json"),
+    )
+}
+
+/// Write `manifest` unless the file already holds it (best-effort, like the cache).
+/// Returns whether the manifest changed.
+fn write_manifest(manifest_path: &Path, manifest: &SpecManifest) -> bool {
+    let unchanged = std::fs::read_to_string(manifest_path)
+        .ok()
+        .and_then(|content| serde_json::from_str::<SpecManifest>(&content).ok())
+        .is_some_and(|existing| existing == *manifest);
+    if unchanged {
+        return false;
+    }
+    if let Some(parent) = manifest_path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    if let Ok(json) = serde_json::to_string_pretty(manifest) {
+        let _ = std::fs::write(manifest_path, json);
+    }
+    true
+}
+
+/// Write the public metadata for companion tools to `path` unless it already holds it
+/// (best-effort, like the cache).
+#[cfg(feature = "unstable-metadata")]
+fn write_api_metadata(path: &Path, metadata: &CollectedMetadata) {
+    let Ok(json) = serde_json::to_string_pretty(&metadata.to_api_metadata()) else {
+        return;
+    };
+    if std::fs::read_to_string(path).is_ok_and(|existing| existing == json) {
+        return;
+    }
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    let _ = std::fs::write(path, json);
+}
+
+/// Generate `OpenAPI` JSON and write to files, returning docs info
+///
+/// Specs of `merge`d apps are read from the target directory of `manifest_dir`.
+#[allow(clippy::too_many_lines)]
+pub fn generate_and_write_openapi(
+    input: &ProcessedVesperaInput,
+    metadata: &CollectedMetadata,
+    file_asts: HashMap<String, syn::File>,
+    route_storage: &[StoredRouteInfo],
+    manifest_dir: &Path,
+) -> MacroResult<DocsInfo> {
+    if input.openapi_file_names.is_empty()
+        && input.docs_url.is_none()
+        && input.redoc_url.is_none()
+        && input.kubernetes.is_none()
+    {
+        return Ok((None, None, None));
+    }
+
+    // Per-scope documents need their own pass over the file ASTs
+    let scoped_file_asts = if input.openapi_file_names.is_empty() || input.scopes.is_empty() {
+        None
+    } else {
+        Some(file_asts.clone())
+    };
+
+    let anyhow_error = input
+        .anyhow_error
+        .as_ref()
+        .map(|(status, body)| (*status, body.as_str()));
+    let format_patterns = format_patterns(&input.formats);
+    let (openapi_doc, defaulted_types) =
+        build_openapi_doc(input, metadata, file_asts, route_storage, manifest_dir);
+    report_defaulted_types(defaulted_types, metadata)?;
+
+    // Pretty-print for user-visible files
+    if !input.openapi_file_names.is_empty() {
+        let json_pretty = serde_json::to_string_pretty(&openapi_doc).map_err(|e| Diagnostic::SpecSerialization.error_call_site(format_args!("OpenAPI generation: failed to serialize document to JSON. Error: {e}. Check that all schema types are serializable.")))?;
+        for openapi_file_name in &input.openapi_file_names {
+            write_openapi_file(openapi_file_name, &json_pretty)?;
+        }
+        if let Some(config_file) = &input.api_gateway {
+            write_api_gateway_files(&openapi_doc, config_file, &input.openapi_file_names)?;
+        }
+        if input.sdk_groups {
+            let sdk_doc = openapi_doc.group_by_sdk_group();
+            let json_pretty = serde_json::to_string_pretty(&sdk_doc).map_err(|e| Diagnostic::SpecSerialization.error_call_site(format_args!("OpenAPI generation: failed to serialize SDK document to JSON. Error: {e}. Check that all schema types are serializable.")))?;
+            for openapi_file_name in &input.openapi_file_names {
+                write_openapi_file(
+                    &scoped_file_name(openapi_file_name, SDK_SUFFIX),
+                    &json_pretty,
+                )?;
+            }
+        }
+    }
+
+    if let Some(manifests) = &input.kubernetes {
+        write_kubernetes_manifests(&openapi_doc, manifests)?;
+    }
+
+    // Per-scope documents include fields marked `#[schema(scope = "...")]`
+    if let Some(file_asts) = scoped_file_asts {
+        for scope in &input.scopes {
+            let mut scoped_doc = with_schema_scope(Some(scope), || {
+                with_generic_naming(input.generic_names, || {
+                    with_strict_nullability(input.strict_nullability, || {
+                        with_opaque_error_response(anyhow_error, || {
+                            generate_openapi_doc_with_metadata(
+                                input.title.clone(),
+                                input.version.clone(),
+                                input.servers.clone(),
+                                metadata,
+                                Some(file_asts.clone()),
+                                route_storage,
+                            )
+                        })
+                    })
+                })
+            });
+            merge_child_specs(&mut scoped_doc, &input.merge, manifest_dir);
+            add_auto_operations(&mut scoped_doc, &input.auto_methods);
+            add_global_params(&mut scoped_doc, &input.global_params);
+            scoped_doc.add_security_schemes(&input.security_schemes);
+            scoped_doc.security.clone_from(&input.security);
+            scoped_doc
+                .json_schema_dialect
+                .clone_from(&input.json_schema_dialect);
+            if input.closed_objects {
+                scoped_doc.close_objects();
+            }
+            scoped_doc.apply_format_patterns(&format_patterns);
+            if !input.declaration_order {
+                scoped_doc.sort_properties();
+            }
+            layout_paths(&mut scoped_doc, input);
+            let json_pretty = serde_json::to_string_pretty(&scoped_doc).map_err(|e| Diagnostic::SpecSerialization.error_call_site(format_args!("OpenAPI generation: failed to serialize `{scope}` scope document to JSON. Error: {e}. Check that all schema types are serializable.")))?;
+            for openapi_file_name in &input.openapi_file_names {
+                write_openapi_file(&scoped_file_name(openapi_file_name, scope), &json_pretty)?;
+            }
+        }
+    }
+
+    // Compact JSON for embedding (smaller binary, faster downstream compilation)
+    let spec_json = if input.docs_url.is_some() || input.redoc_url.is_some() {
+        Some(serde_json::to_string(&openapi_doc).map_err(|e| Diagnostic::SpecSerialization.error_call_site(format_args!("OpenAPI generation: failed to serialize document to JSON. Error: {e}. Check that all schema types are serializable.")))?)
+    } else {
+        None
+    };
+
+    Ok((input.docs_url.clone(), input.redoc_url.clone(), spec_json))
+}
+
+/// Build the `OpenAPI` document of a `vespera!` call, with the unknown types that were
+/// documented as plain objects.
+fn build_openapi_doc(
+    input: &ProcessedVesperaInput,
+    metadata: &CollectedMetadata,
+    file_asts: HashMap<String, syn::File>,
+    route_storage: &[StoredRouteInfo],
+    manifest_dir: &Path,
+) -> (vespera_core::openapi::OpenApi, BTreeSet<String>) {
+    let anyhow_error = input
+        .anyhow_error
+        .as_ref()
+        .map(|(status, body)| (*status, body.as_str()));
+    let (mut openapi_doc, defaulted_types) = collect_defaulted_types(|| {
+        with_generic_naming(input.generic_names, || {
+            with_strict_nullability(input.strict_nullability, || {
+                with_opaque_error_response(anyhow_error, || {
+                    generate_openapi_doc_with_metadata(
+                        input.title.clone(),
+                        input.version.clone(),
+                        input.servers.clone(),
+                        metadata,
+                        Some(file_asts),
+                        route_storage,
+                    )
+                })
+            })
+        })
+    });
+
+    // Merge specs from child apps at compile time
+    merge_child_specs(&mut openapi_doc, &input.merge, manifest_dir);
+    add_auto_operations(&mut openapi_doc, &input.auto_methods);
+    add_global_params(&mut openapi_doc, &input.global_params);
+    openapi_doc.add_security_schemes(&input.security_schemes);
+    openapi_doc.security.clone_from(&input.security);
+    openapi_doc
+        .json_schema_dialect
+        .clone_from(&input.json_schema_dialect);
+    if input.closed_objects {
+        openapi_doc.close_objects();
+    }
+    openapi_doc.apply_format_patterns(&format_patterns(&input.formats));
+    if !input.declaration_order {
+        openapi_doc.sort_properties();
+    }
+    layout_paths(&mut openapi_doc, input);
+    (openapi_doc, defaulted_types)
+}
+
+/// Validation patterns of the custom formats that declare one.
+fn format_patterns(formats: &BTreeMap<String, Option<String>>) -> BTreeMap<String, String> {
+    formats
+        .iter()
+        .filter_map(|(name, pattern)| Some((name.clone(), pattern.clone()?)))
+        .collect()
+}
+
+/// Apply `collapse_paths`, `path_param_case` and `path_order` to `paths`.
+fn layout_paths(openapi_doc: &mut vespera_core::openapi::OpenApi, input: &ProcessedVesperaInput) {
+    if input.collapse_paths {
+        openapi_doc.collapse_equivalent_paths();
+    }
+    if let Some(case) = input.path_param_case.as_deref() {
+        openapi_doc.rename_path_params(|name| rename_field(name, Some(case)));
+    }
+    openapi_doc.sort_paths(input.path_order);
+}
+
+/// Document automatically generated HEAD/OPTIONS operations.
+///
+/// Axum answers HEAD for every GET route, so HEAD only needs documenting; OPTIONS
+/// handlers are added to the router by `generate_router_code`.
+fn add_auto_operations(openapi_doc: &mut vespera_core::openapi::OpenApi, methods: &[HttpMethod]) {
+    if methods.contains(&HttpMethod::Head) {
+        openapi_doc.add_head_operations();
+    }
+    if methods.contains(&HttpMethod::Options) {
+        openapi_doc.add_options_operations();
+    }
+}
+
+/// Reference `global_params` from every operation, backed by `components/parameters`.
+fn add_global_params(openapi_doc: &mut vespera_core::openapi::OpenApi, params: &[GlobalParam]) {
+    let components: Vec<_> = params.iter().map(GlobalParam::to_component).collect();
+    openapi_doc.add_global_parameters(&components);
+}
+
+/// Merge specs written by `export_app!` child apps, found in the target directory of
+/// `manifest_dir`, into `openapi_doc`.
+fn merge_child_specs(
+    openapi_doc: &mut vespera_core::openapi::OpenApi,
+    merge: &[syn::Path],
+    manifest_dir: &Path,
+) {
+    if merge.is_empty() {
+        return;
+    }
+    let target_dir = find_target_dir(manifest_dir);
+    let vespera_dir = target_dir.join("vespera");
+
+    for merge_path in merge {
+        // Extract the struct name (last segment, e.g., "ThirdApp" from "third::ThirdApp")
+        if let Some(last_segment) = merge_path.segments.last() {
+            let struct_name = last_segment.ident.to_string();
+            let spec_file = vespera_dir.join(format!("{struct_name}.openapi.json"));
+
+            if let Ok(spec_content) = std::fs::read_to_string(&spec_file)
+                && let Ok(child_spec) =
+                    serde_json::from_str::<vespera_core::openapi::OpenApi>(&spec_content)
+            {
+                openapi_doc.merge(child_spec, None);
+            }
+        }
+    }
+}
+
+/// Write a pretty-printed spec to `openapi_file_name`, skipping unchanged files.
+fn write_openapi_file(openapi_file_name: &str, json_pretty: &str) -> MacroResult<()> {
+    let file_path = Path::new(openapi_file_name);
+    if let Some(parent) = file_path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| Diagnostic::SpecWrite.error_call_site(format_args!("OpenAPI output: failed to create directory '{}'. Error: {}. Ensure the path is valid and writable.", parent.display(), e)))?;
+    }
+    let should_write =
+        std::fs::read_to_string(file_path).map_or(true, |existing| existing != json_pretty);
+    if should_write {
+        std::fs::write(file_path, json_pretty).map_err(|e| Diagnostic::SpecWrite.error_call_site(format_args!("OpenAPI output: failed to write file '{openapi_file_name}'. Error: {e}. Ensure the file path is writable.")))?;
+    }
+    Ok(())
+}
+
+/// Write the API Gateway import document next to each `openapi` file
+/// (`openapi.json` -> `openapi.apigateway.json`), integrated as mapped in `config_file`.
+fn write_api_gateway_files(
+    openapi_doc: &vespera_core::openapi::OpenApi,
+    config_file: &str,
+    openapi_file_names: &[String],
+) -> MacroResult<()> {
+    let config = std::fs::read_to_string(config_file).map_err(|e| err_call_site(format!("API Gateway export: failed to read mapping file '{config_file}'. Error: {e}. The path is relative to the crate root.")))?;
+    let config: ApiGatewayConfig = serde_json::from_str(&config).map_err(|e| err_call_site(format!("API Gateway export: invalid mapping file '{config_file}'. Error: {e}. Expected `{{ \"integration\": {{ \"type\": ..., \"uri\": ... }}, \"operations\": {{ ... }} }}`.")))?;
+    let spec = to_api_gateway(openapi_doc, &config).map_err(|e| {
+        err_call_site(format!(
+            "API Gateway export: {e} (mapping file '{config_file}')."
+        ))
+    })?;
+    let json_pretty = serde_json::to_string_pretty(&spec).map_err(|e| {
+        err_call_site(format!(
+            "API Gateway export: failed to serialize document to JSON. Error: {e}."
+        ))
+    })?;
+    for openapi_file_name in openapi_file_names {
+        write_openapi_file(
+            &scoped_file_name(openapi_file_name, API_GATEWAY_SUFFIX),
+            &json_pretty,
+        )?;
+    }
+    Ok(())
+}
+
+/// Write the `HTTPRoute` / `Ingress` manifests generated from the document's routes.
+fn write_kubernetes_manifests(
+    openapi_doc: &vespera_core::openapi::OpenApi,
+    manifests: &KubernetesManifests,
+) -> MacroResult<()> {
+    if let Some(file_name) = &manifests.http_route {
+        write_openapi_file(
+            file_name,
+            &kubernetes::http_route(openapi_doc, &manifests.config),
+        )?;
+    }
+    if let Some(file_name) = &manifests.ingress {
+        write_openapi_file(
+            file_name,
+            &kubernetes::ingress(openapi_doc, &manifests.config),
+        )?;
+    }
+    Ok(())
+}
+
+/// Output path of a scope's document: `openapi.json` -> `openapi.admin.json`.
+pub fn scoped_file_name(openapi_file_name: &str, scope: &str) -> String {
+    let path = Path::new(openapi_file_name);
+    let stem = path
+        .file_stem()
+        .map_or_else(String::new, |s| s.to_string_lossy().into_owned());
+    let file_name = path.extension().map_or_else(
+        || format!("{stem}.{scope}"),
+        |ext| format!("{stem}.{scope}.{}", ext.to_string_lossy()),
+    );
+    path.with_file_name(file_name).display().to_string()
+}
+
+/// Find the folder path for route scanning
+pub fn find_folder_path(folder_name: &str) -> MacroResult<std::path::PathBuf> {
+    let root = std::env::var("CARGO_MANIFEST_DIR").map_err(|_| {
+        err_call_site(
+            "CARGO_MANIFEST_DIR is not set. vespera macros must be used within a cargo build.",
+        )
+    })?;
+    let path = format!("{root}/src/{folder_name}");
+    let path = Path::new(&path);
+    if path.exists() && path.is_dir() {
+        return Ok(path.to_path_buf());
+    }
+
+    Ok(Path::new(folder_name).to_path_buf())
+}
+
+/// Warn about unknown types documented as plain objects, except generic parameters
+/// of collected structs and enums.
+fn report_defaulted_types(
+    defaulted_types: BTreeSet<String>,
+    metadata: &CollectedMetadata,
+) -> MacroResult<()> {
+    let generic_params: HashSet<String> = metadata
+        .structs
+        .iter()
+        .filter_map(|s| syn::parse_str::<syn::DeriveInput>(&s.definition).ok())
+        .flat_map(|item| {
+            item.generics
+                .type_params()
+                .map(|param| param.ident.to_string())
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    for name in defaulted_types {
+        if !generic_params.contains(&name) {
+            Diagnostic::UnknownTypeDefaulted.report(format_args!(
+                "type `{name}` is documented as a plain object: it has no `#[derive(Schema)]` vespera can see. Derive `Schema` on it, or on a mirror with `#[schema(remote = \"...\")]` for a type from another crate."
+            ))?;
+        }
+    }
+    Ok(())
+}
+
+/// Report route files that could not be read or parsed: all of them in one error, or with
+/// `skip` one warning each, leaving them out of the router and spec.
+fn report_invalid_files(metadata: &CollectedMetadata, skip: bool) -> MacroResult<()> {
+    if metadata.invalid_files.is_empty() {
+        return Ok(());
+    }
+    if skip {
+        for file in &metadata.invalid_files {
+            eprintln!(
+                "vespera: warning: {}",
+                Diagnostic::RouteFileUnparsable.message(format_args!(
+                    "skipping route file '{}' \u{2014} {}",
+                    file.file_path, file.error
+                ))
+            );
+        }
+        return Ok(());
+    }
+    let mut files = String::new();
+    for file in &metadata.invalid_files {
+        let _ = write!(files, "\n  {}: {}", file.file_path, file.error);
+    }
+    Err(Diagnostic::RouteFileUnparsable.error_call_site(format_args!(
+        "cannot read or parse {} route file(s):{files}\nFix the Rust syntax errors in these files, or set `skip_invalid_files = true` in `vespera!` to leave them out of the router and spec.",
+        metadata.invalid_files.len()
+    )))
+}
+
+/// Complete the metadata collected from the route folder of a `vespera!` call: add
+/// `structs`, merge what `#[route]` recorded, apply module defaults and tag rules, then
+/// check the result. Shared by builds and docs pages regenerating their spec.
+fn prepare_metadata(
+    metadata: &mut CollectedMetadata,
+    processed: &ProcessedVesperaInput,
+    structs: impl IntoIterator<Item = StructMetadata>,
+    route_storage: &[StoredRouteInfo],
+) -> MacroResult<()> {
+    metadata.structs.extend(structs);
+    merge_route_storage_data(metadata, route_storage);
+    apply_module_defaults(&mut metadata.routes, &metadata.module_defaults);
+    apply_tag_rules(&mut metadata.routes, &processed.tag_rules);
+    metadata
+        .check_duplicate_schema_names()
+        .and_then(|()| metadata.check_link_targets())
+        .map_err(|msg| syn::Error::new(Span::call_site(), format!("vespera! macro: {msg}")))?;
+    report_invalid_files(metadata, processed.skip_invalid_files)?;
+    report_unknown_methods(metadata)
+}
+
+/// Warn about routes whose HTTP method is unknown; they are left out of the router and spec.
+fn report_unknown_methods(metadata: &CollectedMetadata) -> MacroResult<()> {
+    for route in &metadata.routes {
+        if HttpMethod::try_from(route.method.as_str()).is_err() {
+            Diagnostic::UnknownHttpMethod.report(format_args!(
+                "skipping route '{}' \u{2014} unknown HTTP method '{}'",
+                route.path, route.method
+            ))?;
+        }
+    }
+    Ok(())
+}
+
+/// Warn about `SeaORM` entity columns that the migrations at `migrations`
+/// (relative to the crate root) do not create.
+fn check_schema_drift(migrations: &str) -> MacroResult<()> {
+    let root = std::env::var("CARGO_MANIFEST_DIR").map_err(|_| {
+        err_call_site(
+            "CARGO_MANIFEST_DIR is not set. vespera macros must be used within a cargo build.",
+        )
+    })?;
+    let root = Path::new(&root);
+    let warnings = schema_drift_warnings(&root.join("src"), &root.join(migrations))
+        .map_err(|e| err_call_site(format!("vespera! macro: migrations: {e}")))?;
+    for warning in warnings {
+        Diagnostic::SchemaDrift.report(warning)?;
+    }
+    Ok(())
+}
+
+/// Find the workspace root's target directory
+pub fn find_target_dir(manifest_path: &Path) -> std::path::PathBuf {
+    // Look for workspace root by finding a Cargo.toml with [workspace] section
+    let mut current = Some(manifest_path);
+    let mut last_with_lock = None;
+
+    while let Some(dir) = current {
+        // Check if this directory has Cargo.lock
+        if dir.join("Cargo.lock").exists() {
+            last_with_lock = Some(dir.to_path_buf());
+        }
+
+        // Check if this is a workspace root (has Cargo.toml with [workspace])
+        let cargo_toml = dir.join("Cargo.toml");
+        if cargo_toml.exists()
+            && let Ok(contents) = std::fs::read_to_string(&cargo_toml)
+            && contents.contains("[workspace]")
+        {
+            return dir.join("target");
+        }
+
+        current = dir.parent();
+    }
+
+    // If we found a Cargo.lock but no [workspace], use the topmost one
+    if let Some(lock_dir) = last_with_lock {
+        return lock_dir.join("target");
+    }
+
+    // Fallback: use manifest dir's target
+    manifest_path.join("target")
+}
+
+/// Supplement collector's `RouteMetadata` with data from `ROUTE_STORAGE`.
+///
+/// `#[route]` stores metadata at attribute expansion time.
+/// `collector.rs` re-parses the same data from file ASTs.
+/// This function merges ROUTE_STORAGE data into collector's output,
+/// preferring ROUTE_STORAGE values when they provide richer info.
+///
+/// Matching is by function name. If multiple routes share a function name,
+/// the match is ambiguous and ROUTE_STORAGE data is skipped for safety.
+fn merge_route_storage_data(metadata: &mut CollectedMetadata, route_storage: &[StoredRouteInfo]) {
+    if route_storage.is_empty() {
+        return;
+    }
+
+    for route in &mut metadata.routes {
+        // Find matching StoredRouteInfo by function name
+        let mut matches = route_storage
+            .iter()
+            .filter(|s| s.fn_name == route.function_name);
+
+        let Some(stored) = matches.next() else {
+            continue;
+        };
+
+        // Skip if ambiguous (multiple routes with same function name)
+        if matches.next().is_some() {
+            continue;
+        }
+
+        // Supplement with ROUTE_STORAGE data
+        // Only override when ROUTE_STORAGE has an explicit value
+        if let Some(ref tags) = stored.tags {
+            route.tags = Some(tags.clone());
+        }
+        if let Some(ref desc) = stored.description {
+            route.description = Some(desc.clone());
+        }
+        if let Some(ref summary) = stored.summary {
+            route.summary = Some(summary.clone());
+        }
+        if stored.deprecated {
+            route.deprecated = true;
+        }
+        if let Some(ref status) = stored.error_status {
+            route.error_status = Some(status.clone());
+        }
+        if let Some(ref security) = stored.security {
+            route.security = Some(security.clone());
+        }
+        if stored.limits.is_some() {
+            route.limits = stored.limits;
+        }
+        if stored.cache.is_some() {
+            route.cache = stored.cache;
+        }
+        if stored.status.is_some() {
+            route.status = stored.status;
+        }
+        if let Some(ref sdk_group) = stored.sdk_group {
+            route.sdk_group = Some(sdk_group.clone());
+        }
+        if let Some(ref operation_id) = stored.operation_id {
+            route.operation_id = Some(operation_id.clone());
+        }
+        if let Some(ref content_type) = stored.content_type {
+            route.content_type = Some(content_type.clone());
+        }
+        if let Some(ref sse_event) = stored.sse_event {
+            route.sse_event = Some(sse_event.clone());
+        }
+        if let Some(ref params) = stored.params {
+            route.params = Some(params.clone());
+        }
+        if let Some(ref links) = stored.links {
+            route.links = Some(links.clone());
+        }
+        if let Some(ref request_example) = stored.request_example {
+            route.request_example = Some(request_example.clone());
+        }
+        if let Some(ref response_example) = stored.response_example {
+            route.response_example = Some(response_example.clone());
+        }
+    }
+}
+
+/// Write cached OpenAPI spec to output files if they are stale or missing.
+pub fn ensure_openapi_files_from_cache(
+    openapi_file_names: &[String],
+    spec_pretty: Option<&str>,
+) -> syn::Result<()> {
+    let Some(pretty) = spec_pretty else {
+        return Ok(());
+    };
+    for openapi_file_name in openapi_file_names {
+        let file_path = Path::new(openapi_file_name);
+        let should_write =
+            std::fs::read_to_string(file_path).map_or(true, |existing| existing != *pretty);
+        if should_write {
+            if let Some(parent) = file_path.parent() {
+                std::fs::create_dir_all(parent).map_err(|e| {
+                    syn::Error::new(
+                        Span::call_site(),
+                        format!(
+                            "OpenAPI output: failed to create directory '{}': {}",
+                            parent.display(),
+                            e
+                        ),
+                    )
+                })?;
+            }
+            std::fs::write(file_path, pretty).map_err(|e| {
+                syn::Error::new(
+                    Span::call_site(),
+                    format!("OpenAPI output: failed to write file '{openapi_file_name}': {e}"),
+                )
+            })?;
+        }
+    }
+    Ok(())
+}
+
+/// Write compact spec JSON to target dir for `include_str!` embedding.
+fn write_spec_for_embedding(
+    spec_json: Option<String>,
+) -> syn::Result<Option<proc_macro2::TokenStream>> {
+    let Some(json) = spec_json else {
+        return Ok(None);
+    };
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+    let manifest_path = Path::new(&manifest_dir);
+    let target_dir = find_target_dir(manifest_path);
+    let vespera_dir = target_dir.join("vespera");
+    std::fs::create_dir_all(&vespera_dir).map_err(|e| {
+        syn::Error::new(
+            Span::call_site(),
+            format!(
+                "vespera! macro: failed to create directory '{}': {}",
+                vespera_dir.display(),
+                e
+            ),
+        )
+    })?;
+    let spec_file = vespera_dir.join("vespera_spec.json");
+    let should_write =
+        std::fs::read_to_string(&spec_file).map_or(true, |existing| existing != json);
+    if should_write {
+        std::fs::write(&spec_file, &json).map_err(|e| {
+            syn::Error::new(
+                Span::call_site(),
+                format!(
+                    "vespera! macro: failed to write spec file '{}': {}",
+                    spec_file.display(),
+                    e
+                ),
+            )
+        })?;
+    }
+    let path_str = spec_file.display().to_string().replace('\\', "/");
+    Ok(Some(quote::quote! { include_str!(#path_str) }))
+}
+
+/// Process vespera macro - extracted for testability
+#[allow(clippy::too_many_lines)]
+///
+/// `source` is the macro input, which docs pages regenerating their spec (`hot-reload`
+/// feature) parse again at runtime.
+pub fn process_vespera_macro(
+    processed: &ProcessedVesperaInput,
+    schema_storage: &HashMap<String, StructMetadata>,
+    route_storage: &[StoredRouteInfo],
+    source: &str,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let profile_start = if std::env::var("VESPERA_PROFILE").is_ok() {
+        Some(std::time::Instant::now())
+    } else {
+        None
+    };
+
+    let folder_path = find_folder_path(&processed.folder_name)?;
+    if !folder_path.exists() {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            format!(
+                "vespera! macro: route folder '{}' not found. Create src/{} or specify a different folder with `dir = \"your_folder\"`.",
+                processed.folder_name, processed.folder_name
+            ),
+        ));
+    }
+
+    if let Some(migrations) = &processed.migrations {
+        check_schema_drift(migrations)?;
+    }
+
+    // --- Incremental cache check ---
+    let cache_path = get_cache_path();
+    let fingerprints = with_ignore_patterns(&processed.ignore, || {
+        collect_file_fingerprints(&folder_path)
+    })
+    .map_err(|e| syn::Error::new(Span::call_site(), format!("vespera! macro: {e}")))?;
+    let schema_hash = compute_schema_hash(schema_storage);
+    let config_hash = compute_config_hash(processed);
+
+    let macro_version = env!("CARGO_PKG_VERSION").to_string();
+    let cached = read_cache(&cache_path);
+    let cache_hit = cached.as_ref().is_some_and(|c| {
+        c.macro_version == macro_version
+            && c.file_fingerprints == fingerprints
+            && c.schema_hash == schema_hash
+            && c.config_hash == config_hash
+            // Scoped documents are not cached; regenerate if any is missing
+            && processed.scopes.iter().all(|scope| {
+                processed
+                    .openapi_file_names
+                    .iter()
+                    .all(|f| Path::new(&scoped_file_name(f, scope)).exists())
+            })
+            && (processed.api_gateway.is_none()
+                || processed
+                    .openapi_file_names
+                    .iter()
+                    .all(|f| Path::new(&scoped_file_name(f, API_GATEWAY_SUFFIX)).exists()))
+            && (!processed.sdk_groups
+                || processed
+                    .openapi_file_names
+                    .iter()
+                    .all(|f| Path::new(&scoped_file_name(f, SDK_SUFFIX)).exists()))
+            && processed.kubernetes.as_ref().is_none_or(|manifests| {
+                [&manifests.http_route, &manifests.ingress]
+                    .into_iter()
+                    .flatten()
+                    .all(|f| Path::new(f).exists())
+            })
+    });
+
+    let (metadata, spec_json, spec_pretty) = if cache_hit {
+        let cache = cached.unwrap();
+        let mut metadata = cache.metadata;
+        prepare_metadata(
+            &mut metadata,
+            processed,
+            schema_storage.values().cloned(),
+            route_storage,
+        )?;
+
+        // Ensure openapi.json files exist and are up-to-date from cache
+        ensure_openapi_files_from_cache(
+            &processed.openapi_file_names,
+            cache.spec_pretty.as_deref(),
+        )?;
+
+        (metadata, cache.spec_json, cache.spec_pretty)
+    } else {
+        let (mut metadata, file_asts) = with_ignore_patterns(&processed.ignore, || collect_metadata(&folder_path, &processed.folder_name, route_storage)).map_err(|e| syn::Error::new(Span::call_site(), format!("vespera! macro: failed to scan route folder '{}'. Error: {}. Check that all .rs files have valid Rust syntax.", processed.folder_name, e)))?;
+
+        // Clone metadata before extending (cache stores file-only structs)
+        let cache_metadata = metadata.clone();
+        prepare_metadata(
+            &mut metadata,
+            processed,
+            schema_storage.values().cloned(),
+            route_storage,
+        )?;
+
+        let (_, _, spec_json) = generate_and_write_openapi(
+            processed,
+            &metadata,
+            file_asts,
+            route_storage,
+            Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default()),
+        )?;
+
+        // Read back spec_pretty from first openapi file for caching
+        let spec_pretty = processed
+            .openapi_file_names
+            .first()
+            .and_then(|f| std::fs::read_to_string(f).ok());
+
+        // Persist cache (best-effort, failures are silent)
+        write_cache(
+            &cache_path,
+            &VesperaCache {
+                macro_version: macro_version.clone(),
+                file_fingerprints: fingerprints,
+                schema_hash,
+                config_hash,
+                metadata: cache_metadata,
+                spec_json: spec_json.clone(),
+                spec_pretty: spec_pretty.clone(),
+            },
+        );
+
+        (metadata, spec_json, spec_pretty)
+    };
+
+    // Content hashes for downstream generators
+    let manifest = SpecManifest::new(&metadata, spec_pretty.as_deref().or(spec_json.as_deref()));
+    let spec_changed = write_manifest(&get_manifest_path(), &manifest);
+    #[cfg(feature = "unstable-metadata")]
+    write_api_metadata(&get_cache_path().with_file_name("metadata.json"), &metadata);
+
+    // Write compact spec for include_str! embedding
+    let spec_tokens = write_spec_for_embedding(spec_json)?;
+    let spec_reload = if cfg!(feature = "hot-reload") && spec_tokens.is_some() {
+        Some(spec_reload_tokens(source, schema_storage)?)
+    } else {
+        None
+    };
+
+    // --- Cron job discovery from CRON_STORAGE ---
+    // #[cron("...")] attribute already registers metadata at expansion time.
+    // No folder scanning needed — just read the storage.
+    let cron_jobs: Vec<crate::metadata::CronMetadata> = {
+        let storage = crate::CRON_STORAGE
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let src_dir = std::env::var("CARGO_MANIFEST_DIR")
+            .map(|d| {
+                let p = std::path::PathBuf::from(d).join("src");
+                // Canonicalize for reliable prefix stripping
+                let canonical = p.canonicalize().unwrap_or(p);
+                canonical.display().to_string().replace('\\', "/")
+            })
+            .unwrap_or_default();
+        storage
+            .iter()
+            .map(|s| {
+                // Derive module path from file_path relative to src/
+                let module_path = s
+                    .file_path
+                    .as_ref()
+                    .map(|fp| {
+                        let canonical = std::path::Path::new(fp)
+                            .canonicalize()
+                            .map_or_else(|_| fp.clone(), |p| p.display().to_string());
+                        let normalized = canonical.replace('\\', "/");
+                        let relative = normalized
+                            .strip_prefix(&src_dir)
+                            .map_or(&*normalized, |rest| rest.trim_start_matches('/'));
+                        // Convert path to module path: strip .rs, replace / with ::, strip mod
+                        // Replace hyphens with underscores (Rust module convention)
+                        relative
+                            .trim_end_matches(".rs")
+                            .replace('/', "::")
+                            .replace('-', "_")
+                            .trim_end_matches("::mod")
+                            .to_string()
+                    })
+                    .unwrap_or_default();
+                crate::metadata::CronMetadata {
+                    expression: s.expression.clone(),
+                    function_name: s.fn_name.clone(),
+                    module_path,
+                    file_path: s.file_path.clone().unwrap_or_default(),
+                }
+            })
+            .collect()
+    };
+
+    let router_code = generate_router_code(
+        &metadata,
+        RouterCodegenOptions {
+            docs_url: processed.docs_url.as_deref(),
+            redoc_url: processed.redoc_url.as_deref(),
+            spec_tokens,
+            merge_apps: &processed.merge,
+            cron_jobs: &cron_jobs,
+            auto_options: processed.auto_methods.contains(&HttpMethod::Options),
+            instrument: processed.instrument,
+            spec_reload: spec_reload.as_ref(),
+        },
+    );
+
+    // Policy matrix served by `vespera::policies()`
+    let policies = policy_matrix(&metadata.routes, processed.security.as_deref());
+    let policies_json = serde_json::to_string(&policies).map_err(|e| {
+        syn::Error::new(
+            Span::call_site(),
+            format!("vespera! macro: failed to serialize the policy matrix. Error: {e}."),
+        )
+    })?;
+    // Custom format patterns checked by `vespera::formats::check()`
+    let (format_names, patterns): (Vec<String>, Vec<String>) =
+        format_patterns(&processed.formats).into_iter().unzip();
+    let result = Ok(quote! {
+        {
+            vespera::policy::register(#policies_json);
+            vespera::formats::register(&[#((#format_names, #patterns)),*]);
+            #router_code
+        }
+    });
+
+    if let Some(start) = profile_start {
+        eprintln!(
+            "[vespera-profile] vespera! macro total: {:?}{}",
+            start.elapsed(),
+            if spec_changed {
+                ""
+            } else {
+                " (no spec changes)"
+            }
+        );
+        crate::schema_macro::print_profile_summary();
+    }
+
+    result
+}
+
+/// Call of `vespera::dev::reload_spec` regenerating the spec of the docs pages, with the
+/// structs registered by `#[derive(Schema)]` in this build.
+fn spec_reload_tokens(
+    source: &str,
+    schema_storage: &HashMap<String, StructMetadata>,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let mut structs: Vec<&StructMetadata> = schema_storage.values().collect();
+    structs.sort_by(|a, b| a.name.cmp(&b.name));
+    let schemas = serde_json::to_string(&structs).map_err(|e| {
+        Diagnostic::SpecSerialization.error_call_site(format_args!(
+            "vespera! macro: failed to serialize the registered schemas for the spec reload. Error: {e}."
+        ))
+    })?;
+    Ok(quote! {
+        vespera::dev::reload_spec(env!("CARGO_MANIFEST_DIR"), #source, #schemas)
+    })
+}
+
+/// Regenerate the compact spec of a `vespera!` call from the current source tree.
+///
+/// `input` is the macro input and `schemas` the JSON of the structs registered by
+/// `#[derive(Schema)]` when the app was built; the route folder under `manifest_dir`
+/// is scanned again, so edited doc comments and attributes show up without a rebuild.
+/// Errors name what could not be read or parsed.
+pub fn regenerate_spec(manifest_dir: &Path, input: &str, schemas: &str) -> Result<String, String> {
+    let tokens: proc_macro2::TokenStream = input
+        .parse()
+        .map_err(|e| format!("invalid `vespera!` input: {e}"))?;
+    let processed = process_vespera_input(
+        syn::parse2(tokens).map_err(|e| format!("invalid `vespera!` input: {e}"))?,
+    );
+    let structs: Vec<StructMetadata> =
+        serde_json::from_str(schemas).map_err(|e| format!("invalid registered schemas: {e}"))?;
+
+    let folder_path = manifest_dir.join("src").join(&processed.folder_name);
+    let (mut metadata, file_asts) = with_ignore_patterns(&processed.ignore, || {
+        collect_metadata(&folder_path, &processed.folder_name, &[])
+    })
+    .map_err(|e| {
+        format!(
+            "failed to scan route folder '{}': {e}",
+            folder_path.display()
+        )
+    })?;
+    // `#[route]` does not run here, so its storage is rebuilt from the handlers' attributes
+    let route_storage = route_storage_from_files(&file_asts);
+    prepare_metadata(&mut metadata, &processed, structs, &route_storage)
+        .map_err(|e| e.to_string())?;
+
+    // Warnings were reported by the build, so defaulted types are not reported again
+    let (openapi_doc, _) = build_openapi_doc(
+        &processed,
+        &metadata,
+        file_asts,
+        &route_storage,
+        manifest_dir,
+    );
+    serde_json::to_string(&openapi_doc).map_err(|e| e.to_string())
+}
+
+/// Process `export_app` macro - extracted for testability
+pub fn process_export_app(
+    name: &syn::Ident,
+    folder_name: &str,
+    schema_storage: &HashMap<String, StructMetadata>,
+    manifest_dir: &str,
+    route_storage: &[StoredRouteInfo],
+) -> syn::Result<proc_macro2::TokenStream> {
+    let profile_start = if std::env::var("VESPERA_PROFILE").is_ok() {
+        Some(std::time::Instant::now())
+    } else {
+        None
+    };
+
+    let folder_path = find_folder_path(folder_name)?;
+    if !folder_path.exists() {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            format!(
+                "export_app! macro: route folder '{folder_name}' not found. Create src/{folder_name} or specify a different folder with `dir = \"your_folder\"`.",
+            ),
+        ));
+    }
+
+    let (mut metadata, file_asts) = collect_metadata(&folder_path, folder_name, route_storage).map_err(|e| syn::Error::new(Span::call_site(), format!("export_app! macro: failed to scan route folder '{folder_name}'. Error: {e}. Check that all .rs files have valid Rust syntax.")))?;
+    metadata.structs.extend(schema_storage.values().cloned());
+    merge_route_storage_data(&mut metadata, route_storage);
+    apply_module_defaults(&mut metadata.routes, &metadata.module_defaults);
+    metadata
+        .check_duplicate_schema_names()
+        .and_then(|()| metadata.check_link_targets())
+        .map_err(|msg| syn::Error::new(Span::call_site(), format!("export_app! macro: {msg}")))?;
+    report_invalid_files(&metadata, false)?;
+    report_unknown_methods(&metadata)?;
+
+    // Generate OpenAPI spec JSON string
+    let openapi_doc = generate_openapi_doc_with_metadata(
+        None,
+        None,
+        None,
+        &metadata,
+        Some(file_asts),
+        route_storage,
+    );
+    let spec_json = serde_json::to_string(&openapi_doc).map_err(|e| syn::Error::new(Span::call_site(), format!("export_app! macro: failed to serialize OpenAPI spec to JSON. Error: {e}. Check that all schema types are serializable.")))?;
+
+    // Write spec to temp file for compile-time merging by parent apps
+    let name_str = name.to_string();
+    let manifest_path = Path::new(manifest_dir);
+    let target_dir = find_target_dir(manifest_path);
+    let vespera_dir = target_dir.join("vespera");
+    std::fs::create_dir_all(&vespera_dir).map_err(|e| syn::Error::new(Span::call_site(), format!("export_app! macro: failed to create build cache directory '{}'. Error: {}. Ensure the target directory is writable.", vespera_dir.display(), e)))?;
+    let spec_file = vespera_dir.join(format!("{name_str}.openapi.json"));
+    std::fs::write(&spec_file, &spec_json).map_err(|e| syn::Error::new(Span::call_site(), format!("export_app! macro: failed to write OpenAPI spec file '{}'. Error: {}. Ensure the file path is writable.", spec_file.display(), e)))?;
+    let spec_path_str = spec_file.display().to_string().replace('\\', "/");
+
+    // Generate router code (without docs routes, no merge)
+    let router_code = generate_router_code(&metadata, RouterCodegenOptions::default());
+
+    let result = Ok(quote! {
+        /// Auto-generated vespera app struct
+        pub struct #name;
+
+        impl #name {
+            /// OpenAPI specification as JSON string
+            pub const OPENAPI_SPEC: &'static str = include_str!(#spec_path_str);
+
+            /// Create the router for this app.
+            /// Returns `Router<()>` which can be merged into any other router.
+            pub fn router() -> vespera::axum::Router<()> {
+                #router_code
+            }
+        }
+    });
+
+    if let Some(start) = profile_start {
+        eprintln!(
+            "[vespera-profile] export_app! macro total: {:?}",
+            start.elapsed()
+        );
+        crate::schema_macro::print_profile_summary();
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use rstest::rstest;
+    use tempfile::TempDir;
+    use vespera_core::openapi::PathOrder;
+
+    use super::*;
+    use crate::metadata::RouteMetadata;
+
+    fn create_temp_file(dir: &TempDir, filename: &str, content: &str) -> std::path::PathBuf {
+        let file_path = dir.path().join(filename);
+        if let Some(parent) = file_path.parent() {
+            fs::create_dir_all(parent).expect("Failed to create parent directory");
+        }
+        fs::write(&file_path, content).expect("Failed to write temp file");
+        file_path
+    }
+
+    // ========== Tests for generate_and_write_openapi ==========
+
+    #[test]
+    fn test_generate_and_write_openapi_no_output() {
+        let processed = ProcessedVesperaInput::default();
+        let metadata = CollectedMetadata::new();
+        let result =
+            generate_and_write_openapi(&processed, &metadata, HashMap::new(), &[], Path::new("."));
+        assert!(result.is_ok());
+        let (docs_url, redoc_url, spec_json) = result.unwrap();
+        assert!(docs_url.is_none());
+        assert!(redoc_url.is_none());
+        assert!(spec_json.is_none());
+    }
+
+    #[test]
+    fn test_generate_and_write_openapi_docs_only() {
+        let processed = ProcessedVesperaInput {
+            title: Some("Test API".to_string()),
+            version: Some("1.0.0".to_string()),
+            docs_url: Some("/docs".to_string()),
+            ..Default::default()
+        };
+        let metadata = CollectedMetadata::new();
+        let result =
+            generate_and_write_openapi(&processed, &metadata, HashMap::new(), &[], Path::new("."));
+        assert!(result.is_ok());
+        let (docs_url, redoc_url, spec_json) = result.unwrap();
+        assert!(docs_url.is_some());
+        assert_eq!(docs_url.unwrap(), "/docs");
+        assert!(spec_json.is_some());
+        let json = spec_json.unwrap();
+        assert!(json.contains("\"openapi\""));
+        assert!(json.contains("Test API"));
+        assert!(redoc_url.is_none());
+    }
+
+    #[test]
+    fn test_generate_and_write_openapi_redoc_only() {
+        let processed = ProcessedVesperaInput {
+            redoc_url: Some("/redoc".to_string()),
+            ..Default::default()
+        };
+        let metadata = CollectedMetadata::new();
+        let result =
+            generate_and_write_openapi(&processed, &metadata, HashMap::new(), &[], Path::new("."));
+        assert!(result.is_ok());
+        let (docs_url, redoc_url, spec_json) = result.unwrap();
+        assert!(docs_url.is_none());
+        assert!(redoc_url.is_some());
+        assert_eq!(redoc_url.unwrap(), "/redoc");
+        assert!(spec_json.is_some());
+    }
+
+    #[test]
+    fn test_generate_and_write_openapi_both_docs() {
+        let processed = ProcessedVesperaInput {
+            docs_url: Some("/docs".to_string()),
+            redoc_url: Some("/redoc".to_string()),
+            ..Default::default()
+        };
+        let metadata = CollectedMetadata::new();
+        let result =
+            generate_and_write_openapi(&processed, &metadata, HashMap::new(), &[], Path::new("."));
+        assert!(result.is_ok());
+        let (docs_url, redoc_url, spec_json) = result.unwrap();
+        assert!(docs_url.is_some());
+        assert!(redoc_url.is_some());
+        assert!(spec_json.is_some());
+    }
+
+    #[test]
+    fn test_generate_and_write_openapi_file_output() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let output_path = temp_dir.path().join("test-openapi.json");
+
+        let processed = ProcessedVesperaInput {
+            openapi_file_names: vec![output_path.to_string_lossy().to_string()],
+            title: Some("File Test".to_string()),
+            version: Some("2.0.0".to_string()),
+            ..Default::default()
+        };
+        let metadata = CollectedMetadata::new();
+        let result =
+            generate_and_write_openapi(&processed, &metadata, HashMap::new(), &[], Path::new("."));
+        assert!(result.is_ok());
+
+        // Verify file was written
+        assert!(output_path.exists());
+        let content = fs::read_to_string(&output_path).unwrap();
+        assert!(content.contains("\"openapi\""));
+        assert!(content.contains("File Test"));
+        assert!(content.contains("2.0.0"));
+    }
+
+    #[test]
+    fn test_generate_and_write_openapi_scoped_documents() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let output_path = temp_dir.path().join("openapi.json");
+
+        let processed = ProcessedVesperaInput {
+            openapi_file_names: vec![output_path.to_string_lossy().to_string()],
+            scopes: vec!["admin".to_string()],
+            ..Default::default()
+        };
+        let mut metadata = CollectedMetadata::new();
+        metadata.structs.push(StructMetadata::new(
+            "User".to_string(),
+            r#"pub struct User { pub id: i32, #[schema(scope = "admin")] pub salary: i64 }"#
+                .to_string(),
+        ));
+        let result =
+            generate_and_write_openapi(&processed, &metadata, HashMap::new(), &[], Path::new("."));
+        assert!(result.is_ok());
+
+        let public = fs::read_to_string(&output_path).unwrap();
+        assert!(public.contains("\"id\""));
+        assert!(!public.contains("salary"));
+
+        let admin = fs::read_to_string(temp_dir.path().join("openapi.admin.json")).unwrap();
+        assert!(admin.contains("\"id\""));
+        assert!(admin.contains("salary"));
+    }
+
+    #[test]
+    fn test_generate_and_write_openapi_api_gateway() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let output_path = temp_dir.path().join("openapi.json");
+        let config_path = temp_dir.path().join("apigateway.json");
+        fs::write(
+            &config_path,
+            r#"{ "integration": { "type": "http_proxy", "uri": "https://backend.internal{path}" } }"#,
+        )
+        .unwrap();
+
+        let processed = ProcessedVesperaInput {
+            openapi_file_names: vec![output_path.to_string_lossy().to_string()],
+            api_gateway: Some(config_path.to_string_lossy().to_string()),
+            ..Default::default()
+        };
+        let mut metadata = CollectedMetadata::new();
+        metadata.routes.push(crate::metadata::RouteMetadata {
+            method: "get".to_string(),
+            path: "/users".to_string(),
+            function_name: "list_users".to_string(),
+            module_path: "routes::users".to_string(),
+            file_path: "routes/users.rs".to_string(),
+            signature: "fn list_users() -> String".to_string(),
+            ..Default::default()
+        });
+        let file_asts = || {
+            HashMap::from([(
+                "routes/users.rs".to_string(),
+                syn::parse_str("pub async fn list_users() -> String { String::new() }").unwrap(),
+            )])
+        };
+        let result =
+            generate_and_write_openapi(&processed, &metadata, file_asts(), &[], Path::new("."));
+        assert!(result.is_ok());
+
+        let gateway: serde_json::Value = serde_json::from_str(
+            &fs::read_to_string(temp_dir.path().join("openapi.apigateway.json")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(gateway["openapi"], "3.0.1");
+        assert_eq!(
+            gateway["paths"]["/users"]["get"]["x-amazon-apigateway-integration"]["uri"],
+            "https://backend.internal/users"
+        );
+
+        // Routes without an integration fail the build
+        fs::write(&config_path, "{}").unwrap();
+        let err =
+            generate_and_write_openapi(&processed, &metadata, file_asts(), &[], Path::new("."))
+                .err()
+                .unwrap();
+        assert!(err.to_string().contains("users_list_users"), "{err}");
+    }
+
+    #[test]
+    fn test_generate_and_write_openapi_sdk_groups() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let output_path = temp_dir.path().join("openapi.json");
+
+        let processed = ProcessedVesperaInput {
+            openapi_file_names: vec![output_path.to_string_lossy().to_string()],
+            sdk_groups: true,
+            ..Default::default()
+        };
+        let mut metadata = CollectedMetadata::new();
+        metadata.routes.push(crate::metadata::RouteMetadata {
+            method: "get".to_string(),
+            path: "/users".to_string(),
+            function_name: "list_users".to_string(),
+            module_path: "routes::users".to_string(),
+            file_path: "routes/users.rs".to_string(),
+            signature: "fn list_users() -> String".to_string(),
+            tags: Some(vec!["users".to_string()]),
+            sdk_group: Some("Accounts".to_string()),
+            ..Default::default()
+        });
+        let file_asts = HashMap::from([(
+            "routes/users.rs".to_string(),
+            syn::parse_str("pub async fn list_users() -> String { String::new() }").unwrap(),
+        )]);
+        let result =
+            generate_and_write_openapi(&processed, &metadata, file_asts, &[], Path::new("."));
+        assert!(result.is_ok());
+
+        let read = |name: &str| -> serde_json::Value {
+            serde_json::from_str(&fs::read_to_string(temp_dir.path().join(name)).unwrap()).unwrap()
+        };
+        // The main document keeps the route's tags; the SDK document groups by `sdk_group`
+        assert_eq!(
+            read("openapi.json")["paths"]["/users"]["get"]["tags"],
+            serde_json::json!(["users"])
+        );
+        let sdk = read("openapi.sdk.json");
+        assert_eq!(
+            sdk["paths"]["/users"]["get"]["tags"],
+            serde_json::json!(["Accounts"])
+        );
+        assert_eq!(sdk["tags"], serde_json::json!([{ "name": "Accounts" }]));
+    }
+
+    #[test]
+    fn test_generate_and_write_openapi_format_patterns() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let output_path = temp_dir.path().join("openapi.json");
+
+        let processed = ProcessedVesperaInput {
+            openapi_file_names: vec![output_path.to_string_lossy().to_string()],
+            formats: BTreeMap::from([
+                ("slug".to_string(), None),
+                (
+                    "ulid".to_string(),
+                    Some("^[0-9A-HJKMNP-TV-Z]{26}$".to_string()),
+                ),
+            ]),
+            ..Default::default()
+        };
+        let mut metadata = CollectedMetadata::new();
+        metadata.structs.push(StructMetadata::new(
+            "Account".to_string(),
+            r#"struct Account { #[schema(format = "ulid")] id: String, #[schema(format = "slug")] handle: String }"#
+                .to_string(),
+        ));
+        let result =
+            generate_and_write_openapi(&processed, &metadata, HashMap::new(), &[], Path::new("."));
+        assert!(result.is_ok());
+
+        let doc: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&output_path).unwrap()).unwrap();
+        let properties = &doc["components"]["schemas"]["Account"]["properties"];
+        assert_eq!(properties["id"]["format"], "ulid");
+        assert_eq!(properties["id"]["pattern"], "^[0-9A-HJKMNP-TV-Z]{26}$");
+        assert_eq!(properties["handle"]["format"], "slug");
+        assert!(properties["handle"].get("pattern").is_none());
+    }
+
+    #[rstest]
+    #[case(false, &["age", "id", "name"])]
+    #[case(true, &["name", "id", "age"])]
+    fn test_generate_and_write_openapi_declaration_order(
+        #[case] declaration_order: bool,
+        #[case] expected: &[&str],
+    ) {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let output_path = temp_dir.path().join("openapi.json");
+
+        let processed = ProcessedVesperaInput {
+            openapi_file_names: vec![output_path.to_string_lossy().to_string()],
+            declaration_order,
+            ..Default::default()
+        };
+        let mut metadata = CollectedMetadata::new();
+        metadata.structs.push(StructMetadata::new(
+            "User".to_string(),
+            "struct User { name: String, id: i32, age: u8 }".to_string(),
+        ));
+        let result =
+            generate_and_write_openapi(&processed, &metadata, HashMap::new(), &[], Path::new("."));
+        assert!(result.is_ok());
+
+        let doc: vespera_core::openapi::OpenApi =
+            serde_json::from_str(&fs::read_to_string(&output_path).unwrap()).unwrap();
+        let schemas = doc.components.unwrap().schemas.unwrap();
+        let properties: Vec<&str> = schemas["User"]
+            .properties
+            .as_ref()
+            .unwrap()
+            .keys()
+            .map(String::as_str)
+            .collect();
+        assert_eq!(properties, expected);
+    }
+
+    #[test]
+    fn test_generate_and_write_openapi_json_schema_dialect() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let output_path = temp_dir.path().join("openapi.json");
+
+        let processed = ProcessedVesperaInput {
+            openapi_file_names: vec![output_path.to_string_lossy().to_string()],
+            json_schema_dialect: Some("https://spec.openapis.org/oas/3.1/dialect/base".to_string()),
+            ..Default::default()
+        };
+        let mut metadata = CollectedMetadata::new();
+        metadata.structs.push(StructMetadata::new(
+            "User".to_string(),
+            "struct User { id: i32 }".to_string(),
+        ));
+        metadata.structs.push(StructMetadata::new(
+            "Legacy".to_string(),
+            "#[schema(dialect = \"https://json-schema.org/draft/2019-09/schema\")] struct Legacy { id: i32 }"
+                .to_string(),
+        ));
+        let result =
+            generate_and_write_openapi(&processed, &metadata, HashMap::new(), &[], Path::new("."));
+        assert!(result.is_ok());
+
+        let doc: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&output_path).unwrap()).unwrap();
+        assert_eq!(
+            doc["jsonSchemaDialect"],
+            "https://spec.openapis.org/oas/3.1/dialect/base"
+        );
+        let schemas = &doc["components"]["schemas"];
+        assert!(schemas["User"].get("$schema").is_none());
+        assert_eq!(
+            schemas["Legacy"]["$schema"],
+            "https://json-schema.org/draft/2019-09/schema"
+        );
+    }
+
+    #[rstest]
+    #[case(PathOrder::Alphabetical, false, None, &["/health", "/users/{id}", "/users/{user_id}"])]
+    #[case(PathOrder::Source, true, Some("camelCase"), &["/users/{userId}", "/health"])]
+    fn test_generate_and_write_openapi_path_layout(
+        #[case] path_order: PathOrder,
+        #[case] collapse_paths: bool,
+        #[case] path_param_case: Option<&str>,
+        #[case] expected: &[&str],
+    ) {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let output_path = temp_dir.path().join("openapi.json");
+
+        let processed = ProcessedVesperaInput {
+            openapi_file_names: vec![output_path.to_string_lossy().to_string()],
+            path_order,
+            collapse_paths,
+            path_param_case: path_param_case.map(str::to_string),
+            ..Default::default()
+        };
+        let route = |method: &str, path: &str, function_name: &str| RouteMetadata {
+            method: method.to_string(),
+            path: path.to_string(),
+            function_name: function_name.to_string(),
+            module_path: "routes::users".to_string(),
+            file_path: "routes/users.rs".to_string(),
+            ..Default::default()
+        };
+        let mut metadata = CollectedMetadata::new();
+        metadata
+            .routes
+            .push(route("get", "/users/{user_id}", "get_user"));
+        metadata.routes.push(route("get", "/health", "health"));
+        metadata
+            .routes
+            .push(route("delete", "/users/{id}", "delete_user"));
+        let file_asts = HashMap::from([(
+            "routes/users.rs".to_string(),
+            syn::parse_str(
+                "pub async fn get_user(Path(user_id): Path<i32>) -> String { String::new() }
+                 pub async fn health() -> String { String::new() }
+                 pub async fn delete_user(Path(id): Path<i32>) -> String { String::new() }",
+            )
+            .unwrap(),
+        )]);
+        let result =
+            generate_and_write_openapi(&processed, &metadata, file_asts, &[], Path::new("."));
+        assert!(result.is_ok());
+
+        let content = fs::read_to_string(&output_path).unwrap();
+        let doc: vespera_core::openapi::OpenApi = serde_json::from_str(&content).unwrap();
+        assert_eq!(
+            doc.paths.keys().map(String::as_str).collect::<Vec<_>>(),
+            expected
+        );
+        if collapse_paths {
+            let doc: serde_json::Value = serde_json::from_str(&content).unwrap();
+            let paths = doc["paths"].as_object().unwrap();
+            let users = &paths["/users/{userId}"];
+            assert_eq!(users["get"]["parameters"][0]["name"], "userId");
+            assert_eq!(users["delete"]["parameters"][0]["name"], "userId");
+        }
+    }
+
+    #[rstest]
+    #[case(false, &["id"], &["nickname", "note"])]
+    #[case(true, &["id", "note"], &["note"])]
+    fn test_generate_and_write_openapi_strict_nullability(
+        #[case] strict_nullability: bool,
+        #[case] required: &[&str],
+        #[case] nullable: &[&str],
+    ) {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let output_path = temp_dir.path().join("openapi.json");
+
+        let processed = ProcessedVesperaInput {
+            openapi_file_names: vec![output_path.to_string_lossy().to_string()],
+            strict_nullability,
+            ..Default::default()
+        };
+        let mut metadata = CollectedMetadata::new();
+        metadata.structs.push(StructMetadata::new(
+            "User".to_string(),
+            r#"struct User {
+                id: i32,
+                #[serde(skip_serializing_if = "Option::is_none")]
+                nickname: Option<String>,
+                note: Option<String>,
+            }"#
+            .to_string(),
+        ));
+        let result =
+            generate_and_write_openapi(&processed, &metadata, HashMap::new(), &[], Path::new("."));
+        assert!(result.is_ok());
+
+        let doc: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&output_path).unwrap()).unwrap();
+        let user = &doc["components"]["schemas"]["User"];
+        assert_eq!(user["required"], serde_json::json!(required));
+        let properties = user["properties"].as_object().unwrap();
+        let nullable_properties: Vec<&str> = properties
+            .iter()
+            .filter(|(_, schema)| schema["nullable"] == true)
+            .map(|(name, _)| name.as_str())
+            .collect();
+        assert_eq!(nullable_properties, nullable);
+    }
+
+    #[test]
+    fn test_generate_and_write_openapi_kubernetes_manifests() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let http_route_path = temp_dir.path().join("deploy/httproute.yaml");
+        let ingress_path = temp_dir.path().join("deploy/ingress.yaml");
+
+        let processed = ProcessedVesperaInput {
+            kubernetes: Some(KubernetesManifests {
+                config: kubernetes::KubernetesConfig {
+                    name: "users-api".to_string(),
+                    service: "users".to_string(),
+                    port: 8080,
+                    ..Default::default()
+                },
+                http_route: Some(http_route_path.to_string_lossy().to_string()),
+                ingress: Some(ingress_path.to_string_lossy().to_string()),
+            }),
+            ..Default::default()
+        };
+        let mut metadata = CollectedMetadata::new();
+        metadata.routes.push(crate::metadata::RouteMetadata {
+            method: "get".to_string(),
+            path: "/users/{id}".to_string(),
+            function_name: "get_user".to_string(),
+            module_path: "routes::users".to_string(),
+            file_path: "routes/users.rs".to_string(),
+            signature: "fn get_user() -> String".to_string(),
+            ..Default::default()
+        });
+        let file_asts = HashMap::from([(
+            "routes/users.rs".to_string(),
+            syn::parse_str("pub async fn get_user() -> String { String::new() }").unwrap(),
+        )]);
+
+        // No `openapi` file is needed for the manifests
+        let result =
+            generate_and_write_openapi(&processed, &metadata, file_asts, &[], Path::new("."));
+        assert!(result.is_ok());
+
+        let http_route = fs::read_to_string(&http_route_path).unwrap();
+        assert!(http_route.contains("kind: HTTPRoute"));
+        assert!(http_route.contains("value: \"/users/[^/]+\""));
+        let ingress = fs::read_to_string(&ingress_path).unwrap();
+        assert!(ingress.contains("- path: /users\n            pathType: Prefix"));
+    }
+
+    #[rstest]
+    #[case("openapi.json", "admin", "openapi.admin.json")]
+    #[case("docs/api.json", "internal", "docs/api.internal.json")]
+    #[case("spec", "admin", "spec.admin")]
+    fn test_scoped_file_name(#[case] file: &str, #[case] scope: &str, #[case] expected: &str) {
+        assert_eq!(scoped_file_name(file, scope), expected);
+    }
+
+    #[test]
+    fn test_generate_and_write_openapi_creates_directories() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let output_path = temp_dir.path().join("nested/dir/openapi.json");
+
+        let processed = ProcessedVesperaInput {
+            openapi_file_names: vec![output_path.to_string_lossy().to_string()],
+            ..Default::default()
+        };
+        let metadata = CollectedMetadata::new();
+        let result =
+            generate_and_write_openapi(&processed, &metadata, HashMap::new(), &[], Path::new("."));
+        assert!(result.is_ok());
+
+        // Verify nested directories and file were created
+        assert!(output_path.exists());
+    }
+
+    // ========== Tests for find_folder_path ==========
+    // Note: find_folder_path uses CARGO_MANIFEST_DIR which is set during cargo test
+
+    #[test]
+    fn test_find_folder_path_nonexistent_returns_path() {
+        // When the constructed path doesn't exist, it falls back to using folder_name directly
+        let result = find_folder_path("nonexistent_folder_xyz").unwrap();
+        // It should return a PathBuf (either from src/nonexistent... or just the folder name)
+        assert!(result.to_string_lossy().contains("nonexistent_folder_xyz"));
+    }
+
+    // ========== Tests for find_target_dir ==========
+
+    #[test]
+    fn test_find_target_dir_no_workspace() {
+        // Test fallback to manifest dir's target
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let manifest_path = temp_dir.path();
+        let result = find_target_dir(manifest_path);
+        assert_eq!(result, manifest_path.join("target"));
+    }
+
+    #[test]
+    fn test_find_target_dir_with_cargo_lock() {
+        // Test finding target dir with Cargo.lock present
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let manifest_path = temp_dir.path();
+
+        // Create Cargo.lock (but no [workspace] in Cargo.toml)
+        fs::write(manifest_path.join("Cargo.lock"), "").expect("Failed to write Cargo.lock");
+
+        let result = find_target_dir(manifest_path);
+        // Should use the directory with Cargo.lock
+        assert_eq!(result, manifest_path.join("target"));
+    }
+
+    #[test]
+    fn test_find_target_dir_with_workspace() {
+        // Test finding workspace root
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let workspace_root = temp_dir.path();
+
+        // Create a workspace Cargo.toml
+        fs::write(
+            workspace_root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"crate1\"]",
+        )
+        .expect("Failed to write Cargo.toml");
+
+        // Create nested crate directory
+        let crate_dir = workspace_root.join("crate1");
+        fs::create_dir(&crate_dir).expect("Failed to create crate dir");
+        fs::write(crate_dir.join("Cargo.toml"), "[package]\nname = \"crate1\"")
+            .expect("Failed to write Cargo.toml");
+
+        let result = find_target_dir(&crate_dir);
+        // Should return workspace root's target
+        assert_eq!(result, workspace_root.join("target"));
+    }
+
+    #[test]
+    fn test_find_target_dir_workspace_with_cargo_lock() {
+        // Test that [workspace] takes priority over Cargo.lock
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let workspace_root = temp_dir.path();
+
+        // Create workspace Cargo.toml and Cargo.lock
+        fs::write(
+            workspace_root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"crate1\"]",
+        )
+        .expect("Failed to write Cargo.toml");
+        fs::write(workspace_root.join("Cargo.lock"), "").expect("Failed to write Cargo.lock");
+
+        // Create nested crate
+        let crate_dir = workspace_root.join("crate1");
+        fs::create_dir(&crate_dir).expect("Failed to create crate dir");
+        fs::write(crate_dir.join("Cargo.toml"), "[package]\nname = \"crate1\"")
+            .expect("Failed to write Cargo.toml");
+
+        let result = find_target_dir(&crate_dir);
+        assert_eq!(result, workspace_root.join("target"));
+    }
+
+    #[test]
+    fn test_find_target_dir_deeply_nested() {
+        // Test deeply nested crate structure
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let workspace_root = temp_dir.path();
+
+        // Create workspace
+        fs::write(
+            workspace_root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"crates/*\"]",
+        )
+        .expect("Failed to write Cargo.toml");
+
+        // Create deeply nested crate
+        let deep_crate = workspace_root.join("crates/group/my-crate");
+        fs::create_dir_all(&deep_crate).expect("Failed to create nested dirs");
+        fs::write(deep_crate.join("Cargo.toml"), "[package]").expect("Failed to write Cargo.toml");
+
+        let result = find_target_dir(&deep_crate);
+        assert_eq!(result, workspace_root.join("target"));
+    }
+
+    // ========== Tests for process_vespera_macro ==========
+
+    #[test]
+    fn test_process_vespera_macro_folder_not_found() {
+        let processed = ProcessedVesperaInput {
+            folder_name: "nonexistent_folder_xyz_123".to_string(),
+            ..Default::default()
+        };
+        let result = process_vespera_macro(&processed, &HashMap::new(), &[], "");
+        assert!(result.is_err());
+        let err = result.unwrap_err().to_string();
+        assert!(err.contains("route folder") && err.contains("not found"));
+    }
+
+    #[test]
+    fn test_process_vespera_macro_collect_metadata_error() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+
+        create_temp_file(&temp_dir, "invalid.rs", "not valid rust code {{{");
+        create_temp_file(&temp_dir, "admin/broken.rs", "pub fn broken( {");
+
+        let mut processed = ProcessedVesperaInput {
+            folder_name: temp_dir.path().to_string_lossy().to_string(),
+            title: Some("Test API".to_string()),
+            version: Some("1.0.0".to_string()),
+            ..Default::default()
+        };
+
+        // Every invalid file is reported, with its path and location
+        let err = process_vespera_macro(&processed, &HashMap::new(), &[], "")
+            .unwrap_err()
+            .to_string();
+        assert!(err.starts_with("[VESPERA007]"), "{err}");
+        assert!(err.contains("2 route file(s)"), "{err}");
+        assert!(err.contains("invalid.rs: line 1, column"), "{err}");
+        assert!(err.contains("broken.rs: line 1, column"), "{err}");
+
+        // ...or skipped, generating the router from the files that parse
+        processed.skip_invalid_files = true;
+        assert!(process_vespera_macro(&processed, &HashMap::new(), &[], "").is_ok());
+    }
+
+    #[test]
+    fn test_process_vespera_macro_with_schema_storage() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+
+        // Create an empty file (valid but no routes)
+        create_temp_file(&temp_dir, "empty.rs", "// empty file\n");
+
+        let schema_storage = HashMap::from([(
+            "TestSchema".to_string(),
+            StructMetadata::new(
+                "TestSchema".to_string(),
+                "struct TestSchema { id: i32 }".to_string(),
+            ),
+        )]);
+
+        let processed = ProcessedVesperaInput {
+            folder_name: temp_dir.path().to_string_lossy().to_string(),
+            docs_url: Some("/docs".to_string()),
+            redoc_url: Some("/redoc".to_string()),
+            ..Default::default()
+        };
+
+        // This exercises the schema_storage extend path
+        let result = process_vespera_macro(&processed, &schema_storage, &[], "");
+        // We only care about exercising the code path
+        let _ = result;
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_process_vespera_macro_with_cron_storage() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+
+        // Create src/ subfolder structure to simulate a real project
+        let src_dir = temp_dir.path().join("src");
+        std::fs::create_dir_all(src_dir.join("routes")).expect("create routes dir");
+        std::fs::write(src_dir.join("routes").join("health.rs"), "// empty\n")
+            .expect("write health.rs");
+
+        // Set CARGO_MANIFEST_DIR so module path derivation works
+        let old_manifest = std::env::var("CARGO_MANIFEST_DIR").ok();
+        unsafe {
+            std::env::set_var(
+                "CARGO_MANIFEST_DIR",
+                temp_dir.path().to_string_lossy().as_ref(),
+            );
+        }
+
+        // Populate CRON_STORAGE with a fake cron entry
+        {
+            let mut storage = crate::CRON_STORAGE
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+            storage.push(crate::cron_impl::StoredCronInfo {
+                fn_name: "test_cron_job".to_string(),
+                expression: "0 */5 * * * *".to_string(),
+                file_path: Some(
+                    src_dir
+                        .join("routes")
+                        .join("health.rs")
+                        .display()
+                        .to_string(),
+                ),
+            });
+        }
+
+        let processed = ProcessedVesperaInput {
+            folder_name: src_dir.join("routes").to_string_lossy().to_string(),
+            ..Default::default()
+        };
+
+        // This exercises the CRON_STORAGE → CronMetadata derivation path
+        let result = process_vespera_macro(&processed, &HashMap::new(), &[], "");
+        assert!(
+            result.is_ok(),
+            "Should succeed with cron storage: {result:?}"
+        );
+
+        // Clean up CRON_STORAGE
+        {
+            let mut storage = crate::CRON_STORAGE
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+            storage.retain(|s| s.fn_name != "test_cron_job");
+        }
+
+        // Restore CARGO_MANIFEST_DIR
+        unsafe {
+            if let Some(val) = old_manifest {
+                std::env::set_var("CARGO_MANIFEST_DIR", val);
+            } else {
+                std::env::remove_var("CARGO_MANIFEST_DIR");
+            }
+        }
+    }
+
+    // ========== Tests for process_export_app ==========
+
+    #[test]
+    fn test_process_export_app_folder_not_found() {
+        let name: syn::Ident = syn::parse_quote!(TestApp);
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let result = process_export_app(
+            &name,
+            "nonexistent_folder_xyz",
+            &HashMap::new(),
+            &temp_dir.path().to_string_lossy(),
+            &[],
+        );
+        assert!(result.is_err());
+        let err = result.unwrap_err().to_string();
+        assert!(err.contains("route folder") && err.contains("not found"));
+    }
+
+    #[test]
+    fn test_process_export_app_with_empty_folder() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+
+        // Create an empty file
+        create_temp_file(&temp_dir, "empty.rs", "// empty\n");
+
+        let name: syn::Ident = syn::parse_quote!(TestApp);
+        let folder_path = temp_dir.path().to_string_lossy().to_string();
+
+        // This exercises collect_metadata and other paths
+        let result = process_export_app(
+            &name,
+            &folder_path,
+            &HashMap::new(),
+            &temp_dir.path().to_string_lossy(),
+            &[],
+        );
+        // We only care about exercising the code path
+        let _ = result;
+    }
+
+    #[test]
+    fn test_process_export_app_with_schema_storage() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+
+        // Create an empty but valid Rust file
+        create_temp_file(&temp_dir, "mod.rs", "// module file\n");
+
+        let schema_storage = HashMap::from([(
+            "AppSchema".to_string(),
+            StructMetadata::new(
+                "AppSchema".to_string(),
+                "struct AppSchema { name: String }".to_string(),
+            ),
+        )]);
+
+        let name: syn::Ident = syn::parse_quote!(MyExportedApp);
+        let folder_path = temp_dir.path().to_string_lossy().to_string();
+
+        let result = process_export_app(
+            &name,
+            &folder_path,
+            &schema_storage,
+            &temp_dir.path().to_string_lossy(),
+            &[],
+        );
+        // Exercises the schema_storage.extend path
+        let _ = result;
+    }
+
+    // ========== Tests for generate_and_write_openapi with merge ==========
+
+    #[test]
+    fn test_generate_and_write_openapi_with_merge_no_child_spec() {
+        // Merged apps without a written spec are skipped
+        let processed = ProcessedVesperaInput {
+            title: Some("Test".to_string()),
+            docs_url: Some("/docs".to_string()),
+            merge: vec![syn::parse_quote!(app::TestApp)], // Has merge but no valid manifest dir
+            ..Default::default()
+        };
+        let metadata = CollectedMetadata::new();
+        let result =
+            generate_and_write_openapi(&processed, &metadata, HashMap::new(), &[], Path::new("."));
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_generate_and_write_openapi_with_merge_and_valid_spec() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+
+        // Create the vespera directory with a spec file
+        let target_dir = temp_dir.path().join("target").join("vespera");
+        fs::create_dir_all(&target_dir).expect("Failed to create target/vespera dir");
+
+        // Write a valid OpenAPI spec file
+        let spec_content = r#"{"openapi":"3.1.0","info":{"title":"Child API","version":"1.0.0"},"paths":{"/child":{"get":{"responses":{}}}}}"#;
+        fs::write(target_dir.join("ChildApp.openapi.json"), spec_content)
+            .expect("Failed to write spec file");
+
+        let processed = ProcessedVesperaInput {
+            title: Some("Parent API".to_string()),
+            version: Some("2.0.0".to_string()),
+            docs_url: Some("/docs".to_string()),
+            merge: vec![syn::parse_quote!(child::ChildApp)],
+            ..Default::default()
+        };
+        let metadata = CollectedMetadata::new();
+
+        let (_, _, spec_json) =
+            generate_and_write_openapi(&processed, &metadata, HashMap::new(), &[], temp_dir.path())
+                .unwrap();
+        assert!(spec_json.unwrap().contains("/child"));
+    }
+
+    // ========== Tests for find_folder_path ==========
+
+    #[test]
+    fn test_find_folder_path_absolute_path() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let absolute_path = temp_dir.path().to_string_lossy().to_string();
+
+        // When given an absolute path that exists, it should return it
+        let result = find_folder_path(&absolute_path).unwrap();
+        // The function tries src/{folder_name} first, then falls back to the folder_name directly
+        assert!(
+            result.to_string_lossy().contains(&absolute_path)
+                || result == Path::new(&absolute_path)
+        );
+    }
+
+    #[test]
+    fn test_find_folder_path_with_src_folder() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+
+        // Create src/routes directory
+        let src_routes = temp_dir.path().join("src").join("routes");
+        fs::create_dir_all(&src_routes).expect("Failed to create src/routes dir");
+
+        // Save and set CARGO_MANIFEST_DIR
+        let old_manifest_dir = std::env::var("CARGO_MANIFEST_DIR").ok();
+        // SAFETY: We're in a single-threaded test context
+        unsafe { std::env::set_var("CARGO_MANIFEST_DIR", temp_dir.path()) };
+
+        let result = find_folder_path("routes").unwrap();
+
+        // Restore CARGO_MANIFEST_DIR
+        if let Some(old_value) = old_manifest_dir {
+            // SAFETY: We're in a single-threaded test context
+            unsafe { std::env::set_var("CARGO_MANIFEST_DIR", old_value) };
+        }
+
+        // Should return the src/routes path since it exists
+        assert!(
+            result.to_string_lossy().contains("src") && result.to_string_lossy().contains("routes")
+        );
+    }
+
+    // ========== Error path coverage tests ==========
+
+    #[test]
+    fn test_generate_and_write_openapi_file_write_error() {
+        // Line 95: fs::write failure when output path is a directory
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+
+        // Create a directory where the output file should be
+        let output_path = temp_dir.path().join("openapi.json");
+        fs::create_dir(&output_path).expect("Failed to create directory");
+
+        let processed = ProcessedVesperaInput {
+            openapi_file_names: vec![output_path.to_string_lossy().to_string()],
+            title: Some("Test API".to_string()),
+            version: Some("1.0.0".to_string()),
+            ..Default::default()
+        };
+        let metadata = CollectedMetadata::new();
+
+        let result =
+            generate_and_write_openapi(&processed, &metadata, HashMap::new(), &[], Path::new("."));
+        assert!(result.is_err());
+        let err = result.unwrap_err().to_string();
+        assert!(err.contains("failed to write file"));
+    }
+
+    #[test]
+    fn test_process_export_app_collect_metadata_error() {
+        // Lines 210-212: collect_metadata returns error for invalid Rust syntax
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+
+        // Create a file with invalid Rust syntax that will cause parse error
+        create_temp_file(&temp_dir, "invalid.rs", "fn broken( { syntax error");
+
+        let name: syn::Ident = syn::parse_quote!(TestApp);
+        let folder_path = temp_dir.path().to_string_lossy().to_string();
+
+        let result = process_export_app(
+            &name,
+            &folder_path,
+            &HashMap::new(),
+            &temp_dir.path().to_string_lossy(),
+            &[],
+        );
+
+        assert!(result.is_err());
+        let err = result.unwrap_err().to_string();
+        assert!(
+            err.contains("[VESPERA007]") && err.contains("invalid.rs"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_process_export_app_create_dir_error() {
+        // Lines 232-234: create_dir_all failure when path contains a file
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+
+        // Create an empty valid Rust file
+        create_temp_file(&temp_dir, "empty.rs", "// empty file\n");
+
+        // Create target directory but make 'vespera' a file instead of directory
+        let target_dir = temp_dir.path().join("target");
+        fs::create_dir(&target_dir).expect("Failed to create target dir");
+        fs::write(target_dir.join("vespera"), "blocking file").expect("Failed to write file");
+
+        let name: syn::Ident = syn::parse_quote!(TestApp);
+        let folder_path = temp_dir.path().to_string_lossy().to_string();
+
+        let result = process_export_app(
+            &name,
+            &folder_path,
+            &HashMap::new(),
+            &temp_dir.path().to_string_lossy(),
+            &[],
+        );
+
+        assert!(result.is_err());
+        let err = result.unwrap_err().to_string();
+        assert!(err.contains("failed to create build cache directory"));
+    }
+
+    #[test]
+    fn test_process_export_app_write_spec_error() {
+        // Lines 239-241: fs::write failure when spec file path is a directory
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+
+        // Create an empty valid Rust file
+        create_temp_file(&temp_dir, "empty.rs", "// empty file\n");
+
+        // Create target/vespera directory and make spec file name a directory
+        let vespera_dir = temp_dir.path().join("target").join("vespera");
+        fs::create_dir_all(&vespera_dir).expect("Failed to create vespera dir");
+        // Create a directory where the spec file should be written
+        fs::create_dir(vespera_dir.join("TestApp.openapi.json"))
+            .expect("Failed to create blocking dir");
+
+        let name: syn::Ident = syn::parse_quote!(TestApp);
+        let folder_path = temp_dir.path().to_string_lossy().to_string();
+
+        let result = process_export_app(
+            &name,
+            &folder_path,
+            &HashMap::new(),
+            &temp_dir.path().to_string_lossy(),
+            &[],
+        );
+
+        assert!(result.is_err());
+        let err = result.unwrap_err().to_string();
+        assert!(err.contains("failed to write OpenAPI spec file"));
+    }
+    #[test]
+    fn test_process_vespera_macro_no_openapi_output() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        create_temp_file(&temp_dir, "empty.rs", "// empty route file\n");
+
+        let processed = ProcessedVesperaInput {
+            folder_name: temp_dir.path().to_string_lossy().to_string(),
+            ..Default::default()
+        };
+
+        let result = process_vespera_macro(&processed, &HashMap::new(), &[], "");
+        assert!(
+            result.is_ok(),
+            "Should succeed with no openapi output configured"
+        );
+        let code = result.unwrap().to_string();
+        assert!(
+            code.contains("vespera :: policy :: register (\"{\\\"operations\\\":[]}\")"),
+            "got: {code}"
+        );
+        assert!(
+            code.contains("vespera :: formats :: register (& [])"),
+            "got: {code}"
+        );
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_process_vespera_macro_with_profiling() {
+        let old_profile = std::env::var("VESPERA_PROFILE").ok();
+        unsafe { std::env::set_var("VESPERA_PROFILE", "1") };
+
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        create_temp_file(&temp_dir, "empty.rs", "// empty\n");
+
+        let processed = ProcessedVesperaInput {
+            folder_name: temp_dir.path().to_string_lossy().to_string(),
+            ..Default::default()
+        };
+
+        let result = process_vespera_macro(&processed, &HashMap::new(), &[], "");
+
+        // Restore
+        unsafe {
+            if let Some(val) = old_profile {
+                std::env::set_var("VESPERA_PROFILE", val);
+            } else {
+                std::env::remove_var("VESPERA_PROFILE");
+            }
+        };
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_process_export_app_with_profiling() {
+        let old_profile = std::env::var("VESPERA_PROFILE").ok();
+        unsafe { std::env::set_var("VESPERA_PROFILE", "1") };
+
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        create_temp_file(&temp_dir, "empty.rs", "// empty\n");
+
+        let name: syn::Ident = syn::parse_quote!(TestProfileApp);
+        let folder_path = temp_dir.path().to_string_lossy().to_string();
+
+        let result = process_export_app(
+            &name,
+            &folder_path,
+            &HashMap::new(),
+            &temp_dir.path().to_string_lossy(),
+            &[],
+        );
+
+        // Restore
+        unsafe {
+            if let Some(val) = old_profile {
+                std::env::set_var("VESPERA_PROFILE", val);
+            } else {
+                std::env::remove_var("VESPERA_PROFILE");
+            }
+        };
+
+        // Exercise the code path
+        let _ = result;
+    }
+
+    // ========== Tests for merge_route_storage_data ==========
+
+    #[test]
+    fn test_merge_route_storage_empty_storage() {
+        let mut metadata = CollectedMetadata::new();
+        metadata.routes.push(RouteMetadata {
+            method: "get".to_string(),
+            path: "/users".to_string(),
+            function_name: "get_users".to_string(),
+            module_path: "routes".to_string(),
+            file_path: "routes/users.rs".to_string(),
+            signature: "pub async fn get_users() -> Json<Vec<User>>".to_string(),
+            ..Default::default()
+        });
+
+        merge_route_storage_data(&mut metadata, &[]);
+        // No changes when storage is empty
+        assert!(metadata.routes[0].tags.is_none());
+        assert!(metadata.routes[0].description.is_none());
+        assert!(metadata.routes[0].error_status.is_none());
+    }
+
+    #[test]
+    fn test_merge_route_storage_matching_route() {
+        let mut metadata = CollectedMetadata::new();
+        metadata.routes.push(RouteMetadata {
+            method: "get".to_string(),
+            path: "/users".to_string(),
+            function_name: "get_users".to_string(),
+            module_path: "routes".to_string(),
+            file_path: "routes/users.rs".to_string(),
+            signature: "pub async fn get_users() -> Json<Vec<User>>".to_string(),
+            ..Default::default()
+        });
+
+        let storage = vec![StoredRouteInfo {
+            fn_name: "get_users".to_string(),
+            method: Some("get".to_string()),
+            custom_path: None,
+            error_status: Some(vec![400, 404]),
+            tags: Some(vec!["users".to_string()]),
+            description: Some("List all users".to_string()),
+            fn_item_str: String::new(),
+            file_path: None,
+            security: None,
+            limits: None,
+            cache: None,
+            response: None,
+            request_body: None,
+            responses: None,
+            params: None,
+            links: None,
+            request_example: None,
+            response_example: None,
+            status: None,
+            sdk_group: None,
+            operation_id: None,
+            content_type: None,
+            sse_event: None,
+            summary: None,
+            deprecated: false,
+        }];
+
+        merge_route_storage_data(&mut metadata, &storage);
+        assert_eq!(metadata.routes[0].tags, Some(vec!["users".to_string()]));
+        assert_eq!(
+            metadata.routes[0].description,
+            Some("List all users".to_string())
+        );
+        assert_eq!(metadata.routes[0].error_status, Some(vec![400, 404]));
+    }
+
+    #[test]
+    fn test_merge_route_storage_no_match() {
+        let mut metadata = CollectedMetadata::new();
+        metadata.routes.push(RouteMetadata {
+            method: "get".to_string(),
+            path: "/users".to_string(),
+            function_name: "get_users".to_string(),
+            module_path: "routes".to_string(),
+            file_path: "routes/users.rs".to_string(),
+            ..Default::default()
+        });
+
+        let storage = vec![StoredRouteInfo {
+            fn_name: "create_user".to_string(),
+            method: Some("post".to_string()),
+            custom_path: None,
+            error_status: Some(vec![400]),
+            tags: Some(vec!["users".to_string()]),
+            description: None,
+            fn_item_str: String::new(),
+            file_path: None,
+            security: None,
+            limits: None,
+            cache: None,
+            response: None,
+            request_body: None,
+            responses: None,
+            params: None,
+            links: None,
+            request_example: None,
+            response_example: None,
+            status: None,
+            sdk_group: None,
+            operation_id: None,
+            content_type: None,
+            sse_event: None,
+            summary: None,
+            deprecated: false,
+        }];
+
+        merge_route_storage_data(&mut metadata, &storage);
+        // No match — fields unchanged
+        assert!(metadata.routes[0].tags.is_none());
+        assert!(metadata.routes[0].error_status.is_none());
+    }
+
+    #[test]
+    fn test_merge_route_storage_ambiguous_skipped() {
+        let mut metadata = CollectedMetadata::new();
+        metadata.routes.push(RouteMetadata {
+            method: "get".to_string(),
+            path: "/users".to_string(),
+            function_name: "handler".to_string(),
+            module_path: "routes".to_string(),
+            file_path: "routes/users.rs".to_string(),
+            ..Default::default()
+        });
+
+        // Two StoredRouteInfo with same fn_name — ambiguous
+        let storage = vec![
+            StoredRouteInfo {
+                fn_name: "handler".to_string(),
+                method: Some("get".to_string()),
+                custom_path: None,
+                error_status: None,
+                tags: Some(vec!["file-a".to_string()]),
+                description: None,
+                fn_item_str: String::new(),
+                file_path: None,
+                security: None,
+                limits: None,
+                cache: None,
+                response: None,
+                request_body: None,
+                responses: None,
+                params: None,
+                links: None,
+                request_example: None,
+                response_example: None,
+                status: None,
+                sdk_group: None,
+                operation_id: None,
+                content_type: None,
+                sse_event: None,
+                summary: None,
+                deprecated: false,
+            },
+            StoredRouteInfo {
+                fn_name: "handler".to_string(),
+                method: Some("post".to_string()),
+                custom_path: None,
+                error_status: None,
+                tags: Some(vec!["file-b".to_string()]),
+                description: None,
+                fn_item_str: String::new(),
+                file_path: None,
+                security: None,
+                limits: None,
+                cache: None,
+                response: None,
+                request_body: None,
+                responses: None,
+                params: None,
+                links: None,
+                request_example: None,
+                response_example: None,
+                status: None,
+                sdk_group: None,
+                operation_id: None,
+                content_type: None,
+                sse_event: None,
+                summary: None,
+                deprecated: false,
+            },
+        ];
+
+        merge_route_storage_data(&mut metadata, &storage);
+        // Ambiguous match — no merge
+        assert!(metadata.routes[0].tags.is_none());
+    }
+
+    #[test]
+    fn test_merge_route_storage_preserves_existing() {
+        let mut metadata = CollectedMetadata::new();
+        metadata.routes.push(RouteMetadata {
+            method: "get".to_string(),
+            path: "/users".to_string(),
+            function_name: "get_users".to_string(),
+            module_path: "routes".to_string(),
+            file_path: "routes/users.rs".to_string(),
+            error_status: Some(vec![500]),
+            tags: Some(vec!["existing-tag".to_string()]),
+            description: Some("Existing description".to_string()),
+            ..Default::default()
+        });
+
+        let storage = vec![StoredRouteInfo {
+            fn_name: "get_users".to_string(),
+            method: Some("get".to_string()),
+            custom_path: None,
+            error_status: Some(vec![400, 404]),
+            tags: Some(vec!["new-tag".to_string()]),
+            description: Some("New description".to_string()),
+            fn_item_str: String::new(),
+            file_path: None,
+            security: None,
+            limits: None,
+            cache: None,
+            response: None,
+            request_body: None,
+            responses: None,
+            params: None,
+            links: None,
+            request_example: None,
+            response_example: None,
+            status: None,
+            sdk_group: None,
+            operation_id: None,
+            content_type: None,
+            sse_event: None,
+            summary: None,
+            deprecated: false,
+        }];
+
+        merge_route_storage_data(&mut metadata, &storage);
+        // ROUTE_STORAGE values override when they have explicit values
+        assert_eq!(metadata.routes[0].tags, Some(vec!["new-tag".to_string()]));
+        assert_eq!(
+            metadata.routes[0].description,
+            Some("New description".to_string())
+        );
+        assert_eq!(metadata.routes[0].error_status, Some(vec![400, 404]));
+    }
+
+    #[test]
+    fn test_merge_route_storage_partial_fields() {
+        let mut metadata = CollectedMetadata::new();
+        metadata.routes.push(RouteMetadata {
+            method: "get".to_string(),
+            path: "/users".to_string(),
+            function_name: "get_users".to_string(),
+            module_path: "routes".to_string(),
+            file_path: "routes/users.rs".to_string(),
+            tags: Some(vec!["from-collector".to_string()]),
+            description: Some("From doc comment".to_string()),
+            ..Default::default()
+        });
+
+        // StoredRouteInfo with only error_status (tags/description are None)
+        let storage = vec![StoredRouteInfo {
+            fn_name: "get_users".to_string(),
+            method: Some("get".to_string()),
+            custom_path: None,
+            error_status: Some(vec![400]),
+            tags: None,
+            description: None,
+            fn_item_str: String::new(),
+            file_path: None,
+            security: None,
+            limits: None,
+            cache: None,
+            response: None,
+            request_body: None,
+            responses: None,
+            params: None,
+            links: None,
+            request_example: None,
+            response_example: None,
+            status: None,
+            sdk_group: None,
+            operation_id: None,
+            content_type: None,
+            sse_event: None,
+            summary: None,
+            deprecated: false,
+        }];
+
+        merge_route_storage_data(&mut metadata, &storage);
+        // Only error_status should be set; tags and description preserved from collector
+        assert_eq!(
+            metadata.routes[0].tags,
+            Some(vec!["from-collector".to_string()])
+        );
+        assert_eq!(
+            metadata.routes[0].description,
+            Some("From doc comment".to_string())
+        );
+        assert_eq!(metadata.routes[0].error_status, Some(vec![400]));
+    }
+
+    #[test]
+    fn test_compute_config_hash_with_servers() {
+        // Exercises lines 92-96: servers loop in compute_config_hash
+        let processed_no_servers = ProcessedVesperaInput::default();
+
+        let processed_with_servers = ProcessedVesperaInput {
+            servers: Some(vec![
+                vespera_core::openapi::Server {
+                    url: "https://api.example.com".to_string(),
+                    description: None,
+                    variables: None,
+                },
+                vespera_core::openapi::Server {
+                    url: "http://localhost:3000".to_string(),
+                    description: None,
+                    variables: None,
+                },
+            ]),
+            ..Default::default()
+        };
+
+        let hash_no_servers = compute_config_hash(&processed_no_servers);
+        let hash_with_servers = compute_config_hash(&processed_with_servers);
+
+        // Different servers should produce different hashes
+        assert_ne!(
+            hash_no_servers, hash_with_servers,
+            "Servers should affect config hash"
+        );
+    }
+
+    #[test]
+    fn test_compute_config_hash_with_tag_rules() {
+        let processed = |tag_rules: Vec<(String, String)>| ProcessedVesperaInput {
+            tag_rules,
+            ..Default::default()
+        };
+
+        assert_ne!(
+            compute_config_hash(&processed(vec![])),
+            compute_config_hash(&processed(vec![(
+                "/admin/**".to_string(),
+                "admin".to_string()
+            )])),
+            "Tag rules should affect config hash"
+        );
+    }
+
+    #[test]
+    fn test_compute_config_hash_with_global_params() {
+        let processed = |global_params: Vec<GlobalParam>| ProcessedVesperaInput {
+            global_params,
+            ..Default::default()
+        };
+
+        assert_ne!(
+            compute_config_hash(&processed(vec![])),
+            compute_config_hash(&processed(vec![GlobalParam::AcceptLanguage])),
+            "Global params should affect config hash"
+        );
+    }
+
+    #[test]
+    fn test_compute_config_hash_with_merge() {
+        // Exercises lines 97-99: merge loop in compute_config_hash
+        let processed_no_merge = ProcessedVesperaInput::default();
+
+        let processed_with_merge = ProcessedVesperaInput {
+            merge: vec![syn::parse_quote!(app::TestApp)],
+            ..Default::default()
+        };
+
+        let hash_no_merge = compute_config_hash(&processed_no_merge);
+        let hash_with_merge = compute_config_hash(&processed_with_merge);
+
+        assert_ne!(
+            hash_no_merge, hash_with_merge,
+            "Merge paths should affect config hash"
+        );
+    }
+
+    #[test]
+    fn test_ensure_openapi_files_from_cache_none_spec() {
+        // Exercises lines 266-267: early return when spec_pretty is None
+        let result = ensure_openapi_files_from_cache(&["dummy.json".to_string()], None);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_ensure_openapi_files_from_cache_writes_file() {
+        // Exercises lines 269-276: write new file
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let output_path = temp_dir.path().join("api.json");
+        let spec = r#"{"openapi":"3.1.0"}"#;
+
+        let result = ensure_openapi_files_from_cache(
+            &[output_path.to_string_lossy().to_string()],
+            Some(spec),
+        );
+        assert!(result.is_ok());
+        assert_eq!(fs::read_to_string(&output_path).unwrap(), spec);
+    }
+
+    #[test]
+    fn test_ensure_openapi_files_from_cache_skip_unchanged() {
+        // Exercises line 271-272: should_write is false when content matches
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let output_path = temp_dir.path().join("api.json");
+        let spec = r#"{"openapi":"3.1.0"}"#;
+
+        // Write file first with same content
+        fs::write(&output_path, spec).unwrap();
+
+        let result = ensure_openapi_files_from_cache(
+            &[output_path.to_string_lossy().to_string()],
+            Some(spec),
+        );
+        assert!(result.is_ok());
+        // File should still contain same content (no unnecessary write)
+        assert_eq!(fs::read_to_string(&output_path).unwrap(), spec);
+    }
+
+    #[test]
+    fn test_spec_manifest_only_hashes_openapi_structs() {
+        let mut metadata = CollectedMetadata::new();
+        metadata.structs.push(StructMetadata::new(
+            "User".to_string(),
+            "struct User { id: i32 }".to_string(),
+        ));
+        metadata.structs.push(StructMetadata::new_model(
+            "Model".to_string(),
+            "struct Model { id: i32 }".to_string(),
+        ));
+
+        let manifest = SpecManifest::new(&metadata, Some(r#"{"openapi":"3.1.0"}"#));
+        assert_eq!(manifest.schemas.keys().collect::<Vec<_>>(), vec!["User"]);
+        assert_eq!(
+            manifest.schemas["User"],
+            format!("{:016x}", metadata.structs[0].content_hash())
+        );
+        assert_eq!(manifest.spec.as_deref().map(str::len), Some(16));
+        assert!(SpecManifest::new(&metadata, None).spec.is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "unstable-metadata")]
+    fn test_write_api_metadata() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let path = vespera_core::metadata::ApiMetadata::path_in(temp_dir.path());
+        let mut metadata = CollectedMetadata::new();
+        metadata.structs.push(StructMetadata::new(
+            "User".to_string(),
+            "struct User { id: i32 }".to_string(),
+        ));
+
+        write_api_metadata(&path, &metadata);
+        let loaded = vespera_core::metadata::ApiMetadata::load(&path).unwrap();
+        assert_eq!(loaded, metadata.to_api_metadata());
+    }
+
+    #[test]
+    fn test_write_manifest_reports_changes() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let manifest_path = manifest_path(Some(temp_dir.path().as_os_str()));
+        assert_eq!(
+            manifest_path,
+            temp_dir.path().join("vespera").join("manifest.json")
+        );
+        let mut metadata = CollectedMetadata::new();
+        metadata.structs.push(StructMetadata::new(
+            "User".to_string(),
+            "struct User { id: i32 }".to_string(),
+        ));
+        let manifest = SpecManifest::new(&metadata, Some("{}"));
+
+        assert!(write_manifest(&manifest_path, &manifest));
+        assert!(!write_manifest(&manifest_path, &manifest));
+
+        metadata.structs[0].definition = "struct User { id: i64 }".to_string();
+        let changed = SpecManifest::new(&metadata, Some("{}"));
+        assert!(write_manifest(&manifest_path, &changed));
+
+        let written: SpecManifest =
+            serde_json::from_str(&fs::read_to_string(&manifest_path).unwrap()).unwrap();
+        assert_eq!(written, changed);
+    }
+
+    #[test]
+    fn test_ensure_openapi_files_from_cache_creates_parent_dirs() {
+        // Exercises lines 273-274: create parent directories
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let output_path = temp_dir.path().join("nested").join("dir").join("api.json");
+        let spec = r#"{"openapi":"3.1.0"}"#;
+
+        let result = ensure_openapi_files_from_cache(
+            &[output_path.to_string_lossy().to_string()],
+            Some(spec),
+        );
+        assert!(result.is_ok());
+        assert!(output_path.exists());
+        assert_eq!(fs::read_to_string(&output_path).unwrap(), spec);
+    }
+
+    #[test]
+    fn test_ensure_openapi_files_from_cache_write_error() {
+        // Exercises line 276: write failure
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let output_path = temp_dir.path().join("api.json");
+
+        // Create a directory where the file should be -> write will fail
+        fs::create_dir(&output_path).unwrap();
+
+        let result = ensure_openapi_files_from_cache(
+            &[output_path.to_string_lossy().to_string()],
+            Some("spec"),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_ensure_openapi_files_from_cache_multiple_files() {
+        // Exercises the loop with multiple file names (line 269)
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let path1 = temp_dir.path().join("api1.json");
+        let path2 = temp_dir.path().join("api2.json");
+        let spec = r#"{"openapi":"3.1.0"}"#;
+
+        let result = ensure_openapi_files_from_cache(
+            &[
+                path1.to_string_lossy().to_string(),
+                path2.to_string_lossy().to_string(),
+            ],
+            Some(spec),
+        );
+        assert!(result.is_ok());
+        assert_eq!(fs::read_to_string(&path1).unwrap(), spec);
+        assert_eq!(fs::read_to_string(&path2).unwrap(), spec);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_process_vespera_macro_cache_hit() {
+        // Exercises lines 320-324, 327, 329: the cache_hit branch in process_vespera_macro.
+        // First call populates the cache, second call hits it.
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        create_temp_file(
+            &temp_dir,
+            "users.rs",
+            "pub async fn list_users() -> String { \"users\".to_string() }\n",
+        );
+
+        let folder_path = temp_dir.path().to_string_lossy().to_string();
+        let openapi_path = temp_dir.path().join("openapi.json");
+
+        // Set CARGO_MANIFEST_DIR so cache path resolves to temp_dir/target/vespera/
+        let old_manifest = std::env::var("CARGO_MANIFEST_DIR").ok();
+        unsafe { std::env::set_var("CARGO_MANIFEST_DIR", temp_dir.path()) };
+
+        let processed = ProcessedVesperaInput {
+            folder_name: folder_path.clone(),
+            openapi_file_names: vec![openapi_path.to_string_lossy().to_string()],
+            title: Some("Test API".to_string()),
+            version: Some("1.0.0".to_string()),
+            docs_url: Some("/docs".to_string()),
+            ..Default::default()
+        };
+
+        // First call: cache MISS — scans files, generates spec, writes cache
+        let result1 = process_vespera_macro(&processed, &HashMap::new(), &[], "");
+        assert!(
+            result1.is_ok(),
+            "First call (cache miss) should succeed: {:?}",
+            result1.err()
+        );
+        assert!(
+            openapi_path.exists(),
+            "openapi.json should be written on first call"
+        );
+
+        // Second call: cache HIT — exercises lines 320-324, 327, 329
+        let result2 = process_vespera_macro(&processed, &HashMap::new(), &[], "");
+        assert!(
+            result2.is_ok(),
+            "Second call (cache hit) should succeed: {:?}",
+            result2.err()
+        );
+
+        // Restore CARGO_MANIFEST_DIR
+        unsafe {
+            if let Some(val) = old_manifest {
+                std::env::set_var("CARGO_MANIFEST_DIR", val);
+            } else {
+                std::env::remove_var("CARGO_MANIFEST_DIR");
+            }
+        };
+    }
+
+    #[test]
+    fn test_regenerate_spec() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let route_file = create_temp_file(
+            &temp_dir,
+            "src/routes/users.rs",
+            "/// List users\n#[vespera::route(get)]\npub async fn list() -> Json<User> { todo!() }\n",
+        );
+        let input = r#"title = "Dev", version = "2.0.0""#;
+        let schemas = r#"[{"name": "User", "definition": "pub struct User { pub id: i32 }"}]"#;
+
+        let spec: serde_json::Value =
+            serde_json::from_str(&regenerate_spec(temp_dir.path(), input, schemas).unwrap())
+                .unwrap();
+        assert_eq!(spec["info"]["title"], "Dev");
+        assert_eq!(spec["paths"]["/users"]["get"]["summary"], "List users");
+        assert!(spec["components"]["schemas"]["User"].is_object());
+
+        // Edited doc comments show up without rebuilding
+        fs::write(
+            &route_file,
+            "/// Every user\n#[vespera::route(get)]\npub async fn list() -> Json<User> { todo!() }\n",
+        )
+        .unwrap();
+        let spec: serde_json::Value =
+            serde_json::from_str(&regenerate_spec(temp_dir.path(), input, schemas).unwrap())
+                .unwrap();
+        assert_eq!(spec["paths"]["/users"]["get"]["summary"], "Every user");
+    }
+
+    #[test]
+    fn test_regenerate_spec_merges_child_specs() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        create_temp_file(
+            &temp_dir,
+            "src/routes/users.rs",
+            "#[vespera::route(get, tags = [\"users\"])]\npub async fn list() -> String { todo!() }\n",
+        );
+        create_temp_file(
+            &temp_dir,
+            "target/vespera/ChildApp.openapi.json",
+            r#"{"openapi":"3.1.0","info":{"title":"Child","version":"1.0.0"},"paths":{"/child":{"get":{"responses":{}}}}}"#,
+        );
+
+        let spec: serde_json::Value = serde_json::from_str(
+            &regenerate_spec(temp_dir.path(), "merge = [child::ChildApp]", "[]").unwrap(),
+        )
+        .unwrap();
+        assert_eq!(spec["paths"]["/users"]["get"]["tags"][0], "users");
+        assert!(spec["paths"]["/child"]["get"].is_object());
+    }
+
+    #[rstest]
+    #[case("title = ", "[]", "invalid `vespera!` input")]
+    #[case("", "{", "invalid registered schemas")]
+    #[case(r#"dir = "missing""#, "[]", "failed to scan route folder")]
+    #[case("", "[]", "cannot read or parse 1 route file(s)")]
+    fn test_regenerate_spec_errors(
+        #[case] input: &str,
+        #[case] schemas: &str,
+        #[case] expected: &str,
+    ) {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        create_temp_file(&temp_dir, "src/routes/broken.rs", "pub fn broken( {");
+
+        let err = regenerate_spec(temp_dir.path(), input, schemas).unwrap_err();
+        assert!(err.contains(expected), "{err}");
+    }
+}